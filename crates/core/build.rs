@@ -1,6 +1,5 @@
-extern crate napi_build;
-
 fn main() {
+    #[cfg(feature = "napi")]
     napi_build::setup();
 
     if std::env::var("CARGO_CFG_TARGET_OS").unwrap() == "windows" {
@@ -0,0 +1,98 @@
+//! Sintesi on its own repository
+//!
+//! Checks the anchors of `crates/core/README.md` against `sintesi-map.json`
+//! and the current code (TypeScript typings and Rust sources alike), the same
+//! pipeline `drift::sync::test_self_docs_in_sync` runs under `cargo test`:
+//!
+//! ```text
+//! # report, exit 1 if out of sync
+//! cargo run --no-default-features --example self_docs
+//! # record the current signatures
+//! cargo run --no-default-features --example self_docs -- --sync
+//! ```
+//!
+//! Like the fuzz targets it runs outside Node, so it is built without the
+//! Node bindings (`--no-default-features`).
+//!
+//! Point it at another project with `-- --root <dir> --doc <file.md>...`.
+
+use std::process::ExitCode;
+
+#[cfg(not(feature = "napi"))]
+fn main() -> ExitCode {
+    self_docs::run()
+}
+
+#[cfg(feature = "napi")]
+fn main() -> ExitCode {
+    eprintln!("Run without the Node bindings: cargo run --no-default-features --example self_docs");
+    ExitCode::from(2)
+}
+
+#[cfg(not(feature = "napi"))]
+mod self_docs {
+    use sintesi_core::drift::{check_docs, now_millis, sync_map, SintesiMap, MAP_FILE};
+    use sintesi_core::provider::DiskProvider;
+    use std::path::{Path, PathBuf};
+    use std::process::ExitCode;
+
+    pub fn run() -> ExitCode {
+        let mut root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let mut docs = Vec::new();
+        let mut sync = false;
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--sync" => sync = true,
+                "--root" => root = args.next().map(PathBuf::from).unwrap_or(root),
+                "--doc" => docs.extend(args.next().map(PathBuf::from)),
+                other => {
+                    eprintln!("Unknown argument: {}", other);
+                    return ExitCode::from(2);
+                }
+            }
+        }
+        if docs.is_empty() {
+            docs.push(PathBuf::from("crates/core/README.md"));
+        }
+
+        let provider = DiskProvider::new(&root);
+        let map_path = root.join(MAP_FILE);
+        let map = SintesiMap::load(&map_path).unwrap_or_default();
+
+        if sync {
+            let synced = sync_map(&provider, &map, &docs, now_millis());
+            if let Err(error) = synced.save(&map_path) {
+                eprintln!("Cannot save {}: {}", map_path.display(), error);
+                return ExitCode::FAILURE;
+            }
+            println!(
+                "Recorded {} anchor(s) in {}",
+                synced.entries.len(),
+                map_path.display()
+            );
+            return ExitCode::SUCCESS;
+        }
+
+        let report = check_docs(&provider, &map, &docs);
+        for entry in &report.drift.entries {
+            println!("{:?} {} ({})", entry.status, entry.entry_id, entry.code_ref);
+        }
+        for id in &report.unmapped {
+            println!("Unmapped {}", id);
+        }
+        for id in &report.orphaned {
+            println!("Orphaned {}", id);
+        }
+        for error in &report.errors {
+            println!("Error {}", error);
+        }
+        match report.is_clean() {
+            true => ExitCode::SUCCESS,
+            false => {
+                eprintln!("Docs are out of sync; update them and rerun with --sync");
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Parsing `sintesi:start` and `sintesi:end` comments
+//!
+//! Parsed attributes are non-empty and unquoted, and a comment written back
+//! from them parses to the same values.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sintesi_core::content::extractor::{parse_sintesi_end, parse_sintesi_start};
+
+fn valid(value: &str) -> bool {
+    !value.is_empty() && !value.contains(['"', '\''])
+}
+
+fuzz_target!(|html: &str| {
+    if let Some((id, code_ref)) = parse_sintesi_start(html) {
+        assert!(valid(&id) && valid(&code_ref), "{:?}", html);
+        let written = format!(
+            "<!-- sintesi:start id=\"{}\" code_ref=\"{}\" -->",
+            id, code_ref
+        );
+        assert_eq!(parse_sintesi_start(&written), Some((id, code_ref)));
+    }
+    if let Some(id) = parse_sintesi_end(html) {
+        assert!(valid(&id), "{:?}", html);
+        let written = format!("<!-- sintesi:end id=\"{}\" -->", id);
+        assert_eq!(parse_sintesi_end(&written), Some(id));
+    }
+});
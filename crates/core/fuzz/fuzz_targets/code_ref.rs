@@ -0,0 +1,18 @@
+//! Parsing `code_ref` attributes
+//!
+//! A parsed reference has a non-empty file path and symbol name that join
+//! back into the input.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sintesi_core::content::MarkdownExtractor;
+
+fuzz_target!(|code_ref: &str| {
+    let extractor = MarkdownExtractor::new();
+    if let Ok((file_path, symbol_name)) = extractor.parse_code_ref(code_ref) {
+        assert!(!file_path.is_empty() && !symbol_name.is_empty());
+        assert!(!file_path.contains('#') && !symbol_name.contains('#'));
+        assert_eq!(format!("{}#{}", file_path, symbol_name), code_ref);
+    }
+});
@@ -0,0 +1,47 @@
+//! Extracting anchors from markdown
+//!
+//! Whatever the input, extraction returns anchors whose spans are ordered,
+//! in bounds, and on character boundaries, with a start tag that parses to
+//! the anchor's ID, and nesting links that agree with each other.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sintesi_core::content::extract_anchors;
+use sintesi_core::content::extractor::parse_sintesi_start;
+
+fuzz_target!(|content: &str| {
+    let result = extract_anchors("fuzz.md", content);
+    assert_eq!(result.anchor_count, result.anchors.len());
+    for (id, anchor) in &result.anchors {
+        assert_eq!(id, &anchor.id);
+        assert!(anchor.start_line <= anchor.end_line);
+        let spans = [
+            &anchor.start_tag_span,
+            &anchor.content_span,
+            &anchor.end_tag_span,
+        ];
+        for span in spans {
+            assert!(span.start <= span.end && span.end <= content.len());
+            assert!(content.is_char_boundary(span.start) && content.is_char_boundary(span.end));
+        }
+        assert!(anchor.start_tag_span.end <= anchor.content_span.start);
+        assert!(anchor.content_span.end <= anchor.end_tag_span.start);
+
+        let start_tag = &content[anchor.start_tag_span.clone()];
+        let parsed = parse_sintesi_start(start_tag).map(|(id, _)| id);
+        assert_eq!(
+            parsed.as_deref(),
+            Some(anchor.id.as_str()),
+            "{:?}",
+            start_tag
+        );
+
+        if let Some(parent) = &anchor.parent {
+            assert!(result.anchors[parent].children.contains(id));
+        }
+        for child in &anchor.children {
+            assert_eq!(result.anchors[child].parent.as_ref(), Some(id));
+        }
+    }
+});
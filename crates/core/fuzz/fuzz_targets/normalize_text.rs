@@ -0,0 +1,25 @@
+//! Decoding file bytes and canonicalizing anchor content
+//!
+//! Any bytes either decode or fail with `InvalidData`; decoded text
+//! canonicalizes to a fixed point, so re-saving canonical content is not an
+//! edit.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sintesi_core::content::{anchor_content_hash, canonicalize};
+use sintesi_core::text::decode;
+use std::io::ErrorKind;
+
+fuzz_target!(|data: &[u8]| {
+    let text = match decode(data.to_vec()) {
+        Ok(text) => text,
+        Err(e) => {
+            assert_eq!(e.kind(), ErrorKind::InvalidData);
+            return;
+        }
+    };
+    let canonical = canonicalize(&text);
+    assert_eq!(canonicalize(&canonical), canonical);
+    assert_eq!(anchor_content_hash(&canonical), anchor_content_hash(&text));
+});
@@ -0,0 +1,21 @@
+//! Plan and apply module
+//!
+//! This module handles:
+//! - Compiling documentation updates into an ordered, reviewable plan
+//! - Applying an approved plan (file rewrites, map updates, commit)
+//! - Transactional writes with rollback on failure
+//! - Splitting and merging anchors together with their map entries
+//!
+//! ## Module Structure
+//!
+//! - `plan`: Plan model, plan creation, and plan application
+//! - `refactor`: Anchor split and merge operations
+//! - `transaction`: Staged file writes committed or rolled back together
+
+pub mod plan;
+pub mod refactor;
+pub mod transaction;
+
+pub use plan::{apply_plan, create_plan, ApplyResult, Plan, PlanOptions, PlanStep};
+pub use refactor::{merge_anchors, split_anchor, RefactorResult};
+pub use transaction::Transaction;
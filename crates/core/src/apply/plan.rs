@@ -0,0 +1,552 @@
+//! Fix plans
+//!
+//! A plan is everything a documentation update would do, compiled up front
+//! into an ordered, serializable list of steps: which anchors are
+//! regenerated, which files are rewritten (with their new content), which map
+//! entries change, and what is committed. The CLI presents the plan for
+//! approval and then hands the same plan to [`apply_plan`].
+//!
+//! Plans also summarize what changed in each regenerated anchor (sections
+//! added or rewritten, parameters whose descriptions changed); the summary
+//! is appended to the commit message and can be reused for a PR body.
+//!
+//! Applying checks every precondition before touching the filesystem: each
+//! rewritten file must still have the content the plan was built from, so a
+//! plan approved against a stale tree is rejected as a whole. The file
+//! rewrites and the map are then written in a single [`Transaction`], so a
+//! failed write leaves the project as it was.
+
+use super::transaction::Transaction;
+use crate::content::canonical::anchor_content_hash;
+use crate::content::extractor::extract_anchors;
+use crate::content::injector::inject_anchor_content;
+use crate::content::summary::{format_changes, summarize_anchor_change};
+use crate::drift::{now_millis, DriftResult, DriftStatus, SintesiMap, SintesiMapEntry, MAP_FILE};
+use crate::error::Error;
+use crate::git::GitService;
+use crate::sandbox::WriteSandbox;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A single planned action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum PlanStep {
+    /// Documentation of a drifted symbol is regenerated
+    #[serde(rename_all = "camelCase")]
+    RegenerateAnchor {
+        doc_file: String,
+        anchor_id: String,
+        code_ref: String,
+    },
+    /// A file (relative to the project root) is replaced with new content
+    #[serde(rename_all = "camelCase")]
+    RewriteFile {
+        path: String,
+        /// SHA256 of the content the plan was built from (`None` for new files)
+        original_hash: Option<String>,
+        content: String,
+    },
+    /// A map entry is inserted or replaced
+    #[serde(rename_all = "camelCase")]
+    UpdateMapEntry { entry: SintesiMapEntry },
+    /// A map entry is removed
+    #[serde(rename_all = "camelCase")]
+    RemoveMapEntry { id: String },
+    /// The rewritten files and the map are committed
+    #[serde(rename_all = "camelCase")]
+    Commit { message: String, paths: Vec<String> },
+}
+
+impl fmt::Display for PlanStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanStep::RegenerateAnchor {
+                doc_file,
+                anchor_id,
+                code_ref,
+            } => write!(
+                f,
+                "Regenerate anchor \"{}\" in {} ({})",
+                anchor_id, doc_file, code_ref
+            ),
+            PlanStep::RewriteFile { path, .. } => write!(f, "Rewrite {}", path),
+            PlanStep::UpdateMapEntry { entry } => {
+                write!(f, "Update map entry \"{}\" ({})", entry.id, entry.code_ref)
+            }
+            PlanStep::RemoveMapEntry { id } => write!(f, "Remove map entry \"{}\"", id),
+            PlanStep::Commit { message, paths } => write!(
+                f,
+                "Commit {} file(s): {}",
+                paths.len(),
+                message.lines().next().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// An ordered, serializable set of planned actions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Plan {
+    /// When the plan was built (milliseconds since Unix epoch)
+    pub created_at: i64,
+    /// Steps in execution order
+    pub steps: Vec<PlanStep>,
+    /// One line per rewritten anchor describing what changed in it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub summary: Vec<String>,
+}
+
+impl Plan {
+    /// Check whether the plan would change anything
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Serialize the plan as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Parse a plan from JSON
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| Error::from_reason(format!("Invalid plan: {}", e)))
+    }
+}
+
+/// Options for building a plan
+#[derive(Debug, Clone, Default)]
+pub struct PlanOptions {
+    /// Commit the changes with this message (no commit if `None`)
+    pub commit_message: Option<String>,
+}
+
+impl PlanOptions {
+    /// Create default options (no commit)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Commit the applied changes with the given message
+    pub fn commit_message(mut self, message: impl Into<String>) -> Self {
+        self.commit_message = Some(message.into());
+        self
+    }
+}
+
+/// Outcome of applying a plan
+#[derive(Debug, Clone, Default)]
+pub struct ApplyResult {
+    /// Files written (relative to the project root), including the map
+    pub files_written: Vec<String>,
+    /// Commit created by a `Commit` step
+    pub commit: Option<String>,
+}
+
+/// Build a plan that brings drifted documentation back in sync
+///
+/// # Arguments
+/// * `root` - Project root containing the map
+/// * `map` - The Sintesi map
+/// * `drift` - Drift result for the map
+/// * `generated` - Regenerated anchor content by anchor ID; drifted anchors
+///   without content are listed for regeneration but not rewritten
+/// * `options` - Plan options
+pub fn create_plan(
+    root: impl AsRef<Path>,
+    map: &SintesiMap,
+    drift: &DriftResult,
+    generated: &BTreeMap<String, String>,
+    options: &PlanOptions,
+) -> Result<Plan, Error> {
+    let root = root.as_ref();
+    let now = now_millis();
+    let mut regenerate = Vec::new();
+    let mut map_steps = Vec::new();
+    // Rewritten documents in order of first appearance: (path, original, content)
+    let mut documents: Vec<(String, String, String)> = Vec::new();
+    let mut changes = Vec::new();
+
+    for entry in drift.drifted() {
+        if entry.status == DriftStatus::Removed {
+            map_steps.push(PlanStep::RemoveMapEntry {
+                id: entry.entry_id.clone(),
+            });
+            continue;
+        }
+
+        regenerate.push(PlanStep::RegenerateAnchor {
+            doc_file: entry.doc_file.clone(),
+            anchor_id: entry.entry_id.clone(),
+            code_ref: entry.code_ref.clone(),
+        });
+        let (Some(content), Some(current_hash)) =
+            (generated.get(&entry.entry_id), &entry.current_hash)
+        else {
+            continue;
+        };
+
+        let index = match documents
+            .iter()
+            .position(|(path, ..)| *path == entry.doc_file)
+        {
+            Some(index) => index,
+            None => {
+                let full_path = root.join(&entry.doc_file);
+                let original = fs::read_to_string(&full_path).map_err(|e| {
+                    Error::from_reason(format!("Failed to read {}: {}", full_path.display(), e))
+                })?;
+                documents.push((entry.doc_file.clone(), original.clone(), original));
+                documents.len() - 1
+            }
+        };
+        let document = &mut documents[index].2;
+        let old_content = extract_anchors(&entry.doc_file, document)
+            .anchors
+            .get(&entry.entry_id)
+            .map(|anchor| anchor.content.clone())
+            .unwrap_or_default();
+        changes.push(summarize_anchor_change(
+            &entry.doc_file,
+            &entry.entry_id,
+            &old_content,
+            content,
+        ));
+        *document = inject_anchor_content(document, &entry.entry_id, content)
+            .map_err(|e| Error::from_reason(format!("{}: {}", entry.doc_file, e)))?;
+        let content_hash = extract_anchors(&entry.doc_file, document)
+            .anchors
+            .get(&entry.entry_id)
+            .map(|anchor| anchor_content_hash(&anchor.content));
+
+        if let Some(existing) = map.get(&entry.entry_id) {
+            map_steps.push(PlanStep::UpdateMapEntry {
+                entry: SintesiMapEntry {
+                    code_signature_hash: current_hash.clone(),
+                    last_updated: now,
+                    deprecated: entry
+                        .deprecation
+                        .map_or(existing.deprecated, |change| change.is_deprecated()),
+                    content_hash,
+                    code_signature: entry
+                        .current_signature
+                        .clone()
+                        .or_else(|| existing.code_signature.clone()),
+                    ..existing.clone()
+                },
+            });
+        }
+    }
+
+    let mut steps = regenerate;
+    let mut paths = Vec::new();
+    for (path, original, content) in documents {
+        if content != original {
+            paths.push(path.clone());
+            steps.push(PlanStep::RewriteFile {
+                path,
+                original_hash: Some(content_hash(&original)),
+                content,
+            });
+        }
+    }
+    if !map_steps.is_empty() {
+        paths.push(MAP_FILE.to_string());
+    }
+    steps.extend(map_steps);
+
+    changes.retain(|change| !change.is_empty());
+    if let Some(message) = &options.commit_message {
+        if !paths.is_empty() {
+            let message = if changes.is_empty() {
+                message.clone()
+            } else {
+                format!("{}\n\n{}", message, format_changes(&changes))
+            };
+            steps.push(PlanStep::Commit { message, paths });
+        }
+    }
+
+    Ok(Plan {
+        created_at: now,
+        steps,
+        summary: changes.iter().map(|change| change.to_string()).collect(),
+    })
+}
+
+/// Apply a plan
+///
+/// All preconditions are checked first; if any rewritten file changed since
+/// the plan was built (or the map cannot be loaded), nothing is written.
+/// Files and the map are written transactionally: either all of them are
+/// updated or, on the first failed write, all are restored. Files outside the
+/// project's write sandbox (see `sandbox`) fail the whole plan.
+pub fn apply_plan(root: impl AsRef<Path>, plan: &Plan) -> Result<ApplyResult, Error> {
+    let root = root.as_ref();
+    let updated_map = verify_plan(root, plan)?;
+    let mut transaction = Transaction::new().confine(WriteSandbox::load(root)?);
+    let mut result = ApplyResult::default();
+
+    for step in &plan.steps {
+        if let PlanStep::RewriteFile { path, content, .. } = step {
+            transaction.stage(root.join(path), content.as_str());
+            result.files_written.push(path.clone());
+        }
+    }
+    if let Some(map) = updated_map {
+        transaction.stage(root.join(MAP_FILE), map.to_json()?);
+        result.files_written.push(MAP_FILE.to_string());
+    }
+    transaction.commit()?;
+
+    for step in &plan.steps {
+        if let PlanStep::Commit { message, paths } = step {
+            let paths: Vec<PathBuf> = paths.iter().map(|p| root.join(p)).collect();
+            result.commit = Some(GitService::open(root)?.commit_paths(&paths, message)?);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Check every precondition of a plan and compute the updated map, if any
+fn verify_plan(root: &Path, plan: &Plan) -> Result<Option<SintesiMap>, Error> {
+    let mut map: Option<SintesiMap> = None;
+
+    for step in &plan.steps {
+        match step {
+            PlanStep::RewriteFile {
+                path,
+                original_hash,
+                ..
+            } => {
+                let current = fs::read_to_string(root.join(path))
+                    .ok()
+                    .map(|c| content_hash(&c));
+                if current != *original_hash {
+                    return Err(Error::from_reason(format!(
+                        "{} changed since the plan was created; create a new plan",
+                        path
+                    )));
+                }
+            }
+            PlanStep::UpdateMapEntry { entry } => {
+                load_map(root, &mut map)?.upsert(entry.clone());
+            }
+            PlanStep::RemoveMapEntry { id } => {
+                load_map(root, &mut map)?.remove(id);
+            }
+            PlanStep::RegenerateAnchor { .. } | PlanStep::Commit { .. } => {}
+        }
+    }
+
+    Ok(map)
+}
+
+fn load_map<'a>(root: &Path, map: &'a mut Option<SintesiMap>) -> Result<&'a mut SintesiMap, Error> {
+    if map.is_none() {
+        *map = Some(SintesiMap::load(root.join(MAP_FILE))?);
+    }
+    Ok(map.as_mut().expect("map was just loaded"))
+}
+
+/// SHA256 of file content, as lowercase hex
+fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::DriftEntry;
+
+    const DOC: &str = "# Auth\n<!-- sintesi:start id=\"a\" code_ref=\"src/auth.ts#login\" -->\nOld.\n<!-- sintesi:end id=\"a\" -->\n";
+
+    fn map_entry(id: &str, code_ref: &str) -> SintesiMapEntry {
+        SintesiMapEntry {
+            id: id.to_string(),
+            code_ref: code_ref.to_string(),
+            doc_file: "docs/auth.md".to_string(),
+            code_signature_hash: "old".to_string(),
+            last_updated: 0,
+            symbol_id: None,
+            symbol_key: None,
+            deprecated: false,
+            content_hash: None,
+            code_signature: None,
+        }
+    }
+
+    fn drift_entry(id: &str, code_ref: &str, status: DriftStatus) -> DriftEntry {
+        DriftEntry {
+            entry_id: id.to_string(),
+            code_ref: code_ref.to_string(),
+            doc_file: "docs/auth.md".to_string(),
+            status,
+            expected_hash: "old".to_string(),
+            current_hash: (status != DriftStatus::Removed).then(|| "new".to_string()),
+            ignore_reason: None,
+            deprecation: None,
+            current_signature: None,
+            severity: None,
+            test_count: None,
+        }
+    }
+
+    /// A project with a drifted anchor "a" and a removed symbol "b"
+    fn project() -> (tempfile::TempDir, SintesiMap, DriftResult) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::write(dir.path().join("docs/auth.md"), DOC).unwrap();
+        let map = SintesiMap {
+            entries: vec![
+                map_entry("a", "src/auth.ts#login"),
+                map_entry("b", "src/auth.ts#logout"),
+            ],
+            ..SintesiMap::new()
+        };
+        map.save(dir.path().join(MAP_FILE)).unwrap();
+        let drift = DriftResult {
+            entries: vec![
+                drift_entry("a", "src/auth.ts#login", DriftStatus::Drifted),
+                drift_entry("b", "src/auth.ts#logout", DriftStatus::Removed),
+            ],
+            ..Default::default()
+        };
+        (dir, map, drift)
+    }
+
+    #[test]
+    fn test_create_plan() {
+        let (dir, map, drift) = project();
+        let generated = BTreeMap::from([("a".to_string(), "New.".to_string())]);
+
+        let plan = create_plan(
+            dir.path(),
+            &map,
+            &drift,
+            &generated,
+            &PlanOptions::new().commit_message("docs: sync"),
+        )
+        .unwrap();
+
+        let summary: Vec<String> = plan.steps.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            summary,
+            [
+                "Regenerate anchor \"a\" in docs/auth.md (src/auth.ts#login)",
+                "Rewrite docs/auth.md",
+                "Update map entry \"a\" (src/auth.ts#login)",
+                "Remove map entry \"b\"",
+                "Commit 2 file(s): docs: sync",
+            ]
+        );
+        assert_eq!(plan.summary, ["docs/auth.md#a: changed introduction"]);
+        assert!(matches!(
+            plan.steps.last(),
+            Some(PlanStep::Commit { message, .. })
+                if message == "docs: sync\n\n- docs/auth.md#a: changed introduction\n"
+        ));
+        assert_eq!(Plan::from_json(&plan.to_json()).unwrap(), plan);
+        assert!(plan.to_json().contains("\"action\": \"rewriteFile\""));
+    }
+
+    #[test]
+    fn test_apply_plan() {
+        let (dir, map, drift) = project();
+        let generated = BTreeMap::from([("a".to_string(), "New.".to_string())]);
+        let plan = create_plan(dir.path(), &map, &drift, &generated, &PlanOptions::new()).unwrap();
+
+        let result = apply_plan(dir.path(), &plan).unwrap();
+
+        assert_eq!(result.files_written, ["docs/auth.md", MAP_FILE]);
+        let doc = fs::read_to_string(dir.path().join("docs/auth.md")).unwrap();
+        assert!(doc.contains("-->\nNew.\n<!--"));
+        let map = SintesiMap::load(dir.path().join(MAP_FILE)).unwrap();
+        assert_eq!(map.entries.len(), 1);
+        assert_eq!(map.entries[0].code_signature_hash, "new");
+        assert_eq!(
+            map.entries[0].content_hash,
+            Some(crate::content::anchor_content_hash("New."))
+        );
+    }
+
+    #[test]
+    fn test_apply_commits() {
+        let (dir, map, drift) = project();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let plan = create_plan(
+            dir.path(),
+            &map,
+            &drift,
+            &BTreeMap::new(),
+            &PlanOptions::new().commit_message("docs: drop removed symbols"),
+        )
+        .unwrap();
+
+        let result = apply_plan(dir.path(), &plan).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.commit, Some(head.id().to_string()));
+        assert_eq!(head.message(), Some("docs: drop removed symbols"));
+        assert!(head.tree().unwrap().get_name(MAP_FILE).is_some());
+    }
+
+    #[test]
+    fn test_apply_rejects_stale_plan() {
+        let (dir, map, drift) = project();
+        let generated = BTreeMap::from([("a".to_string(), "New.".to_string())]);
+        let plan = create_plan(dir.path(), &map, &drift, &generated, &PlanOptions::new()).unwrap();
+        fs::write(
+            dir.path().join("docs/auth.md"),
+            format!("{}\nEdited\n", DOC),
+        )
+        .unwrap();
+
+        let err = apply_plan(dir.path(), &plan).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("docs/auth.md changed since the plan was created"));
+        let map = SintesiMap::load(dir.path().join(MAP_FILE)).unwrap();
+        assert_eq!(map.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_refuses_paths_outside_the_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("project");
+        fs::create_dir_all(root.join("docs")).unwrap();
+        let plan = Plan {
+            created_at: 0,
+            steps: vec![
+                PlanStep::RewriteFile {
+                    path: "docs/auth.md".to_string(),
+                    original_hash: None,
+                    content: "New.".to_string(),
+                },
+                PlanStep::RewriteFile {
+                    path: "../outside.md".to_string(),
+                    original_hash: None,
+                    content: "Pwned.".to_string(),
+                },
+            ],
+            summary: Vec::new(),
+        };
+
+        let err = apply_plan(&root, &plan).unwrap_err();
+
+        assert!(matches!(err, Error::PathNotAllowed { .. }));
+        assert!(!root.join("docs/auth.md").exists());
+        assert!(!dir.path().join("outside.md").exists());
+    }
+}
@@ -0,0 +1,533 @@
+//! Anchor refactoring
+//!
+//! Operations that restructure anchors without regenerating them:
+//!
+//! - [`split_anchor`] turns one anchor documenting several symbols into one
+//!   anchor per symbol. The content is divided heuristically: it is cut into
+//!   sections at headings (or into paragraphs when there are none), each
+//!   section goes to the symbol it mentions first, and sections mentioning
+//!   none stay with the section before them.
+//! - [`merge_anchors`] combines adjacent anchors into the first one.
+//!
+//! Both rewrite the markdown and the map in one [`Transaction`], so the
+//! anchors and their map entries never disagree.
+
+use super::transaction::Transaction;
+use crate::content::canonical::anchor_content_hash;
+use crate::content::extractor::extract_anchors;
+use crate::content::types::SintesiAnchor;
+use crate::drift::{current_signatures, now_millis, SintesiMap, SintesiMapEntry, MAP_FILE};
+use crate::error::Error;
+use crate::provider::DiskProvider;
+use crate::sandbox::WriteSandbox;
+use std::fs;
+use std::path::Path;
+
+/// Outcome of a refactoring
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefactorResult {
+    /// IDs of the resulting anchors, in document order
+    pub anchor_ids: Vec<String>,
+    /// Files written (relative to the project root), including the map
+    pub files_written: Vec<String>,
+}
+
+/// Split an anchor into one anchor per code reference
+///
+/// New anchors are named `<id>-<symbol>` and replace the original anchor and
+/// its map entry. The entry for the original code reference keeps its
+/// recorded hash; the other symbols are recorded with their current
+/// signatures, as the content carried over already documents them.
+///
+/// # Arguments
+/// * `root` - Project root containing the map
+/// * `doc_file` - Markdown file containing the anchor (relative to `root`)
+/// * `anchor_id` - Anchor to split
+/// * `code_refs` - At least two code references, one per new anchor
+pub fn split_anchor(
+    root: impl AsRef<Path>,
+    doc_file: &str,
+    anchor_id: &str,
+    code_refs: &[String],
+) -> Result<RefactorResult, Error> {
+    let root = root.as_ref();
+    if code_refs.len() < 2 {
+        return Err(Error::from_reason(
+            "Splitting an anchor needs at least two code references",
+        ));
+    }
+    let names = code_refs
+        .iter()
+        .map(|code_ref| {
+            code_ref
+                .split_once('#')
+                .map(|(_, name)| name)
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| Error::from_reason(format!("Invalid code_ref: {}", code_ref)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let document = read_document(root, doc_file)?;
+    let extraction = extract_anchors(doc_file, &document);
+    let anchor = find_anchor(&extraction.anchors, doc_file, anchor_id)?;
+    if !anchor.children.is_empty() {
+        return Err(Error::from_reason(format!(
+            "Anchor \"{}\" contains nested anchors and cannot be split",
+            anchor_id
+        )));
+    }
+    let mut map = SintesiMap::load(root.join(MAP_FILE))?;
+    let original = map
+        .get(anchor_id)
+        .cloned()
+        .ok_or_else(|| Error::from_reason(format!("No map entry for anchor \"{}\"", anchor_id)))?;
+
+    let ids: Vec<String> = names
+        .iter()
+        .map(|name| format!("{}-{}", anchor_id, slug(name)))
+        .collect();
+    for (index, id) in ids.iter().enumerate() {
+        if ids[..index].contains(id) || map.get(id).is_some() || extraction.anchors.contains_key(id)
+        {
+            return Err(Error::from_reason(format!(
+                "Anchor \"{}\" already exists",
+                id
+            )));
+        }
+    }
+
+    // Resolve the current signatures of the symbols the anchor gains
+    let lookup = SintesiMap {
+        entries: code_refs
+            .iter()
+            .map(|code_ref| SintesiMapEntry {
+                code_ref: code_ref.clone(),
+                ..original.clone()
+            })
+            .collect(),
+        ..SintesiMap::new()
+    };
+    let current = current_signatures(&DiskProvider::new(root), &lookup);
+    let now = now_millis();
+    let mut entries = Vec::new();
+    for (id, code_ref) in ids.iter().zip(code_refs) {
+        let entry = if *code_ref == original.code_ref {
+            SintesiMapEntry {
+                id: id.clone(),
+                ..original.clone()
+            }
+        } else {
+            let hash = current
+                .hashes
+                .get(code_ref)
+                .ok_or_else(|| Error::from_reason(format!("Symbol not found: {}", code_ref)))?;
+            SintesiMapEntry {
+                id: id.clone(),
+                code_ref: code_ref.clone(),
+                doc_file: original.doc_file.clone(),
+                code_signature_hash: hash.clone(),
+                last_updated: now,
+                symbol_id: current.symbol_ids.get(code_ref).cloned(),
+                symbol_key: current.symbol_keys.get(code_ref).cloned(),
+                deprecated: current.deprecated.contains(code_ref),
+                content_hash: None,
+                code_signature: current.signatures.get(code_ref).cloned(),
+            }
+        };
+        entries.push(entry);
+    }
+
+    let newline = newline(&document);
+    let contents = split_content(&anchor.content, &names);
+    let mut replacement = String::new();
+    for (index, ((id, code_ref), content)) in ids.iter().zip(code_refs).zip(&contents).enumerate() {
+        if index > 0 {
+            replacement.push_str(newline);
+        }
+        replacement.push_str(&format!(
+            "<!-- sintesi:start id=\"{}\" code_ref=\"{}\" -->{}",
+            id, code_ref, newline
+        ));
+        push_body(&mut replacement, content, newline);
+        replacement.push_str(&format!("<!-- sintesi:end id=\"{}\" -->{}", id, newline));
+    }
+    let rewritten = replace_lines(&document, anchor.start_line, anchor.end_line, &replacement);
+
+    let position = map
+        .entries
+        .iter()
+        .position(|e| e.id == anchor_id)
+        .expect("entry was found above");
+    map.entries.splice(position..=position, entries);
+
+    let files_written = write(root, doc_file, rewritten, &mut map, &ids)?;
+    Ok(RefactorResult {
+        anchor_ids: ids,
+        files_written,
+    })
+}
+
+/// Merge adjacent anchors into the first one
+///
+/// The anchors must follow each other in the document with only blank lines
+/// between them. The first anchor keeps its ID, code reference, and map
+/// entry and receives the content of all of them; the map entries of the
+/// others are removed, so their symbols are no longer tracked.
+///
+/// # Arguments
+/// * `root` - Project root containing the map
+/// * `doc_file` - Markdown file containing the anchors (relative to `root`)
+/// * `anchor_ids` - At least two anchors, in document order
+pub fn merge_anchors(
+    root: impl AsRef<Path>,
+    doc_file: &str,
+    anchor_ids: &[String],
+) -> Result<RefactorResult, Error> {
+    let root = root.as_ref();
+    if anchor_ids.len() < 2 {
+        return Err(Error::from_reason("Merging needs at least two anchors"));
+    }
+
+    let document = read_document(root, doc_file)?;
+    let extraction = extract_anchors(doc_file, &document);
+    let anchors = anchor_ids
+        .iter()
+        .map(|id| find_anchor(&extraction.anchors, doc_file, id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let lines: Vec<&str> = document.lines().collect();
+    for pair in anchors.windows(2) {
+        let (previous, next) = (pair[0], pair[1]);
+        let adjacent = next.start_line > previous.end_line
+            && lines[previous.end_line + 1..next.start_line]
+                .iter()
+                .all(|line| line.trim().is_empty());
+        if !adjacent {
+            return Err(Error::from_reason(format!(
+                "Anchors \"{}\" and \"{}\" are not adjacent",
+                previous.id, next.id
+            )));
+        }
+    }
+
+    let first = anchors[0];
+    let last = anchors[anchors.len() - 1];
+    let newline = newline(&document);
+    let body: Vec<&str> = anchors
+        .iter()
+        .map(|anchor| anchor.content.trim())
+        .filter(|content| !content.is_empty())
+        .collect();
+    let mut replacement = format!("{}{}", lines[first.start_line], newline);
+    push_body(&mut replacement, &body.join("\n\n"), newline);
+    replacement.push_str(lines[first.end_line]);
+    replacement.push_str(newline);
+    let rewritten = replace_lines(&document, first.start_line, last.end_line, &replacement);
+
+    let mut map = SintesiMap::load(root.join(MAP_FILE))?;
+    for id in &anchor_ids[1..] {
+        map.remove(id);
+    }
+
+    let ids = vec![first.id.clone()];
+    let files_written = write(root, doc_file, rewritten, &mut map, &ids)?;
+    Ok(RefactorResult {
+        anchor_ids: ids,
+        files_written,
+    })
+}
+
+/// Divide anchor content among symbols
+///
+/// Returns one (possibly empty) content per name, in the order of `names`.
+pub fn split_content(content: &str, names: &[&str]) -> Vec<String> {
+    let mut parts: Vec<Vec<String>> = vec![Vec::new(); names.len()];
+    let mut owner = None;
+    let mut unowned = Vec::new();
+
+    for section in sections(content) {
+        let mentioned = names
+            .iter()
+            .enumerate()
+            .filter_map(|(index, name)| mention(&section, name).map(|at| (at, index)))
+            .min()
+            .map(|(_, index)| index);
+        if let Some(index) = mentioned {
+            owner = Some(index);
+            // Leading sections mentioning no symbol introduce the first one
+            parts[index].append(&mut unowned);
+        }
+        match owner {
+            Some(index) => parts[index].push(section),
+            None => unowned.push(section),
+        }
+    }
+    parts[0].append(&mut unowned);
+
+    parts.into_iter().map(|part| part.join("\n\n")).collect()
+}
+
+/// Cut content at headings, or at blank lines if it has no headings
+fn sections(content: &str) -> Vec<String> {
+    let is_heading = |line: &str| {
+        let text = line.trim_start_matches('#');
+        text.len() < line.len() && text.starts_with(' ')
+    };
+    let mut in_fence = false;
+    let mut fenced = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            fenced.push((line, true));
+        } else {
+            fenced.push((line, in_fence));
+        }
+    }
+    let by_heading = fenced
+        .iter()
+        .any(|(line, in_fence)| !in_fence && is_heading(line));
+
+    let mut sections: Vec<Vec<&str>> = vec![Vec::new()];
+    for (line, in_fence) in fenced {
+        let cut = !in_fence
+            && if by_heading {
+                is_heading(line)
+            } else {
+                line.trim().is_empty()
+            };
+        if cut {
+            sections.push(Vec::new());
+        }
+        // Blank lines between paragraphs are dropped, headings are kept
+        if !cut || by_heading {
+            sections
+                .last_mut()
+                .expect("sections is never empty")
+                .push(line);
+        }
+    }
+    sections
+        .into_iter()
+        .map(|lines| lines.join("\n").trim().to_string())
+        .filter(|section| !section.is_empty())
+        .collect()
+}
+
+/// Byte offset of the first whole-word occurrence of `name`
+fn mention(text: &str, name: &str) -> Option<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    text.match_indices(name).map(|(at, _)| at).find(|&at| {
+        let before = text[..at].chars().next_back();
+        let after = text[at + name.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+/// Anchor ID suffix for a symbol name (`Auth.login` => `auth-login`)
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+fn find_anchor<'a>(
+    anchors: &'a crate::content::AnchorMap,
+    doc_file: &str,
+    id: &str,
+) -> Result<&'a SintesiAnchor, Error> {
+    let anchor = anchors.get(id).ok_or_else(|| {
+        Error::from_reason(format!("Anchor id=\"{}\" not found in {}", id, doc_file))
+    })?;
+    if anchor.end_line <= anchor.start_line {
+        return Err(Error::from_reason(format!(
+            "Anchor id=\"{}\" must have its start and end tags on separate lines",
+            id
+        )));
+    }
+    Ok(anchor)
+}
+
+fn read_document(root: &Path, doc_file: &str) -> Result<String, Error> {
+    let path = root.join(doc_file);
+    fs::read_to_string(&path)
+        .map_err(|e| Error::from_reason(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+fn newline(document: &str) -> &'static str {
+    if document.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+fn push_body(out: &mut String, body: &str, newline: &str) {
+    for line in body.trim().lines() {
+        out.push_str(line);
+        out.push_str(newline);
+    }
+}
+
+/// Replace lines `start..=end` of a document
+fn replace_lines(document: &str, start: usize, end: usize, replacement: &str) -> String {
+    let lines: Vec<&str> = document.split_inclusive('\n').collect();
+    let mut result: String = lines[..start].concat();
+    result.push_str(replacement);
+    result.push_str(&lines[end + 1..].concat());
+    result
+}
+
+/// Write the document and the map in one transaction
+///
+/// The content hashes of the rewritten anchors are recorded first.
+fn write(
+    root: &Path,
+    doc_file: &str,
+    document: String,
+    map: &mut SintesiMap,
+    anchor_ids: &[String],
+) -> Result<Vec<String>, Error> {
+    let anchors = extract_anchors(doc_file, &document).anchors;
+    for entry in map.entries.iter_mut() {
+        if anchor_ids.contains(&entry.id) {
+            entry.content_hash = anchors
+                .get(&entry.id)
+                .map(|anchor| anchor_content_hash(&anchor.content));
+        }
+    }
+    let mut transaction = Transaction::new().confine(WriteSandbox::load(root)?);
+    transaction.stage(root.join(doc_file), document);
+    transaction.stage(root.join(MAP_FILE), map.to_json()?);
+    transaction.commit()?;
+    Ok(vec![doc_file.to_string(), MAP_FILE.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Auth\n\n<!-- sintesi:start id=\"auth\" code_ref=\"src/auth.ts#login\" -->\nSession helpers.\n\n## login\nSigns in.\n\n## logout\nEnds the session; see `login`.\n<!-- sintesi:end id=\"auth\" -->\n\nFooter\n";
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("docs/auth.md"), DOC).unwrap();
+        fs::write(
+            dir.path().join("src/auth.ts"),
+            "export function login() {}\nexport function logout() {}\n",
+        )
+        .unwrap();
+        let mut map = SintesiMap::new();
+        map.upsert(SintesiMapEntry {
+            id: "auth".to_string(),
+            code_ref: "src/auth.ts#login".to_string(),
+            doc_file: "docs/auth.md".to_string(),
+            code_signature_hash: "recorded".to_string(),
+            last_updated: 0,
+            symbol_id: None,
+            symbol_key: None,
+            deprecated: false,
+            content_hash: None,
+            code_signature: None,
+        });
+        map.save(dir.path().join(MAP_FILE)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_split_content() {
+        let content =
+            "Session helpers.\n\n## login\nSigns in.\n\n## logout\nEnds the session; see `login`.";
+        assert_eq!(
+            split_content(content, &["login", "logout"]),
+            [
+                "Session helpers.\n\n## login\nSigns in.",
+                "## logout\nEnds the session; see `login`."
+            ]
+        );
+        assert_eq!(
+            split_content("Use `b`.\n\nThen `a`.\n\nMore.", &["a", "b", "c"]),
+            ["Then `a`.\n\nMore.", "Use `b`.", ""]
+        );
+    }
+
+    #[test]
+    fn test_split_then_merge() {
+        let dir = project();
+        let code_refs = [
+            "src/auth.ts#login".to_string(),
+            "src/auth.ts#logout".to_string(),
+        ];
+
+        let result = split_anchor(dir.path(), "docs/auth.md", "auth", &code_refs).unwrap();
+
+        assert_eq!(result.anchor_ids, ["auth-login", "auth-logout"]);
+        let doc = fs::read_to_string(dir.path().join("docs/auth.md")).unwrap();
+        let anchors = extract_anchors("docs/auth.md", &doc).anchors;
+        assert_eq!(anchors.len(), 2);
+        assert_eq!(
+            anchors["auth-logout"].content.trim(),
+            "## logout\nEnds the session; see `login`."
+        );
+        assert!(doc.ends_with("<!-- sintesi:end id=\"auth-logout\" -->\n\nFooter\n"));
+        let map = SintesiMap::load(dir.path().join(MAP_FILE)).unwrap();
+        assert_eq!(
+            map.get("auth-login").unwrap().code_signature_hash,
+            "recorded"
+        );
+        assert_eq!(
+            map.get("auth-logout").unwrap().code_ref,
+            "src/auth.ts#logout"
+        );
+        assert!(map.get("auth").is_none());
+
+        let ids = result.anchor_ids;
+        let result = merge_anchors(dir.path(), "docs/auth.md", &ids).unwrap();
+
+        assert_eq!(result.anchor_ids, ["auth-login"]);
+        let doc = fs::read_to_string(dir.path().join("docs/auth.md")).unwrap();
+        let anchors = extract_anchors("docs/auth.md", &doc).anchors;
+        assert_eq!(anchors.len(), 1);
+        assert!(anchors["auth-login"].content.contains("## logout"));
+        let map = SintesiMap::load(dir.path().join(MAP_FILE)).unwrap();
+        assert_eq!(map.entries.len(), 1);
+        assert_eq!(
+            map.entries[0].content_hash,
+            Some(anchor_content_hash(&anchors["auth-login"].content))
+        );
+    }
+
+    #[test]
+    fn test_refactor_errors_leave_files_unchanged() {
+        let dir = project();
+        let missing = [
+            "src/auth.ts#login".to_string(),
+            "src/auth.ts#gone".to_string(),
+        ];
+        let err = split_anchor(dir.path(), "docs/auth.md", "auth", &missing).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Symbol not found: src/auth.ts#gone"));
+
+        fs::write(
+            dir.path().join("docs/auth.md"),
+            format!("{}<!-- sintesi:start id=\"b\" code_ref=\"src/auth.ts#logout\" -->\nB\n<!-- sintesi:end id=\"b\" -->\n", DOC),
+        )
+        .unwrap();
+        let ids = ["auth".to_string(), "b".to_string()];
+        let err = merge_anchors(dir.path(), "docs/auth.md", &ids).unwrap_err();
+        assert!(err.to_string().contains("are not adjacent"));
+        assert_eq!(
+            SintesiMap::load(dir.path().join(MAP_FILE)).unwrap().entries[0].id,
+            "auth"
+        );
+    }
+}
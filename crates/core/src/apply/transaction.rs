@@ -0,0 +1,271 @@
+//! Transactional file writes
+//!
+//! Writes are staged in memory and committed together. Each file is written
+//! to a temporary sibling and renamed into place; if any write fails, every
+//! file already committed is restored to its original content (or deleted if
+//! it did not exist), so a failed run never leaves half-updated docs next to
+//! a mismatched map. Files are written through extended-length paths on
+//! Windows, so docs deep in a tree or on a UNC share can be written too.
+//! Original contents are read before the first write: a file that exists but
+//! cannot be read fails the commit up front, since it could not be restored.
+//! In read-only mode (`readonly`), or if a path is outside the write sandbox
+//! (`sandbox`), nothing is written.
+
+use crate::error::Error;
+use crate::paths::extended_length;
+use crate::sandbox::{guard_write, WriteSandbox};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A set of staged file writes that are committed or rolled back together
+#[derive(Debug, Default)]
+pub struct Transaction {
+    staged: Vec<(PathBuf, Vec<u8>)>,
+    /// Where the staged paths must be, besides the process-wide sandbox
+    sandbox: Option<WriteSandbox>,
+}
+
+/// State of a file before the transaction touched it
+struct Original {
+    path: PathBuf,
+    /// Previous content, `None` if the file did not exist
+    content: Option<Vec<u8>>,
+    /// Directories created for the file, outermost first
+    created_dirs: Vec<PathBuf>,
+}
+
+impl Transaction {
+    /// Create an empty transaction
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only commit if every staged path is allowed by `sandbox` (a project's
+    /// sandbox, for writes to paths taken from its content)
+    pub fn confine(mut self, sandbox: WriteSandbox) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    /// Stage a write; staging the same path again replaces the earlier content
+    pub fn stage(&mut self, path: impl Into<PathBuf>, content: impl Into<Vec<u8>>) {
+        let path = path.into();
+        let content = content.into();
+        match self.staged.iter_mut().find(|(p, _)| *p == path) {
+            Some((_, existing)) => *existing = content,
+            None => self.staged.push((path, content)),
+        }
+    }
+
+    /// Number of staged writes
+    pub fn len(&self) -> usize {
+        self.staged.len()
+    }
+
+    /// Check whether nothing is staged
+    pub fn is_empty(&self) -> bool {
+        self.staged.is_empty()
+    }
+
+    /// Write all staged files, rolling back on the first failure
+    ///
+    /// # Returns
+    /// The written paths in staging order, or the write error (with any
+    /// rollback failures appended) after all earlier writes were undone;
+    /// `Error::ReadOnly` in read-only mode, `Error::PathNotAllowed` for a
+    /// path outside the sandbox, and the read error for an existing file that
+    /// cannot be read, before anything is written
+    pub fn commit(self) -> Result<Vec<PathBuf>, Error> {
+        let mut contents = Vec::with_capacity(self.staged.len());
+        for (path, _) in &self.staged {
+            guard_write("write files", path)?;
+            if let Some(sandbox) = &self.sandbox {
+                sandbox.check("write files", path)?;
+            }
+            contents.push(read_original(path)?);
+        }
+        let mut originals: Vec<Original> = Vec::new();
+
+        for ((path, content), original_content) in self.staged.iter().zip(contents) {
+            let original = Original {
+                path: path.clone(),
+                content: original_content,
+                created_dirs: missing_dirs(&extended_length(path)),
+            };
+            let result = write_file(path, content);
+            originals.push(original);
+
+            if let Err(e) = result {
+                let mut message = format!("Failed to write {}: {}", path.display(), e);
+                let rollback_errors = rollback(&originals);
+                if !rollback_errors.is_empty() {
+                    message.push_str(&format!(
+                        "; rollback failed for {}",
+                        rollback_errors.join(", ")
+                    ));
+                }
+                return Err(Error::from_reason(message));
+            }
+        }
+
+        Ok(self.staged.into_iter().map(|(path, _)| path).collect())
+    }
+}
+
+/// Content of a file before it is written, `None` if it does not exist
+fn read_original(path: &Path) -> Result<Option<Vec<u8>>, Error> {
+    match fs::read(extended_length(path)) {
+        Ok(content) => Ok(Some(content)),
+        // A path through a regular file cannot exist either
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::NotADirectory
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(Error::from_reason(format!(
+            "Failed to read {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+/// Write a file through a temporary sibling and an atomic rename
+fn write_file(path: &Path, content: &[u8]) -> io::Result<()> {
+    let path = extended_length(path);
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp = parent.join(format!(".{}.sintesi-tmp", file_name));
+    fs::write(&temp, content)?;
+    fs::rename(&temp, &path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
+}
+
+/// Ancestors of `path` that do not exist yet, outermost first
+fn missing_dirs(path: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = path
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+        .map(Path::to_path_buf)
+        .collect();
+    dirs.reverse();
+    dirs
+}
+
+/// Restore files in reverse order, returning the paths that could not be restored
+fn rollback(originals: &[Original]) -> Vec<String> {
+    let mut failed = Vec::new();
+    for original in originals.iter().rev() {
+        let restored = match &original.content {
+            Some(content) => write_file(&original.path, content),
+            None => match fs::remove_file(extended_length(&original.path)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            },
+        };
+        if restored.is_err() {
+            failed.push(original.path.display().to_string());
+        }
+        for dir in original.created_dirs.iter().rev() {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_writes_all_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.md"), "old").unwrap();
+
+        let mut transaction = Transaction::new();
+        transaction.stage(dir.path().join("a.md"), "first");
+        transaction.stage(dir.path().join("docs/b.md"), "new");
+        transaction.stage(dir.path().join("a.md"), "second");
+        assert_eq!(transaction.len(), 2);
+
+        let written = transaction.commit().unwrap();
+
+        assert_eq!(written.len(), 2);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.md")).unwrap(),
+            "second"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("docs/b.md")).unwrap(),
+            "new"
+        );
+        assert!(!dir.path().join(".a.md.sintesi-tmp").exists());
+    }
+
+    #[test]
+    fn test_failed_write_rolls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.md"), "original").unwrap();
+        // A regular file where a directory is needed makes the third write fail
+        fs::write(dir.path().join("blocker"), "").unwrap();
+
+        let mut transaction = Transaction::new();
+        transaction.stage(dir.path().join("a.md"), "updated");
+        transaction.stage(dir.path().join("new/b.md"), "created");
+        transaction.stage(dir.path().join("blocker/c.md"), "unreachable");
+
+        let err = transaction.commit().unwrap_err();
+
+        assert!(err.to_string().contains("blocker"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.md")).unwrap(),
+            "original"
+        );
+        assert!(!dir.path().join("new/b.md").exists());
+        assert!(!dir.path().join("new").exists());
+    }
+
+    #[test]
+    fn test_unreadable_file_fails_before_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.md"), "original").unwrap();
+        // Exists but cannot be read as a file, so it could not be restored
+        fs::create_dir(dir.path().join("b.md")).unwrap();
+
+        let mut transaction = Transaction::new();
+        transaction.stage(dir.path().join("a.md"), "updated");
+        transaction.stage(dir.path().join("b.md"), "created");
+
+        let err = transaction.commit().unwrap_err();
+
+        assert!(err.to_string().contains("Failed to read "), "{}", err);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.md")).unwrap(),
+            "original"
+        );
+        assert!(dir.path().join("b.md").is_dir());
+    }
+
+    #[test]
+    fn test_deep_paths() {
+        // Longer than Windows' MAX_PATH (260 characters)
+        let dir = tempfile::tempdir().unwrap();
+        let deep = (0..25).fold(dir.path().join("docs"), |path, _| path.join("node_modules"));
+
+        let mut transaction = Transaction::new();
+        transaction.stage(deep.join("README.md"), "deep");
+        transaction.commit().unwrap();
+        assert_eq!(fs::read_to_string(deep.join("README.md")).unwrap(), "deep");
+    }
+}
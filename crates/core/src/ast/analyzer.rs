@@ -4,22 +4,40 @@
 //! public API signatures (functions, classes, interfaces, types, etc.)
 //! using the Oxc parser for high performance.
 
-use crate::types::{CodeSignature, SymbolType};
+use super::arena::with_allocator;
+use super::backend::{self, LanguageBackend, BUILTIN_BACKENDS};
+use super::cache::AstCache;
+use super::diagnostics::Diagnostic;
+use super::hasher::SignatureHasher;
+use super::filter::SymbolFilter;
+use super::ignore::{IgnoreDirectives, IgnoredItem};
+use super::module_kind::{self, PackageType};
+use super::printer::Printer;
+use super::sfc::{self, ScriptBlocks};
+use super::sourcemap::SourceMap;
+use super::symbol_id::symbol_id;
+use super::{config, openapi, proto, python, react, routes, rust, sql};
+use crate::provider::{ContentProvider, DiskProvider};
+use crate::text::{SkippedFile, DEFAULT_MAX_FILE_SIZE};
+use crate::types::{line_starts, CodeSignature, SymbolLocation, SymbolType, TypeParam};
 use oxc_allocator::Allocator;
 use oxc_ast::ast::*;
 use oxc_ast::visit::walk;
 use oxc_ast::Visit;
-use oxc_parser::{Parser, ParserReturn};
+use oxc_parser::{ParseOptions, Parser, ParserReturn};
 use oxc_semantic::ScopeFlags;
-use oxc_span::SourceType;
-use regex::Regex;
-use std::path::Path;
-use std::sync::OnceLock;
+use oxc_span::{SourceType, Span};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Information about a symbol found in the code
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SymbolInfo {
-    /// Name of the symbol
+    /// Name of the symbol, qualified by its enclosing declarations for nested
+    /// symbols (`Outer.helper`, `MyClass.method.helper`)
     pub name: String,
     /// Type of symbol
     pub symbol_type: SymbolType,
@@ -29,85 +47,655 @@ pub struct SymbolInfo {
     pub is_exported: bool,
     /// File path where it was found
     pub file_path: String,
+    /// Marked deprecated (`@deprecated` JSDoc tag, `deprecated: true` in
+    /// OpenAPI operations and schemas)
+    pub deprecated: bool,
+    /// Release tag of the doc comment (`@public`, `@beta`, `@experimental`)
+    pub release_tag: Option<ReleaseTag>,
+    /// Where the symbol is declared (`None` for analyzers that do not track
+    /// source spans: Rust items, configuration keys, and schema definitions)
+    pub location: Option<SymbolLocation>,
+    /// Generic type parameters (`<T extends Base = Default>`), in declaration
+    /// order; their text is also part of `signature`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub type_params: Vec<TypeParam>,
+}
+
+/// API stability declared by an api-extractor style release tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseTag {
+    /// `@public`: stable, covered by semantic versioning
+    Public,
+    /// `@beta`: may change in minor releases
+    Beta,
+    /// `@experimental` (or `@alpha`): may change or disappear at any time
+    Experimental,
+}
+
+impl ReleaseTag {
+    /// Lowercase name (`public`, `beta`, `experimental`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseTag::Public => "public",
+            ReleaseTag::Beta => "beta",
+            ReleaseTag::Experimental => "experimental",
+        }
+    }
+
+    /// Release tag declared in a doc comment, if any
+    pub fn from_doc(doc: &str) -> Option<Self> {
+        doc_tags(doc).find_map(|tag| match tag {
+            "@public" => Some(ReleaseTag::Public),
+            "@beta" => Some(ReleaseTag::Beta),
+            "@experimental" | "@alpha" => Some(ReleaseTag::Experimental),
+            _ => None,
+        })
+    }
+
+    /// Whether breaking changes are allowed without a major version bump
+    pub fn is_unstable(&self) -> bool {
+        *self != ReleaseTag::Public
+    }
+}
+
+/// Block tags of a doc comment (`@deprecated`, `@beta`)
+fn doc_tags(doc: &str) -> impl Iterator<Item = &str> {
+    doc.split_whitespace().filter(|word| word.starts_with('@'))
+}
+
+/// Name of the symbol recorded for an anonymous default export
+pub const DEFAULT_EXPORT: &str = "default";
+
+impl SymbolInfo {
+    /// Human-readable name of an anonymous default export, derived from its
+    /// file (`src/date-picker/index.tsx` → `DatePicker` for a class,
+    /// `src/format_date.ts` → `formatDate` for anything else)
+    ///
+    /// Returns `None` for symbols that have a name of their own.
+    pub fn display_name(&self) -> Option<String> {
+        if self.name != DEFAULT_EXPORT {
+            return None;
+        }
+        let path = Path::new(&self.file_path);
+        let stem = path.file_stem()?.to_str()?;
+        // `button.component.ts` → `button`, `index.ts` → parent directory
+        let stem = stem.split('.').next().unwrap_or(stem);
+        let base = if stem == "index" {
+            path.parent()?.file_name()?.to_str()?
+        } else {
+            stem
+        };
+
+        let mut name = String::new();
+        for (i, word) in base
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .enumerate()
+        {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                if i == 0 && self.symbol_type != SymbolType::Class {
+                    name.extend(first.to_lowercase());
+                } else {
+                    name.extend(first.to_uppercase());
+                }
+                name.push_str(chars.as_str());
+            }
+        }
+        (!name.is_empty()).then_some(name)
+    }
 }
 
 /// Result of analyzing a source file
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct AnalysisResult {
     /// All symbols found in the file
     pub symbols: Vec<SymbolInfo>,
     /// Errors encountered during analysis
     pub errors: Vec<String>,
+    /// Structured parse diagnostics, the same errors as `errors` with their
+    /// position
+    pub diagnostics: Vec<Diagnostic>,
+    /// Files and symbols excluded by ignore directives
+    pub ignored: Vec<IgnoredItem>,
+    /// Files not analyzed because they are not text (binary or undecodable)
+    pub skipped: Vec<SkippedFile>,
 }
 
-/// Global regex cache - compiled once and reused across all analyzer instances
-struct NormalizationRegexes {
-    multi_line_comment: Regex,
-    single_line_comment: Regex,
-    import_paths: Regex,
-    whitespace: Regex,
+/// Parse a program, resolving files of unknown module kind
+///
+/// Scripts may `return` at the top level (CommonJS wraps them in a function).
+/// A file without ESM syntax that only fails to parse as a script (e.g. it
+/// uses top-level `await`) is reparsed as a module.
+fn parse_program<'a>(
+    allocator: &'a Allocator,
+    content: &'a str,
+    source_type: SourceType,
+) -> ParserReturn<'a> {
+    let parse = |source_type: SourceType| {
+        let options = ParseOptions {
+            allow_return_outside_function: source_type.is_script() || source_type.is_unambiguous(),
+            ..ParseOptions::default()
+        };
+        Parser::new(allocator, content, source_type)
+            .with_options(options)
+            .parse()
+    };
+
+    let result = parse(source_type);
+    if source_type.is_unambiguous() && !result.errors.is_empty() {
+        let module = parse(source_type.with_module(true));
+        if module.errors.is_empty() {
+            return module;
+        }
+    }
+    result
 }
 
-impl NormalizationRegexes {
-    fn new() -> Self {
+/// How symbols sharing a qualified name within a file are named
+///
+/// Names can still collide after qualification, e.g. two `helper` functions
+/// declared in different anonymous callbacks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateNames {
+    /// Keep the names as declared
+    #[default]
+    Keep,
+    /// Keep the first occurrence and suffix later ones in source order
+    /// (`helper`, `helper~2`, `helper~3`), so every symbol has a unique
+    /// drift key
+    Suffix,
+}
+
+/// Options for the AST analyzer
+#[derive(Debug, Clone)]
+pub struct AnalyzerOptions {
+    /// Naming of symbols sharing a qualified name
+    pub duplicate_names: DuplicateNames,
+    /// Also extract declarations inside function and method bodies
+    /// (callbacks, local helpers). Off by default: only module-level
+    /// declarations, class members, and namespace members are extracted.
+    pub deep: bool,
+    /// List the public members a class inherits from base classes declared
+    /// in the same file in its signature, so changing a base class drifts
+    /// the classes extending it
+    pub inherited_members: bool,
+    /// Include decorators, with their arguments, in class and property
+    /// signatures (`@Controller('/users') class UsersController { ... }`).
+    /// Method signatures always include theirs.
+    pub decorators: bool,
+    /// Report React function components as `Component` symbols whose
+    /// signature lists their props (see `ast::react`)
+    pub react_components: bool,
+    /// Symbols reported as ignored instead of extracted
+    pub filter: SymbolFilter,
+    /// Size in bytes above which files are reported as skipped instead of
+    /// parsed (bundles, minified and generated artifacts)
+    pub max_file_size: usize,
+    /// Also parse files detected as generated or minified (see
+    /// `text::detect_generated`), which are skipped by default
+    pub include_generated: bool,
+    /// List non-exported symbols too (see `AstAnalyzerInternal::is_listed`),
+    /// e.g. internal helpers anchored in architecture docs. Extraction
+    /// itself always keeps them, so drift is tracked either way.
+    pub include_private: bool,
+    /// Attribute symbols of generated files that have a source map to the
+    /// original sources (see `ast::sourcemap`). Such files are analyzed even
+    /// though they are generated. Only applies to files read through a
+    /// content provider (`analyze_with`, `analyze_from`).
+    pub source_maps: bool,
+}
+
+impl Default for AnalyzerOptions {
+    fn default() -> Self {
         Self {
-            multi_line_comment: Regex::new(r"/\*[\s\S]*?\*/").unwrap(),
-            single_line_comment: Regex::new(r"//.*").unwrap(),
-            import_paths: Regex::new(r#"import\(".*?"\)\."#).unwrap(),
-            whitespace: Regex::new(r"\s+").unwrap(),
+            duplicate_names: DuplicateNames::default(),
+            deep: false,
+            inherited_members: false,
+            decorators: false,
+            react_components: false,
+            filter: SymbolFilter::default(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            include_generated: false,
+            include_private: false,
+            source_maps: false,
         }
     }
 }
 
-/// Static regex cache - initialized once on first use
-static NORMALIZE_REGEX: OnceLock<NormalizationRegexes> = OnceLock::new();
+impl AnalyzerOptions {
+    /// Create default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how symbols sharing a qualified name are named
+    pub fn duplicate_names(mut self, strategy: DuplicateNames) -> Self {
+        self.duplicate_names = strategy;
+        self
+    }
+
+    /// Extract declarations inside function and method bodies
+    pub fn deep(mut self, value: bool) -> Self {
+        self.deep = value;
+        self
+    }
+
+    /// List inherited members in the signatures of derived classes
+    pub fn inherited_members(mut self, value: bool) -> Self {
+        self.inherited_members = value;
+        self
+    }
+
+    /// Include decorators in class and property signatures
+    pub fn decorators(mut self, value: bool) -> Self {
+        self.decorators = value;
+        self
+    }
+
+    /// Report React function components with their props
+    pub fn react_components(mut self, value: bool) -> Self {
+        self.react_components = value;
+        self
+    }
+
+    /// Skip files larger than `bytes` instead of parsing them
+    pub fn max_file_size(mut self, bytes: usize) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Parse generated and minified files too
+    pub fn include_generated(mut self, include: bool) -> Self {
+        self.include_generated = include;
+        self
+    }
 
-/// Get the global regex cache, initializing it if necessary
-fn get_normalize_regex() -> &'static NormalizationRegexes {
-    NORMALIZE_REGEX.get_or_init(NormalizationRegexes::new)
+    /// List non-exported symbols too
+    pub fn include_private(mut self, include: bool) -> Self {
+        self.include_private = include;
+        self
+    }
+
+    /// Attribute symbols of generated files to their original sources
+    pub fn source_maps(mut self, enabled: bool) -> Self {
+        self.source_maps = enabled;
+        self
+    }
+
+    /// Exclude symbols by kind, name, or path (see `ast::filter`)
+    ///
+    /// Also enables decorators, React components, and inherited members
+    /// when the config sets `symbols.decorators`, `symbols.reactComponents`,
+    /// or `symbols.inheritedMembers`, so every analyzer configured from the
+    /// project tracks them.
+    pub fn filter(mut self, filter: SymbolFilter) -> Self {
+        self.decorators |= filter.decorators();
+        self.react_components |= filter.react_components();
+        self.inherited_members |= filter.inherited_members();
+        self.filter = filter;
+        self
+    }
+
+    /// Options producing unique symbol names, for drift keys (`file#symbol`)
+    pub fn drift_keys() -> Self {
+        Self::new().duplicate_names(DuplicateNames::Suffix)
+    }
 }
 
 /// Internal AST analyzer (pure Rust logic)
-pub struct AstAnalyzerInternal;
+#[derive(Debug, Clone, Default)]
+pub struct AstAnalyzerInternal {
+    options: AnalyzerOptions,
+    cache: Option<Arc<AstCache>>,
+    /// Backends registered with `with_backend`
+    backends: Vec<Arc<dyn LanguageBackend>>,
+}
 
 impl AstAnalyzerInternal {
     /// Create a new AST analyzer
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Create an AST analyzer with options
+    pub fn with_options(options: AnalyzerOptions) -> Self {
+        Self {
+            options,
+            cache: None,
+            backends: Vec::new(),
+        }
+    }
+
+    /// Reuse the symbols of files unchanged since they were cached
+    /// (see `ast::cache`); new and changed files are added to the cache
+    pub fn with_cache(mut self, cache: Arc<AstCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Analyze files with the extensions of a language backend (see
+    /// `ast::backend`), ahead of the built-in analyzers
+    pub fn with_backend(mut self, backend: Arc<dyn LanguageBackend>) -> Self {
+        self.backends.push(backend);
+        self
     }
 
     /// Analyze a TypeScript/JavaScript file
+    ///
+    /// OpenAPI documents (`.yaml`, `.yml`, `.json` with an `openapi`/`swagger`
+    /// key), configuration files (JSON Schema, `package.json`, `tsconfig.json`),
+    /// SQL files, `.proto` files, Python (`.py`, `.pyi`), Rust (`.rs`), Go
+    /// (`.go`), and Java (`.java`) files are routed to their dedicated
+    /// analyzers instead of the Oxc parser, as are files handled by a
+    /// registered backend.
+    /// Files over the `max_file_size` option, and generated or minified files
+    /// unless `include_generated` is set, are not parsed and are listed in
+    /// `skipped`.
     pub fn analyze_file(&self, file_path: &str, content: &str) -> AnalysisResult {
-        let allocator = Allocator::default();
+        self.analyze_file_as(file_path, content, None)
+    }
 
-        // Determine source type from file extension
-        let source_type = self.determine_source_type(file_path);
+    /// Analyze a file whose nearest `package.json` declares a module `type`
+    ///
+    /// `.js`/`.jsx` files (and node shebang scripts) are parsed as ES modules
+    /// or CommonJS scripts accordingly; without a type the kind is inferred
+    /// from the syntax.
+    pub fn analyze_file_as(
+        &self,
+        file_path: &str,
+        content: &str,
+        package_type: Option<PackageType>,
+    ) -> AnalysisResult {
+        let include_generated = self.options.include_generated;
+        self.analyze_checked(file_path, content, package_type, include_generated)
+    }
 
-        // Parse the source code
-        let parser = Parser::new(&allocator, content, source_type);
-        let ParserReturn {
-            program,
-            errors: parse_errors,
-            ..
-        } = parser.parse();
+    /// Analyze a file read through a content provider
+    ///
+    /// Same as `analyze_file_as`, except that with the `source_maps` option a
+    /// generated file with a source map is analyzed and its symbols are
+    /// attributed to the original sources, which `provider` reads along with
+    /// the map. `path` is relative to the provider's root.
+    pub fn analyze_from(
+        &self,
+        provider: &dyn ContentProvider,
+        path: &Path,
+        content: &str,
+        package_type: Option<PackageType>,
+    ) -> AnalysisResult {
+        let file_path = path.to_string_lossy().replace('\\', "/");
+        let source_map = if self.options.source_maps {
+            SourceMap::for_file(provider, path, content)
+        } else {
+            None
+        };
+        let Some(source_map) = source_map else {
+            return self.analyze_file_as(&file_path, content, package_type);
+        };
+        let mut result = self.analyze_checked(&file_path, content, package_type, true);
+        source_map.attribute(&mut result.symbols, content, provider);
+        result
+    }
 
-        let mut errors = Vec::new();
-        for error in parse_errors {
-            errors.push(format!("Parse error: {}", error));
+    /// Analyze a file unless it is too large or, without `include_generated`,
+    /// generated
+    fn analyze_checked(
+        &self,
+        file_path: &str,
+        content: &str,
+        package_type: Option<PackageType>,
+        include_generated: bool,
+    ) -> AnalysisResult {
+        let limit = self.options.max_file_size;
+        let mut skipped = SkippedFile::over_limit(file_path, content.len(), limit);
+        if skipped.is_none() && !include_generated {
+            skipped = SkippedFile::generated(file_path, content);
+        }
+        if let Some(skipped) = skipped {
+            return AnalysisResult {
+                skipped: vec![skipped],
+                ..AnalysisResult::default()
+            };
+        }
+        let mut result = match &self.cache {
+            Some(cache) => {
+                let backends: Vec<&str> = self.backends.iter().map(|b| b.name()).collect();
+                let settings = format!(
+                    "{:?} {} {} {} {} {:?} {:?}",
+                    self.options.duplicate_names,
+                    self.options.deep,
+                    self.options.decorators,
+                    self.options.react_components,
+                    self.options.inherited_members,
+                    package_type,
+                    backends
+                );
+                cache.get_or_extract(file_path, content, &settings, || {
+                    self.extract(file_path, content, package_type)
+                })
+            }
+            None => self.extract(file_path, content, package_type),
+        };
+        self.options
+            .filter
+            .apply(&mut result.symbols, &mut result.ignored);
+        result
+    }
+
+    /// Run the analyzer matching a file, before symbol filters apply
+    fn extract(
+        &self,
+        file_path: &str,
+        content: &str,
+        package_type: Option<PackageType>,
+    ) -> AnalysisResult {
+        let directives = IgnoreDirectives::parse(content);
+        if let Some(reason) = directives.file {
+            return AnalysisResult {
+                symbols: Vec::new(),
+                errors: Vec::new(),
+                diagnostics: Vec::new(),
+                ignored: vec![IgnoredItem {
+                    file_path: file_path.to_string(),
+                    symbol_name: None,
+                    reason,
+                }],
+                skipped: Vec::new(),
+            };
+        }
+
+        let registered = self.backends.iter().map(|backend| backend.as_ref());
+        if let Some(backend) = registered
+            .chain(BUILTIN_BACKENDS.iter().copied())
+            .find(|backend| backend::handles(*backend, file_path))
+        {
+            // Overloads (Java) share a name
+            let mut result = backend.analyze(file_path, content);
+            if self.options.duplicate_names == DuplicateNames::Suffix {
+                suffix_duplicates(&mut result.symbols);
+            }
+            return result;
+        }
+        if openapi::is_openapi_document(file_path, content) {
+            return openapi::analyze_openapi(file_path, content);
+        }
+        if let Some(kind) = config::detect_config_kind(file_path, content) {
+            return config::analyze_config(file_path, content, kind);
+        }
+        if file_path.ends_with(".sql") {
+            return sql::analyze_sql(file_path, content);
+        }
+        if file_path.ends_with(".proto") {
+            return proto::analyze_proto(file_path, content);
+        }
+        if file_path.ends_with(".py") || file_path.ends_with(".pyi") {
+            return python::analyze_python(file_path, content);
+        }
+        if file_path.ends_with(".rs") {
+            return rust::analyze_rust(file_path, content);
+        }
+
+        // Components are parsed through their scripts, blanked in place
+        let component = ScriptBlocks::extract(file_path, content);
+        let (content, source_type) = match &component {
+            Some(scripts) => (scripts.text.as_str(), scripts.source_type),
+            // Determine source type from file extension and package type
+            None => (
+                content,
+                self.determine_source_type(file_path, content, package_type),
+            ),
+        };
+
+        let (mut symbols, extra_symbols, ignored, errors, diagnostics) =
+            with_allocator(|allocator| {
+                // Parse the source code
+                let ParserReturn {
+                    program,
+                    errors: parse_errors,
+                    ..
+                } = parse_program(allocator, content, source_type);
+
+                let mut errors = Vec::new();
+                let mut diagnostics = Vec::new();
+                for error in parse_errors {
+                    errors.push(format!("Parse error: {}", error));
+                    diagnostics.push(Diagnostic::from_oxc(file_path, content, &error));
+                }
+
+                // Visit the AST and extract symbols
+                let mut visitor = SymbolExtractor::new(
+                    file_path,
+                    content,
+                    directives.next_symbol,
+                    self.options.deep,
+                );
+                visitor.doc_comments = doc_comments(&program, content);
+                visitor.decorators = self.options.decorators;
+                if self.options.inherited_members {
+                    visitor.inherited_members = true;
+                    visitor.index_classes(&program);
+                }
+                visitor.visit_program(&program);
+                if self.options.react_components {
+                    for (name, signature) in react::function_components(&program, content) {
+                        let component = visitor.symbols.iter_mut().find(|symbol| {
+                            symbol.name == name
+                                && matches!(
+                                    symbol.symbol_type,
+                                    SymbolType::Function | SymbolType::Const
+                                )
+                        });
+                        if let Some(component) = component {
+                            component.symbol_type = SymbolType::Component;
+                            component.signature = signature;
+                        }
+                    }
+                }
+                visitor.apply_export_lists();
+
+                let mut extra_symbols = routes::extract_routes(&program, file_path, content);
+                if component.is_some() {
+                    extra_symbols.push(sfc::component_symbol(&program, file_path, content));
+                }
+                (
+                    visitor.symbols,
+                    extra_symbols,
+                    visitor.ignored,
+                    errors,
+                    diagnostics,
+                )
+            });
+
+        if self.options.duplicate_names == DuplicateNames::Suffix {
+            suffix_duplicates(&mut symbols);
         }
 
-        // Visit the AST and extract symbols
-        let mut visitor = SymbolExtractor::new(file_path, content);
-        visitor.visit_program(&program);
+        // A component's default export is the component symbol
+        if component.is_some() {
+            symbols.retain(|symbol| symbol.name != DEFAULT_EXPORT);
+        }
 
-        let mut symbols = visitor.symbols;
+        // Route and component signatures are built in canonical form by
+        // their extractors
+        symbols.extend(extra_symbols);
 
-        // Normalize signatures
-        for symbol in &mut symbols {
-            symbol.signature = self.normalize_text(&symbol.signature);
+        AnalysisResult {
+            symbols,
+            errors,
+            diagnostics,
+            ignored,
+            skipped: Vec::new(),
         }
+    }
 
-        AnalysisResult { symbols, errors }
+    /// Analyze files on disk
+    ///
+    /// Symbol file paths are made relative to `root` (with `/` separators).
+    /// Parse and read errors are prefixed with the relative path.
+    pub fn analyze_files(&self, root: &Path, files: &[PathBuf]) -> AnalysisResult {
+        let relative: Vec<PathBuf> = files
+            .iter()
+            .map(|path| path.strip_prefix(root).unwrap_or(path).to_path_buf())
+            .collect();
+        self.analyze_with(&DiskProvider::new(root), &relative)
+    }
+
+    /// Analyze files read through a content provider
+    ///
+    /// `files` are relative to the provider's root; symbol file paths use `/`
+    /// separators and errors are prefixed with the path. JavaScript files are
+    /// parsed according to the `type` of their nearest `package.json`. Files
+    /// that are not text are listed in `skipped` rather than `errors`. With
+    /// the `source_maps` option, symbols of generated files are attributed
+    /// to their original sources (see `analyze_from`).
+    pub fn analyze_with(
+        &self,
+        provider: &dyn ContentProvider,
+        files: &[PathBuf],
+    ) -> AnalysisResult {
+        let mut symbols = Vec::new();
+        let mut errors = Vec::new();
+        let mut diagnostics = Vec::new();
+        let mut ignored = Vec::new();
+        let mut skipped = Vec::new();
+        let mut package_types: HashMap<PathBuf, Option<PackageType>> = HashMap::new();
+
+        for path in files {
+            let relative = path.to_string_lossy().replace('\\', "/");
+            match provider.read(path) {
+                Ok(content) => {
+                    let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+                    let package_type = *package_types
+                        .entry(dir)
+                        .or_insert_with(|| module_kind::nearest_package_type(provider, path));
+                    let result = self.analyze_from(provider, path, &content, package_type);
+                    errors.extend(
+                        result
+                            .errors
+                            .into_iter()
+                            .map(|e| format!("{}: {}", relative, e)),
+                    );
+                    symbols.extend(result.symbols);
+                    diagnostics.extend(result.diagnostics);
+                    ignored.extend(result.ignored);
+                    skipped.extend(result.skipped);
+                }
+                Err(e) => match SkippedFile::from_error(&relative, &e) {
+                    Some(file) => skipped.push(file),
+                    None => errors.push(format!("{}: {}", relative, e)),
+                },
+            }
+        }
+
+        AnalysisResult {
+            symbols,
+            errors,
+            diagnostics,
+            ignored,
+            skipped,
+        }
     }
 
     /// Analyze source code directly (without file path context)
@@ -119,191 +707,621 @@ impl AstAnalyzerInternal {
     pub fn extract_signature(&self, symbol: &SymbolInfo) -> CodeSignature {
         CodeSignature {
             symbol_name: symbol.name.clone(),
-            symbol_type: symbol.symbol_type.clone(),
+            symbol_type: symbol.symbol_type,
             signature_text: symbol.signature.clone(),
             is_exported: symbol.is_exported,
             hash: None, // Hash will be computed by the NAPI layer
+            display_name: symbol.display_name(),
+            deprecated: symbol.deprecated,
+            release_tag: symbol.release_tag.map(|tag| tag.as_str().to_string()),
+            location: symbol.location,
+            type_params: symbol.type_params.clone(),
+            id: Some(symbol_id(
+                &symbol.file_path,
+                &symbol.name,
+                symbol.symbol_type,
+            )),
         }
     }
 
-    fn determine_source_type(&self, file_path: &str) -> SourceType {
-        let path = Path::new(file_path);
-        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-        match extension {
-            "ts" => SourceType::ts(),
-            "tsx" => SourceType::tsx(),
-            "jsx" => SourceType::jsx(),
-            "mts" => SourceType::ts().with_module(true),
-            "cts" => SourceType::ts().with_module(false),
-            _ => SourceType::default(),
-        }
-    }
-
-    fn normalize_text(&self, text: &str) -> String {
-        let regex = get_normalize_regex();
-        let text = regex.multi_line_comment.replace_all(text, "");
-        let text = regex.single_line_comment.replace_all(&text, "");
-        let text = regex.import_paths.replace_all(&text, "");
-        let text = regex.whitespace.replace_all(&text, " ");
-
-        // Normalize punctuation spacing
-        let text = text
-            .replace(" : ", ": ")
-            .replace(" (", "(")
-            .replace("( ", "(")
-            .replace(" )", ")")
-            .replace(") ", ")")
-            .replace(" [", "[")
-            .replace("[ ", "[")
-            .replace(" ]", "]")
-            .replace("] ", "]")
-            .replace(" {", "{")
-            .replace("{ ", "{")
-            .replace(" }", "}")
-            .replace("} ", "}")
-            .replace(" ,", ",")
-            .replace(" ;", ";")
-            .replace(",", ", ")
-            .replace(";", "; ");
-
-        text.trim().to_string()
+    /// Extract the signature of a symbol with its hash computed
+    pub fn code_signature(&self, symbol: &SymbolInfo, hasher: &SignatureHasher) -> CodeSignature {
+        let signature = self.extract_signature(symbol);
+        CodeSignature {
+            hash: Some(hasher.hash(signature.clone()).hash),
+            ..signature
+        }
+    }
+
+    /// Whether a symbol is listed to API consumers: exported symbols always
+    /// are, others only with the `include_private` option
+    pub fn is_listed(&self, symbol: &SymbolInfo) -> bool {
+        symbol.is_exported || self.options.include_private
+    }
+
+    /// Look up the current signature of a single symbol, with its hash
+    ///
+    /// When several symbols share the name, the exported one wins. Returns
+    /// `None` if the file has no such symbol.
+    pub fn signature_of(
+        &self,
+        file_path: &str,
+        content: &str,
+        symbol_name: &str,
+    ) -> Option<CodeSignature> {
+        let symbols = self.analyze_file(file_path, content).symbols;
+        let symbol = symbols
+            .iter()
+            .filter(|s| s.name == symbol_name)
+            .max_by_key(|s| s.is_exported)?;
+
+        Some(self.code_signature(symbol, &SignatureHasher::new()))
+    }
+
+    fn determine_source_type(
+        &self,
+        file_path: &str,
+        content: &str,
+        package_type: Option<PackageType>,
+    ) -> SourceType {
+        module_kind::source_type_for(file_path, content, package_type).unwrap_or_default()
     }
 }
 
-impl Default for AstAnalyzerInternal {
-    fn default() -> Self {
-        Self::new()
+/// Suffix later symbols sharing a name with their occurrence number
+fn suffix_duplicates(symbols: &mut [SymbolInfo]) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for symbol in symbols {
+        let count = seen.entry(symbol.name.clone()).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            symbol.name = format!("{}~{}", symbol.name, count);
+        }
     }
 }
 
+/// Keywords between a statement's first token and a declaration's start
+const DECLARATION_MODIFIERS: &[&str] = &[
+    "export", "default", "declare", "abstract", "async", "const", "let", "var",
+];
+
+/// JSDoc comments with the offset of the token each is attached to, sorted
+fn doc_comments<'a>(program: &Program, source_text: &'a str) -> Vec<(u32, &'a str)> {
+    let mut comments: Vec<_> = program
+        .comments
+        .iter()
+        .filter(|comment| comment.is_jsdoc(source_text))
+        .map(|comment| {
+            (
+                comment.attached_to,
+                comment.content_span().source_text(source_text),
+            )
+        })
+        .collect();
+    comments.sort_by_key(|(at, _)| *at);
+    comments
+}
+
 /// Visitor that extracts symbols from the AST
 struct SymbolExtractor<'a> {
     symbols: Vec<SymbolInfo>,
     file_path: String,
     source_text: &'a str,
     current_export: bool,
+    /// Names of the enclosing declarations, outermost first
+    scope: Vec<String>,
+    /// Walk function bodies
+    deep: bool,
+    /// Prefix class and property signatures with their decorators
+    decorators: bool,
+    /// List the members classes inherit from base classes in the file
+    inherited_members: bool,
+    /// Module-level classes by name, for inherited members
+    classes: HashMap<String, ClassShape>,
+    /// Pending `sintesi-ignore-next-symbol` directives (offset, reason), in order
+    ignore_next: std::vec::IntoIter<(u32, Option<String>)>,
+    pending_ignore: Option<(u32, Option<String>)>,
+    ignored: Vec<IgnoredItem>,
+    /// JSDoc comments by the offset of the token they are attached to, sorted
+    doc_comments: Vec<(u32, &'a str)>,
+    /// Byte offsets where the source's lines start
+    line_starts: Vec<usize>,
+    /// Local and exported names of `export { local as exported }` lists and
+    /// CommonJS exports of local names, qualified, in source order
+    export_lists: Vec<(String, String)>,
+}
+
+/// What a CommonJS assignment exports to
+enum CommonJsTarget<'t> {
+    /// `module.exports = ...`
+    Module,
+    /// `exports.name = ...` or `module.exports.name = ...`
+    Named(&'t str),
+}
+
+impl<'t> CommonJsTarget<'t> {
+    fn of(target: &'t AssignmentTarget) -> Option<Self> {
+        let AssignmentTarget::StaticMemberExpression(member) = target else {
+            return None;
+        };
+        let name = member.property.name.as_str();
+        match &member.object {
+            Expression::Identifier(object) if object.name == "module" && name == "exports" => {
+                Some(Self::Module)
+            }
+            Expression::Identifier(object) if object.name == "exports" => Some(Self::Named(name)),
+            object if is_module_exports(object) => Some(Self::Named(name)),
+            _ => None,
+        }
+    }
+}
+
+/// Whether an expression is `module.exports`
+fn is_module_exports(expression: &Expression) -> bool {
+    let Expression::StaticMemberExpression(member) = expression else {
+        return false;
+    };
+    member.property.name == "exports"
+        && matches!(&member.object, Expression::Identifier(object) if object.name == "module")
 }
 
 impl<'a> SymbolExtractor<'a> {
-    fn new(file_path: &str, source_text: &'a str) -> Self {
+    fn new(
+        file_path: &str,
+        source_text: &'a str,
+        ignore_next: Vec<(u32, Option<String>)>,
+        deep: bool,
+    ) -> Self {
+        let mut ignore_next = ignore_next.into_iter();
         Self {
             symbols: Vec::new(),
             file_path: file_path.to_string(),
             source_text,
             current_export: false,
+            scope: Vec::new(),
+            deep,
+            decorators: false,
+            inherited_members: false,
+            classes: HashMap::new(),
+            pending_ignore: ignore_next.next(),
+            ignore_next,
+            ignored: Vec::new(),
+            doc_comments: Vec::new(),
+            line_starts: line_starts(source_text),
+            export_lists: Vec::new(),
+        }
+    }
+
+    /// Export the symbols named in `export { ... }` lists under their
+    /// exported names
+    ///
+    /// An aliased symbol (`export { format as formatDate }`) is renamed, or
+    /// copied when its declaration is exported too; nested declarations
+    /// follow their parent's name (`formatDate.pad`).
+    fn apply_export_lists(&mut self) {
+        if self.export_lists.is_empty() {
+            return;
+        }
+        let declared_exported: HashSet<String> = self
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.is_exported)
+            .map(|symbol| symbol.name.clone())
+            .collect();
+        let mut symbols = Vec::with_capacity(self.symbols.len());
+        for mut symbol in std::mem::take(&mut self.symbols) {
+            // Whether the symbol is also exported under its own name
+            let mut exported_as_is = false;
+            let mut aliases = Vec::new();
+            for (local, exported) in &self.export_lists {
+                let Some(rest) = symbol.name.strip_prefix(local.as_str()) else {
+                    continue;
+                };
+                if !rest.is_empty() && !rest.starts_with('.') {
+                    continue;
+                }
+                if exported == local {
+                    symbol.is_exported |= rest.is_empty();
+                    exported_as_is = true;
+                } else {
+                    exported_as_is |= declared_exported.contains(local);
+                    aliases.push((format!("{}{}", exported, rest), rest.is_empty()));
+                }
+            }
+            let aliases: Vec<SymbolInfo> = aliases
+                .into_iter()
+                .map(|(name, top_level)| SymbolInfo {
+                    name,
+                    is_exported: symbol.is_exported || top_level,
+                    ..symbol.clone()
+                })
+                .collect();
+            if aliases.is_empty() || exported_as_is {
+                symbols.push(symbol);
+            }
+            symbols.extend(aliases);
+        }
+        self.symbols = symbols;
+    }
+
+    /// Record a CommonJS export of `value` as `name`
+    ///
+    /// Local names are exported like `export { local as name }`; functions,
+    /// classes, and other values become symbols of their own.
+    fn commonjs_export(&mut self, name: &str, span: Span, value: &Expression<'a>) {
+        if let Expression::Identifier(local) = value {
+            self.export_lists
+                .push((local.name.to_string(), name.to_string()));
+            return;
+        }
+        let (symbol_type, signature, type_parameters) = match value {
+            Expression::FunctionExpression(func) => (
+                SymbolType::Function,
+                self.printer().function(func),
+                func.type_parameters.as_deref(),
+            ),
+            Expression::ArrowFunctionExpression(arrow) => (
+                SymbolType::Function,
+                self.printer().arrow(arrow),
+                arrow.type_parameters.as_deref(),
+            ),
+            Expression::ClassExpression(class) => (
+                SymbolType::Class,
+                self.extract_class_signature(class, name),
+                class.type_parameters.as_deref(),
+            ),
+            expr => (SymbolType::Const, self.printer().expression(expr), None),
+        };
+        self.push_symbol(
+            span,
+            SymbolInfo {
+                name: name.to_string(),
+                symbol_type,
+                signature,
+                is_exported: true,
+                file_path: self.file_path.clone(),
+                deprecated: false,
+                release_tag: None,
+                location: None,
+                type_params: self.printer().type_params(type_parameters),
+            },
+        );
+        self.nested(Some(name), |this| this.visit_expression(value));
+    }
+
+    /// `module.exports = <function or class>`: the module is exported under
+    /// the function's or class's own name, or as `default` when anonymous
+    fn commonjs_module(&mut self, id: Option<&BindingIdentifier>, expr: &AssignmentExpression<'a>) {
+        match id {
+            Some(id) => {
+                let name = id.name.to_string();
+                self.export_lists.push((name.clone(), name));
+                self.visit_expression(&expr.right);
+            }
+            None => self.commonjs_export(DEFAULT_EXPORT, expr.span, &expr.right),
+        }
+    }
+
+    /// Name qualified by the enclosing declarations
+    fn qualify(&self, name: &str) -> String {
+        if self.scope.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", self.scope.join("."), name)
+        }
+    }
+
+    /// Walk the body of a declaration, qualifying nested names with `name`
+    ///
+    /// Declarations inside a body are not exported by the enclosing `export`.
+    fn nested(&mut self, name: Option<&str>, walk: impl FnOnce(&mut Self)) {
+        let exported = std::mem::replace(&mut self.current_export, false);
+        if let Some(name) = name {
+            self.scope.push(name.to_string());
+        }
+        walk(self);
+        if name.is_some() {
+            self.scope.pop();
+        }
+        self.current_export = exported;
+    }
+
+    /// Walk a function body like `nested`, or skip it unless extracting deeply
+    fn function_body(&mut self, name: Option<&str>, span: Span, walk: impl FnOnce(&mut Self)) {
+        if self.deep {
+            self.nested(name, walk);
+            return;
+        }
+        // Directives inside the skipped body must not apply to later symbols
+        while let Some((offset, _)) = &self.pending_ignore {
+            if *offset < span.start || *offset > span.end {
+                break;
+            }
+            self.pending_ignore = self.ignore_next.next();
         }
     }
 
-    fn extract_text(&self, start: u32, end: u32) -> String {
-        let start = start as usize;
-        let end = end as usize;
-        self.source_text.get(start..end)
-            .unwrap_or("")
-            .to_string()
+    /// JSDoc comment documenting the declaration at `start`
+    ///
+    /// Comments are attached to the first token of their statement, which
+    /// precedes the declaration by its modifiers (`export default`, `declare`,
+    /// the `const` of a variable declaration).
+    fn doc_comment(&self, start: u32) -> Option<&'a str> {
+        let index = self.doc_comments.partition_point(|(at, _)| *at <= start);
+        let (at, doc) = self.doc_comments.get(index.checked_sub(1)?)?;
+        let between = self.source_text.get(*at as usize..start as usize)?;
+        between
+            .split_whitespace()
+            .all(|word| DECLARATION_MODIFIERS.contains(&word))
+            .then_some(*doc)
     }
 
-    fn extract_function_signature(&self, func: &Function, _name: &str) -> String {
-        // Find the body start position to extract just the signature
-        if let Some(body) = &func.body {
-            let signature_end = body.span.start;
-            self.extract_text(func.span.start, signature_end)
+    /// Record a symbol declared at `span`, unless an ignore directive precedes it
+    fn push_symbol(&mut self, span: Span, mut symbol: SymbolInfo) {
+        let start = span.start;
+        symbol.location = Some(SymbolLocation::from_offsets(
+            &self.line_starts,
+            start as usize,
+            span.end as usize,
+        ));
+        if let Some(doc) = self.doc_comment(start) {
+            symbol.deprecated = doc_tags(doc).any(|tag| tag == "@deprecated");
+            symbol.release_tag = ReleaseTag::from_doc(doc);
+        }
+        let mut reason = None;
+        let mut ignore = false;
+        while let Some((offset, directive_reason)) = self.pending_ignore.take() {
+            if offset > start {
+                self.pending_ignore = Some((offset, directive_reason));
+                break;
+            }
+            ignore = true;
+            reason = directive_reason;
+            self.pending_ignore = self.ignore_next.next();
+        }
+
+        if ignore {
+            self.ignored.push(IgnoredItem {
+                file_path: symbol.file_path,
+                symbol_name: Some(symbol.name),
+                reason,
+            });
         } else {
-            self.extract_text(func.span.start, func.span.end)
+            self.symbols.push(symbol);
+        }
+    }
+
+    fn printer(&self) -> Printer<'a> {
+        Printer::new(self.source_text)
+    }
+
+    /// Type parameters of a function or class assigned to a variable
+    fn initializer_type_params(&self, init: Option<&Expression>) -> Vec<TypeParam> {
+        let type_parameters = match init {
+            Some(Expression::ArrowFunctionExpression(arrow)) => arrow.type_parameters.as_deref(),
+            Some(Expression::FunctionExpression(func)) => func.type_parameters.as_deref(),
+            Some(Expression::ClassExpression(class)) => class.type_parameters.as_deref(),
+            _ => None,
+        };
+        self.printer().type_params(type_parameters)
+    }
+
+    /// Decorators as printed (`@Get(":id") `), empty unless enabled
+    fn decorator_prefix(&self, decorators: &[Decorator]) -> String {
+        if !self.decorators {
+            return String::new();
+        }
+        self.printer().decorators(decorators)
+    }
+
+    /// Signature name of a class member, `None` for private members
+    ///
+    /// Computed keys keep their brackets (`[Symbol.iterator]`).
+    fn member_name(&self, key: &PropertyKey, computed: bool) -> Option<String> {
+        if matches!(key, PropertyKey::PrivateIdentifier(_)) {
+            return None;
+        }
+        let name = self.printer().property_key(key, computed);
+        (!name.starts_with('_')).then_some(name)
+    }
+
+    /// Type annotation of a class property as `: type` (`: any` without one)
+    fn member_type(&self, type_annotation: Option<&TSTypeAnnotation>) -> String {
+        match type_annotation {
+            Some(_) => self.printer().type_annotation(type_annotation),
+            None => ": any".to_string(),
         }
     }
 
     fn extract_class_signature(&self, class: &Class, class_name: &str) -> String {
-        let mut signature = String::from("class ");
+        let printer = self.printer();
+        let mut signature = self.decorator_prefix(&class.decorators);
+        if class.r#abstract {
+            signature.push_str("abstract ");
+        }
+        signature.push_str("class ");
 
         // Add class name
         signature.push_str(class_name);
 
-        // Add type parameters (generics) if present
-        if let Some(type_params) = &class.type_parameters {
-            let generics = self.extract_text(type_params.span.start, type_params.span.end);
-            signature.push_str(&generics);
+        // Add type parameters (generics) and heritage clauses if present
+        signature.push_str(&printer.type_parameters(class.type_parameters.as_deref()));
+        signature.push_str(&printer.class_heritage(class));
+
+        let mut members = self.class_members(class);
+        if self.inherited_members {
+            self.inherit_members(class, &mut members);
         }
 
-        signature.push_str(" { ");
+        if members.is_empty() {
+            signature.push_str(" {}");
+        } else {
+            let members: Vec<&str> = members.iter().map(|m| m.signature.as_str()).collect();
+            signature.push_str(" { ");
+            signature.push_str(&members.join("; "));
+            signature.push_str(" }");
+        }
+
+        signature
+    }
 
+    /// Public members of a class (properties, accessors, methods, index
+    /// signatures), as printed in its signature
+    fn class_members(&self, class: &Class) -> Vec<ClassMember> {
+        let printer = self.printer();
         let mut members = Vec::new();
 
-        // Extract class members (properties and methods)
         for element in &class.body.body {
             match element {
                 ClassElement::PropertyDefinition(prop) => {
-                    if let PropertyKey::StaticIdentifier(ident) = &prop.key {
-                        // Skip truly private properties (with # prefix or starting with _)
-                        let prop_name = &ident.name;
-                        if prop_name.starts_with('_') {
-                            continue;
-                        }
+                    // Skip private properties (`#name` or starting with _)
+                    let Some(prop_name) = self.member_name(&prop.key, prop.computed) else {
+                        continue;
+                    };
 
-                        let mut prop_sig = String::new();
+                    let mut prop_sig = self.decorator_prefix(&prop.decorators);
 
-                        // Add modifiers
-                        if prop.r#static {
-                            prop_sig.push_str("static ");
-                        }
-                        if prop.readonly {
-                            prop_sig.push_str("readonly ");
-                        }
-
-                        // Add property name
-                        prop_sig.push_str(prop_name);
-
-                        // Add type annotation
-                        if let Some(type_ann) = &prop.type_annotation {
-                            prop_sig.push_str(": ");
-                            let type_text = self.extract_text(type_ann.span.start, type_ann.span.end);
-                            // Remove ": " prefix if present in extracted text
-                            let type_text = type_text.strip_prefix(": ").unwrap_or(&type_text);
-                            prop_sig.push_str(type_text);
-                        } else {
-                            prop_sig.push_str(": any");
-                        }
+                    // Add modifiers
+                    if prop.r#static {
+                        prop_sig.push_str("static ");
+                    }
+                    if prop.readonly {
+                        prop_sig.push_str("readonly ");
+                    }
 
-                        members.push(prop_sig);
+                    prop_sig.push_str(&prop_name);
+                    prop_sig.push_str(&self.member_type(prop.type_annotation.as_deref()));
+                    members.push(ClassMember::new(Some(prop_name), prop_sig));
+                }
+                ClassElement::AccessorProperty(prop) => {
+                    let Some(prop_name) = self.member_name(&prop.key, prop.computed) else {
+                        continue;
+                    };
+
+                    let mut prop_sig = self.decorator_prefix(&prop.decorators);
+                    if prop.r#static {
+                        prop_sig.push_str("static ");
                     }
+                    prop_sig.push_str("accessor ");
+                    prop_sig.push_str(&prop_name);
+                    prop_sig.push_str(&self.member_type(prop.type_annotation.as_deref()));
+                    members.push(ClassMember::new(Some(prop_name), prop_sig));
                 }
                 ClassElement::MethodDefinition(method) => {
-                    if let PropertyKey::StaticIdentifier(ident) = &method.key {
-                        let method_name = &ident.name;
-                        // Skip private methods
-                        if method_name.starts_with('_') {
-                            continue;
-                        }
-
-                        // Extract full method signature from source
-                        let method_text = self.extract_text(method.span.start, method.span.end);
-
-                        // Extract just the signature (everything before the body)
-                        let signature_part = if let Some(body_start) = method_text.find('{') {
-                            method_text[..body_start].trim()
-                        } else {
-                            // Abstract method or declaration
-                            method_text.trim()
-                        };
-
-                        members.push(signature_part.to_string());
-                    }
+                    // Skip private methods (getters and setters included)
+                    let Some(name) = self.member_name(&method.key, method.computed) else {
+                        continue;
+                    };
+                    members.push(ClassMember::new(Some(name), printer.method(method)));
+                }
+                ClassElement::TSIndexSignature(index) => {
+                    members.push(ClassMember::new(None, printer.index_signature(index)));
                 }
                 _ => {
-                    // Handle other elements like accessors, static blocks, etc.
+                    // Static blocks carry no signature
                 }
             }
         }
 
-        signature.push_str(&members.join("; "));
-        signature.push_str(" }");
+        members
+    }
 
-        signature
+    /// Append the members a class inherits from base classes declared in the
+    /// file, nearest base first, unless the class or a nearer base declares
+    /// a member of the same name
+    fn inherit_members(&self, class: &Class, members: &mut Vec<ClassMember>) {
+        let mut names: HashSet<String> = members.iter().filter_map(|m| m.name.clone()).collect();
+        let mut visited = HashSet::new();
+        let mut base = base_class_name(class);
+        while let Some(name) = base {
+            // Inheritance cycles only occur in invalid code
+            if !visited.insert(name) {
+                break;
+            }
+            let Some(shape) = self.classes.get(name) else {
+                break;
+            };
+            for member in &shape.members {
+                let inherited = match &member.name {
+                    Some(name) => names.insert(name.clone()),
+                    None => !members.contains(member),
+                };
+                if inherited {
+                    members.push(member.clone());
+                }
+            }
+            base = shape.base.as_deref();
+        }
+    }
+
+    /// Record the members and base of the module-level class declarations,
+    /// for `inherit_members`
+    fn index_classes(&mut self, program: &Program) {
+        for statement in &program.body {
+            let class = match statement {
+                Statement::ClassDeclaration(class) => class,
+                Statement::ExportNamedDeclaration(decl) => match &decl.declaration {
+                    Some(Declaration::ClassDeclaration(class)) => class,
+                    _ => continue,
+                },
+                Statement::ExportDefaultDeclaration(decl) => match &decl.declaration {
+                    ExportDefaultDeclarationKind::ClassDeclaration(class) => class,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let Some(id) = &class.id else {
+                continue;
+            };
+            let shape = ClassShape {
+                base: base_class_name(class).map(str::to_string),
+                members: self.class_members(class),
+            };
+            self.classes.insert(id.name.to_string(), shape);
+        }
+    }
+}
+
+/// A public class member as printed in the class signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ClassMember {
+    /// Member name, `None` for index signatures
+    name: Option<String>,
+    signature: String,
+}
+
+impl ClassMember {
+    fn new(name: Option<String>, signature: String) -> Self {
+        Self { name, signature }
+    }
+}
+
+/// Base class and public members of a class declared in the file
+#[derive(Debug)]
+struct ClassShape {
+    base: Option<String>,
+    members: Vec<ClassMember>,
+}
+
+/// Name of the class a class extends, when it is a plain identifier
+fn base_class_name<'c>(class: &'c Class) -> Option<&'c str> {
+    match &class.super_class {
+        Some(Expression::Identifier(ident)) => Some(ident.name.as_str()),
+        _ => None,
     }
 }
 
 impl<'a> Visit<'a> for SymbolExtractor<'a> {
     fn visit_export_named_declaration(&mut self, decl: &ExportNamedDeclaration<'a>) {
+        // Lists re-exporting another module (`export { a } from './a'`)
+        // declare nothing here
+        if decl.source.is_none() {
+            for specifier in &decl.specifiers {
+                let local = self.qualify(&specifier.local.name());
+                let exported = self.qualify(&specifier.exported.name());
+                self.export_lists.push((local, exported));
+            }
+        }
         self.current_export = true;
         walk::walk_export_named_declaration(self, decl);
         self.current_export = false;
@@ -311,58 +1329,177 @@ impl<'a> Visit<'a> for SymbolExtractor<'a> {
 
     fn visit_export_default_declaration(&mut self, decl: &ExportDefaultDeclaration<'a>) {
         self.current_export = true;
-        walk::walk_export_default_declaration(self, decl);
+
+        // Anonymous defaults are recorded as `default`; named ones are
+        // recorded by their declaration's visitor
+        let anonymous = match &decl.declaration {
+            ExportDefaultDeclarationKind::FunctionDeclaration(func) if func.id.is_none() => Some((
+                SymbolType::Function,
+                self.printer().function(func),
+                func.type_parameters.as_deref(),
+            )),
+            ExportDefaultDeclarationKind::ClassDeclaration(class) if class.id.is_none() => Some((
+                SymbolType::Class,
+                self.extract_class_signature(class, DEFAULT_EXPORT),
+                class.type_parameters.as_deref(),
+            )),
+            kind => match kind.as_expression() {
+                // `export default name` refers to a symbol declared elsewhere
+                None | Some(Expression::Identifier(_)) => None,
+                Some(Expression::FunctionExpression(func)) if func.id.is_some() => None,
+                Some(Expression::ClassExpression(class)) if class.id.is_some() => None,
+                Some(Expression::ArrowFunctionExpression(arrow)) => Some((
+                    SymbolType::Function,
+                    self.printer().arrow(arrow),
+                    arrow.type_parameters.as_deref(),
+                )),
+                Some(Expression::FunctionExpression(func)) => Some((
+                    SymbolType::Function,
+                    self.printer().function(func),
+                    func.type_parameters.as_deref(),
+                )),
+                Some(Expression::ClassExpression(class)) => Some((
+                    SymbolType::Class,
+                    self.extract_class_signature(class, DEFAULT_EXPORT),
+                    class.type_parameters.as_deref(),
+                )),
+                Some(expr) => Some((SymbolType::Const, self.printer().expression(expr), None)),
+            },
+        };
+
+        match anonymous {
+            Some((symbol_type, signature, type_parameters)) => {
+                self.push_symbol(
+                    decl.span,
+                    SymbolInfo {
+                        name: DEFAULT_EXPORT.to_string(),
+                        symbol_type,
+                        signature,
+                        is_exported: true,
+                        file_path: self.file_path.clone(),
+                        deprecated: false,
+                        release_tag: None,
+                        location: None,
+                        type_params: self.printer().type_params(type_parameters),
+                    },
+                );
+                self.scope.push(DEFAULT_EXPORT.to_string());
+                walk::walk_export_default_declaration(self, decl);
+                self.scope.pop();
+            }
+            None => walk::walk_export_default_declaration(self, decl),
+        }
         self.current_export = false;
     }
 
     fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
-        if let Some(id) = &func.id {
+        let name = func.id.as_ref().map(|id| id.name.as_str());
+        if let Some(name) = name {
             let is_exported = self.current_export;
-            let name = id.name.as_str();
-            let signature = self.extract_function_signature(func, name);
+            let signature = self.printer().function(func);
 
-            self.symbols.push(SymbolInfo {
-                name: name.to_string(),
-                symbol_type: SymbolType::Function,
-                signature,
-                is_exported,
-                file_path: self.file_path.clone(),
-            });
+            self.push_symbol(
+                func.span,
+                SymbolInfo {
+                    name: self.qualify(name),
+                    symbol_type: SymbolType::Function,
+                    signature,
+                    is_exported,
+                    file_path: self.file_path.clone(),
+                    deprecated: false,
+                    release_tag: None,
+                    location: None,
+                    type_params: self.printer().type_params(func.type_parameters.as_deref()),
+                },
+            );
         }
 
-        walk::walk_function(self, func, flags);
+        self.function_body(name, func.span, |this| {
+            walk::walk_function(this, func, flags)
+        });
+    }
+
+    fn visit_arrow_function_expression(&mut self, func: &ArrowFunctionExpression<'a>) {
+        self.function_body(None, func.span, |this| {
+            walk::walk_arrow_function_expression(this, func)
+        });
     }
 
     fn visit_class(&mut self, class: &Class<'a>) {
-        if let Some(id) = &class.id {
+        let name = class.id.as_ref().map(|id| id.name.as_str());
+        if let Some(name) = name {
             let is_exported = self.current_export;
-            let name = id.name.as_str();
             let signature = self.extract_class_signature(class, name);
 
-            self.symbols.push(SymbolInfo {
-                name: name.to_string(),
-                symbol_type: SymbolType::Class,
-                signature,
-                is_exported,
-                file_path: self.file_path.clone(),
-            });
+            self.push_symbol(
+                class.span,
+                SymbolInfo {
+                    name: self.qualify(name),
+                    symbol_type: SymbolType::Class,
+                    signature,
+                    is_exported,
+                    file_path: self.file_path.clone(),
+                    deprecated: false,
+                    release_tag: None,
+                    location: None,
+                    type_params: self.printer().type_params(class.type_parameters.as_deref()),
+                },
+            );
         }
 
-        walk::walk_class(self, class);
+        self.nested(name, |this| walk::walk_class(this, class));
+    }
+
+    fn visit_method_definition(&mut self, method: &MethodDefinition<'a>) {
+        let name = match &method.key {
+            PropertyKey::StaticIdentifier(ident) => Some(ident.name.as_str()),
+            _ => None,
+        };
+        self.nested(name, |this| walk::walk_method_definition(this, method));
+    }
+
+    fn visit_property_definition(&mut self, prop: &PropertyDefinition<'a>) {
+        let name = match &prop.key {
+            PropertyKey::StaticIdentifier(ident) => Some(ident.name.as_str()),
+            _ => None,
+        };
+        self.nested(name, |this| walk::walk_property_definition(this, prop));
+    }
+
+    fn visit_ts_module_declaration(&mut self, decl: &TSModuleDeclaration<'a>) {
+        match &decl.id {
+            TSModuleDeclarationName::Identifier(id) => {
+                let name = id.name.as_str();
+                self.nested(Some(name), |this| {
+                    walk::walk_ts_module_declaration(this, decl)
+                });
+            }
+            // `declare module "pkg"` augments another module's declarations
+            TSModuleDeclarationName::StringLiteral(_) => {
+                walk::walk_ts_module_declaration(self, decl);
+            }
+        }
     }
 
     fn visit_ts_interface_declaration(&mut self, decl: &TSInterfaceDeclaration<'a>) {
         let is_exported = self.current_export;
         let name = decl.id.name.as_str();
-        let signature = self.extract_text(decl.span.start, decl.span.end);
-
-        self.symbols.push(SymbolInfo {
-            name: name.to_string(),
-            symbol_type: SymbolType::Interface,
-            signature,
-            is_exported,
-            file_path: self.file_path.clone(),
-        });
+        let signature = self.printer().interface(decl);
+
+        self.push_symbol(
+            decl.span,
+            SymbolInfo {
+                name: self.qualify(name),
+                symbol_type: SymbolType::Interface,
+                signature,
+                is_exported,
+                file_path: self.file_path.clone(),
+                deprecated: false,
+                release_tag: None,
+                location: None,
+                type_params: self.printer().type_params(decl.type_parameters.as_deref()),
+            },
+        );
 
         walk::walk_ts_interface_declaration(self, decl);
     }
@@ -370,15 +1507,22 @@ impl<'a> Visit<'a> for SymbolExtractor<'a> {
     fn visit_ts_type_alias_declaration(&mut self, decl: &TSTypeAliasDeclaration<'a>) {
         let is_exported = self.current_export;
         let name = decl.id.name.as_str();
-        let signature = self.extract_text(decl.span.start, decl.span.end);
-
-        self.symbols.push(SymbolInfo {
-            name: name.to_string(),
-            symbol_type: SymbolType::TypeAlias,
-            signature,
-            is_exported,
-            file_path: self.file_path.clone(),
-        });
+        let signature = self.printer().type_alias(decl);
+
+        self.push_symbol(
+            decl.span,
+            SymbolInfo {
+                name: self.qualify(name),
+                symbol_type: SymbolType::TypeAlias,
+                signature,
+                is_exported,
+                file_path: self.file_path.clone(),
+                deprecated: false,
+                release_tag: None,
+                location: None,
+                type_params: self.printer().type_params(decl.type_parameters.as_deref()),
+            },
+        );
 
         walk::walk_ts_type_alias_declaration(self, decl);
     }
@@ -386,30 +1530,84 @@ impl<'a> Visit<'a> for SymbolExtractor<'a> {
     fn visit_ts_enum_declaration(&mut self, decl: &TSEnumDeclaration<'a>) {
         let is_exported = self.current_export;
         let name = decl.id.name.as_str();
-        let signature = self.extract_text(decl.span.start, decl.span.end);
-
-        self.symbols.push(SymbolInfo {
-            name: name.to_string(),
-            symbol_type: SymbolType::Enum,
-            signature,
-            is_exported,
-            file_path: self.file_path.clone(),
-        });
+        let signature = self.printer().enumeration(decl);
+
+        self.push_symbol(
+            decl.span,
+            SymbolInfo {
+                name: self.qualify(name),
+                symbol_type: SymbolType::Enum,
+                signature,
+                is_exported,
+                file_path: self.file_path.clone(),
+                deprecated: false,
+                release_tag: None,
+                location: None,
+                type_params: Vec::new(),
+            },
+        );
 
         walk::walk_ts_enum_declaration(self, decl);
     }
 
+    /// CommonJS exports at the top level: `module.exports = { login }`,
+    /// `module.exports = login`, and `exports.login = ...`
+    fn visit_assignment_expression(&mut self, expr: &AssignmentExpression<'a>) {
+        let target = CommonJsTarget::of(&expr.left);
+        let (Some(target), AssignmentOperator::Assign, true) =
+            (target, expr.operator, self.scope.is_empty())
+        else {
+            walk::walk_assignment_expression(self, expr);
+            return;
+        };
+        match (target, &expr.right) {
+            (CommonJsTarget::Named(name), value) => self.commonjs_export(name, expr.span, value),
+            (CommonJsTarget::Module, Expression::ObjectExpression(object)) => {
+                for property in &object.properties {
+                    let ObjectPropertyKind::ObjectProperty(property) = property else {
+                        continue;
+                    };
+                    let name = self.member_name(&property.key, property.computed);
+                    let Some(name) = name.filter(|_| property.kind == PropertyKind::Init) else {
+                        continue;
+                    };
+                    self.commonjs_export(&name, property.span, &property.value);
+                }
+            }
+            (CommonJsTarget::Module, Expression::Identifier(local)) => {
+                let name = local.name.to_string();
+                self.export_lists.push((name.clone(), name));
+            }
+            (CommonJsTarget::Module, Expression::FunctionExpression(func)) => {
+                self.commonjs_module(func.id.as_ref(), expr)
+            }
+            (CommonJsTarget::Module, Expression::ClassExpression(class)) => {
+                self.commonjs_module(class.id.as_ref(), expr)
+            }
+            (CommonJsTarget::Module, Expression::ArrowFunctionExpression(_)) => {
+                self.commonjs_module(None, expr)
+            }
+            // `module.exports = require('./impl')` and other values
+            (CommonJsTarget::Module, _) => walk::walk_assignment_expression(self, expr),
+        }
+    }
+
     fn visit_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) {
         let is_exported = self.current_export;
         let is_const = decl.kind == VariableDeclarationKind::Const;
 
         for declarator in &decl.declarations {
-            if let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind {
-                let name = id.name.as_str();
-                let signature = self.extract_text(declarator.span.start, declarator.span.end);
+            let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind else {
+                self.visit_variable_declarator(declarator);
+                continue;
+            };
+            let name = id.name.as_str();
+            let signature = self.printer().declarator(declarator);
 
-                self.symbols.push(SymbolInfo {
-                    name: name.to_string(),
+            self.push_symbol(
+                declarator.span,
+                SymbolInfo {
+                    name: self.qualify(name),
                     symbol_type: if is_const {
                         SymbolType::Const
                     } else {
@@ -418,17 +1616,37 @@ impl<'a> Visit<'a> for SymbolExtractor<'a> {
                     signature,
                     is_exported,
                     file_path: self.file_path.clone(),
+                    deprecated: false,
+                    release_tag: None,
+                    location: None,
+                    type_params: self.initializer_type_params(declarator.init.as_ref()),
+                },
+            );
+
+            // Functions and classes assigned to a variable are named after it
+            let is_callable = matches!(
+                declarator.init,
+                Some(
+                    Expression::FunctionExpression(_)
+                        | Expression::ArrowFunctionExpression(_)
+                        | Expression::ClassExpression(_)
+                )
+            );
+            if is_callable {
+                self.nested(Some(name), |this| {
+                    this.visit_variable_declarator(declarator)
                 });
+            } else {
+                self.visit_variable_declarator(declarator);
             }
         }
-
-        walk::walk_variable_declaration(self, decl);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::text::SkipReason;
 
     #[test]
     fn test_analyzer_creation() {
@@ -539,6 +1757,40 @@ mod tests {
         assert!(result.symbols[0].is_exported);
     }
 
+    #[test]
+    fn test_class_heritage() {
+        let code = "abstract class Entity { id: string; abstract save(): void; }
+export class User extends Entity implements Named, Comparable<User> { name: string; save(): void {} }
+export class Admin extends User { role: string; }
+export class Widget extends Base<Props> {}";
+        let signatures = |analyzer: AstAnalyzerInternal| -> Vec<String> {
+            let result = analyzer.analyze_code(code);
+            result.symbols.into_iter().map(|s| s.signature).collect()
+        };
+
+        assert_eq!(
+            signatures(AstAnalyzerInternal::new()),
+            [
+                "abstract class Entity { id: string; abstract save(): void }",
+                "class User extends Entity implements Named, Comparable<User> { name: string; save(): void }",
+                "class Admin extends User { role: string }",
+                "class Widget extends Base<Props> {}",
+            ]
+        );
+
+        // Inherited members, nearest base first, without overridden ones
+        let options = AnalyzerOptions::new().inherited_members(true);
+        assert_eq!(
+            signatures(AstAnalyzerInternal::with_options(options)),
+            [
+                "abstract class Entity { id: string; abstract save(): void }",
+                "class User extends Entity implements Named, Comparable<User> { name: string; save(): void; id: string }",
+                "class Admin extends User { role: string; name: string; save(): void; id: string }",
+                "class Widget extends Base<Props> {}",
+            ]
+        );
+    }
+
     #[test]
     fn test_class_with_readonly_and_static() {
         let analyzer = AstAnalyzerInternal::new();
@@ -560,6 +1812,71 @@ mod tests {
         assert!(signature.contains("static"));
     }
 
+    #[test]
+    fn test_class_accessors_and_index_signatures() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code = r#"
+            export class Store {
+                [key: string]: unknown;
+                static accessor count: number = 0;
+                #secret = 1;
+                get size(): number { return 0; }
+                set size(value: number) {}
+                get _hidden(): number { return 0; }
+                [Symbol.iterator]() {}
+                ["label"]: string;
+            }
+        "#;
+        let result = analyzer.analyze_code(code);
+
+        let signature = &result.symbols[0].signature;
+        for member in [
+            "[key: string]: unknown",
+            "static accessor count: number",
+            "get size(): number",
+            "set size(value: number)",
+            "[Symbol.iterator]()",
+            "[\"label\"]: string",
+        ] {
+            assert!(signature.contains(member), "{} in {}", member, signature);
+        }
+        assert!(!signature.contains("secret") && !signature.contains("_hidden"));
+
+        // Adding a getter changes the class signature
+        let without_getter = analyzer.analyze_code("export class A { x: number; }");
+        let with_getter =
+            analyzer.analyze_code("export class A { x: number; get y(): number { return 1; } }");
+        assert_ne!(
+            without_getter.symbols[0].signature,
+            with_getter.symbols[0].signature
+        );
+    }
+
+    #[test]
+    fn test_decorators_option() {
+        let code = r#"
+            @Controller('/users')
+            export class UsersController {
+                @Input() name: string;
+                findOne(id: string): string { return id; }
+            }
+        "#;
+        let plain = AstAnalyzerInternal::new().analyze_code(code);
+        assert!(!plain.symbols[0].signature.contains('@'));
+
+        let decorated = AstAnalyzerInternal::with_options(AnalyzerOptions::new().decorators(true));
+        let signature = &decorated.analyze_code(code).symbols[0].signature;
+        assert!(signature.starts_with(r#"@Controller("/users") class UsersController"#));
+        assert!(signature.contains("@Input() name: string"));
+
+        // Changing a decorator argument changes the signature
+        let moved = decorated.analyze_code(&code.replace("/users", "/people"));
+        assert_ne!(&moved.symbols[0].signature, signature);
+
+        let filter = SymbolFilter::parse(r#"{"symbols": {"decorators": true}}"#).unwrap();
+        assert!(AnalyzerOptions::new().filter(filter).decorators);
+    }
+
     #[test]
     fn test_complex_generics() {
         let analyzer = AstAnalyzerInternal::new();
@@ -574,6 +1891,87 @@ mod tests {
         assert!(result.symbols[0].signature.contains("Record"));
     }
 
+    #[test]
+    fn test_type_params() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code = "export function pick<T extends object, K extends keyof T = keyof T>(obj: T, keys: K[]) {}
+export interface Store<in out S> {}
+export const wrap = <const T,>(value: T) => value;
+export default class<Item = string> {}
+export enum Role { Admin }";
+        let result = analyzer.analyze_code(code);
+        let params = |name: &str| {
+            let symbol = result.symbols.iter().find(|s| s.name == name).unwrap();
+            symbol.type_params.clone()
+        };
+
+        assert_eq!(
+            params("pick"),
+            [
+                TypeParam {
+                    constraint: Some("object".to_string()),
+                    ..TypeParam::new("T")
+                },
+                TypeParam {
+                    name: "K".to_string(),
+                    constraint: Some("keyof T".to_string()),
+                    default: Some("keyof T".to_string()),
+                }
+            ]
+        );
+        assert_eq!(params("Store"), [TypeParam::new("S")]);
+        assert_eq!(params("wrap"), [TypeParam::new("T")]);
+        assert_eq!(
+            params("default"),
+            [TypeParam {
+                default: Some("string".to_string()),
+                ..TypeParam::new("Item")
+            }]
+        );
+        assert!(params("Role").is_empty());
+        let signature = analyzer.extract_signature(&result.symbols[0]);
+        assert_eq!(signature.type_params, params("pick"));
+    }
+
+    #[test]
+    fn test_analyze_openapi_file() {
+        let analyzer = AstAnalyzerInternal::new();
+        let spec = "openapi: 3.0.0\npaths:\n  /ping:\n    get:\n      operationId: ping\n      responses:\n        '200':\n          description: ok\n";
+        let result = analyzer.analyze_file("openapi.yaml", spec);
+
+        assert_eq!(result.symbols.len(), 1);
+        assert_eq!(result.symbols[0].name, "ping");
+        assert_eq!(result.symbols[0].symbol_type, SymbolType::Operation);
+    }
+
+    #[test]
+    fn test_analyze_python_file() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code = "def login(user: str) -> bool:\n    return True\n";
+        let result = analyzer.analyze_file("src/auth.py", code);
+
+        assert_eq!(result.symbols.len(), 1);
+        assert_eq!(result.symbols[0].name, "login");
+        assert_eq!(result.symbols[0].signature, "def login(user: str) -> bool");
+        assert!(analyzer
+            .signature_of("src/auth.py", code, "login")
+            .is_some_and(|signature| signature.hash.is_some()));
+    }
+
+    #[test]
+    fn test_analyze_rust_file() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code = "pub struct Session;\nimpl Session {\n    pub fn new() -> Self { Session }\n}\n";
+        let result = analyzer.analyze_file("src/session.rs", code);
+
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Session", "Session::new"]);
+        assert_eq!(
+            result.symbols[1].signature,
+            "impl Session { pub fn new() -> Self }"
+        );
+    }
+
     #[test]
     fn test_multiple_analyzer_instances() {
         // Test that OnceLock works correctly across multiple instances
@@ -589,4 +1987,418 @@ mod tests {
         assert_eq!(result1.symbols.len(), result2.symbols.len());
         assert_eq!(result1.symbols[0].signature, result2.symbols[0].signature);
     }
+
+    #[test]
+    fn test_script_and_module_detection() {
+        let analyzer = AstAnalyzerInternal::new();
+
+        let commonjs = "const fs = require('fs');\nif (!fs) return;\nmodule.exports = { a: 1 };";
+        assert!(analyzer
+            .analyze_file("lib/index.cjs", commonjs)
+            .errors
+            .is_empty());
+        assert!(analyzer
+            .analyze_file("lib/index.js", commonjs)
+            .errors
+            .is_empty());
+
+        let top_level_await = "const config = await load();\nexport function run() {}";
+        let result = analyzer.analyze_file("lib/index.mjs", top_level_await);
+        assert!(result.errors.is_empty());
+        assert!(result
+            .symbols
+            .iter()
+            .any(|s| s.name == "run" && s.is_exported));
+        assert!(analyzer
+            .analyze_file("lib/main.js", "await main();")
+            .errors
+            .is_empty());
+
+        let shebang = "#!/usr/bin/env node\nconst args = process.argv.slice(2);";
+        assert!(analyzer.analyze_file("bin/cli", shebang).errors.is_empty());
+
+        let await_identifier = "var await = 1;";
+        assert!(!analyzer
+            .analyze_file_as("legacy.js", await_identifier, Some(PackageType::Module))
+            .errors
+            .is_empty());
+        assert!(analyzer
+            .analyze_file_as("legacy.js", await_identifier, Some(PackageType::CommonJs))
+            .errors
+            .is_empty());
+    }
+
+    #[test]
+    fn test_signature_of_single_symbol() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code =
+            "function helper() {}\nexport function login(user: string): boolean { return true; }";
+
+        let signature = analyzer.signature_of("src/auth.ts", code, "login").unwrap();
+        assert_eq!(signature.symbol_name, "login");
+        assert!(signature
+            .signature_text
+            .contains("login(user: string): boolean"));
+        let full = analyzer.analyze_file("src/auth.ts", code);
+        let login = full.symbols.iter().find(|s| s.name == "login").unwrap();
+        let expected = SignatureHasher::new()
+            .hash(analyzer.extract_signature(login))
+            .hash;
+        assert_eq!(signature.hash, Some(expected));
+        assert!(analyzer
+            .signature_of("src/auth.ts", code, "logout")
+            .is_none());
+    }
+
+    #[test]
+    fn test_ignore_directives() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code = "// sintesi-ignore-next-symbol: deprecated\nexport function old() {}\nexport function current() {}";
+
+        let result = analyzer.analyze_file("src/api.ts", code);
+        let names: Vec<_> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["current"]);
+        assert_eq!(result.ignored[0].code_ref(), "src/api.ts#old");
+        assert_eq!(result.ignored[0].reason.as_deref(), Some("deprecated"));
+
+        let result = analyzer.analyze_file(
+            "db/schema.sql",
+            "-- sintesi-ignore-file\nCREATE TABLE a (id int);",
+        );
+        assert!(result.symbols.is_empty());
+        assert_eq!(result.ignored[0].code_ref(), "db/schema.sql");
+    }
+
+    #[test]
+    fn test_deprecated_jsdoc() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code = "/** @deprecated Use login2 */
+export function login() {}
+/**
+ * Current login.
+ */
+export function login2() {}
+/** @deprecated */
+export const LIMIT = 1, MAX = 2;
+/** @deprecated */
+export default class {}";
+
+        let result = analyzer.analyze_file("src/api.ts", code);
+        let deprecated: Vec<_> = result
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.deprecated))
+            .collect();
+        assert_eq!(
+            deprecated,
+            [
+                ("login", true),
+                ("login2", false),
+                ("LIMIT", true),
+                ("MAX", false),
+                ("default", true)
+            ]
+        );
+        assert!(analyzer.extract_signature(&result.symbols[0]).deprecated);
+    }
+
+    #[test]
+    fn test_release_tags() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code = "/**
+ * Stable entry point.
+ * @public
+ */
+export function login() {}
+/** @beta */
+export interface Session {}
+/** Alpha API. @alpha */
+export const flags = {};
+export function logout() {}";
+
+        let result = analyzer.analyze_file("src/api.ts", code);
+        let tags: Vec<_> = result.symbols.iter().map(|s| s.release_tag).collect();
+        assert_eq!(
+            tags,
+            [
+                Some(ReleaseTag::Public),
+                Some(ReleaseTag::Beta),
+                Some(ReleaseTag::Experimental),
+                None
+            ]
+        );
+        let signature = analyzer.extract_signature(&result.symbols[1]);
+        assert_eq!(signature.release_tag.as_deref(), Some("beta"));
+    }
+
+    #[test]
+    fn test_qualified_nested_names() {
+        let analyzer = AstAnalyzerInternal::with_options(AnalyzerOptions::new().deep(true));
+        let code = "export namespace Outer { export function helper() {} }
+export class Api { run() { function helper() {} } }
+export const setup = () => { const helper = 1; };";
+
+        let result = analyzer.analyze_file("src/a.ts", code);
+        let names: Vec<_> = result
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.is_exported))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("Outer.helper", true),
+                ("Api", true),
+                ("Api.run.helper", false),
+                ("setup", true),
+                ("setup.helper", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export_lists() {
+        let analyzer = AstAnalyzerInternal::with_options(AnalyzerOptions::new().deep(true));
+        let code = "function fmt(d: Date) { const pad = 2; }
+interface Options {}
+export function parse(s: string) {}
+function internal() {}
+export { fmt as format, Options, parse as parseDate };
+export { login as signIn } from './auth';";
+
+        let result = analyzer.analyze_file("src/date.ts", code);
+        let names: Vec<_> = result
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.is_exported))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("format", true),
+                ("format.pad", false),
+                ("Options", true),
+                ("parse", true),
+                ("parseDate", true),
+                ("internal", false),
+            ]
+        );
+        // Signatures keep the declaration as written
+        assert_eq!(result.symbols[0].signature, "function fmt(d: Date)");
+    }
+
+    #[test]
+    fn test_commonjs_exports() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code = "function login(user) { return true; }
+function signOut() {}
+function internal() {}
+/** @deprecated */
+exports.VERSION = '1.0';
+module.exports.Store = class { get(key) {} };
+exports.reset = (force) => {};
+module.exports = { login, logout: signOut, refresh(token) {}, ...require('./extra') };";
+
+        let result = analyzer.analyze_file("lib/auth.cjs", code);
+        let symbols: Vec<_> = result
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.signature.as_str(), s.is_exported))
+            .collect();
+        assert_eq!(
+            symbols,
+            [
+                ("login", "function login(user)", true),
+                ("logout", "function signOut()", true),
+                ("internal", "function internal()", false),
+                ("VERSION", "\"1.0\"", true),
+                ("Store", "class Store { get(key) }", true),
+                ("reset", "(force) =>", true),
+                ("refresh", "function(token)", true),
+            ]
+        );
+        assert!(result.symbols[3].deprecated);
+
+        let result =
+            analyzer.analyze_file("lib/login.js", "module.exports = function login(user) {};");
+        assert_eq!(result.symbols[0].name, "login");
+        let result = analyzer.analyze_file("lib/login.js", "module.exports = (user) => {};");
+        assert_eq!(result.symbols[0].name, "default");
+        // Only top-level assignments export
+        let deep = AstAnalyzerInternal::with_options(AnalyzerOptions::new().deep(true));
+        let code = "function setup() { exports.inner = 1; }";
+        let result = deep.analyze_file("lib/setup.js", code);
+        assert!(result.symbols.iter().all(|s| !s.name.contains("inner")));
+    }
+
+    #[test]
+    fn test_symbol_locations() {
+        let analyzer = AstAnalyzerInternal::new();
+        let code = "import { x } from './x';
+
+export function hello(name: string) {
+    return x;
+}
+export type Id = string;
+";
+        let result = analyzer.analyze_code(code);
+
+        let hello = result.symbols[0].location.unwrap();
+        assert_eq!((hello.start_line, hello.end_line), (2, 4));
+        assert_eq!(
+            &code[hello.start_offset as usize..hello.end_offset as usize],
+            "function hello(name: string) {\n    return x;\n}"
+        );
+        let id = result.symbols[1].location.unwrap();
+        assert_eq!((id.start_line, id.end_line), (5, 5));
+
+        let signature = analyzer.extract_signature(&result.symbols[0]);
+        assert_eq!(signature.location, Some(hello));
+    }
+
+    #[test]
+    fn test_duplicate_names_suffix() {
+        let code = "[1].map(() => { function helper() {} });
+[2].map(() => { function helper() {} });";
+
+        let deep = AnalyzerOptions::new().deep(true);
+        let kept = AstAnalyzerInternal::with_options(deep.clone()).analyze_file("src/a.ts", code);
+        let names: Vec<_> = kept.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["helper", "helper"]);
+
+        let suffixed =
+            AstAnalyzerInternal::with_options(deep.duplicate_names(DuplicateNames::Suffix))
+                .analyze_file("src/a.ts", code);
+        let names: Vec<_> = suffixed.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["helper", "helper~2"]);
+    }
+
+    #[test]
+    fn test_top_level_only_by_default() {
+        let code = "export function handler() {\n  // sintesi-ignore-next-symbol\n  const local = 1;\n  [1].forEach(function each() {});\n}\nexport class Api { run = () => { function inner() {} }; }\nexport function after() {}";
+
+        let result = AstAnalyzerInternal::new().analyze_file("src/a.ts", code);
+        let names: Vec<_> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["handler", "Api", "after"]);
+        assert!(result.ignored.is_empty());
+
+        let deep = AstAnalyzerInternal::with_options(AnalyzerOptions::new().deep(true))
+            .analyze_file("src/a.ts", code);
+        let names: Vec<_> = deep.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["handler", "handler.each", "Api", "Api.run.inner", "after"]
+        );
+        assert_eq!(deep.ignored[0].code_ref(), "src/a.ts#handler.local");
+    }
+
+    #[test]
+    fn test_max_file_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = "export function a() {}\n".repeat(100);
+        std::fs::write(dir.path().join("bundle.js"), &bundle).unwrap();
+        std::fs::write(dir.path().join("auth.ts"), "export function login() {}").unwrap();
+        let files = [dir.path().join("auth.ts"), dir.path().join("bundle.js")];
+
+        let analyzer =
+            AstAnalyzerInternal::with_options(AnalyzerOptions::new().max_file_size(1024));
+        let result = analyzer.analyze_files(dir.path(), &files);
+        let names: Vec<_> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["login"]);
+        assert_eq!(
+            result.skipped,
+            [SkippedFile {
+                file_path: "bundle.js".to_string(),
+                reason: SkipReason::TooLarge {
+                    size: bundle.len(),
+                    limit: 1024
+                },
+            }]
+        );
+        // The default limit leaves ordinary files alone
+        let ordinary = AstAnalyzerInternal::new().analyze_file("bundle.js", &bundle);
+        assert!(ordinary.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_generated_files_skipped_by_default() {
+        let code = "// @generated by protoc-gen-ts\nexport interface User { id: string }\n";
+        let result = AstAnalyzerInternal::new().analyze_file("src/user_pb.ts", code);
+        assert!(result.symbols.is_empty());
+        assert_eq!(result.skipped[0].reason, SkipReason::Generated);
+
+        let analyzer =
+            AstAnalyzerInternal::with_options(AnalyzerOptions::new().include_generated(true));
+        let result = analyzer.analyze_file("src/user_pb.ts", code);
+        assert_eq!(result.symbols[0].name, "User");
+        assert!(result.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_include_private() {
+        let code = "function hashToken(token: string): string { return token; }\n\
+                    export function login(token: string): void {}";
+        let listed = |analyzer: AstAnalyzerInternal| -> Vec<String> {
+            let result = analyzer.analyze_code(code);
+            result
+                .symbols
+                .into_iter()
+                .filter(|s| analyzer.is_listed(s))
+                .map(|s| s.name)
+                .collect()
+        };
+        assert_eq!(listed(AstAnalyzerInternal::new()), ["login"]);
+        let analyzer =
+            AstAnalyzerInternal::with_options(AnalyzerOptions::new().include_private(true));
+        assert_eq!(listed(analyzer), ["hashToken", "login"]);
+    }
+
+    #[test]
+    fn test_anonymous_default_exports() {
+        let analyzer = AstAnalyzerInternal::new();
+        let cases = [
+            (
+                "src/format_date.ts",
+                "export default function (d: Date): string { return ''; }",
+                SymbolType::Function,
+                "formatDate",
+            ),
+            (
+                "src/date-picker/index.tsx",
+                "export default class { open() {} }",
+                SymbolType::Class,
+                "DatePicker",
+            ),
+            (
+                "src/handler.ts",
+                "export default async (req: Request) => new Response();",
+                SymbolType::Function,
+                "handler",
+            ),
+            (
+                "vite.config.ts",
+                "export default defineConfig({ root: 'src' });",
+                SymbolType::Const,
+                "vite",
+            ),
+        ];
+
+        for (file, code, symbol_type, display_name) in cases {
+            let result = analyzer.analyze_file(file, code);
+            assert_eq!(result.symbols.len(), 1, "{}", file);
+            let symbol = &result.symbols[0];
+            assert_eq!(symbol.name, DEFAULT_EXPORT);
+            assert_eq!(symbol.symbol_type, symbol_type);
+            assert!(symbol.is_exported);
+            assert_eq!(symbol.display_name().as_deref(), Some(display_name));
+        }
+
+        let reexport =
+            analyzer.analyze_file("src/a.ts", "function login() {}\nexport default login;");
+        let names: Vec<_> = reexport.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["login"]);
+        let named = analyzer.analyze_file("src/a.ts", "export default function logout() {}");
+        assert_eq!(named.symbols[0].name, "logout");
+        assert_eq!(named.symbols[0].display_name(), None);
+    }
 }
@@ -0,0 +1,355 @@
+//! Pluggable language backends
+//!
+//! Languages outside the Oxc analyzer can be added as a `LanguageBackend`:
+//! given a file, it returns the same `AnalysisResult` the TypeScript analyzer
+//! does, so its symbols are hashed, anchored (`code_ref="pkg/auth.go#Login"`),
+//! and checked for drift like any other. The analyzer routes files to a
+//! backend by extension; backends registered with
+//! `AstAnalyzerInternal::with_backend` take precedence over the built-in
+//! ones.
+//!
+//! The built-in backends parse with tree-sitter grammars:
+//! - Go (`ast::go`): functions, methods, types, constants, and variables
+//! - Java (`ast::java`): classes, interfaces, enums, records, and their
+//!   methods, constructors, and fields
+//!
+//! Signatures are the declaration's tokens without comments or bodies, spaced
+//! as written except that runs of whitespace become one space, parentheses,
+//! square brackets, and type argument brackets have no inner padding, braces
+//! have one space around and inside them (`struct { Name string }`, `{}`),
+//! commas are followed by one space, and trailing commas are dropped.
+//! Re-wrapping a parameter list or a struct is therefore not drift.
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::diagnostics::Diagnostic;
+use super::go::GoBackend;
+use super::java::JavaBackend;
+use crate::types::{SymbolLocation, SymbolType, TypeParam};
+use std::fmt;
+use std::path::Path;
+use tree_sitter::{Language, Node, Parser, Tree};
+
+/// Symbol extraction for one language
+pub trait LanguageBackend: fmt::Debug + Send + Sync {
+    /// Language name, for messages (`Go`, `Java`)
+    fn name(&self) -> &str;
+
+    /// Extensions of the files the backend analyzes, without the dot
+    fn extensions(&self) -> &[&str];
+
+    /// Extract the symbols of a file
+    fn analyze(&self, file_path: &str, content: &str) -> AnalysisResult;
+}
+
+/// Backends every analyzer has
+pub static BUILTIN_BACKENDS: &[&dyn LanguageBackend] = &[&GoBackend, &JavaBackend];
+
+/// Whether a backend analyzes a file, by its extension
+pub fn handles(backend: &dyn LanguageBackend, file_path: &str) -> bool {
+    Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| backend.extensions().contains(&ext))
+}
+
+/// Parse a file with a tree-sitter grammar
+///
+/// The tree is recovered around syntax errors; they are returned as
+/// diagnostics (see `diagnostics::error_messages` for `errors`).
+pub(crate) fn parse(
+    language: Language,
+    file_path: &str,
+    content: &str,
+) -> (Option<Tree>, Vec<Diagnostic>) {
+    let mut parser = Parser::new();
+    if let Err(error) = parser.set_language(&language) {
+        let diagnostic = Diagnostic::error(file_path, content, error.to_string(), 0..0);
+        return (None, vec![diagnostic]);
+    }
+    let Some(tree) = parser.parse(content, None) else {
+        let diagnostic = Diagnostic::error(file_path, content, "parsing was cancelled", 0..0);
+        return (None, vec![diagnostic]);
+    };
+    let mut diagnostics = Vec::new();
+    collect_errors(tree.root_node(), file_path, content, &mut diagnostics);
+    (Some(tree), diagnostics)
+}
+
+fn collect_errors(node: Node, file_path: &str, content: &str, out: &mut Vec<Diagnostic>) {
+    if node.is_missing() {
+        let message = format!("missing `{}`", node.kind());
+        out.push(Diagnostic::error(
+            file_path,
+            content,
+            message,
+            node.byte_range(),
+        ));
+    } else if node.is_error() {
+        let text = text(node, content);
+        let token = text.split_whitespace().next().unwrap_or("");
+        let message = format!("unexpected `{}`", token);
+        out.push(Diagnostic::error(
+            file_path,
+            content,
+            message,
+            node.byte_range(),
+        ));
+    } else if node.has_error() {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            collect_errors(child, file_path, content, out);
+        }
+    }
+}
+
+/// Source text of a node
+pub(crate) fn text<'s>(node: Node, content: &'s str) -> &'s str {
+    &content[node.byte_range()]
+}
+
+/// Source text of a named field of a node
+pub(crate) fn field<'s>(node: Node, name: &str, content: &'s str) -> Option<&'s str> {
+    node.child_by_field_name(name)
+        .map(|child| text(child, content))
+}
+
+/// Canonical signature of a node's tokens before byte offset `end`
+///
+/// With `newline_separates` (Go), a line break after a token that can end a
+/// statement becomes `;`, as the Go lexer reads it, so multi-line struct and
+/// interface types keep their member boundaries.
+pub(crate) fn render(node: Node, content: &str, end: usize, newline_separates: bool) -> String {
+    let mut tokens = Vec::new();
+    leaves(node, end, &mut tokens);
+    let mut out = String::new();
+    let mut prev: Option<(Node, &str)> = None;
+    for token in &tokens {
+        let word = text(*token, content);
+        let gap = prev.map_or("", |(prev, _)| {
+            &content[prev.end_byte()..token.start_byte()]
+        });
+        let prev_word = prev.map_or("", |(_, word)| word);
+        let after_open = prev.is_some_and(|(prev, word)| opens(prev, word));
+        let closing = closes(*token, word);
+        if newline_separates && gap.contains('\n') && !closing && ends_statement(&out) {
+            out.push(';');
+        }
+        match word {
+            "," | ";" => out.push_str(word),
+            _ if closing => {
+                if out.ends_with(',') || out.ends_with(';') {
+                    out.pop();
+                }
+                if word == "}" && prev_word != "{" {
+                    out.push(' ');
+                }
+                out.push_str(word);
+            }
+            _ => {
+                let spaced = match word {
+                    "{" => true,
+                    "<" if opens(*token, word) => {
+                        token.parent().map(|p| p.kind()) != Some("type_arguments")
+                            && !gap.is_empty()
+                    }
+                    _ => matches!(prev_word, "," | ";" | "{") || !gap.is_empty(),
+                };
+                if spaced && !out.is_empty() && !after_open {
+                    out.push(' ');
+                }
+                out.push_str(word);
+            }
+        }
+        prev = Some((*token, word));
+    }
+    out.trim_end_matches(';').to_string()
+}
+
+/// Whether a token opens brackets without inner padding: `(`, `[`, and the
+/// `<` of type arguments and parameters
+fn opens(token: Node, word: &str) -> bool {
+    matches!(word, "(" | "[") || (word == "<" && is_type_bracket(token))
+}
+
+/// Whether a token closes brackets: `)`, `]`, `}`, and the `>` of type
+/// arguments and parameters
+fn closes(token: Node, word: &str) -> bool {
+    matches!(word, ")" | "]" | "}") || (word == ">" && is_type_bracket(token))
+}
+
+fn is_type_bracket(token: Node) -> bool {
+    token
+        .parent()
+        .is_some_and(|parent| matches!(parent.kind(), "type_arguments" | "type_parameters"))
+}
+
+/// Type parameters in a node's `type_parameters` field: Go
+/// (`[K comparable, V any]`, one per name) and Java (`<T extends A & B>`)
+///
+/// Constraints are rendered like signatures; neither language has defaults.
+pub(crate) fn type_params(node: Node, content: &str) -> Vec<TypeParam> {
+    let Some(list) = node.child_by_field_name("type_parameters") else {
+        return Vec::new();
+    };
+    let mut params = Vec::new();
+    let mut cursor = list.walk();
+    for param in list.named_children(&mut cursor) {
+        match param.kind() {
+            "type_parameter_declaration" => {
+                let constraint = param
+                    .child_by_field_name("type")
+                    .map(|ty| render(ty, content, ty.end_byte(), true));
+                let mut cursor = param.walk();
+                for name in param.children_by_field_name("name", &mut cursor) {
+                    params.push(TypeParam {
+                        constraint: constraint.clone(),
+                        ..TypeParam::new(text(name, content))
+                    });
+                }
+            }
+            "type_parameter" => {
+                let mut cursor = param.walk();
+                let mut children = param.named_children(&mut cursor);
+                let Some(name) = children.find(|child| child.kind() == "type_identifier") else {
+                    continue;
+                };
+                let constraint = children
+                    .find(|child| child.kind() == "type_bound")
+                    .map(|bound| {
+                        let bound = render(bound, content, bound.end_byte(), false);
+                        bound.trim_start_matches("extends ").to_string()
+                    });
+                params.push(TypeParam {
+                    constraint,
+                    ..TypeParam::new(text(name, content))
+                });
+            }
+            _ => {}
+        }
+    }
+    params
+}
+
+/// Whether a Go line ending after `out` ends a statement
+fn ends_statement(out: &str) -> bool {
+    !out.is_empty() && !out.ends_with(['(', '[', '{', ',', ';', '.', '=', '|', '&', '+', '-'])
+}
+
+/// Leaf tokens of a node starting before `end`, without comments
+fn leaves<'t>(node: Node<'t>, end: usize, out: &mut Vec<Node<'t>>) {
+    if node.start_byte() >= end || node.is_extra() {
+        return;
+    }
+    if node.child_count() == 0 {
+        out.push(node);
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        leaves(child, end, out);
+    }
+}
+
+/// Comments directly above a node (no blank line in between), top to bottom
+pub(crate) fn comments_before<'s>(node: Node, content: &'s str) -> Vec<&'s str> {
+    let mut comments = Vec::new();
+    let mut line = node.start_position().row;
+    let mut sibling = node.prev_sibling();
+    while let Some(comment) = sibling.filter(|s| s.is_extra() && s.end_position().row + 1 >= line) {
+        comments.push(text(comment, content));
+        line = comment.start_position().row;
+        sibling = comment.prev_sibling();
+    }
+    comments.reverse();
+    comments
+}
+
+/// A symbol declared by `node`
+pub(crate) fn symbol(
+    file_path: &str,
+    node: Node,
+    name: String,
+    symbol_type: SymbolType,
+    signature: String,
+    is_exported: bool,
+    deprecated: bool,
+) -> SymbolInfo {
+    SymbolInfo {
+        name,
+        symbol_type,
+        signature,
+        is_exported,
+        file_path: file_path.to_string(),
+        deprecated,
+        release_tag: None,
+        location: Some(SymbolLocation {
+            start_line: node.start_position().row as u32,
+            end_line: node.end_position().row as u32,
+            start_offset: node.start_byte() as u32,
+            end_offset: node.end_byte() as u32,
+        }),
+        type_params: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::AstAnalyzerInternal;
+    use std::sync::Arc;
+
+    /// Backend listing one function per `fn name` line
+    #[derive(Debug)]
+    struct LinesBackend;
+
+    impl LanguageBackend for LinesBackend {
+        fn name(&self) -> &str {
+            "Lines"
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["lines", "go"]
+        }
+
+        fn analyze(&self, file_path: &str, content: &str) -> AnalysisResult {
+            let symbols = content
+                .lines()
+                .filter_map(|line| line.strip_prefix("fn "))
+                .map(|name| SymbolInfo {
+                    name: name.to_string(),
+                    symbol_type: SymbolType::Function,
+                    signature: format!("fn {}", name),
+                    is_exported: true,
+                    file_path: file_path.to_string(),
+                    deprecated: false,
+                    release_tag: None,
+                    location: None,
+                    type_params: Vec::new(),
+                })
+                .collect();
+            AnalysisResult {
+                symbols,
+                ..AnalysisResult::default()
+            }
+        }
+    }
+
+    #[test]
+    fn test_routes_files_to_backends() {
+        let analyzer = AstAnalyzerInternal::new();
+        let result = analyzer.analyze_file("pkg/auth.go", "package auth\n\nfunc Login() {}\n");
+        assert_eq!(result.symbols[0].signature, "func Login()");
+        let result = analyzer.analyze_file("Auth.java", "public class Auth {}\n");
+        assert_eq!(result.symbols[0].signature, "public class Auth");
+
+        // Registered backends come first
+        let analyzer = AstAnalyzerInternal::new().with_backend(Arc::new(LinesBackend));
+        let result = analyzer.analyze_file("a.lines", "fn login\nfn logout\n");
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["login", "logout"]);
+        assert!(analyzer
+            .analyze_file("a.go", "package a\n")
+            .symbols
+            .is_empty());
+    }
+}
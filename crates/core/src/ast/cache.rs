@@ -0,0 +1,380 @@
+//! Incremental analysis cache
+//!
+//! Re-parsing every file of a large monorepo on each run is slow. An
+//! `AstCache` remembers the symbols extracted from each file along with a
+//! hash of its content, and is persisted between runs
+//! (`.sintesi/ast-cache.json`). An analyzer given a cache (see
+//! `AstAnalyzerInternal::with_cache`) skips parsing files whose hash is
+//! unchanged.
+//!
+//! The hash also covers the analyzer options changing extraction (depth,
+//! decorators, React components, duplicate naming) and the file's package
+//! type, and a cache written by another version of the crate is discarded, so
+//! results are never reused across analyzers extracting differently. Symbol
+//! filters apply after the cache, so changing them does not invalidate it.
+//! Files with parse errors are not cached.
+//!
+//! In huge monorepos the cache can be sharded by workspace package
+//! (`AstCache::load_sharded`): one file per package under
+//! `.sintesi/ast-cache/`, each loaded the first time a file of its package
+//! is looked up. A job analyzing one package only loads and saves its shard.
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::ignore::IgnoredItem;
+use crate::context::{package_of, WorkspacePackage, ROOT_PACKAGE};
+use crate::error::Error;
+use crate::sandbox::guard_write;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Default location of the cache, relative to the project root
+pub const AST_CACHE_FILE: &str = ".sintesi/ast-cache.json";
+
+/// Default directory of a sharded cache, relative to the project root
+pub const AST_CACHE_DIR: &str = ".sintesi/ast-cache";
+
+/// Revision of the cached data, bumped whenever extraction output changes
+/// within a crate version (e.g. how signatures are printed)
+const CACHE_FORMAT: u32 = 6;
+
+/// Cached extraction of one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    /// Hash of the content and the extraction settings
+    hash: String,
+    symbols: Vec<SymbolInfo>,
+    ignored: Vec<IgnoredItem>,
+}
+
+/// On-disk form of the cache
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    /// Crate version that wrote the cache
+    version: String,
+    /// `CACHE_FORMAT` of the writer
+    #[serde(default)]
+    format: u32,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Cached files of one shard, by file path
+type Shard = HashMap<String, CacheEntry>;
+
+/// Where the shards of a sharded cache are loaded from
+#[derive(Debug)]
+struct Sharding {
+    dir: PathBuf,
+    packages: Vec<WorkspacePackage>,
+}
+
+/// Symbols extracted from each file, by file path
+///
+/// Shared by reference between threads; lookups and inserts lock internally.
+#[derive(Debug, Default)]
+pub struct AstCache {
+    /// Loaded shards, by package path (one shard keyed `""` when unsharded)
+    shards: Mutex<HashMap<String, Shard>>,
+    sharding: Option<Sharding>,
+    /// Files looked up since the cache was loaded
+    used: Mutex<HashSet<String>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl AstCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a cache from a JSON file
+    ///
+    /// A missing, unreadable, or corrupt file, or one written by another
+    /// version of the crate or in another format, yields an empty cache: it
+    /// is only ever a shortcut.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        Self {
+            shards: Mutex::new(HashMap::from([(String::new(), read_shard(path.as_ref()))])),
+            ..Self::default()
+        }
+    }
+
+    /// Open a cache sharded by workspace package, stored in `dir`
+    ///
+    /// Nothing is read yet: the shard of a package is loaded (like `load`)
+    /// when a file of the package is first looked up. Files outside every
+    /// package share the root shard.
+    pub fn load_sharded(dir: impl Into<PathBuf>, packages: Vec<WorkspacePackage>) -> Self {
+        Self {
+            sharding: Some(Sharding {
+                dir: dir.into(),
+                packages,
+            }),
+            ..Self::default()
+        }
+    }
+
+    /// Save the cache as JSON, creating parent directories
+    ///
+    /// A sharded cache is saved to the directory `path`, one file per loaded
+    /// shard; shards never loaded are left as they are. Fails with
+    /// `Error::ReadOnly` in read-only mode and `Error::PathNotAllowed` outside
+    /// the write sandbox, before writing anything.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let shards = self.shards.lock().unwrap();
+        let empty = Shard::new();
+        let files: Vec<(PathBuf, &Shard)> = match &self.sharding {
+            Some(_) => shards
+                .iter()
+                .map(|(key, entries)| (path.join(shard_file(key)), entries))
+                .collect(),
+            None => vec![(path.to_path_buf(), shards.get("").unwrap_or(&empty))],
+        };
+        for (file, _) in &files {
+            guard_write("save analysis cache", file)?;
+        }
+        files
+            .iter()
+            .try_for_each(|(file, entries)| write_shard(file, entries))
+    }
+
+    /// Drop the entries of files not looked up since the cache was loaded
+    /// (deleted or no longer analyzed files)
+    pub fn prune(&self) {
+        let used = self.used.lock().unwrap();
+        for entries in self.shards.lock().unwrap().values_mut() {
+            entries.retain(|file_path, _| used.contains(file_path));
+        }
+    }
+
+    /// Number of cached files in the loaded shards
+    pub fn len(&self) -> usize {
+        self.shards.lock().unwrap().values().map(HashMap::len).sum()
+    }
+
+    /// Number of loaded shards
+    pub fn loaded_shards(&self) -> usize {
+        self.shards.lock().unwrap().len()
+    }
+
+    /// Whether no file is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Files whose cached symbols were reused
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Files that had to be parsed
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Run `f` on the shard of a file, loading it first if needed
+    fn with_shard<R>(&self, file_path: &str, f: impl FnOnce(&mut Shard) -> R) -> R {
+        let key = self.sharding.as_ref().map_or("", |sharding| {
+            package_of(&sharding.packages, file_path).map_or("", |p| p.path.as_str())
+        });
+        let mut shards = self.shards.lock().unwrap();
+        let shard = shards.entry(key.to_string()).or_insert_with(|| {
+            self.sharding
+                .as_ref()
+                .map(|sharding| read_shard(&sharding.dir.join(shard_file(key))))
+                .unwrap_or_default()
+        });
+        f(shard)
+    }
+
+    /// Cached result of a file, or the result of `extract`, cached
+    ///
+    /// `settings` describes everything besides the content that changes what
+    /// `extract` returns.
+    pub(crate) fn get_or_extract(
+        &self,
+        file_path: &str,
+        content: &str,
+        settings: &str,
+        extract: impl FnOnce() -> AnalysisResult,
+    ) -> AnalysisResult {
+        let mut hasher = Sha256::new();
+        hasher.update(settings.as_bytes());
+        hasher.update([0]);
+        hasher.update(content.as_bytes());
+        let hash = format!("{:x}", hasher.finalize());
+        self.used.lock().unwrap().insert(file_path.to_string());
+
+        let cached = self.with_shard(file_path, |entries| {
+            entries
+                .get(file_path)
+                .filter(|entry| entry.hash == hash)
+                .map(|entry| AnalysisResult {
+                    symbols: entry.symbols.clone(),
+                    ignored: entry.ignored.clone(),
+                    ..AnalysisResult::default()
+                })
+        });
+        if let Some(result) = cached {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return result;
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let result = extract();
+        self.with_shard(file_path, |entries| {
+            if result.errors.is_empty() && result.diagnostics.is_empty() {
+                entries.insert(
+                    file_path.to_string(),
+                    CacheEntry {
+                        hash,
+                        symbols: result.symbols.clone(),
+                        ignored: result.ignored.clone(),
+                    },
+                );
+            } else {
+                entries.remove(file_path);
+            }
+        });
+        result
+    }
+}
+
+/// Entries of a cache file (empty if it is missing, corrupt, or stale)
+fn read_shard(path: &Path) -> Shard {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+        .filter(|file| file.version == env!("CARGO_PKG_VERSION") && file.format == CACHE_FORMAT)
+        .map(|file| file.entries)
+        .unwrap_or_default()
+}
+
+/// File name of the shard of a package path (`packages/core` =>
+/// `packages%2Fcore.json`, the root => `(root).json`)
+fn shard_file(key: &str) -> String {
+    match key {
+        "" => format!("{}.json", ROOT_PACKAGE),
+        key => format!("{}.json", key.replace('%', "%25").replace('/', "%2F")),
+    }
+}
+
+/// Write cache entries as JSON, creating parent directories
+fn write_shard(path: &Path, entries: &Shard) -> Result<(), Error> {
+    let file = CacheFile {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        format: CACHE_FORMAT,
+        entries: entries.clone(),
+    };
+    let write = || -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string(&file).map_err(std::io::Error::other)?;
+        fs::write(path, json)
+    };
+    write().map_err(|e| {
+        Error::from_reason(format!(
+            "Failed to write AST cache {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{AnalyzerOptions, AstAnalyzerInternal};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_skips_unchanged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AST_CACHE_FILE);
+        let cache = Arc::new(AstCache::load(&path));
+        let analyzer = AstAnalyzerInternal::new().with_cache(cache.clone());
+
+        let code = "export function login(user: string): void {}\n";
+        let first = analyzer.analyze_file("src/auth.ts", code);
+        let second = analyzer.analyze_file("src/auth.ts", code);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert_eq!(first.symbols[0].signature, second.symbols[0].signature);
+
+        analyzer.analyze_file("src/auth.ts", "export function logout(): void {}\n");
+        analyzer.analyze_file("src/broken.ts", "export function (");
+        assert_eq!((cache.hits(), cache.misses()), (1, 3));
+        cache.save(&path).unwrap();
+
+        // A fresh run reuses the saved symbols, unless the options differ
+        let cache = Arc::new(AstCache::load(&path));
+        assert_eq!(cache.len(), 1);
+        let symbols = AstAnalyzerInternal::new()
+            .with_cache(cache.clone())
+            .analyze_file("src/auth.ts", "export function logout(): void {}\n")
+            .symbols;
+        assert_eq!(symbols[0].name, "logout");
+        assert_eq!(cache.hits(), 1);
+        AstAnalyzerInternal::with_options(AnalyzerOptions::new().deep(true))
+            .with_cache(cache.clone())
+            .analyze_file("src/auth.ts", "export function logout(): void {}\n");
+        assert_eq!(cache.misses(), 1);
+
+        // Files not analyzed since loading are pruned
+        let cache = AstCache::load(&path);
+        cache.prune();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_shards_by_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let shards = dir.path().join(AST_CACHE_DIR);
+        let packages = vec![
+            WorkspacePackage {
+                name: "@acme/api".to_string(),
+                path: "packages/api".to_string(),
+            },
+            WorkspacePackage {
+                name: "@acme/web".to_string(),
+                path: "packages/web".to_string(),
+            },
+        ];
+        let code = "export function login(user: string): void {}\n";
+        let cache = Arc::new(AstCache::load_sharded(&shards, packages.clone()));
+        let analyzer = AstAnalyzerInternal::new().with_cache(cache.clone());
+        analyzer.analyze_file("packages/api/src/auth.ts", code);
+        analyzer.analyze_file("packages/web/src/auth.ts", code);
+        analyzer.analyze_file("scripts/release.ts", code);
+        cache.save(&shards).unwrap();
+        let mut files: Vec<String> = fs::read_dir(&shards)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            ["(root).json", "packages%2Fapi.json", "packages%2Fweb.json"]
+        );
+
+        // Only the shards of the files looked up are loaded, and saved back
+        let cache = Arc::new(AstCache::load_sharded(&shards, packages));
+        assert_eq!(cache.loaded_shards(), 0);
+        AstAnalyzerInternal::new()
+            .with_cache(cache.clone())
+            .analyze_file("packages/api/src/auth.ts", code);
+        assert_eq!((cache.hits(), cache.loaded_shards()), (1, 1));
+        fs::remove_dir_all(&shards).unwrap();
+        cache.prune();
+        cache.save(&shards).unwrap();
+        assert!(shards.join("packages%2Fapi.json").exists());
+        assert!(!shards.join("packages%2Fweb.json").exists());
+    }
+}
@@ -0,0 +1,477 @@
+//! Configuration file analyzer
+//!
+//! This module turns configuration surfaces into symbols so documentation can
+//! be anchored to individual keys:
+//!
+//! - **JSON Schema** documents (`*.schema.json` or any JSON/YAML file with a
+//!   `$schema` pointing at json-schema.org): every property becomes a
+//!   `ConfigKey` symbol named by its dotted path, and the document itself
+//!   becomes a `Schema` symbol listing its top-level keys.
+//! - **`package.json`**: `scripts` and `bin` entries.
+//! - **`tsconfig*.json`**: `compilerOptions` entries (comments and trailing
+//!   commas are tolerated, as `tsc` does).
+//!
+//! Container symbols list their child keys, so adding or removing a key
+//! drifts the container while changing a value drifts the key itself.
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::diagnostics::Diagnostic;
+use crate::types::SymbolType;
+use serde_json::Value;
+use std::path::Path;
+
+/// Sections of `package.json` that are tracked as configuration keys
+const PACKAGE_JSON_SECTIONS: &[&str] = &["scripts", "bin"];
+
+/// Sections of `tsconfig.json` that are tracked as configuration keys
+const TSCONFIG_SECTIONS: &[&str] = &["compilerOptions"];
+
+/// Kind of configuration document recognized by the analyzer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigKind {
+    /// A JSON Schema describing configuration keys
+    JsonSchema,
+    /// An npm `package.json`
+    PackageJson,
+    /// A TypeScript `tsconfig.json` (or `tsconfig.*.json`)
+    TsConfig,
+}
+
+/// Detect whether a file is a configuration document this module understands
+pub fn detect_config_kind(file_path: &str, content: &str) -> Option<ConfigKind> {
+    let file_name = Path::new(file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+
+    if file_name == "package.json" {
+        return Some(ConfigKind::PackageJson);
+    }
+    if file_name.starts_with("tsconfig") && file_name.ends_with(".json") {
+        return Some(ConfigKind::TsConfig);
+    }
+
+    let is_data_file = [".json", ".yaml", ".yml"]
+        .iter()
+        .any(|ext| file_name.ends_with(ext));
+    if file_name.ends_with(".schema.json") || (is_data_file && content.contains("json-schema.org"))
+    {
+        return Some(ConfigKind::JsonSchema);
+    }
+
+    None
+}
+
+/// Analyze a configuration document and extract its keys as symbols
+///
+/// # Arguments
+/// * `file_path` - Path of the configuration file
+/// * `content` - Raw file content
+/// * `kind` - The kind of document, as returned by [`detect_config_kind`]
+pub fn analyze_config(file_path: &str, content: &str, kind: ConfigKind) -> AnalysisResult {
+    let document = match parse_document(file_path, content) {
+        Ok(doc) => doc,
+        Err(diagnostic) => {
+            return AnalysisResult {
+                symbols: Vec::new(),
+                errors: vec![format!("Parse error: {}", diagnostic.message)],
+                diagnostics: vec![*diagnostic],
+                ignored: Vec::new(),
+                skipped: Vec::new(),
+            };
+        }
+    };
+
+    let mut symbols = Vec::new();
+    match kind {
+        ConfigKind::JsonSchema => {
+            let root_name = document
+                .get("title")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| file_stem(file_path));
+            let keys = property_names(&document);
+            symbols.push(symbol(
+                file_path,
+                root_name.clone(),
+                SymbolType::Schema,
+                format!("schema {} {{ {} }}", root_name, keys.join(", ")),
+            ));
+            collect_schema_properties(file_path, "", &document, &mut symbols);
+        }
+        ConfigKind::PackageJson => {
+            collect_sections(file_path, &document, PACKAGE_JSON_SECTIONS, &mut symbols)
+        }
+        ConfigKind::TsConfig => {
+            collect_sections(file_path, &document, TSCONFIG_SECTIONS, &mut symbols)
+        }
+    }
+
+    AnalysisResult {
+        symbols,
+        errors: Vec::new(),
+        diagnostics: Vec::new(),
+        ignored: Vec::new(),
+        skipped: Vec::new(),
+    }
+}
+
+fn parse_document(file_path: &str, content: &str) -> Result<Value, Box<Diagnostic>> {
+    if file_path.ends_with(".yaml") || file_path.ends_with(".yml") {
+        serde_yaml::from_str(content)
+            .map_err(|e| Box::new(Diagnostic::from_yaml(file_path, content, &e)))
+    } else {
+        // Stripping keeps offsets, so errors point into `content`
+        serde_json::from_str(&strip_jsonc(content))
+            .map_err(|e| Box::new(Diagnostic::from_json(file_path, content, &e)))
+    }
+}
+
+fn symbol(file_path: &str, name: String, symbol_type: SymbolType, signature: String) -> SymbolInfo {
+    SymbolInfo {
+        name,
+        symbol_type,
+        signature,
+        is_exported: true,
+        file_path: file_path.to_string(),
+        deprecated: false,
+        release_tag: None,
+        location: None,
+        type_params: Vec::new(),
+    }
+}
+
+fn file_stem(file_path: &str) -> String {
+    let name = Path::new(file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(file_path);
+    name.split('.').next().unwrap_or(name).to_string()
+}
+
+fn property_names(schema: &Value) -> Vec<String> {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|props| props.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Recursively emit one `ConfigKey` per schema property
+fn collect_schema_properties(
+    file_path: &str,
+    prefix: &str,
+    schema: &Value,
+    symbols: &mut Vec<SymbolInfo>,
+) {
+    let Some(props) = schema.get("properties").and_then(Value::as_object) else {
+        return;
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|list| list.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    for (name, prop) in props {
+        let path = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+
+        let mut signature = format!(
+            "key {}{}: {}",
+            path,
+            if required.contains(&name.as_str()) {
+                ""
+            } else {
+                "?"
+            },
+            schema_type(prop)
+        );
+        if let Some(default) = prop.get("default") {
+            signature.push_str(&format!(" = {}", default));
+        }
+        let children = property_names(prop);
+        if !children.is_empty() {
+            signature.push_str(&format!(" {{ {} }}", children.join(", ")));
+        }
+
+        symbols.push(symbol(
+            file_path,
+            path.clone(),
+            SymbolType::ConfigKey,
+            signature,
+        ));
+        collect_schema_properties(file_path, &path, prop, symbols);
+    }
+}
+
+/// Render the type of a schema property (`string`, `"a" | "b"`, `Array<number>`)
+fn schema_type(prop: &Value) -> String {
+    if let Some(reference) = prop.get("$ref").and_then(Value::as_str) {
+        return reference
+            .rsplit('/')
+            .next()
+            .unwrap_or(reference)
+            .to_string();
+    }
+    if let Some(values) = prop.get("enum").and_then(Value::as_array) {
+        return values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+    match prop.get("type") {
+        Some(Value::String(ty)) if ty == "array" => format!(
+            "Array<{}>",
+            prop.get("items")
+                .map(schema_type)
+                .unwrap_or_else(|| "any".to_string())
+        ),
+        Some(Value::String(ty)) => ty.clone(),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        _ => "any".to_string(),
+    }
+}
+
+/// Emit a container symbol plus one `ConfigKey` per entry for each tracked section
+fn collect_sections(
+    file_path: &str,
+    document: &Value,
+    sections: &[&str],
+    symbols: &mut Vec<SymbolInfo>,
+) {
+    for section in sections {
+        match document.get(*section) {
+            Some(Value::Object(entries)) => {
+                let keys: Vec<&str> = entries.keys().map(String::as_str).collect();
+                symbols.push(symbol(
+                    file_path,
+                    section.to_string(),
+                    SymbolType::ConfigKey,
+                    format!("config {} {{ {} }}", section, keys.join(", ")),
+                ));
+                for (key, value) in entries {
+                    let path = format!("{}.{}", section, key);
+                    let signature = format!("config {} = {}", path, value);
+                    symbols.push(symbol(file_path, path, SymbolType::ConfigKey, signature));
+                }
+            }
+            // `"bin": "./cli.js"` shorthand
+            Some(value) => symbols.push(symbol(
+                file_path,
+                section.to_string(),
+                SymbolType::ConfigKey,
+                format!("config {} = {}", section, value),
+            )),
+            None => {}
+        }
+    }
+}
+
+/// Strip `//` and `/* */` comments and trailing commas from JSON-with-comments
+///
+/// Stripped characters become spaces (line breaks are kept), so byte offsets,
+/// lines, and columns are those of `content`.
+fn strip_jsonc(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == '"' {
+            out.push(ch);
+            i += 1;
+            while i < chars.len() {
+                out.push(chars[i]);
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    out.push(chars[i + 1]);
+                    i += 2;
+                    continue;
+                }
+                i += 1;
+                if chars[i - 1] == '"' {
+                    break;
+                }
+            }
+        } else if ch == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                blank(&mut out, chars[i]);
+                i += 1;
+            }
+        } else if ch == '/' && chars.get(i + 1) == Some(&'*') {
+            let end = (i + 2..chars.len())
+                .find(|&j| chars[j] == '*' && chars.get(j + 1) == Some(&'/'))
+                .map_or(chars.len(), |j| j + 2);
+            for &c in &chars[i..end] {
+                blank(&mut out, c);
+            }
+            i = end;
+        } else if ch == ',' {
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+            if matches!(next, Some('}') | Some(']')) {
+                blank(&mut out, ch);
+            } else {
+                out.push(ch);
+            }
+            i += 1;
+        } else {
+            out.push(ch);
+            i += 1;
+        }
+    }
+
+    out
+}
+
+/// Replace a stripped character with spaces of the same byte length
+fn blank(out: &mut String, ch: char) {
+    if ch == '\n' {
+        out.push('\n');
+    } else {
+        out.extend(std::iter::repeat_n(' ', ch.len_utf8()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(result: &'a AnalysisResult, name: &str) -> &'a SymbolInfo {
+        result.symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_detect_config_kind() {
+        assert_eq!(
+            detect_config_kind("pkg/package.json", "{}"),
+            Some(ConfigKind::PackageJson)
+        );
+        assert_eq!(
+            detect_config_kind("tsconfig.build.json", "{}"),
+            Some(ConfigKind::TsConfig)
+        );
+        assert_eq!(
+            detect_config_kind("app.schema.json", "{}"),
+            Some(ConfigKind::JsonSchema)
+        );
+        assert_eq!(
+            detect_config_kind(
+                "config.yaml",
+                "$schema: http://json-schema.org/draft-07/schema#"
+            ),
+            Some(ConfigKind::JsonSchema)
+        );
+        assert_eq!(detect_config_kind("data.json", "{}"), None);
+    }
+
+    #[test]
+    fn test_json_schema_properties() {
+        let schema = r#"{
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "SintesiConfig",
+            "type": "object",
+            "required": ["outputDir"],
+            "properties": {
+                "outputDir": { "type": "string", "default": "docs" },
+                "ai": {
+                    "type": "object",
+                    "properties": {
+                        "provider": { "enum": ["openai", "gemini"] }
+                    }
+                }
+            }
+        }"#;
+        let result = analyze_config("sintesi.schema.json", schema, ConfigKind::JsonSchema);
+
+        assert!(result.errors.is_empty());
+        assert_eq!(
+            find(&result, "SintesiConfig").signature,
+            "schema SintesiConfig { ai, outputDir }"
+        );
+        assert_eq!(
+            find(&result, "outputDir").signature,
+            "key outputDir: string = \"docs\""
+        );
+        assert_eq!(
+            find(&result, "ai").signature,
+            "key ai?: object { provider }"
+        );
+        assert_eq!(
+            find(&result, "ai.provider").signature,
+            "key ai.provider?: \"openai\" | \"gemini\""
+        );
+    }
+
+    #[test]
+    fn test_package_json_scripts() {
+        let pkg = r#"{ "name": "x", "scripts": { "build": "tsc", "test": "vitest" }, "bin": "./cli.js" }"#;
+        let result = analyze_config("package.json", pkg, ConfigKind::PackageJson);
+
+        assert_eq!(
+            find(&result, "scripts").signature,
+            "config scripts { build, test }"
+        );
+        assert_eq!(
+            find(&result, "scripts.build").signature,
+            "config scripts.build = \"tsc\""
+        );
+        assert_eq!(find(&result, "bin").signature, "config bin = \"./cli.js\"");
+        assert!(result
+            .symbols
+            .iter()
+            .all(|s| s.symbol_type == SymbolType::ConfigKey));
+    }
+
+    #[test]
+    fn test_tsconfig_with_comments() {
+        let tsconfig = r#"{
+            // compiler settings
+            "compilerOptions": {
+                "strict": true, /* always */
+                "outDir": "dist",
+            },
+        }"#;
+        let result = analyze_config("tsconfig.json", tsconfig, ConfigKind::TsConfig);
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            find(&result, "compilerOptions").signature,
+            "config compilerOptions { outDir, strict }"
+        );
+        assert_eq!(
+            find(&result, "compilerOptions.strict").signature,
+            "config compilerOptions.strict = true"
+        );
+    }
+
+    #[test]
+    fn test_adding_key_drifts_container() {
+        let before = analyze_config(
+            "package.json",
+            r#"{"scripts":{"a":"x"}}"#,
+            ConfigKind::PackageJson,
+        );
+        let after = analyze_config(
+            "package.json",
+            r#"{"scripts":{"a":"x","b":"y"}}"#,
+            ConfigKind::PackageJson,
+        );
+        assert_ne!(
+            find(&before, "scripts").signature,
+            find(&after, "scripts").signature
+        );
+        assert_eq!(
+            find(&before, "scripts.a").signature,
+            find(&after, "scripts.a").signature
+        );
+    }
+}
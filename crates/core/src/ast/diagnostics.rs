@@ -0,0 +1,248 @@
+//! Structured parse diagnostics
+//!
+//! Oxc reports parse errors with byte-offset labels. This module converts
+//! them into line/column spans with the offending source line attached, so
+//! editors can draw squiggles and CI output can point at the exact code.
+//! The tree-sitter backends (Go, Java), Python, Rust, Protobuf, SQL, and
+//! JSON/YAML documents report their parse errors the same way.
+
+use oxc_diagnostics::{OxcDiagnostic, Severity};
+use std::ops::Range;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Advice,
+}
+
+impl DiagnosticSeverity {
+    /// Lowercase name, as exposed to JavaScript
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Advice => "advice",
+        }
+    }
+}
+
+/// A position in a source file (0-based line and character column)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A parse diagnostic with its location in the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// File the diagnostic belongs to
+    pub file_path: String,
+    /// Human-readable message
+    pub message: String,
+    /// Suggested fix, if the parser offers one
+    pub help: Option<String>,
+    pub severity: DiagnosticSeverity,
+    /// Start of the offending code
+    pub start: Position,
+    /// End of the offending code (exclusive)
+    pub end: Position,
+    /// The source line containing `start`, without its line terminator
+    pub excerpt: String,
+}
+
+impl Diagnostic {
+    /// Convert an Oxc diagnostic, locating its primary label in `content`
+    ///
+    /// Diagnostics without labels are placed at the start of the file.
+    pub fn from_oxc(file_path: &str, content: &str, diagnostic: &OxcDiagnostic) -> Self {
+        let labels = diagnostic.labels.as_deref().unwrap_or_default();
+        let label = labels
+            .iter()
+            .find(|label| label.primary())
+            .or_else(|| labels.first());
+        let (offset, len) = label.map_or((0, 0), |label| (label.offset(), label.len()));
+
+        let start = position_at(content, offset);
+        Self {
+            file_path: file_path.to_string(),
+            message: diagnostic.message.to_string(),
+            help: diagnostic.help.as_ref().map(|help| help.to_string()),
+            severity: match diagnostic.severity {
+                Severity::Error => DiagnosticSeverity::Error,
+                Severity::Warning => DiagnosticSeverity::Warning,
+                Severity::Advice => DiagnosticSeverity::Advice,
+            },
+            start,
+            end: position_at(content, offset + len),
+            excerpt: content.lines().nth(start.line).unwrap_or("").to_string(),
+        }
+    }
+
+    /// An error spanning the bytes `span` of `content`
+    pub fn error(
+        file_path: &str,
+        content: &str,
+        message: impl Into<String>,
+        span: Range<usize>,
+    ) -> Self {
+        let start = position_at(content, span.start);
+        Self {
+            file_path: file_path.to_string(),
+            message: message.into(),
+            help: None,
+            severity: DiagnosticSeverity::Error,
+            start,
+            end: position_at(content, span.end),
+            excerpt: content.lines().nth(start.line).unwrap_or("").to_string(),
+        }
+    }
+
+    /// A JSON syntax error, at the line and column it reports
+    pub fn from_json(file_path: &str, content: &str, error: &serde_json::Error) -> Self {
+        let line_start: usize = content
+            .split_inclusive('\n')
+            .take(error.line().saturating_sub(1))
+            .map(str::len)
+            .sum();
+        let offset = line_start + error.column().saturating_sub(1);
+        Self::error(file_path, content, error.to_string(), offset..offset)
+    }
+
+    /// A Rust syntax error, at the span `syn` reports
+    pub fn from_syn(file_path: &str, content: &str, error: &syn::Error) -> Self {
+        let span = error.span();
+        let offset =
+            |at: proc_macro2::LineColumn| offset_at(content, at.line.saturating_sub(1), at.column);
+        Self::error(
+            file_path,
+            content,
+            error.to_string(),
+            offset(span.start())..offset(span.end()),
+        )
+    }
+
+    /// A YAML syntax error, at the offset it reports
+    pub fn from_yaml(file_path: &str, content: &str, error: &serde_yaml::Error) -> Self {
+        let offset = error.location().map_or(0, |location| location.index());
+        Self::error(file_path, content, error.to_string(), offset..offset)
+    }
+}
+
+/// `AnalysisResult.errors` messages for diagnostics
+/// (`Parse error: <message> at line <n>`, 1-based)
+pub(crate) fn error_messages(diagnostics: &[Diagnostic]) -> Vec<String> {
+    diagnostics
+        .iter()
+        .map(|d| format!("Parse error: {} at line {}", d.message, d.start.line + 1))
+        .collect()
+}
+
+/// Byte offset of a 0-based line and character column (clamped to the content)
+fn offset_at(content: &str, line: usize, column: usize) -> usize {
+    let line_start: usize = content.split_inclusive('\n').take(line).map(str::len).sum();
+    let rest = &content[line_start.min(content.len())..];
+    line_start
+        + rest
+            .char_indices()
+            .nth(column)
+            .map_or(rest.len(), |(i, _)| i)
+}
+
+/// Line and character column of a byte offset (clamped to the content)
+fn position_at(content: &str, offset: usize) -> Position {
+    let mut offset = offset.min(content.len());
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position {
+        line: before.matches('\n').count(),
+        column: before[line_start..].chars().count(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::AstAnalyzerInternal;
+
+    #[test]
+    fn test_parse_error_has_span_and_excerpt() {
+        let code = "export const a = 1;\n  export const b = ;\n";
+        let result = AstAnalyzerInternal::new().analyze_file("src/b.ts", code);
+
+        assert!(!result.diagnostics.is_empty());
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.file_path, "src/b.ts");
+        assert_eq!(diagnostic.severity, DiagnosticSeverity::Error);
+        assert_eq!(diagnostic.start.line, 1);
+        assert_eq!(diagnostic.start.column, 19);
+        assert_eq!(diagnostic.excerpt, "  export const b = ;");
+        assert_eq!(diagnostic.end.line, 1);
+        assert_eq!(result.errors.len(), result.diagnostics.len());
+    }
+
+    #[test]
+    fn test_backend_parse_errors_are_diagnostics() {
+        let analyzer = AstAnalyzerInternal::new();
+        let result = analyzer.analyze_file("a.go", "package a\n\nfunc F( {\n");
+        assert!(!result.diagnostics.is_empty());
+        assert_eq!(result.errors, error_messages(&result.diagnostics));
+        assert_eq!(result.diagnostics[0].start.line, 2);
+        assert_eq!(result.diagnostics[0].excerpt, "func F( {");
+
+        let result = analyzer.analyze_file("app.py", "def f():\n    s = 'open\n");
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.message, "unterminated string");
+        assert_eq!((diagnostic.start.line, diagnostic.start.column), (1, 8));
+        assert_eq!(
+            result.errors,
+            ["Parse error: unterminated string at line 2"]
+        );
+
+        let result = analyzer.analyze_file("src/lib.rs", "pub fn f() {}\npub fn g(a: u8 {}\n");
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.start.line, 1);
+        assert_eq!(diagnostic.excerpt, "pub fn g(a: u8 {}");
+        assert_eq!(result.errors, error_messages(&result.diagnostics));
+
+        let result = analyzer.analyze_file("api.proto", "syntax = \"proto3\";\nmessage {}\n");
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.message, "expected message name");
+        assert_eq!((diagnostic.start.line, diagnostic.start.column), (1, 8));
+        assert_eq!(
+            result.errors,
+            ["Parse error: expected message name at line 2"]
+        );
+
+        let sql = "CREATE TABLE a (id int);\n\nCREATE TABLE users (id int;\n";
+        let result = analyzer.analyze_file("schema.sql", sql);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.message, "unbalanced parentheses in table users");
+        assert_eq!((diagnostic.start.line, diagnostic.start.column), (2, 0));
+        assert_eq!((diagnostic.end.line, diagnostic.end.column), (2, 26));
+        assert_eq!(result.errors, error_messages(&result.diagnostics));
+
+        let json = "{\n  /* a\n     comment */ \"name\": x\n}";
+        let result = analyzer.analyze_file("tsconfig.json", json);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.start.line, 2);
+        assert_eq!(diagnostic.start.column, 24);
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_position_counts_characters() {
+        let content = "é\nab€c";
+        assert_eq!(position_at(content, 0), Position { line: 0, column: 0 });
+        assert_eq!(position_at(content, 3), Position { line: 1, column: 0 });
+        assert_eq!(position_at(content, 8), Position { line: 1, column: 3 });
+        assert_eq!(position_at(content, 100), Position { line: 1, column: 4 });
+        assert_eq!(offset_at(content, 1, 3), 8);
+        assert_eq!(offset_at(content, 1, 9), content.len());
+    }
+}
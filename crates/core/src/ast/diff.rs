@@ -0,0 +1,719 @@
+//! Public API surface diff
+//!
+//! A changed hash says that a symbol drifted, not how. `diff_results`
+//! compares the exported symbols of two analyses of the same code (and
+//! `diff_contents` two versions of a file) and returns what a reader of the
+//! documentation would need to know:
+//!
+//! - Symbols added and removed, matched by file and name (the n-th symbol of
+//!   a name with the n-th of the same name, so overloads pair up in order)
+//! - Symbols whose signature changed, with the parameters added, removed, or
+//!   retyped (matched by name) and the return type before and after
+//!
+//! Parameters are read from the signature's first top-level parenthesized
+//! list (after the receiver of a Go method); names are what precedes the
+//! type annotation (`user?: string`), the first word in Go (`user string`),
+//! or the last word otherwise (`String user`). Return types are read after
+//! the list when introduced by `:` or `->` (TypeScript, Python, Rust) and in
+//! Go; other signatures (classes, Java methods) only have their old and new
+//! text.
+//!
+//! Each change is classified (`classify`) as non-breaking when existing
+//! callers and readers are unaffected, and breaking otherwise:
+//!
+//! - Non-breaking: optional parameters (`remember?: boolean`, defaults, rest
+//!   and variadic parameters) added after the existing ones, a required
+//!   parameter made optional, and members added to a class, struct, or enum
+//!   (or optional members added to an interface or object type)
+//! - Breaking: removed symbols and parameters, new required parameters,
+//!   retyped or reordered parameters, changed return types, removed or
+//!   changed members, and anything that cannot be read as one of the above
+
+use super::analyzer::{AnalysisResult, AstAnalyzerInternal, SymbolInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Impact of an API change on existing callers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeSeverity {
+    /// Existing callers keep working
+    NonBreaking,
+    /// Existing callers may stop working
+    Breaking,
+}
+
+impl ChangeSeverity {
+    /// Name as serialized (`nonBreaking`, `breaking`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeSeverity::NonBreaking => "nonBreaking",
+            ChangeSeverity::Breaking => "breaking",
+        }
+    }
+
+    /// Short description for reports (`non-breaking`, `breaking`)
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeSeverity::NonBreaking => "non-breaking",
+            ChangeSeverity::Breaking => "breaking",
+        }
+    }
+}
+
+/// Differences between two versions of the exported symbols
+#[derive(Debug, Clone, Default)]
+pub struct ApiDiff {
+    /// Symbols only in the new version
+    pub added: Vec<SymbolInfo>,
+    /// Symbols only in the old version
+    pub removed: Vec<SymbolInfo>,
+    /// Symbols in both whose signature or kind differs
+    pub changed: Vec<SignatureChange>,
+}
+
+impl ApiDiff {
+    /// Check whether the API is unchanged
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Severity of the whole diff (`None` if the API is unchanged)
+    ///
+    /// Breaking if a symbol was removed or any change is breaking.
+    pub fn severity(&self) -> Option<ChangeSeverity> {
+        if self.is_empty() {
+            return None;
+        }
+        let breaking = !self.removed.is_empty()
+            || self
+                .changed
+                .iter()
+                .any(|change| change.severity == ChangeSeverity::Breaking);
+        Some(match breaking {
+            true => ChangeSeverity::Breaking,
+            false => ChangeSeverity::NonBreaking,
+        })
+    }
+}
+
+/// A symbol whose signature changed
+#[derive(Debug, Clone)]
+pub struct SignatureChange {
+    /// The symbol in the old version
+    pub old: SymbolInfo,
+    /// The symbol in the new version
+    pub new: SymbolInfo,
+    /// Parameters only in the new signature, as written (`remember?: boolean`)
+    pub added_params: Vec<String>,
+    /// Parameters only in the old signature, as written
+    pub removed_params: Vec<String>,
+    /// Parameters in both whose declaration differs
+    pub changed_params: Vec<ParamChange>,
+    /// Return types before and after, if they differ
+    pub return_type: Option<ReturnTypeChange>,
+    /// Whether the change breaks existing callers
+    pub severity: ChangeSeverity,
+}
+
+/// A parameter declared differently in the new signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamChange {
+    /// Parameter name
+    pub name: String,
+    /// Old declaration (`user: string`)
+    pub old: String,
+    /// New declaration (`user: User`)
+    pub new: String,
+}
+
+/// A changed return type (`None` where there is none)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReturnTypeChange {
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Compare the exported symbols of two analyses
+pub fn diff_results(old: &AnalysisResult, new: &AnalysisResult) -> ApiDiff {
+    let new_symbols = keyed(new);
+    let mut unmatched: HashMap<_, &SymbolInfo> = new_symbols.iter().copied().collect();
+    let mut diff = ApiDiff::default();
+
+    for (key, old_symbol) in keyed(old) {
+        match unmatched.remove(&key) {
+            Some(new_symbol) => {
+                if old_symbol.signature != new_symbol.signature
+                    || old_symbol.symbol_type != new_symbol.symbol_type
+                {
+                    diff.changed
+                        .push(signature_change(old_symbol.clone(), new_symbol.clone()));
+                }
+            }
+            None => diff.removed.push(old_symbol.clone()),
+        }
+    }
+    // In the order of the new version
+    diff.added = new_symbols
+        .into_iter()
+        .filter(|(key, _)| unmatched.contains_key(key))
+        .map(|(_, symbol)| symbol.clone())
+        .collect();
+    diff
+}
+
+/// Compare the exported symbols of two versions of a file
+pub fn diff_contents(
+    analyzer: &AstAnalyzerInternal,
+    file_path: &str,
+    old_content: &str,
+    new_content: &str,
+) -> ApiDiff {
+    diff_results(
+        &analyzer.analyze_file(file_path, old_content),
+        &analyzer.analyze_file(file_path, new_content),
+    )
+}
+
+/// Exported symbols by file, name, and occurrence of the name, in order
+fn keyed(result: &AnalysisResult) -> Vec<((&str, &str, usize), &SymbolInfo)> {
+    let mut seen: HashMap<(&str, &str), usize> = HashMap::new();
+    result
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.is_exported)
+        .map(|symbol| {
+            let count = seen
+                .entry((symbol.file_path.as_str(), symbol.name.as_str()))
+                .or_default();
+            *count += 1;
+            (
+                (symbol.file_path.as_str(), symbol.name.as_str(), *count),
+                symbol,
+            )
+        })
+        .collect()
+}
+
+fn signature_change(old: SymbolInfo, new: SymbolInfo) -> SignatureChange {
+    let mut change = SignatureChange {
+        added_params: Vec::new(),
+        removed_params: Vec::new(),
+        changed_params: Vec::new(),
+        return_type: None,
+        severity: ChangeSeverity::Breaking,
+        old,
+        new,
+    };
+    if change.old.symbol_type == change.new.symbol_type {
+        change.severity = classify(&change.old.signature, &change.new.signature);
+    }
+    let (Some(old), Some(new)) = (
+        Callable::parse(&change.old.signature),
+        Callable::parse(&change.new.signature),
+    ) else {
+        return change;
+    };
+
+    for param in &new.params {
+        match old.params.iter().find(|p| p.name == param.name) {
+            None => change.added_params.push(param.text.clone()),
+            Some(old_param) if old_param.text != param.text => {
+                change.changed_params.push(ParamChange {
+                    name: param.name.clone(),
+                    old: old_param.text.clone(),
+                    new: param.text.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    change.removed_params = old
+        .params
+        .iter()
+        .filter(|param| !new.params.iter().any(|p| p.name == param.name))
+        .map(|param| param.text.clone())
+        .collect();
+    if old.return_type != new.return_type {
+        change.return_type = Some(ReturnTypeChange {
+            old: old.return_type,
+            new: new.return_type,
+        });
+    }
+    change
+}
+
+/// Classify a change from one signature of a symbol to another
+///
+/// Callable signatures are compared parameter by parameter; signatures with
+/// a braced body (`interface User { ... }`) member by member. Unchanged
+/// signatures are non-breaking.
+pub fn classify(old_signature: &str, new_signature: &str) -> ChangeSeverity {
+    if old_signature == new_signature {
+        return ChangeSeverity::NonBreaking;
+    }
+    let compatible = match (
+        Callable::parse(old_signature),
+        Callable::parse(new_signature),
+    ) {
+        (Some(old), Some(new)) => callable_compatible(&old, &new),
+        (None, None) => match (Braced::parse(old_signature), Braced::parse(new_signature)) {
+            (Some(old), Some(new)) => braced_compatible(&old, &new),
+            _ => false,
+        },
+        _ => false,
+    };
+    match compatible {
+        true => ChangeSeverity::NonBreaking,
+        false => ChangeSeverity::Breaking,
+    }
+}
+
+/// Whether every call of the old signature is a valid call of the new one
+fn callable_compatible(old: &Callable, new: &Callable) -> bool {
+    if old.head != new.head || old.tail != new.tail || new.params.len() < old.params.len() {
+        return false;
+    }
+    let (kept, added) = new.params.split_at(old.params.len());
+    old.params.iter().zip(kept).all(|(old, new)| {
+        old.text == new.text
+            || (old.name == new.name
+                && is_optional(&new.text)
+                && required_form(&old.text) == required_form(&new.text))
+    }) && added.iter().all(|param| is_optional(&param.text))
+}
+
+/// Whether the new members keep every old member, adding only members
+/// readers and implementors of the type can ignore
+fn braced_compatible(old: &Braced, new: &Braced) -> bool {
+    if old.head != new.head || !old.members.iter().all(|m| new.members.contains(m)) {
+        return false;
+    }
+    // Adding to an interface or object type breaks its implementors unless
+    // the member is optional
+    let extensible = new
+        .head
+        .split_whitespace()
+        .any(|word| matches!(word, "class" | "struct" | "enum"));
+    extensible
+        || new
+            .members
+            .iter()
+            .filter(|member| !old.members.contains(member))
+            .all(|member| split_top_level_at(member, &[':', '(']).ends_with('?'))
+}
+
+/// Whether a parameter can be left out of a call
+///
+/// Optional (`x?: T`), defaulted (`x = 1`), rest (`...args: T[]`, `*args`),
+/// and variadic (`opts ...Option`, `String... names`) parameters can.
+fn is_optional(param: &str) -> bool {
+    let param = strip_decorators(param);
+    param.starts_with('*')
+        || param.contains("...")
+        || default_at(param).is_some()
+        || split_top_level_at(param, &[':']).ends_with('?')
+}
+
+/// A parameter without its default value and optional marker, to compare
+/// types (`x?: T = a` reads `x: T`)
+fn required_form(param: &str) -> String {
+    let param = default_at(param).map_or(param, |i| param[..i].trim_end());
+    let name = split_top_level_at(param, &[':']);
+    match name.strip_suffix('?') {
+        Some(bare) => format!("{}{}", bare, &param[name.len()..]),
+        None => param.to_string(),
+    }
+}
+
+/// Byte offset of a parameter's top-level default value `=` (not `=>`,
+/// `==`, or a comparison)
+fn default_at(param: &str) -> Option<usize> {
+    let bytes = param.as_bytes();
+    let mut depth = 0usize;
+    let mut prev = b' ';
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'>' if prev == b'=' || prev == b'-' => {}
+            b'(' | b'[' | b'{' | b'<' => depth += 1,
+            b')' | b']' | b'}' | b'>' => depth = depth.saturating_sub(1),
+            b'=' if depth == 0
+                && !matches!(prev, b'=' | b'!' | b'<' | b'>')
+                && !matches!(bytes.get(i + 1), Some(b'=' | b'>')) =>
+            {
+                return Some(i);
+            }
+            _ => {}
+        }
+        prev = b;
+    }
+    None
+}
+
+/// Parameters and return type of a callable signature
+#[derive(Debug)]
+struct Callable {
+    /// Text before the parameter list
+    head: String,
+    params: Vec<Param>,
+    /// Text after the parameter list (return type, `throws`)
+    tail: String,
+    return_type: Option<String>,
+}
+
+#[derive(Debug)]
+struct Param {
+    name: String,
+    text: String,
+}
+
+impl Callable {
+    fn parse(signature: &str) -> Option<Self> {
+        let signature = strip_decorators(signature);
+        let go = signature.starts_with("func ");
+        let mut groups = top_level_groups(signature);
+        if signature.starts_with("func (") {
+            // Go method receiver
+            groups.next();
+        }
+        let (open, close) = groups.next()?;
+
+        let list = &signature[open + 1..close];
+        let params = split_top_level(list, &[','])
+            .into_iter()
+            .filter(|text| !text.is_empty())
+            .map(|text| Param {
+                name: param_name(text, go),
+                text: text.to_string(),
+            })
+            .collect();
+
+        let rest = signature[close + 1..].trim();
+        let rest = rest.strip_suffix("=>").unwrap_or(rest).trim_end();
+        let return_type = match (rest.strip_prefix(':'), rest.strip_prefix("->")) {
+            (Some(ty), _) | (_, Some(ty)) => Some(ty.trim().trim_end_matches(':').trim_end()),
+            _ if go && !rest.is_empty() => Some(rest),
+            _ => None,
+        };
+        Some(Self {
+            head: signature[..open].trim_end().to_string(),
+            params,
+            tail: rest.to_string(),
+            return_type: return_type.map(str::to_string),
+        })
+    }
+}
+
+/// Head and members of a signature with a braced body
+/// (`interface User { id: string; name?: string }`)
+#[derive(Debug)]
+struct Braced<'s> {
+    head: &'s str,
+    members: Vec<&'s str>,
+}
+
+impl<'s> Braced<'s> {
+    fn parse(signature: &'s str) -> Option<Self> {
+        let body = signature.strip_suffix('}')?;
+        let mut depth = 0usize;
+        let mut prev = ' ';
+        for (i, c) in body.char_indices() {
+            match c {
+                '{' if depth == 0 => {
+                    let members = split_top_level(&body[i + 1..], &[';', ','])
+                        .into_iter()
+                        .filter(|member| !member.is_empty())
+                        .collect();
+                    return Some(Self {
+                        head: body[..i].trim_end(),
+                        members,
+                    });
+                }
+                '>' if prev == '=' || prev == '-' => {}
+                '(' | '[' | '{' | '<' => depth += 1,
+                ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            prev = c;
+        }
+        None
+    }
+}
+
+/// Byte ranges `(open, close)` of the parenthesized groups outside any
+/// bracket, brace, angle bracket, or string
+fn top_level_groups(signature: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut depth = 0usize;
+    let mut open = None;
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    let mut groups = Vec::new();
+    for (i, c) in signature.char_indices() {
+        match quote {
+            Some(q) if c == q && prev != '\\' => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '(' if depth == 0 => {
+                    open = Some(i);
+                    depth += 1;
+                }
+                ')' if depth == 1 && open.is_some() => {
+                    groups.extend(open.take().map(|open| (open, i)));
+                    depth = 0;
+                }
+                // `=>` is not a closing angle bracket
+                '>' if prev == '=' || prev == '-' => {}
+                '(' | '[' | '{' | '<' => depth += 1,
+                ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+                _ => {}
+            },
+        }
+        prev = c;
+    }
+    groups.into_iter()
+}
+
+/// Split a list on separators outside brackets and strings
+fn split_top_level<'l>(list: &'l str, separators: &[char]) -> Vec<&'l str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match quote {
+            Some(q) if c == q && prev != '\\' => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '>' if prev == '=' || prev == '-' => {}
+                '(' | '[' | '{' | '<' => depth += 1,
+                ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+                c if depth == 0 && separators.contains(&c) => {
+                    parts.push(list[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            },
+        }
+        prev = c;
+    }
+    parts.push(list[start..].trim());
+    parts
+}
+
+/// Name of a declared parameter
+fn param_name(text: &str, go: bool) -> String {
+    // Decorators and modifiers come first
+    let text = strip_decorators(text);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let skip = words
+        .iter()
+        .take_while(|w| {
+            matches!(
+                **w,
+                "public" | "private" | "protected" | "readonly" | "override" | "final" | "mut"
+            )
+        })
+        .count();
+    let text = words[skip..].join(" ");
+    let declaration = split_top_level_at(&text, &[':', '=']);
+    let declaration = declaration.trim_start_matches("...").trim_end_matches('?');
+    if declaration.starts_with(['{', '[']) || !declaration.contains(' ') {
+        return declaration.to_string();
+    }
+    let mut words = declaration.split_whitespace();
+    match go {
+        true => words.next(),
+        false => words.last(),
+    }
+    .unwrap_or(declaration)
+    .to_string()
+}
+
+/// Text after leading decorators (`@Param("id") `)
+fn strip_decorators(mut text: &str) -> &str {
+    while let Some(rest) = text.strip_prefix('@') {
+        let name_end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+            .unwrap_or(rest.len());
+        let end = match rest[name_end..].starts_with('(') {
+            true => top_level_groups(rest)
+                .next()
+                .map_or(rest.len(), |(_, close)| close + 1),
+            false => name_end,
+        };
+        text = rest[end..].trim_start();
+    }
+    text
+}
+
+/// Text before the first of `stops` outside brackets
+fn split_top_level_at<'t>(text: &'t str, stops: &[char]) -> &'t str {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+            c if depth == 0 && stops.contains(&c) => return text[..i].trim_end(),
+            _ => {}
+        }
+    }
+    text.trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(old: &str, new: &str) -> ApiDiff {
+        diff_contents(&AstAnalyzerInternal::new(), "src/auth.ts", old, new)
+    }
+
+    #[test]
+    fn test_added_removed_and_changed_symbols() {
+        let diff = changes(
+            "export function login(user: string, password: string): Session { return s; }\n\
+             export function logout(): void {}\n\
+             export const VERSION = 1;\n\
+             function helper() {}\n",
+            "export function login(user: User, password: string, remember?: boolean): Promise<Session> { return s; }\n\
+             export const VERSION = 1;\n\
+             export class Session {}\n\
+             function helper(a: number) {}\n",
+        );
+        let names = |symbols: &[SymbolInfo]| -> Vec<String> {
+            symbols.iter().map(|s| s.name.clone()).collect()
+        };
+        assert_eq!(names(&diff.added), ["Session"]);
+        assert_eq!(names(&diff.removed), ["logout"]);
+        assert_eq!(diff.changed.len(), 1);
+
+        let login = &diff.changed[0];
+        assert_eq!(login.new.name, "login");
+        assert_eq!(login.added_params, ["remember?: boolean"]);
+        assert!(login.removed_params.is_empty());
+        assert_eq!(
+            login.changed_params,
+            [ParamChange {
+                name: "user".to_string(),
+                old: "user: string".to_string(),
+                new: "user: User".to_string(),
+            }]
+        );
+        assert_eq!(
+            login.return_type,
+            Some(ReturnTypeChange {
+                old: Some("Session".to_string()),
+                new: Some("Promise<Session>".to_string()),
+            })
+        );
+
+        assert!(changes("export function a() {}\n", "export function a() {}\n").is_empty());
+    }
+
+    #[test]
+    fn test_reads_parameters_across_languages() {
+        let parse = |signature: &str| {
+            let callable = Callable::parse(signature).unwrap();
+            let names: Vec<String> = callable.params.into_iter().map(|p| p.name).collect();
+            (names, callable.return_type)
+        };
+        assert_eq!(
+            parse("const f = async <T extends (a: A) => B>({ a, b }: T, ...rest: string[]): Promise<Map<K, V>> =>"),
+            (
+                vec!["{ a, b }".to_string(), "rest".to_string()],
+                Some("Promise<Map<K, V>>".to_string())
+            )
+        );
+        assert_eq!(
+            parse("@Get(\":id\") find(@Param(\"id\") id: string, private readonly x = \"a,b\")"),
+            (vec!["id".to_string(), "x".to_string()], None)
+        );
+        assert_eq!(
+            parse("func (s *Server[T]) Serve(addr string, opts ...Option) (int, error)"),
+            (
+                vec!["addr".to_string(), "opts".to_string()],
+                Some("(int, error)".to_string())
+            )
+        );
+        assert_eq!(
+            parse("public Session login(final String user, int attempts) throws AuthException"),
+            (vec!["user".to_string(), "attempts".to_string()], None)
+        );
+        assert_eq!(
+            parse("pub fn login(&self, mut user: String) -> Result<Session>"),
+            (
+                vec!["&self".to_string(), "user".to_string()],
+                Some("Result<Session>".to_string())
+            )
+        );
+        assert!(Callable::parse("interface A { b(c: C): D }").is_none());
+    }
+
+    #[test]
+    fn test_classifies_changes() {
+        use ChangeSeverity::{Breaking, NonBreaking};
+        let cases = [
+            // Optional, defaulted, and rest parameters appended
+            (
+                "function login(user: string): Session",
+                "function login(user: string, remember?: boolean, ttl = 60, ...scopes: string[]): Session",
+                NonBreaking,
+            ),
+            ("def login(user)", "def login(user, remember=False, **options)", NonBreaking),
+            (
+                "func Login(user string) error",
+                "func Login(user string, opts ...Option) error",
+                NonBreaking,
+            ),
+            // Required parameter made optional
+            ("function a(x: number)", "function a(x?: number)", NonBreaking),
+            ("function a(x: number)", "function a(x: number = 1)", NonBreaking),
+            ("function a(x?: number)", "function a(x: number)", Breaking),
+            // New required, removed, retyped, or reordered parameters
+            ("function a(x: number)", "function a(x: number, y: string)", Breaking),
+            ("function a(cb: () => void)", "function a(cb: () => void, f: (x: number) => string)", Breaking),
+            ("function a(x: number, y: string)", "function a(x: number)", Breaking),
+            ("function a(x: number)", "function a(x: string)", Breaking),
+            ("function a(x: number, y?: string)", "function a(y?: string, x: number)", Breaking),
+            // Return types and heads
+            ("function a(): string", "function a(): string | null", Breaking),
+            ("function a(): void", "async function a(): void", Breaking),
+            // Members
+            (
+                "interface User { id: string }",
+                "interface User { id: string; name?: string }",
+                NonBreaking,
+            ),
+            ("interface User { id: string }", "interface User { id: string; name: string }", Breaking),
+            ("interface User { id: string; name: string }", "interface User { id: string }", Breaking),
+            ("class A { x: number; }", "class A { x: number; y(): void; }", NonBreaking),
+            ("export enum Color { Red, Green }", "export enum Color { Red, Green, Blue }", NonBreaking),
+            (
+                "type Store interface { Get(id string) User }",
+                "type Store interface { Get(id string) User; Put(u User) }",
+                Breaking,
+            ),
+            ("type ID = string", "type ID = string | number", Breaking),
+            ("function a()", "interface a { }", Breaking),
+        ];
+        for (old, new, severity) in cases {
+            assert_eq!(classify(old, new), severity, "{} -> {}", old, new);
+        }
+
+        let diff = changes(
+            "export function a(x: number) {}\nexport function b() {}\n",
+            "export function a(x: number, y?: number) {}\nexport function b() {}\nexport function c() {}\n",
+        );
+        assert_eq!(diff.changed[0].severity, NonBreaking);
+        assert_eq!(diff.severity(), Some(NonBreaking));
+        let diff = changes(
+            "export function a(x: number) {}\nexport function b() {}\n",
+            "export function a(x: number, y?: number) {}\n",
+        );
+        assert_eq!(diff.severity(), Some(Breaking));
+        assert_eq!(changes("", "").severity(), None);
+    }
+}
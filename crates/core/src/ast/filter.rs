@@ -0,0 +1,280 @@
+//! Config-driven symbol filters
+//!
+//! Projects exclude whole kinds of symbols, name patterns, and paths from
+//! documentation tracking in the `symbols` key of `sintesi.config.json`:
+//!
+//! ```json
+//! {
+//!   "symbols": {
+//!     "excludeKinds": ["const"],
+//!     "excludeNames": ["_*", "*Internal"],
+//!     "excludePaths": ["**/internal/**"],
+//!     "decorators": true,
+//!     "reactComponents": true,
+//!     "inheritedMembers": true
+//!   }
+//! }
+//! ```
+//!
+//! `decorators`, `reactComponents`, and `inheritedMembers` are not filters:
+//! they make analyzers configured with the filter include decorators in
+//! class signatures (see `AnalyzerOptions::decorators`), e.g. for NestJS and
+//! Angular projects, report React function components with their props (see
+//! `AnalyzerOptions::react_components`), and list inherited members in class
+//! signatures (see `AnalyzerOptions::inherited_members`).
+//!
+//! The analyzer applies the filter to its output (see
+//! `AnalyzerOptions::filter`): excluded symbols are reported as ignored
+//! items rather than symbols, so drift detection marks entries documenting
+//! them as ignored instead of removed, and every consumer sees the same set.
+
+use super::analyzer::SymbolInfo;
+use super::ignore::IgnoredItem;
+use crate::error::Error;
+use crate::init::CONFIG_FILE;
+use crate::provider::ContentProvider;
+use crate::types::SymbolType;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Reason recorded on symbols and files excluded by the filter
+pub const FILTER_REASON: &str = "excluded by symbol filter";
+
+/// Every symbol kind, for parsing kind names
+const KINDS: &[SymbolType] = &[
+    SymbolType::Function,
+    SymbolType::Class,
+    SymbolType::Interface,
+    SymbolType::TypeAlias,
+    SymbolType::Enum,
+    SymbolType::Variable,
+    SymbolType::Const,
+    SymbolType::Operation,
+    SymbolType::Schema,
+    SymbolType::Table,
+    SymbolType::View,
+    SymbolType::Service,
+    SymbolType::ConfigKey,
+    SymbolType::Route,
+    SymbolType::Impl,
+    SymbolType::Component,
+];
+
+/// The `symbols` key of `sintesi.config.json`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolFilterConfig {
+    /// Symbol kinds to exclude (`function`, `typeAlias`, `const`, ...)
+    #[serde(default)]
+    pub exclude_kinds: Vec<String>,
+    /// Glob patterns matched against symbol names
+    #[serde(default)]
+    pub exclude_names: Vec<String>,
+    /// Glob patterns matched against file paths
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+    /// Include decorators in class and property signatures
+    #[serde(default)]
+    pub decorators: bool,
+    /// Report React function components with their props
+    #[serde(default)]
+    pub react_components: bool,
+    /// List inherited members in the signatures of derived classes
+    #[serde(default)]
+    pub inherited_members: bool,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    #[serde(default)]
+    symbols: SymbolFilterConfig,
+}
+
+/// Excludes symbols by kind, name, and file path
+#[derive(Debug, Clone, Default)]
+pub struct SymbolFilter {
+    kinds: Vec<SymbolType>,
+    names: Option<GlobSet>,
+    paths: Option<GlobSet>,
+    decorators: bool,
+    react_components: bool,
+    inherited_members: bool,
+}
+
+impl SymbolFilter {
+    /// A filter that excludes nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compile a filter configuration
+    ///
+    /// # Errors
+    /// Fails on unknown kinds and invalid glob patterns.
+    pub fn from_config(config: &SymbolFilterConfig) -> Result<Self, Error> {
+        let kinds = config
+            .exclude_kinds
+            .iter()
+            .map(|name| {
+                parse_kind(name)
+                    .ok_or_else(|| Error::from_reason(format!("Unknown symbol kind: {}", name)))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            kinds,
+            names: compile(&config.exclude_names)?,
+            paths: compile(&config.exclude_paths)?,
+            decorators: config.decorators,
+            react_components: config.react_components,
+            inherited_members: config.inherited_members,
+        })
+    }
+
+    /// Load the `symbols` key of a config file (a filter excluding nothing if
+    /// the file does not exist)
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let Ok(content) = fs::read_to_string(path) else {
+            return Ok(Self::new());
+        };
+        Self::parse(&content)
+            .map_err(|e| Error::from_reason(format!("Invalid config {}: {}", path.display(), e)))
+    }
+
+    /// Read the filter from a provider's `sintesi.config.json`
+    ///
+    /// Used by the drift pipeline, so checks against a revision use the
+    /// filters of that revision. A missing or invalid config excludes
+    /// nothing; `load` reports config errors.
+    pub fn from_provider(provider: &dyn ContentProvider) -> Self {
+        provider
+            .read(Path::new(CONFIG_FILE))
+            .ok()
+            .and_then(|content| Self::parse(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Parse the `symbols` key of config file content
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let config: Config =
+            serde_json::from_str(content).map_err(|e| Error::from_reason(e.to_string()))?;
+        Self::from_config(&config.symbols)
+    }
+
+    /// Check whether the filter excludes nothing
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty() && self.names.is_none() && self.paths.is_none()
+    }
+
+    /// Whether the config asks for decorators in signatures
+    pub fn decorators(&self) -> bool {
+        self.decorators
+    }
+
+    /// Whether the config asks for React components with their props
+    pub fn react_components(&self) -> bool {
+        self.react_components
+    }
+
+    /// Whether the config asks for inherited members in class signatures
+    pub fn inherited_members(&self) -> bool {
+        self.inherited_members
+    }
+
+    /// Check whether all symbols of a file are excluded
+    pub fn excludes_file(&self, file_path: &str) -> bool {
+        self.paths.as_ref().is_some_and(|g| g.is_match(file_path))
+    }
+
+    /// Check whether a symbol is excluded
+    pub fn excludes(&self, symbol: &SymbolInfo) -> bool {
+        self.kinds.contains(&symbol.symbol_type)
+            || self
+                .names
+                .as_ref()
+                .is_some_and(|g| g.is_match(&symbol.name))
+            || self.excludes_file(&symbol.file_path)
+    }
+
+    /// Move excluded symbols to `ignored`
+    pub(crate) fn apply(&self, symbols: &mut Vec<SymbolInfo>, ignored: &mut Vec<IgnoredItem>) {
+        if self.is_empty() {
+            return;
+        }
+        symbols.retain(|symbol| {
+            if !self.excludes(symbol) {
+                return true;
+            }
+            ignored.push(IgnoredItem {
+                file_path: symbol.file_path.clone(),
+                symbol_name: Some(symbol.name.clone()),
+                reason: Some(FILTER_REASON.to_string()),
+            });
+            false
+        });
+    }
+}
+
+/// Parse a kind name, ignoring case and separators (`typeAlias`, `type_alias`)
+fn parse_kind(name: &str) -> Option<SymbolType> {
+    let wanted: String = name.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    KINDS
+        .iter()
+        .find(|kind| format!("{:?}", kind).eq_ignore_ascii_case(&wanted))
+        .copied()
+}
+
+fn compile(patterns: &[String]) -> Result<Option<GlobSet>, Error> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| {
+            Error::from_reason(format!("Invalid symbol filter pattern {}: {}", pattern, e))
+        })?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| Error::from_reason(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{AnalyzerOptions, AstAnalyzerInternal};
+
+    #[test]
+    fn test_filter_moves_symbols_to_ignored() {
+        let filter = SymbolFilter::parse(
+            r#"{"docsDir": "docs", "symbols": {"excludeKinds": ["const"], "excludeNames": ["_*"], "excludePaths": ["**/internal/**"]}}"#,
+        )
+        .unwrap();
+        let analyzer = AstAnalyzerInternal::with_options(AnalyzerOptions::new().filter(filter));
+
+        let result = analyzer.analyze_file(
+            "src/api.ts",
+            "export const VERSION = '1';\nexport function _private() {}\nexport function login() {}",
+        );
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["login"]);
+        assert_eq!(result.ignored.len(), 2);
+        assert_eq!(result.ignored[0].reason.as_deref(), Some(FILTER_REASON));
+
+        let result = analyzer.analyze_file("src/internal/db.ts", "export function query() {}");
+        assert!(result.symbols.is_empty());
+        assert_eq!(result.ignored[0].code_ref(), "src/internal/db.ts#query");
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(SymbolFilter::parse(r#"{"symbols": {"excludeKinds": ["macro"]}}"#).is_err());
+        assert!(SymbolFilter::parse(r#"{"symbols": {"excludePaths": ["src/[a"]}}"#).is_err());
+        assert_eq!(parse_kind("type_alias"), Some(SymbolType::TypeAlias));
+        assert!(SymbolFilter::parse("{}").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,361 @@
+//! Go analyzer
+//!
+//! A `LanguageBackend` extracting the package-level declarations of `.go`
+//! files with the tree-sitter Go grammar:
+//!
+//! - Functions (`pkg/auth.go#Login`) and methods, qualified with their
+//!   receiver's type (`Server.Login`, whether the receiver is a pointer or
+//!   not)
+//! - Type declarations: structs as `Class`, interfaces as `Interface`, and
+//!   other types and aliases as `TypeAlias`, with their fields and methods
+//!   in the signature
+//! - Constants and variables, one symbol per declared name
+//!
+//! Capitalized names are exported, as in Go; methods also need an exported
+//! receiver type. A `Deprecated:` paragraph in the doc comment marks the
+//! symbol deprecated. Test files (`_test.go`) are not part of the API and
+//! yield no symbols.
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::backend::{
+    comments_before, field, parse, render, symbol, text, type_params, LanguageBackend,
+};
+use super::diagnostics::error_messages;
+use crate::types::SymbolType;
+use tree_sitter::Node;
+
+/// The Go backend
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GoBackend;
+
+impl LanguageBackend for GoBackend {
+    fn name(&self) -> &str {
+        "Go"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["go"]
+    }
+
+    fn analyze(&self, file_path: &str, content: &str) -> AnalysisResult {
+        let mut symbols = Vec::new();
+        let mut diagnostics = Vec::new();
+        if !file_path.ends_with("_test.go") {
+            let (tree, parse_errors) = parse(tree_sitter_go::LANGUAGE.into(), file_path, content);
+            diagnostics = parse_errors;
+            if let Some(tree) = tree {
+                let mut extractor = GoExtractor {
+                    file_path,
+                    content,
+                    symbols: &mut symbols,
+                };
+                let root = tree.root_node();
+                let mut cursor = root.walk();
+                for node in root.named_children(&mut cursor) {
+                    extractor.declaration(node);
+                }
+            }
+        }
+
+        AnalysisResult {
+            symbols,
+            errors: error_messages(&diagnostics),
+            diagnostics,
+            ignored: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+struct GoExtractor<'a> {
+    file_path: &'a str,
+    content: &'a str,
+    symbols: &'a mut Vec<SymbolInfo>,
+}
+
+impl GoExtractor<'_> {
+    fn declaration(&mut self, node: Node) {
+        match node.kind() {
+            "function_declaration" => {
+                let Some(name) = field(node, "name", self.content) else {
+                    return;
+                };
+                let exported = is_exported(name);
+                self.push(
+                    node,
+                    node,
+                    name.to_string(),
+                    SymbolType::Function,
+                    "",
+                    exported,
+                );
+            }
+            "method_declaration" => {
+                let Some(name) = field(node, "name", self.content) else {
+                    return;
+                };
+                let receiver = self.receiver_type(node).unwrap_or_default();
+                let exported = is_exported(name) && is_exported(&receiver);
+                let name = format!("{}.{}", receiver, name);
+                self.push(node, node, name, SymbolType::Function, "", exported);
+            }
+            "type_declaration" => {
+                let mut cursor = node.walk();
+                for spec in node.named_children(&mut cursor) {
+                    let Some(name) = field(spec, "name", self.content) else {
+                        continue;
+                    };
+                    let symbol_type = match spec.child_by_field_name("type").map(|t| t.kind()) {
+                        Some("struct_type") if spec.kind() == "type_spec" => SymbolType::Class,
+                        Some("interface_type") if spec.kind() == "type_spec" => {
+                            SymbolType::Interface
+                        }
+                        _ => SymbolType::TypeAlias,
+                    };
+                    let exported = is_exported(name);
+                    self.push(node, spec, name.to_string(), symbol_type, "type ", exported);
+                }
+            }
+            "const_declaration" | "var_declaration" => {
+                let (symbol_type, keyword) = match node.kind() {
+                    "const_declaration" => (SymbolType::Const, "const "),
+                    _ => (SymbolType::Variable, "var "),
+                };
+                for spec in specs(node) {
+                    let mut cursor = spec.walk();
+                    for name in spec.children_by_field_name("name", &mut cursor) {
+                        let name = text(name, self.content);
+                        let exported = is_exported(name);
+                        self.push(node, spec, name.to_string(), symbol_type, keyword, exported);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Base type name of a method receiver (`Server` for `(s *Server[T])`)
+    fn receiver_type(&self, method: Node) -> Option<String> {
+        let receiver = method.child_by_field_name("receiver")?;
+        let mut cursor = receiver.walk();
+        let param = receiver.named_children(&mut cursor).next()?;
+        let ty = field(param, "type", self.content)?;
+        let ty = ty.trim_start_matches('*').trim_start();
+        Some(ty.split('[').next().unwrap_or(ty).to_string())
+    }
+
+    /// Push the symbol declared by `spec`, documented above `declaration`
+    fn push(
+        &mut self,
+        declaration: Node,
+        spec: Node,
+        name: String,
+        symbol_type: SymbolType,
+        keyword: &str,
+        is_exported: bool,
+    ) {
+        let end = spec
+            .child_by_field_name("body")
+            .map_or(spec.end_byte(), |body| body.start_byte());
+        let signature = format!("{}{}", keyword, render(spec, self.content, end, true));
+        let mut comments = comments_before(spec, self.content);
+        if spec != declaration {
+            comments.extend(comments_before(declaration, self.content));
+        }
+        let deprecated = comments.iter().any(|comment| {
+            comment
+                .lines()
+                .map(|line| line.trim_start_matches(['/', '*', ' ', '\t']))
+                .any(|line| line.starts_with("Deprecated:"))
+        });
+        self.symbols.push(SymbolInfo {
+            type_params: type_params(spec, self.content),
+            ..symbol(
+                self.file_path,
+                spec,
+                name,
+                symbol_type,
+                signature,
+                is_exported,
+                deprecated,
+            )
+        });
+    }
+}
+
+/// Specs of a `const` or `var` declaration, grouped or not
+fn specs(declaration: Node) -> Vec<Node> {
+    let mut cursor = declaration.walk();
+    let mut specs = Vec::new();
+    for child in declaration.named_children(&mut cursor) {
+        match child.kind() {
+            "const_spec" | "var_spec" => specs.push(child),
+            "var_spec_list" => specs.extend(specs_of_list(child)),
+            _ => {}
+        }
+    }
+    specs
+}
+
+fn specs_of_list(list: Node) -> Vec<Node> {
+    let mut cursor = list.walk();
+    list.named_children(&mut cursor)
+        .filter(|spec| spec.kind() == "var_spec")
+        .collect()
+}
+
+/// Whether a Go identifier is exported (starts with an upper case letter)
+fn is_exported(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_uppercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = r#"package auth
+
+import "errors"
+
+// ErrDenied is returned for invalid credentials.
+var ErrDenied = errors.New("denied")
+
+const (
+	MaxAttempts = 3
+	minDelay    = 10
+)
+
+// User is an account.
+type User struct {
+	Name  string // display name
+	Email string
+	age   int
+}
+
+type Store interface {
+	Get(id string) (User, error)
+}
+
+type ID = string
+
+// Login checks credentials.
+//
+// Deprecated: use LoginContext.
+func Login(
+	user string,
+	password string,
+) (*User, error) {
+	return nil, ErrDenied
+}
+
+func (s *Server[T]) Serve(addr string) error { return nil }
+
+func helper() {}
+"#;
+
+    fn find<'a>(result: &'a AnalysisResult, name: &str) -> &'a SymbolInfo {
+        result.symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_extracts_declarations() {
+        let result = GoBackend.analyze("pkg/auth/auth.go", CODE);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "ErrDenied",
+                "MaxAttempts",
+                "minDelay",
+                "User",
+                "Store",
+                "ID",
+                "Login",
+                "Server.Serve",
+                "helper"
+            ]
+        );
+
+        let login = find(&result, "Login");
+        assert_eq!(
+            login.signature,
+            "func Login(user string, password string) (*User, error)"
+        );
+        assert_eq!(login.symbol_type, SymbolType::Function);
+        assert!(login.is_exported && login.deprecated);
+        assert_eq!(login.location.unwrap().start_line, 28);
+
+        let user = find(&result, "User");
+        assert_eq!(user.symbol_type, SymbolType::Class);
+        assert_eq!(
+            user.signature,
+            "type User struct { Name string; Email string; age int }"
+        );
+        assert_eq!(find(&result, "Store").symbol_type, SymbolType::Interface);
+        assert_eq!(
+            find(&result, "Store").signature,
+            "type Store interface { Get(id string) (User, error) }"
+        );
+        assert_eq!(find(&result, "ID").signature, "type ID = string");
+        assert_eq!(
+            find(&result, "MaxAttempts").signature,
+            "const MaxAttempts = 3"
+        );
+        assert_eq!(
+            find(&result, "ErrDenied").signature,
+            "var ErrDenied = errors.New(\"denied\")"
+        );
+
+        let serve = find(&result, "Server.Serve");
+        assert_eq!(
+            serve.signature,
+            "func (s *Server[T]) Serve(addr string) error"
+        );
+        assert!(serve.is_exported);
+        assert!(!find(&result, "minDelay").is_exported);
+        assert!(!find(&result, "helper").is_exported);
+    }
+
+    #[test]
+    fn test_type_params() {
+        let result = GoBackend.analyze(
+            "a.go",
+            "package a\n\nfunc Keys[K comparable, V any](m map[K]V) []K { return nil }\n\ntype Set[T int | string] struct{}\n",
+        );
+        let keys = &result.symbols[0].type_params;
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].name, "K");
+        assert_eq!(keys[0].constraint.as_deref(), Some("comparable"));
+        assert_eq!(keys[1].constraint.as_deref(), Some("any"));
+        assert_eq!(
+            result.symbols[1].type_params[0].constraint.as_deref(),
+            Some("int | string")
+        );
+    }
+
+    #[test]
+    fn test_formatting_is_not_drift() {
+        let wrapped = GoBackend.analyze(
+            "a.go",
+            "package a\n\nfunc F(\n\ta int,\n\tb int,\n) int { return a }\n",
+        );
+        let inline = GoBackend.analyze(
+            "a.go",
+            "package a\nfunc F(a int,b int) int {\n\treturn a + b\n}\n",
+        );
+        assert_eq!(wrapped.symbols[0].signature, inline.symbols[0].signature);
+    }
+
+    #[test]
+    fn test_skips_test_files_and_reports_errors() {
+        let result = GoBackend.analyze(
+            "pkg/auth/auth_test.go",
+            "package auth\nfunc TestLogin(t *testing.T) {}\n",
+        );
+        assert!(result.symbols.is_empty());
+
+        let result = GoBackend.analyze("a.go", "package a\nfunc F( {\n");
+        assert!(result.errors[0].starts_with("Parse error:"));
+    }
+}
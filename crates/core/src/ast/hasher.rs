@@ -119,7 +119,7 @@ impl SignatureHasher {
     /// return parts.join('|');
     /// ```
     fn serialize_signature(&self, signature: &CodeSignature) -> String {
-        let parts = vec![
+        let parts = [
             format!("name:{}", signature.symbol_name),
             format!("type:{}", self.symbol_type_to_string(signature.symbol_type)),
             format!("exported:{}", signature.is_exported),
@@ -129,7 +129,7 @@ impl SignatureHasher {
     }
 
     /// Convert SymbolType to string representation (matches TypeScript enum values)
-    fn symbol_type_to_string(&self, symbol_type: SymbolType) -> &'static str {
+    pub(crate) fn symbol_type_to_string(&self, symbol_type: SymbolType) -> &'static str {
         match symbol_type {
             SymbolType::Function => "Function",
             SymbolType::Class => "Class",
@@ -138,6 +138,15 @@ impl SignatureHasher {
             SymbolType::Enum => "Enum",
             SymbolType::Variable => "Variable",
             SymbolType::Const => "Const",
+            SymbolType::Operation => "Operation",
+            SymbolType::Schema => "Schema",
+            SymbolType::Table => "Table",
+            SymbolType::View => "View",
+            SymbolType::Service => "Service",
+            SymbolType::ConfigKey => "ConfigKey",
+            SymbolType::Route => "Route",
+            SymbolType::Impl => "Impl",
+            SymbolType::Component => "Component",
         }
     }
 
@@ -172,7 +181,9 @@ pub fn hash_signature(signature: &CodeSignature) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ast::AstAnalyzerInternal;
     use crate::types::SymbolType;
+    use proptest::prelude::*;
 
     #[test]
     fn test_hash_method() {
@@ -182,6 +193,12 @@ mod tests {
             signature_text: "function test(): void".to_string(),
             is_exported: true,
             hash: None,
+            display_name: None,
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+            id: None,
         };
 
         let hasher = SignatureHasher::new();
@@ -205,6 +222,12 @@ mod tests {
             signature_text: "function test(): void".to_string(),
             is_exported: true,
             hash: None,
+            display_name: None,
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+            id: None,
         };
 
         let sig2 = CodeSignature {
@@ -213,6 +236,12 @@ mod tests {
             signature_text: "function test(): void".to_string(),
             is_exported: true,
             hash: None,
+            display_name: None,
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+            id: None,
         };
 
         let hasher = SignatureHasher::new();
@@ -231,6 +260,12 @@ mod tests {
             signature_text: "function test(): void".to_string(),
             is_exported: true,
             hash: None,
+            display_name: None,
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+            id: None,
         };
 
         let sig2 = CodeSignature {
@@ -239,6 +274,12 @@ mod tests {
             signature_text: "function test(): string".to_string(), // Different return type
             is_exported: true,
             hash: None,
+            display_name: None,
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+            id: None,
         };
 
         let hasher = SignatureHasher::new();
@@ -258,6 +299,12 @@ mod tests {
                 signature_text: "function func1(): void".to_string(),
                 is_exported: true,
                 hash: None,
+                display_name: None,
+                deprecated: false,
+                release_tag: None,
+                location: None,
+                type_params: Vec::new(),
+                id: None,
             },
             CodeSignature {
                 symbol_name: "func2".to_string(),
@@ -265,6 +312,12 @@ mod tests {
                 signature_text: "function func2(): string".to_string(),
                 is_exported: true,
                 hash: None,
+                display_name: None,
+                deprecated: false,
+                release_tag: None,
+                location: None,
+                type_params: Vec::new(),
+                id: None,
             },
         ];
 
@@ -308,6 +361,12 @@ mod tests {
             signature_text: "function myFunc(x: number): string".to_string(),
             is_exported: true,
             hash: None,
+            display_name: None,
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+            id: None,
         };
 
         let hasher = SignatureHasher::new();
@@ -319,4 +378,365 @@ mod tests {
             "name:myFunc|type:Function|exported:true|signature:function myFunc(x: number): string"
         );
     }
+
+    /// Source of a declaration, in pieces whose layout can vary
+    #[derive(Debug, Clone, PartialEq)]
+    enum Piece {
+        /// Token written as is
+        Tok(String),
+        /// String literal, single- or double-quoted
+        Str(String),
+        /// Optional whitespace or comment
+        Gap,
+        /// Whitespace or comment separating two tokens
+        Space,
+        /// Separator that may be left out (a trailing comma)
+        Trailing(String),
+        /// `;` or `,` between members
+        Sep,
+    }
+
+    /// Pieces of a template: a space is a `Space`, `~` a `Gap`, `'text'` a
+    /// `Str`, `$` a `Sep`, and `%` starts a `Trailing` separator that runs
+    /// to the next `~`
+    fn pieces(template: &str) -> Vec<Piece> {
+        let mut pieces = Vec::new();
+        let mut chars = template.chars();
+        let mut token = String::new();
+        while let Some(c) = chars.next() {
+            let piece = match c {
+                ' ' => Piece::Space,
+                '~' => Piece::Gap,
+                '$' => Piece::Sep,
+                '\'' => Piece::Str(chars.by_ref().take_while(|&c| c != '\'').collect()),
+                '%' => {
+                    let separator = chars.by_ref().take_while(|&c| c != '~').collect();
+                    if !token.is_empty() {
+                        pieces.push(Piece::Tok(std::mem::take(&mut token)));
+                    }
+                    pieces.extend([Piece::Trailing(separator), Piece::Gap]);
+                    continue;
+                }
+                _ => {
+                    token.push(c);
+                    continue;
+                }
+            };
+            if !token.is_empty() {
+                pieces.push(Piece::Tok(std::mem::take(&mut token)));
+            }
+            pieces.push(piece);
+        }
+        if !token.is_empty() {
+            pieces.push(Piece::Tok(token));
+        }
+        pieces
+    }
+
+    /// How pieces are laid out: indices into `GAPS`, and choices of quote
+    /// style, trailing separators, and member separators
+    #[derive(Debug, Clone)]
+    struct Layout {
+        gaps: Vec<usize>,
+        flags: Vec<bool>,
+    }
+
+    fn layouts() -> impl Strategy<Value = Layout> {
+        (
+            prop::collection::vec(0..GAPS.len(), 1..40),
+            prop::collection::vec(any::<bool>(), 1..16),
+        )
+            .prop_map(|(gaps, flags)| Layout { gaps, flags })
+    }
+
+    const GAPS: &[&str] = &[
+        "",
+        " ",
+        "  ",
+        "\n",
+        "\n    ",
+        "\t",
+        " /* note */ ",
+        " // note\n",
+    ];
+
+    /// Lexical rules of a language that limit layout changes
+    struct Syntax {
+        /// Whether strings may be single-quoted
+        single_quotes: bool,
+        /// Characters a line may end with without changing what follows
+        /// (automatic semicolons), or `None` if lines may end anywhere
+        line_ends: Option<&'static str>,
+    }
+
+    const TS: Syntax = Syntax {
+        single_quotes: true,
+        line_ends: Some("({[,;:|="),
+    };
+    const GO: Syntax = Syntax {
+        single_quotes: false,
+        line_ends: Some("({,;"),
+    };
+    const JAVA: Syntax = Syntax {
+        single_quotes: false,
+        line_ends: None,
+    };
+
+    /// Render a template in its plain layout (`None`) or a varied one
+    fn render(template: &str, layout: Option<&Layout>, syntax: &Syntax) -> String {
+        let mut out = String::new();
+        let (mut gap, mut flag) = (0, 0);
+        for piece in pieces(template) {
+            let Some(layout) = layout else {
+                match piece {
+                    Piece::Tok(token) => out.push_str(&token),
+                    Piece::Str(text) => out.push_str(&format!("\"{}\"", text)),
+                    Piece::Space => out.push(' '),
+                    Piece::Sep => out.push(';'),
+                    Piece::Gap | Piece::Trailing(_) => {}
+                }
+                continue;
+            };
+            let mut next_flag = || {
+                flag += 1;
+                layout.flags[flag % layout.flags.len()]
+            };
+            match piece {
+                Piece::Tok(token) => out.push_str(&token),
+                Piece::Str(text) if syntax.single_quotes && next_flag() => {
+                    out.push_str(&format!("'{}'", text))
+                }
+                Piece::Str(text) => out.push_str(&format!("\"{}\"", text)),
+                Piece::Trailing(separator) if next_flag() => out.push_str(&separator),
+                Piece::Trailing(_) => {}
+                Piece::Sep if next_flag() => out.push(','),
+                Piece::Sep => out.push(';'),
+                Piece::Gap | Piece::Space => {
+                    gap += 1;
+                    let mut choice = GAPS[layout.gaps[gap % layout.gaps.len()]];
+                    let line_end = |ends: &str| out.ends_with(|c| ends.contains(c));
+                    if choice.contains('\n') && !syntax.line_ends.is_none_or(line_end) {
+                        choice = " /* note */ ";
+                    }
+                    if choice.is_empty() && piece == Piece::Space {
+                        choice = " ";
+                    }
+                    out.push_str(choice);
+                }
+            }
+        }
+        out
+    }
+
+    /// A TypeScript type, by kind
+    fn ts_type(kind: u8) -> &'static str {
+        match kind % 4 {
+            0 => "string",
+            1 => "Array<~number~>",
+            2 => "'read'~|~'write'",
+            _ => "{~id~:~string~$~}",
+        }
+    }
+
+    /// Parameters of the given kinds, separated by commas with a trailing
+    /// comma (`trailing`) that may be left out
+    fn params(params: Vec<String>, trailing: &str) -> String {
+        match params.is_empty() {
+            true => String::new(),
+            false => format!("{}{}~", params.join("~,~"), trailing),
+        }
+    }
+
+    /// `export function load(...): T {}` with parameters of the given kinds
+    fn ts_function(kinds: &[u8], return_kind: u8) -> String {
+        const NAMES: &[&str] = &["a", "b", "c", "d"];
+        let list = kinds
+            .iter()
+            .zip(NAMES)
+            .map(|(kind, name)| match kind % 3 {
+                0 => format!("{}~:~{}", name, ts_type(kind / 3)),
+                1 => format!("{}~?:~{}", name, ts_type(kind / 3)),
+                _ => format!("{}~=~'none'", name),
+            })
+            .collect();
+        format!(
+            "export function load~(~{})~:~{}~{{~}}",
+            params(list, "%,"),
+            ts_type(return_kind)
+        )
+    }
+
+    /// `export interface User { ... }` with members of the given kinds
+    fn ts_interface(kinds: &[u8]) -> String {
+        const NAMES: &[&str] = &["id", "name", "mode", "tags"];
+        let members: Vec<String> = kinds
+            .iter()
+            .zip(NAMES)
+            .map(|(kind, name)| {
+                let colon = if kind % 2 == 0 { ":" } else { "?:" };
+                format!("{}{}~{}", name, colon, ts_type(kind / 2))
+            })
+            .collect();
+        format!("export interface User~{{~{}%;~}}", members.join("~$~"))
+    }
+
+    /// A class whose decorators take string arguments
+    const TS_DECORATED_CLASS: &str = "@Controller(~'users'~) export class Users~{~@Get(~':id'~) \
+        find(~@Param(~'id'~) id:~string~):~string~{~return id~}~}";
+
+    /// `func Login(...) (*User, error) {}` with parameters of the given
+    /// kinds; only the last one may be variadic
+    fn go_function(kinds: &[u8], variadic: bool) -> String {
+        const NAMES: &[&str] = &["user", "password", "attempts", "opts"];
+        let mut list: Vec<String> = kinds
+            .iter()
+            .zip(NAMES)
+            .map(|(kind, name)| match kind % 2 {
+                0 => format!("{} string", name),
+                _ => format!("{} map[~string~]int", name),
+            })
+            .collect();
+        if variadic {
+            list.push(format!("{} ...Option", NAMES[list.len()]));
+        }
+        // A trailing comma needs the closing parenthesis on the next line
+        format!(
+            "package auth\n\nfunc Login(~{}) (~*User~,~error~) {{}}\n",
+            params(list, "%,\n")
+        )
+    }
+
+    /// `type User struct { ... }` with the given number of fields
+    fn go_struct(fields: usize) -> String {
+        const FIELDS: &[&str] = &["Name string", "Email string", "Age int"];
+        format!(
+            "package auth\n\ntype User struct~{{~{}~}}\n",
+            FIELDS[..fields].join("~;~")
+        )
+    }
+
+    /// A Java class with an annotated method taking parameters of the given
+    /// kinds
+    fn java_method(kinds: &[u8]) -> String {
+        const NAMES: &[&str] = &["user", "password", "attempts", "roles"];
+        let list: Vec<String> = kinds
+            .iter()
+            .zip(NAMES)
+            .map(|(kind, name)| match kind % 3 {
+                0 => format!("String {}", name),
+                1 => format!("final int {}", name),
+                _ => format!("List<~Role~> {}", name),
+            })
+            .collect();
+        format!(
+            "public class Auth~{{~@Path(~value = \"/login\"~) public Session login(~{}~) \
+             throws AuthException~{{~}}~}}",
+            list.join("~,~")
+        )
+    }
+
+    /// Signature text and hash of a symbol in a file
+    fn signature_of(file_path: &str, code: &str, name: &str) -> (String, String) {
+        let analyzer = AstAnalyzerInternal::new();
+        let result = analyzer.analyze_file(file_path, code);
+        assert!(
+            result.errors.is_empty(),
+            "{:?} in:\n{}",
+            result.errors,
+            code
+        );
+        let symbol = result
+            .symbols
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap_or_else(|| panic!("no {} in:\n{}", name, code));
+        let signature = analyzer.extract_signature(symbol);
+        let hash = SignatureHasher::new().hash(signature.clone()).hash;
+        (signature.signature_text, hash)
+    }
+
+    /// Assert that a varied layout of a declaration has the signature and
+    /// hash of its plain layout
+    fn assert_stable(file_path: &str, name: &str, template: &str, layout: &Layout) {
+        let syntax = match file_path.rsplit('.').next() {
+            Some("go") => &GO,
+            Some("java") => &JAVA,
+            _ => &TS,
+        };
+        let plain = render(template, None, syntax);
+        let varied = render(template, Some(layout), syntax);
+        assert_eq!(
+            signature_of(file_path, &varied, name),
+            signature_of(file_path, &plain, name),
+            "\n{}\nvs\n{}",
+            varied,
+            plain
+        );
+    }
+
+    #[test]
+    fn test_templates() {
+        use Piece::*;
+        assert_eq!(
+            pieces("f(~a:~'x'%,~) $"),
+            [
+                Tok("f(".to_string()),
+                Gap,
+                Tok("a:".to_string()),
+                Gap,
+                Str("x".to_string()),
+                Trailing(",".to_string()),
+                Gap,
+                Tok(")".to_string()),
+                Space,
+                Sep
+            ]
+        );
+        assert_eq!(render("f(~a:~'x'%,~) $", None, &TS), "f(a:\"x\") ;");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_ts_function_layout_is_not_drift(
+            params in prop::collection::vec(0..12u8, 0..4),
+            return_kind in 0..4u8,
+            layout in layouts(),
+        ) {
+            assert_stable("src/a.ts", "load", &ts_function(&params, return_kind), &layout);
+        }
+
+        #[test]
+        fn test_ts_interface_layout_is_not_drift(
+            members in prop::collection::vec(0..8u8, 1..4),
+            layout in layouts(),
+        ) {
+            assert_stable("src/a.ts", "User", &ts_interface(&members), &layout);
+        }
+
+        #[test]
+        fn test_ts_decorator_quoting_is_not_drift(layout in layouts()) {
+            assert_stable("src/a.ts", "Users", TS_DECORATED_CLASS, &layout);
+        }
+
+        #[test]
+        fn test_go_layout_is_not_drift(
+            params in prop::collection::vec(0..2u8, 0..3),
+            variadic in any::<bool>(),
+            fields in 0..4usize,
+            layout in layouts(),
+        ) {
+            assert_stable("pkg/auth/auth.go", "Login", &go_function(&params, variadic), &layout);
+            assert_stable("pkg/auth/auth.go", "User", &go_struct(fields), &layout);
+        }
+
+        #[test]
+        fn test_java_layout_is_not_drift(
+            params in prop::collection::vec(0..3u8, 0..4),
+            layout in layouts(),
+        ) {
+            assert_stable("Auth.java", "Auth.login", &java_method(&params), &layout);
+        }
+    }
 }
@@ -0,0 +1,124 @@
+//! Ignore directives in source code
+//!
+//! Two comment directives exclude code from analysis and drift detection:
+//!
+//! - `// sintesi-ignore-file` anywhere in a file excludes the whole file. Any
+//!   comment style works (`//`, `#`, `--`, `/* */`), so the directive also
+//!   applies to SQL, YAML, and protobuf files.
+//! - `// sintesi-ignore-next-symbol` excludes the next TypeScript/JavaScript
+//!   or Python declaration.
+//!
+//! Both accept an optional reason after the directive, separated by a colon
+//! or whitespace (`// sintesi-ignore-next-symbol: kept for v1 clients`). The
+//! reason is carried into drift reports so exclusions stay visible.
+
+use serde::{Deserialize, Serialize};
+
+/// Directive excluding a whole file
+pub const IGNORE_FILE: &str = "sintesi-ignore-file";
+
+/// Directive excluding the next declaration
+pub const IGNORE_NEXT_SYMBOL: &str = "sintesi-ignore-next-symbol";
+
+/// A file or symbol excluded by an ignore directive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnoredItem {
+    /// File containing the directive
+    pub file_path: String,
+    /// Name of the ignored symbol, or `None` when the whole file is ignored
+    pub symbol_name: Option<String>,
+    /// Reason given after the directive
+    pub reason: Option<String>,
+}
+
+impl IgnoredItem {
+    /// Code reference this item excludes (`file#symbol`, or the file path)
+    pub fn code_ref(&self) -> String {
+        match &self.symbol_name {
+            Some(name) => format!("{}#{}", self.file_path, name),
+            None => self.file_path.clone(),
+        }
+    }
+}
+
+/// Ignore directives found in a file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IgnoreDirectives {
+    /// Set when the file carries `sintesi-ignore-file`, with its reason
+    pub file: Option<Option<String>>,
+    /// Byte offset of each `sintesi-ignore-next-symbol` line end, with its reason
+    pub next_symbol: Vec<(u32, Option<String>)>,
+}
+
+impl IgnoreDirectives {
+    /// Scan a file's comment lines for directives
+    pub fn parse(content: &str) -> Self {
+        let mut directives = Self::default();
+        let mut offset = 0;
+
+        for line in content.split_inclusive('\n') {
+            offset += line.len();
+            let Some(comment) = comment_text(line.trim()) else {
+                continue;
+            };
+            if let Some(reason) = directive(comment, IGNORE_NEXT_SYMBOL) {
+                directives.next_symbol.push((offset as u32, reason));
+            } else if let Some(reason) = directive(comment, IGNORE_FILE) {
+                directives.file.get_or_insert(reason);
+            }
+        }
+
+        directives
+    }
+}
+
+/// Text of a whole-line comment, after its marker
+fn comment_text(line: &str) -> Option<&str> {
+    ["//", "/*", "#", "--"]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+        .map(|text| text.trim_start_matches(['*', '/', '-', '#']).trim())
+}
+
+/// Match a directive keyword at the start of a comment, returning its reason
+fn directive(comment: &str, keyword: &str) -> Option<Option<String>> {
+    let rest = comment.strip_prefix(keyword)?;
+    // `sintesi-ignore-filename` is not a directive
+    if rest.starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '_') {
+        return None;
+    }
+    let reason = rest
+        .trim()
+        .trim_end_matches("*/")
+        .trim()
+        .trim_start_matches(':')
+        .trim();
+    Some((!reason.is_empty()).then(|| reason.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_directives() {
+        let content = "// sintesi-ignore-file: generated client\nconst a = 1;\n/* sintesi-ignore-next-symbol */\nfunction b() {}\n# sintesi-ignore-next-symbol legacy\n";
+        let directives = IgnoreDirectives::parse(content);
+
+        assert_eq!(directives.file, Some(Some("generated client".to_string())));
+        assert_eq!(
+            directives.next_symbol,
+            [(87, None), (139, Some("legacy".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_directive_must_be_whole_comment_line() {
+        let content = "const s = '// sintesi-ignore-file';\n// sintesi-ignore-filename\n";
+        assert_eq!(
+            IgnoreDirectives::parse(content),
+            IgnoreDirectives::default()
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! Java analyzer
+//!
+//! A `LanguageBackend` extracting the types of `.java` files and their
+//! members with the tree-sitter Java grammar:
+//!
+//! - Classes and records as `Class`, interfaces and annotation types as
+//!   `Interface`, and enums as `Enum` (with their constants in the
+//!   signature); nested types are qualified with their enclosing type
+//!   (`User.Builder`)
+//! - Methods and constructors as `Function`, fields as `Variable` (`Const`
+//!   when `static final`), all qualified with their type (`User.login`)
+//!
+//! Type signatures stop before the body, so each member is tracked on its
+//! own. Annotations stay in signatures; Javadoc does not. Public members of
+//! public types are exported, as are interface members not marked
+//! `private`. `@Deprecated` or a `@deprecated` Javadoc tag marks the symbol
+//! deprecated.
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::backend::{
+    comments_before, field, parse, render, symbol, text, type_params, LanguageBackend,
+};
+use super::diagnostics::error_messages;
+use crate::types::SymbolType;
+use tree_sitter::Node;
+
+/// The Java backend
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JavaBackend;
+
+impl LanguageBackend for JavaBackend {
+    fn name(&self) -> &str {
+        "Java"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["java"]
+    }
+
+    fn analyze(&self, file_path: &str, content: &str) -> AnalysisResult {
+        let mut symbols = Vec::new();
+        let (tree, diagnostics) = parse(tree_sitter_java::LANGUAGE.into(), file_path, content);
+        if let Some(tree) = tree {
+            let mut extractor = JavaExtractor {
+                file_path,
+                content,
+                symbols: &mut symbols,
+            };
+            extractor.members(tree.root_node(), "", true, false);
+        }
+
+        AnalysisResult {
+            symbols,
+            errors: error_messages(&diagnostics),
+            diagnostics,
+            ignored: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+struct JavaExtractor<'a> {
+    file_path: &'a str,
+    content: &'a str,
+    symbols: &'a mut Vec<SymbolInfo>,
+}
+
+impl JavaExtractor<'_> {
+    /// Extract the declarations in a program or type body
+    ///
+    /// `prefix` qualifies member names (`User.`), `public` is whether the
+    /// enclosing type is exported, and `interface` whether members are
+    /// implicitly public.
+    fn members(&mut self, body: Node, prefix: &str, public: bool, interface: bool) {
+        let mut cursor = body.walk();
+        for node in body.named_children(&mut cursor) {
+            let modifiers = Modifiers::of(node, self.content);
+            let exported = public && !modifiers.private && (modifiers.public || interface);
+            match node.kind() {
+                "class_declaration"
+                | "record_declaration"
+                | "interface_declaration"
+                | "annotation_type_declaration"
+                | "enum_declaration" => {
+                    let Some(name) = field(node, "name", self.content) else {
+                        continue;
+                    };
+                    let name = format!("{}{}", prefix, name);
+                    let (symbol_type, members_public) = match node.kind() {
+                        "interface_declaration" | "annotation_type_declaration" => {
+                            (SymbolType::Interface, true)
+                        }
+                        "enum_declaration" => (SymbolType::Enum, false),
+                        _ => (SymbolType::Class, false),
+                    };
+                    let body = node.child_by_field_name("body");
+                    let end = body.map_or(node.end_byte(), |body| body.start_byte());
+                    let mut signature = render(node, self.content, end, false);
+                    if let Some(body) = body.filter(|_| symbol_type == SymbolType::Enum) {
+                        let constants = enum_constants(body, self.content);
+                        signature.push_str(&format!(" {{ {} }}", constants.join(", ")));
+                    }
+                    self.push(
+                        node,
+                        name.clone(),
+                        symbol_type,
+                        signature,
+                        exported,
+                        &modifiers,
+                    );
+
+                    let Some(mut body) = body else {
+                        continue;
+                    };
+                    // Enum members follow the constants
+                    let mut cursor = body.walk();
+                    if let Some(declarations) = body
+                        .named_children(&mut cursor)
+                        .find(|child| child.kind() == "enum_body_declarations")
+                    {
+                        body = declarations;
+                    }
+                    self.members(body, &format!("{}.", name), exported, members_public);
+                }
+                "method_declaration"
+                | "constructor_declaration"
+                | "compact_constructor_declaration" => {
+                    let Some(name) = field(node, "name", self.content) else {
+                        continue;
+                    };
+                    let end = node
+                        .child_by_field_name("body")
+                        .map_or(node.end_byte(), |body| body.start_byte());
+                    let signature = render(node, self.content, end, false);
+                    let name = format!("{}{}", prefix, name);
+                    self.push(
+                        node,
+                        name,
+                        SymbolType::Function,
+                        signature,
+                        exported,
+                        &modifiers,
+                    );
+                }
+                "field_declaration" | "constant_declaration" => {
+                    let symbol_type = match node.kind() == "constant_declaration"
+                        || (modifiers.is_static && modifiers.is_final)
+                    {
+                        true => SymbolType::Const,
+                        false => SymbolType::Variable,
+                    };
+                    let signature = render(node, self.content, node.end_byte(), false);
+                    let mut cursor = node.walk();
+                    for declarator in node.children_by_field_name("declarator", &mut cursor) {
+                        let Some(name) = field(declarator, "name", self.content) else {
+                            continue;
+                        };
+                        let name = format!("{}{}", prefix, name);
+                        let signature = signature.clone();
+                        self.push(node, name, symbol_type, signature, exported, &modifiers);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn push(
+        &mut self,
+        node: Node,
+        name: String,
+        symbol_type: SymbolType,
+        signature: String,
+        is_exported: bool,
+        modifiers: &Modifiers,
+    ) {
+        let deprecated = modifiers.deprecated
+            || comments_before(node, self.content)
+                .iter()
+                .any(|comment| comment.contains("@deprecated"));
+        self.symbols.push(SymbolInfo {
+            type_params: type_params(node, self.content),
+            ..symbol(
+                self.file_path,
+                node,
+                name,
+                symbol_type,
+                signature,
+                is_exported,
+                deprecated,
+            )
+        });
+    }
+}
+
+/// Modifiers of a declaration
+#[derive(Debug, Default)]
+struct Modifiers {
+    public: bool,
+    private: bool,
+    is_static: bool,
+    is_final: bool,
+    /// Annotated `@Deprecated`
+    deprecated: bool,
+}
+
+impl Modifiers {
+    fn of(node: Node, content: &str) -> Self {
+        let mut modifiers = Self::default();
+        let mut cursor = node.walk();
+        let Some(list) = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "modifiers")
+        else {
+            return modifiers;
+        };
+        let mut cursor = list.walk();
+        for modifier in list.children(&mut cursor) {
+            match modifier.kind() {
+                "public" => modifiers.public = true,
+                "private" => modifiers.private = true,
+                "static" => modifiers.is_static = true,
+                "final" => modifiers.is_final = true,
+                "marker_annotation" | "annotation" => {
+                    let name = field(modifier, "name", content).unwrap_or("");
+                    modifiers.deprecated |= name.rsplit('.').next() == Some("Deprecated");
+                }
+                _ => {}
+            }
+        }
+        modifiers
+    }
+}
+
+/// Names of the constants of an enum body
+fn enum_constants<'s>(body: Node, content: &'s str) -> Vec<&'s str> {
+    let mut cursor = body.walk();
+    body.named_children(&mut cursor)
+        .filter(|child| child.kind() == "enum_constant")
+        .filter_map(|constant| constant.child_by_field_name("name"))
+        .map(|name| text(name, content))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = r#"package com.acme.auth;
+
+import java.util.List;
+
+/** Authenticates users. */
+public class AuthService extends BaseService implements Service {
+    public static final int MAX_ATTEMPTS = 3;
+    private final UserStore store;
+
+    public AuthService(UserStore store) {
+        this.store = store;
+    }
+
+    /**
+     * Log a user in.
+     * @deprecated use {@link #login(Credentials)}
+     */
+    public Session login(
+        String user,
+        String password
+    ) throws AuthException {
+        return store.find(user).open(password);
+    }
+
+    @Override
+    public <T extends Role> List<T> roles(User user) { return List.of(); }
+
+    void reset() {}
+
+    public static class Builder {
+        public Builder store(UserStore store) { return this; }
+    }
+}
+
+interface UserStore {
+    User find(String name);
+}
+
+public enum Role {
+    ADMIN, USER;
+
+    public boolean isAdmin() { return this == ADMIN; }
+}
+
+public record Credentials(String user, String password) {}
+"#;
+
+    fn find<'a>(result: &'a AnalysisResult, name: &str) -> &'a SymbolInfo {
+        result.symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_extracts_types_and_members() {
+        let result = JavaBackend.analyze("src/main/java/com/acme/auth/AuthService.java", CODE);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "AuthService",
+                "AuthService.MAX_ATTEMPTS",
+                "AuthService.store",
+                "AuthService.AuthService",
+                "AuthService.login",
+                "AuthService.roles",
+                "AuthService.reset",
+                "AuthService.Builder",
+                "AuthService.Builder.store",
+                "UserStore",
+                "UserStore.find",
+                "Role",
+                "Role.isAdmin",
+                "Credentials"
+            ]
+        );
+
+        let service = find(&result, "AuthService");
+        assert_eq!(
+            service.signature,
+            "public class AuthService extends BaseService implements Service"
+        );
+        assert_eq!(service.symbol_type, SymbolType::Class);
+        assert!(service.is_exported);
+
+        let login = find(&result, "AuthService.login");
+        assert_eq!(
+            login.signature,
+            "public Session login(String user, String password) throws AuthException"
+        );
+        assert!(login.deprecated);
+        assert_eq!(login.location.unwrap().start_line, 17);
+        assert_eq!(
+            find(&result, "AuthService.roles").signature,
+            "@Override public <T extends Role> List<T> roles(User user)"
+        );
+        let roles = &find(&result, "AuthService.roles").type_params;
+        assert_eq!(roles[0].name, "T");
+        assert_eq!(roles[0].constraint.as_deref(), Some("Role"));
+        assert!(login.type_params.is_empty());
+        assert_eq!(
+            find(&result, "AuthService.MAX_ATTEMPTS").symbol_type,
+            SymbolType::Const
+        );
+        assert_eq!(
+            find(&result, "AuthService.MAX_ATTEMPTS").signature,
+            "public static final int MAX_ATTEMPTS = 3"
+        );
+        assert!(!find(&result, "AuthService.store").is_exported);
+        assert!(!find(&result, "AuthService.reset").is_exported);
+        assert!(find(&result, "AuthService.Builder.store").is_exported);
+
+        // Package-private interface, implicitly public members
+        assert!(!find(&result, "UserStore").is_exported);
+        assert_eq!(
+            find(&result, "UserStore.find").signature,
+            "User find(String name)"
+        );
+
+        let role = find(&result, "Role");
+        assert_eq!(role.symbol_type, SymbolType::Enum);
+        assert_eq!(role.signature, "public enum Role { ADMIN, USER }");
+        assert_eq!(
+            find(&result, "Credentials").signature,
+            "public record Credentials(String user, String password)"
+        );
+    }
+}
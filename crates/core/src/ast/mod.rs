@@ -2,15 +2,68 @@
 //!
 //! This module handles:
 //! - TypeScript/JavaScript AST analysis
-//! - Code signature extraction
+//! - Incremental analysis cache keyed by file content hash
+//! - ES module vs. CommonJS script detection
+//! - Structured parse diagnostics with line/column spans
+//! - `sintesi-ignore-file` / `sintesi-ignore-next-symbol` directives
+//! - Config-driven symbol filters (kinds, names, paths)
+//! - HTTP route extraction (Express, Fastify, NestJS)
+//! - Test-to-symbol mapping
+//! - Re-export chain resolution for barrel files
+//! - OpenAPI specification analysis
+//! - SQL DDL (migration/schema) analysis
+//! - Protobuf/gRPC IDL analysis
+//! - Python function and class analysis
+//! - Rust item and impl analysis
+//! - Pluggable language backends, with tree-sitter Go and Java analysis
+//! - Vue and Svelte component props and events
+//! - React function component props
+//! - JSON Schema and well-known config file analysis
+//! - Code signature extraction, printed canonically from the AST
+//! - Source map attribution of generated code to its original files
 //! - Signature hashing (SHA256)
+//! - Symbol IDs from file, name, and kind, and path-independent symbol keys
+//! - Public API diffs: added, removed, and changed symbols, with their
+//!   parameter and return type changes
 //! - Drift detection by comparing hashes
 
 pub mod analyzer;
+mod arena;
+pub mod backend;
+pub mod cache;
+pub mod config;
+pub mod diagnostics;
+pub mod diff;
+pub mod filter;
+pub mod go;
 pub mod hasher;
+pub mod ignore;
+pub mod java;
+pub mod module_kind;
+pub mod openapi;
+mod printer;
+pub mod proto;
+pub mod python;
+pub mod react;
+pub mod reexports;
+pub mod routes;
+pub mod rust;
+pub mod sfc;
+pub mod sourcemap;
+pub mod sql;
+pub mod symbol_id;
+pub mod test_mapping;
 
 
 // Re-export commonly used types
-pub use analyzer::{AstAnalyzerInternal, SymbolInfo, AnalysisResult};
+pub use analyzer::{
+    AnalysisResult, AnalyzerOptions, AstAnalyzerInternal, DuplicateNames, ReleaseTag, SymbolInfo,
+    DEFAULT_EXPORT,
+};
+pub use cache::{AstCache, AST_CACHE_DIR, AST_CACHE_FILE};
+pub use diagnostics::{Diagnostic, DiagnosticSeverity};
+pub use filter::SymbolFilter;
+pub use ignore::IgnoredItem;
 pub use hasher::SignatureHasher;
+pub use symbol_id::{symbol_id, symbol_key};
 
@@ -0,0 +1,134 @@
+//! JavaScript module kind detection
+//!
+//! Decides whether a source file is parsed as an ES module or as a script
+//! (CommonJS), following Node's rules:
+//!
+//! - `.mjs`/`.mts` are always modules, `.cjs`/`.cts` always scripts
+//! - `.js`/`.jsx` follow the nearest `package.json` `type` field
+//! - Extensionless files with a `node` shebang are JavaScript and follow the
+//!   same `type` field
+//!
+//! Without a `type` field the kind is inferred from the syntax, so `require`
+//! calls and top-level `await` both parse without spurious errors.
+
+use crate::provider::ContentProvider;
+use oxc_span::SourceType;
+use std::path::Path;
+
+/// Module system declared by a `package.json` `type` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageType {
+    /// `"type": "module"`
+    Module,
+    /// `"type": "commonjs"`
+    CommonJs,
+}
+
+impl PackageType {
+    /// Read the `type` field of a `package.json` document
+    pub fn from_package_json(content: &str) -> Option<Self> {
+        let json: serde_json::Value = serde_json::from_str(content).ok()?;
+        match json.get("type")?.as_str()? {
+            "module" => Some(Self::Module),
+            "commonjs" => Some(Self::CommonJs),
+            _ => None,
+        }
+    }
+}
+
+/// Find the `type` of the nearest `package.json` above a file
+///
+/// `file_path` is relative to the provider's root; the search stops at the
+/// root. The nearest `package.json` wins even if it has no `type` field.
+pub fn nearest_package_type(
+    provider: &dyn ContentProvider,
+    file_path: &Path,
+) -> Option<PackageType> {
+    for dir in file_path.ancestors().skip(1) {
+        if let Ok(content) = provider.read(&dir.join("package.json")) {
+            return PackageType::from_package_json(&content);
+        }
+    }
+    None
+}
+
+/// Check whether the content starts with a shebang that runs node
+pub fn has_node_shebang(content: &str) -> bool {
+    content
+        .strip_prefix("#!")
+        .and_then(|rest| rest.lines().next())
+        .is_some_and(|line| line.split_whitespace().any(|word| word.ends_with("node")))
+}
+
+/// Determine the Oxc source type for a file
+///
+/// Returns `None` for files that are not TypeScript/JavaScript.
+pub fn source_type_for(
+    file_path: &str,
+    content: &str,
+    package_type: Option<PackageType>,
+) -> Option<SourceType> {
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+
+    let javascript = match extension {
+        "ts" => return Some(SourceType::ts()),
+        "tsx" => return Some(SourceType::tsx()),
+        "mts" => return Some(SourceType::ts().with_module(true)),
+        "cts" => return Some(SourceType::ts().with_module(false)),
+        "mjs" => return Some(SourceType::mjs()),
+        "cjs" => return Some(SourceType::cjs()),
+        "js" => SourceType::unambiguous(),
+        "jsx" => SourceType::unambiguous().with_jsx(true),
+        "" if has_node_shebang(content) => SourceType::unambiguous(),
+        _ => return None,
+    };
+
+    Some(match package_type {
+        Some(PackageType::Module) => javascript.with_module(true),
+        Some(PackageType::CommonJs) => javascript.with_script(true),
+        None => javascript,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::MemoryProvider;
+
+    #[test]
+    fn test_source_type_by_extension_and_package_type() {
+        assert!(source_type_for("a.mjs", "", None).unwrap().is_module());
+        assert!(source_type_for("a.cjs", "", Some(PackageType::Module))
+            .unwrap()
+            .is_script());
+        assert!(source_type_for("a.js", "", Some(PackageType::Module))
+            .unwrap()
+            .is_module());
+        assert!(source_type_for("a.js", "", Some(PackageType::CommonJs))
+            .unwrap()
+            .is_script());
+        assert!(source_type_for("a.js", "", None).unwrap().is_unambiguous());
+        assert!(source_type_for("bin/cli", "#!/usr/bin/env node\n", None).is_some());
+        assert!(source_type_for("bin/run", "#!/bin/sh\n", None).is_none());
+        assert!(source_type_for("README.md", "", None).is_none());
+    }
+
+    #[test]
+    fn test_nearest_package_type() {
+        let mut provider = MemoryProvider::new();
+        provider.set("package.json", r#"{"type": "module"}"#);
+        provider.set("legacy/package.json", r#"{"name": "legacy"}"#);
+
+        assert_eq!(
+            nearest_package_type(&provider, Path::new("src/index.js")),
+            Some(PackageType::Module)
+        );
+        assert_eq!(
+            nearest_package_type(&provider, Path::new("legacy/lib/index.js")),
+            None
+        );
+    }
+}
@@ -0,0 +1,470 @@
+//! OpenAPI specification analyzer
+//!
+//! This module extracts operations and component schemas from OpenAPI
+//! (and legacy Swagger) documents written in YAML or JSON. Each operation
+//! and schema becomes a symbol with a deterministic signature, so anchors
+//! can reference `openapi.yaml#getUser` and drift when the spec changes.
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::diagnostics::Diagnostic;
+use crate::types::SymbolType;
+use serde_json::{Map, Value};
+use std::path::Path;
+
+/// HTTP methods that may appear as operation keys under a path item
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Check whether a file looks like an OpenAPI/Swagger document
+///
+/// Only YAML and JSON files are considered, and the content must declare a
+/// top-level `openapi` or `swagger` version key.
+pub fn is_openapi_document(file_path: &str, content: &str) -> bool {
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+
+    match extension {
+        "yaml" | "yml" => content
+            .lines()
+            .any(|line| line.starts_with("openapi:") || line.starts_with("swagger:")),
+        "json" => serde_json::from_str::<Value>(content).is_ok_and(|document| {
+            document
+                .as_object()
+                .is_some_and(|root| root.contains_key("openapi") || root.contains_key("swagger"))
+        }),
+        _ => false,
+    }
+}
+
+/// Analyze an OpenAPI document and extract operations and schemas
+///
+/// # Arguments
+/// * `file_path` - Path of the spec file (used for format detection and symbol metadata)
+/// * `content` - Raw YAML or JSON content
+///
+/// # Returns
+/// AnalysisResult with one `Operation` symbol per path/method pair and one
+/// `Schema` symbol per entry in `components.schemas` (or `definitions`)
+pub fn analyze_openapi(file_path: &str, content: &str) -> AnalysisResult {
+    let document = match parse_document(file_path, content) {
+        Ok(doc) => doc,
+        Err(diagnostic) => {
+            return AnalysisResult {
+                symbols: Vec::new(),
+                errors: vec![format!("Parse error: {}", diagnostic.message)],
+                diagnostics: vec![*diagnostic],
+                ignored: Vec::new(),
+                skipped: Vec::new(),
+            };
+        }
+    };
+
+    let mut symbols = Vec::new();
+
+    if let Some(paths) = document.get("paths").and_then(Value::as_object) {
+        for (path, item) in paths {
+            let Some(item) = item.as_object() else {
+                continue;
+            };
+            let shared_params = item.get("parameters");
+
+            for method in HTTP_METHODS {
+                if let Some(operation) = item.get(*method).and_then(Value::as_object) {
+                    symbols.push(operation_symbol(
+                        file_path,
+                        path,
+                        method,
+                        operation,
+                        shared_params,
+                    ));
+                }
+            }
+        }
+    }
+
+    let schemas = document
+        .pointer("/components/schemas")
+        .or_else(|| document.get("definitions"))
+        .and_then(Value::as_object);
+
+    if let Some(schemas) = schemas {
+        for (name, schema) in schemas {
+            symbols.push(SymbolInfo {
+                name: name.clone(),
+                symbol_type: SymbolType::Schema,
+                signature: format!("schema {} {}", name, render_schema(schema)),
+                is_exported: true,
+                file_path: file_path.to_string(),
+                deprecated: schema
+                    .get("deprecated")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+                release_tag: None,
+                location: None,
+                type_params: Vec::new(),
+            });
+        }
+    }
+
+    AnalysisResult {
+        symbols,
+        errors: Vec::new(),
+        diagnostics: Vec::new(),
+        ignored: Vec::new(),
+        skipped: Vec::new(),
+    }
+}
+
+fn parse_document(file_path: &str, content: &str) -> Result<Value, Box<Diagnostic>> {
+    if file_path.ends_with(".json") {
+        serde_json::from_str(content)
+            .map_err(|e| Box::new(Diagnostic::from_json(file_path, content, &e)))
+    } else {
+        serde_yaml::from_str(content)
+            .map_err(|e| Box::new(Diagnostic::from_yaml(file_path, content, &e)))
+    }
+}
+
+/// Build the symbol for a single operation
+///
+/// The symbol name is the `operationId` when present, otherwise
+/// `METHOD path` so that anonymous operations can still be anchored.
+fn operation_symbol(
+    file_path: &str,
+    path: &str,
+    method: &str,
+    operation: &Map<String, Value>,
+    shared_params: Option<&Value>,
+) -> SymbolInfo {
+    let method_upper = method.to_uppercase();
+    let name = operation
+        .get("operationId")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{} {}", method_upper, path));
+
+    let mut params = Vec::new();
+    for list in [shared_params, operation.get("parameters")]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(list) = list.as_array() {
+            params.extend(list.iter().map(render_parameter));
+        }
+    }
+
+    if let Some(body) = operation.get("requestBody") {
+        let required = body
+            .get("required")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        params.push(format!(
+            "body{}: {}",
+            if required { "" } else { "?" },
+            render_content(body.get("content"))
+        ));
+    }
+
+    let mut responses = Vec::new();
+    if let Some(map) = operation.get("responses").and_then(Value::as_object) {
+        for (status, response) in map {
+            let body = response.get("content").map(|c| render_content(Some(c)));
+            // Swagger 2.0 puts the schema directly on the response
+            let body = body.or_else(|| response.get("schema").map(render_schema));
+            match body {
+                Some(body) => responses.push(format!("{}: {}", status, body)),
+                None => responses.push(status.clone()),
+            }
+        }
+    }
+
+    let deprecated = operation
+        .get("deprecated")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let signature = format!(
+        "{}operation {} {} {}({}) -> {{ {} }}",
+        if deprecated { "deprecated " } else { "" },
+        name,
+        method_upper,
+        path,
+        params.join(", "),
+        responses.join(", ")
+    );
+
+    SymbolInfo {
+        name,
+        symbol_type: SymbolType::Operation,
+        signature,
+        is_exported: true,
+        file_path: file_path.to_string(),
+        deprecated,
+        release_tag: None,
+        location: None,
+        type_params: Vec::new(),
+    }
+}
+
+/// Render a parameter as `location name?: type`
+fn render_parameter(param: &Value) -> String {
+    if let Some(reference) = param.get("$ref").and_then(Value::as_str) {
+        return format!("ref {}", ref_name(reference));
+    }
+
+    let location = param.get("in").and_then(Value::as_str).unwrap_or("query");
+    let name = param.get("name").and_then(Value::as_str).unwrap_or("");
+    let required = param
+        .get("required")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    // OpenAPI 3 nests the type in `schema`; Swagger 2.0 inlines it
+    let ty = match param.get("schema") {
+        Some(schema) => render_schema(schema),
+        None => render_schema(param),
+    };
+
+    format!(
+        "{} {}{}: {}",
+        location,
+        name,
+        if required { "" } else { "?" },
+        ty
+    )
+}
+
+/// Render a `content` map as `media/type Schema | other/type Schema`
+fn render_content(content: Option<&Value>) -> String {
+    let Some(map) = content.and_then(Value::as_object) else {
+        return "unknown".to_string();
+    };
+
+    map.iter()
+        .map(|(media, media_type)| match media_type.get("schema") {
+            Some(schema) => format!("{} {}", media, render_schema(schema)),
+            None => media.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Render a JSON schema into a compact, TypeScript-like type expression
+fn render_schema(schema: &Value) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return ref_name(reference).to_string();
+    }
+
+    for (key, separator) in [("oneOf", " | "), ("anyOf", " | "), ("allOf", " & ")] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            return variants
+                .iter()
+                .map(render_schema)
+                .collect::<Vec<_>>()
+                .join(separator);
+        }
+    }
+
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+
+    let ty = schema.get("type").and_then(Value::as_str);
+    let rendered = match ty {
+        Some("array") => format!(
+            "Array<{}>",
+            schema
+                .get("items")
+                .map(render_schema)
+                .unwrap_or_else(|| "any".to_string())
+        ),
+        Some("object") | None if schema.get("properties").is_some() => render_object(schema),
+        Some(ty) => match schema.get("format").and_then(Value::as_str) {
+            Some(format) => format!("{}({})", ty, format),
+            None => ty.to_string(),
+        },
+        None => "any".to_string(),
+    };
+
+    let nullable = schema
+        .get("nullable")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if nullable {
+        format!("{} | null", rendered)
+    } else {
+        rendered
+    }
+}
+
+/// Render an object schema as `{ a: string, b?: number }`
+fn render_object(schema: &Value) -> String {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|list| list.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let fields: Vec<String> = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|props| {
+            props
+                .iter()
+                .map(|(name, prop)| {
+                    format!(
+                        "{}{}: {}",
+                        name,
+                        if required.contains(&name.as_str()) {
+                            ""
+                        } else {
+                            "?"
+                        },
+                        render_schema(prop)
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    format!("{{ {} }}", fields.join(", "))
+}
+
+/// Extract the trailing name from a JSON reference (`#/components/schemas/User` -> `User`)
+fn ref_name(reference: &str) -> &str {
+    reference.rsplit('/').next().unwrap_or(reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::AstAnalyzerInternal;
+
+    const SPEC: &str = r#"
+openapi: 3.0.3
+info:
+  title: Users
+  version: 1.0.0
+paths:
+  /users/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+        schema:
+          type: string
+    get:
+      operationId: getUser
+      parameters:
+        - name: verbose
+          in: query
+          schema:
+            type: boolean
+      responses:
+        '200':
+          description: ok
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/User'
+        '404':
+          description: missing
+  /users:
+    post:
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/User'
+      responses:
+        '201':
+          description: created
+components:
+  schemas:
+    User:
+      type: object
+      required: [id]
+      properties:
+        id:
+          type: string
+        tags:
+          type: array
+          items:
+            type: string
+"#;
+
+    #[test]
+    fn test_detects_openapi_documents() {
+        assert!(is_openapi_document("openapi.yaml", SPEC.trim_start()));
+        assert!(is_openapi_document("api.json", r#"{"openapi": "3.0.0"}"#));
+        assert!(!is_openapi_document("config.yaml", "name: test"));
+        assert!(!is_openapi_document("api.ts", SPEC));
+        // Only a top-level key counts, not the word anywhere in the file
+        let pkg = r#"{ "keywords": ["openapi"], "scripts": { "build": "tsc" } }"#;
+        assert!(!is_openapi_document("package.json", pkg));
+        let result = AstAnalyzerInternal::new().analyze_file("package.json", pkg);
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert!(names.contains(&"scripts.build"), "{:?}", names);
+    }
+
+    #[test]
+    fn test_extracts_operations_and_schemas() {
+        let result = analyze_openapi("openapi.yaml", SPEC);
+        assert!(result.errors.is_empty());
+
+        let get_user = result.symbols.iter().find(|s| s.name == "getUser").unwrap();
+        assert_eq!(get_user.symbol_type, SymbolType::Operation);
+        assert_eq!(
+            get_user.signature,
+            "operation getUser GET /users/{id}(path id: string, query verbose?: boolean) -> { 200: application/json User, 404 }"
+        );
+
+        let anonymous = result
+            .symbols
+            .iter()
+            .find(|s| s.name == "POST /users")
+            .unwrap();
+        assert!(anonymous.signature.contains("body: application/json User"));
+
+        let user = result.symbols.iter().find(|s| s.name == "User").unwrap();
+        assert_eq!(user.symbol_type, SymbolType::Schema);
+        assert_eq!(
+            user.signature,
+            "schema User { id: string, tags?: Array<string> }"
+        );
+    }
+
+    #[test]
+    fn test_spec_change_changes_signature() {
+        let before = analyze_openapi("openapi.yaml", SPEC);
+        let after = analyze_openapi(
+            "openapi.yaml",
+            &SPEC.replace("type: boolean", "type: integer"),
+        );
+
+        let sig = |r: &AnalysisResult| {
+            r.symbols
+                .iter()
+                .find(|s| s.name == "getUser")
+                .unwrap()
+                .signature
+                .clone()
+        };
+        assert_ne!(sig(&before), sig(&after));
+    }
+
+    #[test]
+    fn test_invalid_document_reports_error() {
+        let result = analyze_openapi("openapi.yaml", "openapi: [unclosed");
+        assert!(result.symbols.is_empty());
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.diagnostics[0].start.line, 0);
+    }
+}
@@ -0,0 +1,1116 @@
+//! Canonical printing of TypeScript/JavaScript signatures
+//!
+//! Signatures used to be slices of the source cleaned up with regexes, which
+//! left formatting in them: spacing inside template literal types, the
+//! leading `|` of a multi-line union, quote style, trailing separators. Any of
+//! these changed the hash and was reported as drift. The printer re-serializes
+//! declarations from their AST nodes instead, so signatures that differ only
+//! in formatting print identically:
+//!
+//! - Declarations, parameters, and types are printed node by node with one
+//!   spacing (`a: T`, `A | B`, `{ a: T; b(): U }`), double-quoted strings,
+//!   and no comments or trailing separators. Inline `import("...")`
+//!   qualifiers, which declaration emit adds, are dropped.
+//! - Common expressions (literals, calls, member access, object and array
+//!   literals, operators, function headers) are printed the same way. Other
+//!   expressions and function bodies are printed from their source tokens
+//!   (see `compact`), keeping a space only where one separates two words.
+
+use crate::types::TypeParam;
+use oxc_ast::ast::*;
+use oxc_span::GetSpan;
+
+/// Printer of AST nodes taken from `source`
+pub(crate) struct Printer<'s> {
+    source: &'s str,
+}
+
+impl<'s> Printer<'s> {
+    pub(crate) fn new(source: &'s str) -> Self {
+        Self { source }
+    }
+
+    // Declarations
+
+    /// Function header, without the body:
+    /// `async function name<T>(a: T): R`
+    pub(crate) fn function(&self, func: &Function) -> String {
+        let mut out = String::new();
+        if func.declare {
+            out.push_str("declare ");
+        }
+        if func.r#async {
+            out.push_str("async ");
+        }
+        out.push_str("function");
+        if func.generator {
+            out.push('*');
+        }
+        if let Some(id) = &func.id {
+            out.push(' ');
+            out.push_str(&id.name);
+        }
+        out.push_str(&self.callable(
+            func.type_parameters.as_deref(),
+            func.this_param.as_deref(),
+            &func.params,
+            func.return_type.as_deref(),
+        ));
+        out
+    }
+
+    /// Arrow function header, up to the arrow: `async <T>(a: T): R =>`
+    pub(crate) fn arrow(&self, arrow: &ArrowFunctionExpression) -> String {
+        let mut out = String::new();
+        if arrow.r#async {
+            out.push_str("async ");
+        }
+        out.push_str(&self.callable(
+            arrow.type_parameters.as_deref(),
+            None,
+            &arrow.params,
+            arrow.return_type.as_deref(),
+        ));
+        out.push_str(" =>");
+        out
+    }
+
+    /// Class method or accessor header, decorators included:
+    /// `@Get(":id") static async find<T>(id: T): R`
+    pub(crate) fn method(&self, method: &MethodDefinition) -> String {
+        let mut out = self.decorators(&method.decorators);
+        if let Some(accessibility) = method.accessibility {
+            out.push_str(accessibility.as_str());
+            out.push(' ');
+        }
+        if method.r#static {
+            out.push_str("static ");
+        }
+        if method.r#type == MethodDefinitionType::TSAbstractMethodDefinition {
+            out.push_str("abstract ");
+        }
+        if method.r#override {
+            out.push_str("override ");
+        }
+        let func = &method.value;
+        if func.r#async {
+            out.push_str("async ");
+        }
+        match method.kind {
+            MethodDefinitionKind::Get => out.push_str("get "),
+            MethodDefinitionKind::Set => out.push_str("set "),
+            MethodDefinitionKind::Constructor | MethodDefinitionKind::Method => {}
+        }
+        if func.generator {
+            out.push('*');
+        }
+        out.push_str(&self.property_key(&method.key, method.computed));
+        if method.optional {
+            out.push('?');
+        }
+        out.push_str(&self.callable(
+            func.type_parameters.as_deref(),
+            func.this_param.as_deref(),
+            &func.params,
+            func.return_type.as_deref(),
+        ));
+        out
+    }
+
+    /// `interface Name<T> extends Base { a: T; b(): void }`
+    pub(crate) fn interface(&self, decl: &TSInterfaceDeclaration) -> String {
+        let mut out = String::new();
+        if decl.declare {
+            out.push_str("declare ");
+        }
+        out.push_str("interface ");
+        out.push_str(&decl.id.name);
+        out.push_str(&self.type_parameters(decl.type_parameters.as_deref()));
+        if let Some(extends) = decl.extends.as_ref().filter(|e| !e.is_empty()) {
+            let heritage: Vec<String> = extends
+                .iter()
+                .map(|heritage| {
+                    self.expression(&heritage.expression)
+                        + &self.type_arguments(heritage.type_parameters.as_deref())
+                })
+                .collect();
+            out.push_str(" extends ");
+            out.push_str(&heritage.join(", "));
+        }
+        out.push(' ');
+        out.push_str(&self.members(&decl.body.body));
+        out
+    }
+
+    /// ` extends Base<T> implements Named, Sized<T>`, empty for classes
+    /// without heritage clauses
+    pub(crate) fn class_heritage(&self, class: &Class) -> String {
+        let mut out = String::new();
+        if let Some(super_class) = &class.super_class {
+            out.push_str(" extends ");
+            out.push_str(&self.expression(super_class));
+            out.push_str(&self.type_arguments(class.super_type_parameters.as_deref()));
+        }
+        if let Some(implements) = class.implements.as_ref().filter(|i| !i.is_empty()) {
+            let interfaces: Vec<String> = implements
+                .iter()
+                .map(|implemented| {
+                    self.type_name(&implemented.expression)
+                        + &self.type_arguments(implemented.type_parameters.as_deref())
+                })
+                .collect();
+            out.push_str(" implements ");
+            out.push_str(&interfaces.join(", "));
+        }
+        out
+    }
+
+    /// `type Name<T> = T[]`
+    pub(crate) fn type_alias(&self, decl: &TSTypeAliasDeclaration) -> String {
+        let mut out = String::new();
+        if decl.declare {
+            out.push_str("declare ");
+        }
+        out.push_str("type ");
+        out.push_str(&decl.id.name);
+        out.push_str(&self.type_parameters(decl.type_parameters.as_deref()));
+        out.push_str(" = ");
+        out.push_str(&self.ts_type(&decl.type_annotation));
+        out
+    }
+
+    /// `const enum Name { A, B = 1 }`
+    pub(crate) fn enumeration(&self, decl: &TSEnumDeclaration) -> String {
+        let mut out = String::new();
+        if decl.declare {
+            out.push_str("declare ");
+        }
+        if decl.r#const {
+            out.push_str("const ");
+        }
+        out.push_str("enum ");
+        out.push_str(&decl.id.name);
+        let members: Vec<String> = decl
+            .members
+            .iter()
+            .map(|member| {
+                let mut out = match &member.id {
+                    TSEnumMemberName::Identifier(ident) => ident.name.to_string(),
+                    TSEnumMemberName::String(string) => quote(&string.value),
+                };
+                if let Some(initializer) = &member.initializer {
+                    out.push_str(" = ");
+                    out.push_str(&self.expression(initializer));
+                }
+                out
+            })
+            .collect();
+        out.push(' ');
+        out.push_str(&braced(&members, ", "));
+        out
+    }
+
+    /// Variable declarator: `name: T = value`
+    pub(crate) fn declarator(&self, declarator: &VariableDeclarator) -> String {
+        let mut out = self.binding_pattern_kind(&declarator.id.kind);
+        if declarator.definite {
+            out.push('!');
+        }
+        out.push_str(&self.type_annotation(declarator.id.type_annotation.as_deref()));
+        if let Some(init) = &declarator.init {
+            out.push_str(" = ");
+            out.push_str(&self.expression(init));
+        }
+        out
+    }
+
+    /// Type parameters, parameters, and return type of a callable
+    fn callable(
+        &self,
+        type_parameters: Option<&TSTypeParameterDeclaration>,
+        this_param: Option<&TSThisParameter>,
+        params: &FormalParameters,
+        return_type: Option<&TSTypeAnnotation>,
+    ) -> String {
+        let mut out = self.type_parameters(type_parameters);
+        out.push_str(&self.params(this_param, params));
+        out.push_str(&self.type_annotation(return_type));
+        out
+    }
+
+    /// `(this: T, a: T, b?: U, ...rest: V[])`
+    fn params(&self, this_param: Option<&TSThisParameter>, params: &FormalParameters) -> String {
+        let mut parts = Vec::new();
+        if let Some(this_param) = this_param {
+            parts.push(format!(
+                "this{}",
+                self.type_annotation(this_param.type_annotation.as_deref())
+            ));
+        }
+        for param in &params.items {
+            let mut part = self.decorators(&param.decorators);
+            if let Some(accessibility) = param.accessibility {
+                part.push_str(accessibility.as_str());
+                part.push(' ');
+            }
+            if param.r#override {
+                part.push_str("override ");
+            }
+            if param.readonly {
+                part.push_str("readonly ");
+            }
+            part.push_str(&self.binding_pattern(&param.pattern));
+            parts.push(part);
+        }
+        if let Some(rest) = &params.rest {
+            parts.push(format!("...{}", self.binding_pattern(&rest.argument)));
+        }
+        format!("({})", parts.join(", "))
+    }
+
+    /// Binding with its optional marker and type: `{ a, b }?: T`
+    pub(crate) fn binding_pattern(&self, pattern: &BindingPattern) -> String {
+        let mut out = self.binding_pattern_kind(&pattern.kind);
+        if pattern.optional {
+            out.push('?');
+        }
+        out.push_str(&self.type_annotation(pattern.type_annotation.as_deref()));
+        out
+    }
+
+    fn binding_pattern_kind(&self, kind: &BindingPatternKind) -> String {
+        match kind {
+            BindingPatternKind::BindingIdentifier(ident) => ident.name.to_string(),
+            BindingPatternKind::ObjectPattern(object) => {
+                let mut parts: Vec<String> = object
+                    .properties
+                    .iter()
+                    .map(|property| {
+                        let value = self.binding_pattern(&property.value);
+                        if property.shorthand {
+                            value
+                        } else {
+                            format!(
+                                "{}: {}",
+                                self.property_key(&property.key, property.computed),
+                                value
+                            )
+                        }
+                    })
+                    .collect();
+                if let Some(rest) = &object.rest {
+                    parts.push(format!("...{}", self.binding_pattern(&rest.argument)));
+                }
+                braced(&parts, ", ")
+            }
+            BindingPatternKind::ArrayPattern(array) => {
+                let mut parts: Vec<String> = array
+                    .elements
+                    .iter()
+                    .map(|element| match element {
+                        Some(element) => self.binding_pattern(element),
+                        None => String::new(),
+                    })
+                    .collect();
+                if let Some(rest) = &array.rest {
+                    parts.push(format!("...{}", self.binding_pattern(&rest.argument)));
+                }
+                format!("[{}]", parts.join(", "))
+            }
+            BindingPatternKind::AssignmentPattern(assignment) => format!(
+                "{} = {}",
+                self.binding_pattern(&assignment.left),
+                self.expression(&assignment.right)
+            ),
+        }
+    }
+
+    /// Decorators, each followed by a space: `@Input() `
+    pub(crate) fn decorators(&self, decorators: &[Decorator]) -> String {
+        decorators
+            .iter()
+            .map(|decorator| format!("@{} ", self.expression(&decorator.expression)))
+            .collect()
+    }
+
+    /// Property name; computed keys keep their brackets (`[Symbol.iterator]`)
+    pub(crate) fn property_key(&self, key: &PropertyKey, computed: bool) -> String {
+        let name = match key {
+            PropertyKey::StaticIdentifier(ident) => ident.name.to_string(),
+            PropertyKey::PrivateIdentifier(ident) => format!("#{}", ident.name),
+            key => match key.as_expression() {
+                Some(expression) => self.expression(expression),
+                None => self.compact(key.span()),
+            },
+        };
+        match computed {
+            true => format!("[{}]", name),
+            false => name,
+        }
+    }
+
+    // Types
+
+    /// Type annotation as `: T`, empty without one
+    pub(crate) fn type_annotation(&self, annotation: Option<&TSTypeAnnotation>) -> String {
+        match annotation {
+            Some(annotation) => format!(": {}", self.ts_type(&annotation.type_annotation)),
+            None => String::new(),
+        }
+    }
+
+    /// `<const T extends U = D>`, empty without parameters
+    pub(crate) fn type_parameters(&self, params: Option<&TSTypeParameterDeclaration>) -> String {
+        match params {
+            Some(params) => {
+                let params: Vec<String> = params
+                    .params
+                    .iter()
+                    .map(|param| self.type_parameter(param))
+                    .collect();
+                format!("<{}>", params.join(", "))
+            }
+            None => String::new(),
+        }
+    }
+
+    /// Type parameters as structured data, printed like in `type_parameters`
+    pub(crate) fn type_params(
+        &self,
+        params: Option<&TSTypeParameterDeclaration>,
+    ) -> Vec<TypeParam> {
+        params.map_or_else(Vec::new, |params| {
+            params
+                .params
+                .iter()
+                .map(|param| TypeParam {
+                    name: param.name.name.to_string(),
+                    constraint: param.constraint.as_ref().map(|ty| self.ts_type(ty)),
+                    default: param.default.as_ref().map(|ty| self.ts_type(ty)),
+                })
+                .collect()
+        })
+    }
+
+    fn type_parameter(&self, param: &TSTypeParameter) -> String {
+        let mut out = String::new();
+        if param.r#const {
+            out.push_str("const ");
+        }
+        if param.r#in {
+            out.push_str("in ");
+        }
+        if param.out {
+            out.push_str("out ");
+        }
+        out.push_str(&param.name.name);
+        if let Some(constraint) = &param.constraint {
+            out.push_str(" extends ");
+            out.push_str(&self.ts_type(constraint));
+        }
+        if let Some(default) = &param.default {
+            out.push_str(" = ");
+            out.push_str(&self.ts_type(default));
+        }
+        out
+    }
+
+    /// `<A, B>`, empty without arguments
+    fn type_arguments(&self, args: Option<&TSTypeParameterInstantiation>) -> String {
+        match args {
+            Some(args) => format!("<{}>", self.types(&args.params, ", ")),
+            None => String::new(),
+        }
+    }
+
+    fn types(&self, types: &[TSType], separator: &str) -> String {
+        let types: Vec<String> = types.iter().map(|t| self.ts_type(t)).collect();
+        types.join(separator)
+    }
+
+    pub(crate) fn ts_type(&self, ts_type: &TSType) -> String {
+        match ts_type {
+            TSType::TSAnyKeyword(_) => "any".to_string(),
+            TSType::TSBigIntKeyword(_) => "bigint".to_string(),
+            TSType::TSBooleanKeyword(_) => "boolean".to_string(),
+            TSType::TSIntrinsicKeyword(_) => "intrinsic".to_string(),
+            TSType::TSNeverKeyword(_) => "never".to_string(),
+            TSType::TSNullKeyword(_) => "null".to_string(),
+            TSType::TSNumberKeyword(_) => "number".to_string(),
+            TSType::TSObjectKeyword(_) => "object".to_string(),
+            TSType::TSStringKeyword(_) => "string".to_string(),
+            TSType::TSSymbolKeyword(_) => "symbol".to_string(),
+            TSType::TSUndefinedKeyword(_) => "undefined".to_string(),
+            TSType::TSUnknownKeyword(_) => "unknown".to_string(),
+            TSType::TSVoidKeyword(_) => "void".to_string(),
+            TSType::TSThisType(_) => "this".to_string(),
+            TSType::TSArrayType(array) => format!("{}[]", self.ts_type(&array.element_type)),
+            TSType::TSConditionalType(conditional) => format!(
+                "{} extends {} ? {} : {}",
+                self.ts_type(&conditional.check_type),
+                self.ts_type(&conditional.extends_type),
+                self.ts_type(&conditional.true_type),
+                self.ts_type(&conditional.false_type)
+            ),
+            TSType::TSConstructorType(constructor) => format!(
+                "{}new {}{} => {}",
+                if constructor.r#abstract {
+                    "abstract "
+                } else {
+                    ""
+                },
+                self.type_parameters(constructor.type_parameters.as_deref()),
+                self.params(None, &constructor.params),
+                self.ts_type(&constructor.return_type.type_annotation)
+            ),
+            TSType::TSFunctionType(function) => format!(
+                "{}{} => {}",
+                self.type_parameters(function.type_parameters.as_deref()),
+                self.params(function.this_param.as_deref(), &function.params),
+                self.ts_type(&function.return_type.type_annotation)
+            ),
+            TSType::TSImportType(import) => self.import_type(import),
+            TSType::TSIndexedAccessType(indexed) => format!(
+                "{}[{}]",
+                self.ts_type(&indexed.object_type),
+                self.ts_type(&indexed.index_type)
+            ),
+            TSType::TSInferType(infer) => {
+                format!("infer {}", self.type_parameter(&infer.type_parameter))
+            }
+            TSType::TSIntersectionType(intersection) => self.types(&intersection.types, " & "),
+            TSType::TSUnionType(union) => self.types(&union.types, " | "),
+            TSType::TSLiteralType(literal) => match &literal.literal {
+                TSLiteral::BooleanLiteral(boolean) => boolean.value.to_string(),
+                TSLiteral::NullLiteral(_) => "null".to_string(),
+                TSLiteral::NumericLiteral(number) => number.raw.to_string(),
+                TSLiteral::BigIntLiteral(bigint) => bigint.raw.to_string(),
+                TSLiteral::RegExpLiteral(regexp) => regexp.raw.to_string(),
+                TSLiteral::StringLiteral(string) => quote(&string.value),
+                TSLiteral::TemplateLiteral(template) => self.template(template),
+                TSLiteral::UnaryExpression(unary) => self.unary(unary),
+            },
+            TSType::TSMappedType(mapped) => self.mapped_type(mapped),
+            TSType::TSNamedTupleMember(member) => format!(
+                "{}{}: {}",
+                member.label.name,
+                if member.optional { "?" } else { "" },
+                self.tuple_element(&member.element_type)
+            ),
+            TSType::TSQualifiedName(name) => self.qualified_name(name),
+            TSType::TSTemplateLiteralType(template) => {
+                let mut out = String::from("`");
+                for (index, quasi) in template.quasis.iter().enumerate() {
+                    out.push_str(&quasi.value.raw);
+                    if let Some(ts_type) = template.types.get(index) {
+                        out.push_str("${");
+                        out.push_str(&self.ts_type(ts_type));
+                        out.push('}');
+                    }
+                }
+                out.push('`');
+                out
+            }
+            TSType::TSTupleType(tuple) => {
+                let elements: Vec<String> = tuple
+                    .element_types
+                    .iter()
+                    .map(|element| self.tuple_element(element))
+                    .collect();
+                format!("[{}]", elements.join(", "))
+            }
+            TSType::TSTypeLiteral(literal) => self.members(&literal.members),
+            TSType::TSTypeOperatorType(operator) => format!(
+                "{} {}",
+                operator.operator.to_str(),
+                self.ts_type(&operator.type_annotation)
+            ),
+            TSType::TSTypePredicate(predicate) => {
+                let mut out = String::new();
+                if predicate.asserts {
+                    out.push_str("asserts ");
+                }
+                match &predicate.parameter_name {
+                    TSTypePredicateName::Identifier(ident) => out.push_str(&ident.name),
+                    TSTypePredicateName::This(_) => out.push_str("this"),
+                }
+                if let Some(annotation) = &predicate.type_annotation {
+                    out.push_str(" is ");
+                    out.push_str(&self.ts_type(&annotation.type_annotation));
+                }
+                out
+            }
+            TSType::TSTypeQuery(query) => {
+                let name = match &query.expr_name {
+                    TSTypeQueryExprName::TSImportType(import) => self.import_type(import),
+                    name => match name.as_ts_type_name() {
+                        Some(name) => self.type_name(name),
+                        None => self.compact(name.span()),
+                    },
+                };
+                format!(
+                    "typeof {}{}",
+                    name,
+                    self.type_arguments(query.type_parameters.as_deref())
+                )
+            }
+            TSType::TSTypeReference(reference) => {
+                self.type_name(&reference.type_name)
+                    + &self.type_arguments(reference.type_parameters.as_deref())
+            }
+            TSType::TSParenthesizedType(parenthesized) => {
+                format!("({})", self.ts_type(&parenthesized.type_annotation))
+            }
+            TSType::JSDocNullableType(nullable) => match nullable.postfix {
+                true => format!("{}?", self.ts_type(&nullable.type_annotation)),
+                false => format!("?{}", self.ts_type(&nullable.type_annotation)),
+            },
+            TSType::JSDocNonNullableType(non_nullable) => match non_nullable.postfix {
+                true => format!("{}!", self.ts_type(&non_nullable.type_annotation)),
+                false => format!("!{}", self.ts_type(&non_nullable.type_annotation)),
+            },
+            TSType::JSDocUnknownType(_) => "?".to_string(),
+        }
+    }
+
+    pub(crate) fn tuple_element(&self, element: &TSTupleElement) -> String {
+        match element {
+            TSTupleElement::TSOptionalType(optional) => {
+                format!("{}?", self.ts_type(&optional.type_annotation))
+            }
+            TSTupleElement::TSRestType(rest) => {
+                format!("...{}", self.ts_type(&rest.type_annotation))
+            }
+            element => match element.as_ts_type() {
+                Some(ts_type) => self.ts_type(ts_type),
+                None => self.compact(element.span()),
+            },
+        }
+    }
+
+    /// `{ readonly [K in keyof T as N]?: V }`
+    fn mapped_type(&self, mapped: &TSMappedType) -> String {
+        let modifier = |operator: TSMappedTypeModifierOperator| match operator {
+            TSMappedTypeModifierOperator::True => "",
+            TSMappedTypeModifierOperator::Plus => "+",
+            TSMappedTypeModifierOperator::Minus => "-",
+            TSMappedTypeModifierOperator::None => "",
+        };
+        let mut out = String::from("{ ");
+        if mapped.readonly != TSMappedTypeModifierOperator::None {
+            out.push_str(modifier(mapped.readonly));
+            out.push_str("readonly ");
+        }
+        out.push('[');
+        out.push_str(&mapped.type_parameter.name.name);
+        if let Some(constraint) = &mapped.type_parameter.constraint {
+            out.push_str(" in ");
+            out.push_str(&self.ts_type(constraint));
+        }
+        if let Some(name_type) = &mapped.name_type {
+            out.push_str(" as ");
+            out.push_str(&self.ts_type(name_type));
+        }
+        out.push(']');
+        if mapped.optional != TSMappedTypeModifierOperator::None {
+            out.push_str(modifier(mapped.optional));
+            out.push('?');
+        }
+        if let Some(ts_type) = &mapped.type_annotation {
+            out.push_str(": ");
+            out.push_str(&self.ts_type(ts_type));
+        }
+        out.push_str(" }");
+        out
+    }
+
+    /// Qualifier of an inline import type, without the `import("...")`
+    /// prefix declaration emit adds (`import("./user").User` is `User`)
+    fn import_type(&self, import: &TSImportType) -> String {
+        let type_arguments = self.type_arguments(import.type_parameters.as_deref());
+        let import_type = match &import.qualifier {
+            Some(qualifier) => self.type_name(qualifier) + &type_arguments,
+            None => format!(
+                "import({}){}",
+                self.ts_type(&import.parameter),
+                type_arguments
+            ),
+        };
+        match import.is_type_of {
+            true => format!("typeof {}", import_type),
+            false => import_type,
+        }
+    }
+
+    fn type_name(&self, name: &TSTypeName) -> String {
+        match name {
+            TSTypeName::IdentifierReference(ident) => ident.name.to_string(),
+            TSTypeName::QualifiedName(name) => self.qualified_name(name),
+        }
+    }
+
+    fn qualified_name(&self, name: &TSQualifiedName) -> String {
+        format!("{}.{}", self.type_name(&name.left), name.right.name)
+    }
+
+    /// Interface body or type literal: `{ a: T; b(): void }`
+    fn members(&self, members: &[TSSignature]) -> String {
+        let members: Vec<String> = members.iter().map(|m| self.signature(m)).collect();
+        braced(&members, "; ")
+    }
+
+    pub(crate) fn signature(&self, signature: &TSSignature) -> String {
+        match signature {
+            TSSignature::TSIndexSignature(index) => self.index_signature(index),
+            TSSignature::TSPropertySignature(property) => {
+                let mut out = String::new();
+                if property.readonly {
+                    out.push_str("readonly ");
+                }
+                out.push_str(&self.property_key(&property.key, property.computed));
+                if property.optional {
+                    out.push('?');
+                }
+                out.push_str(&self.type_annotation(property.type_annotation.as_deref()));
+                out
+            }
+            TSSignature::TSCallSignatureDeclaration(call) => self.callable(
+                call.type_parameters.as_deref(),
+                call.this_param.as_ref(),
+                &call.params,
+                call.return_type.as_deref(),
+            ),
+            TSSignature::TSConstructSignatureDeclaration(construct) => format!(
+                "new {}",
+                self.callable(
+                    construct.type_parameters.as_deref(),
+                    None,
+                    &construct.params,
+                    construct.return_type.as_deref(),
+                )
+            ),
+            TSSignature::TSMethodSignature(method) => {
+                let mut out = match method.kind {
+                    TSMethodSignatureKind::Method => String::new(),
+                    TSMethodSignatureKind::Get => "get ".to_string(),
+                    TSMethodSignatureKind::Set => "set ".to_string(),
+                };
+                out.push_str(&self.property_key(&method.key, method.computed));
+                if method.optional {
+                    out.push('?');
+                }
+                out.push_str(&self.callable(
+                    method.type_parameters.as_deref(),
+                    method.this_param.as_deref(),
+                    &method.params,
+                    method.return_type.as_deref(),
+                ));
+                out
+            }
+        }
+    }
+
+    /// `static readonly [key: string]: T`
+    pub(crate) fn index_signature(&self, index: &TSIndexSignature) -> String {
+        let mut out = String::new();
+        if index.r#static {
+            out.push_str("static ");
+        }
+        if index.readonly {
+            out.push_str("readonly ");
+        }
+        let parameters: Vec<String> = index
+            .parameters
+            .iter()
+            .map(|parameter| {
+                format!(
+                    "{}{}",
+                    parameter.name,
+                    self.type_annotation(Some(&parameter.type_annotation))
+                )
+            })
+            .collect();
+        out.push_str(&format!("[{}]", parameters.join(", ")));
+        out.push_str(&self.type_annotation(Some(&index.type_annotation)));
+        out
+    }
+
+    // Expressions
+
+    pub(crate) fn expression(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::BooleanLiteral(boolean) => boolean.value.to_string(),
+            Expression::NullLiteral(_) => "null".to_string(),
+            Expression::NumericLiteral(number) => number.raw.to_string(),
+            Expression::BigIntLiteral(bigint) => bigint.raw.to_string(),
+            Expression::RegExpLiteral(regexp) => regexp.raw.to_string(),
+            Expression::StringLiteral(string) => quote(&string.value),
+            Expression::TemplateLiteral(template) => self.template(template),
+            Expression::Identifier(ident) => ident.name.to_string(),
+            Expression::ThisExpression(_) => "this".to_string(),
+            Expression::Super(_) => "super".to_string(),
+            Expression::ArrayExpression(array) => {
+                let elements: Vec<String> = array
+                    .elements
+                    .iter()
+                    .map(|element| match element {
+                        ArrayExpressionElement::SpreadElement(spread) => {
+                            format!("...{}", self.expression(&spread.argument))
+                        }
+                        ArrayExpressionElement::Elision(_) => String::new(),
+                        element => match element.as_expression() {
+                            Some(expression) => self.expression(expression),
+                            None => self.compact(element.span()),
+                        },
+                    })
+                    .collect();
+                format!("[{}]", elements.join(", "))
+            }
+            Expression::ObjectExpression(object) => {
+                let properties: Vec<String> = object
+                    .properties
+                    .iter()
+                    .map(|property| match property {
+                        ObjectPropertyKind::SpreadProperty(spread) => {
+                            format!("...{}", self.expression(&spread.argument))
+                        }
+                        ObjectPropertyKind::ObjectProperty(property)
+                            if property.kind == PropertyKind::Init && !property.method =>
+                        {
+                            match property.shorthand {
+                                true => self.expression(&property.value),
+                                false => format!(
+                                    "{}: {}",
+                                    self.property_key(&property.key, property.computed),
+                                    self.expression(&property.value)
+                                ),
+                            }
+                        }
+                        ObjectPropertyKind::ObjectProperty(property) => self.compact(property.span),
+                    })
+                    .collect();
+                braced(&properties, ", ")
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                let body = match (arrow.expression, arrow.body.statements.first()) {
+                    (true, Some(Statement::ExpressionStatement(statement))) => {
+                        self.expression(&statement.expression)
+                    }
+                    _ => self.compact(arrow.body.span),
+                };
+                format!("{} {}", self.arrow(arrow), body)
+            }
+            Expression::FunctionExpression(func) => match &func.body {
+                Some(body) => format!("{} {}", self.function(func), self.compact(body.span)),
+                None => self.function(func),
+            },
+            Expression::AwaitExpression(await_expression) => {
+                format!("await {}", self.expression(&await_expression.argument))
+            }
+            Expression::BinaryExpression(binary) => format!(
+                "{} {} {}",
+                self.expression(&binary.left),
+                binary.operator.as_str(),
+                self.expression(&binary.right)
+            ),
+            Expression::LogicalExpression(logical) => format!(
+                "{} {} {}",
+                self.expression(&logical.left),
+                logical.operator.as_str(),
+                self.expression(&logical.right)
+            ),
+            Expression::ConditionalExpression(conditional) => format!(
+                "{} ? {} : {}",
+                self.expression(&conditional.test),
+                self.expression(&conditional.consequent),
+                self.expression(&conditional.alternate)
+            ),
+            Expression::UnaryExpression(unary) => self.unary(unary),
+            Expression::CallExpression(call) => format!(
+                "{}{}{}{}",
+                self.expression(&call.callee),
+                if call.optional { "?." } else { "" },
+                self.type_arguments(call.type_parameters.as_deref()),
+                self.arguments(&call.arguments)
+            ),
+            Expression::NewExpression(new) => format!(
+                "new {}{}{}",
+                self.expression(&new.callee),
+                self.type_arguments(new.type_parameters.as_deref()),
+                self.arguments(&new.arguments)
+            ),
+            Expression::StaticMemberExpression(member) => format!(
+                "{}{}{}",
+                self.expression(&member.object),
+                if member.optional { "?." } else { "." },
+                member.property.name
+            ),
+            Expression::ComputedMemberExpression(member) => format!(
+                "{}{}[{}]",
+                self.expression(&member.object),
+                if member.optional { "?." } else { "" },
+                self.expression(&member.expression)
+            ),
+            Expression::ParenthesizedExpression(parenthesized) => {
+                format!("({})", self.expression(&parenthesized.expression))
+            }
+            Expression::TSAsExpression(as_expression) => format!(
+                "{} as {}",
+                self.expression(&as_expression.expression),
+                self.ts_type(&as_expression.type_annotation)
+            ),
+            Expression::TSSatisfiesExpression(satisfies) => format!(
+                "{} satisfies {}",
+                self.expression(&satisfies.expression),
+                self.ts_type(&satisfies.type_annotation)
+            ),
+            Expression::TSNonNullExpression(non_null) => {
+                format!("{}!", self.expression(&non_null.expression))
+            }
+            expression => self.compact(expression.span()),
+        }
+    }
+
+    fn arguments(&self, arguments: &[Argument]) -> String {
+        let arguments: Vec<String> = arguments
+            .iter()
+            .map(|argument| match argument {
+                Argument::SpreadElement(spread) => {
+                    format!("...{}", self.expression(&spread.argument))
+                }
+                argument => match argument.as_expression() {
+                    Some(expression) => self.expression(expression),
+                    None => self.compact(argument.span()),
+                },
+            })
+            .collect();
+        format!("({})", arguments.join(", "))
+    }
+
+    fn unary(&self, unary: &UnaryExpression) -> String {
+        let separator = if unary.operator.is_keyword() { " " } else { "" };
+        format!(
+            "{}{}{}",
+            unary.operator.as_str(),
+            separator,
+            self.expression(&unary.argument)
+        )
+    }
+
+    fn template(&self, template: &TemplateLiteral) -> String {
+        let mut out = String::from("`");
+        for (index, quasi) in template.quasis.iter().enumerate() {
+            out.push_str(&quasi.value.raw);
+            if let Some(expression) = template.expressions.get(index) {
+                out.push_str("${");
+                out.push_str(&self.expression(expression));
+                out.push('}');
+            }
+        }
+        out.push('`');
+        out
+    }
+
+    /// Source of a node in canonical token form (see `compact`)
+    fn compact(&self, span: oxc_span::Span) -> String {
+        compact(
+            self.source
+                .get(span.start as usize..span.end as usize)
+                .unwrap_or(""),
+        )
+    }
+}
+
+/// Items in braces: `{ a, b }`, or `{}` without items
+pub(crate) fn braced(items: &[String], separator: &str) -> String {
+    match items.is_empty() {
+        true => "{}".to_string(),
+        false => format!("{{ {} }}", items.join(separator)),
+    }
+}
+
+/// String literal in double quotes, escaped as in JSON
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+/// Whether a character is part of an identifier, keyword, or number
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$' || !c.is_ascii()
+}
+
+/// Characters after which a `/` starts a regular expression, not a division
+const BEFORE_REGEX: &str = "(,=:[!&|?{};+-*%<>~^";
+
+/// Source code in canonical token form
+///
+/// Comments are dropped and whitespace is kept, as a single space, only
+/// where it separates two words (`new Foo`) or would otherwise join two
+/// operators (`a + +b`). String, template, and regular expression literals
+/// are kept as written.
+pub(crate) fn compact(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace() {
+            space = true;
+            i += 1;
+            continue;
+        }
+        if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            space = true;
+            continue;
+        }
+        if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+            space = true;
+            continue;
+        }
+
+        if space {
+            if let Some(last) = out.chars().last() {
+                let joins_words = is_word(last) && is_word(c);
+                let joins_operators = matches!((last, c), ('+', '+') | ('-', '-'));
+                if joins_words || joins_operators {
+                    out.push(' ');
+                }
+            }
+            space = false;
+        }
+
+        let regex = c == '/'
+            && out
+                .chars()
+                .last()
+                .is_none_or(|last| BEFORE_REGEX.contains(last));
+        if matches!(c, '"' | '\'' | '`') || regex {
+            // Copy the literal up to its closing delimiter
+            let mut class = false;
+            out.push(c);
+            i += 1;
+            while i < chars.len() {
+                let d = chars[i];
+                out.push(d);
+                i += 1;
+                match d {
+                    '\\' => {
+                        if let Some(&escaped) = chars.get(i) {
+                            out.push(escaped);
+                            i += 1;
+                        }
+                    }
+                    '[' if regex => class = true,
+                    ']' if regex => class = false,
+                    d if d == c && !class => break,
+                    _ => {}
+                }
+            }
+            continue;
+        }
+
+        out.push(c);
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::AstAnalyzerInternal;
+
+    fn signature(code: &str) -> String {
+        let result = AstAnalyzerInternal::new().analyze_file("src/a.ts", code);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        result.symbols[0].signature.clone()
+    }
+
+    #[test]
+    fn test_formatting_does_not_change_signatures() {
+        let pairs = [
+            (
+                "export type Route = `/users/${ string }/posts`;",
+                "export type Route = `/users/${string}/posts`",
+            ),
+            (
+                "export type Mode =\n  | 'read'\n  | 'write';",
+                "export type Mode = \"read\" | \"write\"",
+            ),
+            (
+                "export function find<T>( id : string, /* cached */ opts?: Options<T>, ): Promise<T> {}",
+                "export function find<T>(id: string, opts?: Options<T>): Promise<T> {}",
+            ),
+            (
+                "export interface User {\n  id: string,\n  name?: string;\n  greet(): void\n}",
+                "export interface User { id: string; name?: string; greet(): void; }",
+            ),
+            (
+                "export enum Color { Red = 'red', Green = 'green', }",
+                "export enum Color {\n  Red = \"red\",\n  Green = \"green\"\n}",
+            ),
+            (
+                "export const config = { retries: 3, url: 'https://x' } as const;",
+                "export const config = {\n  retries: 3,\n  url: \"https://x\",\n} as const;",
+            ),
+            (
+                "export type Fn = (a: import('./user').User) => void;",
+                "export type Fn = (a: User) => void;",
+            ),
+            (
+                "export type Mod = typeof import('./user');",
+                "export type Mod = typeof import(\"./user\")",
+            ),
+        ];
+        for (a, b) in pairs {
+            assert_eq!(signature(a), signature(b), "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_printed_signatures() {
+        assert_eq!(
+            signature("export async function load<T extends object = {}>(this: Ctx, { id, ...rest }: Args, ...more: T[]): Promise<T> {}"),
+            "async function load<T extends object = {}>(this: Ctx, { id, ...rest }: Args, ...more: T[]): Promise<T>"
+        );
+        assert_eq!(
+            signature("export type Opt<T> = { readonly [K in keyof T]?: T[K] extends infer U ? U : never };"),
+            "type Opt<T> = { readonly [K in keyof T]?: T[K] extends infer U ? U : never }"
+        );
+        assert_eq!(
+            signature("export const handler = async (req: Req) => { return  req.body ; };"),
+            "handler = async (req: Req) => {return req.body;}"
+        );
+        assert_eq!(
+            signature("export type Mod = Foo.Bar<typeof import('./user'), unique symbol>;"),
+            "type Mod = Foo.Bar<typeof import(\"./user\"), unique symbol>"
+        );
+        // Changes that matter still change the signature
+        assert_ne!(
+            signature("export type Route = `/users/${string}`;"),
+            signature("export type Route = `/user/${string}`;")
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        assert_eq!(compact("new  Foo( a ,b ) // trailing"), "new Foo(a,b)");
+        assert_eq!(compact("a + +b - -c"), "a+ +b- -c");
+        assert_eq!(compact("'a  b' + `x ${ y }`"), "'a  b'+`x ${ y }`");
+        assert_eq!(
+            compact("x.replace(/ +/g, ' ') /* done */"),
+            "x.replace(/ +/g,' ')"
+        );
+    }
+}
@@ -0,0 +1,563 @@
+//! Protocol Buffers / gRPC IDL analyzer
+//!
+//! This module extracts messages, enums, services, and RPC methods from
+//! `.proto` files. Messages list their fields (with numbers, labels, and
+//! `oneof` groups) so that adding, removing, or renumbering a field changes
+//! the signature and triggers drift for the documentation that references it.
+//!
+//! Nested declarations are qualified with their parent (`Outer.Inner`) and
+//! RPCs with their service (`UserService.GetUser`).
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::diagnostics::{error_messages, Diagnostic};
+use crate::types::SymbolType;
+use std::ops::Range;
+
+/// Lexical token of the proto language
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Identifier, keyword, or dotted type name
+    Word(String),
+    /// Numeric literal
+    Number(String),
+    /// String literal (quotes included)
+    Str(String),
+    /// Single punctuation character
+    Punct(char),
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match self {
+            Token::Word(s) | Token::Number(s) | Token::Str(s) => s.clone(),
+            Token::Punct(c) => c.to_string(),
+        }
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self, Token::Word(w) if w == word)
+    }
+
+    fn is_punct(&self, ch: char) -> bool {
+        matches!(self, Token::Punct(c) if *c == ch)
+    }
+}
+
+/// Analyze a `.proto` file and extract its public declarations
+///
+/// # Arguments
+/// * `file_path` - Path of the proto file (used for symbol metadata)
+/// * `content` - Raw proto source
+///
+/// # Returns
+/// AnalysisResult with `Schema` symbols for messages, `Enum` symbols for
+/// enums, `Service` symbols for services, and `Operation` symbols for RPCs
+pub fn analyze_proto(file_path: &str, content: &str) -> AnalysisResult {
+    let (tokens, spans) = tokenize(content);
+    let mut parser = ProtoParser {
+        tokens: &tokens,
+        spans: &spans,
+        pos: 0,
+        content,
+        file_path,
+        symbols: Vec::new(),
+        diagnostics: Vec::new(),
+    };
+
+    parser.parse_top_level();
+
+    AnalysisResult {
+        symbols: parser.symbols,
+        errors: error_messages(&parser.diagnostics),
+        diagnostics: parser.diagnostics,
+        ignored: Vec::new(),
+        skipped: Vec::new(),
+    }
+}
+
+struct ProtoParser<'a> {
+    tokens: &'a [Token],
+    /// Byte span of each token
+    spans: &'a [Range<usize>],
+    pos: usize,
+    content: &'a str,
+    file_path: &'a str,
+    symbols: Vec<SymbolInfo>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> ProtoParser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    /// Report an error at the token at `pos` (the end of the file past the
+    /// last token)
+    fn error_at(&mut self, pos: usize, message: String) {
+        let end = self.content.len();
+        let span = self.spans.get(pos).cloned().unwrap_or(end..end);
+        self.diagnostics.push(Diagnostic::error(
+            self.file_path,
+            self.content,
+            message,
+            span,
+        ));
+    }
+
+    fn push_symbol(&mut self, name: String, symbol_type: SymbolType, signature: String) {
+        self.symbols.push(SymbolInfo {
+            name,
+            symbol_type,
+            signature,
+            is_exported: true,
+            file_path: self.file_path.to_string(),
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+        });
+    }
+
+    fn parse_top_level(&mut self) {
+        while let Some(token) = self.peek() {
+            if token.is_word("message") {
+                self.pos += 1;
+                self.parse_message("");
+            } else if token.is_word("enum") {
+                self.pos += 1;
+                self.parse_enum("");
+            } else if token.is_word("service") {
+                self.pos += 1;
+                self.parse_service();
+            } else if token.is_punct('{') {
+                // `extend Foo { ... }` and other blocks we don't track
+                self.skip_block();
+            } else {
+                self.pos += 1;
+            }
+        }
+    }
+
+    /// Parse `message Name { ... }` (the `message` keyword is already consumed)
+    ///
+    /// Returns the rendered message signature so parents can embed it.
+    fn parse_message(&mut self, prefix: &str) -> Option<String> {
+        let name = self.expect_name("message")?;
+        let qualified = qualify(prefix, &name);
+        if !self.expect_punct('{') {
+            return None;
+        }
+
+        let mut members = Vec::new();
+        while let Some(token) = self.peek() {
+            if token.is_punct('}') {
+                self.pos += 1;
+                break;
+            } else if token.is_word("message") {
+                self.pos += 1;
+                self.parse_message(&qualified);
+            } else if token.is_word("enum") {
+                self.pos += 1;
+                self.parse_enum(&qualified);
+            } else if token.is_word("oneof") {
+                self.pos += 1;
+                members.push(self.parse_oneof());
+            } else if token.is_word("option")
+                || token.is_word("reserved")
+                || token.is_word("extensions")
+            {
+                let statement = self.take_statement();
+                if !statement.starts_with("option") {
+                    members.push(statement);
+                }
+            } else if token.is_punct(';') {
+                self.pos += 1;
+            } else if token.is_word("extend") {
+                self.take_until_block();
+                self.skip_block();
+            } else {
+                members.push(self.take_statement());
+            }
+        }
+
+        let signature = format!("message {} {{ {} }}", qualified, join_members(&members));
+        self.push_symbol(qualified, SymbolType::Schema, signature.clone());
+        Some(signature)
+    }
+
+    /// Parse `oneof name { fields }` into a single member string
+    fn parse_oneof(&mut self) -> String {
+        let name = self.next().map(Token::text).unwrap_or_default();
+        if !self.expect_punct('{') {
+            return format!("oneof {}", name);
+        }
+
+        let mut fields = Vec::new();
+        while let Some(token) = self.peek() {
+            if token.is_punct('}') {
+                self.pos += 1;
+                break;
+            }
+            let field = self.take_statement();
+            if !field.starts_with("option") && !field.is_empty() {
+                fields.push(field);
+            }
+        }
+
+        format!("oneof {} {{ {} }}", name, join_members(&fields))
+    }
+
+    /// Parse `enum Name { ... }` (the `enum` keyword is already consumed)
+    fn parse_enum(&mut self, prefix: &str) {
+        let Some(name) = self.expect_name("enum") else {
+            return;
+        };
+        let qualified = qualify(prefix, &name);
+        if !self.expect_punct('{') {
+            return;
+        }
+
+        let mut values = Vec::new();
+        while let Some(token) = self.peek() {
+            if token.is_punct('}') {
+                self.pos += 1;
+                break;
+            }
+            let value = self.take_statement();
+            if !value.starts_with("option") && !value.is_empty() {
+                values.push(value);
+            }
+        }
+
+        let signature = format!("enum {} {{ {} }}", qualified, join_members(&values));
+        self.push_symbol(qualified, SymbolType::Enum, signature);
+    }
+
+    /// Parse `service Name { rpc ... }` (the `service` keyword is already consumed)
+    fn parse_service(&mut self) {
+        let Some(name) = self.expect_name("service") else {
+            return;
+        };
+        if !self.expect_punct('{') {
+            return;
+        }
+
+        let mut rpcs = Vec::new();
+        while let Some(token) = self.peek() {
+            if token.is_punct('}') {
+                self.pos += 1;
+                break;
+            } else if token.is_word("rpc") {
+                let rpc = self.take_until_block();
+                // Either `{ option ...; }` or a bare `;` follows the declaration
+                if self.peek().is_some_and(|t| t.is_punct('{')) {
+                    self.skip_block();
+                }
+                let rpc_name = rpc
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|n| n.split('(').next())
+                    .unwrap_or("")
+                    .to_string();
+                let qualified = format!("{}.{}", name, rpc_name);
+                let signature = rpc.replacen(
+                    &format!("rpc {}", rpc_name),
+                    &format!("rpc {}", qualified),
+                    1,
+                );
+                self.push_symbol(qualified, SymbolType::Operation, signature);
+                rpcs.push(rpc);
+            } else {
+                self.take_statement();
+            }
+        }
+
+        let signature = format!("service {} {{ {} }}", name, join_members(&rpcs));
+        self.push_symbol(name, SymbolType::Service, signature);
+    }
+
+    fn expect_name(&mut self, kind: &str) -> Option<String> {
+        match self.next() {
+            Some(Token::Word(name)) => Some(name.clone()),
+            _ => {
+                self.error_at(self.pos - 1, format!("expected {} name", kind));
+                None
+            }
+        }
+    }
+
+    fn expect_punct(&mut self, ch: char) -> bool {
+        if self.peek().is_some_and(|t| t.is_punct(ch)) {
+            self.pos += 1;
+            true
+        } else {
+            self.error_at(self.pos, format!("expected '{}'", ch));
+            false
+        }
+    }
+
+    /// Consume tokens up to and including `;`, dropping `[...]` field options
+    fn take_statement(&mut self) -> String {
+        let mut parts = Vec::new();
+        let mut bracket_depth = 0;
+
+        while let Some(token) = self.peek() {
+            if token.is_punct('}') && bracket_depth == 0 {
+                break;
+            }
+            self.pos += 1;
+            match token {
+                Token::Punct(';') if bracket_depth == 0 => break,
+                Token::Punct('[') => bracket_depth += 1,
+                Token::Punct(']') => bracket_depth -= 1,
+                _ if bracket_depth > 0 => {}
+                _ => parts.push(token),
+            }
+        }
+
+        render_tokens(&parts)
+    }
+
+    /// Consume tokens up to (not including) `{`, or up to and including `;`
+    fn take_until_block(&mut self) -> String {
+        let mut parts = Vec::new();
+        while let Some(token) = self.peek() {
+            if token.is_punct('{') {
+                break;
+            }
+            self.pos += 1;
+            if token.is_punct(';') {
+                break;
+            }
+            parts.push(token);
+        }
+        render_tokens(&parts)
+    }
+
+    /// Skip a balanced `{ ... }` block starting at the current token
+    fn skip_block(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.next() {
+            if token.is_punct('{') {
+                depth += 1;
+            } else if token.is_punct('}') {
+                depth -= 1;
+                if depth <= 0 {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn qualify(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+fn join_members(members: &[String]) -> String {
+    members
+        .iter()
+        .map(|m| format!("{};", m))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Join tokens with canonical spacing (`map<string, int32> tags = 1`)
+fn render_tokens(tokens: &[&Token]) -> String {
+    let mut out = String::new();
+    for (idx, token) in tokens.iter().enumerate() {
+        let text = token.text();
+        let glue_left = matches!(token, Token::Punct('<' | '>' | ',' | ')' | '('));
+        let prev_glues_right = idx > 0 && matches!(tokens[idx - 1], Token::Punct('<' | '('));
+        if idx > 0 && !glue_left && !prev_glues_right {
+            out.push(' ');
+        }
+        out.push_str(&text);
+        if matches!(token, Token::Punct(',')) {
+            out.push(' ');
+        }
+    }
+    // `returns (X)` keeps the space before the parenthesis
+    out.replace("returns(", "returns (").replace("  ", " ")
+}
+
+/// Split proto source into tokens, discarding comments
+///
+/// # Returns
+/// The tokens, and the byte span of each
+fn tokenize(content: &str) -> (Vec<Token>, Vec<Range<usize>>) {
+    let chars: Vec<char> = content.chars().collect();
+    let offsets: Vec<usize> = content
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain([content.len()])
+        .collect();
+    let mut tokens = Vec::new();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let ch = chars[i];
+        if ch.is_whitespace() {
+            i += 1;
+        } else if ch == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if ch == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if ch == '"' || ch == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != ch {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(Token::Str(
+                chars[start..i.min(chars.len())].iter().collect(),
+            ));
+        } else if ch.is_alphabetic()
+            || ch == '_'
+            || (ch == '.' && chars.get(i + 1).is_some_and(|c| c.is_alphabetic()))
+        {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else if ch.is_ascii_digit()
+            || (ch == '-' && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            tokens.push(Token::Number(chars[start..i].iter().collect()));
+        } else {
+            tokens.push(Token::Punct(ch));
+            i += 1;
+        }
+        if spans.len() < tokens.len() {
+            spans.push(offsets[start]..offsets[i.min(chars.len())]);
+        }
+    }
+
+    (tokens, spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROTO: &str = r#"
+        syntax = "proto3";
+        package acme.users.v1;
+
+        // A user account
+        message User {
+            string id = 1;
+            repeated string tags = 2 [deprecated = true];
+            map<string, int32> scores = 3;
+            oneof contact {
+                string email = 4;
+                string phone = 5;
+            }
+            message Address { string city = 1; }
+            enum Status { STATUS_UNKNOWN = 0; STATUS_ACTIVE = 1; }
+            reserved 6, 7;
+        }
+
+        service UserService {
+            rpc GetUser (GetUserRequest) returns (User);
+            rpc Watch(stream WatchRequest) returns (stream User) {
+                option (google.api.http) = { get: "/v1/users" };
+            }
+        }
+    "#;
+
+    fn find<'a>(result: &'a AnalysisResult, name: &str) -> &'a SymbolInfo {
+        result.symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_extracts_messages_with_fields() {
+        let result = analyze_proto("users.proto", PROTO);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+        let user = find(&result, "User");
+        assert_eq!(user.symbol_type, SymbolType::Schema);
+        assert_eq!(
+            user.signature,
+            "message User { string id = 1; repeated string tags = 2; map<string, int32> scores = 3; oneof contact { string email = 4; string phone = 5; }; reserved 6, 7; }"
+        );
+
+        assert_eq!(
+            find(&result, "User.Address").symbol_type,
+            SymbolType::Schema
+        );
+        let status = find(&result, "User.Status");
+        assert_eq!(status.symbol_type, SymbolType::Enum);
+        assert_eq!(
+            status.signature,
+            "enum User.Status { STATUS_UNKNOWN = 0; STATUS_ACTIVE = 1; }"
+        );
+    }
+
+    #[test]
+    fn test_extracts_services_and_rpcs() {
+        let result = analyze_proto("users.proto", PROTO);
+
+        let get_user = find(&result, "UserService.GetUser");
+        assert_eq!(get_user.symbol_type, SymbolType::Operation);
+        assert_eq!(
+            get_user.signature,
+            "rpc UserService.GetUser(GetUserRequest) returns (User)"
+        );
+
+        let watch = find(&result, "UserService.Watch");
+        assert_eq!(
+            watch.signature,
+            "rpc UserService.Watch(stream WatchRequest) returns (stream User)"
+        );
+
+        let service = find(&result, "UserService");
+        assert_eq!(service.symbol_type, SymbolType::Service);
+        assert!(service
+            .signature
+            .contains("rpc GetUser(GetUserRequest) returns (User);"));
+    }
+
+    #[test]
+    fn test_field_renumbering_changes_signature() {
+        let before = analyze_proto("users.proto", PROTO);
+        let after = analyze_proto(
+            "users.proto",
+            &PROTO.replace("string id = 1;", "string id = 9;"),
+        );
+        assert_ne!(
+            find(&before, "User").signature,
+            find(&after, "User").signature
+        );
+    }
+}
@@ -0,0 +1,660 @@
+//! Python analyzer
+//!
+//! This module extracts functions and classes from `.py` and `.pyi` files so
+//! documentation can be anchored to Python symbols (`src/auth.py#login`,
+//! `src/models.py#User.Address`) and drift like TypeScript does:
+//!
+//! - Functions: the `def` line with its decorators
+//!   (`@cache def lookup(key: str) -> User`)
+//! - Classes: the `class` line followed by its public members, i.e. method
+//!   signatures, annotated attributes, and class constants
+//!   (`class User(Base) { id: int; def save(self) -> None }`)
+//!
+//! Signatures are normalized: line breaks and runs of whitespace collapse to
+//! single spaces and trailing commas are dropped, so reformatting (e.g. a
+//! formatter exploding a long parameter list) leaves the hash unchanged.
+//!
+//! Like the SQL and proto analyzers this is a scanner rather than a full
+//! grammar: it understands comments, strings (prefixed and triple-quoted),
+//! bracket and backslash continuations, and indentation, and skips function
+//! bodies. A name is exported when listed in `__all__` or, without
+//! `__all__`, when it does not start with an underscore. A `deprecated`
+//! decorator (PEP 702) marks the symbol deprecated.
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::diagnostics::{error_messages, Diagnostic};
+use super::ignore::{IgnoreDirectives, IgnoredItem};
+use crate::types::{line_starts, SymbolLocation, SymbolType};
+
+/// One statement, continuation lines joined
+#[derive(Debug, Default)]
+struct Line {
+    /// Byte offset of the statement in the file
+    offset: usize,
+    /// Byte offset just past its last token
+    end: usize,
+    /// Indentation width of its first physical line
+    indent: usize,
+    /// Normalized text without comments
+    text: String,
+    /// Position in `text` of the first colon outside brackets and strings
+    colon: Option<usize>,
+    /// Position in `text` of the first assignment `=` outside brackets and strings
+    assign: Option<usize>,
+}
+
+impl Line {
+    /// Text before the block colon (`def f(x) -> int` of `def f(x) -> int: ...`)
+    fn header(&self) -> &str {
+        match self.colon {
+            Some(colon) => self.text[..colon].trim_end(),
+            None => &self.text,
+        }
+    }
+}
+
+/// A function or class being collected
+struct Declaration {
+    name: String,
+    symbol_type: SymbolType,
+    /// Decorators and `def`/`class` line
+    header: String,
+    /// Byte offset of the first decorator or the `def`/`class` keyword
+    start: usize,
+    /// Index of the `def`/`class` line
+    line: usize,
+    /// Public members of a class
+    members: Vec<String>,
+    deprecated: bool,
+    is_exported: bool,
+}
+
+/// Enclosing block of a statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    /// Class body, by index into the declarations
+    Class(usize),
+    /// Function body (skipped)
+    Function,
+}
+
+/// Analyze a Python file and extract its functions and classes
+///
+/// # Arguments
+/// * `file_path` - Path of the Python file (used for symbol metadata)
+/// * `content` - Raw Python source
+///
+/// # Returns
+/// AnalysisResult with `Function` symbols for module-level functions and
+/// `Class` symbols for classes (nested classes qualified as `Outer.Inner`)
+pub fn analyze_python(file_path: &str, content: &str) -> AnalysisResult {
+    let (lines, diagnostics) = logical_lines(file_path, content);
+    let exports = dunder_all(&lines);
+    let mut ignore_next = IgnoreDirectives::parse(content).next_symbol.into_iter();
+    let mut pending_ignore = ignore_next.next();
+
+    let mut declarations: Vec<Declaration> = Vec::new();
+    let mut ignored = Vec::new();
+    let mut scopes: Vec<(usize, Scope)> = Vec::new();
+    let mut decorators: Vec<&Line> = Vec::new();
+
+    for (position, line) in lines.iter().enumerate() {
+        while scopes
+            .last()
+            .is_some_and(|(indent, _)| line.indent <= *indent)
+        {
+            scopes.pop();
+        }
+        let scope = scopes.last().map(|(_, scope)| *scope);
+        let header = line.header();
+        if header.starts_with('@') {
+            decorators.push(line);
+            continue;
+        }
+        let start = decorators.first().map_or(line.offset, |d| d.offset);
+        let decorated: Vec<&str> = decorators.drain(..).map(|d| d.header()).collect();
+
+        let Some((symbol_type, name)) = declaration(header) else {
+            if let Some(Scope::Class(index)) = scope {
+                declarations[index].members.extend(member(line));
+            }
+            continue;
+        };
+
+        // Directives before the declaration apply to it
+        let mut ignore = None;
+        while let Some((offset, reason)) = pending_ignore.take() {
+            if offset as usize > start {
+                pending_ignore = Some((offset, reason));
+                break;
+            }
+            ignore = Some(reason);
+            pending_ignore = ignore_next.next();
+        }
+
+        let signature = decorated
+            .iter()
+            .copied()
+            .chain([header])
+            .collect::<Vec<_>>()
+            .join(" ");
+        let deprecated = decorated.iter().any(|d| is_deprecated(d));
+        let (qualified, is_exported) = match scope {
+            None => {
+                let exported = match &exports {
+                    Some(exports) => exports.iter().any(|e| e == name),
+                    None => !name.starts_with('_'),
+                };
+                (name.to_string(), exported)
+            }
+            Some(Scope::Class(index)) => {
+                let parent = &declarations[index];
+                let qualified = format!("{}.{}", parent.name, name);
+                let exported = parent.is_exported && !name.starts_with('_');
+                (qualified, exported)
+            }
+            Some(Scope::Function) => {
+                scopes.push((line.indent, Scope::Function));
+                continue;
+            }
+        };
+
+        if let Some(reason) = ignore {
+            ignored.push(IgnoredItem {
+                file_path: file_path.to_string(),
+                symbol_name: Some(qualified),
+                reason,
+            });
+            scopes.push((line.indent, Scope::Function));
+            continue;
+        }
+
+        match (symbol_type, scope) {
+            // Methods are members of their class
+            (SymbolType::Function, Some(Scope::Class(index))) => {
+                if is_public(name) {
+                    declarations[index].members.push(signature);
+                }
+                scopes.push((line.indent, Scope::Function));
+            }
+            _ => {
+                let body = match symbol_type {
+                    SymbolType::Class => Scope::Class(declarations.len()),
+                    _ => Scope::Function,
+                };
+                declarations.push(Declaration {
+                    name: qualified,
+                    symbol_type,
+                    header: signature,
+                    start,
+                    line: position,
+                    members: Vec::new(),
+                    deprecated,
+                    is_exported,
+                });
+                scopes.push((line.indent, body));
+            }
+        }
+    }
+
+    let line_starts = line_starts(content);
+    let symbols = declarations
+        .into_iter()
+        .map(|declaration| SymbolInfo {
+            location: Some(SymbolLocation::from_offsets(
+                &line_starts,
+                declaration.start,
+                block_end(&lines, declaration.line),
+            )),
+            signature: match declaration.symbol_type {
+                SymbolType::Class if declaration.members.is_empty() => {
+                    format!("{} {{ }}", declaration.header)
+                }
+                SymbolType::Class => format!(
+                    "{} {{ {} }}",
+                    declaration.header,
+                    declaration.members.join("; ")
+                ),
+                _ => declaration.header,
+            },
+            name: declaration.name,
+            symbol_type: declaration.symbol_type,
+            is_exported: declaration.is_exported,
+            file_path: file_path.to_string(),
+            deprecated: declaration.deprecated,
+            release_tag: None,
+            type_params: Vec::new(),
+        })
+        .collect();
+
+    AnalysisResult {
+        symbols,
+        errors: error_messages(&diagnostics),
+        diagnostics,
+        ignored,
+        skipped: Vec::new(),
+    }
+}
+
+/// End offset of the block opened by the statement at `index`, i.e. of its
+/// last more-indented statement
+fn block_end(lines: &[Line], index: usize) -> usize {
+    let indent = lines[index].indent;
+    lines[index + 1..]
+        .iter()
+        .take_while(|line| line.indent > indent)
+        .last()
+        .unwrap_or(&lines[index])
+        .end
+}
+
+/// Kind and name of a `def`, `async def`, or `class` header
+fn declaration(header: &str) -> Option<(SymbolType, &str)> {
+    let (symbol_type, rest) = if let Some(rest) = header.strip_prefix("class ") {
+        (SymbolType::Class, rest)
+    } else {
+        let rest = header.strip_prefix("async ").unwrap_or(header);
+        (SymbolType::Function, rest.strip_prefix("def ")?)
+    };
+    let name = rest.split(['(', '[', ' ']).next().unwrap_or_default();
+    is_identifier(name).then_some((symbol_type, name))
+}
+
+/// Class member rendered from a statement in the class body
+///
+/// Annotated attributes keep their annotation (`id: int`), plain assignments
+/// their value (`ADMIN = "admin"`, for enums). Docstrings, `pass`, and
+/// private names are skipped.
+fn member(line: &Line) -> Option<String> {
+    let text = line.text.as_str();
+    match (line.colon, line.assign) {
+        (Some(colon), assign) if assign.is_none_or(|assign| colon < assign) => {
+            let name = text[..colon].trim();
+            let annotation = text[colon + 1..assign.unwrap_or(text.len())].trim();
+            (is_identifier(name) && is_public(name)).then(|| format!("{}: {}", name, annotation))
+        }
+        (_, Some(assign)) => {
+            let target = text[..assign].trim();
+            (is_identifier(target) && is_public(target)).then(|| text.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Names listed in a module-level `__all__`, if the module defines one
+fn dunder_all(lines: &[Line]) -> Option<Vec<String>> {
+    let mut exports: Option<Vec<String>> = None;
+    for line in lines.iter().filter(|line| line.indent == 0) {
+        let Some(rest) = line.text.strip_prefix("__all__") else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        if !(rest.starts_with('=') || rest.starts_with("+=") || rest.starts_with(':')) {
+            continue;
+        }
+        let names = exports.get_or_insert_with(Vec::new);
+        if !rest.starts_with("+=") {
+            names.clear();
+        }
+        names.extend(string_literals(rest));
+    }
+    exports
+}
+
+/// Contents of the string literals in normalized text
+fn string_literals(text: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(['"', '\'']) {
+        let quote = rest.as_bytes()[start] as char;
+        let body = &rest[start + 1..];
+        let Some(end) = body.find(quote) else {
+            break;
+        };
+        literals.push(body[..end].to_string());
+        rest = &body[end + 1..];
+    }
+    literals
+}
+
+/// Whether a decorator is `@deprecated(...)` (or `@warnings.deprecated(...)`)
+fn is_deprecated(decorator: &str) -> bool {
+    let name = decorator.trim_start_matches('@');
+    let name = name.split('(').next().unwrap_or_default();
+    name.rsplit('.').next() == Some("deprecated")
+}
+
+/// Names without a leading underscore, and dunder methods (`__init__`)
+fn is_public(name: &str) -> bool {
+    !name.starts_with('_') || (name.starts_with("__") && name.ends_with("__"))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Split source into logical lines, with errors for unterminated constructs
+fn logical_lines(file_path: &str, content: &str) -> (Vec<Line>, Vec<Diagnostic>) {
+    let chars: Vec<(usize, char)> = content.char_indices().collect();
+    let mut lines = Vec::new();
+    let mut errors = Vec::new();
+    let mut current: Option<Line> = None;
+    // Offsets of the open brackets
+    let mut open = Vec::new();
+    let mut space = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (offset, ch) = chars[i];
+        let Some(line) = current.as_mut() else {
+            // Start of a physical line: measure indentation
+            let mut indent = 0;
+            while let Some((_, c)) = chars.get(i) {
+                match c {
+                    ' ' => indent += 1,
+                    '\t' => indent = (indent / 8 + 1) * 8,
+                    '\x0c' => indent = 0,
+                    _ => break,
+                }
+                i += 1;
+            }
+            match chars.get(i) {
+                // Blank and comment-only lines
+                Some((_, '\n' | '\r' | '#')) => {
+                    while chars.get(i).is_some_and(|(_, c)| *c != '\n') {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                Some((offset, _)) => {
+                    current = Some(Line {
+                        offset: *offset,
+                        indent,
+                        ..Line::default()
+                    });
+                }
+                None => {}
+            }
+            continue;
+        };
+
+        if ch != '#' && ch != '\\' && !ch.is_whitespace() {
+            line.end = offset + ch.len_utf8();
+        }
+        match ch {
+            '#' => {
+                while chars.get(i + 1).is_some_and(|(_, c)| *c != '\n') {
+                    i += 1;
+                }
+            }
+            '\\' if matches!(chars.get(i + 1), Some((_, '\n' | '\r'))) => space = true,
+            '\n' if open.is_empty() => {
+                lines.extend(current.take());
+                space = false;
+            }
+            _ if ch.is_whitespace() => space = true,
+            '"' | '\'' => {
+                let triple = chars.get(i + 1).map(|(_, c)| *c) == Some(ch)
+                    && chars.get(i + 2).map(|(_, c)| *c) == Some(ch);
+                let quotes = if triple { 3 } else { 1 };
+                let mut end = i + quotes;
+                loop {
+                    match chars.get(end) {
+                        None => {
+                            errors.push(Diagnostic::error(
+                                file_path,
+                                content,
+                                "unterminated string",
+                                offset..offset + quotes,
+                            ));
+                            break;
+                        }
+                        Some((_, '\\')) => end += 2,
+                        Some((_, '\n')) if !triple => {
+                            errors.push(Diagnostic::error(
+                                file_path,
+                                content,
+                                "unterminated string",
+                                offset..offset + quotes,
+                            ));
+                            break;
+                        }
+                        Some((_, c))
+                            if *c == ch
+                                && (0..quotes)
+                                    .all(|k| chars.get(end + k).map(|(_, c)| *c) == Some(ch)) =>
+                        {
+                            end += quotes;
+                            break;
+                        }
+                        Some(_) => end += 1,
+                    }
+                }
+                let end = end.min(chars.len());
+                let literal_end = chars.get(end).map_or(content.len(), |(o, _)| *o);
+                line.end = literal_end;
+                push(line, &mut space, &content[offset..literal_end]);
+                i = end;
+                continue;
+            }
+            '(' | '[' | '{' => {
+                push(line, &mut space, &ch.to_string());
+                open.push(offset);
+            }
+            ')' | ']' | '}' => {
+                space = false;
+                // Trailing commas are formatting
+                if line.text.ends_with(',') {
+                    line.text.pop();
+                }
+                push(line, &mut space, &ch.to_string());
+                open.pop();
+            }
+            ',' | ':' => {
+                space = false;
+                if ch == ':' && open.is_empty() && line.colon.is_none() {
+                    line.colon = Some(line.text.len());
+                }
+                push(line, &mut space, &ch.to_string());
+            }
+            '=' => {
+                let previous = line.text.chars().last();
+                let next = chars.get(i + 1).map(|(_, c)| *c);
+                let is_assign = open.is_empty()
+                    && !matches!(
+                        previous.filter(|_| !space),
+                        Some(
+                            '=' | '<'
+                                | '>'
+                                | '!'
+                                | ':'
+                                | '+'
+                                | '-'
+                                | '*'
+                                | '/'
+                                | '%'
+                                | '&'
+                                | '|'
+                                | '^'
+                                | '@'
+                        )
+                    )
+                    && next != Some('=');
+                if is_assign && line.assign.is_none() {
+                    push(line, &mut space, "");
+                    line.assign = Some(line.text.len());
+                }
+                push(line, &mut space, "=");
+            }
+            _ => push(line, &mut space, &ch.to_string()),
+        }
+        i += 1;
+    }
+    lines.extend(current);
+    if let Some(&offset) = open.first() {
+        errors.push(Diagnostic::error(
+            file_path,
+            content,
+            "unclosed bracket",
+            offset..offset + 1,
+        ));
+    }
+
+    (lines, errors)
+}
+
+/// Append text, emitting a pending space unless it would follow an opening
+/// bracket
+fn push(line: &mut Line, space: &mut bool, text: &str) {
+    if *space && !line.text.is_empty() && !line.text.ends_with(['(', '[', '{']) {
+        line.text.push(' ');
+    }
+    *space = false;
+    line.text.push_str(text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULE: &str = r#"
+"""Authentication helpers."""
+from typing import Optional
+
+__all__ = ["login", "User"]
+
+
+@cache
+async def login(
+    user: str,
+    password: str = "a#b",  # not a comment
+    *,
+    remember: bool = False,
+) -> Optional["Session"]:
+    def helper(): pass
+    return None
+
+
+def _private(): ...
+
+
+class User(Base, metaclass=Meta):
+    """A user account."""
+
+    ADMIN = "admin"
+    id: int
+    name: str = "x: y"
+    _secret: str
+
+    def __init__(self, id: int) -> None:
+        self.id = id
+
+    @property
+    def display_name(self) -> str:
+        return self.name
+
+    def _hidden(self): ...
+
+    class Address:
+        city: str
+
+    if TYPE_CHECKING:
+        def typed(self) -> "User": ...
+
+
+# sintesi-ignore-next-symbol: internal
+def legacy(x): \
+        return x
+
+@warnings.deprecated("use login")
+def old_login(user): ...
+"#;
+
+    fn find<'a>(result: &'a AnalysisResult, name: &str) -> &'a SymbolInfo {
+        result.symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_extracts_functions_and_classes() {
+        let result = analyze_python("src/auth.py", MODULE);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["login", "_private", "User", "User.Address", "old_login"]
+        );
+
+        let login = find(&result, "login");
+        assert_eq!(login.symbol_type, SymbolType::Function);
+        assert_eq!(
+            login.signature,
+            "@cache async def login(user: str, password: str = \"a#b\", *, remember: bool = False) -> Optional[\"Session\"]"
+        );
+
+        let user = find(&result, "User");
+        assert_eq!(user.symbol_type, SymbolType::Class);
+        assert_eq!(
+            user.signature,
+            "class User(Base, metaclass=Meta) { ADMIN = \"admin\"; id: int; name: str; def __init__(self, id: int) -> None; @property def display_name(self) -> str; def typed(self) -> \"User\" }"
+        );
+        assert_eq!(
+            find(&result, "User.Address").signature,
+            "class Address { city: str }"
+        );
+
+        assert_eq!(result.ignored.len(), 1);
+        assert_eq!(result.ignored[0].symbol_name.as_deref(), Some("legacy"));
+        assert_eq!(result.ignored[0].reason.as_deref(), Some("internal"));
+        assert!(find(&result, "old_login").deprecated);
+    }
+
+    #[test]
+    fn test_exports_follow_dunder_all() {
+        let result = analyze_python("src/auth.py", MODULE);
+        assert!(find(&result, "login").is_exported);
+        assert!(find(&result, "User.Address").is_exported);
+        assert!(!find(&result, "old_login").is_exported);
+
+        let result = analyze_python("src/auth.py", "def a(): ...\ndef _b(): ...\n");
+        assert!(find(&result, "a").is_exported);
+        assert!(!find(&result, "_b").is_exported);
+    }
+
+    #[test]
+    fn test_reformatting_keeps_signature() {
+        let compact = analyze_python("a.py", "def f(a: int, b: str = 'x') -> None:\n    pass\n");
+        let exploded = analyze_python(
+            "a.py",
+            "def f(\n    a: int,\n    b: str = 'x',\n) -> None:  # done\n    pass\n",
+        );
+        assert_eq!(compact.symbols[0].signature, exploded.symbols[0].signature);
+
+        let changed = analyze_python("a.py", "def f(a: int, b: str = 'y') -> None: pass\n");
+        assert_ne!(compact.symbols[0].signature, changed.symbols[0].signature);
+
+        let broken = analyze_python("a.py", "def f(a:\n");
+        assert_eq!(broken.errors, ["Parse error: unclosed bracket at line 1"]);
+    }
+    #[test]
+    fn test_locations_span_decorators_and_body() {
+        let code =
+            "import os\n\n@cache\ndef f(x):\n    return x  # done\n\n\nclass C:\n    y = 1\n";
+        let result = analyze_python("a.py", code);
+
+        let f = result.symbols[0].location.unwrap();
+        assert_eq!((f.start_line, f.end_line), (2, 4));
+        assert_eq!(
+            &code[f.start_offset as usize..f.end_offset as usize],
+            "@cache\ndef f(x):\n    return x"
+        );
+        let c = result.symbols[1].location.unwrap();
+        assert_eq!((c.start_line, c.end_line), (7, 8));
+        assert_eq!(
+            &code[c.start_offset as usize..c.end_offset as usize],
+            "class C:\n    y = 1"
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! React function components
+//!
+//! A function component's header says little about its API: documentation
+//! anchored on `export function Button(props: ButtonProps)` should drift when
+//! a prop changes, not only when the parameter is renamed. With
+//! `AnalyzerOptions::react_components`, the analyzer reports top-level
+//! function components as `Component` symbols whose signature lists their
+//! props, in the same form as Vue and Svelte components (see `ast::sfc`):
+//!
+//! ```text
+//! component Button { label: string; variant?: "primary" | "ghost"; onClick?: () => void }
+//! ```
+//!
+//! A function component is a function with a capitalized name that renders
+//! JSX (or calls `createElement`), declared with `function` or assigned to a
+//! `const` as an arrow function or function expression, possibly wrapped in
+//! `memo` or `forwardRef`. Its props come from, in order:
+//! - A `FC<Props>` annotation of the variable (also `React.FC`,
+//!   `FunctionComponent`, and `VFC`), or the props type argument of
+//!   `forwardRef<Ref, Props>`
+//! - The type of the first parameter: a type literal, an interface or type
+//!   alias declared in the file (`Props`, `ButtonProps`), or an intersection
+//!   of these
+//! - The names destructured from the first parameter, optional when they
+//!   have a default
+//!
+//! Props types declared in other modules are spread as a whole
+//! (`...ButtonProps`).
+
+use super::printer::{braced, Printer};
+use super::sfc::props_of_type;
+use oxc_ast::ast::*;
+use oxc_ast::visit::walk;
+use oxc_ast::Visit;
+
+/// Type names annotating a variable as a function component
+const COMPONENT_TYPES: &[&str] = &["FC", "FunctionComponent", "VFC", "VoidFunctionComponent"];
+
+/// Names and signatures of the top-level function components of a program
+///
+/// # Arguments
+/// * `program` - The Oxc program produced by the analyzer
+/// * `source_text` - Source the program was parsed from
+///
+/// # Returns
+/// `(name, signature)` pairs in declaration order, the signature being
+/// `component Name { ...props }`
+pub fn function_components(program: &Program<'_>, source_text: &str) -> Vec<(String, String)> {
+    let extractor = PropsExtractor {
+        printer: Printer::new(source_text),
+        program,
+    };
+    let mut components = Vec::new();
+    for statement in &program.body {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export) => export.declaration.as_ref(),
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    components.extend(extractor.function(func));
+                    continue;
+                }
+                _ => None,
+            },
+            statement => statement.as_declaration(),
+        };
+        match declaration {
+            Some(Declaration::FunctionDeclaration(func)) => {
+                components.extend(extractor.function(func));
+            }
+            Some(Declaration::VariableDeclaration(decl)) => {
+                for declarator in &decl.declarations {
+                    components.extend(extractor.declarator(declarator));
+                }
+            }
+            _ => {}
+        }
+    }
+    components
+}
+
+/// Resolves the props of components declared in `program`
+struct PropsExtractor<'p, 'a> {
+    printer: Printer<'p>,
+    program: &'p Program<'a>,
+}
+
+impl<'p, 'a> PropsExtractor<'p, 'a> {
+    /// A `function` component
+    fn function(&self, func: &'p Function<'a>) -> Option<(String, String)> {
+        let name = func.id.as_ref()?.name.as_str();
+        let body = func.body.as_ref()?;
+        if !is_component_name(name) || !renders(|visitor| visitor.visit_function_body(body)) {
+            return None;
+        }
+        let props = self.props(None, func.params.items.first());
+        Some((name.to_string(), signature(name, &props)))
+    }
+
+    /// A component assigned to a `const`
+    fn declarator(&self, declarator: &'p VariableDeclarator<'a>) -> Option<(String, String)> {
+        let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind else {
+            return None;
+        };
+        let name = id.name.as_str();
+        if !is_component_name(name) {
+            return None;
+        }
+        let annotated = declarator
+            .id
+            .type_annotation
+            .as_ref()
+            .and_then(|annotation| component_props_type(&annotation.type_annotation));
+        let (function, wrapped) = unwrap(declarator.init.as_ref()?)?;
+        let props_type = annotated.or(wrapped);
+        let (params, rendered) = match function {
+            Expression::ArrowFunctionExpression(arrow) => (
+                &arrow.params,
+                renders(|visitor| visitor.visit_function_body(&arrow.body)),
+            ),
+            Expression::FunctionExpression(func) => (
+                &func.params,
+                func.body
+                    .as_ref()
+                    .is_some_and(|body| renders(|visitor| visitor.visit_function_body(body))),
+            ),
+            _ => return None,
+        };
+        if !rendered {
+            return None;
+        }
+        let props = self.props(props_type, params.items.first());
+        Some((name.to_string(), signature(name, &props)))
+    }
+
+    /// Props from a props type, or else from the props parameter
+    fn props(
+        &self,
+        props_type: Option<&'p TSType<'a>>,
+        param: Option<&'p FormalParameter<'a>>,
+    ) -> Vec<String> {
+        let pattern = param.map(|param| &param.pattern);
+        let annotation = pattern
+            .and_then(|pattern| pattern.type_annotation.as_ref())
+            .map(|annotation| &annotation.type_annotation);
+        if let Some(props_type) = props_type.or(annotation) {
+            return self.props_of(props_type);
+        }
+        let Some(BindingPatternKind::ObjectPattern(object)) = pattern.map(|p| &p.kind) else {
+            return Vec::new();
+        };
+        let mut props = Vec::new();
+        for property in &object.properties {
+            let name = self.printer.property_key(&property.key, property.computed);
+            match property.value.kind {
+                BindingPatternKind::AssignmentPattern(_) => props.push(format!("{}?", name)),
+                _ => props.push(name),
+            }
+        }
+        if let Some(rest) = &object.rest {
+            props.push(format!(
+                "...{}",
+                self.printer.binding_pattern(&rest.argument)
+            ));
+        }
+        props
+    }
+
+    /// Props of a type, through intersections (`BaseProps & { size: number }`)
+    /// and the type aliases naming them
+    fn props_of(&self, props_type: &'p TSType<'a>) -> Vec<String> {
+        match props_type {
+            TSType::TSIntersectionType(intersection) => intersection
+                .types
+                .iter()
+                .flat_map(|part| self.props_of(part))
+                .collect(),
+            TSType::TSParenthesizedType(parenthesized) => {
+                self.props_of(&parenthesized.type_annotation)
+            }
+            TSType::TSTypeReference(reference) => match self.alias(reference) {
+                Some(aliased @ TSType::TSIntersectionType(_)) => self.props_of(aliased),
+                _ => props_of_type(&self.printer, self.program, props_type),
+            },
+            props_type => props_of_type(&self.printer, self.program, props_type),
+        }
+    }
+
+    /// Type named by a reference to a type alias declared in the program
+    fn alias(&self, reference: &TSTypeReference<'a>) -> Option<&'p TSType<'a>> {
+        let TSTypeName::IdentifierReference(ident) = &reference.type_name else {
+            return None;
+        };
+        self.program.body.iter().find_map(|statement| {
+            let declaration = match statement {
+                Statement::ExportNamedDeclaration(export) => export.declaration.as_ref()?,
+                statement => statement.as_declaration()?,
+            };
+            match declaration {
+                Declaration::TSTypeAliasDeclaration(decl) if decl.id.name == ident.name => {
+                    Some(&decl.type_annotation)
+                }
+                _ => None,
+            }
+        })
+    }
+}
+
+/// The function of a component initializer, without `memo` and `forwardRef`
+/// wrappers, and the props type argument of `forwardRef<Ref, Props>`
+fn unwrap<'p, 'a>(
+    init: &'p Expression<'a>,
+) -> Option<(&'p Expression<'a>, Option<&'p TSType<'a>>)> {
+    match init {
+        Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_) => {
+            Some((init, None))
+        }
+        Expression::CallExpression(call) => {
+            let (function, props_type) = unwrap(call.arguments.first()?.as_expression()?)?;
+            let type_arguments = call.type_parameters.as_ref().map(|t| &t.params);
+            let props_type = match call.callee_name()? {
+                "memo" => props_type.or(type_arguments.and_then(|t| t.first())),
+                "forwardRef" => props_type.or(type_arguments.and_then(|t| t.get(1))),
+                _ => return None,
+            };
+            Some((function, props_type))
+        }
+        Expression::ParenthesizedExpression(parenthesized) => unwrap(&parenthesized.expression),
+        _ => None,
+    }
+}
+
+/// `Props` of a `FC<Props>` (or `React.FC<Props>`) annotation
+fn component_props_type<'p, 'a>(annotation: &'p TSType<'a>) -> Option<&'p TSType<'a>> {
+    let TSType::TSTypeReference(reference) = annotation else {
+        return None;
+    };
+    let name = match &reference.type_name {
+        TSTypeName::IdentifierReference(ident) => ident.name.as_str(),
+        TSTypeName::QualifiedName(qualified) => qualified.right.name.as_str(),
+    };
+    if !COMPONENT_TYPES.contains(&name) {
+        return None;
+    }
+    reference.type_parameters.as_ref()?.params.first()
+}
+
+/// Whether a name can be a component's: React treats lowercase JSX tags as
+/// host elements
+fn is_component_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+fn signature(name: &str, props: &[String]) -> String {
+    format!("component {} {}", name, braced(props, "; "))
+}
+
+/// Whether the code walked by `walk` renders JSX or calls `createElement`
+fn renders(walk: impl FnOnce(&mut JsxFinder)) -> bool {
+    let mut finder = JsxFinder { found: false };
+    walk(&mut finder);
+    finder.found
+}
+
+struct JsxFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for JsxFinder {
+    fn visit_jsx_element(&mut self, _element: &JSXElement<'a>) {
+        self.found = true;
+    }
+
+    fn visit_jsx_fragment(&mut self, _fragment: &JSXFragment<'a>) {
+        self.found = true;
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        if call.callee_name() == Some("createElement") {
+            self.found = true;
+        }
+        walk::walk_call_expression(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{AnalyzerOptions, AstAnalyzerInternal, SymbolInfo};
+    use crate::types::SymbolType;
+
+    const CODE: &str = r#"import React, { forwardRef, memo } from "react";
+import type { CardProps } from "./card";
+
+interface ButtonProps {
+  label: string;
+  variant?: "primary" | "ghost";
+}
+
+export function Button({ label, variant = "primary" }: ButtonProps) {
+  return <button className={variant}>{label}</button>;
+}
+
+type AvatarProps = { src: string } & { size?: number };
+
+export const Avatar: React.FC<AvatarProps> = ({ src, size }) => <img src={src} width={size} />;
+
+export const Card = memo(function Card(props: CardProps) {
+  return <div>{props.title}</div>;
+});
+
+export const Input = forwardRef<HTMLInputElement, { value: string }>((props, ref) => (
+  <input ref={ref} value={props.value} />
+));
+
+export const Badge = ({ count, ...rest }) => React.createElement("span", rest, count);
+
+export default function Layout() {
+  return <></>;
+}
+
+export function formatLabel(label: string) {
+  return label.trim();
+}
+
+export function Parse(input: string) {
+  return input.split(",");
+}
+"#;
+
+    fn analyze(react_components: bool) -> Vec<SymbolInfo> {
+        let analyzer = AstAnalyzerInternal::with_options(
+            AnalyzerOptions::new().react_components(react_components),
+        );
+        let result = analyzer.analyze_file("src/components.tsx", CODE);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        result.symbols
+    }
+
+    #[test]
+    fn test_component_signatures() {
+        let symbols = analyze(true);
+        let components: Vec<&str> = symbols
+            .iter()
+            .filter(|s| s.symbol_type == SymbolType::Component)
+            .map(|s| s.signature.as_str())
+            .collect();
+        assert_eq!(
+            components,
+            [
+                "component Button { label: string; variant?: \"primary\" | \"ghost\" }",
+                "component Avatar { src: string; size?: number }",
+                "component Card { ...CardProps }",
+                "component Input { value: string }",
+                "component Badge { count; ...rest }",
+                "component Layout {}",
+            ]
+        );
+
+        // Functions that are not components keep their signature
+        let function = |name: &str| symbols.iter().find(|s| s.name == name).unwrap();
+        assert_eq!(function("formatLabel").symbol_type, SymbolType::Function);
+        assert_eq!(function("Parse").symbol_type, SymbolType::Function);
+        // Locations, doc tags, and exports are those of the declaration
+        assert!(function("Button").is_exported);
+        assert_eq!(function("Button").location.unwrap().start_line, 8);
+    }
+
+    #[test]
+    fn test_off_by_default() {
+        let symbols = analyze(false);
+        assert!(symbols
+            .iter()
+            .all(|s| s.symbol_type != SymbolType::Component));
+        let button = symbols.iter().find(|s| s.name == "Button").unwrap();
+        assert_eq!(
+            button.signature,
+            "function Button({ label, variant = \"primary\" }: ButtonProps)"
+        );
+    }
+}
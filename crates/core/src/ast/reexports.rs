@@ -0,0 +1,431 @@
+//! Re-export chain resolution
+//!
+//! Barrel files (`index.ts`) re-export symbols defined in deeper modules, so
+//! an anchor may reference `src/index.ts#login` while `login` is declared in
+//! `src/auth/session.ts`. `resolve_reexport` follows the barrel's re-exports
+//! to the module that defines the symbol:
+//!
+//! - `export { login } from './auth'` and `export { a as login } from './auth'`
+//! - `export * from './auth'`
+//! - `import { login } from './auth'; export { login }`
+//! - `export { default as login } from './auth'`, resolved to the default
+//!   export's declaration
+//!
+//! `module_exports` lists everything a module exports with the declaration
+//! each name resolves to, so a barrel's public API can be attributed to the
+//! files defining it.
+//!
+//! Only relative specifiers are followed; package imports are never resolved.
+
+use super::analyzer::{AstAnalyzerInternal, DEFAULT_EXPORT};
+use super::arena::with_allocator;
+use super::module_kind::source_type_for;
+use crate::graph::{module_candidates, normalize_path};
+use crate::provider::ContentProvider;
+use oxc_ast::ast::*;
+use oxc_parser::Parser;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+/// The declaration a re-exported symbol resolves to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedSymbol {
+    /// File declaring the symbol
+    pub file_path: String,
+    /// Symbol name in that file
+    pub name: String,
+}
+
+impl ResolvedSymbol {
+    /// Code reference of the declaration (`file#symbol`)
+    pub fn code_ref(&self) -> String {
+        format!("{}#{}", self.file_path, self.name)
+    }
+}
+
+/// Resolve a symbol exported by a file to the module declaring it
+///
+/// Returns `None` when the file declares the symbol itself, or when the
+/// chain cannot be followed (missing module, package import, cycle).
+pub fn resolve_reexport(
+    provider: &dyn ContentProvider,
+    file_path: &str,
+    symbol: &str,
+) -> Option<ResolvedSymbol> {
+    let mut resolver = Resolver {
+        provider,
+        analyzer: AstAnalyzerInternal::new(),
+        visited: HashSet::new(),
+    };
+    let exports = resolver.exports_of(file_path)?;
+    resolver
+        .visited
+        .insert((file_path.to_string(), symbol.to_string()));
+    resolver.follow(file_path, &exports, symbol)
+}
+
+/// A name exported by a module and the declaration it resolves to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedSymbol {
+    /// Name the module exports
+    pub name: String,
+    /// Declaration of the symbol (the module itself for local declarations)
+    pub declaration: ResolvedSymbol,
+}
+
+/// Every name a module exports, sorted, with the declaration it resolves to
+///
+/// Names declared in the file resolve to the file itself; re-exported names,
+/// including those pulled in by `export *`, to the module declaring them.
+/// Names whose chain cannot be followed are left out.
+pub fn module_exports(provider: &dyn ContentProvider, file_path: &str) -> Vec<ExportedSymbol> {
+    let mut resolver = Resolver {
+        provider,
+        analyzer: AstAnalyzerInternal::new(),
+        visited: HashSet::new(),
+    };
+    let Some(exports) = resolver.exports_of(file_path) else {
+        return Vec::new();
+    };
+    let mut files = HashSet::from([file_path.to_string()]);
+    let names = resolver.exported_names(file_path, &exports, &mut files);
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let declaration = if exports.exported.contains(&name) {
+                ResolvedSymbol {
+                    file_path: file_path.to_string(),
+                    name: name.clone(),
+                }
+            } else {
+                resolver.visited.clear();
+                resolver
+                    .visited
+                    .insert((file_path.to_string(), name.clone()));
+                resolver.follow(file_path, &exports, &name)?
+            };
+            Some(ExportedSymbol { name, declaration })
+        })
+        .collect()
+}
+
+/// Where a name exported or imported by a module comes from
+#[derive(Debug, Clone)]
+struct Binding {
+    specifier: String,
+    imported: String,
+}
+
+/// Module-level imports and exports of a file
+#[derive(Debug, Default)]
+struct ModuleExports {
+    /// Symbols declared in the file
+    declared: HashSet<String>,
+    /// Declared symbols exported with their declaration
+    exported: HashSet<String>,
+    /// Local names bound by imports
+    imports: HashMap<String, Binding>,
+    /// `export { a as b } from '...'`, keyed by exported name
+    reexports: HashMap<String, Binding>,
+    /// `export { a as b }` of local or imported names, keyed by exported name
+    locals: HashMap<String, String>,
+    /// `export * from '...'` specifiers, in source order
+    star: Vec<String>,
+    /// Local name of a default export that refers to a named declaration
+    default_local: Option<String>,
+}
+
+struct Resolver<'p> {
+    provider: &'p dyn ContentProvider,
+    analyzer: AstAnalyzerInternal,
+    visited: HashSet<(String, String)>,
+}
+
+impl Resolver<'_> {
+    /// Resolve `symbol` through the exports of `file_path`
+    fn follow(
+        &mut self,
+        file_path: &str,
+        exports: &ModuleExports,
+        symbol: &str,
+    ) -> Option<ResolvedSymbol> {
+        let local = if symbol == DEFAULT_EXPORT {
+            exports.default_local.as_deref()
+        } else {
+            exports.locals.get(symbol).map(String::as_str)
+        };
+        if let Some(local) = local {
+            if let Some(binding) = exports.imports.get(local) {
+                return self.resolve_binding(file_path, binding);
+            }
+            if exports.declared.contains(local) {
+                return Some(ResolvedSymbol {
+                    file_path: file_path.to_string(),
+                    name: local.to_string(),
+                });
+            }
+        }
+
+        if let Some(binding) = exports.reexports.get(symbol) {
+            return self.resolve_binding(file_path, binding);
+        }
+        // `export *` never re-exports a default
+        if symbol == DEFAULT_EXPORT {
+            return None;
+        }
+        exports.star.iter().find_map(|specifier| {
+            self.resolve_binding(
+                file_path,
+                &Binding {
+                    specifier: specifier.clone(),
+                    imported: symbol.to_string(),
+                },
+            )
+        })
+    }
+
+    /// Resolve a name imported from a module specifier
+    fn resolve_binding(&mut self, from: &str, binding: &Binding) -> Option<ResolvedSymbol> {
+        let target = self.resolve_module(from, &binding.specifier)?;
+        if !self
+            .visited
+            .insert((target.clone(), binding.imported.clone()))
+        {
+            return None;
+        }
+
+        let exports = self.exports_of(&target)?;
+        if exports.exported.contains(&binding.imported) {
+            return Some(ResolvedSymbol {
+                file_path: target,
+                name: binding.imported.clone(),
+            });
+        }
+        self.follow(&target, &exports, &binding.imported)
+    }
+
+    /// Names exported by a module, through `export *` of modules not in `files`
+    fn exported_names(
+        &self,
+        file_path: &str,
+        exports: &ModuleExports,
+        files: &mut HashSet<String>,
+    ) -> BTreeSet<String> {
+        let mut names: BTreeSet<String> = exports
+            .exported
+            .iter()
+            .chain(exports.locals.keys())
+            .chain(exports.reexports.keys())
+            .cloned()
+            .collect();
+        if exports.default_local.is_some() {
+            names.insert(DEFAULT_EXPORT.to_string());
+        }
+        for specifier in &exports.star {
+            let Some(target) = self.resolve_module(file_path, specifier) else {
+                continue;
+            };
+            if !files.insert(target.clone()) {
+                continue;
+            }
+            let Some(target_exports) = self.exports_of(&target) else {
+                continue;
+            };
+            // `export *` never re-exports a default
+            names.extend(
+                self.exported_names(&target, &target_exports, files)
+                    .into_iter()
+                    .filter(|name| name != DEFAULT_EXPORT),
+            );
+        }
+        names
+    }
+
+    /// File a relative specifier points at, relative to the project root
+    fn resolve_module(&self, from: &str, specifier: &str) -> Option<String> {
+        if !specifier.starts_with('.') {
+            return None;
+        }
+        let dir = Path::new(from).parent().unwrap_or(Path::new(""));
+        let resolved = normalize_path(&dir.join(specifier));
+        module_candidates(&resolved)
+            .into_iter()
+            .find(|candidate| self.provider.exists(candidate))
+            .map(|candidate| candidate.to_string_lossy().replace('\\', "/"))
+    }
+
+    fn exports_of(&self, file_path: &str) -> Option<ModuleExports> {
+        let content = self.provider.read(Path::new(file_path)).ok()?;
+        let source_type = source_type_for(file_path, &content, None)?;
+        let mut exports = ModuleExports::default();
+        for symbol in self.analyzer.analyze_file(file_path, &content).symbols {
+            if symbol.is_exported {
+                exports.exported.insert(symbol.name.clone());
+            }
+            exports.declared.insert(symbol.name);
+        }
+        with_allocator(|allocator| {
+            let ret = Parser::new(allocator, &content, source_type).parse();
+            for statement in &ret.program.body {
+                match statement {
+                    Statement::ImportDeclaration(decl) => {
+                        let specifier = decl.source.value.to_string();
+                        for import in decl.specifiers.iter().flatten() {
+                            let (local, imported) = match import {
+                                ImportDeclarationSpecifier::ImportSpecifier(s) => {
+                                    (s.local.name.to_string(), s.imported.name().to_string())
+                                }
+                                ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
+                                    (s.local.name.to_string(), DEFAULT_EXPORT.to_string())
+                                }
+                                // Namespace members are not individual symbols
+                                ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => continue,
+                            };
+                            exports.imports.insert(
+                                local,
+                                Binding {
+                                    specifier: specifier.clone(),
+                                    imported,
+                                },
+                            );
+                        }
+                    }
+                    Statement::ExportNamedDeclaration(decl) => {
+                        for export in &decl.specifiers {
+                            let local = export.local.name().to_string();
+                            let exported = export.exported.name().to_string();
+                            match &decl.source {
+                                Some(source) => {
+                                    exports.reexports.insert(
+                                        exported,
+                                        Binding {
+                                            specifier: source.value.to_string(),
+                                            imported: local,
+                                        },
+                                    );
+                                }
+                                None => {
+                                    exports.locals.insert(exported, local);
+                                }
+                            }
+                        }
+                    }
+                    Statement::ExportAllDeclaration(decl) if decl.exported.is_none() => {
+                        exports.star.push(decl.source.value.to_string());
+                    }
+                    Statement::ExportDefaultDeclaration(decl) => {
+                        exports.default_local = match &decl.declaration {
+                            ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                                func.id.as_ref().map(|id| id.name.to_string())
+                            }
+                            ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                                class.id.as_ref().map(|id| id.name.to_string())
+                            }
+                            ExportDefaultDeclarationKind::Identifier(ident) => {
+                                Some(ident.name.to_string())
+                            }
+                            _ => None,
+                        };
+                    }
+                    _ => {}
+                }
+            }
+        });
+        Some(exports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::MemoryProvider;
+
+    fn resolved(file_path: &str, name: &str) -> Option<ResolvedSymbol> {
+        Some(ResolvedSymbol {
+            file_path: file_path.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_follows_reexport_chains() {
+        let mut provider = MemoryProvider::new();
+        provider.set(
+            "src/index.ts",
+            "export * from './auth';\nexport { default as Client, format as formatDate } from './util/index';",
+        );
+        provider.set(
+            "src/auth/index.ts",
+            "import { login } from './session';\nexport { login };",
+        );
+        provider.set(
+            "src/auth/session.ts",
+            "export function login(user: string) {}",
+        );
+        provider.set(
+            "src/util/index.ts",
+            "export { format } from './date';\nexport { default } from './client';",
+        );
+        provider.set("src/util/date.ts", "export const format = (d: Date) => '';");
+        provider.set(
+            "src/util/client.ts",
+            "class Client {}\nexport default Client;",
+        );
+
+        assert_eq!(
+            resolve_reexport(&provider, "src/index.ts", "login"),
+            resolved("src/auth/session.ts", "login")
+        );
+        assert_eq!(
+            resolve_reexport(&provider, "src/index.ts", "formatDate"),
+            resolved("src/util/date.ts", "format")
+        );
+        assert_eq!(
+            resolve_reexport(&provider, "src/index.ts", "Client"),
+            resolved("src/util/client.ts", "Client")
+        );
+        assert_eq!(resolve_reexport(&provider, "src/index.ts", "logout"), None);
+    }
+
+    #[test]
+    fn test_module_exports() {
+        let mut provider = MemoryProvider::new();
+        provider.set(
+            "src/index.ts",
+            "export * from './auth';\nexport { format as formatDate } from './date';\nexport { x } from 'pkg';\nexport const VERSION = '1';",
+        );
+        provider.set(
+            "src/auth.ts",
+            "export * from './index';\nexport function login() {}\nexport default class Session {}",
+        );
+        provider.set("src/date.ts", "export const format = (d: Date) => '';");
+
+        let exports: Vec<(String, String)> = module_exports(&provider, "src/index.ts")
+            .into_iter()
+            .map(|e| (e.name, e.declaration.code_ref()))
+            .collect();
+        // Named default declarations are exported under their name, as analyzed
+        let expected = [
+            ("Session", "src/auth.ts#Session"),
+            ("VERSION", "src/index.ts#VERSION"),
+            ("formatDate", "src/date.ts#format"),
+            ("login", "src/auth.ts#login"),
+        ];
+        assert_eq!(
+            exports,
+            expected.map(|(name, code_ref)| (name.to_string(), code_ref.to_string()))
+        );
+        assert!(module_exports(&provider, "src/missing.ts").is_empty());
+    }
+
+    #[test]
+    fn test_cycles_and_packages_are_not_followed() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/a.ts", "export * from './b';\nexport { x } from 'pkg';");
+        provider.set("src/b.ts", "export * from './a';");
+
+        assert_eq!(resolve_reexport(&provider, "src/a.ts", "missing"), None);
+        assert_eq!(resolve_reexport(&provider, "src/a.ts", "x"), None);
+    }
+}
@@ -0,0 +1,425 @@
+//! HTTP route extraction for Express, Fastify, and NestJS
+//!
+//! This module walks an already-parsed Oxc program and emits one `Route`
+//! symbol per endpoint, named `METHOD /path`, so documentation can anchor
+//! individual endpoints and drift when a route's path, middleware chain, or
+//! handler changes.
+//!
+//! Recognized patterns:
+//! - `app.get('/users', auth, listUsers)` (Express/Fastify/Koa-router style)
+//! - `router.route('/users').get(listUsers).post(createUser)`
+//! - `fastify.route({ method: 'GET', url: '/users', handler })`
+//! - NestJS `@Controller('users')` classes with `@Get(':id')` methods
+
+use super::analyzer::SymbolInfo;
+use crate::types::{line_starts, SymbolLocation, SymbolType};
+use oxc_ast::ast::*;
+use oxc_ast::visit::walk;
+use oxc_ast::Visit;
+use oxc_span::{GetSpan, Span};
+
+/// Method names that register a route on an Express-like router
+const ROUTER_METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "options", "head", "all",
+];
+
+/// NestJS method decorators and the HTTP method they map to
+const NEST_DECORATORS: &[(&str, &str)] = &[
+    ("Get", "GET"),
+    ("Post", "POST"),
+    ("Put", "PUT"),
+    ("Delete", "DELETE"),
+    ("Patch", "PATCH"),
+    ("Options", "OPTIONS"),
+    ("Head", "HEAD"),
+    ("All", "ALL"),
+];
+
+/// Extract HTTP routes from a parsed program
+///
+/// # Arguments
+/// * `program` - The Oxc program produced by the analyzer
+/// * `file_path` - File path recorded on each route symbol
+/// * `source_text` - Source the program was parsed from (for handler text)
+pub fn extract_routes(
+    program: &Program<'_>,
+    file_path: &str,
+    source_text: &str,
+) -> Vec<SymbolInfo> {
+    let mut extractor = RouteExtractor {
+        file_path,
+        source_text,
+        line_starts: line_starts(source_text),
+        routes: Vec::new(),
+    };
+    extractor.visit_program(program);
+    extractor.routes
+}
+
+struct RouteExtractor<'s> {
+    file_path: &'s str,
+    source_text: &'s str,
+    line_starts: Vec<usize>,
+    routes: Vec<SymbolInfo>,
+}
+
+impl<'s> RouteExtractor<'s> {
+    fn text(&self, start: u32, end: u32) -> String {
+        let raw = self
+            .source_text
+            .get(start as usize..end as usize)
+            .unwrap_or("");
+        raw.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn push_route(&mut self, span: Span, method: &str, path: &str, signature: String) {
+        let location =
+            SymbolLocation::from_offsets(&self.line_starts, span.start as usize, span.end as usize);
+        self.routes.push(SymbolInfo {
+            name: format!("{} {}", method, path),
+            symbol_type: SymbolType::Route,
+            signature,
+            is_exported: true,
+            file_path: self.file_path.to_string(),
+            deprecated: false,
+            release_tag: None,
+            location: Some(location),
+            type_params: Vec::new(),
+        });
+    }
+
+    /// Describe a handler or middleware argument for the route signature
+    fn describe_handler(&self, expr: &Expression<'_>) -> String {
+        match expr {
+            Expression::Identifier(ident) => ident.name.to_string(),
+            Expression::ArrowFunctionExpression(arrow) => {
+                format!(
+                    "{} => {{}}",
+                    self.text(arrow.span.start, arrow.body.span.start)
+                        .trim_end_matches("=>")
+                        .trim()
+                )
+            }
+            Expression::FunctionExpression(func) => match &func.body {
+                Some(body) => self.text(func.span.start, body.span.start),
+                None => self.text(func.span.start, func.span.end),
+            },
+            other => {
+                let span = other.span();
+                self.text(span.start, span.end)
+            }
+        }
+    }
+
+    /// Handle `router.get('/path', ...handlers)` and `router.route('/path').get(...)`
+    fn try_router_call(&mut self, call: &CallExpression<'_>) {
+        let Some(member) = call.callee.as_member_expression() else {
+            return;
+        };
+        let Some(method) = member.static_property_name() else {
+            return;
+        };
+
+        if method == "route" {
+            self.try_route_object(call);
+            return;
+        }
+        if !ROUTER_METHODS.contains(&method) {
+            return;
+        }
+
+        let first = call.arguments.first().and_then(|a| a.as_expression());
+        // Number of leading arguments that are not handlers (the path itself)
+        let (path, skip) = match first.and_then(static_string) {
+            Some(path) => (path, 1),
+            None => {
+                // Chained form: router.route('/path').get(a).post(b)
+                let mut object = member.object();
+                let inner = loop {
+                    let Expression::CallExpression(inner) = object else {
+                        return;
+                    };
+                    if inner.callee_name() == Some("route") {
+                        break inner;
+                    }
+                    match inner.callee.as_member_expression() {
+                        Some(parent) => object = parent.object(),
+                        None => return,
+                    }
+                };
+                match inner
+                    .arguments
+                    .first()
+                    .and_then(|a| a.as_expression())
+                    .and_then(static_string)
+                {
+                    Some(path) => (path, 0),
+                    None => return,
+                }
+            }
+        };
+
+        if !is_route_path(&path) {
+            return;
+        }
+
+        let handlers: Vec<String> = call
+            .arguments
+            .iter()
+            .skip(skip)
+            .filter_map(|a| a.as_expression())
+            .map(|e| self.describe_handler(e))
+            .collect();
+        let Some((handler, middleware)) = handlers.split_last() else {
+            return;
+        };
+
+        let method = method.to_uppercase();
+        let middleware = if middleware.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", middleware.join(", "))
+        };
+        let signature = format!("route {} {}{} -> {}", method, path, middleware, handler);
+        self.push_route(call.span, &method, &path, signature);
+    }
+
+    /// Handle `fastify.route({ method, url, handler })`
+    fn try_route_object(&mut self, call: &CallExpression<'_>) {
+        let Some(Expression::ObjectExpression(options)) =
+            call.arguments.first().and_then(|a| a.as_expression())
+        else {
+            return;
+        };
+
+        let mut methods = Vec::new();
+        let mut url = None;
+        let mut handler = None;
+        for prop in &options.properties {
+            let ObjectPropertyKind::ObjectProperty(prop) = prop else {
+                continue;
+            };
+            match prop.key.static_name().as_deref() {
+                Some("method") => match &prop.value {
+                    Expression::ArrayExpression(list) => methods.extend(
+                        list.elements
+                            .iter()
+                            .filter_map(|e| e.as_expression())
+                            .filter_map(static_string),
+                    ),
+                    value => methods.extend(static_string(value)),
+                },
+                Some("url") | Some("path") => url = static_string(&prop.value),
+                Some("handler") => handler = Some(self.describe_handler(&prop.value)),
+                _ => {}
+            }
+        }
+
+        let (Some(url), Some(handler)) = (url, handler) else {
+            return;
+        };
+        for method in methods {
+            let method = method.to_uppercase();
+            let signature = format!("route {} {} -> {}", method, url, handler);
+            self.push_route(call.span, &method, &url, signature);
+        }
+    }
+
+    /// Handle NestJS `@Controller()` classes with HTTP method decorators
+    fn try_nest_controller(&mut self, class: &Class<'_>) {
+        let Some(prefix) = class
+            .decorators
+            .iter()
+            .find_map(|d| decorator_call(d, "Controller"))
+        else {
+            return;
+        };
+        let class_name = class
+            .id
+            .as_ref()
+            .map(|id| id.name.to_string())
+            .unwrap_or_else(|| "default".to_string());
+
+        for element in &class.body.body {
+            let ClassElement::MethodDefinition(method) = element else {
+                continue;
+            };
+            for decorator in &method.decorators {
+                for (decorator_name, http_method) in NEST_DECORATORS {
+                    let Some(sub_path) = decorator_call(decorator, decorator_name) else {
+                        continue;
+                    };
+                    let path = join_paths(&prefix, &sub_path);
+                    let handler_end = method
+                        .value
+                        .body
+                        .as_ref()
+                        .map(|b| b.span.start)
+                        .unwrap_or(method.span.end);
+                    let handler = self.text(method.key.span().start, handler_end);
+                    let signature = format!(
+                        "route {} {} -> {}.{}",
+                        http_method, path, class_name, handler
+                    );
+                    self.push_route(method.span, http_method, &path, signature);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for RouteExtractor<'_> {
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        self.try_router_call(call);
+        walk::walk_call_expression(self, call);
+    }
+
+    fn visit_class(&mut self, class: &Class<'a>) {
+        self.try_nest_controller(class);
+        walk::walk_class(self, class);
+    }
+}
+
+/// If the decorator is `@name(...)` (or bare `@name`), return its path argument
+///
+/// The path is the first string argument, or the `path` property of an
+/// options object (`@Controller({ path: 'users' })`); empty when absent.
+fn decorator_call(decorator: &Decorator<'_>, name: &str) -> Option<String> {
+    match &decorator.expression {
+        Expression::Identifier(ident) if ident.name == name => Some(String::new()),
+        Expression::CallExpression(call) if call.callee_name() == Some(name) => {
+            let first = call.arguments.first().and_then(|a| a.as_expression());
+            let path = match first {
+                Some(Expression::ObjectExpression(options)) => {
+                    options.properties.iter().find_map(|p| match p {
+                        ObjectPropertyKind::ObjectProperty(p)
+                            if p.key.static_name().as_deref() == Some("path") =>
+                        {
+                            static_string(&p.value)
+                        }
+                        _ => None,
+                    })
+                }
+                Some(expr) => static_string(expr),
+                None => None,
+            };
+            Some(path.unwrap_or_default())
+        }
+        _ => None,
+    }
+}
+
+/// Statically known string value of a literal or expression-free template
+fn static_string(expr: &Expression<'_>) -> Option<String> {
+    match expr {
+        Expression::StringLiteral(lit) => Some(lit.value.to_string()),
+        Expression::TemplateLiteral(tpl) if tpl.expressions.is_empty() => {
+            tpl.quasis.first().map(|q| q.value.raw.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Router paths start with `/` (or are the `*` catch-all); this filters out
+/// unrelated `.get('key')` calls on maps, caches, and the like.
+fn is_route_path(path: &str) -> bool {
+    path.starts_with('/') || path == "*"
+}
+
+/// Join a controller prefix and a method path into a normalized route path
+fn join_paths(prefix: &str, sub: &str) -> String {
+    let segments: Vec<&str> = [prefix, sub]
+        .iter()
+        .map(|s| s.trim_matches('/'))
+        .filter(|s| !s.is_empty())
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    fn routes(code: &str) -> Vec<SymbolInfo> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, code, SourceType::ts()).parse();
+        extract_routes(&ret.program, "server.ts", code)
+    }
+
+    #[test]
+    fn test_express_routes() {
+        let found = routes(
+            r#"
+            app.get('/users', auth, listUsers);
+            router.post(`/users/:id`, async (req, res) => { res.send(1); });
+            cache.get('key');
+            router.route('/items').put(updateItem).delete(controller.remove);
+            "#,
+        );
+        let sigs: Vec<&str> = found.iter().map(|r| r.signature.as_str()).collect();
+
+        assert_eq!(found.len(), 4);
+        assert!(found
+            .iter()
+            .all(|r| r.symbol_type == SymbolType::Route && r.is_exported));
+        assert!(sigs.contains(&"route GET /users [auth] -> listUsers"));
+        assert!(sigs.contains(&"route POST /users/:id -> async (req, res) => {}"));
+        assert!(sigs.contains(&"route PUT /items -> updateItem"));
+        assert!(sigs.contains(&"route DELETE /items -> controller.remove"));
+        assert_eq!(found[0].name, "GET /users");
+    }
+
+    #[test]
+    fn test_route_locations() {
+        let code = "const app = express();\napp.get('/users', listUsers);\n";
+        let found = routes(code);
+
+        let location = found[0].location.unwrap();
+        assert_eq!((location.start_line, location.end_line), (1, 1));
+        assert_eq!(
+            &code[location.start_offset as usize..location.end_offset as usize],
+            "app.get('/users', listUsers)"
+        );
+    }
+
+    #[test]
+    fn test_fastify_route_object() {
+        let found = routes(
+            "fastify.route({ method: ['GET', 'HEAD'], url: '/health', handler: healthCheck });",
+        );
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].signature, "route GET /health -> healthCheck");
+        assert_eq!(found[1].name, "HEAD /health");
+    }
+
+    #[test]
+    fn test_nest_controller_routes() {
+        let found = routes(
+            r#"
+            @Controller('users')
+            export class UsersController {
+                @Get(':id')
+                findOne(@Param('id') id: string): Promise<User> {
+                    return this.service.find(id);
+                }
+
+                @Post()
+                create(@Body() dto: CreateUserDto) {}
+
+                helper() {}
+            }
+            "#,
+        );
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].name, "GET /users/:id");
+        assert_eq!(
+            found[0].signature,
+            "route GET /users/:id -> UsersController.findOne(@Param('id') id: string): Promise<User>"
+        );
+        assert_eq!(found[1].name, "POST /users");
+    }
+}
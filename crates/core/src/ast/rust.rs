@@ -0,0 +1,675 @@
+//! Rust analyzer
+//!
+//! This module extracts public API signatures from `.rs` files with `syn`, so
+//! mixed Rust/TypeScript monorepos get drift detection on both sides:
+//!
+//! - Functions, structs, enums, traits, type aliases, constants, and statics
+//!   (`src/lib.rs#login`); items of inline modules are qualified with the
+//!   module path (`auth::login`)
+//! - Methods of inherent impls, qualified with their type (`Session::new`)
+//! - Trait impls, named like a qualified path (`<Session as Display>`)
+//!
+//! Signatures are rendered from tokens, so formatting never affects them.
+//! Doc comments and function bodies are left out; other attributes
+//! (`#[derive]`, `#[serde]`) are kept since they change the API. Private
+//! fields of public structs and trait default bodies are omitted. `#[deprecated]` marks
+//! the symbol deprecated and `#[cfg(test)]` modules are skipped.
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::diagnostics::{error_messages, Diagnostic};
+use crate::types::{SymbolType, TypeParam};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{
+    Attribute, Fields, GenericParam, Generics, ImplItem, Item, ItemImpl, ItemStruct, ItemTrait,
+    TraitItem, Type, Visibility,
+};
+
+/// Analyze a Rust source file and extract its item signatures
+///
+/// # Arguments
+/// * `file_path` - Path of the Rust file (used for symbol metadata)
+/// * `content` - Raw Rust source
+///
+/// # Returns
+/// AnalysisResult with `Function`, `Class` (structs), `Enum`, `Interface`
+/// (traits), `TypeAlias`, `Const`, `Variable` (statics), and `Impl` symbols
+pub fn analyze_rust(file_path: &str, content: &str) -> AnalysisResult {
+    let mut symbols = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    match syn::parse_file(content) {
+        Ok(file) => {
+            let mut extractor = RustExtractor {
+                file_path,
+                symbols: &mut symbols,
+            };
+            extractor.items(&file.items, "", true);
+        }
+        Err(error) => diagnostics.push(Diagnostic::from_syn(file_path, content, &error)),
+    }
+    // Span locations keep every parsed file's source; nothing outlives this call
+    proc_macro2::extra::invalidate_current_thread_spans();
+
+    AnalysisResult {
+        symbols,
+        errors: error_messages(&diagnostics),
+        diagnostics,
+        ignored: Vec::new(),
+        skipped: Vec::new(),
+    }
+}
+
+struct RustExtractor<'a> {
+    file_path: &'a str,
+    symbols: &'a mut Vec<SymbolInfo>,
+}
+
+impl RustExtractor<'_> {
+    /// Extract the items of a module
+    ///
+    /// `prefix` is the module path (`auth::`) and `public` whether the module
+    /// is reachable from outside the crate.
+    fn items(&mut self, items: &[Item], prefix: &str, public: bool) {
+        for item in items {
+            match item {
+                Item::Fn(item) => {
+                    let signature = render_with(&item.attrs, |tokens| {
+                        item.vis.to_tokens(tokens);
+                        item.sig.to_tokens(tokens);
+                    });
+                    self.push(
+                        format!("{}{}", prefix, item.sig.ident),
+                        SymbolType::Function,
+                        signature,
+                        public && is_pub(&item.vis),
+                        &item.attrs,
+                        &item.sig.generics,
+                    );
+                }
+                Item::Struct(item) => {
+                    let signature = render(&public_fields(item));
+                    self.push(
+                        format!("{}{}", prefix, item.ident),
+                        SymbolType::Class,
+                        signature,
+                        public && is_pub(&item.vis),
+                        &item.attrs,
+                        &item.generics,
+                    );
+                }
+                Item::Enum(item) => {
+                    let mut item = item.clone();
+                    for variant in &mut item.variants {
+                        strip_docs(&mut variant.attrs);
+                        for field in variant.fields.iter_mut() {
+                            strip_docs(&mut field.attrs);
+                        }
+                    }
+                    let generics = item.generics.clone();
+                    self.push_item(prefix, SymbolType::Enum, public, item, &generics);
+                }
+                Item::Trait(item) => {
+                    let signature = render(&trait_signature(item));
+                    self.push(
+                        format!("{}{}", prefix, item.ident),
+                        SymbolType::Interface,
+                        signature,
+                        public && is_pub(&item.vis),
+                        &item.attrs,
+                        &item.generics,
+                    );
+                }
+                Item::Type(item) => {
+                    self.push_item(
+                        prefix,
+                        SymbolType::TypeAlias,
+                        public,
+                        item.clone(),
+                        &item.generics,
+                    );
+                }
+                Item::Const(item) => {
+                    let generics = &item.generics;
+                    self.push_item(prefix, SymbolType::Const, public, item.clone(), generics);
+                }
+                Item::Static(item) => {
+                    let generics = &Generics::default();
+                    self.push_item(prefix, SymbolType::Variable, public, item.clone(), generics);
+                }
+                Item::Impl(item) => self.impl_block(item, prefix, public),
+                Item::Mod(module) => {
+                    let Some((_, items)) = &module.content else {
+                        continue;
+                    };
+                    if module.attrs.iter().any(is_cfg_test) {
+                        continue;
+                    }
+                    let prefix = format!("{}{}::", prefix, module.ident);
+                    self.items(items, &prefix, public && is_pub(&module.vis));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Methods of an inherent impl, or the whole impl of a trait
+    fn impl_block(&mut self, item: &ItemImpl, prefix: &str, public: bool) {
+        let header = render_with(&[], |tokens| {
+            item.unsafety.to_tokens(tokens);
+            item.impl_token.to_tokens(tokens);
+            item.generics.to_tokens(tokens);
+            if let Some((bang, path, for_token)) = &item.trait_ {
+                bang.to_tokens(tokens);
+                path.to_tokens(tokens);
+                for_token.to_tokens(tokens);
+            }
+            item.self_ty.to_tokens(tokens);
+            item.generics.where_clause.to_tokens(tokens);
+        });
+        let self_name = type_name(&item.self_ty);
+
+        let Some((_, path, _)) = &item.trait_ else {
+            for impl_item in &item.items {
+                let ImplItem::Fn(method) = impl_item else {
+                    continue;
+                };
+                let method_signature = render_with(&method.attrs, |tokens| {
+                    method.vis.to_tokens(tokens);
+                    method.sig.to_tokens(tokens);
+                });
+                self.push(
+                    format!("{}{}::{}", prefix, self_name, method.sig.ident),
+                    SymbolType::Function,
+                    format!("{} {{ {} }}", header, method_signature),
+                    public && is_pub(&method.vis),
+                    &method.attrs,
+                    &method.sig.generics,
+                );
+            }
+            return;
+        };
+
+        let members: Vec<String> = item
+            .items
+            .iter()
+            .filter_map(|impl_item| match impl_item {
+                ImplItem::Fn(method) => Some(render_with(&method.attrs, |tokens| {
+                    method.sig.to_tokens(tokens);
+                })),
+                ImplItem::Type(ty) => Some(render_with(&ty.attrs, |tokens| {
+                    ty.type_token.to_tokens(tokens);
+                    ty.ident.to_tokens(tokens);
+                    ty.generics.to_tokens(tokens);
+                    ty.eq_token.to_tokens(tokens);
+                    ty.ty.to_tokens(tokens);
+                })),
+                ImplItem::Const(constant) => Some(render_with(&constant.attrs, |tokens| {
+                    constant.const_token.to_tokens(tokens);
+                    constant.ident.to_tokens(tokens);
+                    constant.colon_token.to_tokens(tokens);
+                    constant.ty.to_tokens(tokens);
+                })),
+                _ => None,
+            })
+            .map(|member| format!("{};", member))
+            .collect();
+        let signature = if members.is_empty() {
+            format!("{} {{}}", header)
+        } else {
+            format!("{} {{ {} }}", header, members.join(" "))
+        };
+        self.push(
+            format!("{}<{} as {}>", prefix, self_name, render(path)),
+            SymbolType::Impl,
+            signature,
+            public,
+            &item.attrs,
+            &item.generics,
+        );
+    }
+
+    /// Push an item rendered whole, without its doc comments
+    fn push_item<T: NamedItem + ToTokens>(
+        &mut self,
+        prefix: &str,
+        symbol_type: SymbolType,
+        public: bool,
+        mut item: T,
+        generics: &Generics,
+    ) {
+        let attrs = item.attrs_mut().clone();
+        strip_docs(item.attrs_mut());
+        self.push(
+            format!("{}{}", prefix, item.ident()),
+            symbol_type,
+            render(&item),
+            public && is_pub(item.vis()),
+            &attrs,
+            generics,
+        );
+    }
+
+    fn push(
+        &mut self,
+        name: String,
+        symbol_type: SymbolType,
+        signature: String,
+        is_exported: bool,
+        attrs: &[Attribute],
+        generics: &Generics,
+    ) {
+        self.symbols.push(SymbolInfo {
+            name,
+            symbol_type,
+            signature,
+            is_exported,
+            file_path: self.file_path.to_string(),
+            deprecated: attrs.iter().any(|attr| attr.path().is_ident("deprecated")),
+            release_tag: None,
+            location: None,
+            type_params: type_params(generics),
+        });
+    }
+}
+
+/// Items rendered whole by `push_item`
+trait NamedItem {
+    fn ident(&self) -> &syn::Ident;
+    fn vis(&self) -> &Visibility;
+    fn attrs_mut(&mut self) -> &mut Vec<Attribute>;
+}
+
+macro_rules! named_item {
+    ($($item:ty),*) => {
+        $(impl NamedItem for $item {
+            fn ident(&self) -> &syn::Ident {
+                &self.ident
+            }
+            fn vis(&self) -> &Visibility {
+                &self.vis
+            }
+            fn attrs_mut(&mut self) -> &mut Vec<Attribute> {
+                &mut self.attrs
+            }
+        })*
+    };
+}
+
+named_item!(
+    syn::ItemEnum,
+    syn::ItemType,
+    syn::ItemConst,
+    syn::ItemStatic
+);
+
+/// Type parameters of an item, without lifetimes and const parameters
+///
+/// Constraints are the bounds in the parameter list (`Clone + Send`), not
+/// those of a `where` clause.
+fn type_params(generics: &Generics) -> Vec<TypeParam> {
+    generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(TypeParam {
+                name: param.ident.to_string(),
+                constraint: (!param.bounds.is_empty()).then(|| render(&param.bounds)),
+                default: param.default.as_ref().map(render),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Struct without doc comments, and without private fields if it is public
+fn public_fields(item: &ItemStruct) -> ItemStruct {
+    let mut item = item.clone();
+    strip_docs(&mut item.attrs);
+    let public = is_pub(&item.vis);
+    let fields = match &mut item.fields {
+        Fields::Named(fields) => &mut fields.named,
+        Fields::Unnamed(fields) => &mut fields.unnamed,
+        Fields::Unit => return item,
+    };
+    *fields = std::mem::take(fields)
+        .into_iter()
+        .filter(|field| !public || is_pub(&field.vis))
+        .map(|mut field| {
+            strip_docs(&mut field.attrs);
+            field
+        })
+        .collect();
+    item
+}
+
+/// Trait without doc comments and default bodies
+fn trait_signature(item: &ItemTrait) -> ItemTrait {
+    let mut item = item.clone();
+    strip_docs(&mut item.attrs);
+    for trait_item in &mut item.items {
+        match trait_item {
+            TraitItem::Fn(method) => {
+                strip_docs(&mut method.attrs);
+                if method.default.take().is_some() {
+                    method.semi_token = Some(Default::default());
+                }
+            }
+            TraitItem::Type(ty) => strip_docs(&mut ty.attrs),
+            TraitItem::Const(constant) => strip_docs(&mut constant.attrs),
+            _ => {}
+        }
+    }
+    item
+}
+
+/// Name of the implementing type, without generics (`Session` for `Session<T>`)
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default(),
+        _ => render(ty),
+    }
+}
+
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+fn is_cfg_test(attr: &Attribute) -> bool {
+    attr.path().is_ident("cfg") && render(&attr.meta).replace(' ', "") == "cfg(test)"
+}
+
+fn strip_docs(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| !attr.path().is_ident("doc"));
+}
+
+/// Render non-doc attributes followed by the tokens written by `write`
+fn render_with(attrs: &[Attribute], write: impl FnOnce(&mut TokenStream)) -> String {
+    let mut tokens = TokenStream::new();
+    for attr in attrs.iter().filter(|attr| !attr.path().is_ident("doc")) {
+        attr.to_tokens(&mut tokens);
+    }
+    write(&mut tokens);
+    render_tokens(tokens)
+}
+
+fn render(node: &impl ToTokens) -> String {
+    render_tokens(node.to_token_stream())
+}
+
+/// Join tokens with conventional Rust spacing (`pub fn f<'a>(x: &'a str) -> u8`)
+fn render_tokens(tokens: TokenStream) -> String {
+    let mut out = String::new();
+    write_tokens(&mut out, tokens);
+    out
+}
+
+fn write_tokens(out: &mut String, tokens: TokenStream) {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                let glued = matches!(
+                    group.delimiter(),
+                    Delimiter::Parenthesis | Delimiter::Bracket
+                ) && ends_word(out)
+                    || out.ends_with(['#', '!', '&', '(', '[', '<']);
+                space(out, !glued);
+                out.push_str(open);
+                let start = out.len();
+                write_tokens(out, group.stream());
+                // Trailing commas are formatting
+                if out.len() > start && out.ends_with(',') {
+                    out.pop();
+                }
+                if group.delimiter() == Delimiter::Brace && out.len() > start {
+                    space(out, true);
+                    if !out[start..].starts_with(' ') {
+                        out.insert(start, ' ');
+                    }
+                }
+                out.push_str(close);
+            }
+            TokenTree::Ident(ident) => {
+                let glued = out.ends_with(['(', '[', '<', '&', '#', '!', '\'', '*'])
+                    || out.ends_with("::")
+                    || out.ends_with(" -")
+                        && !out
+                            .trim_end_matches(" -")
+                            .ends_with(|c: char| c.is_alphanumeric());
+                space(out, !glued);
+                out.push_str(&ident.to_string());
+            }
+            TokenTree::Literal(literal) => {
+                let glued = out.ends_with(['(', '[', '<', '-']) && !out.ends_with("->");
+                space(out, !glued);
+                out.push_str(&literal.to_string());
+            }
+            TokenTree::Punct(punct) => {
+                // Operators spanning several tokens (`::`, `->`, `'a`)
+                let mut op = punct.as_char().to_string();
+                let mut spacing = punct.spacing();
+                while spacing == Spacing::Joint && punct.as_char() != '\'' {
+                    match tokens.peek() {
+                        Some(TokenTree::Punct(next)) => {
+                            op.push(next.as_char());
+                            spacing = next.spacing();
+                            tokens.next();
+                        }
+                        _ => break,
+                    }
+                }
+                let glued = match op.as_str() {
+                    "," | ";" | ":" | "::" | ">" | "?" | "." => true,
+                    "<" => ends_word(out) || out.ends_with("::"),
+                    "'" | "&" | "*" | "!" => {
+                        out.ends_with(['(', '[', '<', '&', '*']) || (op == "!" && ends_word(out))
+                    }
+                    _ => false,
+                } || out.ends_with("::")
+                    || out.ends_with('#');
+                space(out, !glued);
+                out.push_str(&op);
+            }
+        }
+    }
+}
+
+/// Whether the output ends with an identifier, literal, or closing delimiter
+fn ends_word(out: &str) -> bool {
+    out.ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == '"' || c == '>')
+}
+
+fn space(out: &mut String, wanted: bool) {
+    if wanted && !out.is_empty() && !out.ends_with(' ') {
+        out.push(' ');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+//! Sessions
+use std::fmt;
+
+/// Signs a user in
+#[must_use]
+pub fn login<'a>(user: &'a str, password: &str) -> Result<Session, Error> {
+    todo!()
+}
+
+fn helper() {}
+
+/// A session
+#[derive(Debug, Clone)]
+pub struct Session<T: Clone = ()> {
+    /// Token
+    pub token: String,
+    secret: Vec<u8>,
+    pub data: Option<T>,
+}
+
+pub enum Role {
+    /// Full access
+    Admin,
+    User { id: u64 },
+}
+
+pub trait Store: Send + Sync {
+    type Key;
+    /// Load a session
+    fn load(&self, key: &Self::Key) -> Option<Session>;
+    fn clear(&mut self) {
+        todo!()
+    }
+}
+
+pub type SessionMap = std::collections::HashMap<String, Session>;
+pub const TIMEOUT: u64 = 60 * 60;
+
+impl<T: Clone> Session<T> {
+    /// Create a session
+    pub fn new(token: impl Into<String>) -> Self {
+        todo!()
+    }
+    #[deprecated(note = "use new")]
+    pub fn create(token: &str) -> Self {
+        todo!()
+    }
+    fn rotate(&mut self) {}
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.token)
+    }
+}
+
+pub mod auth {
+    pub fn logout(session: &mut super::Session) -> bool { true }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_login() {}
+}
+"#;
+
+    fn find<'a>(result: &'a AnalysisResult, name: &str) -> &'a SymbolInfo {
+        result.symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_extracts_items() {
+        let result = analyze_rust("src/lib.rs", SOURCE);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "login",
+                "helper",
+                "Session",
+                "Role",
+                "Store",
+                "SessionMap",
+                "TIMEOUT",
+                "Session::new",
+                "Session::create",
+                "Session::rotate",
+                "<Session as fmt::Display>",
+                "auth::logout",
+            ]
+        );
+
+        let login = find(&result, "login");
+        assert_eq!(login.symbol_type, SymbolType::Function);
+        assert!(login.is_exported);
+        assert_eq!(
+            login.signature,
+            "#[must_use] pub fn login<'a>(user: &'a str, password: &str) -> Result<Session, Error>"
+        );
+        assert!(!find(&result, "helper").is_exported);
+
+        assert_eq!(
+            find(&result, "Session").signature,
+            "#[derive(Debug, Clone)] pub struct Session<T: Clone = ()> { pub token: String, pub data: Option<T> }"
+        );
+        let params = &find(&result, "Session").type_params;
+        assert_eq!(params.len(), 1);
+        assert_eq!(params[0].name, "T");
+        assert_eq!(params[0].constraint.as_deref(), Some("Clone"));
+        assert_eq!(params[0].default.as_deref(), Some("()"));
+        // Lifetimes are not type parameters
+        assert!(login.type_params.is_empty());
+        assert_eq!(
+            find(&result, "Role").signature,
+            "pub enum Role { Admin, User { id: u64 } }"
+        );
+        let store = find(&result, "Store");
+        assert_eq!(store.symbol_type, SymbolType::Interface);
+        assert_eq!(
+            store.signature,
+            "pub trait Store: Send + Sync { type Key; fn load(&self, key: &Self::Key) -> Option<Session>; fn clear(&mut self); }"
+        );
+        assert_eq!(
+            find(&result, "TIMEOUT").signature,
+            "pub const TIMEOUT: u64 = 60 * 60;"
+        );
+    }
+
+    #[test]
+    fn test_extracts_impls() {
+        let result = analyze_rust("src/lib.rs", SOURCE);
+
+        let new = find(&result, "Session::new");
+        assert_eq!(
+            new.signature,
+            "impl<T: Clone> Session<T> { pub fn new(token: impl Into<String>) -> Self }"
+        );
+        assert!(new.is_exported);
+        assert!(find(&result, "Session::create").deprecated);
+        assert!(!find(&result, "Session::rotate").is_exported);
+
+        let display = find(&result, "<Session as fmt::Display>");
+        assert_eq!(display.symbol_type, SymbolType::Impl);
+        assert_eq!(
+            display.signature,
+            "impl fmt::Display for Session { fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result; }"
+        );
+        assert_eq!(
+            find(&result, "auth::logout").signature,
+            "pub fn logout(session: &mut super::Session) -> bool"
+        );
+    }
+
+    #[test]
+    fn test_formatting_and_bodies_do_not_change_signatures() {
+        let before = analyze_rust("a.rs", "pub fn f(a: u8, b: &str) -> u8 { a }");
+        let after = analyze_rust(
+            "a.rs",
+            "/// Docs\npub fn f(\n    a: u8,\n    b: &str,\n) -> u8 {\n    a + 1\n}\n",
+        );
+        assert_eq!(before.symbols[0].signature, after.symbols[0].signature);
+
+        let broken = analyze_rust("a.rs", "pub fn f(");
+        assert_eq!(broken.symbols.len(), 0);
+        assert_eq!(broken.errors.len(), 1);
+        assert_eq!(broken.diagnostics[0].start.line, 0);
+    }
+}
@@ -0,0 +1,700 @@
+//! Vue and Svelte single-file components
+//!
+//! A `.vue` or `.svelte` file is markup with embedded `<script>` blocks. The
+//! analyzer parses only the scripts: `ScriptBlocks` blanks everything outside
+//! them with spaces, keeping line breaks, so the Oxc parser sees plain
+//! TypeScript/JavaScript while spans, lines, and locations still refer to the
+//! component file.
+//!
+//! Besides the symbols of its scripts, each component yields one `Component`
+//! symbol named after the file (`user-card.vue` is `UserCard`), whose
+//! signature lists its props and the events it emits:
+//!
+//! ```text
+//! component UserCard { title: string; count?: number; @change(id: number); @close() }
+//! ```
+//!
+//! Recognized declarations:
+//! - Vue `defineProps<T>()`, optionally in `withDefaults`, and runtime
+//!   `defineProps({ title: { type: String, required: true } })` or
+//!   `defineProps(['title'])`
+//! - Vue `defineEmits<{ (e: 'change', id: number): void }>()`,
+//!   `defineEmits<{ change: [id: number] }>()`, and `defineEmits(['change'])`
+//! - Vue options API `props` and `emits` of `export default { ... }` or
+//!   `export default defineComponent({ ... })`
+//! - Svelte `export let title: string` (optional with an initializer),
+//!   Svelte 5 `let { title }: Props = $props()`, and
+//!   `createEventDispatcher<{ change: number }>()`
+
+use super::analyzer::SymbolInfo;
+use super::printer::{braced, Printer};
+use crate::types::{line_starts, SymbolLocation, SymbolType};
+use oxc_ast::ast::*;
+use oxc_span::SourceType;
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Whether a file is a Vue or Svelte component
+pub fn is_component(file_path: &str) -> bool {
+    file_path.ends_with(".vue") || file_path.ends_with(".svelte")
+}
+
+fn script_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"(?is)<script\b([^>]*)>(.*?)</script\s*>").unwrap())
+}
+
+fn lang_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r#"\blang\s*=\s*["']?(\w+)"#).unwrap())
+}
+
+/// The `<script>` blocks of a component, at their offsets in the file
+#[derive(Debug, Clone)]
+pub struct ScriptBlocks {
+    /// The component file with everything outside its scripts blanked
+    pub text: String,
+    /// TypeScript if any script has `lang="ts"`, else a JavaScript module
+    pub source_type: SourceType,
+}
+
+impl ScriptBlocks {
+    /// Scripts of a `.vue` or `.svelte` file; `None` for other files
+    pub fn extract(file_path: &str, content: &str) -> Option<Self> {
+        if !is_component(file_path) {
+            return None;
+        }
+        let mut text = String::with_capacity(content.len());
+        let mut source_type = SourceType::mjs();
+        let mut end = 0;
+        for captures in script_regex().captures_iter(content) {
+            let body = captures.get(2).unwrap();
+            let lang = lang_regex()
+                .captures(&captures[1])
+                .map(|lang| lang[1].to_ascii_lowercase());
+            match lang.as_deref() {
+                Some("ts" | "typescript") => source_type = SourceType::ts(),
+                Some("tsx") => source_type = SourceType::tsx(),
+                Some("jsx") if !source_type.is_typescript() => source_type = SourceType::jsx(),
+                _ => {}
+            }
+            blank(&mut text, &content[end..body.start()]);
+            text.push_str(body.as_str());
+            end = body.end();
+        }
+        blank(&mut text, &content[end..]);
+        Some(Self { text, source_type })
+    }
+}
+
+/// Append `markup` with every character but line breaks replaced by as many
+/// spaces as it takes bytes
+fn blank(text: &mut String, markup: &str) {
+    for c in markup.chars() {
+        match c {
+            '\n' | '\r' => text.push(c),
+            c => text.extend(std::iter::repeat_n(' ', c.len_utf8())),
+        }
+    }
+}
+
+/// Component name for a file: its stem in PascalCase
+pub fn component_name(file_path: &str) -> String {
+    let stem = Path::new(file_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("");
+    stem.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .unwrap()
+                .to_uppercase()
+                .chain(chars)
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// The `Component` symbol of a parsed component, spanning the whole file
+///
+/// `source_text` is the text the program was parsed from (`ScriptBlocks`).
+pub fn component_symbol(program: &Program<'_>, file_path: &str, source_text: &str) -> SymbolInfo {
+    let mut component = ComponentExtractor {
+        printer: Printer::new(source_text),
+        program,
+        members: Vec::new(),
+        emits: Vec::new(),
+    };
+    for statement in &program.body {
+        component.statement(statement);
+    }
+    component.members.append(&mut component.emits);
+    let name = component_name(file_path);
+    SymbolInfo {
+        signature: format!("component {} {}", name, braced(&component.members, "; ")),
+        name,
+        symbol_type: SymbolType::Component,
+        is_exported: true,
+        file_path: file_path.to_string(),
+        deprecated: false,
+        release_tag: None,
+        location: Some(SymbolLocation::from_offsets(
+            &line_starts(source_text),
+            0,
+            source_text.len(),
+        )),
+        type_params: Vec::new(),
+    }
+}
+
+/// Members of a type literal, or of the interface or type alias it names in
+/// the program
+pub(crate) fn type_members<'p, 'a>(
+    program: &'p Program<'a>,
+    ts_type: &'p TSType<'a>,
+) -> Option<&'p [TSSignature<'a>]> {
+    match ts_type {
+        TSType::TSTypeLiteral(literal) => Some(&literal.members),
+        TSType::TSTypeReference(reference) => {
+            let TSTypeName::IdentifierReference(ident) = &reference.type_name else {
+                return None;
+            };
+            program.body.iter().find_map(|statement| {
+                let declaration = match statement {
+                    Statement::ExportNamedDeclaration(export) => export.declaration.as_ref()?,
+                    statement => statement.as_declaration()?,
+                };
+                match declaration {
+                    Declaration::TSInterfaceDeclaration(decl) if decl.id.name == ident.name => {
+                        Some(decl.body.body.as_slice())
+                    }
+                    Declaration::TSTypeAliasDeclaration(decl) if decl.id.name == ident.name => {
+                        type_members(program, &decl.type_annotation)
+                    }
+                    _ => None,
+                }
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Props declared by a type, as printed members (`title: string`)
+///
+/// Types not declared in the program are spread as a whole (`...Props`).
+pub(crate) fn props_of_type(printer: &Printer, program: &Program, ts_type: &TSType) -> Vec<String> {
+    match type_members(program, ts_type) {
+        Some(members) => members
+            .iter()
+            .map(|member| printer.signature(member))
+            .collect(),
+        None => vec![format!("...{}", printer.ts_type(ts_type))],
+    }
+}
+
+/// Collects the props (`members`) and events (`emits`) of a component
+struct ComponentExtractor<'p, 'a> {
+    printer: Printer<'p>,
+    program: &'p Program<'a>,
+    members: Vec<String>,
+    emits: Vec<String>,
+}
+
+impl<'p, 'a> ComponentExtractor<'p, 'a> {
+    fn statement(&mut self, statement: &'p Statement<'a>) {
+        match statement {
+            Statement::VariableDeclaration(decl) => {
+                for declarator in &decl.declarations {
+                    if let Some(Expression::CallExpression(call)) = &declarator.init {
+                        self.macro_call(call, Some(&declarator.id));
+                    }
+                }
+            }
+            Statement::ExpressionStatement(statement) => {
+                if let Expression::CallExpression(call) = &statement.expression {
+                    self.macro_call(call, None);
+                }
+            }
+            // Svelte props: `export let title: string`
+            Statement::ExportNamedDeclaration(export) => {
+                let Some(Declaration::VariableDeclaration(decl)) = &export.declaration else {
+                    return;
+                };
+                if decl.kind.is_const() {
+                    return;
+                }
+                for declarator in &decl.declarations {
+                    let mut id = self.printer.binding_pattern(&declarator.id);
+                    if declarator.init.is_some() {
+                        let end = id.find(':').unwrap_or(id.len());
+                        id.insert(end, '?');
+                    }
+                    self.members.push(id);
+                }
+            }
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::ObjectExpression(options) => self.options(options),
+                ExportDefaultDeclarationKind::CallExpression(call)
+                    if call.callee_name() == Some("defineComponent") =>
+                {
+                    if let Some(Expression::ObjectExpression(options)) =
+                        call.arguments.first().and_then(Argument::as_expression)
+                    {
+                        self.options(options);
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// `defineProps`, `defineEmits`, `$props`, or `createEventDispatcher`,
+    /// with the binding it is assigned to
+    fn macro_call(
+        &mut self,
+        call: &'p CallExpression<'a>,
+        binding: Option<&'p BindingPattern<'a>>,
+    ) {
+        let type_argument = call.type_parameters.as_ref().and_then(|t| t.params.first());
+        let argument = call.arguments.first().and_then(Argument::as_expression);
+        match call.callee_name() {
+            Some("withDefaults") => {
+                if let Some(Expression::CallExpression(inner)) = argument {
+                    self.macro_call(inner, binding);
+                }
+            }
+            Some("defineProps") => match (type_argument, argument) {
+                (Some(ts_type), _) => self.props_from_type(ts_type),
+                (None, Some(argument)) => self.runtime_props(argument),
+                (None, None) => {}
+            },
+            Some("defineEmits") => match (type_argument, argument) {
+                (Some(ts_type), _) => self.emits_from_type(ts_type),
+                (None, Some(argument)) => self.runtime_emits(argument),
+                (None, None) => {}
+            },
+            Some("$props") => match binding {
+                Some(BindingPattern {
+                    type_annotation: Some(annotation),
+                    ..
+                }) => self.props_from_type(&annotation.type_annotation),
+                Some(BindingPattern {
+                    kind: BindingPatternKind::ObjectPattern(object),
+                    ..
+                }) => {
+                    for property in &object.properties {
+                        let name = self.printer.property_key(&property.key, property.computed);
+                        match property.value.kind {
+                            BindingPatternKind::AssignmentPattern(_) => {
+                                self.members.push(format!("{}?", name))
+                            }
+                            _ => self.members.push(name),
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Some("createEventDispatcher") => {
+                if let Some(TSType::TSTypeLiteral(events)) = type_argument {
+                    for member in &events.members {
+                        let TSSignature::TSPropertySignature(event) = member else {
+                            continue;
+                        };
+                        let name = self.printer.property_key(&event.key, event.computed);
+                        let detail = event
+                            .type_annotation
+                            .as_ref()
+                            .map(|a| self.printer.ts_type(&a.type_annotation));
+                        match detail.as_deref() {
+                            None | Some("null" | "undefined" | "void" | "never") => {
+                                self.emits.push(format!("@{}()", name))
+                            }
+                            Some(detail) => {
+                                self.emits.push(format!("@{}(detail: {})", name, detail))
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn props_from_type(&mut self, ts_type: &'p TSType<'a>) {
+        let props = props_of_type(&self.printer, self.program, ts_type);
+        self.members.extend(props);
+    }
+
+    fn emits_from_type(&mut self, ts_type: &'p TSType<'a>) {
+        let Some(members) = type_members(self.program, ts_type) else {
+            return;
+        };
+        for member in members {
+            let event = match member {
+                // `(e: 'change', id: number): void`
+                TSSignature::TSCallSignatureDeclaration(call) => {
+                    let Some(name) = call.params.items.first().and_then(event_name) else {
+                        continue;
+                    };
+                    let mut params: Vec<String> = call
+                        .params
+                        .items
+                        .iter()
+                        .skip(1)
+                        .map(|param| self.printer.binding_pattern(&param.pattern))
+                        .collect();
+                    if let Some(rest) = &call.params.rest {
+                        params.push(format!(
+                            "...{}",
+                            self.printer.binding_pattern(&rest.argument)
+                        ));
+                    }
+                    format!("@{}({})", name, params.join(", "))
+                }
+                // `change: [id: number]`
+                TSSignature::TSPropertySignature(property) => {
+                    let name = self.printer.property_key(&property.key, property.computed);
+                    match property
+                        .type_annotation
+                        .as_ref()
+                        .map(|a| &a.type_annotation)
+                    {
+                        Some(TSType::TSTupleType(tuple)) => {
+                            let params: Vec<String> = tuple
+                                .element_types
+                                .iter()
+                                .map(|element| self.printer.tuple_element(element))
+                                .collect();
+                            format!("@{}({})", name, params.join(", "))
+                        }
+                        Some(ts_type) => {
+                            format!("@{}(...args: {})", name, self.printer.ts_type(ts_type))
+                        }
+                        None => format!("@{}", name),
+                    }
+                }
+                _ => continue,
+            };
+            self.emits.push(event);
+        }
+    }
+
+    /// Options API `props` and `emits`
+    fn options(&mut self, options: &'p ObjectExpression<'a>) {
+        if let Some(props) = option(options, "props") {
+            self.runtime_props(props);
+        }
+        if let Some(emits) = option(options, "emits") {
+            self.runtime_emits(emits);
+        }
+    }
+
+    /// `['title']` or `{ title: String, count: { type: Number, required: true } }`
+    ///
+    /// Props are optional unless `required: true`; constructors stand for
+    /// their TypeScript types.
+    fn runtime_props(&mut self, props: &'p Expression<'a>) {
+        match props {
+            Expression::ArrayExpression(array) => {
+                for name in string_elements(array) {
+                    self.members.push(format!("{}?", name));
+                }
+            }
+            Expression::ObjectExpression(object) => {
+                for property in &object.properties {
+                    let ObjectPropertyKind::ObjectProperty(property) = property else {
+                        continue;
+                    };
+                    let mut prop = self.printer.property_key(&property.key, property.computed);
+                    let (prop_type, required) = match &property.value {
+                        Expression::ObjectExpression(definition) => (
+                            option(definition, "type"),
+                            matches!(
+                                option(definition, "required"),
+                                Some(Expression::BooleanLiteral(required)) if required.value
+                            ),
+                        ),
+                        value => (Some(value), false),
+                    };
+                    if !required {
+                        prop.push('?');
+                    }
+                    if let Some(prop_type) = prop_type {
+                        prop.push_str(": ");
+                        prop.push_str(&self.prop_type(prop_type));
+                    }
+                    self.members.push(prop);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// TypeScript type of a runtime prop `type`: `String` is `string`,
+    /// `[String, Number]` is `string | number`, and
+    /// `Object as PropType<User>` is `User`
+    fn prop_type(&self, prop_type: &Expression<'_>) -> String {
+        match prop_type {
+            Expression::Identifier(ident) => match ident.name.as_str() {
+                "String" => "string".to_string(),
+                "Number" => "number".to_string(),
+                "Boolean" => "boolean".to_string(),
+                "BigInt" => "bigint".to_string(),
+                "Symbol" => "symbol".to_string(),
+                "Array" => "unknown[]".to_string(),
+                "Object" => "Record<string, unknown>".to_string(),
+                name => name.to_string(),
+            },
+            Expression::ArrayExpression(array) => {
+                let types: Vec<String> = array
+                    .elements
+                    .iter()
+                    .filter_map(ArrayExpressionElement::as_expression)
+                    .map(|element| self.prop_type(element))
+                    .collect();
+                types.join(" | ")
+            }
+            Expression::TSAsExpression(cast) => match &cast.type_annotation {
+                TSType::TSTypeReference(reference)
+                    if matches!(
+                        &reference.type_name,
+                        TSTypeName::IdentifierReference(ident) if ident.name == "PropType"
+                    ) =>
+                {
+                    match reference
+                        .type_parameters
+                        .as_ref()
+                        .and_then(|t| t.params.first())
+                    {
+                        Some(ts_type) => self.printer.ts_type(ts_type),
+                        None => self.printer.ts_type(&cast.type_annotation),
+                    }
+                }
+                ts_type => self.printer.ts_type(ts_type),
+            },
+            expression => self.printer.expression(expression),
+        }
+    }
+
+    /// `['change']` or `{ change: (id: number) => true }`
+    fn runtime_emits(&mut self, emits: &'p Expression<'a>) {
+        match emits {
+            Expression::ArrayExpression(array) => {
+                for name in string_elements(array) {
+                    self.emits.push(format!("@{}", name));
+                }
+            }
+            Expression::ObjectExpression(object) => {
+                for property in &object.properties {
+                    let ObjectPropertyKind::ObjectProperty(property) = property else {
+                        continue;
+                    };
+                    let name = self.printer.property_key(&property.key, property.computed);
+                    let params = match &property.value {
+                        Expression::ArrowFunctionExpression(validator) => &validator.params,
+                        Expression::FunctionExpression(validator) => &validator.params,
+                        _ => {
+                            self.emits.push(format!("@{}", name));
+                            continue;
+                        }
+                    };
+                    let params: Vec<String> = params
+                        .items
+                        .iter()
+                        .map(|param| self.printer.binding_pattern(&param.pattern))
+                        .collect();
+                    self.emits.push(format!("@{}({})", name, params.join(", ")));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Value of a property of an object literal
+fn option<'p, 'a>(object: &'p ObjectExpression<'a>, key: &str) -> Option<&'p Expression<'a>> {
+    object
+        .properties
+        .iter()
+        .find_map(|property| match property {
+            ObjectPropertyKind::ObjectProperty(property)
+                if property.key.static_name().as_deref() == Some(key) =>
+            {
+                Some(&property.value)
+            }
+            _ => None,
+        })
+}
+
+/// Event name typed on the first parameter of an emit signature: `e: 'change'`
+fn event_name(param: &FormalParameter<'_>) -> Option<String> {
+    let annotation = param.pattern.type_annotation.as_ref()?;
+    match &annotation.type_annotation {
+        TSType::TSLiteralType(literal) => match &literal.literal {
+            TSLiteral::StringLiteral(name) => Some(name.value.to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// String literal elements of an array literal
+fn string_elements(array: &ArrayExpression<'_>) -> Vec<String> {
+    array
+        .elements
+        .iter()
+        .filter_map(|element| match element.as_expression() {
+            Some(Expression::StringLiteral(string)) => Some(string.value.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::AstAnalyzerInternal;
+
+    fn analyze(file_path: &str, code: &str) -> Vec<SymbolInfo> {
+        let result = AstAnalyzerInternal::new().analyze_file(file_path, code);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        result.symbols
+    }
+
+    fn component(symbols: &[SymbolInfo]) -> &SymbolInfo {
+        symbols
+            .iter()
+            .find(|symbol| symbol.symbol_type == SymbolType::Component)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_component_name() {
+        assert_eq!(component_name("src/components/user-card.vue"), "UserCard");
+        assert_eq!(component_name("src/UserCard.vue"), "UserCard");
+        assert_eq!(component_name("src/routes/+page.svelte"), "Page");
+    }
+
+    #[test]
+    fn test_script_blocks_keep_offsets() {
+        let code =
+            "<template>\n  <p>é</p>\n</template>\n<script lang=\"ts\">\nconst a = 1;\n</script>\n";
+        let scripts = ScriptBlocks::extract("a.vue", code).unwrap();
+        assert_eq!(scripts.text.len(), code.len());
+        assert_eq!(scripts.text.lines().count(), code.lines().count());
+        assert_eq!(scripts.text.find("const a"), code.find("const a"));
+        assert!(!scripts.text.contains("template"));
+        assert!(scripts.source_type.is_typescript());
+        assert!(ScriptBlocks::extract("a.ts", code).is_none());
+    }
+
+    #[test]
+    fn test_vue_script_setup() {
+        let code = r#"<template>
+  <button @click="emit('change', 1)">{{ title }}</button>
+</template>
+
+<script setup lang="ts">
+import { ref } from 'vue';
+
+interface Props {
+  title: string;
+  count?: number;
+}
+
+const props = withDefaults(defineProps<Props>(), { count: 0 });
+const emit = defineEmits<{
+  (e: 'change', id: number): void;
+  (e: 'close'): void;
+}>();
+
+export function format(value: number): string {
+  return value.toFixed(2);
+}
+</script>
+"#;
+        let symbols = analyze("src/components/user-card.vue", code);
+        let card = component(&symbols);
+        assert_eq!(card.name, "UserCard");
+        assert_eq!(
+            card.signature,
+            "component UserCard { title: string; count?: number; @change(id: number); @close() }"
+        );
+        assert!(card.is_exported);
+        assert_eq!(card.location.unwrap().start_line, 0);
+
+        // Script symbols keep their lines in the component file
+        let format = symbols.iter().find(|s| s.name == "format").unwrap();
+        assert_eq!(format.signature, "function format(value: number): string");
+        assert_eq!(format.location.unwrap().start_line, 18);
+    }
+
+    #[test]
+    fn test_vue_options_api() {
+        let code = r#"<script>
+import { defineComponent } from 'vue';
+
+export default defineComponent({
+  props: {
+    title: { type: String, required: true },
+    tags: [String, Array],
+    user: Object,
+  },
+  emits: ['close', 'save'],
+});
+</script>
+<template><div /></template>
+"#;
+        let symbols = analyze("src/Modal.vue", code);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(
+            symbols[0].signature,
+            "component Modal { title: string; tags?: string | unknown[]; user?: Record<string, unknown>; @close; @save }"
+        );
+
+        let code = "<script setup>\ndefineProps(['title']);\ndefineEmits({ save: (id) => true });\n</script>\n";
+        let symbols = analyze("src/Modal.vue", code);
+        assert_eq!(
+            symbols[0].signature,
+            "component Modal { title?; @save(id) }"
+        );
+    }
+
+    #[test]
+    fn test_svelte_component() {
+        let code = r#"<script lang="ts">
+  import { createEventDispatcher } from 'svelte';
+
+  export let title: string;
+  export let count = 0;
+  export const version = '1';
+
+  const dispatch = createEventDispatcher<{ change: number; close: null }>();
+</script>
+
+<h1 on:click={() => dispatch('close')}>{title}</h1>
+"#;
+        let symbols = analyze("src/Counter.svelte", code);
+        assert_eq!(
+            component(&symbols).signature,
+            "component Counter { title: string; count?; @change(detail: number); @close() }"
+        );
+
+        let code = r#"<script lang="ts">
+  type Props = { label: string; disabled?: boolean };
+  let { label, disabled = false }: Props = $props();
+</script>
+<button {disabled}>{label}</button>
+"#;
+        let symbols = analyze("src/Button.svelte", code);
+        assert_eq!(
+            component(&symbols).signature,
+            "component Button { label: string; disabled?: boolean }"
+        );
+    }
+}
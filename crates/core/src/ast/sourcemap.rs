@@ -0,0 +1,362 @@
+//! Source map attribution for generated code
+//!
+//! Build output (`dist/auth.js`) ending with a `sourceMappingURL` comment can
+//! be traced back to the files it was compiled from. With the analyzer's
+//! `source_maps` option, symbols found in such a file are attributed to their
+//! original file and lines, so anchors can reference `src/auth.ts#login`
+//! rather than the build output.
+//!
+//! Inline maps (`data:application/json;base64,...`) and map files are both
+//! read. Symbols whose position maps nowhere, or to a source outside the
+//! project (a `webpack://` URL), keep their generated location.
+
+use super::analyzer::SymbolInfo;
+use crate::graph::normalize_path;
+use crate::provider::ContentProvider;
+use crate::types::{line_starts, SymbolLocation};
+use base64::Engine;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Alphabet of the base64 VLQ digits in `mappings`
+const VLQ_DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Source map v3 as written by compilers and bundlers
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSourceMap {
+    version: u32,
+    #[serde(default)]
+    source_root: Option<String>,
+    sources: Vec<Option<String>>,
+    #[serde(default)]
+    sources_content: Vec<Option<String>>,
+    mappings: String,
+}
+
+/// A position in an original source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OriginalPosition {
+    /// Index into `SourceMap::sources`
+    pub source: usize,
+    /// Line (0-based)
+    pub line: u32,
+    /// Column in UTF-16 code units (0-based)
+    pub column: u32,
+}
+
+/// Decoded source map of a generated file
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// Original files, resolved against the map's directory (`None` for
+    /// sources given as URLs)
+    pub sources: Vec<Option<PathBuf>>,
+    /// Content of the original files embedded in the map
+    sources_content: Vec<Option<String>>,
+    /// Mapped segments of each generated line, by generated column
+    lines: Vec<Vec<(u32, OriginalPosition)>>,
+}
+
+impl SourceMap {
+    /// Parse a version 3 source map whose relative sources are resolved
+    /// against `map_dir`; `None` if it is malformed
+    pub fn parse(json: &str, map_dir: &Path) -> Option<Self> {
+        let raw: RawSourceMap = serde_json::from_str(json).ok()?;
+        if raw.version != 3 {
+            return None;
+        }
+        let root = map_dir.join(raw.source_root.unwrap_or_default());
+        let sources = raw
+            .sources
+            .iter()
+            .map(|source| {
+                let source = source.as_deref()?;
+                let source = match source.split_once("://") {
+                    Some(("file", path)) => path,
+                    Some(_) => return None,
+                    None => source,
+                };
+                Some(normalize_path(&root.join(source)))
+            })
+            .collect();
+        Some(Self {
+            sources,
+            sources_content: raw.sources_content,
+            lines: decode_mappings(&raw.mappings)?,
+        })
+    }
+
+    /// Source map of a generated file, from the `sourceMappingURL` comment
+    /// at its end
+    ///
+    /// `path` is the file's path for `provider`, which reads map files.
+    pub fn for_file(provider: &dyn ContentProvider, path: &Path, content: &str) -> Option<Self> {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let url = source_mapping_url(content)?;
+        if let Some(data) = url.strip_prefix("data:") {
+            let (header, payload) = data.split_once(',')?;
+            if !header.ends_with(";base64") {
+                return None;
+            }
+            let json = base64::engine::general_purpose::STANDARD
+                .decode(payload)
+                .ok()?;
+            return Self::parse(&String::from_utf8(json).ok()?, dir);
+        }
+        let map_path = normalize_path(&dir.join(url));
+        let json = provider.read(&map_path).ok()?;
+        Self::parse(&json, map_path.parent().unwrap_or(Path::new("")))
+    }
+
+    /// Original position of a generated position (0-based line, UTF-16
+    /// column)
+    ///
+    /// The closest segment starting at or before the column wins; a position
+    /// before the first segment of its line maps like that segment.
+    pub fn lookup(&self, line: u32, column: u32) -> Option<OriginalPosition> {
+        let segments = self.lines.get(line as usize)?;
+        let index = segments.partition_point(|(start, _)| *start <= column);
+        segments
+            .get(index.saturating_sub(1))
+            .map(|(_, position)| *position)
+    }
+
+    /// Attribute symbols found in `content`, the generated file, to the
+    /// original sources they map to
+    ///
+    /// File paths use `/` separators. Locations are translated line by line;
+    /// byte offsets need the original content (embedded in the map, or read
+    /// through `provider`), and the location is dropped without it.
+    pub fn attribute(
+        &self,
+        symbols: &mut [SymbolInfo],
+        content: &str,
+        provider: &dyn ContentProvider,
+    ) {
+        let starts = line_starts(content);
+        let position = |offset: usize| {
+            let line = starts.partition_point(|&start| start <= offset) - 1;
+            let column = content[starts[line]..offset].encode_utf16().count();
+            self.lookup(line as u32, column as u32)
+        };
+
+        for symbol in symbols {
+            let Some(location) = symbol.location else {
+                continue;
+            };
+            let start = location.start_offset as usize;
+            let last = (location.end_offset as usize).saturating_sub(1).max(start);
+            let Some(original) = position(start) else {
+                continue;
+            };
+            let Some(Some(source)) = self.sources.get(original.source) else {
+                continue;
+            };
+            let end = position(last)
+                .filter(|end| end.source == original.source && end.line >= original.line)
+                .unwrap_or(original);
+
+            symbol.file_path = source.to_string_lossy().replace('\\', "/");
+            symbol.location = self.source_content(original.source, provider).map(|text| {
+                let starts = line_starts(&text);
+                SymbolLocation {
+                    start_line: original.line,
+                    end_line: end.line,
+                    start_offset: byte_offset(&text, &starts, original) as u32,
+                    end_offset: line_end(&text, &starts, end.line) as u32,
+                }
+            });
+        }
+    }
+
+    /// Content of an original source, embedded or read from disk
+    fn source_content(&self, source: usize, provider: &dyn ContentProvider) -> Option<String> {
+        if let Some(Some(content)) = self.sources_content.get(source) {
+            return Some(content.clone());
+        }
+        provider.read(self.sources.get(source)?.as_ref()?).ok()
+    }
+}
+
+/// URL of the `//# sourceMappingURL=` (or `/*# ... */`) comment ending a file
+fn source_mapping_url(content: &str) -> Option<&str> {
+    const MARKER: &str = "# sourceMappingURL=";
+    let start = content.rfind(MARKER)?;
+    if !content[..start].ends_with("//") && !content[..start].ends_with("/*") {
+        return None;
+    }
+    let url = content[start + MARKER.len()..]
+        .split(|c: char| c.is_whitespace() || c == '*')
+        .next()?;
+    (!url.is_empty()).then_some(url)
+}
+
+/// Byte offset of an original position, clamped to its line
+fn byte_offset(text: &str, starts: &[usize], position: OriginalPosition) -> usize {
+    let Some(&line_start) = starts.get(position.line as usize) else {
+        return text.len();
+    };
+    let mut units = 0;
+    for (index, c) in text[line_start..].char_indices() {
+        if units >= position.column as usize || c == '\n' {
+            return line_start + index;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+/// Byte offset just past the end of a line, before its terminator
+fn line_end(text: &str, starts: &[usize], line: u32) -> usize {
+    match starts.get(line as usize + 1) {
+        Some(&next) => text[..next - 1]
+            .strip_suffix('\r')
+            .map_or(next - 1, str::len),
+        None => text.len(),
+    }
+}
+
+/// Decode the `mappings` field: for each generated line, the segments that
+/// map to a source, sorted by generated column
+fn decode_mappings(mappings: &str) -> Option<Vec<Vec<(u32, OriginalPosition)>>> {
+    // Source, line, and column are relative to the previous segment's
+    let (mut source, mut line, mut column) = (0i64, 0i64, 0i64);
+    let mut lines = Vec::new();
+    for encoded in mappings.split(';') {
+        let mut generated = 0i64;
+        let mut segments = Vec::new();
+        for segment in encoded.split(',').filter(|s| !s.is_empty()) {
+            let fields = decode_vlq(segment)?;
+            generated += fields[0];
+            if let [_, source_delta, line_delta, column_delta, ..] = fields[..] {
+                source += source_delta;
+                line += line_delta;
+                column += column_delta;
+                let position = OriginalPosition {
+                    source: usize::try_from(source).ok()?,
+                    line: u32::try_from(line).ok()?,
+                    column: u32::try_from(column).ok()?,
+                };
+                segments.push((u32::try_from(generated).ok()?, position));
+            }
+        }
+        segments.sort_by_key(|(start, _)| *start);
+        lines.push(segments);
+    }
+    Some(lines)
+}
+
+/// Decode one segment's base64 VLQ values
+fn decode_vlq(segment: &str) -> Option<Vec<i64>> {
+    let mut values = Vec::new();
+    let (mut value, mut shift) = (0i64, 0u32);
+    for byte in segment.bytes() {
+        let digit = VLQ_DIGITS.iter().position(|&d| d == byte)? as i64;
+        value += (digit & 31) << shift;
+        if digit & 32 != 0 {
+            shift += 5;
+            if shift > 55 {
+                return None;
+            }
+        } else {
+            values.push(if value & 1 == 1 {
+                -(value >> 1)
+            } else {
+                value >> 1
+            });
+            (value, shift) = (0, 0);
+        }
+    }
+    (shift == 0 && !values.is_empty()).then_some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{AnalyzerOptions, AstAnalyzerInternal};
+    use crate::provider::MemoryProvider;
+
+    const ORIGINAL: &str = "// Authentication\n\
+                            /** Log in */\n\
+                            export function login(user: string): string {\n\
+                            \x20   return user;\n\
+                            }\n";
+
+    /// Compiled `ORIGINAL`, mapping lines 1-3 to its lines 2-4
+    fn generated(url: &str) -> String {
+        format!(
+            "\"use strict\";\nexport function login(user) {{\n    return user;\n}}\n//# sourceMappingURL={}\n",
+            url
+        )
+    }
+
+    const MAP: &str = r#"{"version":3,"sources":["../src/auth.ts"],"mappings":";AAEA;IACI;AACJ"}"#;
+
+    #[test]
+    fn test_decode_vlq() {
+        assert_eq!(decode_vlq("AAAA"), Some(vec![0, 0, 0, 0]));
+        assert_eq!(decode_vlq("IACJ"), Some(vec![4, 0, 1, -4]));
+        assert_eq!(decode_vlq("gB"), Some(vec![16]));
+        assert_eq!(decode_vlq("g"), None);
+        assert_eq!(decode_vlq("A!"), None);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let map = SourceMap::parse(MAP, Path::new("dist")).unwrap();
+        assert_eq!(map.sources, [Some(PathBuf::from("src/auth.ts"))]);
+        assert_eq!(map.lookup(0, 0), None);
+        let position = |line, column| OriginalPosition {
+            source: 0,
+            line,
+            column,
+        };
+        assert_eq!(map.lookup(1, 7), Some(position(2, 0)));
+        assert_eq!(map.lookup(2, 10), Some(position(3, 4)));
+        // Before the first segment of a line
+        assert_eq!(map.lookup(2, 0), Some(position(3, 4)));
+
+        let urls = r#"{"version":3,"sources":["webpack://app/./src/a.ts"],"mappings":"AAAA"}"#;
+        assert_eq!(
+            SourceMap::parse(urls, Path::new("")).unwrap().sources,
+            [None]
+        );
+        assert!(
+            SourceMap::parse(r#"{"version":2,"sources":[],"mappings":""}"#, Path::new(""))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_symbols_attributed_to_sources() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/auth.ts", ORIGINAL);
+        provider.set("dist/auth.js", generated("auth.js.map"));
+        provider.set("dist/auth.js.map", MAP);
+        let inline = format!(
+            "data:application/json;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(MAP)
+        );
+        provider.set("dist/inline.js", generated(&inline));
+        let files = [
+            PathBuf::from("dist/auth.js"),
+            PathBuf::from("dist/inline.js"),
+        ];
+
+        let analyzer = AstAnalyzerInternal::with_options(AnalyzerOptions::new().source_maps(true));
+        let result = analyzer.analyze_with(&provider, &files);
+        assert_eq!(result.symbols.len(), 2);
+        for symbol in &result.symbols {
+            assert_eq!(symbol.file_path, "src/auth.ts");
+            let location = symbol.location.unwrap();
+            assert_eq!((location.start_line, location.end_line), (2, 4));
+            let span = location.start_offset as usize..location.end_offset as usize;
+            assert!(ORIGINAL[span].starts_with("export function login"));
+        }
+
+        // Generated files are skipped unless their maps are used
+        let result = AstAnalyzerInternal::new().analyze_with(&provider, &files);
+        assert!(result.symbols.is_empty());
+        assert_eq!(result.skipped.len(), 2);
+    }
+}
@@ -0,0 +1,499 @@
+//! SQL DDL analyzer
+//!
+//! This module extracts tables, views, and functions from SQL migration or
+//! schema files so database documentation can be anchored to them. The
+//! analyzer replays `CREATE`, `ALTER TABLE`, and `DROP` statements in file
+//! order, so the resulting signatures describe the schema as it stands at
+//! the end of the file and drift when columns change.
+//!
+//! This is intentionally a lightweight statement scanner rather than a full
+//! SQL grammar: it understands comments, quoted strings and identifiers, and
+//! PostgreSQL dollar-quoted bodies, which is enough to split statements
+//! reliably across dialects.
+
+use super::analyzer::{AnalysisResult, SymbolInfo};
+use super::diagnostics::{error_messages, Diagnostic};
+use crate::types::SymbolType;
+use regex::Regex;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// Compiled statement patterns, built once on first use
+struct SqlRegexes {
+    create_table: Regex,
+    create_view: Regex,
+    create_function: Regex,
+    alter_table: Regex,
+    drop_object: Regex,
+    returns: Regex,
+}
+
+impl SqlRegexes {
+    fn new() -> Self {
+        Self {
+            create_table: Regex::new(
+                r"(?is)^CREATE\s+(?:OR\s+REPLACE\s+)?(?:(?:GLOBAL|LOCAL)\s+)?(?:TEMP(?:ORARY)?\s+|UNLOGGED\s+)?TABLE\s+(?:IF\s+NOT\s+EXISTS\s+)?([^\s(]+)\s*\(",
+            )
+            .unwrap(),
+            create_view: Regex::new(
+                r"(?is)^CREATE\s+(?:OR\s+REPLACE\s+)?(?:TEMP(?:ORARY)?\s+)?(?:MATERIALIZED\s+)?VIEW\s+(?:IF\s+NOT\s+EXISTS\s+)?([^\s(]+)\s*(\([^)]*\))?\s*AS\s+(.*)$",
+            )
+            .unwrap(),
+            create_function: Regex::new(
+                r"(?is)^CREATE\s+(?:OR\s+REPLACE\s+)?(FUNCTION|PROCEDURE)\s+([^\s(]+)\s*\(",
+            )
+            .unwrap(),
+            alter_table: Regex::new(
+                r"(?is)^ALTER\s+TABLE\s+(?:IF\s+EXISTS\s+)?(?:ONLY\s+)?([^\s]+)\s+(.*)$",
+            )
+            .unwrap(),
+            drop_object: Regex::new(
+                r"(?is)^DROP\s+(?:MATERIALIZED\s+)?(TABLE|VIEW|FUNCTION|PROCEDURE)\s+(?:IF\s+EXISTS\s+)?([^\s(;]+)",
+            )
+            .unwrap(),
+            returns: Regex::new(
+                r"(?is)^\s*RETURNS\s+(.+?)(?:\s+(?:AS|LANGUAGE|BEGIN|IMMUTABLE|STABLE|VOLATILE|STRICT|SECURITY|RETURN)\b.*)?$",
+            )
+            .unwrap(),
+        }
+    }
+}
+
+static SQL_REGEX: OnceLock<SqlRegexes> = OnceLock::new();
+
+fn get_sql_regex() -> &'static SqlRegexes {
+    SQL_REGEX.get_or_init(SqlRegexes::new)
+}
+
+/// A schema object being rebuilt while replaying statements
+enum SqlObject {
+    Table { name: String, columns: Vec<String> },
+    View { name: String, definition: String },
+    Function { name: String, signature: String },
+}
+
+impl SqlObject {
+    fn name(&self) -> &str {
+        match self {
+            SqlObject::Table { name, .. }
+            | SqlObject::View { name, .. }
+            | SqlObject::Function { name, .. } => name,
+        }
+    }
+
+    fn into_symbol(self, file_path: &str) -> SymbolInfo {
+        let (name, symbol_type, signature) = match self {
+            SqlObject::Table { name, columns } => {
+                let signature = format!("table {}({})", name, columns.join(", "));
+                (name, SymbolType::Table, signature)
+            }
+            SqlObject::View { name, definition } => {
+                let signature = format!("view {} as {}", name, definition);
+                (name, SymbolType::View, signature)
+            }
+            SqlObject::Function { name, signature } => (name, SymbolType::Function, signature),
+        };
+
+        SymbolInfo {
+            name,
+            symbol_type,
+            signature,
+            is_exported: true,
+            file_path: file_path.to_string(),
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+        }
+    }
+}
+
+/// Analyze SQL DDL and extract tables, views, and functions
+///
+/// # Arguments
+/// * `file_path` - Path of the SQL file (used for symbol metadata)
+/// * `content` - Raw SQL content, possibly containing many statements
+///
+/// # Returns
+/// AnalysisResult with one symbol per object still defined at the end of the file
+pub fn analyze_sql(file_path: &str, content: &str) -> AnalysisResult {
+    let regex = get_sql_regex();
+    let mut objects: Vec<SqlObject> = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut error = |message: String, span: Range<usize>| {
+        diagnostics.push(Diagnostic::error(file_path, content, message, span));
+    };
+
+    for (statement, span) in split_statements(content) {
+        if let Some(caps) = regex.create_table.captures(&statement) {
+            let name = unquote_identifier(&caps[1]);
+            let open = caps.get(0).unwrap().end() - 1;
+            match matching_paren(&statement, open) {
+                Some(close) => {
+                    let columns = split_top_level(&statement[open + 1..close]);
+                    replace_object(&mut objects, SqlObject::Table { name, columns });
+                }
+                None => error(format!("unbalanced parentheses in table {}", name), span),
+            }
+        } else if let Some(caps) = regex.create_view.captures(&statement) {
+            let name = unquote_identifier(&caps[1]);
+            let columns = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+            let definition = format!("{}{}", columns, &caps[3]).trim().to_string();
+            replace_object(&mut objects, SqlObject::View { name, definition });
+        } else if let Some(caps) = regex.create_function.captures(&statement) {
+            let kind = caps[1].to_lowercase();
+            let name = unquote_identifier(&caps[2]);
+            let open = caps.get(0).unwrap().end() - 1;
+            let Some(close) = matching_paren(&statement, open) else {
+                error(format!("unbalanced parentheses in {} {}", kind, name), span);
+                continue;
+            };
+
+            let args = split_top_level(&statement[open + 1..close]).join(", ");
+            let mut signature = format!("{} {}({})", kind, name, args);
+            if let Some(returns) = regex.returns.captures(&statement[close + 1..]) {
+                signature.push_str(" returns ");
+                signature.push_str(returns[1].trim());
+            }
+            replace_object(&mut objects, SqlObject::Function { name, signature });
+        } else if let Some(caps) = regex.alter_table.captures(&statement) {
+            let name = unquote_identifier(&caps[1]);
+            let Some(SqlObject::Table { columns, .. }) =
+                objects.iter_mut().find(|o| o.name() == name)
+            else {
+                // Altering a table defined in another migration: nothing to replay here
+                continue;
+            };
+
+            for action in split_top_level(&caps[2]) {
+                apply_alter_action(columns, &action);
+            }
+        } else if let Some(caps) = regex.drop_object.captures(&statement) {
+            let name = unquote_identifier(&caps[2]);
+            objects.retain(|o| o.name() != name);
+        }
+    }
+
+    AnalysisResult {
+        symbols: objects
+            .into_iter()
+            .map(|o| o.into_symbol(file_path))
+            .collect(),
+        errors: error_messages(&diagnostics),
+        diagnostics,
+        ignored: Vec::new(),
+        skipped: Vec::new(),
+    }
+}
+
+/// Replace an existing object with the same name, or append a new one
+fn replace_object(objects: &mut Vec<SqlObject>, object: SqlObject) {
+    match objects.iter_mut().find(|o| o.name() == object.name()) {
+        Some(existing) => *existing = object,
+        None => objects.push(object),
+    }
+}
+
+/// Apply a single `ALTER TABLE` action to a table's column list
+fn apply_alter_action(columns: &mut Vec<String>, action: &str) {
+    let words: Vec<&str> = action.split_whitespace().collect();
+    let upper: Vec<String> = words.iter().map(|w| w.to_uppercase()).collect();
+    let is = |idx: usize, kw: &str| upper.get(idx).map(|w| w == kw).unwrap_or(false);
+
+    match upper.first().map(String::as_str) {
+        Some("ADD") => {
+            let skip = if is(1, "COLUMN") { 2 } else { 1 };
+            let skip = if is(skip, "IF") { skip + 3 } else { skip };
+            if words.len() > skip {
+                columns.push(words[skip..].join(" "));
+            }
+        }
+        Some("DROP") => {
+            let skip = if is(1, "COLUMN") { 2 } else { 1 };
+            let skip = if is(skip, "IF") { skip + 2 } else { skip };
+            if let Some(column) = words.get(skip) {
+                let column = unquote_identifier(column);
+                columns.retain(|c| column_name(c) != column);
+            }
+        }
+        Some("RENAME") if is(1, "COLUMN") && is(3, "TO") && words.len() > 4 => {
+            let (from, to) = (unquote_identifier(words[2]), words[4]);
+            for column in columns.iter_mut() {
+                if column_name(column) == from {
+                    let rest = column.split_once(' ').map(|(_, r)| r).unwrap_or("");
+                    *column = format!("{} {}", to, rest).trim().to_string();
+                }
+            }
+        }
+        Some("ALTER") => {
+            let skip = if is(1, "COLUMN") { 2 } else { 1 };
+            let Some(target) = words.get(skip).map(|c| unquote_identifier(c)) else {
+                return;
+            };
+            let type_idx = if is(skip + 1, "SET") && is(skip + 2, "DATA") && is(skip + 3, "TYPE") {
+                skip + 4
+            } else if is(skip + 1, "TYPE") {
+                skip + 2
+            } else {
+                return;
+            };
+
+            if let Some(new_type) = words.get(type_idx) {
+                for column in columns.iter_mut() {
+                    if column_name(column) == target {
+                        let mut parts: Vec<&str> = column.split_whitespace().collect();
+                        if parts.len() > 1 {
+                            parts[1] = new_type;
+                        }
+                        *column = parts.join(" ");
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Name of the column defined by a column definition (first identifier)
+fn column_name(definition: &str) -> String {
+    definition
+        .split_whitespace()
+        .next()
+        .map(unquote_identifier)
+        .unwrap_or_default()
+}
+
+/// Strip SQL identifier quoting (`"x"`, `` `x` ``, `[x]`) from each dotted part
+fn unquote_identifier(identifier: &str) -> String {
+    identifier
+        .split('.')
+        .map(|part| part.trim_matches(|c| matches!(c, '"' | '`' | '[' | ']')))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Find the closing parenthesis matching the one at `open`
+fn matching_paren(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (idx, ch) in text.char_indices().skip_while(|(i, _)| *i < open) {
+        match ch {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on commas that are not nested inside parentheses
+fn split_top_level(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+
+    for ch in text.chars() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+/// Split SQL into statements with comments removed and whitespace collapsed
+///
+/// Semicolons inside string literals, quoted identifiers, and dollar-quoted
+/// bodies do not terminate a statement.
+///
+/// # Returns
+/// Each statement, with the byte span from its first character up to its `;`
+fn split_statements(content: &str) -> Vec<(String, Range<usize>)> {
+    let chars: Vec<char> = content.chars().collect();
+    let offsets: Vec<usize> = content
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain([content.len()])
+        .collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut start = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        let next = chars.get(i + 1).copied();
+        let comment = matches!((ch, next), ('-', Some('-')) | ('/', Some('*')));
+        if !(ch.is_whitespace() || ch == ';' || comment) {
+            start.get_or_insert(offsets[i]);
+        }
+
+        match ch {
+            '-' if next == Some('-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                current.push(' ');
+                continue;
+            }
+            '/' if next == Some('*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+                current.push(' ');
+                continue;
+            }
+            '\'' | '"' | '`' => {
+                current.push(ch);
+                i += 1;
+                while i < chars.len() {
+                    current.push(chars[i]);
+                    i += 1;
+                    if chars[i - 1] == ch {
+                        break;
+                    }
+                }
+                continue;
+            }
+            '$' => {
+                // Dollar quoting: $$ ... $$ or $tag$ ... $tag$
+                let tag_end = chars[i + 1..]
+                    .iter()
+                    .position(|c| !(c.is_alphanumeric() || *c == '_'))
+                    .map(|p| i + 1 + p);
+                if let Some(end) = tag_end.filter(|&e| chars[e] == '$') {
+                    let tag: String = chars[i..=end].iter().collect();
+                    let body_start = end + 1;
+                    let rest: String = chars[body_start..].iter().collect();
+                    let body_len = rest.find(&tag).map(|p| rest[..p].chars().count());
+                    let consumed = match body_len {
+                        Some(len) => len + tag.chars().count(),
+                        None => chars.len() - body_start,
+                    };
+                    current.push_str(&tag);
+                    current.push_str("...");
+                    current.push_str(&tag);
+                    i = body_start + consumed;
+                    continue;
+                }
+            }
+            ';' => {
+                let span = start.take().unwrap_or(offsets[i])..offsets[i];
+                push_statement(&mut statements, &current, span);
+                current.clear();
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        current.push(ch);
+        i += 1;
+    }
+
+    let end = content.len();
+    push_statement(&mut statements, &current, start.unwrap_or(end)..end);
+    statements
+}
+
+fn push_statement(statements: &mut Vec<(String, Range<usize>)>, raw: &str, span: Range<usize>) {
+    let collapsed = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !collapsed.is_empty() {
+        statements.push((collapsed, span));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_table() {
+        let sql = r#"
+            -- users table
+            CREATE TABLE IF NOT EXISTS public."users" (
+                id uuid PRIMARY KEY,
+                email   text NOT NULL, /* login */
+                balance numeric(10, 2) DEFAULT 0
+            );
+        "#;
+        let result = analyze_sql("001_init.sql", sql);
+
+        assert!(result.errors.is_empty());
+        assert_eq!(result.symbols.len(), 1);
+        assert_eq!(result.symbols[0].name, "public.users");
+        assert_eq!(result.symbols[0].symbol_type, SymbolType::Table);
+        assert_eq!(
+            result.symbols[0].signature,
+            "table public.users(id uuid PRIMARY KEY, email text NOT NULL, balance numeric(10, 2) DEFAULT 0)"
+        );
+    }
+
+    #[test]
+    fn test_alter_table_replays_column_changes() {
+        let sql = r#"
+            CREATE TABLE users (id int, name text, legacy text);
+            ALTER TABLE users ADD COLUMN email text NOT NULL;
+            ALTER TABLE users DROP COLUMN legacy, RENAME COLUMN name TO full_name;
+            ALTER TABLE users ALTER COLUMN id TYPE bigint;
+        "#;
+        let result = analyze_sql("002.sql", sql);
+
+        assert_eq!(
+            result.symbols[0].signature,
+            "table users(id bigint, full_name text, email text NOT NULL)"
+        );
+    }
+
+    #[test]
+    fn test_view_and_function() {
+        let sql = r#"
+            CREATE OR REPLACE VIEW active_users AS SELECT id FROM users WHERE active;
+            CREATE FUNCTION add_user(name text, age int DEFAULT 0) RETURNS uuid LANGUAGE plpgsql AS $body$
+            BEGIN
+                INSERT INTO users VALUES (name; age);
+            END;
+            $body$;
+        "#;
+        let result = analyze_sql("003.sql", sql);
+
+        assert_eq!(result.symbols.len(), 2);
+        assert_eq!(result.symbols[0].symbol_type, SymbolType::View);
+        assert_eq!(
+            result.symbols[0].signature,
+            "view active_users as SELECT id FROM users WHERE active"
+        );
+        assert_eq!(result.symbols[1].symbol_type, SymbolType::Function);
+        assert_eq!(
+            result.symbols[1].signature,
+            "function add_user(name text, age int DEFAULT 0) returns uuid"
+        );
+    }
+
+    #[test]
+    fn test_drop_removes_object() {
+        let sql = "CREATE TABLE a (id int); CREATE TABLE b (id int); DROP TABLE IF EXISTS a;";
+        let result = analyze_sql("004.sql", sql);
+
+        assert_eq!(result.symbols.len(), 1);
+        assert_eq!(result.symbols[0].name, "b");
+    }
+}
@@ -0,0 +1,126 @@
+//! Symbol IDs
+//!
+//! Two identifiers are derived from a symbol's declaration, both here:
+//!
+//! - `symbol_key`: its qualified name and kind. It survives file moves, so
+//!   map entries record it (`symbolKey`) to find a symbol again after a
+//!   refactor moves it; symbols with the same name and kind in different
+//!   files share it.
+//! - `symbol_id`: the symbol key within its normalized file path, so two
+//!   symbols only share an ID if they are the same declaration, and the ID
+//!   does not change with the signature. It is `CodeSignature::id`, and map
+//!   entries record it (`symbolId`) so lookups do not depend on how
+//!   `file#symbol` strings happen to be spelled.
+//!
+//! Paths are normalized the way imports see modules: `/`-separated, without
+//! `./` and `..` segments, and for JavaScript and TypeScript modules without
+//! the extension or a trailing `/index`. Converting `auth.js` to `auth.ts`,
+//! or moving it to `auth/index.ts`, keeps its symbols' IDs; any other move
+//! changes them, and only the key is left to follow.
+
+use super::hasher::SignatureHasher;
+use crate::types::SymbolType;
+
+/// Extensions of modules imported without them
+const MODULE_EXTENSIONS: &[&str] = &[
+    ".d.ts", ".ts", ".tsx", ".mts", ".cts", ".js", ".jsx", ".mjs", ".cjs",
+];
+
+/// Symbol ID of a symbol, from its file, name, and kind
+///
+/// # Arguments
+/// * `file_path` - File declaring the symbol, relative to the project root
+/// * `qualified_name` - Name of the symbol (`User.login` for members)
+/// * `symbol_type` - Kind of the symbol
+///
+/// # Returns
+/// The first 16 hex characters of the SHA256 of `path|key`, the path
+/// normalized with `normalize_module_path` and the key from `symbol_key`
+pub fn symbol_id(file_path: &str, qualified_name: &str, symbol_type: SymbolType) -> String {
+    let id = SignatureHasher::new().hash_text(&format!(
+        "{}|{}",
+        normalize_module_path(file_path),
+        symbol_key(qualified_name, symbol_type)
+    ));
+    id[..16].to_string()
+}
+
+/// Symbol key of a symbol, from its name and kind only
+///
+/// # Returns
+/// The first 16 hex characters of the SHA256 of `kind:name`
+pub fn symbol_key(qualified_name: &str, symbol_type: SymbolType) -> String {
+    let hasher = SignatureHasher::new();
+    let key = hasher.hash_text(&format!(
+        "{}:{}",
+        hasher.symbol_type_to_string(symbol_type),
+        qualified_name
+    ));
+    key[..16].to_string()
+}
+
+/// Path of a module as imports see it (`./src\auth/index.ts` => `src/auth`)
+pub fn normalize_module_path(file_path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in file_path.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let mut path = segments.join("/");
+    if let Some(extension) = MODULE_EXTENSIONS.iter().find(|ext| path.ends_with(*ext)) {
+        path.truncate(path.len() - extension.len());
+        if path == "index" {
+            path.clear();
+        } else if let Some(parent) = path.strip_suffix("/index") {
+            path = parent.to_string();
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_id_is_keyed_on_module_path() {
+        let id = symbol_id("src/auth.ts", "login", SymbolType::Function);
+        assert_eq!(id.len(), 16);
+        assert_eq!(
+            id,
+            symbol_id("./src\\auth.ts", "login", SymbolType::Function)
+        );
+        assert_eq!(id, symbol_id("src/auth.js", "login", SymbolType::Function));
+        assert_eq!(
+            id,
+            symbol_id("src/auth/index.tsx", "login", SymbolType::Function)
+        );
+        assert_ne!(
+            id,
+            symbol_id("src/session.ts", "login", SymbolType::Function)
+        );
+        assert_ne!(id, symbol_id("src/auth.ts", "login", SymbolType::Const));
+        assert_ne!(id, symbol_id("src/auth.ts", "logout", SymbolType::Function));
+
+        assert_eq!(normalize_module_path("lib/../src/types.d.ts"), "src/types");
+        assert_eq!(normalize_module_path("index.js"), "");
+        // Other languages keep their extension
+        assert_eq!(normalize_module_path("src/auth.py"), "src/auth.py");
+    }
+
+    #[test]
+    fn test_symbol_key_depends_on_name_and_kind() {
+        let key = symbol_key("login", SymbolType::Function);
+
+        assert_eq!(key.len(), 16);
+        assert_eq!(key, symbol_key("login", SymbolType::Function));
+        assert_ne!(key, symbol_key("login", SymbolType::Const));
+        assert_ne!(key, symbol_key("logout", SymbolType::Function));
+        assert_ne!(key, symbol_id("src/auth.ts", "login", SymbolType::Function));
+    }
+}
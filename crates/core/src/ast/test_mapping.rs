@@ -0,0 +1,529 @@
+//! Test-to-symbol mapping
+//!
+//! This module associates test cases with the symbols they exercise, so
+//! documentation can state "covered by N tests" and drift reports can flag
+//! API changes that no test touches.
+//!
+//! Test cases are discovered from:
+//! - TypeScript/JavaScript `describe`/`it`/`test` blocks (titles are joined
+//!   with ` > `), where references are resolved through the file's imports
+//! - Rust `#[test]` functions, where called names are matched against known
+//!   symbols from Rust sources
+//!
+//! Symbols are identified by their code reference (`file_path#symbol_name`),
+//! the same format used by anchors. `DriftResult::with_tests` joins a map
+//! into drift results, which the pipeline does for the project's test files
+//! (`map_project_tests`).
+
+use super::analyzer::SymbolInfo;
+use super::arena::with_allocator;
+use crate::graph::{module_candidates, normalize_path};
+use crate::text::read_text;
+use oxc_ast::ast::*;
+use oxc_ast::visit::walk;
+use oxc_ast::Visit;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Test-runner functions that declare a suite
+const SUITE_FUNCTIONS: &[&str] = &["describe", "suite", "context"];
+
+/// Test-runner functions that declare a test case
+const CASE_FUNCTIONS: &[&str] = &["it", "test", "specify"];
+
+/// A single test case and the names it references
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    /// Full test title (`suite > nested suite > case`) or Rust test function name
+    pub name: String,
+    /// File the test is declared in
+    pub file_path: String,
+    /// Line where the test is declared (0-indexed)
+    pub line: usize,
+    /// Code references (`file#symbol`) of the symbols this test exercises
+    pub covers: Vec<String>,
+}
+
+/// Mapping from symbols to the tests that exercise them
+#[derive(Debug, Clone, Default)]
+pub struct TestMap {
+    /// All discovered test cases
+    pub tests: Vec<TestCase>,
+    /// Code reference -> indices into `tests`
+    pub by_symbol: HashMap<String, Vec<usize>>,
+}
+
+impl TestMap {
+    /// Tests exercising the given code reference
+    pub fn tests_for(&self, code_ref: &str) -> Vec<&TestCase> {
+        self.by_symbol
+            .get(code_ref)
+            .map(|ids| ids.iter().map(|&i| &self.tests[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Number of tests exercising the given code reference
+    pub fn coverage_count(&self, code_ref: &str) -> usize {
+        self.by_symbol.get(code_ref).map_or(0, Vec::len)
+    }
+
+    /// Exported symbols that no test exercises
+    pub fn untested<'s>(&self, symbols: &'s [SymbolInfo]) -> Vec<&'s SymbolInfo> {
+        symbols
+            .iter()
+            .filter(|s| s.is_exported && self.coverage_count(&code_ref(s)) == 0)
+            .collect()
+    }
+}
+
+/// Check whether a path looks like a test file
+pub fn is_test_file(file_path: &str) -> bool {
+    let path = Path::new(file_path);
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let in_test_dir = path
+        .components()
+        .any(|c| matches!(c.as_os_str().to_str(), Some("__tests__") | Some("tests")));
+
+    file_name.contains(".test.") || file_name.contains(".spec.") || in_test_dir
+}
+
+/// Build a test map from test sources and the symbols they may exercise
+///
+/// # Arguments
+/// * `test_files` - `(file_path, content)` pairs of test sources
+/// * `symbols` - Symbols from analyzed source files (their `file_path` is used
+///   to resolve test imports)
+pub fn map_tests_to_symbols(test_files: &[(String, String)], symbols: &[SymbolInfo]) -> TestMap {
+    let mut map = TestMap::default();
+
+    for (file_path, content) in test_files {
+        let cases = if file_path.ends_with(".rs") {
+            rust_test_cases(file_path, content, symbols)
+        } else {
+            js_test_cases(file_path, content, symbols)
+        };
+
+        for case in cases {
+            let index = map.tests.len();
+            for code_ref in &case.covers {
+                map.by_symbol
+                    .entry(code_ref.clone())
+                    .or_default()
+                    .push(index);
+            }
+            map.tests.push(case);
+        }
+    }
+
+    map
+}
+
+/// Build a test map from the test files among a project's files
+///
+/// # Arguments
+/// * `root` - Project root
+/// * `files` - Project files (absolute, or relative to `root`); files that
+///   are not tests or cannot be read are skipped
+/// * `symbols` - Symbols from analyzed source files, with paths relative to
+///   `root`
+pub fn map_project_tests(root: &Path, files: &[PathBuf], symbols: &[SymbolInfo]) -> TestMap {
+    let test_files: Vec<(String, String)> = files
+        .iter()
+        .filter_map(|path| {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            let file_path = relative.to_string_lossy().replace('\\', "/");
+            if !is_test_file(&file_path) {
+                return None;
+            }
+            let content = read_text(&root.join(relative)).ok()?;
+            Some((file_path, content))
+        })
+        .collect();
+    map_tests_to_symbols(&test_files, symbols)
+}
+
+fn code_ref(symbol: &SymbolInfo) -> String {
+    format!("{}#{}", symbol.file_path, symbol.name)
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count()
+}
+
+// ============================================================================
+// TypeScript / JavaScript
+// ============================================================================
+
+/// What a local name in a test file is bound to by its imports
+enum ImportBinding {
+    /// `import { name as local } from 'module'` or a default import
+    Named { module: String, imported: String },
+    /// `import * as local from 'module'`
+    Namespace { module: String },
+}
+
+fn js_test_cases(file_path: &str, content: &str, symbols: &[SymbolInfo]) -> Vec<TestCase> {
+    let source_type = SourceType::from_path(file_path).unwrap_or_else(|_| SourceType::ts());
+    let collector = with_allocator(|allocator| {
+        let ret = Parser::new(allocator, content, source_type).parse();
+        let mut collector = TestCollector {
+            content,
+            suites: Vec::new(),
+            imports: HashMap::new(),
+            cases: Vec::new(),
+            current: None,
+        };
+        collector.visit_program(&ret.program);
+        collector
+    });
+
+    let known_files: HashSet<&str> = symbols.iter().map(|s| s.file_path.as_str()).collect();
+    let test_dir = Path::new(file_path).parent().unwrap_or(Path::new(""));
+
+    collector
+        .cases
+        .into_iter()
+        .map(|raw| {
+            let mut covers = Vec::new();
+            for reference in &raw.references {
+                let (local, member) = match reference.split_once('.') {
+                    Some((local, member)) => (local, Some(member)),
+                    None => (reference.as_str(), None),
+                };
+                let (module, imported) = match (collector.imports.get(local), member) {
+                    (Some(ImportBinding::Named { module, imported }), _) => {
+                        (module, imported.as_str())
+                    }
+                    (Some(ImportBinding::Namespace { module }), Some(member)) => (module, member),
+                    _ => continue,
+                };
+                let Some(target) = resolve_module(test_dir, module, &known_files) else {
+                    continue;
+                };
+                let found = symbols
+                    .iter()
+                    .find(|s| s.file_path == target && s.name == imported);
+                if let Some(symbol) = found {
+                    let code_ref = code_ref(symbol);
+                    if !covers.contains(&code_ref) {
+                        covers.push(code_ref);
+                    }
+                }
+            }
+
+            TestCase {
+                name: raw.name,
+                file_path: file_path.to_string(),
+                line: raw.line,
+                covers,
+            }
+        })
+        .collect()
+}
+
+/// Resolve a relative module specifier against the set of known source files
+fn resolve_module(test_dir: &Path, specifier: &str, known_files: &HashSet<&str>) -> Option<String> {
+    if !specifier.starts_with('.') {
+        return None;
+    }
+    let resolved = normalize_path(&test_dir.join(specifier));
+    module_candidates(&resolved)
+        .into_iter()
+        .map(|c| c.to_string_lossy().replace('\\', "/"))
+        .find(|c| known_files.contains(c.as_str()))
+}
+
+struct RawTestCase {
+    name: String,
+    line: usize,
+    references: Vec<String>,
+}
+
+struct TestCollector<'s> {
+    content: &'s str,
+    suites: Vec<String>,
+    imports: HashMap<String, ImportBinding>,
+    cases: Vec<RawTestCase>,
+    current: Option<usize>,
+}
+
+impl TestCollector<'_> {
+    fn record(&mut self, reference: String) {
+        if let Some(index) = self.current {
+            let references = &mut self.cases[index].references;
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+    }
+}
+
+/// Name of a test-runner call, looking through modifiers like `it.only`
+fn runner_name<'b>(call: &'b CallExpression<'_>) -> Option<&'b str> {
+    match &call.callee {
+        Expression::Identifier(ident) => Some(ident.name.as_str()),
+        Expression::StaticMemberExpression(member) => match &member.object {
+            Expression::Identifier(ident) => Some(ident.name.as_str()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn title_of(call: &CallExpression<'_>) -> Option<String> {
+    match call.arguments.first()?.as_expression()? {
+        Expression::StringLiteral(lit) => Some(lit.value.to_string()),
+        Expression::TemplateLiteral(tpl) => tpl.quasis.first().map(|q| q.value.raw.to_string()),
+        _ => None,
+    }
+}
+
+impl<'a> Visit<'a> for TestCollector<'_> {
+    fn visit_import_declaration(&mut self, decl: &ImportDeclaration<'a>) {
+        let module = decl.source.value.to_string();
+        for specifier in decl.specifiers.iter().flatten() {
+            let (local, binding) = match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(s) => (
+                    s.local.name.to_string(),
+                    ImportBinding::Named {
+                        module: module.clone(),
+                        imported: s.imported.name().to_string(),
+                    },
+                ),
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => (
+                    s.local.name.to_string(),
+                    ImportBinding::Named {
+                        module: module.clone(),
+                        imported: "default".to_string(),
+                    },
+                ),
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => (
+                    s.local.name.to_string(),
+                    ImportBinding::Namespace {
+                        module: module.clone(),
+                    },
+                ),
+            };
+            self.imports.insert(local, binding);
+        }
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        let runner = runner_name(call);
+        let title = title_of(call);
+
+        match (runner, title) {
+            (Some(runner), Some(title)) if SUITE_FUNCTIONS.contains(&runner) => {
+                self.suites.push(title);
+                walk::walk_call_expression(self, call);
+                self.suites.pop();
+            }
+            (Some(runner), Some(title))
+                if CASE_FUNCTIONS.contains(&runner) && self.current.is_none() =>
+            {
+                let mut parts = self.suites.clone();
+                parts.push(title);
+                self.cases.push(RawTestCase {
+                    name: parts.join(" > "),
+                    line: line_of(self.content, call.span.start as usize),
+                    references: Vec::new(),
+                });
+                self.current = Some(self.cases.len() - 1);
+                walk::walk_call_expression(self, call);
+                self.current = None;
+            }
+            _ => walk::walk_call_expression(self, call),
+        }
+    }
+
+    fn visit_static_member_expression(&mut self, expr: &StaticMemberExpression<'a>) {
+        if let Expression::Identifier(object) = &expr.object {
+            self.record(format!("{}.{}", object.name, expr.property.name));
+        }
+        walk::walk_static_member_expression(self, expr);
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        self.record(ident.name.to_string());
+    }
+}
+
+// ============================================================================
+// Rust
+// ============================================================================
+
+struct RustTestRegexes {
+    test_fn: Regex,
+    call: Regex,
+}
+
+static RUST_TEST_REGEX: OnceLock<RustTestRegexes> = OnceLock::new();
+
+fn rust_test_regex() -> &'static RustTestRegexes {
+    RUST_TEST_REGEX.get_or_init(|| RustTestRegexes {
+        test_fn: Regex::new(r"#\[(?:tokio::)?test\]\s*(?:#\[[^\]]*\]\s*)*(?:async\s+)?fn\s+(\w+)")
+            .unwrap(),
+        call: Regex::new(r"\b([A-Za-z_]\w*)\s*(?:::<[^>]*>)?\s*[(!{]|\b([A-Z]\w*)::").unwrap(),
+    })
+}
+
+fn rust_test_cases(file_path: &str, content: &str, symbols: &[SymbolInfo]) -> Vec<TestCase> {
+    let regex = rust_test_regex();
+    let rust_symbols: Vec<&SymbolInfo> = symbols
+        .iter()
+        .filter(|s| s.file_path.ends_with(".rs"))
+        .collect();
+
+    regex
+        .test_fn
+        .captures_iter(content)
+        .map(|caps| {
+            let whole = caps.get(0).unwrap();
+            let body = function_body(content, whole.end());
+            let mut covers = Vec::new();
+
+            for call in regex.call.captures_iter(body) {
+                let name = call.get(1).or_else(|| call.get(2)).unwrap().as_str();
+                for symbol in rust_symbols.iter().filter(|s| s.name == name) {
+                    let code_ref = code_ref(symbol);
+                    if !covers.contains(&code_ref) {
+                        covers.push(code_ref);
+                    }
+                }
+            }
+
+            TestCase {
+                name: caps[1].to_string(),
+                file_path: file_path.to_string(),
+                line: line_of(content, whole.start()),
+                covers,
+            }
+        })
+        .collect()
+}
+
+/// Slice the brace-delimited body following `from`
+fn function_body(content: &str, from: usize) -> &str {
+    let Some(open) = content[from..].find('{').map(|p| from + p) else {
+        return "";
+    };
+    let mut depth = 0;
+    for (idx, ch) in content[open..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &content[open..open + idx + 1];
+                }
+            }
+            _ => {}
+        }
+    }
+    &content[open..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SymbolType;
+
+    fn symbol(file_path: &str, name: &str) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            symbol_type: SymbolType::Function,
+            signature: String::new(),
+            is_exported: true,
+            file_path: file_path.to_string(),
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_test_file() {
+        assert!(is_test_file("src/auth.test.ts"));
+        assert!(is_test_file("packages/cli/__tests__/check.ts"));
+        assert!(is_test_file("crates/core/tests/self_docs.rs"));
+        assert!(!is_test_file("src/auth.ts"));
+    }
+
+    #[test]
+    fn test_maps_js_tests_through_imports() {
+        let symbols = vec![
+            symbol("src/auth.ts", "login"),
+            symbol("src/auth.ts", "logout"),
+            symbol("src/utils/index.ts", "slugify"),
+        ];
+        let test = r#"
+            import { login as signIn } from '../src/auth';
+            import * as utils from '../src/utils';
+
+            describe('auth', () => {
+                it('signs in', () => {
+                    expect(signIn('a')).toBe(true);
+                });
+                test.only('slugs', () => {
+                    utils.slugify('x');
+                });
+            });
+        "#;
+
+        let map = map_tests_to_symbols(
+            &[("test/auth.test.ts".to_string(), test.to_string())],
+            &symbols,
+        );
+
+        assert_eq!(map.tests.len(), 2);
+        assert_eq!(map.tests[0].name, "auth > signs in");
+        assert_eq!(map.tests[0].line, 5);
+        assert_eq!(map.coverage_count("src/auth.ts#login"), 1);
+        assert_eq!(map.coverage_count("src/utils/index.ts#slugify"), 1);
+        assert_eq!(
+            map.tests_for("src/auth.ts#login")[0].name,
+            "auth > signs in"
+        );
+
+        let untested: Vec<&str> = map
+            .untested(&symbols)
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(untested, vec!["logout"]);
+    }
+
+    #[test]
+    fn test_maps_rust_tests_by_called_name() {
+        let symbols = vec![
+            symbol("src/lib.rs", "parse"),
+            symbol("src/lib.rs", "Config"),
+        ];
+        let test = r#"
+            #[test]
+            fn parses_config() {
+                let config = Config::default();
+                assert!(parse("x").is_ok());
+            }
+
+            #[test]
+            fn unrelated() { assert_eq!(1, 1); }
+        "#;
+
+        let map = map_tests_to_symbols(
+            &[("tests/parse.rs".to_string(), test.to_string())],
+            &symbols,
+        );
+
+        assert_eq!(map.tests.len(), 2);
+        assert_eq!(map.tests[0].name, "parses_config");
+        assert_eq!(map.coverage_count("src/lib.rs#parse"), 1);
+        assert_eq!(map.coverage_count("src/lib.rs#Config"), 1);
+        assert!(map.tests[1].covers.is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+//! Architecture brief from the README and ADRs
+//!
+//! Generated documentation should agree with what the project says about
+//! itself. The brief condenses the repository README and the architecture
+//! decision records under `docs/adr/` into their outline: every heading with
+//! the first paragraph below it. Code blocks, HTML, tables, and badge-only
+//! paragraphs are skipped, and long paragraphs are cut, so the brief stays
+//! small enough to include in every LLM prompt.
+
+use super::extractor::markdown_options;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Longest summary kept for one section, in characters
+const MAX_SUMMARY_CHARS: usize = 400;
+
+/// Longest rendered brief, in characters; later sections are dropped
+const MAX_BRIEF_CHARS: usize = 12_000;
+
+/// A heading and the first paragraph below it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BriefSection {
+    /// Heading level (1-6), or 0 for text before the first heading
+    pub level: u8,
+    pub heading: String,
+    /// First paragraph of the section (empty if it has none)
+    pub summary: String,
+}
+
+/// Outline of one document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BriefDocument {
+    /// Path relative to the project root
+    pub path: String,
+    pub sections: Vec<BriefSection>,
+}
+
+/// Outlines of the README and ADRs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchitectureBrief {
+    pub documents: Vec<BriefDocument>,
+}
+
+impl ArchitectureBrief {
+    /// Build the brief from the root README and `docs/adr/*.md`
+    ///
+    /// # Arguments
+    /// * `root` - Project root
+    /// * `files` - Project files, relative to `root`
+    pub fn collect(root: &Path, files: &[PathBuf]) -> Self {
+        let mut readmes: Vec<&PathBuf> = files.iter().filter(|f| is_readme(f)).collect();
+        let mut adrs: Vec<&PathBuf> = files.iter().filter(|f| is_adr(f)).collect();
+        readmes.sort();
+        adrs.sort();
+
+        let documents = readmes
+            .into_iter()
+            .take(1)
+            .chain(adrs)
+            .filter_map(|path| {
+                let content = fs::read_to_string(root.join(path)).ok()?;
+                let document = outline(&path.to_string_lossy().replace('\\', "/"), &content);
+                (!document.sections.is_empty()).then_some(document)
+            })
+            .collect();
+        Self { documents }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+}
+
+impl fmt::Display for ArchitectureBrief {
+    /// One `### path` block per document with its headings and summaries,
+    /// cut at `MAX_BRIEF_CHARS`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        'documents: for document in &self.documents {
+            let mut block = format!("### {}\n", document.path);
+            for section in &document.sections {
+                let mut text = String::new();
+                if section.level > 0 {
+                    text.push_str(&format!(
+                        "{} {}\n",
+                        "#".repeat((usize::from(section.level) + 3).min(6)),
+                        section.heading
+                    ));
+                }
+                if !section.summary.is_empty() {
+                    text.push_str(&format!("{}\n", section.summary));
+                }
+                if out.len() + block.len() + text.len() > MAX_BRIEF_CHARS {
+                    out.push_str(&block);
+                    out.push_str("...\n");
+                    break 'documents;
+                }
+                block.push_str(&text);
+            }
+            out.push_str(&block);
+            out.push('\n');
+        }
+        write!(f, "{}", out.trim_end())
+    }
+}
+
+/// Root `README.md` (any case)
+fn is_readme(path: &Path) -> bool {
+    path.parent().is_none_or(|p| p.as_os_str().is_empty())
+        && path
+            .to_str()
+            .is_some_and(|name| name.eq_ignore_ascii_case("readme.md"))
+}
+
+/// `docs/adr/*.md`
+fn is_adr(path: &Path) -> bool {
+    path.parent() == Some(Path::new("docs/adr")) && path.extension().is_some_and(|ext| ext == "md")
+}
+
+/// Headings and first paragraphs of a markdown document
+fn outline(path: &str, content: &str) -> BriefDocument {
+    let mut sections = vec![BriefSection {
+        level: 0,
+        heading: String::new(),
+        summary: String::new(),
+    }];
+    // Text of the heading or paragraph being read
+    let mut text: Option<String> = None;
+    let mut image_depth = 0;
+
+    for event in Parser::new_ext(content, markdown_options()) {
+        let section = sections.last_mut().expect("sections start non-empty");
+        match event {
+            Event::Start(Tag::Heading { .. }) => text = Some(String::new()),
+            Event::End(TagEnd::Heading(level)) => {
+                sections.push(BriefSection {
+                    level: level as u8,
+                    heading: collapse(&text.take().unwrap_or_default()),
+                    summary: String::new(),
+                });
+            }
+            Event::Start(Tag::Paragraph) if section.summary.is_empty() => {
+                text = Some(String::new())
+            }
+            Event::End(TagEnd::Paragraph) => {
+                if let Some(paragraph) = text.take() {
+                    section.summary = truncate(&collapse(&paragraph));
+                }
+            }
+            Event::Start(Tag::Image { .. }) => image_depth += 1,
+            Event::End(TagEnd::Image) => image_depth -= 1,
+            Event::Text(value) | Event::Code(value) if image_depth == 0 => {
+                if let Some(text) = &mut text {
+                    text.push_str(&value);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(text) = &mut text {
+                    text.push(' ');
+                }
+            }
+            _ => {}
+        }
+    }
+
+    sections.retain(|s| !s.heading.is_empty() || !s.summary.is_empty());
+    BriefDocument {
+        path: path.to_string(),
+        sections,
+    }
+}
+
+/// Collapse runs of whitespace into single spaces
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Cut a summary at `MAX_SUMMARY_CHARS`, on a word boundary when possible
+fn truncate(text: &str) -> String {
+    let Some((cut, _)) = text.char_indices().nth(MAX_SUMMARY_CHARS) else {
+        return text.to_string();
+    };
+    let cut = text[..cut].rfind(' ').unwrap_or(cut);
+    format!("{}...", &text[..cut])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outline_keeps_headings_and_first_paragraphs() {
+        let readme = "[![CI](https://ci/badge.svg)](https://ci)\n\n# Kit\n\nAuth toolkit\nfor **Node**.\n\nSecond paragraph.\n\n```sh\nnpm i kit\n```\n\n## Design\n\n<!-- sintesi:start id=\"a\" code_ref=\"src/a.ts#a\" -->\nUses `Session` tokens.\n<!-- sintesi:end id=\"a\" -->\n\n## Empty\n";
+        let document = outline("README.md", readme);
+
+        let sections: Vec<(u8, &str, &str)> = document
+            .sections
+            .iter()
+            .map(|s| (s.level, s.heading.as_str(), s.summary.as_str()))
+            .collect();
+        assert_eq!(
+            sections,
+            [
+                (1, "Kit", "Auth toolkit for Node."),
+                (2, "Design", "Uses Session tokens."),
+                (2, "Empty", ""),
+            ]
+        );
+
+        let long = format!("# Long\n\n{}", "word ".repeat(200));
+        let summary = &outline("a.md", &long).sections[0].summary;
+        assert!(summary.ends_with("word..."));
+        assert!(summary.len() <= MAX_SUMMARY_CHARS + 3);
+    }
+
+    #[test]
+    fn test_collect_readme_and_adrs() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("docs/adr")).unwrap();
+        fs::write(dir.path().join("README.md"), "# Kit\n\nAuth toolkit.\n").unwrap();
+        fs::write(
+            dir.path().join("docs/adr/0001-use-jwt.md"),
+            "# 1. Use JWT\n\n## Decision\n\nSessions are stateless JWTs.\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("docs/guide.md"), "# Guide\n").unwrap();
+        let files: Vec<PathBuf> = ["docs/guide.md", "docs/adr/0001-use-jwt.md", "README.md"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        let brief = ArchitectureBrief::collect(dir.path(), &files);
+
+        assert_eq!(
+            brief.to_string(),
+            "### README.md\n#### Kit\nAuth toolkit.\n\n### docs/adr/0001-use-jwt.md\n#### 1. Use JWT\n##### Decision\nSessions are stateless JWTs."
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Canonical form of anchor content
+//!
+//! Doc-side change detection hashes anchor content to notice human edits.
+//! Editors and formatters rewrap paragraphs, strip trailing whitespace, and
+//! swap bullet markers without changing what the documentation says, so the
+//! content is canonicalized before hashing:
+//!
+//! - Line endings and trailing whitespace are normalized, runs of blank lines
+//!   collapsed, and leading/trailing blank lines removed
+//! - Paragraphs and list items are joined onto one line (any wrap width
+//!   hashes the same) with runs of spaces collapsed
+//! - Bullet markers (`*`, `+`, `-`) become `-` and ordered list numbers
+//!   become `1.`
+//!
+//! Fenced code blocks are kept as they are, apart from trailing whitespace.
+
+use sha2::{Digest, Sha256};
+
+/// Canonical form of markdown content
+pub fn canonicalize(content: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    // Paragraph or list item still collecting wrapped lines
+    let mut open: Option<String> = None;
+    let mut fence: Option<String> = None;
+    let mut blank = false;
+
+    for line in content.lines() {
+        let line = line.trim_end();
+        if let Some(marker) = &fence {
+            if line.trim_start().starts_with(marker.as_str()) {
+                fence = None;
+            }
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let text = line.trim_start();
+        if text.is_empty() {
+            lines.extend(open.take().map(close));
+            blank = true;
+            continue;
+        }
+        let starts_block = fence_marker(text).is_some() || is_block_start(text);
+        let item = list_item(line);
+        if open.is_some() && (starts_block || item.is_some()) {
+            lines.extend(open.take().map(close));
+        }
+        if blank && !lines.is_empty() {
+            lines.push(String::new());
+        }
+        blank = false;
+
+        if let Some(marker) = fence_marker(text) {
+            fence = Some(marker);
+            lines.push(text.to_string());
+        } else if let Some(item) = item {
+            open = Some(item);
+        } else if starts_block {
+            lines.push(collapse(text));
+        } else {
+            match &mut open {
+                Some(paragraph) => {
+                    paragraph.push(' ');
+                    paragraph.push_str(&collapse(text));
+                }
+                None => open = Some(collapse(text)),
+            }
+        }
+    }
+    lines.extend(open.map(close));
+    // Blank lines an unclosed fence ran into
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+/// SHA256 of the canonical form of anchor content, as lowercase hex
+pub fn anchor_content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(canonicalize(content).as_bytes()))
+}
+
+/// A finished paragraph or list item
+///
+/// Joined lines may read as a thematic break (`* - -` is `- - -`), which is
+/// then written as one, without indentation.
+fn close(block: String) -> String {
+    match is_thematic_break(&block) {
+        true => block.trim_start().to_string(),
+        false => block,
+    }
+}
+
+/// Opening fence (three or more backticks or tildes) of a code block
+fn fence_marker(text: &str) -> Option<String> {
+    let marker = text.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = text.chars().take_while(|c| *c == marker).count();
+    (length >= 3).then(|| marker.to_string().repeat(length))
+}
+
+/// Lines that are blocks of their own and never wrapped into a paragraph
+fn is_block_start(text: &str) -> bool {
+    let heading = text.trim_start_matches('#');
+    (heading.len() < text.len() && (heading.is_empty() || heading.starts_with(char::is_whitespace)))
+        || text.starts_with(['>', '|', '<'])
+        || is_thematic_break(text)
+}
+
+/// `---`, `***`, `_ _ _`, ...
+fn is_thematic_break(text: &str) -> bool {
+    let marks: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && ['-', '*', '_'].iter().any(|m| marks.iter().all(|c| c == m))
+}
+
+/// A list item with its indentation and marker normalized
+fn list_item(line: &str) -> Option<String> {
+    let text = line.trim_start();
+    let indent = line.len() - text.len();
+    let (marker, rest) = match text.strip_prefix(['-', '*', '+']) {
+        Some(rest) => ("-", rest),
+        None => {
+            let digits = text.chars().take_while(char::is_ascii_digit).count();
+            if digits == 0 {
+                return None;
+            }
+            ("1.", text[digits..].strip_prefix(['.', ')'])?)
+        }
+    };
+    // A bare marker is an empty item, which wrapped lines may continue
+    if !(rest.is_empty() || rest.starts_with(char::is_whitespace)) || is_thematic_break(text) {
+        return None;
+    }
+    let item = format!("{}{} {}", " ".repeat(indent), marker, collapse(rest));
+    Some(item.trim_end().to_string())
+}
+
+/// Collapse runs of whitespace into single spaces
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reformatting_hashes_the_same() {
+        let original = "Signs a user in and returns\na session token.\n\n* first\n* second item\n  wrapped\n\n1. one\n2. two\n\n```ts\nlogin(  'a' );\n```\n";
+        let reformatted = "Signs a user in and   returns a session token.   \r\n\n\n\n- first\n- second item wrapped\n\n1. one\n1. two\n\n```ts\nlogin(  'a' );   \n```";
+
+        assert_eq!(canonicalize(original), canonicalize(reformatted));
+        assert_eq!(
+            anchor_content_hash(original),
+            anchor_content_hash(reformatted)
+        );
+        assert_eq!(
+            canonicalize(original),
+            "Signs a user in and returns a session token.\n\n- first\n- second item wrapped\n\n1. one\n1. two\n\n```ts\nlogin(  'a' );\n```"
+        );
+    }
+
+    #[test]
+    fn test_edits_change_the_hash() {
+        let original = "# Login\nSigns in.\n\n```ts\nlogin('a');\n```";
+        for edited in [
+            "# Login\nSigns a user in.\n\n```ts\nlogin('a');\n```",
+            "# Login\nSigns in.\n\n```ts\nlogin('b');\n```",
+            "## Login\nSigns in.\n\n```ts\nlogin('a');\n```",
+            "# Login\n\n- Signs in.\n\n```ts\nlogin('a');\n```",
+        ] {
+            assert_ne!(anchor_content_hash(original), anchor_content_hash(edited));
+        }
+        // Code indentation is significant
+        assert_ne!(
+            canonicalize("```\nif a:\n    b\n```"),
+            canonicalize("```\nif a:\nb\n```")
+        );
+    }
+
+    #[test]
+    fn test_canonical_form_is_stable() {
+        for content in [
+            // Bare markers are empty items, wrapped or not
+            "* \r\nfirst",
+            "*\u{a0}first",
+            // Joined lines that read as a thematic break
+            " * - -",
+            " - -\n  --",
+            // Blank lines an unclosed fence runs into
+            "```\ncode\n\n",
+        ] {
+            let canonical = canonicalize(content);
+            assert_eq!(canonicalize(&canonical), canonical, "{:?}", content);
+        }
+    }
+}
@@ -1,19 +1,24 @@
 //! File discovery module for scanning codebases
 //!
 //! This module provides functionality to discover and categorize files in a directory tree.
-//! It can find TypeScript/JavaScript source files and Markdown documentation files while
+//! It can find TypeScript/JavaScript, Python, and Rust source files and Markdown documentation files while
 //! respecting .gitignore rules and providing flexible configuration options.
+//! On Windows the tree is walked through an extended-length root so deep paths
+//! (nested `node_modules`) and UNC shares work; discovered paths keep the form
+//! of the given root.
 
+use crate::paths::extended_length;
 use ignore::{Walk, WalkBuilder};
 use std::ffi::OsStr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Represents a discovered file in the codebase
 #[derive(Debug, Clone)]
 pub enum DiscoveredFile {
     /// Markdown documentation file (.md, .mdx)
     Markdown(PathBuf),
-    /// TypeScript/JavaScript source file (.ts, .tsx, .js, .jsx, .mts, .cts)
+    /// TypeScript/JavaScript, Vue, Svelte, Python, Rust, Go, or Java source file
+    /// (.ts, .tsx, .js, .jsx, .mts, .cts, .vue, .svelte, .py, .pyi, .rs, .go, .java)
     Source(PathBuf),
 }
 
@@ -98,11 +103,43 @@ impl DiscoveryConfig {
         self.custom_markdown_extensions.push(ext.into());
         self
     }
+
+    /// Classify a file by its extension, as discovery does
+    ///
+    /// Returns `None` for files discovery skips (lockfiles, assets, files
+    /// without an extension).
+    pub fn classify(&self, path: impl Into<PathBuf>) -> Option<DiscoveredFile> {
+        let path = path.into();
+        let extension = path.extension().and_then(OsStr::to_str)?;
+        if self.is_markdown_extension(extension) {
+            Some(DiscoveredFile::Markdown(path))
+        } else if self.is_source_extension(extension) {
+            Some(DiscoveredFile::Source(path))
+        } else {
+            None
+        }
+    }
+
+    /// Check if a file extension is a source file
+    fn is_source_extension(&self, ext: &str) -> bool {
+        matches!(ext, "ts" | "tsx" | "js" | "jsx" | "mts" | "cts" | "mjs" | "cjs" | "py" | "pyi" | "rs" | "go" | "java" | "vue" | "svelte")
+            || self.custom_source_extensions.iter().any(|e| e == ext)
+    }
+
+    /// Check if a file extension is a markdown file
+    fn is_markdown_extension(&self, ext: &str) -> bool {
+        matches!(ext, "md" | "mdx")
+            || self.custom_markdown_extensions.iter().any(|e| e == ext)
+    }
 }
 
 /// File discovery iterator for traversing a codebase
 pub struct FileCollector {
     walker: Walk,
+    /// Root as given, which discovered paths start with
+    root: PathBuf,
+    /// Root actually walked (extended-length on Windows)
+    walk_root: PathBuf,
     config: DiscoveryConfig,
     stats: DiscoveryStats,
 }
@@ -135,7 +172,9 @@ impl FileCollector {
 
     /// Create a new file collector with custom configuration
     pub fn with_config(root: impl Into<PathBuf>, config: DiscoveryConfig) -> Self {
-        let mut builder = WalkBuilder::new(root.into());
+        let root = root.into();
+        let walk_root = extended_length(&root);
+        let mut builder = WalkBuilder::new(&walk_root);
 
         builder
             .hidden(!config.include_hidden)
@@ -151,6 +190,8 @@ impl FileCollector {
 
         Self {
             walker,
+            root,
+            walk_root,
             config,
             stats: DiscoveryStats::default(),
         }
@@ -161,16 +202,13 @@ impl FileCollector {
         &self.stats
     }
 
-    /// Check if a file extension is a source file
-    fn is_source_extension(&self, ext: &str) -> bool {
-        matches!(ext, "ts" | "tsx" | "js" | "jsx" | "mts" | "cts" | "mjs" | "cjs")
-            || self.config.custom_source_extensions.iter().any(|e| e == ext)
-    }
-
-    /// Check if a file extension is a markdown file
-    fn is_markdown_extension(&self, ext: &str) -> bool {
-        matches!(ext, "md" | "mdx")
-            || self.config.custom_markdown_extensions.iter().any(|e| e == ext)
+    /// Walked path in the form of the given root
+    fn original_path(&self, path: &Path) -> PathBuf {
+        match path.strip_prefix(&self.walk_root) {
+            Ok(relative) if !relative.as_os_str().is_empty() => self.root.join(relative),
+            Ok(_) => self.root.clone(),
+            Err(_) => path.to_path_buf(),
+        }
     }
 }
 
@@ -190,19 +228,17 @@ impl Iterator for FileCollector {
                         continue;
                     }
 
-                    // Get file extension
-                    let extension = match path.extension().and_then(OsStr::to_str) {
-                        Some(ext) => ext,
-                        None => continue,
-                    };
-
                     // Classify and return the file
-                    if self.is_markdown_extension(extension) {
-                        self.stats.markdown_files += 1;
-                        return Some(DiscoveredFile::Markdown(path.to_path_buf()));
-                    } else if self.is_source_extension(extension) {
-                        self.stats.source_files += 1;
-                        return Some(DiscoveredFile::Source(path.to_path_buf()));
+                    match self.config.classify(self.original_path(path)) {
+                        Some(file @ DiscoveredFile::Markdown(_)) => {
+                            self.stats.markdown_files += 1;
+                            return Some(file);
+                        }
+                        Some(file @ DiscoveredFile::Source(_)) => {
+                            self.stats.source_files += 1;
+                            return Some(file);
+                        }
+                        None => continue,
                     }
                 }
                 Err(err) => {
@@ -286,12 +322,37 @@ mod tests {
         assert!(ts_file.is_source());
     }
 
+    #[test]
+    fn test_classify() {
+        let config = DiscoveryConfig::new().add_source_extension("astro");
+        assert!(config.classify("docs/guide.mdx").unwrap().is_markdown());
+        assert!(config.classify("src/main.rs").unwrap().is_source());
+        assert!(config.classify("src/Page.astro").unwrap().is_source());
+        assert!(config.classify("Cargo.lock").is_none());
+        assert!(config.classify(".gitignore").is_none());
+    }
+
     #[test]
     fn test_discover_files_function() {
         let config = DiscoveryConfig::new();
         let result = discover_files(".", config);
 
         // We should find at least this Rust file
-        assert!(result.source_files.len() > 0 || result.markdown_files.len() > 0);
+        assert!(!result.source_files.is_empty() || !result.markdown_files.is_empty());
+    }
+
+    #[test]
+    fn test_discover_deep_paths() {
+        // Longer than Windows' MAX_PATH (260 characters)
+        let dir = tempfile::tempdir().unwrap();
+        let deep = (0..25).fold(dir.path().to_path_buf(), |path, _| {
+            path.join("node_modules")
+        });
+        std::fs::create_dir_all(&deep).unwrap();
+        std::fs::write(deep.join("index.ts"), "export const a = 1;\n").unwrap();
+        assert!(deep.join("index.ts").as_os_str().len() > 260);
+
+        let result = discover_files(dir.path(), DiscoveryConfig::new().respect_gitignore(false));
+        assert_eq!(result.source_files, [deep.join("index.ts")]);
     }
 }
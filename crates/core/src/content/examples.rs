@@ -0,0 +1,396 @@
+//! Example code validation for anchor content
+//!
+//! Documentation examples rot quietly: a renamed export or a moved file leaves
+//! a snippet that no longer compiles, but the anchor itself still looks valid.
+//! This module pulls fenced TypeScript/JavaScript blocks out of anchors and
+//! checks them against the current project:
+//!
+//! - Syntax: the block must parse with Oxc
+//! - Imports: relative imports must resolve to a project file (and named
+//!   imports must be exported by it); bare imports must name the project
+//!   itself, a declared dependency, an installed package, or a Node builtin
+//!
+//! Blocks tagged `ignore` or `no-check` in their info string (e.g.
+//! ```` ```ts ignore ````) are skipped.
+
+use super::extractor::{
+    build_line_map, byte_offset_to_line, markdown_events, parse_sintesi_end, parse_sintesi_start,
+};
+use crate::ast::AstAnalyzerInternal;
+use crate::graph::{module_candidates, normalize_path};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{ImportDeclarationSpecifier, Statement};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use pulldown_cmark::{CodeBlockKind, Event, Tag, TagEnd};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Node.js builtin modules that may be imported without the `node:` prefix
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "buffer",
+    "child_process",
+    "crypto",
+    "events",
+    "fs",
+    "http",
+    "https",
+    "net",
+    "os",
+    "path",
+    "process",
+    "readline",
+    "stream",
+    "timers",
+    "url",
+    "util",
+    "worker_threads",
+    "zlib",
+];
+
+/// A fenced code block found inside an anchor
+#[derive(Debug, Clone)]
+pub struct CodeExample {
+    /// ID of the anchor containing the block
+    pub anchor_id: String,
+    /// Language from the fence info string (`ts`, `tsx`, `js`, ...)
+    pub lang: String,
+    /// Line of the opening fence in the markdown file (0-indexed)
+    pub line: usize,
+    /// Code inside the fence
+    pub code: String,
+}
+
+/// Extract checkable code blocks from the anchors of a markdown document
+pub fn extract_code_examples(content: &str) -> Vec<CodeExample> {
+    let line_map = build_line_map(content);
+    let mut examples = Vec::new();
+    let mut open_anchors: Vec<String> = Vec::new();
+    let mut current: Option<CodeExample> = None;
+
+    for (event, range) in markdown_events(content) {
+        match event {
+            Event::Html(html) => {
+                if let Some((id, _)) = parse_sintesi_start(&html) {
+                    open_anchors.push(id);
+                } else if let Some(id) = parse_sintesi_end(&html) {
+                    open_anchors.retain(|open| open != &id);
+                }
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                let Some(anchor_id) = open_anchors.last() else {
+                    continue;
+                };
+                let mut words = info.split_whitespace();
+                let lang = words.next().unwrap_or("").to_lowercase();
+                let skipped = words.any(|w| w == "ignore" || w == "no-check");
+                if source_type_for(&lang).is_some() && !skipped {
+                    current = Some(CodeExample {
+                        anchor_id: anchor_id.clone(),
+                        lang,
+                        line: byte_offset_to_line(&line_map, range.start),
+                        code: String::new(),
+                    });
+                }
+            }
+            Event::Text(text) => {
+                if let Some(example) = current.as_mut() {
+                    example.code.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                examples.extend(current.take());
+            }
+            _ => {}
+        }
+    }
+
+    examples
+}
+
+/// Validates anchor code examples against a project on disk
+pub struct ExampleValidator {
+    project_root: PathBuf,
+    analyzer: AstAnalyzerInternal,
+    dependencies: HashSet<String>,
+}
+
+impl ExampleValidator {
+    /// Create a validator for the project at `project_root`
+    ///
+    /// The root `package.json` (if any) supplies the project's own package
+    /// name and its declared dependencies.
+    pub fn new(project_root: impl Into<PathBuf>) -> Self {
+        let project_root = project_root.into();
+        let dependencies = read_package_names(&project_root.join("package.json"));
+        Self {
+            project_root,
+            analyzer: AstAnalyzerInternal::new(),
+            dependencies,
+        }
+    }
+
+    /// Validate all code examples in a markdown document
+    ///
+    /// # Arguments
+    /// * `markdown_path` - Path of the markdown file relative to the project
+    ///   root (relative imports in examples resolve against its directory)
+    /// * `content` - Markdown content
+    ///
+    /// # Returns
+    /// Validation error messages, empty if every example checks out
+    pub fn validate(&self, markdown_path: &str, content: &str) -> Vec<String> {
+        let base_dir = Path::new(markdown_path)
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+
+        extract_code_examples(content)
+            .iter()
+            .flat_map(|example| {
+                self.validate_example(&base_dir, example)
+                    .into_iter()
+                    .map(move |problem| {
+                        format!(
+                            "Invalid code example at line {} in anchor id=\"{}\": {}",
+                            example.line + 1,
+                            example.anchor_id,
+                            problem
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    fn validate_example(&self, base_dir: &Path, example: &CodeExample) -> Vec<String> {
+        let source_type = source_type_for(&example.lang).unwrap_or_default();
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, &example.code, source_type).parse();
+
+        if !ret.errors.is_empty() {
+            return ret
+                .errors
+                .iter()
+                .map(|e| format!("Syntax error: {}", e))
+                .collect();
+        }
+
+        let mut problems = Vec::new();
+        for statement in &ret.program.body {
+            let Statement::ImportDeclaration(decl) = statement else {
+                continue;
+            };
+            let specifier = decl.source.value.as_str();
+
+            if !specifier.starts_with('.') {
+                if !self.is_known_package(specifier) {
+                    problems.push(format!("Cannot resolve package '{}'", specifier));
+                }
+                continue;
+            }
+
+            let Some(module) = self.resolve_relative(base_dir, specifier) else {
+                problems.push(format!("Cannot resolve module '{}'", specifier));
+                continue;
+            };
+
+            let named: Vec<&str> = decl
+                .specifiers
+                .iter()
+                .flatten()
+                .filter_map(|s| match s {
+                    ImportDeclarationSpecifier::ImportSpecifier(s) => Some(s.imported.name()),
+                    _ => None,
+                })
+                .map(|name| name.as_str())
+                .collect();
+            if let Some(exports) = self.exported_names(&module) {
+                for name in named.into_iter().filter(|n| !exports.contains(*n)) {
+                    problems.push(format!("'{}' is not exported by '{}'", name, specifier));
+                }
+            }
+        }
+
+        problems
+    }
+
+    fn is_known_package(&self, specifier: &str) -> bool {
+        if specifier.starts_with("node:") {
+            return true;
+        }
+        let package = package_name(specifier);
+        NODE_BUILTINS.contains(&package)
+            || self.dependencies.contains(package)
+            || self
+                .project_root
+                .join("node_modules")
+                .join(package)
+                .exists()
+    }
+
+    fn resolve_relative(&self, base_dir: &Path, specifier: &str) -> Option<PathBuf> {
+        let resolved = normalize_path(&base_dir.join(specifier));
+        module_candidates(&resolved)
+            .into_iter()
+            .find(|candidate| self.project_root.join(candidate).is_file())
+            .map(|candidate| self.project_root.join(candidate))
+    }
+
+    /// Names exported by a module, or `None` when they cannot be determined
+    /// statically (re-exports) and named imports should not be checked
+    fn exported_names(&self, module: &Path) -> Option<HashSet<String>> {
+        let content = fs::read_to_string(module).ok()?;
+        if reexport_regex().is_match(&content) {
+            return None;
+        }
+
+        let result = self
+            .analyzer
+            .analyze_file(&module.to_string_lossy(), &content);
+        Some(
+            result
+                .symbols
+                .into_iter()
+                .filter(|s| s.is_exported)
+                .map(|s| s.name)
+                .collect(),
+        )
+    }
+}
+
+/// Validate the code examples of a markdown document against a project
+pub fn validate_code_examples(
+    project_root: impl Into<PathBuf>,
+    markdown_path: &str,
+    content: &str,
+) -> Vec<String> {
+    ExampleValidator::new(project_root).validate(markdown_path, content)
+}
+
+fn source_type_for(lang: &str) -> Option<SourceType> {
+    match lang {
+        "ts" | "typescript" => Some(SourceType::ts()),
+        "tsx" => Some(SourceType::tsx()),
+        "js" | "javascript" | "mjs" => Some(SourceType::mjs()),
+        "jsx" => Some(SourceType::jsx()),
+        _ => None,
+    }
+}
+
+/// Package portion of a bare specifier (`@scope/pkg/sub` -> `@scope/pkg`)
+fn package_name(specifier: &str) -> &str {
+    let segments = if specifier.starts_with('@') { 2 } else { 1 };
+    match specifier.match_indices('/').nth(segments - 1) {
+        Some((idx, _)) => &specifier[..idx],
+        None => specifier,
+    }
+}
+
+/// The project's own name plus every declared dependency
+fn read_package_names(package_json: &Path) -> HashSet<String> {
+    let Some(manifest) = fs::read_to_string(package_json)
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    else {
+        return HashSet::new();
+    };
+
+    let mut names: HashSet<String> = manifest["name"]
+        .as_str()
+        .map(String::from)
+        .into_iter()
+        .collect();
+    for field in [
+        "dependencies",
+        "devDependencies",
+        "peerDependencies",
+        "optionalDependencies",
+    ] {
+        if let Some(deps) = manifest[field].as_object() {
+            names.extend(deps.keys().cloned());
+        }
+    }
+    names
+}
+
+fn reexport_regex() -> &'static Regex {
+    static REEXPORT: OnceLock<Regex> = OnceLock::new();
+    REEXPORT.get_or_init(|| {
+        Regex::new(r"export\s*(\*|\{[^}]*\}\s*from|type\s*\{[^}]*\}\s*from)").unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::write(
+            dir.path().join("src/auth.ts"),
+            "export function login(user: string): boolean { return true; }",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{ "name": "@acme/auth", "dependencies": { "zod": "^3.0.0" } }"#,
+        )
+        .unwrap();
+        dir
+    }
+
+    fn doc(example: &str) -> String {
+        format!(
+            "# Auth\n\n<!-- sintesi:start id=\"a1\" code_ref=\"src/auth.ts#login\" -->\n{}\n<!-- sintesi:end id=\"a1\" -->\n",
+            example
+        )
+    }
+
+    #[test]
+    fn test_extracts_only_examples_inside_anchors() {
+        let content = format!(
+            "```ts\noutside();\n```\n\n{}",
+            doc("```ts\nlogin('a');\n```\n\n```bash\nnpm i\n```\n\n```ts ignore\nbroken(\n```")
+        );
+        let examples = extract_code_examples(&content);
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].anchor_id, "a1");
+        assert_eq!(examples[0].code, "login('a');\n");
+        assert_eq!(examples[0].line, 7);
+    }
+
+    #[test]
+    fn test_valid_example_passes() {
+        let dir = project();
+        let content = doc(
+            "```ts\nimport { login } from '../src/auth';\nimport { readFile } from 'node:fs';\nimport { z } from 'zod';\nimport { login as l } from '@acme/auth';\nlogin('a');\n```",
+        );
+
+        let errors = validate_code_examples(dir.path(), "docs/auth.md", &content);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_reports_syntax_and_import_errors() {
+        let dir = project();
+        let content = doc(
+            "```ts\nimport { logout } from '../src/auth';\nimport { x } from '../src/missing';\nimport left from 'left-pad';\n```\n\n```js\nconst = 1;\n```",
+        );
+
+        let errors = validate_code_examples(dir.path(), "docs/auth.md", &content);
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors[0].contains("line 4 in anchor id=\"a1\""));
+        assert!(errors[0].contains("'logout' is not exported by '../src/auth'"));
+        assert!(errors[1].contains("Cannot resolve module '../src/missing'"));
+        assert!(errors[2].contains("Cannot resolve package 'left-pad'"));
+        assert!(errors[3].contains("Syntax error"));
+    }
+}
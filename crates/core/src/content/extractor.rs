@@ -13,6 +13,13 @@
 //! <!-- sintesi:end id="uuid" -->
 //! ```
 //! 
+//! ## Nested Anchors
+//!
+//! Anchors may contain other anchors, e.g. a module overview wrapping one
+//! anchor per symbol. Each anchor records its `parent` and `children`; an
+//! anchor closed while one nested inside it is still open is reported as
+//! improperly nested. A parent's content includes its children verbatim.
+//!
 //! ## Implementation Notes
 //! 
 //! This implementation uses pulldown-cmark's event-based parser:
@@ -20,24 +27,95 @@
 //! - Line numbers are 0-indexed for TypeScript compatibility
 //! - Content extraction excludes anchor lines
 //! - Comprehensive validation (duplicate IDs, nested anchors, code_ref format)
-
-use pulldown_cmark::{Event, Parser};
+//! - GFM extensions (tables, footnotes, task lists, strikethrough) are enabled,
+//!   so documents are read the way doc sites render them; e.g. anchors indented
+//!   inside a footnote definition are found
+//!
+//! ## Ignore Regions
+//!
+//! Sections that intentionally contain anchor-like text (e.g. tutorials about
+//! Sintesi itself) can be excluded from extraction and every validator:
+//!
+//! ```markdown
+//! <!-- sintesi-ignore -->
+//! <!-- sintesi:start id="example" code_ref="src/file.ts#Example" -->
+//! <!-- sintesi-ignore-end -->
+//! ```
+//!
+//! A region without `sintesi-ignore-end` runs to the end of the file.
+//!
+//! ## Code Reference Checks
+//!
+//! By default only the `code_ref` format is validated. Given a project root,
+//! the extractor can also check that each referenced file exists
+//! (`CodeRefValidation::Files`) and that the file declares or re-exports the
+//! symbol (`CodeRefValidation::Symbols`), so one pass reports both malformed
+//! anchors and dangling references.
+//!
+//! ## Large Files
+//!
+//! Files over `max_file_size` bytes (1 MiB by default) are not parsed; the
+//! result has no anchors and names the file in `skipped`.
+
+use crate::ast::reexports::resolve_reexport;
+use crate::ast::{AnalyzerOptions, AstAnalyzerInternal};
+use crate::provider::{ContentProvider, DiskProvider};
+use crate::text::{SkippedFile, DEFAULT_MAX_FILE_SIZE};
+use pulldown_cmark::{Event, Options, Parser};
+use std::ops::Range;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // Import types from the content/types module
-use super::types::{SintesiAnchor, ExtractionResult};
+use super::types::{AnchorMap, SintesiAnchor, ExtractionResult};
+
+/// How thoroughly anchor code references are checked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CodeRefValidation {
+    /// Only the `file_path#symbol_name` format
+    #[default]
+    Format,
+    /// The referenced file must exist under the project root
+    Files,
+    /// The file must also declare or re-export the symbol
+    Symbols,
+}
 
 /// Markdown extractor that finds Sintesi anchors using pulldown-cmark
 pub struct MarkdownExtractor {
-    // No regex needed - we parse proper Markdown AST
+    /// Project root code references are checked against
+    project_root: Option<PathBuf>,
+    code_refs: CodeRefValidation,
+    /// Size in bytes above which files are skipped
+    max_file_size: usize,
 }
 
 impl MarkdownExtractor {
     /// Create a new markdown extractor
     pub fn new() -> Self {
-        Self {}
+        Self {
+            project_root: None,
+            code_refs: CodeRefValidation::Format,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+
+    /// Skip files larger than `bytes` instead of parsing them
+    pub fn max_file_size(mut self, bytes: usize) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Check code references against the files (and symbols) of a project
+    pub fn validate_code_refs(
+        mut self,
+        project_root: impl Into<PathBuf>,
+        level: CodeRefValidation,
+    ) -> Self {
+        self.project_root = Some(project_root.into());
+        self.code_refs = level;
+        self
     }
 
     /// Extract anchors from a markdown file
@@ -51,16 +129,26 @@ impl MarkdownExtractor {
     pub fn extract_from_file(&self, file_path: impl AsRef<Path>, content: &str) -> ExtractionResult {
         let file_path = file_path.as_ref();
 
+        let relative = file_path.to_string_lossy().replace('\\', "/");
+        let limit = self.max_file_size;
+        if let Some(skipped) = SkippedFile::over_limit(&relative, content.len(), limit) {
+            return ExtractionResult {
+                skipped: Some(skipped),
+                ..Default::default()
+            };
+        }
+
         // Build line map for byte offset -> line number conversion
         let line_map = build_line_map(content);
 
-        let mut anchors = HashMap::new();
+        let mut anchors = AnchorMap::new();
         let mut errors = Vec::new();
-        let mut anchor_stack: HashMap<String, AnchorInProgress> = HashMap::new();
+        // Open anchors, innermost last
+        let mut anchor_stack: Vec<(String, AnchorInProgress)> = Vec::new();
         let mut seen_ids = HashSet::new();
 
         // Parse markdown into events with byte offsets
-        let parser = Parser::new(content).into_offset_iter();
+        let parser = markdown_events(content);
 
         for (event, range) in parser {
             // We only care about HTML events (comments)
@@ -82,7 +170,8 @@ impl MarkdownExtractor {
                     seen_ids.insert(id.clone());
 
                     // Validation: Check for nested anchors with same ID
-                    if anchor_stack.contains_key(&id) {
+                    let open = anchor_stack.iter().position(|(open_id, _)| *open_id == id);
+                    if open.is_some() {
                         errors.push(format!(
                             "Nested anchor with same id=\"{}\" at line {}",
                             id,
@@ -99,24 +188,39 @@ impl MarkdownExtractor {
                         ));
                     }
 
-                    anchor_stack.insert(
-                        id,
-                        AnchorInProgress {
-                            start_line: line_num,
-                            start_offset: range.end, // Content starts after this comment
-                            code_ref,
-                        },
-                    );
+                    let start_info = AnchorInProgress {
+                        start_line: line_num,
+                        start_offset: range.end, // Content starts after this comment
+                        start_tag: comment_span(html_str, &range),
+                        code_ref,
+                        parent: anchor_stack.last().map(|(open_id, _)| open_id.clone()),
+                    };
+                    match open {
+                        // The inner start replaces the outer one
+                        Some(index) => anchor_stack[index].1 = start_info,
+                        None => anchor_stack.push((id, start_info)),
+                    }
                 }
                 // Check if this is a sintesi:end comment
                 else if let Some(id) = parse_sintesi_end(html_str) {
                     let line_num = byte_offset_to_line(&line_map, range.start);
 
-                    match anchor_stack.remove(&id) {
-                        Some(start_info) => {
+                    match anchor_stack.iter().rposition(|(open_id, _)| *open_id == id) {
+                        Some(index) => {
+                            // Validation: Anchors opened inside must be closed first
+                            for (inner, _) in &anchor_stack[index + 1..] {
+                                errors.push(improper_nesting(&id, inner, line_num));
+                            }
+                            let (_, start_info) = anchor_stack.remove(index);
+
                             // Extract content between anchors (by byte offset)
                             let content_str = content[start_info.start_offset..range.start].trim();
 
+                            // Whole lines between the tags (the HTML event may stop
+                            // short of a CRLF line ending)
+                            let content_start =
+                                line_start(&line_map, start_info.start_line + 1, content.len());
+
                             let anchor = SintesiAnchor {
                                 id: id.clone(),
                                 code_ref: Some(start_info.code_ref),
@@ -126,6 +230,11 @@ impl MarkdownExtractor {
                                 // Normalize line endings for cross-platform compatibility
                                 // This ensures hash consistency between Windows (\r\n) and Unix (\n)
                                 content: content_str.replace("\r\n", "\n"),
+                                parent: start_info.parent,
+                                children: Vec::new(),
+                                start_tag_span: start_info.start_tag,
+                                content_span: content_start..line_map[line_num].max(content_start),
+                                end_tag_span: comment_span(html_str, &range),
                             };
 
                             anchors.insert(id, anchor);
@@ -142,6 +251,21 @@ impl MarkdownExtractor {
             }
         }
 
+        // Link parents to their children, in document order
+        let mut nested: Vec<(String, usize, String)> = anchors
+            .values()
+            .filter_map(|anchor: &SintesiAnchor| {
+                let parent = anchor.parent.clone()?;
+                Some((parent, anchor.start_line, anchor.id.clone()))
+            })
+            .collect();
+        nested.sort();
+        for (parent, _, child) in nested {
+            if let Some(parent) = anchors.get_mut(&parent) {
+                parent.children.push(child);
+            }
+        }
+
         // Check for unclosed anchors
         if !anchor_stack.is_empty() {
             for (id, start_info) in anchor_stack {
@@ -153,10 +277,15 @@ impl MarkdownExtractor {
             }
         }
 
+        if let Some(root) = &self.project_root {
+            errors.extend(self.code_ref_errors(root, &anchors));
+        }
+
         ExtractionResult {
             anchor_count: anchors.len(),
             anchors,
             errors,
+            skipped: None,
         }
     }
 
@@ -168,9 +297,10 @@ impl MarkdownExtractor {
         let line_map = build_line_map(content);
         let mut errors = Vec::new();
         let mut seen_ids = HashSet::new();
-        let mut anchor_stack: HashMap<String, usize> = HashMap::new();
+        // Open anchors with their start lines, innermost last
+        let mut anchor_stack: Vec<(String, usize)> = Vec::new();
 
-        let parser = Parser::new(content).into_offset_iter();
+        let parser = markdown_events(content);
 
         for (event, range) in parser {
             if let Event::Html(html) = event {
@@ -190,14 +320,17 @@ impl MarkdownExtractor {
                     seen_ids.insert(id.clone());
 
                     // Check if already open
-                    if anchor_stack.contains_key(&id) {
-                        errors.push(format!(
-                            "Nested anchor with same id=\"{}\" at line {}",
-                            id,
-                            line_num + 1
-                        ));
+                    match anchor_stack.iter().position(|(open_id, _)| *open_id == id) {
+                        Some(index) => {
+                            errors.push(format!(
+                                "Nested anchor with same id=\"{}\" at line {}",
+                                id,
+                                line_num + 1
+                            ));
+                            anchor_stack[index].1 = line_num;
+                        }
+                        None => anchor_stack.push((id.clone(), line_num)),
                     }
-                    anchor_stack.insert(id.clone(), line_num);
 
                     // Validate code_ref format
                     if !code_ref.contains('#') {
@@ -210,14 +343,20 @@ impl MarkdownExtractor {
                 }
                 // Check for sintesi:end
                 else if let Some(id) = parse_sintesi_end(html_str) {
-                    if !anchor_stack.contains_key(&id) {
-                        errors.push(format!(
-                            "Found sintesi:end without matching sintesi:start for id=\"{}\" at line {}",
-                            id,
-                            line_num + 1
-                        ));
-                    } else {
-                        anchor_stack.remove(&id);
+                    match anchor_stack.iter().rposition(|(open_id, _)| *open_id == id) {
+                        Some(index) => {
+                            for (inner, _) in &anchor_stack[index + 1..] {
+                                errors.push(improper_nesting(&id, inner, line_num));
+                            }
+                            anchor_stack.remove(index);
+                        }
+                        None => {
+                            errors.push(format!(
+                                "Found sintesi:end without matching sintesi:start for id=\"{}\" at line {}",
+                                id,
+                                line_num + 1
+                            ));
+                        }
                     }
                 }
             }
@@ -235,6 +374,66 @@ impl MarkdownExtractor {
         errors
     }
 
+    /// Errors for code references that don't resolve in the project, in
+    /// document order
+    fn code_ref_errors(
+        &self,
+        root: &Path,
+        anchors: &AnchorMap,
+    ) -> Vec<String> {
+        if self.code_refs == CodeRefValidation::Format {
+            return Vec::new();
+        }
+        let provider = DiskProvider::new(root);
+        let analyzer = AstAnalyzerInternal::with_options(AnalyzerOptions::drift_keys());
+        // Symbol names (or `None` for a wholly ignored file) by file
+        let mut symbols: HashMap<&str, Option<HashSet<String>>> = HashMap::new();
+
+        let mut anchors: Vec<&SintesiAnchor> = anchors.values().collect();
+        anchors.sort_by_key(|anchor| anchor.start_line);
+        let mut errors = Vec::new();
+        for anchor in anchors {
+            let code_ref = anchor.code_ref.as_deref().and_then(|r| r.split_once('#'));
+            let Some((file, name)) = code_ref else {
+                continue;
+            };
+            if !root.join(file).is_file() {
+                errors.push(format!(
+                    "Anchor id=\"{}\" at line {}: code_ref file \"{}\" not found",
+                    anchor.id,
+                    anchor.start_line + 1,
+                    file
+                ));
+                continue;
+            }
+            if self.code_refs != CodeRefValidation::Symbols {
+                continue;
+            }
+
+            let names = symbols.entry(file).or_insert_with(|| {
+                let content = provider.read(Path::new(file)).unwrap_or_default();
+                let result = analyzer.analyze_file(file, &content);
+                if result.ignored.iter().any(|item| item.symbol_name.is_none()) {
+                    return None;
+                }
+                let ignored = result.ignored.into_iter().filter_map(|item| item.symbol_name);
+                Some(result.symbols.into_iter().map(|s| s.name).chain(ignored).collect())
+            });
+            let found = names.as_ref().is_none_or(|names| names.contains(name))
+                || resolve_reexport(&provider, file, name).is_some();
+            if !found {
+                errors.push(format!(
+                    "Anchor id=\"{}\" at line {}: symbol \"{}\" not found in {}",
+                    anchor.id,
+                    anchor.start_line + 1,
+                    name,
+                    file
+                ));
+            }
+        }
+        errors
+    }
+
     /// Parse the code_ref field into file path and symbol name
     pub fn parse_code_ref(&self, code_ref: &str) -> Result<(String, String), String> {
         let parts: Vec<&str> = code_ref.split('#').collect();
@@ -261,11 +460,38 @@ impl Default for MarkdownExtractor {
 struct AnchorInProgress {
     start_line: usize,
     start_offset: usize, // Byte offset where content starts
+    start_tag: Range<usize>, // Byte range of the start comment
     code_ref: String,
+    parent: Option<String>, // Innermost anchor open at the start
+}
+
+/// Byte offset where a line starts (`len` past the last line)
+fn line_start(line_map: &[usize], line: usize, len: usize) -> usize {
+    line_map.get(line).copied().unwrap_or(len)
+}
+
+/// Byte range of the comment in an HTML event spanning `range`
+///
+/// Block HTML events cover whole lines (indentation and line ending
+/// included); the span is narrowed to the comment itself.
+fn comment_span(html: &str, range: &Range<usize>) -> Range<usize> {
+    let start = html.find("<!--").unwrap_or(0);
+    let end = html.rfind("-->").map_or(html.len(), |end| end + 3);
+    range.start + start..range.start + end
+}
+
+/// Error for an anchor closed while an anchor nested inside it is still open
+fn improper_nesting(id: &str, inner: &str, line_num: usize) -> String {
+    format!(
+        "Improperly nested anchors: id=\"{}\" closed at line {} while nested id=\"{}\" is still open",
+        id,
+        line_num + 1,
+        inner
+    )
 }
 
 /// Build a map of byte offsets to line numbers (0-indexed)
-fn build_line_map(content: &str) -> Vec<usize> {
+pub(super) fn build_line_map(content: &str) -> Vec<usize> {
     let mut line_starts = vec![0];
 
     for (idx, ch) in content.char_indices() {
@@ -278,7 +504,7 @@ fn build_line_map(content: &str) -> Vec<usize> {
 }
 
 /// Convert a byte offset to a line number (0-indexed)
-fn byte_offset_to_line(line_map: &[usize], offset: usize) -> usize {
+pub(super) fn byte_offset_to_line(line_map: &[usize], offset: usize) -> usize {
     // Binary search for the line containing this offset
     match line_map.binary_search(&offset) {
         Ok(line) => line,
@@ -286,9 +512,57 @@ fn byte_offset_to_line(line_map: &[usize], offset: usize) -> usize {
     }
 }
 
+/// GitHub Flavored Markdown extensions used when parsing documents
+pub(super) fn markdown_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_STRIKETHROUGH
+}
+
+/// Parse markdown into events with byte offsets, skipping ignore regions
+///
+/// Events between `<!-- sintesi-ignore -->` and `<!-- sintesi-ignore-end -->`
+/// (and the markers themselves) are dropped. Markers are block-level HTML, so
+/// skipped events are always whole blocks.
+pub(super) fn markdown_events(content: &str) -> impl Iterator<Item = (Event<'_>, Range<usize>)> {
+    let mut ignoring = false;
+    Parser::new_ext(content, markdown_options())
+        .into_offset_iter()
+        .filter(move |(event, _)| {
+            if let Event::Html(html) = event {
+                match html_comment(html) {
+                    Some("sintesi-ignore-end") => {
+                        ignoring = false;
+                        return false;
+                    }
+                    Some(inner) if is_ignore_start(inner) => {
+                        ignoring = true;
+                        return false;
+                    }
+                    _ => {}
+                }
+            }
+            !ignoring
+        })
+}
+
+/// Check for `sintesi-ignore`, optionally followed by a reason
+fn is_ignore_start(inner: &str) -> bool {
+    inner
+        .strip_prefix("sintesi-ignore")
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with([':', ' ']))
+}
+
+/// Inner text of a single HTML comment
+fn html_comment(html: &str) -> Option<&str> {
+    let inner = html.trim().strip_prefix("<!--")?.strip_suffix("-->")?;
+    Some(inner.trim())
+}
+
 /// Parse a sintesi:start HTML comment
 /// Returns (id, code_ref) if valid
-fn parse_sintesi_start(html: &str) -> Option<(String, String)> {
+pub fn parse_sintesi_start(html: &str) -> Option<(String, String)> {
     // Look for: <!-- sintesi:start id="..." code_ref="..." -->
     let html = html.trim();
 
@@ -311,7 +585,7 @@ fn parse_sintesi_start(html: &str) -> Option<(String, String)> {
 
 /// Parse a sintesi:end HTML comment
 /// Returns id if valid
-fn parse_sintesi_end(html: &str) -> Option<String> {
+pub fn parse_sintesi_end(html: &str) -> Option<String> {
     // Look for: <!-- sintesi:end id="..." -->
     let html = html.trim();
 
@@ -350,8 +624,123 @@ fn extract_attribute(text: &str, attr_name: &str) -> Option<String> {
         .map(|m| m.as_str().to_string())
 }
 
+/// Extract anchors from a markdown file read through a content provider
+///
+/// Read failures are reported in `errors` rather than aborting.
+pub fn extract_anchors_from(provider: &dyn ContentProvider, file_path: &Path) -> ExtractionResult {
+    match provider.read(file_path) {
+        Ok(content) => extract_anchors(file_path, &content),
+        Err(e) => {
+            let relative = file_path.to_string_lossy().replace('\\', "/");
+            match SkippedFile::from_error(&relative, &e) {
+                Some(skipped) => ExtractionResult {
+                    skipped: Some(skipped),
+                    ..Default::default()
+                },
+                None => ExtractionResult {
+                    errors: vec![format!("{}: {}", file_path.display(), e)],
+                    ..Default::default()
+                },
+            }
+        }
+    }
+}
+
 /// Convenience function to extract anchors from a markdown file
 pub fn extract_anchors(file_path: impl AsRef<Path>, content: &str) -> ExtractionResult {
     let extractor = MarkdownExtractor::new();
     extractor.extract_from_file(file_path, content)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_improper_nesting_is_reported() {
+        let doc = "<!-- sintesi:start id=\"a\" code_ref=\"src/a.ts#a\" -->\n<!-- sintesi:start id=\"b\" code_ref=\"src/a.ts#b\" -->\n<!-- sintesi:end id=\"a\" -->\n<!-- sintesi:end id=\"b\" -->\n";
+        let expected = "Improperly nested anchors: id=\"a\" closed at line 3 while nested id=\"b\" is still open";
+
+        assert_eq!(extract_anchors("", doc).errors, [expected]);
+        assert_eq!(MarkdownExtractor::new().validate(doc), [expected]);
+    }
+
+    #[test]
+    fn test_gfm_extensions() {
+        let doc = "| Name | Status |\n|------|--------|\n| a    | ~~old~~ |\n\n- [x] done\n\nSee the note[^1].\n\n[^1]: Details\n    <!-- sintesi:start id=\"note\" code_ref=\"src/a.ts#a\" -->\n    Footnote body\n    <!-- sintesi:end id=\"note\" -->\n";
+
+        let result = extract_anchors("", doc);
+
+        assert!(result.errors.is_empty());
+        let anchor = &result.anchors["note"];
+        assert_eq!((anchor.start_line, anchor.end_line), (9, 11));
+        assert_eq!(anchor.content.trim(), "Footnote body");
+    }
+    #[test]
+    fn test_anchor_byte_spans() {
+        let doc = "# Café ☕\r\n  <!-- sintesi:start id=\"a\" code_ref=\"src/a.ts#a\" -->\r\nÜber naïve\r\n<!-- sintesi:end id=\"a\" -->\r\n";
+
+        let anchor = &extract_anchors("", doc).anchors["a"];
+
+        assert_eq!(
+            &doc[anchor.start_tag_span.clone()],
+            "<!-- sintesi:start id=\"a\" code_ref=\"src/a.ts#a\" -->"
+        );
+        assert_eq!(&doc[anchor.content_span.clone()], "Über naïve\r\n");
+        assert_eq!(
+            &doc[anchor.end_tag_span.clone()],
+            "<!-- sintesi:end id=\"a\" -->"
+        );
+    }
+
+    #[test]
+    fn test_max_file_size() {
+        let doc = "<!-- sintesi:start id=\"a\" code_ref=\"src/a.ts#a\" -->\n\
+                   <!-- sintesi:end id=\"a\" -->\n";
+        let result = MarkdownExtractor::new()
+            .max_file_size(16)
+            .extract_from_file("docs/api.md", doc);
+        assert_eq!(result.anchor_count, 0);
+        assert_eq!(result.skipped.unwrap().reason.as_str(), "too-large");
+        assert_eq!(extract_anchors("docs/api.md", doc).anchor_count, 1);
+    }
+
+    #[test]
+    fn test_code_ref_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/auth.ts"), "export function login() {}\n").unwrap();
+        std::fs::write(dir.path().join("src/index.ts"), "export * from './auth';\n").unwrap();
+        let doc: String = [
+            ("ok", "src/auth.ts#login"),
+            ("barrel", "src/index.ts#login"),
+            ("gone", "src/old.ts#login"),
+            ("renamed", "src/auth.ts#signIn"),
+        ]
+        .iter()
+        .map(|(id, code_ref)| {
+            format!(
+                "<!-- sintesi:start id=\"{}\" code_ref=\"{}\" -->\n<!-- sintesi:end id=\"{}\" -->\n",
+                id, code_ref, id
+            )
+        })
+        .collect();
+        let extract = |level| {
+            MarkdownExtractor::new()
+                .validate_code_refs(dir.path(), level)
+                .extract_from_file("docs/auth.md", &doc)
+                .errors
+        };
+
+        assert!(extract(CodeRefValidation::Format).is_empty());
+        let missing_file = "Anchor id=\"gone\" at line 5: code_ref file \"src/old.ts\" not found";
+        assert_eq!(extract(CodeRefValidation::Files), [missing_file]);
+        assert_eq!(
+            extract(CodeRefValidation::Symbols),
+            [
+                missing_file,
+                "Anchor id=\"renamed\" at line 7: symbol \"signIn\" not found in src/auth.ts"
+            ]
+        );
+    }
+}
@@ -0,0 +1,471 @@
+//! Dead link and dead code_ref detection with fix suggestions
+//!
+//! Anchors point at code through `code_ref="file#symbol"` and prose links to
+//! other files with relative markdown links. Both break silently when code is
+//! moved or renamed. This module finds broken references and, for each one,
+//! suggests replacements:
+//!
+//! - code_refs: the same symbol name in another file, then similarly named
+//!   symbols, ranked by how close their file is to the original path
+//! - links: files with the same name elsewhere, then similar paths
+//!
+//! Accepted suggestions are written back with [`apply_fixes`].
+
+use super::extractor::{
+    build_line_map, byte_offset_to_line, markdown_events, parse_sintesi_start,
+};
+use crate::ast::{AstAnalyzerInternal, SymbolInfo};
+use crate::graph::normalize_path;
+use ignore::WalkBuilder;
+use pulldown_cmark::{Event, Tag};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Maximum number of suggestions reported per broken reference
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Kind of reference that failed to resolve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrokenKind {
+    /// An anchor `code_ref` whose file or symbol no longer exists
+    CodeRef,
+    /// A relative markdown link to a missing file
+    Link,
+}
+
+/// A reference that does not resolve, with candidate replacements
+#[derive(Debug, Clone)]
+pub struct BrokenReference {
+    /// What kind of reference this is
+    pub kind: BrokenKind,
+    /// ID of the anchor, for code_refs
+    pub anchor_id: Option<String>,
+    /// Line of the reference in the markdown file (0-indexed)
+    pub line: usize,
+    /// The reference as written (`src/a.ts#foo` or `./guide.md`)
+    pub target: String,
+    /// Human-readable error, including suggestions
+    pub message: String,
+    /// Replacement targets, best first
+    pub suggestions: Vec<String>,
+}
+
+impl BrokenReference {
+    /// Build the fix that replaces this reference with one of its suggestions
+    pub fn fix(&self, suggestion: usize) -> Option<Fix> {
+        self.suggestions.get(suggestion).map(|replacement| Fix {
+            line: self.line,
+            original: self.target.clone(),
+            replacement: replacement.clone(),
+        })
+    }
+}
+
+/// A textual replacement on a single markdown line
+#[derive(Debug, Clone)]
+pub struct Fix {
+    /// Line to rewrite (0-indexed)
+    pub line: usize,
+    /// Text to replace (first occurrence on the line)
+    pub original: String,
+    /// Replacement text
+    pub replacement: String,
+}
+
+/// Files and symbols of a project, used to resolve references
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceIndex {
+    /// Project-relative file paths (forward slashes)
+    files: HashSet<String>,
+    /// Symbol names by project-relative file path
+    symbols: HashMap<String, HashSet<String>>,
+}
+
+impl ReferenceIndex {
+    /// Build an index from known files and analyzed symbols
+    ///
+    /// Symbol `file_path`s must be project-relative, like the files.
+    pub fn new(files: impl IntoIterator<Item = String>, symbols: &[SymbolInfo]) -> Self {
+        let mut index = Self {
+            files: files.into_iter().collect(),
+            symbols: HashMap::new(),
+        };
+        for symbol in symbols {
+            index.files.insert(symbol.file_path.clone());
+            index
+                .symbols
+                .entry(symbol.file_path.clone())
+                .or_default()
+                .insert(symbol.name.clone());
+        }
+        index
+    }
+
+    /// Walk a project (respecting .gitignore) and analyze its source files
+    pub fn from_project(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        let analyzer = AstAnalyzerInternal::new();
+        let mut files = Vec::new();
+        let mut symbols = Vec::new();
+
+        for entry in WalkBuilder::new(root).build().flatten() {
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(root) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            let is_source = matches!(
+                entry.path().extension().and_then(|e| e.to_str()),
+                Some("ts" | "tsx" | "js" | "jsx" | "mts" | "cts" | "py" | "pyi" | "rs")
+            );
+            if is_source {
+                if let Ok(content) = fs::read_to_string(entry.path()) {
+                    symbols.extend(analyzer.analyze_file(&relative, &content).symbols);
+                }
+            }
+            files.push(relative);
+        }
+
+        Self::new(files, &symbols)
+    }
+
+    fn has_file(&self, path: &str) -> bool {
+        let dir_prefix = format!("{}/", path.trim_end_matches('/'));
+        self.files.contains(path) || self.files.iter().any(|f| f.starts_with(&dir_prefix))
+    }
+}
+
+/// Find broken code_refs and relative links in a markdown document
+///
+/// # Arguments
+/// * `markdown_path` - Project-relative path of the document (links resolve
+///   against its directory)
+/// * `content` - Markdown content
+/// * `index` - Files and symbols of the project
+pub fn find_broken_references(
+    markdown_path: &str,
+    content: &str,
+    index: &ReferenceIndex,
+) -> Vec<BrokenReference> {
+    let line_map = build_line_map(content);
+    let base_dir = Path::new(markdown_path).parent().unwrap_or(Path::new(""));
+    let mut broken = Vec::new();
+
+    for (event, range) in markdown_events(content) {
+        let line = byte_offset_to_line(&line_map, range.start);
+        match event {
+            Event::Html(html) => {
+                if let Some((id, code_ref)) = parse_sintesi_start(&html) {
+                    if let Some(reference) = check_code_ref(&code_ref, index) {
+                        broken.push(BrokenReference {
+                            anchor_id: Some(id),
+                            line,
+                            ..reference
+                        });
+                    }
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                if let Some(reference) = check_link(&dest_url, base_dir, index) {
+                    broken.push(BrokenReference { line, ..reference });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    broken
+}
+
+fn check_code_ref(code_ref: &str, index: &ReferenceIndex) -> Option<BrokenReference> {
+    // Malformed code_refs are reported by the extractor's validation
+    let (file, name) = code_ref.split_once('#')?;
+    let in_file = index.symbols.get(file);
+    if in_file.is_some_and(|names| names.contains(name)) {
+        return None;
+    }
+
+    let problem = if index.files.contains(file) {
+        format!("symbol \"{}\" not found in {}", name, file)
+    } else {
+        format!("file {} not found", file)
+    };
+
+    let threshold = (name.len() / 3).max(1);
+    let mut candidates: Vec<(usize, usize, String)> = index
+        .symbols
+        .iter()
+        .flat_map(|(path, names)| names.iter().map(move |n| (path, n)))
+        .filter_map(|(path, candidate)| {
+            let name_distance = edit_distance(name, candidate);
+            (name_distance <= threshold).then(|| {
+                (
+                    name_distance,
+                    edit_distance(file, path),
+                    format!("{}#{}", path, candidate),
+                )
+            })
+        })
+        .collect();
+    candidates.sort();
+
+    let suggestions = candidates
+        .into_iter()
+        .map(|(_, _, suggestion)| suggestion)
+        .take(MAX_SUGGESTIONS)
+        .collect();
+
+    Some(broken(
+        BrokenKind::CodeRef,
+        code_ref,
+        "code_ref",
+        &problem,
+        suggestions,
+    ))
+}
+
+fn check_link(dest: &str, base_dir: &Path, index: &ReferenceIndex) -> Option<BrokenReference> {
+    let is_external = dest.contains("://") || dest.starts_with("mailto:");
+    if is_external || dest.starts_with('#') || dest.starts_with('/') || dest.is_empty() {
+        return None;
+    }
+
+    let path = dest.split(['#', '?']).next().unwrap_or(dest);
+    let resolved = normalize_path(&base_dir.join(path));
+    let resolved = resolved.to_string_lossy().replace('\\', "/");
+    if index.has_file(&resolved) {
+        return None;
+    }
+
+    let file_name = Path::new(&resolved).file_name().and_then(|n| n.to_str());
+    let mut candidates: Vec<(usize, usize, &String)> = index
+        .files
+        .iter()
+        .filter_map(|candidate| {
+            let same_name = Path::new(candidate).file_name().and_then(|n| n.to_str()) == file_name;
+            let distance = edit_distance(&resolved, candidate);
+            (same_name || distance <= 3).then_some((usize::from(!same_name), distance, candidate))
+        })
+        .collect();
+    candidates.sort();
+
+    let suggestions = candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, _, candidate)| relative_path(base_dir, Path::new(candidate)))
+        .map(|relative| {
+            // Keep the original `./` style and any fragment or query
+            let relative = if dest.starts_with("./") && !relative.starts_with("../") {
+                format!("./{}", relative)
+            } else {
+                relative
+            };
+            format!("{}{}", relative, &dest[path.len()..])
+        })
+        .collect();
+
+    Some(broken(
+        BrokenKind::Link,
+        dest,
+        "link",
+        &format!("{} not found", resolved),
+        suggestions,
+    ))
+}
+
+fn broken(
+    kind: BrokenKind,
+    target: &str,
+    label: &str,
+    problem: &str,
+    suggestions: Vec<String>,
+) -> BrokenReference {
+    let mut message = format!("Broken {} \"{}\": {}", label, target, problem);
+    if !suggestions.is_empty() {
+        let quoted: Vec<String> = suggestions.iter().map(|s| format!("\"{}\"", s)).collect();
+        message.push_str(&format!(" (did you mean {}?)", quoted.join(", ")));
+    }
+
+    BrokenReference {
+        kind,
+        anchor_id: None,
+        line: 0,
+        target: target.to_string(),
+        message,
+        suggestions,
+    }
+}
+
+/// Rewrite a markdown document with accepted fixes
+///
+/// # Returns
+/// The updated content, or an error naming the first fix whose original
+/// text is not on its line (the document changed since it was checked)
+pub fn apply_fixes(content: &str, fixes: &[Fix]) -> Result<String, String> {
+    let mut lines: Vec<String> = content.split('\n').map(String::from).collect();
+
+    for fix in fixes {
+        let line = lines
+            .get_mut(fix.line)
+            .filter(|line| line.contains(&fix.original))
+            .ok_or_else(|| {
+                format!(
+                    "Cannot apply fix at line {}: \"{}\" not found",
+                    fix.line + 1,
+                    fix.original
+                )
+            })?;
+        *line = line.replacen(&fix.original, &fix.replacement, 1);
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Path of `target` relative to the directory `from` (both project-relative)
+fn relative_path(from: &Path, target: &Path) -> String {
+    let from: Vec<Component> = from.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut result = PathBuf::new();
+    for _ in common..from.len() {
+        result.push("..");
+    }
+    for component in &target[common..] {
+        result.push(component);
+    }
+    result.to_string_lossy().replace('\\', "/")
+}
+
+/// Levenshtein distance between two strings
+pub(super) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SymbolType;
+
+    fn index() -> ReferenceIndex {
+        let symbol = |file_path: &str, name: &str| SymbolInfo {
+            name: name.to_string(),
+            symbol_type: SymbolType::Function,
+            signature: String::new(),
+            is_exported: true,
+            file_path: file_path.to_string(),
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+        };
+        ReferenceIndex::new(
+            vec!["docs/guides/setup.md".to_string(), "README.md".to_string()],
+            &[
+                symbol("src/auth/login.ts", "login"),
+                symbol("src/auth/session.ts", "logout"),
+                symbol("src/utils.ts", "slugify"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_moved_symbol_suggests_new_file() {
+        let content = "<!-- sintesi:start id=\"a\" code_ref=\"src/auth.ts#login\" -->\nx\n<!-- sintesi:end id=\"a\" -->\n";
+        let broken = find_broken_references("docs/api.md", content, &index());
+
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].kind, BrokenKind::CodeRef);
+        assert_eq!(broken[0].anchor_id.as_deref(), Some("a"));
+        assert_eq!(broken[0].suggestions[0], "src/auth/login.ts#login");
+        assert!(broken[0].message.contains("file src/auth.ts not found"));
+        assert!(broken[0]
+            .message
+            .contains("did you mean \"src/auth/login.ts#login\""));
+    }
+
+    #[test]
+    fn test_ignore_regions_are_skipped() {
+        let content = "# Writing anchors\n\n<!-- sintesi-ignore: tutorial -->\n<!-- sintesi:start id=\"demo\" code_ref=\"src/missing.ts#demo\" -->\nSee [the guide](missing.md).\n<!-- sintesi-ignore-end -->\n\n[Broken](gone.md)\n";
+
+        let broken = find_broken_references("docs/api.md", content, &index());
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].line, 7);
+
+        let extraction = crate::content::extract_anchors("docs/api.md", content);
+        assert!(extraction.anchors.is_empty());
+        assert!(extraction.errors.is_empty());
+    }
+
+    #[test]
+    fn test_renamed_symbol_suggests_fuzzy_match() {
+        let content = "<!-- sintesi:start id=\"a\" code_ref=\"src/utils.ts#slugfy\" -->\nx\n<!-- sintesi:end id=\"a\" -->\n<!-- sintesi:start id=\"b\" code_ref=\"src/utils.ts#slugify\" -->\ny\n<!-- sintesi:end id=\"b\" -->\n";
+        let broken = find_broken_references("docs/api.md", content, &index());
+
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].suggestions, vec!["src/utils.ts#slugify"]);
+    }
+
+    #[test]
+    fn test_dead_link_suggestions() {
+        let content =
+            "See [setup](./setup.md#install), [readme](../README.md) and [site](https://x.dev).\n";
+        let broken = find_broken_references("docs/api.md", content, &index());
+
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].kind, BrokenKind::Link);
+        assert_eq!(broken[0].target, "./setup.md#install");
+        assert_eq!(broken[0].suggestions, vec!["./guides/setup.md#install"]);
+    }
+
+    #[test]
+    fn test_apply_fixes() {
+        let content = "# Api\n<!-- sintesi:start id=\"a\" code_ref=\"src/auth.ts#login\" -->\nx\n<!-- sintesi:end id=\"a\" -->";
+        let broken = find_broken_references("docs/api.md", content, &index());
+
+        let fixed = apply_fixes(content, &[broken[0].fix(0).unwrap()]).unwrap();
+        assert!(fixed.contains("code_ref=\"src/auth/login.ts#login\""));
+        assert!(find_broken_references("docs/api.md", &fixed, &index()).is_empty());
+
+        let stale = Fix {
+            line: 0,
+            original: "missing".to_string(),
+            replacement: "x".to_string(),
+        };
+        assert!(apply_fixes(content, &[stale]).is_err());
+    }
+
+    #[test]
+    fn test_edit_distance_and_relative_path() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(
+            relative_path(Path::new("docs/api"), Path::new("src/a.ts")),
+            "../../src/a.ts"
+        );
+        assert_eq!(
+            relative_path(Path::new("docs"), Path::new("docs/g/s.md")),
+            "g/s.md"
+        );
+    }
+}
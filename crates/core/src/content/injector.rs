@@ -0,0 +1,236 @@
+//! Anchor content injection
+//!
+//! Replaces the documentation between an anchor's start and end tags while
+//! leaving the tags and everything outside the anchor untouched. The tags are
+//! expected on their own lines, as written by the generator. The anchor's
+//! byte spans from extraction locate the content, so the bytes outside it
+//! are copied exactly.
+//!
+//! Anchors nested inside the rewritten one are kept: the new content replaces
+//! the parent's own text and the children follow it unchanged, unless the new
+//! content already contains them.
+//!
+//! `inject_file` does the same to a markdown file: it is written back in the
+//! encoding it was read in (UTF-8 or UTF-16, see `text::read_text`), byte
+//! order mark included, and replaced atomically (see `apply::Transaction`),
+//! or only patched in memory in a dry run.
+
+use super::extractor::extract_anchors;
+use crate::apply::Transaction;
+use crate::error::Error;
+use crate::paths::extended_length;
+use crate::text::read_text_with_format;
+use std::path::Path;
+
+/// Replace the content of an anchor in a markdown document
+///
+/// # Arguments
+/// * `content` - Markdown document
+/// * `anchor_id` - ID of the anchor to rewrite
+/// * `new_content` - Replacement documentation (without anchor tags)
+///
+/// # Returns
+/// The rewritten document, or an error if the anchor is missing or malformed
+pub fn inject_anchor_content(
+    content: &str,
+    anchor_id: &str,
+    new_content: &str,
+) -> Result<String, String> {
+    let extraction = extract_anchors("", content);
+    let anchor = extraction
+        .anchors
+        .get(anchor_id)
+        .ok_or_else(|| format!("Anchor id=\"{}\" not found", anchor_id))?;
+
+    if anchor.end_line <= anchor.start_line {
+        return Err(format!(
+            "Anchor id=\"{}\" must have its start and end tags on separate lines",
+            anchor_id
+        ));
+    }
+
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let span = &anchor.content_span;
+    let mut result = String::with_capacity(content.len() + new_content.len());
+    result.push_str(&content[..span.start]);
+    if !result.ends_with('\n') {
+        result.push_str(newline);
+    }
+    let body = new_content.trim();
+    if !body.is_empty() {
+        for line in body.lines() {
+            result.push_str(line);
+            result.push_str(newline);
+        }
+    }
+    // Keep nested anchors, separated from the new text by blank lines
+    let included = extract_anchors("", body).anchors;
+    let mut separate = !body.is_empty();
+    for child in anchor
+        .children
+        .iter()
+        .filter_map(|id| extraction.anchors.get(id))
+    {
+        if included.contains_key(&child.id) {
+            continue;
+        }
+        if separate {
+            result.push_str(newline);
+        }
+        // The child's tag lines, indentation and line ending included
+        let start = content[..child.start_tag_span.start]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let end = content[child.end_tag_span.end..]
+            .find('\n')
+            .map_or(content.len(), |i| child.end_tag_span.end + i + 1);
+        result.push_str(&content[start..end]);
+        if !result.ends_with('\n') {
+            result.push_str(newline);
+        }
+        separate = true;
+    }
+    result.push_str(&content[span.end..]);
+
+    Ok(result)
+}
+
+/// Replace the content of an anchor in a markdown file
+///
+/// # Arguments
+/// * `path` - Markdown file (UTF-8 or UTF-16, with or without a byte order
+///   mark)
+/// * `anchor_id` - ID of the anchor to rewrite
+/// * `new_content` - Replacement documentation (without anchor tags)
+/// * `dry_run` - Return the patched file without writing it
+///
+/// # Returns
+/// The patched file content, decoded with its byte order mark, or an error if the
+/// file cannot be read or written (`Error::ReadOnly` in read-only mode) or
+/// the anchor is missing or malformed
+pub fn inject_file(
+    path: impl AsRef<Path>,
+    anchor_id: &str,
+    new_content: &str,
+    dry_run: bool,
+) -> Result<String, Error> {
+    let path = path.as_ref();
+    let (content, format) = read_text_with_format(&extended_length(path))
+        .map_err(|e| Error::from_reason(format!("Failed to read {}: {}", path.display(), e)))?;
+    let patched = inject_anchor_content(&content, anchor_id, new_content)
+        .map_err(|e| Error::from_reason(format!("{}: {}", path.display(), e)))?;
+    if !dry_run && patched != content {
+        let mut transaction = Transaction::new();
+        transaction.stage(path, format.encode(&patched));
+        transaction.commit()?;
+    }
+    let bom = if format.bom { "\u{FEFF}" } else { "" };
+    Ok(format!("{}{}", bom, patched))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Byte order mark, as the decoded file content starts with it
+    const BOM: &str = "\u{FEFF}";
+
+    const DOC: &str = "# Auth\n\n<!-- sintesi:start id=\"a\" code_ref=\"src/auth.ts#login\" -->\nOld text.\n\nMore old text.\n<!-- sintesi:end id=\"a\" -->\n\nFooter\n";
+
+    #[test]
+    fn test_inject_replaces_only_anchor_body() {
+        let result = inject_anchor_content(DOC, "a", "New text.\n").unwrap();
+        assert_eq!(
+            result,
+            "# Auth\n\n<!-- sintesi:start id=\"a\" code_ref=\"src/auth.ts#login\" -->\nNew text.\n<!-- sintesi:end id=\"a\" -->\n\nFooter\n"
+        );
+        assert_eq!(
+            inject_anchor_content(&result, "a", "Old text.\n\nMore old text.").unwrap(),
+            DOC
+        );
+    }
+
+    #[test]
+    fn test_inject_keeps_nested_anchors() {
+        let doc = "<!-- sintesi:start id=\"auth\" code_ref=\"src/auth.ts#Auth\" -->\nOverview.\n\n<!-- sintesi:start id=\"a\" code_ref=\"src/auth.ts#login\" -->\nLogin.\n<!-- sintesi:end id=\"a\" -->\nTrailing.\n<!-- sintesi:end id=\"auth\" -->\n";
+
+        let result = inject_anchor_content(doc, "auth", "New overview.").unwrap();
+
+        assert_eq!(
+            result,
+            "<!-- sintesi:start id=\"auth\" code_ref=\"src/auth.ts#Auth\" -->\nNew overview.\n\n<!-- sintesi:start id=\"a\" code_ref=\"src/auth.ts#login\" -->\nLogin.\n<!-- sintesi:end id=\"a\" -->\n<!-- sintesi:end id=\"auth\" -->\n"
+        );
+        let anchors = extract_anchors("", &result).anchors;
+        assert_eq!(anchors["auth"].children, ["a"]);
+        assert_eq!(anchors["a"].parent.as_deref(), Some("auth"));
+        assert_eq!(
+            inject_anchor_content(&result, "a", "New login.").unwrap(),
+            result.replace("Login.", "New login.")
+        );
+    }
+
+    #[test]
+    fn test_inject_file_keeps_bom_and_supports_dry_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("auth.md");
+        let original = format!("{}{}", BOM, DOC.replace('\n', "\r\n"));
+        fs::write(&path, &original).unwrap();
+
+        let patched = inject_file(&path, "a", "New text.", true).unwrap();
+        assert!(patched.starts_with(BOM));
+        assert!(patched.contains("-->\r\nNew text.\r\n<!-- sintesi:end"));
+        assert!(patched.ends_with("\r\n\r\nFooter\r\n"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+        assert_eq!(
+            inject_file(&path, "a", "New text.", false).unwrap(),
+            patched
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), patched);
+
+        let error = inject_file(&path, "missing", "x", false).unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("Anchor id=\"missing\" not found"));
+    }
+
+    #[test]
+    fn test_inject_file_keeps_utf16() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("auth.md");
+        let utf16le = |text: &str| {
+            let units = text.encode_utf16().flat_map(u16::to_le_bytes);
+            [b"\xFF\xFE".to_vec(), units.collect()].concat()
+        };
+        fs::write(&path, utf16le(DOC)).unwrap();
+
+        let patched = inject_file(&path, "a", "Café.", false).unwrap();
+
+        let expected = DOC.replace("Old text.\n\nMore old text.", "Café.");
+        assert_eq!(patched, format!("{}{}", BOM, expected));
+        assert_eq!(fs::read(&path).unwrap(), utf16le(&expected));
+    }
+
+    #[test]
+    fn test_inject_preserves_crlf_and_reports_missing_anchor() {
+        let crlf = DOC.replace('\n', "\r\n");
+        let result = inject_anchor_content(&crlf, "a", "Line one\nLine two").unwrap();
+        assert!(result.contains("-->\r\nLine one\r\nLine two\r\n<!-- sintesi:end"));
+
+        assert_eq!(
+            inject_anchor_content(DOC, "missing", "x").unwrap_err(),
+            "Anchor id=\"missing\" not found"
+        );
+
+        let multibyte = DOC.replace("# Auth", "# Authentification ✓ für Café");
+        assert_eq!(
+            inject_anchor_content(&multibyte, "a", "Übersicht.").unwrap(),
+            multibyte.replace("Old text.\n\nMore old text.", "Übersicht.")
+        );
+    }
+}
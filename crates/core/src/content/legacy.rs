@@ -0,0 +1,73 @@
+//! Legacy doctype anchor rewriting
+//!
+//! Before the rename, anchors were written in the doctype dialect:
+//!
+//! ```markdown
+//! <!-- doctype:start id="uuid" code_ref="src/file.ts#SymbolName" -->
+//! <!-- doctype:end id="uuid" -->
+//! ```
+//!
+//! Only markers in HTML comments are rewritten; text in code blocks and
+//! ignore regions is left alone.
+
+use super::extractor::markdown_events;
+use pulldown_cmark::Event;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Legacy anchor marker prefix
+pub const DOCTYPE_PREFIX: &str = "doctype:";
+
+fn marker_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(<!--\s*)doctype:(start|end)\b").unwrap())
+}
+
+/// Rewrite `doctype:start`/`doctype:end` markers to the sintesi dialect
+///
+/// # Returns
+/// The rewritten content and the number of markers rewritten
+pub fn rewrite_doctype_anchors(content: &str) -> (String, usize) {
+    let ranges: Vec<_> = markdown_events(content)
+        .filter(|(event, _)| matches!(event, Event::Html(_) | Event::InlineHtml(_)))
+        .map(|(_, range)| range)
+        .collect();
+
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+    let mut count = 0;
+    for range in ranges {
+        let Some(html) = content.get(range.clone()) else {
+            continue;
+        };
+        if range.start < last {
+            continue;
+        }
+        output.push_str(&content[last..range.start]);
+        let rewritten = marker_regex().replace_all(html, |caps: &regex::Captures| {
+            count += 1;
+            format!("{}sintesi:{}", &caps[1], &caps[2])
+        });
+        output.push_str(&rewritten);
+        last = range.end;
+    }
+    output.push_str(&content[last..]);
+
+    (output, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrites_comments_only() {
+        let content = "# Auth\n\n<!-- doctype:start id=\"a\" code_ref=\"src/a.ts#a\" -->\nText\n<!-- doctype:end id=\"a\" -->\n\n```markdown\n<!-- doctype:start id=\"x\" -->\n```\n";
+        let (rewritten, count) = rewrite_doctype_anchors(content);
+
+        assert_eq!(count, 2);
+        assert!(rewritten.contains("<!-- sintesi:start id=\"a\""));
+        assert!(rewritten.contains("<!-- sintesi:end id=\"a\" -->"));
+        assert!(rewritten.contains("```markdown\n<!-- doctype:start id=\"x\" -->"));
+    }
+}
@@ -0,0 +1,300 @@
+//! Readability and lint checks for generated markdown
+//!
+//! Generated documentation is only useful if people can read it. This module
+//! runs a set of lightweight checks over markdown (a whole document or a
+//! single anchor's content) and returns structured findings, so callers can
+//! gate injection on documentation quality:
+//!
+//! - Heading hierarchy: levels must not skip (`#` followed by `###`) and a
+//!   document should have at most one top-level heading
+//! - Long paragraphs: more words than the configured limit
+//! - Passive voice: a "to be" verb followed by a past participle (heuristic)
+//! - Banned words: terms a team does not want in its docs ("simply", "just")
+//!
+//! Code blocks and inline code are never checked.
+
+use super::extractor::{build_line_map, byte_offset_to_line, markdown_events};
+use pulldown_cmark::{Event, HeadingLevel, Tag, TagEnd};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Lint rule that produced a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+    HeadingHierarchy,
+    LongParagraph,
+    PassiveVoice,
+    BannedWord,
+}
+
+impl LintRule {
+    /// Stable rule identifier (used in reports and configuration)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintRule::HeadingHierarchy => "heading-hierarchy",
+            LintRule::LongParagraph => "long-paragraph",
+            LintRule::PassiveVoice => "passive-voice",
+            LintRule::BannedWord => "banned-word",
+        }
+    }
+}
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+    Warning,
+    Error,
+}
+
+/// A single lint finding
+#[derive(Debug, Clone)]
+pub struct LintFinding {
+    /// Rule that produced the finding
+    pub rule: LintRule,
+    /// Severity of the finding
+    pub severity: LintSeverity,
+    /// Line in the linted content (0-indexed)
+    pub line: usize,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Configuration for the markdown linter
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+    /// Maximum words per paragraph
+    pub max_paragraph_words: usize,
+    /// Report passive-voice constructions
+    pub check_passive_voice: bool,
+    /// Words or phrases that must not appear (case-insensitive)
+    pub banned_words: Vec<String>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            max_paragraph_words: 120,
+            check_passive_voice: true,
+            banned_words: vec![],
+        }
+    }
+}
+
+impl LintConfig {
+    /// Create a new configuration with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of words per paragraph
+    pub fn max_paragraph_words(mut self, words: usize) -> Self {
+        self.max_paragraph_words = words;
+        self
+    }
+
+    /// Enable or disable the passive-voice heuristic
+    pub fn check_passive_voice(mut self, value: bool) -> Self {
+        self.check_passive_voice = value;
+        self
+    }
+
+    /// Add a banned word or phrase
+    pub fn ban_word(mut self, word: impl Into<String>) -> Self {
+        self.banned_words.push(word.into());
+        self
+    }
+}
+
+/// Lint markdown content
+///
+/// # Returns
+/// Findings ordered by line
+pub fn lint_markdown(content: &str, config: &LintConfig) -> Vec<LintFinding> {
+    let line_map = build_line_map(content);
+    let banned = banned_regex(&config.banned_words);
+    let mut findings = Vec::new();
+
+    let mut previous_level: Option<usize> = None;
+    let mut top_level_headings = 0;
+    let mut paragraph: Option<(usize, usize)> = None; // (start line, word count)
+    let mut in_code_block = false;
+
+    for (event, range) in markdown_events(content) {
+        let line = byte_offset_to_line(&line_map, range.start);
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                let level = heading_depth(level);
+                if let Some(previous) = previous_level.filter(|p| level > p + 1) {
+                    findings.push(LintFinding {
+                        rule: LintRule::HeadingHierarchy,
+                        severity: LintSeverity::Warning,
+                        line,
+                        message: format!(
+                            "Heading level {} follows level {} (skipped level {})",
+                            level,
+                            previous,
+                            previous + 1
+                        ),
+                    });
+                }
+                if level == 1 {
+                    top_level_headings += 1;
+                    if top_level_headings == 2 {
+                        findings.push(LintFinding {
+                            rule: LintRule::HeadingHierarchy,
+                            severity: LintSeverity::Warning,
+                            line,
+                            message: "Multiple top-level headings".to_string(),
+                        });
+                    }
+                }
+                previous_level = Some(level);
+            }
+            Event::Start(Tag::Paragraph) => paragraph = Some((line, 0)),
+            Event::End(TagEnd::Paragraph) => {
+                if let Some((start, words)) = paragraph.take() {
+                    if words > config.max_paragraph_words {
+                        findings.push(LintFinding {
+                            rule: LintRule::LongParagraph,
+                            severity: LintSeverity::Warning,
+                            line: start,
+                            message: format!(
+                                "Paragraph has {} words (max {})",
+                                words, config.max_paragraph_words
+                            ),
+                        });
+                    }
+                }
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(text) if !in_code_block => {
+                if let Some((_, words)) = paragraph.as_mut() {
+                    *words += text.split_whitespace().count();
+                }
+                // Text events map onto their source range unless escapes
+                // shortened them; positions are then approximate
+                let line_at = |offset: usize| {
+                    byte_offset_to_line(&line_map, (range.start + offset).min(range.end))
+                };
+
+                if config.check_passive_voice {
+                    for m in passive_regex().find_iter(&text) {
+                        findings.push(LintFinding {
+                            rule: LintRule::PassiveVoice,
+                            severity: LintSeverity::Warning,
+                            line: line_at(m.start()),
+                            message: format!("Possible passive voice: \"{}\"", m.as_str()),
+                        });
+                    }
+                }
+                if let Some(banned) = &banned {
+                    for m in banned.find_iter(&text) {
+                        findings.push(LintFinding {
+                            rule: LintRule::BannedWord,
+                            severity: LintSeverity::Error,
+                            line: line_at(m.start()),
+                            message: format!("Banned word \"{}\"", m.as_str()),
+                        });
+                    }
+                }
+            }
+            Event::Code(code) => {
+                if let Some((_, words)) = paragraph.as_mut() {
+                    *words += code.split_whitespace().count();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    findings.sort_by_key(|f| f.line);
+    findings
+}
+
+/// Check whether any finding is severe enough to block injection
+pub fn has_blocking_findings(findings: &[LintFinding]) -> bool {
+    findings.iter().any(|f| f.severity == LintSeverity::Error)
+}
+
+fn heading_depth(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+fn passive_regex() -> &'static Regex {
+    static PASSIVE: OnceLock<Regex> = OnceLock::new();
+    PASSIVE.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:am|is|are|was|were|be|been|being)\s+(?:\w+ly\s+)?(?:\w+ed|built|done|found|given|held|kept|known|made|run|seen|sent|set|shown|taken|thrown|written)\b",
+        )
+        .unwrap()
+    })
+}
+
+fn banned_regex(words: &[String]) -> Option<Regex> {
+    let alternatives: Vec<String> = words
+        .iter()
+        .map(|w| w.trim())
+        .filter(|w| !w.is_empty())
+        .map(regex::escape)
+        .collect();
+    if alternatives.is_empty() {
+        return None;
+    }
+    Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives.join("|"))).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(findings: &[LintFinding]) -> Vec<LintRule> {
+        findings.iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn test_heading_hierarchy() {
+        let content = "# Title\n\n### Skipped\n\n## Fine\n\n# Second title\n";
+        let findings = lint_markdown(content, &LintConfig::new());
+
+        assert_eq!(rules(&findings), vec![LintRule::HeadingHierarchy; 2]);
+        assert_eq!(findings[0].line, 2);
+        assert!(findings[0].message.contains("skipped level 2"));
+        assert_eq!(findings[1].message, "Multiple top-level headings");
+    }
+
+    #[test]
+    fn test_long_paragraph() {
+        let long = ["word"; 12].join(" ");
+        let content = format!("Short one.\n\n{}\n", long);
+        let findings = lint_markdown(&content, &LintConfig::new().max_paragraph_words(10));
+
+        assert_eq!(rules(&findings), vec![LintRule::LongParagraph]);
+        assert_eq!(findings[0].line, 2);
+    }
+
+    #[test]
+    fn test_passive_voice_and_banned_words() {
+        let content =
+            "The token is validated by the server.\nSimply call `login`.\n\n```ts\nx is called;\n```\n";
+        let config = LintConfig::new().ban_word("simply");
+        let findings = lint_markdown(content, &config);
+
+        assert_eq!(
+            rules(&findings),
+            vec![LintRule::PassiveVoice, LintRule::BannedWord]
+        );
+        assert_eq!(findings[1].line, 1);
+        assert!(has_blocking_findings(&findings));
+
+        let relaxed = LintConfig::new().check_passive_voice(false);
+        assert!(lint_markdown(content, &relaxed).is_empty());
+    }
+}
@@ -4,16 +4,46 @@
 //! - File discovery (TypeScript/JavaScript and Markdown files)
 //! - Markdown parsing and anchor extraction
 //! - Content injection into documentation
+//! - Validation of code examples embedded in anchors
+//! - Dead link and code_ref detection with fix suggestions
+//! - Readability and lint checks for generated markdown
+//! - Optional spell checking of anchor content
+//! - `<!-- sintesi-ignore -->` regions skipped by extraction and validators
+//! - Rewriting legacy `doctype:` anchors to the sintesi dialect
+//! - Summarizing what changed in regenerated anchors
+//! - Canonical anchor content hashes that ignore reformatting
+//! - Architecture briefs condensed from the README and ADRs
+//! - Markdown skeletons with anchors scaffolded from code signatures
 //!
 //! ## Module Structure
 //!
 //! - `types`: Data structures for anchors and extraction results
 //! - `discovery`: File discovery functionality (source and markdown files)
 //! - `extractor`: Markdown anchor extraction using pulldown-cmark
+//! - `injector`: Anchor content replacement
+//! - `examples`: Syntax and import checks for fenced code in anchors
+//! - `fixes`: Broken reference detection, suggestions, and rewriting
+//! - `lint`: Heading, paragraph, passive-voice, and banned-word checks
+//! - `spell`: Dictionary-based spell checker aware of project symbols
+//! - `legacy`: Legacy doctype anchor rewriting
+//! - `canonical`: Whitespace- and wrap-insensitive content hashing
+//! - `summary`: Section and parameter level summaries of anchor changes
+//! - `brief`: Headings and first paragraphs of the README and `docs/adr/*.md`
+//! - `scaffold`: Anchor skeletons for the symbols of a source file
 
 pub mod types;
 pub mod discovery;
 pub mod extractor;
+pub mod injector;
+pub mod examples;
+pub mod fixes;
+pub mod lint;
+pub mod spell;
+pub mod legacy;
+pub mod summary;
+pub mod canonical;
+pub mod brief;
+pub mod scaffold;
 
 // Re-export types
 pub use types::{AnchorMap, SintesiAnchor, ExtractionResult};
@@ -25,4 +55,34 @@ pub use discovery::{
 };
 
 // Re-export extractor
-pub use extractor::{extract_anchors, MarkdownExtractor};
+pub use extractor::{extract_anchors, extract_anchors_from, CodeRefValidation, MarkdownExtractor};
+
+// Re-export injection
+pub use injector::{inject_anchor_content, inject_file};
+
+// Re-export example validation
+pub use examples::{extract_code_examples, validate_code_examples, CodeExample, ExampleValidator};
+
+// Re-export reference fixing
+pub use fixes::{apply_fixes, find_broken_references, BrokenKind, BrokenReference, Fix, ReferenceIndex};
+
+// Re-export linting
+pub use lint::{has_blocking_findings, lint_markdown, LintConfig, LintFinding, LintRule, LintSeverity};
+
+// Re-export spell checking
+pub use spell::{Misspelling, SpellChecker};
+
+// Re-export legacy anchor rewriting
+pub use legacy::rewrite_doctype_anchors;
+
+// Re-export change summaries
+pub use summary::{format_changes, summarize_anchor_change, AnchorChange};
+
+// Re-export canonical hashing
+pub use canonical::{anchor_content_hash, canonicalize};
+
+// Re-export architecture briefs
+pub use brief::{ArchitectureBrief, BriefDocument, BriefSection};
+
+// Re-export anchor scaffolding
+pub use scaffold::{scaffold, ScaffoldOptions};
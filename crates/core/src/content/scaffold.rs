@@ -0,0 +1,157 @@
+//! Anchor scaffolding
+//!
+//! Hand-written anchors are where `code_ref` typos come from. `scaffold`
+//! turns the signatures of a source file into a markdown skeleton instead: a
+//! title, then for each symbol a heading and an anchor with a fresh UUID and
+//! the symbol's `code_ref`, holding a placeholder for the GenAI agent to
+//! replace. Members (`User.login`) get a heading one level below their type.
+//! Only exported symbols are scaffolded unless private ones are asked for,
+//! and a name declared more than once (overloads) gets a single anchor.
+
+use crate::types::CodeSignature;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Deepest heading level emitted for nested members
+const MAX_HEADING_LEVEL: usize = 6;
+
+/// Options for scaffolding a markdown file
+#[derive(Debug, Clone, Default)]
+pub struct ScaffoldOptions {
+    /// Title of the document (the source file path if `None`)
+    pub title: Option<String>,
+    /// Scaffold symbols that are not exported too
+    pub include_private: bool,
+}
+
+impl ScaffoldOptions {
+    /// Create default options (exported symbols, titled with the file path)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Title the document
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Scaffold symbols that are not exported too
+    pub fn include_private(mut self, include: bool) -> Self {
+        self.include_private = include;
+        self
+    }
+}
+
+/// Generate a markdown skeleton with one anchor per symbol
+///
+/// # Arguments
+/// * `file_path` - Source file of the signatures, as used in `code_ref`s
+/// * `signatures` - Signatures of the file, in declaration order
+/// * `options` - Title and which symbols to include
+///
+/// # Returns
+/// The markdown, ending with a newline
+pub fn scaffold(
+    file_path: &str,
+    signatures: &[CodeSignature],
+    options: &ScaffoldOptions,
+) -> String {
+    let title = options.title.as_deref().unwrap_or(file_path);
+    let mut markdown = format!("# {}\n", title);
+    let mut seen = HashSet::new();
+
+    for signature in signatures {
+        if !(signature.is_exported || options.include_private)
+            || !seen.insert(&signature.symbol_name)
+        {
+            continue;
+        }
+        let name = signature
+            .display_name
+            .as_deref()
+            .unwrap_or(&signature.symbol_name);
+        let level = (2 + signature.symbol_name.matches('.').count()).min(MAX_HEADING_LEVEL);
+        let id = Uuid::new_v4();
+        markdown.push_str(&format!(
+            "\n{} `{}`\n\n<!-- sintesi:start id=\"{}\" code_ref=\"{}#{}\" -->\nTODO: document `{}`.\n<!-- sintesi:end id=\"{}\" -->\n",
+            "#".repeat(level),
+            name,
+            id,
+            file_path,
+            signature.symbol_name,
+            name,
+            id
+        ));
+    }
+
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::extract_anchors;
+    use crate::types::SymbolType;
+
+    fn signature(name: &str, is_exported: bool) -> CodeSignature {
+        CodeSignature {
+            symbol_name: name.to_string(),
+            symbol_type: SymbolType::Function,
+            signature_text: format!("function {}()", name),
+            is_exported,
+            hash: None,
+            display_name: None,
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+            id: None,
+        }
+    }
+
+    #[test]
+    fn test_scaffolds_anchors_for_exported_symbols() {
+        let signatures = [
+            signature("User", true),
+            signature("User.login", true),
+            signature("hash", false),
+            signature("logout", true),
+            signature("logout", true),
+        ];
+
+        let markdown = scaffold("src/auth.ts", &signatures, &ScaffoldOptions::new());
+
+        assert!(markdown.starts_with("# src/auth.ts\n\n## `User`\n"));
+        assert!(markdown.contains("\n### `User.login`\n"));
+        assert!(!markdown.contains("hash"));
+        let result = extract_anchors("docs/auth.md", &markdown);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let mut anchors: Vec<_> = result.anchors.values().collect();
+        anchors.sort_by_key(|anchor| anchor.start_line);
+        let code_refs: Vec<_> = anchors
+            .iter()
+            .map(|anchor| anchor.code_ref.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            code_refs,
+            [
+                "src/auth.ts#User",
+                "src/auth.ts#User.login",
+                "src/auth.ts#logout"
+            ]
+        );
+        assert!(anchors
+            .iter()
+            .all(|anchor| Uuid::parse_str(&anchor.id).is_ok()));
+        assert_eq!(anchors[2].content.trim(), "TODO: document `logout`.");
+
+        let markdown = scaffold(
+            "src/auth.ts",
+            &signatures,
+            &ScaffoldOptions::new().title("Auth").include_private(true),
+        );
+        assert!(markdown.starts_with("# Auth\n"));
+        assert!(markdown.contains("code_ref=\"src/auth.ts#hash\""));
+    }
+}
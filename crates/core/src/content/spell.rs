@@ -0,0 +1,286 @@
+//! Dictionary-based spell checking for anchor content
+//!
+//! Spell checking is optional: it needs a word list (one word per line, such
+//! as `/usr/share/dict/words` or a Hunspell `.dic` export) and is extended
+//! with a per-project list of custom words. Symbol names from the analyzer
+//! are added too, so prose mentioning `getUserProfile` or `UserProfile` is
+//! not flagged; camelCase and PascalCase names also contribute their parts.
+//!
+//! Only prose inside anchors is checked. Code blocks, inline code, HTML, and
+//! URLs are skipped, as are acronyms and words containing digits.
+
+use super::extractor::{
+    build_line_map, byte_offset_to_line, markdown_events, parse_sintesi_end, parse_sintesi_start,
+};
+use super::fixes::edit_distance;
+use crate::ast::SymbolInfo;
+use pulldown_cmark::{Event, Tag, TagEnd};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Word lists tried by [`SpellChecker::with_system_dictionary`]
+const SYSTEM_DICTIONARIES: &[&str] = &["/usr/share/dict/words", "/usr/dict/words"];
+
+/// Words shorter than this are never flagged
+const MIN_WORD_LENGTH: usize = 3;
+
+/// Maximum number of suggestions per misspelling
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A misspelled word in a markdown document
+#[derive(Debug, Clone)]
+pub struct Misspelling {
+    /// The word as written
+    pub word: String,
+    /// ID of the anchor containing the word
+    pub anchor_id: String,
+    /// Line in the markdown file (0-indexed)
+    pub line: usize,
+    /// Column within the line (0-indexed, in bytes)
+    pub column: usize,
+    /// Dictionary words close to the misspelling, best first
+    pub suggestions: Vec<String>,
+}
+
+/// Spell checker backed by a word list plus project-specific words
+#[derive(Debug, Clone, Default)]
+pub struct SpellChecker {
+    dictionary: HashSet<String>,
+    custom: HashSet<String>,
+}
+
+impl SpellChecker {
+    /// Create a spell checker from dictionary words
+    pub fn new(words: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        Self {
+            dictionary: words
+                .into_iter()
+                .map(|w| w.as_ref().trim().to_lowercase())
+                .filter(|w| !w.is_empty())
+                .collect(),
+            custom: HashSet::new(),
+        }
+    }
+
+    /// Load a dictionary file (one word per line, Hunspell `/FLAGS` ignored)
+    pub fn from_dictionary_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(Self::new(
+            content.lines().map(|l| l.split('/').next().unwrap_or("")),
+        ))
+    }
+
+    /// Load the first system word list found, if any
+    pub fn with_system_dictionary() -> Option<Self> {
+        SYSTEM_DICTIONARIES
+            .iter()
+            .find_map(|path| Self::from_dictionary_file(path).ok())
+    }
+
+    /// Add project-specific words (product names, jargon)
+    pub fn add_words(&mut self, words: impl IntoIterator<Item = impl AsRef<str>>) {
+        self.custom
+            .extend(words.into_iter().map(|w| w.as_ref().to_lowercase()));
+    }
+
+    /// Add symbol names (and their camelCase parts) from the analyzer
+    pub fn add_symbols(&mut self, symbols: &[SymbolInfo]) {
+        for symbol in symbols {
+            // Qualified names like `User.Address` or `GET /users` contribute each part
+            for name in symbol
+                .name
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+            {
+                self.custom.insert(name.to_lowercase());
+                self.custom
+                    .extend(split_identifier(name).map(|p| p.to_lowercase()));
+            }
+        }
+    }
+
+    /// Check whether a word is spelled correctly
+    pub fn is_known(&self, word: &str) -> bool {
+        let lower = word.to_lowercase();
+        let known = |w: &str| self.dictionary.contains(w) || self.custom.contains(w);
+
+        if known(&lower) {
+            return true;
+        }
+        // Possessives and simple plurals missing from plain word lists
+        let stems = [
+            lower.strip_suffix("'s"),
+            lower.strip_suffix("es"),
+            lower.strip_suffix('s'),
+        ];
+        if stems.into_iter().flatten().any(known) {
+            return true;
+        }
+        // Unknown camelCase words are fine if every part is known
+        let parts: Vec<&str> = split_identifier(word).collect();
+        parts.len() > 1 && parts.iter().all(|p| self.is_known(p))
+    }
+
+    /// Dictionary words within a small edit distance of `word`
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let lower = word.to_lowercase();
+        let first = lower.chars().next();
+        let mut candidates: Vec<(usize, &String)> = self
+            .dictionary
+            .iter()
+            .chain(&self.custom)
+            .filter(|c| c.len().abs_diff(lower.len()) <= 2 && c.chars().next() == first)
+            .map(|c| (edit_distance(&lower, c), c))
+            .filter(|(distance, _)| *distance <= 2)
+            .collect();
+        candidates.sort();
+        candidates.dedup_by(|a, b| a.1 == b.1);
+
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, c)| c.clone())
+            .collect()
+    }
+
+    /// Check the prose inside every anchor of a markdown document
+    pub fn check_markdown(&self, content: &str) -> Vec<Misspelling> {
+        let line_map = build_line_map(content);
+        let mut misspellings = Vec::new();
+        let mut open_anchors: Vec<String> = Vec::new();
+        let mut in_code_block = false;
+
+        for (event, range) in markdown_events(content) {
+            match event {
+                Event::Html(html) => {
+                    if let Some((id, _)) = parse_sintesi_start(&html) {
+                        open_anchors.push(id);
+                    } else if let Some(id) = parse_sintesi_end(&html) {
+                        open_anchors.retain(|open| open != &id);
+                    }
+                }
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(TagEnd::CodeBlock) => in_code_block = false,
+                Event::Text(text) if !in_code_block => {
+                    let Some(anchor_id) = open_anchors.last() else {
+                        continue;
+                    };
+                    for (offset, word) in words(&text) {
+                        if self.is_known(word) {
+                            continue;
+                        }
+                        // Offsets are exact unless escapes shortened the text
+                        let position = (range.start + offset).min(range.end);
+                        let line = byte_offset_to_line(&line_map, position);
+                        misspellings.push(Misspelling {
+                            word: word.to_string(),
+                            anchor_id: anchor_id.clone(),
+                            line,
+                            column: position - line_map[line],
+                            suggestions: self.suggest(word),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        misspellings
+    }
+}
+
+/// Checkable words in a text fragment with their byte offsets
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    static WORD: OnceLock<Regex> = OnceLock::new();
+    let word = WORD.get_or_init(|| Regex::new(r"[A-Za-z][A-Za-z0-9']*[A-Za-z]").unwrap());
+
+    text.split_whitespace()
+        .filter(|chunk| !chunk.contains("://") && !chunk.contains('@') && !chunk.contains('/'))
+        .flat_map(move |chunk| {
+            let base = chunk.as_ptr() as usize - text.as_ptr() as usize;
+            word.find_iter(chunk)
+                .map(move |m| (base + m.start(), m.as_str()))
+        })
+        .filter(|(_, w)| {
+            w.len() >= MIN_WORD_LENGTH
+                && !w.chars().any(|c| c.is_ascii_digit())
+                && !w.chars().all(|c| c.is_ascii_uppercase())
+        })
+}
+
+/// Split a camelCase, PascalCase, or snake_case identifier into its parts
+fn split_identifier(name: &str) -> impl Iterator<Item = &str> {
+    let mut parts = Vec::new();
+    for segment in name.split('_').filter(|s| !s.is_empty()) {
+        let mut start = 0;
+        let chars: Vec<(usize, char)> = segment.char_indices().collect();
+        for window in chars.windows(2) {
+            let ((_, prev), (idx, next)) = (window[0], window[1]);
+            if prev.is_lowercase() && next.is_uppercase() {
+                parts.push(&segment[start..idx]);
+                start = idx;
+            }
+        }
+        parts.push(&segment[start..]);
+    }
+    parts.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SymbolType;
+
+    fn checker() -> SpellChecker {
+        let mut checker = SpellChecker::new([
+            "the", "user", "profile", "returns", "a", "for", "given", "call", "to", "load", "see",
+            "and",
+        ]);
+        checker.add_words(["Sintesi"]);
+        checker.add_symbols(&[SymbolInfo {
+            name: "getUserProfile".to_string(),
+            symbol_type: SymbolType::Function,
+            signature: String::new(),
+            is_exported: true,
+            file_path: "src/user.ts".to_string(),
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+        }]);
+        checker
+    }
+
+    #[test]
+    fn test_known_words() {
+        let checker = checker();
+        assert!(checker.is_known("Returns"));
+        assert!(checker.is_known("users"));
+        assert!(checker.is_known("getUserProfile"));
+        assert!(checker.is_known("sintesi"));
+        assert!(checker.is_known("loadProfile"));
+        assert!(!checker.is_known("profle"));
+    }
+
+    #[test]
+    fn test_checks_only_anchor_prose() {
+        let content = "Outsde the anchor.\n\n<!-- sintesi:start id=\"a\" code_ref=\"src/user.ts#getUserProfile\" -->\nCall getUserProfile to load the usr profle.\n\n```ts\nconst misspeled = 1;\n```\n\nSee `inlne` and https://exmple.com for the API.\n<!-- sintesi:end id=\"a\" -->\n";
+        let found = checker().check_markdown(content);
+
+        let words: Vec<&str> = found.iter().map(|m| m.word.as_str()).collect();
+        assert_eq!(words, vec!["usr", "profle"]);
+        assert_eq!(found[0].anchor_id, "a");
+        assert_eq!(found[0].line, 3);
+        assert_eq!(found[0].column, 32);
+        assert_eq!(found[0].suggestions, vec!["user"]);
+        assert_eq!(found[1].suggestions[0], "profile");
+    }
+
+    #[test]
+    fn test_split_identifier() {
+        let parts: Vec<&str> = split_identifier("parseHTTPResponse_body").collect();
+        assert_eq!(parts, vec!["parse", "HTTPResponse", "body"]);
+    }
+}
@@ -0,0 +1,253 @@
+//! Summaries of generated documentation changes
+//!
+//! Regenerated anchors follow the structure of the markdown builder
+//! (`**Purpose:**`, `**Parameters:**`, `**Returns:**`, ... sections, or
+//! headings), so an update can be described by what changed in that
+//! structure rather than by a line diff: which sections were added, removed,
+//! or rewritten, and which parameters were added, removed, or described
+//! differently. The summary is short enough for a commit message or PR body:
+//!
+//! ```text
+//! - docs/api.md#login: added Usage Example; changed description of `user`
+//! ```
+
+use std::fmt;
+
+/// Section holding one bullet per parameter
+const PARAMETERS: &str = "Parameters";
+
+/// Label of content before the first section
+const INTRODUCTION: &str = "introduction";
+
+/// What changed in one anchor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnchorChange {
+    /// Documentation file containing the anchor
+    pub doc_file: String,
+    /// Anchor ID
+    pub anchor_id: String,
+    /// Sections only in the new content
+    pub added_sections: Vec<String>,
+    /// Sections only in the old content
+    pub removed_sections: Vec<String>,
+    /// Sections in both whose text differs (other than parameters)
+    pub changed_sections: Vec<String>,
+    /// Parameters only in the new content
+    pub added_params: Vec<String>,
+    /// Parameters only in the old content
+    pub removed_params: Vec<String>,
+    /// Parameters whose description differs
+    pub changed_params: Vec<String>,
+}
+
+impl AnchorChange {
+    /// Check whether the structure is unchanged (whitespace is ignored)
+    pub fn is_empty(&self) -> bool {
+        self.added_sections.is_empty()
+            && self.removed_sections.is_empty()
+            && self.changed_sections.is_empty()
+            && self.added_params.is_empty()
+            && self.removed_params.is_empty()
+            && self.changed_params.is_empty()
+    }
+}
+
+impl fmt::Display for AnchorChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = |names: &[String]| -> Vec<String> {
+            names.iter().map(|name| format!("`{}`", name)).collect()
+        };
+        let parts: Vec<String> = [
+            ("added", self.added_sections.clone()),
+            ("removed", self.removed_sections.clone()),
+            ("changed", self.changed_sections.clone()),
+            ("added parameter", code(&self.added_params)),
+            ("removed parameter", code(&self.removed_params)),
+            ("changed description of", code(&self.changed_params)),
+        ]
+        .into_iter()
+        .filter(|(_, names)| !names.is_empty())
+        .map(|(verb, names)| format!("{} {}", verb, names.join(", ")))
+        .collect();
+
+        write!(f, "{}#{}: ", self.doc_file, self.anchor_id)?;
+        if parts.is_empty() {
+            write!(f, "reformatted")
+        } else {
+            write!(f, "{}", parts.join("; "))
+        }
+    }
+}
+
+/// Summarize how an anchor's content changed
+pub fn summarize_anchor_change(
+    doc_file: &str,
+    anchor_id: &str,
+    old_content: &str,
+    new_content: &str,
+) -> AnchorChange {
+    let old = outline(old_content);
+    let new = outline(new_content);
+    let mut change = AnchorChange {
+        doc_file: doc_file.to_string(),
+        anchor_id: anchor_id.to_string(),
+        ..Default::default()
+    };
+
+    diff(
+        &old,
+        &new,
+        &mut change.added_sections,
+        &mut change.removed_sections,
+        &mut change.changed_sections,
+    );
+    // Parameter sections are described per parameter
+    if let Some(index) = change.changed_sections.iter().position(|s| s == PARAMETERS) {
+        change.changed_sections.remove(index);
+        diff(
+            &params(find(&old, PARAMETERS)),
+            &params(find(&new, PARAMETERS)),
+            &mut change.added_params,
+            &mut change.removed_params,
+            &mut change.changed_params,
+        );
+    }
+    change
+}
+
+/// Render changes as a markdown bullet list, omitting unchanged anchors
+pub fn format_changes(changes: &[AnchorChange]) -> String {
+    changes
+        .iter()
+        .filter(|change| !change.is_empty())
+        .map(|change| format!("- {}\n", change))
+        .collect()
+}
+
+/// Split content into (label, normalized body) sections
+fn outline(content: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        let section = if in_fence {
+            None
+        } else {
+            section_label(trimmed)
+        };
+        let rest = match section {
+            Some((label, rest)) => {
+                sections.push((label.to_string(), String::new()));
+                rest
+            }
+            None => {
+                if sections.is_empty() && !trimmed.is_empty() {
+                    sections.push((INTRODUCTION.to_string(), String::new()));
+                }
+                trimmed
+            }
+        };
+        if let Some((_, body)) = sections.last_mut() {
+            append(body, rest);
+        }
+    }
+    sections
+}
+
+/// Label of a `**Label:**` line or heading, and the text after it
+fn section_label(line: &str) -> Option<(&str, &str)> {
+    if let Some(rest) = line.strip_prefix("**") {
+        let end = rest.find(":**")?;
+        return Some((rest[..end].trim(), rest[end + 3..].trim()));
+    }
+    let heading = line.trim_start_matches('#');
+    (heading.len() < line.len() && heading.starts_with(' ')).then(|| (heading.trim(), ""))
+}
+
+/// (name, normalized description) of each ``- `name` ...`` bullet
+fn params(body: Option<&str>) -> Vec<(String, String)> {
+    let mut params: Vec<(String, String)> = Vec::new();
+    for item in body.unwrap_or_default().split("- `").skip(1) {
+        if let Some(end) = item.find('`') {
+            params.push((item[..end].to_string(), item[end + 1..].trim().to_string()));
+        }
+    }
+    params
+}
+
+fn find<'a>(sections: &'a [(String, String)], label: &str) -> Option<&'a str> {
+    sections
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(label))
+        .map(|(_, body)| body.as_str())
+}
+
+/// Append a line with whitespace collapsed, so rewrapping is not a change
+fn append(body: &mut String, line: &str) {
+    for word in line.split_whitespace() {
+        if !body.is_empty() {
+            body.push(' ');
+        }
+        body.push_str(word);
+    }
+}
+
+/// Sort the labels of two outlines into added, removed, and changed
+fn diff(
+    old: &[(String, String)],
+    new: &[(String, String)],
+    added: &mut Vec<String>,
+    removed: &mut Vec<String>,
+    changed: &mut Vec<String>,
+) {
+    for (label, body) in new {
+        match find(old, label) {
+            None => added.push(label.clone()),
+            Some(old_body) if old_body != body => changed.push(label.clone()),
+            Some(_) => {}
+        }
+    }
+    for (label, _) in old {
+        if find(new, label).is_none() {
+            removed.push(label.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "**Purpose:** Signs a user in.\n\n**Parameters:**\n- `user` (`string`): The user name\n- `remember` (optional) (`boolean`): Keep the session\n\n**Returns:** A session token\n";
+
+    #[test]
+    fn test_summarize_anchor_change() {
+        let new = "**Purpose:** Signs a user in.\n\n**Parameters:**\n- `user` (`string`): The user name or email\n- `password` (`string`): The password\n\n**Returns:** A session token\n\n**Usage Example:**\n```ts\n**Not:** a section\n```\n";
+
+        let change = summarize_anchor_change("docs/api.md", "login", OLD, new);
+
+        assert_eq!(change.added_sections, ["Usage Example"]);
+        assert!(change.changed_sections.is_empty());
+        assert_eq!(change.added_params, ["password"]);
+        assert_eq!(change.removed_params, ["remember"]);
+        assert_eq!(change.changed_params, ["user"]);
+        assert_eq!(
+            format_changes(&[change]),
+            "- docs/api.md#login: added Usage Example; added parameter `password`; removed parameter `remember`; changed description of `user`\n"
+        );
+    }
+
+    #[test]
+    fn test_rewrapping_is_not_a_change() {
+        let rewrapped = OLD.replace("Signs a user", "Signs a\nuser");
+        assert!(summarize_anchor_change("a.md", "a", OLD, &rewrapped).is_empty());
+
+        let change = summarize_anchor_change("a.md", "a", "Old intro.\n## Usage\nx", "New intro.");
+        assert_eq!(change.changed_sections, ["introduction"]);
+        assert_eq!(change.removed_sections, ["Usage"]);
+    }
+}
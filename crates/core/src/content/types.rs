@@ -3,7 +3,9 @@
 //! This module contains data structures used throughout the content module
 //! for markdown processing, anchor management, and file discovery.
 
-use std::collections::HashMap;
+use crate::text::SkippedFile;
+use std::collections::BTreeMap;
+use std::ops::Range;
 use std::path::PathBuf;
 
 // ============================================================================
@@ -42,8 +44,24 @@ pub struct SintesiAnchor {
     pub end_line: usize,
 
     /// Content between the start and end tags
-    /// This is the actual documentation text
+    /// This is the actual documentation text, including nested anchors
     pub content: String,
+
+    /// ID of the anchor this one is nested in
+    pub parent: Option<String>,
+
+    /// IDs of anchors nested directly inside this one, in document order
+    pub children: Vec<String>,
+
+    /// Byte range of the start comment (`<!-- sintesi:start ... -->`)
+    pub start_tag_span: Range<usize>,
+
+    /// Byte range of the raw content: from the line after the start comment
+    /// to the beginning of the end comment's line
+    pub content_span: Range<usize>,
+
+    /// Byte range of the end comment (`<!-- sintesi:end ... -->`)
+    pub end_tag_span: Range<usize>,
 }
 
 impl SintesiAnchor {
@@ -97,7 +115,7 @@ impl SintesiAnchor {
 /// Map of anchor IDs to their complete anchor information
 ///
 /// This is the primary data structure returned by the markdown extractor.
-/// It maps unique anchor IDs to their full anchor details.
+/// It maps unique anchor IDs to their full anchor details, in ID order.
 ///
 /// # Example
 /// ```rust,ignore
@@ -115,7 +133,7 @@ impl SintesiAnchor {
 ///     );
 /// }
 /// ```
-pub type AnchorMap = HashMap<String, SintesiAnchor>;
+pub type AnchorMap = BTreeMap<String, SintesiAnchor>;
 
 // ============================================================================
 // Extraction Result Types
@@ -125,7 +143,7 @@ pub type AnchorMap = HashMap<String, SintesiAnchor>;
 ///
 /// Contains all anchors found in a markdown file along with statistics
 /// and any errors encountered during parsing.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ExtractionResult {
     /// All anchors found in the file, indexed by their ID
     pub anchors: AnchorMap,
@@ -139,6 +157,9 @@ pub struct ExtractionResult {
     /// - Unclosed anchors
     /// - Malformed anchor tags
     pub errors: Vec<String>,
+
+    /// Set when the file was not extracted (over the size limit, or not text)
+    pub skipped: Option<SkippedFile>,
 }
 
 impl ExtractionResult {
@@ -154,7 +175,9 @@ impl ExtractionResult {
 
     /// Get a summary of the extraction
     pub fn summary(&self) -> String {
-        if self.is_ok() {
+        if let Some(skipped) = &self.skipped {
+            format!("- Skipped ({})", skipped.reason)
+        } else if self.is_ok() {
             format!("✓ Found {} anchor(s)", self.anchor_count)
         } else {
             format!(
@@ -171,16 +194,6 @@ impl ExtractionResult {
     }
 }
 
-impl Default for ExtractionResult {
-    fn default() -> Self {
-        Self {
-            anchors: HashMap::new(),
-            anchor_count: 0,
-            errors: Vec::new(),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,6 +207,11 @@ mod tests {
             start_line: 1,
             end_line: 10,
             content: "Test content".to_string(),
+            parent: None,
+            children: Vec::new(),
+            start_tag_span: 0..0,
+            content_span: 0..0,
+            end_tag_span: 0..0,
         };
 
         assert_eq!(anchor.symbol_name(), Some("login"));
@@ -209,6 +227,11 @@ mod tests {
             start_line: 5,
             end_line: 15,
             content: "Test".to_string(),
+            parent: None,
+            children: Vec::new(),
+            start_tag_span: 0..0,
+            content_span: 0..0,
+            end_tag_span: 0..0,
         };
 
         assert_eq!(anchor.line_span(), 11);
@@ -223,6 +246,11 @@ mod tests {
             start_line: 1,
             end_line: 2,
             content: "   \n  ".to_string(),
+            parent: None,
+            children: Vec::new(),
+            start_tag_span: 0..0,
+            content_span: 0..0,
+            end_tag_span: 0..0,
         };
 
         assert!(empty_anchor.is_empty());
@@ -238,18 +266,20 @@ mod tests {
     #[test]
     fn test_extraction_result_summary() {
         let ok_result = ExtractionResult {
-            anchors: HashMap::new(),
+            anchors: BTreeMap::new(),
             anchor_count: 5,
             errors: vec![],
+            skipped: None,
         };
 
         assert!(ok_result.is_ok());
         assert_eq!(ok_result.summary(), "✓ Found 5 anchor(s)");
 
         let error_result = ExtractionResult {
-            anchors: HashMap::new(),
+            anchors: BTreeMap::new(),
             anchor_count: 3,
             errors: vec!["Error 1".to_string(), "Error 2".to_string()],
+            skipped: None,
         };
 
         assert!(error_result.has_errors());
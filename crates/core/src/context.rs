@@ -1,17 +1,25 @@
+use crate::content::ArchitectureBrief;
 use crate::crawler::get_project_files;
-use crate::graph::build_graph;
+use crate::graph::{build_graph, module_candidates, normalize_path, ProjectGraph};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PackageJson {
     pub name: Option<String>,
     pub version: Option<String>,
-    pub dependencies: Option<HashMap<String, String>>,
-    pub dev_dependencies: Option<HashMap<String, String>>,
-    pub scripts: Option<HashMap<String, String>>,
+    pub dependencies: Option<BTreeMap<String, String>>,
+    pub dev_dependencies: Option<BTreeMap<String, String>>,
+    pub peer_dependencies: Option<BTreeMap<String, String>>,
+    pub scripts: Option<BTreeMap<String, String>>,
+    pub main: Option<String>,
+    pub module: Option<String>,
+    pub exports: Option<Value>,
+    pub bin: Option<Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -26,7 +34,130 @@ pub struct FileContext {
 pub struct ProjectContext {
     pub files: Vec<FileContext>,
     pub package_json: Option<PackageJson>,
-    // In the future: main_files, project_type, etc.
+    /// Package entry points (package.json and Rust crates, including nested packages)
+    pub entry_points: Vec<EntryPoint>,
+    /// Files reachable through imports (or Rust `mod` declarations) from the
+    /// entry points, sorted
+    pub public_surface: Vec<String>,
+    /// Headings and first paragraphs of the README and `docs/adr/*.md`,
+    /// rendered as markdown for LLM prompts (`None` without either)
+    pub architecture_brief: Option<String>,
+    /// Frameworks used anywhere in the project
+    pub frameworks: Vec<Framework>,
+    /// Workspace packages (package.json and Cargo.toml directories), sorted by path
+    pub packages: Vec<WorkspacePackage>,
+    // In the future: project_type, etc.
+}
+
+/// How a package exposes an entry point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EntryKind {
+    /// package.json `main`
+    Main,
+    /// package.json `module`
+    Module,
+    /// A subpath of package.json `exports`
+    Export,
+    /// An executable in package.json `bin`, or a Rust binary
+    Bin,
+    /// A Rust library crate root (`src/lib.rs`)
+    Lib,
+}
+
+impl EntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryKind::Main => "main",
+            EntryKind::Module => "module",
+            EntryKind::Export => "export",
+            EntryKind::Bin => "bin",
+            EntryKind::Lib => "lib",
+        }
+    }
+}
+
+/// A framework with conventions worth knowing when analyzing or documenting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Framework {
+    NextJs,
+    NestJs,
+    Express,
+    React,
+    Tauri,
+    Actix,
+}
+
+impl Framework {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Framework::NextJs => "nextjs",
+            Framework::NestJs => "nestjs",
+            Framework::Express => "express",
+            Framework::React => "react",
+            Framework::Tauri => "tauri",
+            Framework::Actix => "actix",
+        }
+    }
+
+    /// Framework using an npm package or crate
+    fn from_dependency(name: &str) -> Option<Self> {
+        match name {
+            "next" => Some(Framework::NextJs),
+            "@nestjs/core" => Some(Framework::NestJs),
+            "express" => Some(Framework::Express),
+            "react" | "react-dom" => Some(Framework::React),
+            "tauri" | "@tauri-apps/api" | "@tauri-apps/cli" => Some(Framework::Tauri),
+            "actix-web" => Some(Framework::Actix),
+            _ => None,
+        }
+    }
+
+    /// Framework owning a conventional configuration file
+    fn from_file_name(name: &str) -> Option<Self> {
+        match name {
+            "next.config.js" | "next.config.mjs" | "next.config.ts" => Some(Framework::NextJs),
+            "nest-cli.json" => Some(Framework::NestJs),
+            "tauri.conf.json" | "Tauri.toml" => Some(Framework::Tauri),
+            _ => None,
+        }
+    }
+}
+
+/// An entry point declared by a package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntryPoint {
+    pub kind: EntryKind,
+    /// Export subpath (`.`, `./client`) or binary name
+    pub name: Option<String>,
+    /// Path as declared, relative to the project root (often build output)
+    pub target: String,
+    /// Project source file the entry resolves to, if any
+    pub source: Option<String>,
+}
+
+/// Name of the project root package when its manifest declares none
+pub const ROOT_PACKAGE: &str = "(root)";
+
+/// A package of the workspace: a directory with a package.json, or with a
+/// Cargo.toml declaring `[package]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkspacePackage {
+    /// Name from the manifest (the directory when it declares none)
+    pub name: String,
+    /// Directory relative to the project root, `/`-separated (empty for the root)
+    pub path: String,
+}
+
+impl WorkspacePackage {
+    /// Whether `file` (relative to the project root) is inside the package directory
+    pub fn contains(&self, file: &str) -> bool {
+        self.path.is_empty()
+            || file
+                .strip_prefix(self.path.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
 }
 
 pub fn get_project_context(root_path: &str) -> ProjectContext {
@@ -77,8 +208,503 @@ pub fn get_project_context(root_path: &str) -> ProjectContext {
         });
     }
 
+    let entry_points = detect_entry_points(root, &file_paths);
+    let public_surface = public_surface(&graph, &entry_points);
+    let brief = ArchitectureBrief::collect(root, &file_paths);
+    let frameworks = detect_frameworks(root, &file_paths);
+    let packages = detect_packages(root, &file_paths);
+
     ProjectContext {
         files: file_contexts,
         package_json,
+        entry_points,
+        public_surface,
+        architecture_brief: (!brief.is_empty()).then(|| brief.to_string()),
+        frameworks,
+        packages,
+    }
+}
+
+/// Entry points of every package.json and Cargo.toml in the project
+///
+/// package.json targets usually point at build output (`dist/index.js`); they
+/// resolve to the project file itself when it exists, else to the matching
+/// source (`src/index.ts`). Rust crates use the conventional layout:
+/// `src/lib.rs`, `src/main.rs`, and `src/bin/*.rs`.
+pub fn detect_entry_points(root: &Path, files: &[PathBuf]) -> Vec<EntryPoint> {
+    let known: HashSet<&Path> = files.iter().map(PathBuf::as_path).collect();
+    let mut entries = Vec::new();
+
+    for manifest in files {
+        let dir = manifest.parent().unwrap_or(Path::new(""));
+        match manifest.file_name().and_then(|n| n.to_str()) {
+            Some("package.json") => {
+                let Some(package) = fs::read_to_string(root.join(manifest))
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<PackageJson>(&content).ok())
+                else {
+                    continue;
+                };
+                for (kind, name, target) in package_targets(&package) {
+                    let target = normalize_path(&dir.join(&target));
+                    entries.push(EntryPoint {
+                        kind,
+                        name,
+                        source: resolve_source(&target, &known),
+                        target: slash_path(&target),
+                    });
+                }
+            }
+            Some("Cargo.toml") => {
+                let src = dir.join("src");
+                let crate_roots = [(EntryKind::Lib, src.join("lib.rs")), (EntryKind::Bin, src.join("main.rs"))];
+                let bins = files
+                    .iter()
+                    .filter(|f| f.parent() == Some(&src.join("bin")) && f.extension().is_some_and(|e| e == "rs"))
+                    .map(|f| (EntryKind::Bin, f.clone()));
+                for (kind, path) in crate_roots.into_iter().chain(bins) {
+                    if known.contains(path.as_path()) {
+                        entries.push(EntryPoint {
+                            kind,
+                            name: None,
+                            target: slash_path(&path),
+                            source: Some(slash_path(&path)),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+/// Workspace packages of the project, sorted by path
+///
+/// Every package.json is a package; a Cargo.toml is one when it declares
+/// `[package]` (virtual workspace manifests are not). A directory with both
+/// counts once, named after its Cargo.toml.
+pub fn detect_packages(root: &Path, files: &[PathBuf]) -> Vec<WorkspacePackage> {
+    let mut manifests: Vec<&PathBuf> = files.iter().collect();
+    manifests.sort();
+    let mut packages: BTreeMap<String, String> = BTreeMap::new();
+    for manifest in manifests {
+        let name = match manifest.file_name().and_then(|n| n.to_str()) {
+            Some("package.json") => fs::read_to_string(root.join(manifest))
+                .ok()
+                .and_then(|content| serde_json::from_str::<PackageJson>(&content).ok())
+                .map(|package| package.name),
+            Some("Cargo.toml") => fs::read_to_string(root.join(manifest))
+                .ok()
+                .filter(|content| content.lines().any(|line| line.trim() == "[package]"))
+                .map(|content| cargo_package_name(&content)),
+            _ => None,
+        };
+        let Some(name) = name else {
+            continue;
+        };
+        let path = slash_path(manifest.parent().unwrap_or(Path::new("")));
+        let name = name.unwrap_or_else(|| match path.is_empty() {
+            true => ROOT_PACKAGE.to_string(),
+            false => path.clone(),
+        });
+        packages.entry(path).or_insert(name);
+    }
+    packages
+        .into_iter()
+        .map(|(path, name)| WorkspacePackage { name, path })
+        .collect()
+}
+
+/// Workspace packages of the project at `root` (`detect_packages` over its files)
+pub fn workspace_packages(root: &Path) -> Vec<WorkspacePackage> {
+    let files: Vec<PathBuf> = get_project_files(&root.to_string_lossy())
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+    detect_packages(root, &files)
+}
+
+/// Innermost package containing `file` (relative to the project root)
+pub fn package_of<'a>(
+    packages: &'a [WorkspacePackage],
+    file: &str,
+) -> Option<&'a WorkspacePackage> {
+    packages
+        .iter()
+        .filter(|package| package.contains(file))
+        .max_by_key(|package| package.path.len())
+}
+
+/// Files reachable from the entry points' sources through the import graph
+pub fn public_surface(graph: &ProjectGraph, entries: &[EntryPoint]) -> Vec<String> {
+    let mut seen: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut queue: Vec<PathBuf> = entries
+        .iter()
+        .filter_map(|entry| entry.source.as_ref().map(PathBuf::from))
+        .collect();
+    while let Some(path) = queue.pop() {
+        if seen.insert(path.clone()) {
+            queue.extend(graph.dependencies(&path));
+        }
+    }
+    seen.iter().map(|path| slash_path(path)).collect()
+}
+
+/// Frameworks used by the project, sorted
+///
+/// A framework is detected from the dependencies of any package.json or
+/// Cargo.toml (nested packages and workspace members included), or from its
+/// configuration file (`next.config.js`, `nest-cli.json`, `tauri.conf.json`).
+pub fn detect_frameworks(root: &Path, files: &[PathBuf]) -> Vec<Framework> {
+    let mut frameworks = BTreeSet::new();
+    for file in files {
+        let Some(name) = file.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        frameworks.extend(Framework::from_file_name(name));
+        let dependencies = match name {
+            "package.json" => fs::read_to_string(root.join(file))
+                .ok()
+                .and_then(|content| serde_json::from_str::<PackageJson>(&content).ok())
+                .map(|package| package_dependencies(&package))
+                .unwrap_or_default(),
+            "Cargo.toml" => fs::read_to_string(root.join(file))
+                .map(|content| cargo_dependencies(&content))
+                .unwrap_or_default(),
+            _ => continue,
+        };
+        frameworks.extend(dependencies.iter().filter_map(|d| Framework::from_dependency(d)));
+    }
+    frameworks.into_iter().collect()
+}
+
+/// Names of all (dev, peer) dependencies of a package.json
+fn package_dependencies(package: &PackageJson) -> Vec<String> {
+    [
+        &package.dependencies,
+        &package.dev_dependencies,
+        &package.peer_dependencies,
+    ]
+    .into_iter()
+    .flatten()
+    .flat_map(|deps| deps.keys().cloned())
+    .collect()
+}
+
+/// Crate names in the dependency tables of a Cargo.toml (`[dependencies]`,
+/// `[dev-dependencies]`, `[workspace.dependencies]`, `[dependencies.name]`, ...)
+fn cargo_dependencies(content: &str) -> Vec<String> {
+    let mut dependencies = Vec::new();
+    let mut in_table = false;
+    for line in content.lines().map(str::trim) {
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header.trim_end_matches(']').trim();
+            match header.rsplit_once('.') {
+                Some((table, name)) if table.ends_with("dependencies") => {
+                    dependencies.push(name.trim_matches('"').to_string());
+                    in_table = false;
+                }
+                _ => in_table = header.ends_with("dependencies"),
+            }
+        } else if in_table && !line.starts_with('#') {
+            if let Some((key, _)) = line.split_once('=') {
+                dependencies.push(key.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    dependencies
+}
+
+/// `name` of the `[package]` table of a Cargo.toml
+fn cargo_package_name(content: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if in_package {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim() == "name" {
+                    return Some(value.trim().trim_matches('"').to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// (kind, name, target) of each entry declared by a package.json
+fn package_targets(package: &PackageJson) -> Vec<(EntryKind, Option<String>, String)> {
+    let mut targets = Vec::new();
+    targets.extend(package.main.clone().map(|t| (EntryKind::Main, None, t)));
+    targets.extend(package.module.clone().map(|t| (EntryKind::Module, None, t)));
+
+    match &package.exports {
+        Some(Value::Object(map)) if map.keys().any(|k| k.starts_with('.')) => {
+            for (subpath, value) in map {
+                for target in export_targets(value) {
+                    targets.push((EntryKind::Export, Some(subpath.clone()), target));
+                }
+            }
+        }
+        // A string, array, or conditions object exports the package root
+        Some(value) => {
+            for target in export_targets(value) {
+                targets.push((EntryKind::Export, Some(".".to_string()), target));
+            }
+        }
+        None => {}
+    }
+
+    match &package.bin {
+        Some(Value::String(target)) => {
+            targets.push((EntryKind::Bin, package.name.clone(), target.clone()));
+        }
+        Some(Value::Object(map)) => {
+            for (name, target) in map {
+                if let Value::String(target) = target {
+                    targets.push((EntryKind::Bin, Some(name.clone()), target.clone()));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    targets
+}
+
+/// Distinct string targets of an `exports` value, through nested conditions
+/// (`import`, `require`, `types`, ...); wildcard patterns are skipped
+fn export_targets(value: &Value) -> Vec<String> {
+    fn collect(value: &Value, targets: &mut Vec<String>) {
+        match value {
+            Value::String(target) if !target.contains('*') && !targets.contains(target) => {
+                targets.push(target.clone())
+            }
+            Value::Array(values) => values.iter().for_each(|v| collect(v, targets)),
+            Value::Object(conditions) => conditions.values().for_each(|v| collect(v, targets)),
+            _ => {}
+        }
+    }
+    let mut targets = Vec::new();
+    collect(value, &mut targets);
+    targets
+}
+
+/// Project file for a declared target: the file itself, or the source it is
+/// built from (`dist/index.js`, `lib/index.d.ts` => `src/index.ts`)
+fn resolve_source(target: &Path, known: &HashSet<&Path>) -> Option<String> {
+    if known.contains(target) {
+        return Some(slash_path(target));
+    }
+    let text = slash_path(target);
+    let stem = [".d.ts", ".d.mts", ".d.cts", ".mjs", ".cjs", ".js", ".ts"]
+        .iter()
+        .find_map(|ext| text.strip_suffix(ext))
+        .unwrap_or(&text);
+    let mut bases = vec![PathBuf::from(stem)];
+    for build_dir in ["dist/", "lib/", "build/", "out/"] {
+        if let Some((package, rest)) = split_dir(stem, build_dir) {
+            bases.push(PathBuf::from(format!("{}src/{}", package, rest)));
+        }
+    }
+    bases
+        .iter()
+        .flat_map(|base| module_candidates(base))
+        .find(|candidate| known.contains(candidate.as_path()))
+        .map(|path| slash_path(&path))
+}
+
+/// Split `packages/a/dist/index` at the build directory into (`packages/a/`, `index`)
+fn split_dir<'a>(path: &'a str, dir: &str) -> Option<(&'a str, &'a str)> {
+    if let Some(rest) = path.strip_prefix(dir) {
+        return Some(("", rest));
+    }
+    let index = path.find(&format!("/{}", dir))?;
+    Some((&path[..index + 1], &path[index + 1 + dir.len()..]))
+}
+
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, files: &[(&str, &str)]) {
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_package_entry_points() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                (
+                    "package.json",
+                    r#"{
+                        "name": "auth-kit",
+                        "main": "./dist/index.js",
+                        "exports": {
+                            ".": { "types": "./dist/index.d.ts", "import": "./dist/index.js" },
+                            "./client": "./dist/client.js",
+                            "./utils/*": "./dist/utils/*.js"
+                        },
+                        "bin": "bin/cli.js"
+                    }"#,
+                ),
+                ("bin/cli.js", "require('../dist/index.js');"),
+                ("src/index.ts", "export * from './session';\nimport { token } from './token';"),
+                ("src/session.ts", "export const login = () => {};"),
+                ("src/token.ts", "export const token = 1;"),
+                ("src/client.tsx", "export const Client = () => null;"),
+                ("src/internal.ts", "export const secret = 1;"),
+                ("packages/cli/package.json", r#"{ "bin": { "kit": "./lib/main.js" } }"#),
+                ("packages/cli/src/main.ts", ""),
+            ],
+        );
+
+        let context = get_project_context(dir.path().to_str().unwrap());
+
+        let entries: Vec<(&str, Option<&str>, &str, Option<&str>)> = context
+            .entry_points
+            .iter()
+            .map(|e| (e.kind.as_str(), e.name.as_deref(), e.target.as_str(), e.source.as_deref()))
+            .collect();
+        let index = Some("src/index.ts");
+        for entry in [
+            ("main", None, "dist/index.js", index),
+            ("export", Some("."), "dist/index.d.ts", index),
+            ("export", Some("."), "dist/index.js", index),
+            ("export", Some("./client"), "dist/client.js", Some("src/client.tsx")),
+            ("bin", Some("auth-kit"), "bin/cli.js", Some("bin/cli.js")),
+            ("bin", Some("kit"), "packages/cli/lib/main.js", Some("packages/cli/src/main.ts")),
+        ] {
+            assert!(entries.contains(&entry), "missing {:?} in {:?}", entry, entries);
+        }
+        assert_eq!(entries.len(), 6);
+
+        // The binary's require of the build output has no project file behind it
+        assert_eq!(
+            context.public_surface,
+            [
+                "bin/cli.js",
+                "packages/cli/src/main.ts",
+                "src/client.tsx",
+                "src/index.ts",
+                "src/session.ts",
+                "src/token.ts"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rust_crate_entry_points() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                ("Cargo.toml", "[package]\nname = \"kit\"\n"),
+                ("src/lib.rs", "pub mod auth;\nmod util;\n"),
+                ("src/auth/mod.rs", "mod token;\n"),
+                ("src/auth/token.rs", ""),
+                ("src/util.rs", ""),
+                ("src/bin/kit.rs", ""),
+                ("src/unused.rs", ""),
+            ],
+        );
+
+        let context = get_project_context(dir.path().to_str().unwrap());
+
+        let mut entries: Vec<(&str, &str)> = context
+            .entry_points
+            .iter()
+            .map(|e| (e.kind.as_str(), e.target.as_str()))
+            .collect();
+        entries.sort();
+        assert_eq!(entries, [("bin", "src/bin/kit.rs"), ("lib", "src/lib.rs")]);
+        assert_eq!(
+            context.public_surface,
+            ["src/auth/mod.rs", "src/auth/token.rs", "src/bin/kit.rs", "src/lib.rs", "src/util.rs"]
+        );
+    }
+
+    #[test]
+    fn test_detect_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                ("package.json", "{\"private\": true}"),
+                ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n"),
+                ("packages/auth/package.json", "{\"name\": \"@acme/auth\"}"),
+                ("packages/auth/src/index.ts", ""),
+                (
+                    "crates/kit/Cargo.toml",
+                    "[lib]\nname = \"kit_lib\"\n[package]\nname = \"kit\"\n",
+                ),
+                ("crates/kit/package.json", "{\"name\": \"kit-js\"}"),
+            ],
+        );
+
+        let context = get_project_context(dir.path().to_str().unwrap());
+        let packages: Vec<(&str, &str)> = context
+            .packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.path.as_str()))
+            .collect();
+        assert_eq!(
+            packages,
+            [("(root)", ""), ("kit", "crates/kit"), ("@acme/auth", "packages/auth")]
+        );
+
+        let owner = |file| package_of(&context.packages, file).map(|p| p.name.as_str());
+        assert_eq!(owner("packages/auth/src/index.ts"), Some("@acme/auth"));
+        assert_eq!(owner("packages/authz/index.ts"), Some("(root)"));
+        assert_eq!(owner("crates/kit"), Some("(root)"));
+    }
+
+    #[test]
+    fn test_detect_frameworks() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                (
+                    "package.json",
+                    r#"{ "dependencies": { "react": "^18" }, "devDependencies": { "@tauri-apps/cli": "^2" } }"#,
+                ),
+                ("apps/api/package.json", r#"{ "dependencies": { "@nestjs/core": "^10" } }"#),
+                ("apps/web/next.config.mjs", "export default {};"),
+                (
+                    "crates/server/Cargo.toml",
+                    "[package]\nname = \"express\"\n\n[dependencies]\nserde = \"1\"\n\n[dependencies.actix-web]\nversion = \"4\"\n",
+                ),
+            ],
+        );
+
+        let context = get_project_context(dir.path().to_str().unwrap());
+
+        assert_eq!(
+            context.frameworks,
+            [
+                Framework::NextJs,
+                Framework::NestJs,
+                Framework::React,
+                Framework::Tauri,
+                Framework::Actix
+            ]
+        );
+        assert_eq!(
+            cargo_dependencies("[workspace.dependencies]\ntauri = { version = \"2\" }\n# axum = \"0.7\"\n[target.'cfg(unix)'.dev-dependencies]\n\"actix-web\" = \"4\"\n[features]\ndefault = []\n"),
+            ["tauri", "actix-web"]
+        );
     }
 }
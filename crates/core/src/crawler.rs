@@ -1,5 +1,9 @@
+use crate::paths::extended_length;
 use ignore::WalkBuilder;
-use std::path::PathBuf;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct FileInfo {
@@ -7,9 +11,40 @@ pub struct FileInfo {
     pub extension: Option<String>,
 }
 
+/// Options for `get_project_files_with`
+#[derive(Debug, Clone, Default)]
+pub struct CrawlOptions {
+    /// Skip files whose content matches a file already listed
+    pub dedup_content: bool,
+}
+
+impl CrawlOptions {
+    /// Create default options (hard links deduplicated, content not compared)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skip files whose content matches a file already listed
+    pub fn dedup_content(mut self, value: bool) -> Self {
+        self.dedup_content = value;
+        self
+    }
+}
+
 pub fn get_project_files(root_path: &str) -> Vec<FileInfo> {
+    get_project_files_with(root_path, &CrawlOptions::new())
+}
+
+/// List project files, each unique file once
+///
+/// Hard links of a file already listed (pnpm's `node_modules`, hardlinked
+/// vendored trees) are always skipped; with `dedup_content`, so are copies
+/// with identical content. The first path in walk order is kept.
+pub fn get_project_files_with(root_path: &str, options: &CrawlOptions) -> Vec<FileInfo> {
     let mut files = Vec::new();
-    let walker = WalkBuilder::new(root_path)
+    let mut unique = UniqueFiles::new().by_content(options.dedup_content);
+    let root = extended_length(Path::new(root_path));
+    let walker = WalkBuilder::new(&root)
         .hidden(false) // Allow hidden files (like .env), gitignore will still handle .git
         .git_ignore(true)
         .build();
@@ -17,10 +52,10 @@ pub fn get_project_files(root_path: &str) -> Vec<FileInfo> {
     for result in walker {
         match result {
             Ok(entry) => {
-                if entry.file_type().map_or(false, |ft| ft.is_file()) {
+                if entry.file_type().is_some_and(|ft| ft.is_file()) {
                     let path = entry.path();
                     // Get path relative to root if possible
-                    let rel_path = match path.strip_prefix(root_path) {
+                    let rel_path = match path.strip_prefix(&root) {
                         Ok(p) => p.to_path_buf(),
                         Err(_) => path.to_path_buf(),
                     };
@@ -30,6 +65,9 @@ pub fn get_project_files(root_path: &str) -> Vec<FileInfo> {
                     if rel_path.components().any(|c| c.as_os_str() == ".git") {
                         continue;
                     }
+                    if !unique.insert(path) {
+                        continue;
+                    }
 
                     files.push(FileInfo {
                         path: rel_path.clone(),
@@ -43,3 +81,115 @@ pub fn get_project_files(root_path: &str) -> Vec<FileInfo> {
 
     files
 }
+
+/// Tracks which files were already seen, by inode and optionally by content
+///
+/// Content is only hashed once two files of the same size have been seen,
+/// so crawls without duplicates read no file contents.
+#[derive(Debug, Default)]
+pub struct UniqueFiles {
+    by_content: bool,
+    /// (device, inode) of seen files
+    ids: HashSet<(u64, u64)>,
+    /// Seen files per size that have not been hashed yet
+    unhashed: HashMap<u64, Vec<PathBuf>>,
+    /// (size, SHA-256) of hashed files
+    hashes: HashSet<(u64, [u8; 32])>,
+}
+
+impl UniqueFiles {
+    /// Detect hard links only
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also detect files with identical content
+    pub fn by_content(mut self, value: bool) -> Self {
+        self.by_content = value;
+        self
+    }
+
+    /// Record a file, returning `false` if it duplicates one seen before
+    ///
+    /// Files whose metadata cannot be read are treated as unique.
+    pub fn insert(&mut self, path: &Path) -> bool {
+        let Ok(metadata) = fs::metadata(path) else {
+            return true;
+        };
+        if let Some(id) = file_id(&metadata) {
+            if !self.ids.insert(id) {
+                return false;
+            }
+        }
+        if !self.by_content {
+            return true;
+        }
+
+        let size = metadata.len();
+        let Some(pending) = self.unhashed.get_mut(&size) else {
+            self.unhashed.insert(size, vec![path.to_path_buf()]);
+            return true;
+        };
+        for earlier in std::mem::take(pending) {
+            if let Some(hash) = content_hash(&earlier) {
+                self.hashes.insert((size, hash));
+            }
+        }
+        content_hash(path).is_none_or(|hash| self.hashes.insert((size, hash)))
+    }
+}
+
+/// Identity shared by all hard links of a file
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+fn content_hash(path: &Path) -> Option<[u8; 32]> {
+    fs::read(path)
+        .ok()
+        .map(|bytes| Sha256::digest(bytes).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates_are_listed_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("store")).unwrap();
+        fs::create_dir_all(root.join("vendor")).unwrap();
+        fs::write(root.join("store/lib.js"), "module.exports = 1;").unwrap();
+        fs::write(root.join("store/other.js"), "module.exports = 2;").unwrap();
+        fs::write(root.join("vendor/copy.js"), "module.exports = 1;").unwrap();
+        fs::hard_link(root.join("store/lib.js"), root.join("vendor/lib.js")).unwrap();
+
+        let root_path = root.to_str().unwrap();
+        let count = |options: &CrawlOptions| get_project_files_with(root_path, options).len();
+        if cfg!(unix) {
+            assert_eq!(count(&CrawlOptions::new()), 3);
+        }
+        assert_eq!(count(&CrawlOptions::new().dedup_content(true)), 2);
+    }
+
+    #[test]
+    fn test_deep_paths_are_relative_to_the_root() {
+        // Longer than Windows' MAX_PATH (260 characters)
+        let dir = tempfile::tempdir().unwrap();
+        let relative = (0..25).fold(PathBuf::new(), |path, _| path.join("node_modules"));
+        fs::create_dir_all(dir.path().join(&relative)).unwrap();
+        fs::write(dir.path().join(&relative).join("index.js"), "").unwrap();
+
+        let files = get_project_files(dir.path().to_str().unwrap());
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, relative.join("index.js"));
+    }
+}
@@ -0,0 +1,538 @@
+//! Daemon state
+//!
+//! Long-lived project state for watch/daemon mode. When a file changes, only
+//! that file is re-analyzed and the project graph, analysis cache, anchor
+//! index, and search index are patched in place instead of being rebuilt.
+//!
+//! ## Concurrency
+//!
+//! `ProjectState` is `Send + Sync` and is meant to be shared (e.g. in an
+//! `Arc`) between the watcher and request handlers. Each component sits
+//! behind its own `RwLock`:
+//!
+//! - Parsing and anchor extraction happen before any lock is taken, so
+//!   concurrent updates of different files only serialize on the brief patch
+//! - At most one lock is held at a time, so lock ordering cannot deadlock
+//! - Readers get snapshots (clones) and never hold locks across calls;
+//!   searches only hold the search index lock while picking candidate files
+//!
+//! Updates of the same file are applied in the order their write locks are
+//! acquired; the watcher is expected to debounce events per file.
+//!
+//! After a checkout or pull, `reindex_changed` refreshes only the files git
+//! reports as changed instead of loading the project again.
+
+use crate::ast::{AnalysisResult, AnalyzerOptions, AstAnalyzerInternal, SymbolFilter, SymbolInfo};
+use crate::content::{discover_files, extract_anchors, AnchorMap, DiscoveryConfig, SintesiAnchor};
+use crate::error::Error;
+use crate::git::GitService;
+use crate::graph::{build_graph, ProjectGraph};
+use crate::paths::canonical;
+use crate::provider::DiskProvider;
+use crate::search::{search_files, SearchOptions, SearchPage, TrigramIndex};
+use crate::text::read_text;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// What changed when a file was updated or removed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileUpdate {
+    /// File path relative to the project root
+    pub path: PathBuf,
+    /// The file's exported API (symbol names and signatures) changed
+    pub symbols_changed: bool,
+    /// The file's anchors (IDs, code_refs, or content) changed
+    pub anchors_changed: bool,
+    /// Files that import the updated file and may need re-checking
+    pub dependents: Vec<PathBuf>,
+}
+
+/// Incrementally maintained project state
+pub struct ProjectState {
+    root: PathBuf,
+    analyzer: AstAnalyzerInternal,
+    graph: RwLock<ProjectGraph>,
+    analysis: RwLock<BTreeMap<PathBuf, AnalysisResult>>,
+    anchors: RwLock<BTreeMap<PathBuf, AnchorMap>>,
+    search_index: RwLock<TrigramIndex>,
+}
+
+impl ProjectState {
+    /// Create empty state for a project root
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            analyzer: AstAnalyzerInternal::new(),
+            graph: RwLock::new(ProjectGraph::new()),
+            analysis: RwLock::new(BTreeMap::new()),
+            anchors: RwLock::new(BTreeMap::new()),
+            search_index: RwLock::new(TrigramIndex::new()),
+        }
+    }
+
+    /// Discover and analyze the whole project once
+    pub fn load(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let discovered = discover_files(&root, DiscoveryConfig::new());
+        let relative = |path: &PathBuf| path.strip_prefix(&root).unwrap_or(path).to_path_buf();
+
+        let source_files: Vec<PathBuf> = discovered.source_files.iter().map(relative).collect();
+        let filter = SymbolFilter::from_provider(&DiskProvider::new(&root));
+        let analyzer = AstAnalyzerInternal::with_options(AnalyzerOptions::new().filter(filter));
+        let mut analysis = BTreeMap::new();
+        for path in &source_files {
+            if let Ok(content) = read_text(&root.join(path)) {
+                analysis.insert(path.clone(), analyze(&analyzer, path, &content));
+            }
+        }
+
+        let mut anchors = BTreeMap::new();
+        for path in discovered.markdown_files.iter().map(relative) {
+            if let Ok(content) = read_text(&root.join(&path)) {
+                let extracted = extract_anchors(&path, &content).anchors;
+                anchors.insert(path, extracted);
+            }
+        }
+
+        Self {
+            graph: RwLock::new(build_graph(&source_files, &root)),
+            analysis: RwLock::new(analysis),
+            anchors: RwLock::new(anchors),
+            search_index: RwLock::new(TrigramIndex::build(&DiskProvider::new(&root))),
+            analyzer,
+            root,
+        }
+    }
+
+    /// Project root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Apply new content for a file (path relative to the root)
+    ///
+    /// Only files discovery classifies as source are analyzed and added to
+    /// the graph, and only markdown files are scanned for anchors; other
+    /// files (lockfiles, assets) only update the search index.
+    pub fn update_file(&self, path: impl AsRef<Path>, content: &str) -> FileUpdate {
+        let path = path.as_ref().to_path_buf();
+        write(&self.search_index).update_file(&path_key(&path), content);
+
+        if is_markdown(&path) {
+            let extracted = extract_anchors(&path, content).anchors;
+            let previous = write(&self.anchors).insert(path.clone(), extracted.clone());
+            return FileUpdate {
+                anchors_changed: previous.is_none_or(|old| !same_anchors(&old, &extracted)),
+                path,
+                ..Default::default()
+            };
+        }
+        // Lockfiles, assets, and the like are only searched
+        if !is_source(&path) {
+            return FileUpdate {
+                path,
+                ..Default::default()
+            };
+        }
+
+        let result = analyze(&self.analyzer, &path, content);
+        let symbols_changed = {
+            let mut analysis = write(&self.analysis);
+            let changed = analysis
+                .get(&path)
+                .is_none_or(|old| api_of(&old.symbols) != api_of(&result.symbols));
+            analysis.insert(path.clone(), result);
+            changed
+        };
+        write(&self.graph).update_file(path.clone(), content);
+
+        FileUpdate {
+            symbols_changed,
+            dependents: self.dependents(&path),
+            path,
+            ..Default::default()
+        }
+    }
+
+    /// Re-read a file from disk, treating a missing file as removed
+    pub fn refresh_file(&self, path: impl AsRef<Path>) -> FileUpdate {
+        let path = path.as_ref();
+        match read_text(&self.root.join(path)) {
+            Ok(content) => self.update_file(path, &content),
+            Err(_) => self.remove_file(path),
+        }
+    }
+
+    /// Refresh the files that differ from a git revision
+    ///
+    /// `base_ref` is the revision the state was last indexed at (`HEAD` when
+    /// `None`). Files changed since then, committed or not (untracked files
+    /// included), are re-read if they are under the root; deleted files are
+    /// forgotten. Returns one update per file, in git's order.
+    ///
+    /// # Errors
+    /// `Error::Git` when the root is not in a git working tree or `base_ref`
+    /// does not resolve.
+    pub fn reindex_changed(&self, base_ref: Option<&str>) -> Result<Vec<FileUpdate>, Error> {
+        let git = GitService::open(&self.root)?;
+        let workdir = git.workdir()?;
+        let root = canonical(&self.root);
+        let updates = git
+            .get_changed_files(base_ref, false)?
+            .iter()
+            .filter_map(|path| {
+                workdir
+                    .join(path)
+                    .strip_prefix(&root)
+                    .ok()
+                    .map(Path::to_path_buf)
+            })
+            .map(|path| self.refresh_file(path))
+            .collect();
+        Ok(updates)
+    }
+
+    /// Forget a deleted file
+    pub fn remove_file(&self, path: impl AsRef<Path>) -> FileUpdate {
+        let path = path.as_ref().to_path_buf();
+        write(&self.search_index).remove_file(&path_key(&path));
+
+        if is_markdown(&path) {
+            let previous = write(&self.anchors).remove(&path);
+            return FileUpdate {
+                anchors_changed: previous.is_some_and(|old| !old.is_empty()),
+                path,
+                ..Default::default()
+            };
+        }
+        if !is_source(&path) {
+            return FileUpdate {
+                path,
+                ..Default::default()
+            };
+        }
+
+        // Capture dependents before the node and its edges disappear
+        let dependents = self.dependents(&path);
+        let previous = write(&self.analysis).remove(&path);
+        write(&self.graph).remove_file(&path);
+
+        FileUpdate {
+            symbols_changed: previous.is_some_and(|old| !old.symbols.is_empty()),
+            dependents,
+            path,
+            ..Default::default()
+        }
+    }
+
+    /// Cached symbols of a source file
+    pub fn symbols(&self, path: impl AsRef<Path>) -> Option<Vec<SymbolInfo>> {
+        read(&self.analysis)
+            .get(path.as_ref())
+            .map(|result| result.symbols.clone())
+    }
+
+    /// Cached symbols of all source files, in file order
+    pub fn all_symbols(&self) -> Vec<SymbolInfo> {
+        read(&self.analysis)
+            .values()
+            .flat_map(|result| result.symbols.iter().cloned())
+            .collect()
+    }
+
+    /// Find an anchor by ID in any markdown file
+    pub fn anchor(&self, id: &str) -> Option<SintesiAnchor> {
+        read(&self.anchors)
+            .values()
+            .find_map(|anchors| anchors.get(id).cloned())
+    }
+
+    /// All anchors, ordered by file and line
+    pub fn anchors(&self) -> Vec<SintesiAnchor> {
+        let mut anchors: Vec<SintesiAnchor> = read(&self.anchors)
+            .values()
+            .flat_map(|anchors| anchors.values().cloned())
+            .collect();
+        anchors.sort_by(|a, b| (&a.file_path, a.start_line).cmp(&(&b.file_path, b.start_line)));
+        anchors
+    }
+
+    /// Files that import `path`
+    pub fn dependents(&self, path: impl AsRef<Path>) -> Vec<PathBuf> {
+        read(&self.graph).dependents(path.as_ref())
+    }
+
+    /// Files imported by `path`
+    pub fn dependencies(&self, path: impl AsRef<Path>) -> Vec<PathBuf> {
+        read(&self.graph).dependencies(path.as_ref())
+    }
+
+    /// Search the project's files, reading from disk
+    ///
+    /// Literal searches only read the files the search index lists as
+    /// candidates; see `search::TrigramIndex`.
+    ///
+    /// # Errors
+    /// Same as `search::search_project`.
+    pub fn search(&self, pattern: &str, options: &SearchOptions) -> Result<SearchPage, Error> {
+        let files = read(&self.search_index).candidate_files(pattern, options);
+        search_files(&DiskProvider::new(&self.root), files, pattern, options)
+    }
+}
+
+/// Analyze a file, prefixing errors with its path
+fn analyze(analyzer: &AstAnalyzerInternal, path: &Path, content: &str) -> AnalysisResult {
+    let relative = path.to_string_lossy().replace('\\', "/");
+    let mut result = analyzer.analyze_file(&relative, content);
+    for error in &mut result.errors {
+        *error = format!("{}: {}", relative, error);
+    }
+    result
+}
+
+/// Search index key of a path (forward slashes)
+fn path_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Whether discovery classifies a path as markdown
+fn is_markdown(path: &Path) -> bool {
+    DiscoveryConfig::new()
+        .classify(path)
+        .is_some_and(|file| file.is_markdown())
+}
+
+/// Whether discovery classifies a path as source, which is analyzed and
+/// part of the graph
+fn is_source(path: &Path) -> bool {
+    DiscoveryConfig::new()
+        .classify(path)
+        .is_some_and(|file| file.is_source())
+}
+
+/// The parts of a symbol list that documentation depends on
+fn api_of(symbols: &[SymbolInfo]) -> Vec<(&str, &str, bool)> {
+    symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.signature.as_str(), s.is_exported))
+        .collect()
+}
+
+fn same_anchors(a: &AnchorMap, b: &AnchorMap) -> bool {
+    a.len() == b.len()
+        && a.iter().all(|(id, anchor)| {
+            b.get(id).is_some_and(|other| {
+                other.code_ref == anchor.code_ref && other.content == anchor.content
+            })
+        })
+}
+
+// A panic while a lock was held leaves data that was fully replaced or not
+// touched at all (every write is a single insert/remove), so poisoned locks
+// are safe to keep using.
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::write(
+            dir.path().join("src/auth.ts"),
+            "export function login(user: string): boolean { return true; }",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("src/app.ts"),
+            "import { login } from './auth';\nexport const app = 1;",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("docs/auth.md"),
+            "<!-- sintesi:start id=\"a\" code_ref=\"src/auth.ts#login\" -->\nLogs in.\n<!-- sintesi:end id=\"a\" -->\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_incremental_source_update() {
+        let dir = project();
+        let state = ProjectState::load(dir.path());
+        assert_eq!(state.all_symbols().len(), 2);
+
+        // Body-only change: same API
+        let update = state.update_file(
+            "src/auth.ts",
+            "export function login(user: string): boolean { return false; }",
+        );
+        assert!(!update.symbols_changed);
+        assert_eq!(update.dependents, [PathBuf::from("src/app.ts")]);
+
+        let update = state.update_file(
+            "src/auth.ts",
+            "export function login(user: string, otp: string): boolean { return true; }",
+        );
+        assert!(update.symbols_changed);
+        assert!(state.symbols("src/auth.ts").unwrap()[0]
+            .signature
+            .contains("otp"));
+
+        let update = state.remove_file("src/auth.ts");
+        assert!(update.symbols_changed);
+        assert_eq!(update.dependents, [PathBuf::from("src/app.ts")]);
+        assert!(state.dependencies("src/app.ts").is_empty());
+        assert!(state.symbols("src/auth.ts").is_none());
+    }
+
+    #[test]
+    fn test_incremental_markdown_update() {
+        let dir = project();
+        let state = ProjectState::load(dir.path());
+        assert_eq!(state.anchor("a").unwrap().content, "Logs in.");
+
+        fs::write(
+            dir.path().join("docs/auth.md"),
+            "<!-- sintesi:start id=\"a\" code_ref=\"src/auth.ts#login\" -->\nSigns in.\n<!-- sintesi:end id=\"a\" -->\n",
+        )
+        .unwrap();
+        assert!(state.refresh_file("docs/auth.md").anchors_changed);
+        assert_eq!(state.anchor("a").unwrap().content, "Signs in.");
+
+        fs::remove_file(dir.path().join("docs/auth.md")).unwrap();
+        assert!(state.refresh_file("docs/auth.md").anchors_changed);
+        assert!(state.anchors().is_empty());
+    }
+
+    #[test]
+    fn test_search_follows_updates() {
+        let dir = project();
+        let state = ProjectState::load(dir.path());
+        let literal = SearchOptions::new().fixed_string(true);
+        let paths = |page: SearchPage| -> Vec<String> {
+            page.results.into_iter().map(|r| r.file_path).collect()
+        };
+        assert_eq!(
+            paths(state.search("login", &literal).unwrap()),
+            ["docs/auth.md", "src/app.ts", "src/auth.ts"]
+        );
+
+        let content = "export function signIn(user: string): boolean { return true; }";
+        fs::write(dir.path().join("src/auth.ts"), content).unwrap();
+        state.refresh_file("src/auth.ts");
+        assert_eq!(
+            paths(state.search("signIn", &literal).unwrap()),
+            ["src/auth.ts"]
+        );
+
+        fs::remove_file(dir.path().join("src/app.ts")).unwrap();
+        state.refresh_file("src/app.ts");
+        assert_eq!(
+            paths(state.search("login", &literal).unwrap()),
+            ["docs/auth.md"]
+        );
+    }
+
+    #[test]
+    fn test_reindex_changed_files() {
+        let dir = project();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Ana").unwrap();
+        config.set_str("user.email", "ana@example.com").unwrap();
+        let files = ["src/auth.ts", "src/app.ts", "docs/auth.md"].map(|f| dir.path().join(f));
+        let git = GitService::open(dir.path()).unwrap();
+        git.commit_paths(&files, "init").unwrap();
+        let state = ProjectState::load(dir.path());
+        assert!(state.reindex_changed(None).unwrap().is_empty());
+
+        let content = "export function login(user: string, otp: string): boolean { return true; }";
+        fs::write(dir.path().join("src/auth.ts"), content).unwrap();
+        fs::remove_file(dir.path().join("docs/auth.md")).unwrap();
+        fs::write(dir.path().join("src/new.ts"), "export const b = 2;").unwrap();
+        fs::create_dir(dir.path().join("src/billing")).unwrap();
+        fs::write(dir.path().join("src/billing/pay.ts"), "export const c = 3;").unwrap();
+        fs::write(
+            dir.path().join("package-lock.json"),
+            "{\"name\": \"login\"}",
+        )
+        .unwrap();
+
+        let mut updates = state.reindex_changed(None).unwrap();
+        updates.sort_by(|a, b| a.path.cmp(&b.path));
+        let paths: Vec<&Path> = updates.iter().map(|u| u.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("docs/auth.md"),
+                Path::new("package-lock.json"),
+                Path::new("src/auth.ts"),
+                Path::new("src/billing/pay.ts"),
+                Path::new("src/new.ts")
+            ]
+        );
+        assert!(updates
+            .iter()
+            .filter(|u| u.path != Path::new("package-lock.json"))
+            .all(|u| u.symbols_changed || u.anchors_changed));
+        // Only searched, never analyzed
+        assert!(state.symbols("package-lock.json").is_none());
+        let literal = SearchOptions::new().fixed_string(true);
+        let found = state.search("login", &literal).unwrap().results;
+        assert!(found.iter().any(|r| r.file_path == "package-lock.json"));
+        assert!(state.anchors().is_empty());
+        assert!(state.symbols("src/new.ts").is_some());
+        assert!(state.symbols("src/billing/pay.ts").is_some());
+        assert!(state.symbols("src/auth.ts").unwrap()[0]
+            .signature
+            .contains("otp"));
+
+        assert!(state.reindex_changed(Some("no-such-ref")).is_err());
+    }
+
+    #[test]
+    fn test_concurrent_updates() {
+        let state = Arc::new(ProjectState::new("."));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let state = Arc::clone(&state);
+                thread::spawn(move || {
+                    let content = format!(
+                        "import {{ x }} from './m{}';\nexport const v{} = {};",
+                        (i + 1) % 8,
+                        i,
+                        i
+                    );
+                    state.update_file(format!("src/m{}.ts", i), &content);
+                    state.dependents(format!("src/m{}.ts", i))
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(state.all_symbols().len(), 8);
+        // The ring of imports is complete regardless of update order
+        for i in 0..8 {
+            assert_eq!(
+                state.dependencies(format!("src/m{}.ts", i)),
+                [PathBuf::from(format!("src/m{}.ts", (i + 1) % 8))]
+            );
+        }
+    }
+}
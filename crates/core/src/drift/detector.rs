@@ -0,0 +1,642 @@
+//! Drift detection
+//!
+//! Compares the signature hash recorded in each map entry with the hash of
+//! the symbol's current signature. An entry is in sync when they match,
+//! drifted when they differ, and removed when the symbol no longer exists.
+//! Entries whose symbol or file is excluded by an ignore directive are
+//! reported as ignored, with the directive's reason.
+//!
+//! Deprecation (`@deprecated`) is not part of the signature hash, so the map
+//! records it separately: an entry whose symbol was deprecated or
+//! un-deprecated since the documentation was written is drifted, with the
+//! change in `DriftEntry::deprecation`, so the docs get a migration note.
+//!
+//! Drifted and removed entries carry a `ChangeSeverity`: removed symbols are
+//! breaking, and a changed signature is classified against the signature
+//! recorded in the map (`ast::diff::classify`). Entries recorded before the
+//! map kept signatures have no severity, which gates treat as breaking.
+//!
+//! With a `TestMap` (`ast::test_mapping`), entries also count the tests
+//! exercising their symbol, so reports can flag drifted symbols no test
+//! covers (`DriftResult::untested`).
+
+use super::map::SintesiMap;
+use crate::ast::diff::{classify, ChangeSeverity};
+use crate::ast::reexports::resolve_reexport;
+use crate::ast::test_mapping::TestMap;
+use crate::ast::{
+    symbol_id, symbol_key, AnalysisResult, AnalyzerOptions, AstAnalyzerInternal, SignatureHasher,
+    SymbolFilter,
+};
+use crate::provider::{ContentProvider, DiskProvider};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// Drift state of a documented symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftStatus {
+    /// Documentation matches the current signature
+    InSync,
+    /// The signature changed since the documentation was written
+    Drifted,
+    /// The symbol (or its file) no longer exists
+    Removed,
+    /// The symbol (or its file) is excluded by an ignore directive
+    Ignored,
+}
+
+/// Deprecation change of a documented symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeprecationChange {
+    /// The symbol was deprecated since the documentation was written
+    Deprecated,
+    /// The symbol is no longer deprecated
+    Undeprecated,
+}
+
+impl DeprecationChange {
+    /// Lowercase name (`deprecated`, `undeprecated`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeprecationChange::Deprecated => "deprecated",
+            DeprecationChange::Undeprecated => "undeprecated",
+        }
+    }
+
+    /// Short description for reports (`newly deprecated`, `no longer deprecated`)
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeprecationChange::Deprecated => "newly deprecated",
+            DeprecationChange::Undeprecated => "no longer deprecated",
+        }
+    }
+
+    /// Whether the symbol is deprecated after the change
+    pub fn is_deprecated(&self) -> bool {
+        *self == DeprecationChange::Deprecated
+    }
+}
+
+/// Drift result for a single map entry
+#[derive(Debug, Clone)]
+pub struct DriftEntry {
+    /// Anchor ID of the map entry
+    pub entry_id: String,
+    /// Code reference (`file_path#symbol_name`)
+    pub code_ref: String,
+    /// Markdown file containing the anchor
+    pub doc_file: String,
+    /// Drift state
+    pub status: DriftStatus,
+    /// Hash recorded in the map
+    pub expected_hash: String,
+    /// Hash of the current signature, if the symbol still exists
+    pub current_hash: Option<String>,
+    /// Current signature, if the symbol still exists and it was looked up
+    pub current_signature: Option<String>,
+    /// Reason given by the ignore directive, for ignored entries
+    pub ignore_reason: Option<String>,
+    /// Deprecation change since the documentation was written
+    pub deprecation: Option<DeprecationChange>,
+    /// Impact of the change on readers of the documentation, for drifted and
+    /// removed entries (`None` if it cannot be told)
+    pub severity: Option<ChangeSeverity>,
+    /// Tests exercising the symbol (`None` unless tests were mapped with
+    /// `DriftResult::with_tests`)
+    pub test_count: Option<usize>,
+}
+
+/// Result of comparing a map against the current code
+#[derive(Debug, Clone, Default)]
+pub struct DriftResult {
+    /// One result per map entry, in map order
+    pub entries: Vec<DriftEntry>,
+    /// Drift state by code reference, in code reference order
+    pub symbol_status: BTreeMap<String, DriftStatus>,
+}
+
+impl DriftResult {
+    /// Entries that are drifted or removed
+    pub fn drifted(&self) -> impl Iterator<Item = &DriftEntry> {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, DriftStatus::Drifted | DriftStatus::Removed))
+    }
+
+    /// Entries excluded by ignore directives
+    pub fn ignored(&self) -> impl Iterator<Item = &DriftEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.status == DriftStatus::Ignored)
+    }
+
+    /// Mark entries excluded by ignore directives as ignored
+    ///
+    /// `ignored` maps code references (`file#symbol`) or whole file paths to
+    /// the directive's reason.
+    pub fn with_ignored(mut self, ignored: &HashMap<String, Option<String>>) -> Self {
+        for entry in &mut self.entries {
+            let file = entry.code_ref.split('#').next().unwrap_or(&entry.code_ref);
+            if let Some(reason) = ignored.get(&entry.code_ref).or_else(|| ignored.get(file)) {
+                entry.status = DriftStatus::Ignored;
+                entry.ignore_reason = reason.clone();
+                self.symbol_status
+                    .insert(entry.code_ref.clone(), DriftStatus::Ignored);
+            }
+        }
+        self
+    }
+
+    /// Entries whose symbol was deprecated or un-deprecated
+    pub fn deprecation_changes(&self) -> impl Iterator<Item = &DriftEntry> {
+        self.entries.iter().filter(|e| e.deprecation.is_some())
+    }
+
+    /// Record deprecation changes against the map, marking entries drifted
+    ///
+    /// `deprecated` holds the code references of currently deprecated
+    /// symbols. Removed and ignored entries are left as they are.
+    pub fn with_deprecations(mut self, map: &SintesiMap, deprecated: &HashSet<String>) -> Self {
+        for (entry, map_entry) in self.entries.iter_mut().zip(&map.entries) {
+            if matches!(entry.status, DriftStatus::Removed | DriftStatus::Ignored) {
+                continue;
+            }
+            let is_deprecated = deprecated.contains(&entry.code_ref);
+            if is_deprecated == map_entry.deprecated {
+                continue;
+            }
+            entry.deprecation = Some(if is_deprecated {
+                DeprecationChange::Deprecated
+            } else {
+                DeprecationChange::Undeprecated
+            });
+            entry.status = DriftStatus::Drifted;
+            self.symbol_status
+                .insert(entry.code_ref.clone(), DriftStatus::Drifted);
+        }
+        self
+    }
+
+    /// Record current signatures and classify drifted and removed entries
+    ///
+    /// `signatures` holds the current signature by code reference. Removed
+    /// symbols are breaking; entries drifted only by a deprecation change are
+    /// not. A changed signature is classified against the one recorded in the
+    /// map, and left unclassified if the map has none.
+    pub fn with_severities(
+        mut self,
+        map: &SintesiMap,
+        signatures: &HashMap<String, String>,
+    ) -> Self {
+        for (entry, map_entry) in self.entries.iter_mut().zip(&map.entries) {
+            entry.current_signature = signatures.get(&entry.code_ref).cloned();
+            entry.severity = match entry.status {
+                DriftStatus::Removed => Some(ChangeSeverity::Breaking),
+                DriftStatus::Drifted
+                    if entry.current_hash.as_ref() == Some(&entry.expected_hash) =>
+                {
+                    Some(ChangeSeverity::NonBreaking)
+                }
+                DriftStatus::Drifted => map_entry
+                    .code_signature
+                    .as_deref()
+                    .zip(entry.current_signature.as_deref())
+                    .map(|(old, new)| classify(old, new)),
+                DriftStatus::InSync | DriftStatus::Ignored => None,
+            };
+        }
+        self
+    }
+
+    /// Entries whose drift is breaking or unclassified
+    pub fn breaking(&self) -> impl Iterator<Item = &DriftEntry> {
+        self.drifted()
+            .filter(|e| e.severity != Some(ChangeSeverity::NonBreaking))
+    }
+
+    /// Record how many tests exercise each entry's symbol
+    pub fn with_tests(mut self, tests: &TestMap) -> Self {
+        for entry in &mut self.entries {
+            entry.test_count = Some(tests.coverage_count(&entry.code_ref));
+        }
+        self
+    }
+
+    /// Drifted entries whose symbol no test exercises (none unless tests
+    /// were mapped)
+    pub fn untested(&self) -> impl Iterator<Item = &DriftEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.status == DriftStatus::Drifted && e.test_count == Some(0))
+    }
+
+    /// Check whether any entry is out of sync
+    pub fn has_drift(&self) -> bool {
+        self.drifted().next().is_some()
+    }
+}
+
+/// Compare map entries with current signature hashes
+///
+/// # Arguments
+/// * `map` - The Sintesi map
+/// * `current_hashes` - Current signature hash by code reference; symbols
+///   missing from it are reported as removed
+pub fn detect_drift(map: &SintesiMap, current_hashes: &HashMap<String, String>) -> DriftResult {
+    let mut result = DriftResult::default();
+
+    for entry in &map.entries {
+        let current_hash = current_hashes.get(&entry.code_ref).cloned();
+        let status = match &current_hash {
+            None => DriftStatus::Removed,
+            Some(hash) if *hash == entry.code_signature_hash => DriftStatus::InSync,
+            Some(_) => DriftStatus::Drifted,
+        };
+
+        result.symbol_status.insert(entry.code_ref.clone(), status);
+        result.entries.push(DriftEntry {
+            entry_id: entry.id.clone(),
+            code_ref: entry.code_ref.clone(),
+            doc_file: entry.doc_file.clone(),
+            status,
+            expected_hash: entry.code_signature_hash.clone(),
+            current_hash,
+            ignore_reason: None,
+            deprecation: None,
+            current_signature: None,
+            severity: None,
+            test_count: None,
+        });
+    }
+
+    result
+}
+
+/// Current state of the code referenced by a map
+#[derive(Debug, Clone, Default)]
+pub struct CurrentSignatures {
+    /// Signature hash by code reference
+    pub hashes: HashMap<String, String>,
+    /// Symbol ID (`ast::symbol_id`) by code reference
+    pub symbol_ids: HashMap<String, String>,
+    /// Symbol key (`ast::symbol_key`) by code reference
+    pub symbol_keys: HashMap<String, String>,
+    /// Ignore reason by code reference (`file#symbol`) or ignored file path
+    pub ignored: HashMap<String, Option<String>>,
+    /// Code references of deprecated symbols
+    pub deprecated: HashSet<String>,
+    /// Signature text by code reference
+    pub signatures: HashMap<String, String>,
+}
+
+/// Check the code referenced by a map for drift
+///
+/// Hashes current signatures through the provider, compares them with the
+/// map, marks entries excluded by ignore directives, records deprecation
+/// changes, and classifies the drift.
+pub fn check_drift(provider: &dyn ContentProvider, map: &SintesiMap) -> DriftResult {
+    let current = current_signatures(provider, map);
+    detect_drift(map, &current.hashes)
+        .with_ignored(&current.ignored)
+        .with_deprecations(map, &current.deprecated)
+        .with_severities(map, &current.signatures)
+}
+
+/// Hash the current signatures of every file referenced by the map
+///
+/// Code file paths in the map are resolved against `root`. Files that cannot
+/// be read contribute no hashes (their entries will be reported as removed).
+pub fn compute_current_hashes(root: impl AsRef<Path>, map: &SintesiMap) -> HashMap<String, String> {
+    compute_current_hashes_from(&DiskProvider::new(root.as_ref()), map)
+}
+
+/// Hash the current signatures of every file referenced by the map, reading
+/// code files through a content provider
+///
+/// Use a `GitProvider` to check drift against a revision instead of the
+/// working tree.
+pub fn compute_current_hashes_from(
+    provider: &dyn ContentProvider,
+    map: &SintesiMap,
+) -> HashMap<String, String> {
+    current_signatures(provider, map).hashes
+}
+
+/// Hash current signatures, derive symbol IDs and keys, and collect ignore
+/// directives for every file referenced by the map
+///
+/// Symbols sharing a qualified name are suffixed (`helper~2`) so each one
+/// has its own code reference. A code reference to a symbol its file only
+/// re-exports (a barrel `index.ts`) gets the signature of the declaration the
+/// re-export chain leads to. Symbols excluded by the project's symbol
+/// filters (`symbols` in `sintesi.config.json`) are reported as ignored.
+pub fn current_signatures(provider: &dyn ContentProvider, map: &SintesiMap) -> CurrentSignatures {
+    let analyzer = AstAnalyzerInternal::with_options(
+        AnalyzerOptions::drift_keys().filter(SymbolFilter::from_provider(provider)),
+    );
+    let hasher = SignatureHasher::new();
+    let mut current = CurrentSignatures::default();
+    let mut analyzed: HashMap<String, AnalysisResult> = HashMap::new();
+
+    let files: HashSet<&str> = map.entries.iter().map(|e| e.code_file_path()).collect();
+    for file in files {
+        let Ok(content) = provider.read(Path::new(file)) else {
+            continue;
+        };
+        let result = analyzer.analyze_file(file, &content);
+        for symbol in &result.symbols {
+            let signature = analyzer.extract_signature(symbol);
+            let code_ref = format!("{}#{}", file, symbol.name);
+            if symbol.deprecated {
+                current.deprecated.insert(code_ref.clone());
+            }
+            current.symbol_ids.insert(
+                code_ref.clone(),
+                symbol_id(file, &symbol.name, symbol.symbol_type),
+            );
+            current.symbol_keys.insert(
+                code_ref.clone(),
+                symbol_key(&symbol.name, symbol.symbol_type),
+            );
+            current
+                .signatures
+                .insert(code_ref.clone(), signature.signature_text.clone());
+            current.hashes.insert(code_ref, hasher.hash(signature).hash);
+        }
+        for item in &result.ignored {
+            current.ignored.insert(item.code_ref(), item.reason.clone());
+        }
+        analyzed.insert(file.to_string(), result);
+    }
+
+    for entry in &map.entries {
+        let code_ref = &entry.code_ref;
+        if current.hashes.contains_key(code_ref)
+            || current.ignored.contains_key(code_ref)
+            || current.ignored.contains_key(entry.code_file_path())
+        {
+            continue;
+        }
+        let Some(resolved) =
+            resolve_reexport(provider, entry.code_file_path(), entry.symbol_name())
+        else {
+            continue;
+        };
+        if !analyzed.contains_key(&resolved.file_path) {
+            let Ok(content) = provider.read(Path::new(&resolved.file_path)) else {
+                continue;
+            };
+            let result = analyzer.analyze_file(&resolved.file_path, &content);
+            analyzed.insert(resolved.file_path.clone(), result);
+        }
+        let result = &analyzed[&resolved.file_path];
+
+        if let Some(symbol) = result.symbols.iter().find(|s| s.name == resolved.name) {
+            if symbol.deprecated {
+                current.deprecated.insert(code_ref.clone());
+            }
+            let signature = analyzer.extract_signature(symbol);
+            current.symbol_ids.insert(
+                code_ref.clone(),
+                symbol_id(&resolved.file_path, &symbol.name, symbol.symbol_type),
+            );
+            current.symbol_keys.insert(
+                code_ref.clone(),
+                symbol_key(&symbol.name, symbol.symbol_type),
+            );
+            current
+                .signatures
+                .insert(code_ref.clone(), signature.signature_text.clone());
+            current
+                .hashes
+                .insert(code_ref.clone(), hasher.hash(signature).hash);
+        } else if let Some(item) = result.ignored.iter().find(|item| {
+            item.symbol_name.is_none() || item.symbol_name.as_deref() == Some(&resolved.name)
+        }) {
+            current
+                .ignored
+                .insert(code_ref.clone(), item.reason.clone());
+        }
+    }
+
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::map::SintesiMapEntry;
+    use std::fs;
+
+    fn map_with(code_ref: &str, hash: &str) -> SintesiMap {
+        SintesiMap {
+            entries: vec![SintesiMapEntry {
+                id: "a".to_string(),
+                code_ref: code_ref.to_string(),
+                doc_file: "docs/api.md".to_string(),
+                code_signature_hash: hash.to_string(),
+                last_updated: 0,
+                symbol_id: None,
+                symbol_key: None,
+                deprecated: false,
+                content_hash: None,
+                code_signature: None,
+            }],
+            ..SintesiMap::new()
+        }
+    }
+
+    #[test]
+    fn test_detects_drift_against_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(
+            dir.path().join("src/auth.ts"),
+            "export function login(user: string): boolean { return true; }",
+        )
+        .unwrap();
+
+        let map = map_with("src/auth.ts#login", "");
+        let hashes = compute_current_hashes(dir.path(), &map);
+        let current = hashes["src/auth.ts#login"].clone();
+
+        let in_sync = detect_drift(&map_with("src/auth.ts#login", &current), &hashes);
+        assert!(!in_sync.has_drift());
+
+        let drifted = detect_drift(&map_with("src/auth.ts#login", "stale"), &hashes);
+        assert_eq!(drifted.entries[0].status, DriftStatus::Drifted);
+        assert_eq!(
+            drifted.entries[0].current_hash.as_deref(),
+            Some(current.as_str())
+        );
+
+        let removed = detect_drift(&map_with("src/auth.ts#logout", "x"), &hashes);
+        assert_eq!(
+            removed.symbol_status["src/auth.ts#logout"],
+            DriftStatus::Removed
+        );
+    }
+
+    #[test]
+    fn test_ignore_directives_mark_entries_ignored() {
+        let mut provider = crate::provider::MemoryProvider::new();
+        provider.set(
+            "src/auth.ts",
+            "// sintesi-ignore-next-symbol: internal helper\nexport function login() {}\nexport function logout() {}",
+        );
+        provider.set("src/gen.ts", "// sintesi-ignore-file\nexport const a = 1;");
+
+        let mut map = map_with("src/auth.ts#login", "stale");
+        map.entries
+            .push(map_with("src/auth.ts#logout", "stale").entries.remove(0));
+        map.entries
+            .push(map_with("src/gen.ts#a", "stale").entries.remove(0));
+        let result = check_drift(&provider, &map);
+
+        let statuses: Vec<_> = result.entries.iter().map(|e| e.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                DriftStatus::Ignored,
+                DriftStatus::Drifted,
+                DriftStatus::Ignored
+            ]
+        );
+        assert_eq!(
+            result.entries[0].ignore_reason.as_deref(),
+            Some("internal helper")
+        );
+        assert_eq!(result.entries[2].ignore_reason, None);
+        assert_eq!(result.drifted().count(), 1);
+        assert_eq!(result.ignored().count(), 2);
+    }
+
+    #[test]
+    fn test_barrel_code_ref_tracks_declaration() {
+        let mut provider = crate::provider::MemoryProvider::new();
+        provider.set("src/index.ts", "export { login } from './auth/session';");
+        provider.set(
+            "src/auth/session.ts",
+            "export function login(user: string) {}",
+        );
+        let hash = current_signatures(&provider, &map_with("src/index.ts#login", "")).hashes
+            ["src/index.ts#login"]
+            .clone();
+        let map = map_with("src/index.ts#login", &hash);
+        assert!(!check_drift(&provider, &map).has_drift());
+
+        provider.set(
+            "src/auth/session.ts",
+            "export function login(user: string, otp: string) {}",
+        );
+        assert_eq!(
+            check_drift(&provider, &map).entries[0].status,
+            DriftStatus::Drifted
+        );
+    }
+
+    #[test]
+    fn test_deprecation_changes_drift() {
+        let mut provider = crate::provider::MemoryProvider::new();
+        provider.set("src/auth.ts", "export function login() {}");
+        let hash = current_signatures(&provider, &map_with("src/auth.ts#login", "")).hashes
+            ["src/auth.ts#login"]
+            .clone();
+        let mut map = map_with("src/auth.ts#login", &hash);
+        assert!(!check_drift(&provider, &map).has_drift());
+
+        provider.set(
+            "src/auth.ts",
+            "/** @deprecated Use signIn */\nexport function login() {}",
+        );
+        let result = check_drift(&provider, &map);
+        assert_eq!(result.entries[0].status, DriftStatus::Drifted);
+        assert_eq!(
+            result.entries[0].deprecation,
+            Some(DeprecationChange::Deprecated)
+        );
+        assert_eq!(
+            result.entries[0].current_hash.as_deref(),
+            Some(hash.as_str())
+        );
+        assert_eq!(
+            result.entries[0].severity,
+            Some(ChangeSeverity::NonBreaking)
+        );
+
+        map.entries[0].deprecated = true;
+        assert!(!check_drift(&provider, &map).has_drift());
+        provider.set("src/auth.ts", "export function login() {}");
+        assert_eq!(
+            check_drift(&provider, &map).entries[0].deprecation,
+            Some(DeprecationChange::Undeprecated)
+        );
+    }
+
+    #[test]
+    fn test_classifies_drift_severity() {
+        let mut provider = crate::provider::MemoryProvider::new();
+        provider.set("src/auth.ts", "export function login(user: string) {}");
+        let current = current_signatures(&provider, &map_with("src/auth.ts#login", ""));
+        let mut map = map_with("src/auth.ts#login", &current.hashes["src/auth.ts#login"]);
+        map.entries[0].code_signature = current.signatures.get("src/auth.ts#login").cloned();
+        map.entries
+            .push(map_with("src/auth.ts#logout", "stale").entries.remove(0));
+
+        provider.set(
+            "src/auth.ts",
+            "export function login(user: string, remember?: boolean) {}",
+        );
+        let result = check_drift(&provider, &map);
+        let severities: Vec<_> = result.entries.iter().map(|e| e.severity).collect();
+        assert_eq!(
+            severities,
+            [
+                Some(ChangeSeverity::NonBreaking),
+                Some(ChangeSeverity::Breaking)
+            ]
+        );
+        let breaking: Vec<&str> = result.breaking().map(|e| e.code_ref.as_str()).collect();
+        assert_eq!(breaking, ["src/auth.ts#logout"]);
+
+        provider.set("src/auth.ts", "export function login(user: User) {}");
+        let result = check_drift(&provider, &map);
+        assert_eq!(result.entries[0].severity, Some(ChangeSeverity::Breaking));
+
+        // Without a recorded signature, the change cannot be classified
+        map.entries[0].code_signature = None;
+        let result = check_drift(&provider, &map);
+        assert_eq!(result.entries[0].severity, None);
+        assert_eq!(result.breaking().count(), 2);
+    }
+
+    #[test]
+    fn test_flags_untested_drift() {
+        let mut provider = crate::provider::MemoryProvider::new();
+        let code = "export function login(user: string) {}\nexport function logout() {}";
+        provider.set("src/auth.ts", code);
+        let mut map = map_with("src/auth.ts#login", "stale");
+        map.entries
+            .push(map_with("src/auth.ts#logout", "stale").entries.remove(0));
+        let tests = crate::ast::test_mapping::map_tests_to_symbols(
+            &[(
+                "src/auth.test.ts".to_string(),
+                "import { logout } from './auth';\ntest('logs out', () => logout());".to_string(),
+            )],
+            &AstAnalyzerInternal::new()
+                .analyze_file("src/auth.ts", code)
+                .symbols,
+        );
+
+        let result = check_drift(&provider, &map);
+        assert_eq!(result.untested().count(), 0);
+        let result = result.with_tests(&tests);
+        let counts: Vec<_> = result.entries.iter().map(|e| e.test_count).collect();
+        assert_eq!(counts, [Some(0), Some(1)]);
+        let untested: Vec<&str> = result.untested().map(|e| e.code_ref.as_str()).collect();
+        assert_eq!(untested, ["src/auth.ts#login"]);
+    }
+}
@@ -0,0 +1,176 @@
+//! Drift history
+//!
+//! A drift check only knows what is out of sync right now. Freshness
+//! policies also need to know for how long, so every check updates a small
+//! persistent record (`.sintesi/drift-history.json`) of when each entry was
+//! first seen drifting. Records are dropped as soon as the entry is back in
+//! sync.
+
+use super::detector::{DriftResult, DriftStatus};
+use crate::error::Error;
+use crate::sandbox::guard_write;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Drift record for a single map entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftRecord {
+    /// Code reference of the entry
+    pub code_ref: String,
+    /// Most recent drift state (drifted or removed)
+    pub status: DriftStatus,
+    /// When drift was first detected (milliseconds since Unix epoch)
+    pub first_detected: i64,
+    /// When drift was last confirmed (milliseconds since Unix epoch)
+    pub last_seen: i64,
+}
+
+/// Drift records by map entry (anchor) ID, saved in ID order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftHistory {
+    pub records: BTreeMap<String, DriftRecord>,
+}
+
+impl DriftHistory {
+    /// Load history from a JSON file; a missing file yields empty history
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::from_reason(format!(
+                "Failed to read drift history {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            Error::from_reason(format!("Invalid drift history {}: {}", path.display(), e))
+        })
+    }
+
+    /// Save history as pretty-printed JSON, creating parent directories
+    /// (`Error::ReadOnly` in read-only mode, `Error::PathNotAllowed` outside
+    /// the write sandbox)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        guard_write("save drift history", path)?;
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+            fs::write(path, json + "\n")
+        };
+        write().map_err(|e| {
+            Error::from_reason(format!(
+                "Failed to write drift history {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Update history with the result of a drift check performed at `now`
+    pub fn record(&mut self, result: &DriftResult, now: i64) {
+        for entry in &result.entries {
+            if matches!(entry.status, DriftStatus::InSync | DriftStatus::Ignored) {
+                self.records.remove(&entry.entry_id);
+                continue;
+            }
+            self.records
+                .entry(entry.entry_id.clone())
+                .and_modify(|r| {
+                    r.status = entry.status;
+                    r.last_seen = now;
+                })
+                .or_insert_with(|| DriftRecord {
+                    code_ref: entry.code_ref.clone(),
+                    status: entry.status,
+                    first_detected: now,
+                    last_seen: now,
+                });
+        }
+    }
+
+    /// When drift was first detected for an entry, if it is drifting
+    pub fn first_detected(&self, entry_id: &str) -> Option<i64> {
+        self.records.get(entry_id).map(|r| r.first_detected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::detector::DriftEntry;
+
+    fn result(status: DriftStatus) -> DriftResult {
+        DriftResult {
+            entries: vec![DriftEntry {
+                entry_id: "a".to_string(),
+                code_ref: "src/pay.ts#charge".to_string(),
+                doc_file: "docs/pay.md".to_string(),
+                status,
+                expected_hash: "old".to_string(),
+                current_hash: Some("new".to_string()),
+                ignore_reason: None,
+                deprecation: None,
+                current_signature: None,
+                severity: None,
+                test_count: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_keeps_first_detection_until_resolved() {
+        let mut history = DriftHistory::default();
+        history.record(&result(DriftStatus::Drifted), 100);
+        history.record(&result(DriftStatus::Drifted), 200);
+
+        assert_eq!(history.first_detected("a"), Some(100));
+        assert_eq!(history.records["a"].last_seen, 200);
+
+        history.record(&result(DriftStatus::InSync), 300);
+        assert_eq!(history.first_detected("a"), None);
+    }
+
+    #[test]
+    fn test_missing_file_loads_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".sintesi/drift-history.json");
+        assert_eq!(DriftHistory::load(&path).unwrap(), DriftHistory::default());
+
+        let mut history = DriftHistory::default();
+        history.record(&result(DriftStatus::Removed), 42);
+        history.save(&path).unwrap();
+        assert_eq!(DriftHistory::load(&path).unwrap(), history);
+    }
+
+    #[test]
+    fn test_saves_records_in_id_order() {
+        let mut drifted = result(DriftStatus::Drifted);
+        drifted.entries = ["c", "a", "b"]
+            .iter()
+            .map(|id| DriftEntry {
+                entry_id: id.to_string(),
+                ..drifted.entries[0].clone()
+            })
+            .collect();
+        let mut history = DriftHistory::default();
+        history.record(&drifted, 100);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("drift-history.json");
+        history.save(&path).unwrap();
+        let json = fs::read_to_string(&path).unwrap();
+        let position = |id: &str| json.find(&format!("\"{}\": {{", id)).unwrap();
+        assert!(position("a") < position("b") && position("b") < position("c"));
+    }
+}
@@ -0,0 +1,187 @@
+//! Sintesi map model
+//!
+//! The map is the persistent link between code and documentation: one entry
+//! per anchor, recording the code symbol it documents, the signature hash the
+//! documentation was written against, and when the anchor was last updated.
+//! It is stored as JSON (`sintesi-map.json`) with camelCase keys so the
+//! TypeScript CLI can read and write the same file.
+
+use crate::error::Error;
+use crate::sandbox::guard_write;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Current map format version
+pub const MAP_VERSION: &str = "1.0";
+
+/// A single code-to-documentation link
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SintesiMapEntry {
+    /// Anchor ID in the documentation
+    pub id: String,
+    /// Code reference (`file_path#symbol_name`)
+    pub code_ref: String,
+    /// Markdown file containing the anchor
+    pub doc_file: String,
+    /// Signature hash the documentation was last written against
+    pub code_signature_hash: String,
+    /// When the anchor was last updated (milliseconds since Unix epoch)
+    pub last_updated: i64,
+    /// Symbol ID of the documented symbol (`ast::symbol_id`, as in
+    /// `CodeSignature::id`), to look it up however the code reference is
+    /// spelled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_id: Option<String>,
+    /// Symbol key of the documented symbol (`ast::symbol_key`), to find it
+    /// again when its file moves. Maps written before keys were recorded
+    /// hold the key in `symbolId`, which relocation still accepts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_key: Option<String>,
+    /// Whether the symbol was deprecated when the documentation was written
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Canonical hash of the anchor content as last written by Sintesi
+    /// (`anchor_content_hash`), to tell human edits from reformatting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// Signature the documentation was last written against, to classify
+    /// later changes as breaking or not (`ast::diff::classify`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_signature: Option<String>,
+}
+
+impl SintesiMapEntry {
+    /// File path part of the code reference
+    pub fn code_file_path(&self) -> &str {
+        self.code_ref.split('#').next().unwrap_or("")
+    }
+
+    /// Symbol name part of the code reference
+    pub fn symbol_name(&self) -> &str {
+        self.code_ref.split('#').nth(1).unwrap_or("")
+    }
+}
+
+/// All code-to-documentation links of a project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SintesiMap {
+    /// Map format version
+    pub version: String,
+    /// Map entries, one per anchor
+    pub entries: Vec<SintesiMapEntry>,
+}
+
+impl Default for SintesiMap {
+    fn default() -> Self {
+        Self {
+            version: MAP_VERSION.to_string(),
+            entries: vec![],
+        }
+    }
+}
+
+impl SintesiMap {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a map from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::from_reason(format!("Failed to read map {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|e| Error::from_reason(format!("Invalid map {}: {}", path.display(), e)))
+    }
+
+    /// Serialize the map as pretty-printed JSON, as written by `save`
+    pub fn to_json(&self) -> Result<String, Error> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::from_reason(format!("Failed to serialize map: {}", e)))?;
+        Ok(json + "\n")
+    }
+
+    /// Save the map as pretty-printed JSON (`Error::ReadOnly` in read-only
+    /// mode, `Error::PathNotAllowed` outside the write sandbox)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        guard_write("save map", path)?;
+        fs::write(path, self.to_json()?).map_err(|e| {
+            Error::from_reason(format!("Failed to write map {}: {}", path.display(), e))
+        })
+    }
+
+    /// Find an entry by anchor ID
+    pub fn get(&self, id: &str) -> Option<&SintesiMapEntry> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+
+    /// Insert an entry, replacing any existing entry with the same anchor ID
+    pub fn upsert(&mut self, entry: SintesiMapEntry) {
+        match self.entries.iter_mut().find(|e| e.id == entry.id) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Remove an entry by anchor ID, returning it if present
+    pub fn remove(&mut self, id: &str) -> Option<SintesiMapEntry> {
+        let index = self.entries.iter().position(|e| e.id == id)?;
+        Some(self.entries.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, hash: &str) -> SintesiMapEntry {
+        SintesiMapEntry {
+            id: id.to_string(),
+            code_ref: "src/auth.ts#login".to_string(),
+            doc_file: "docs/auth.md".to_string(),
+            code_signature_hash: hash.to_string(),
+            last_updated: 1_700_000_000_000,
+            symbol_id: None,
+            symbol_key: None,
+            deprecated: false,
+            content_hash: None,
+            code_signature: None,
+        }
+    }
+
+    #[test]
+    fn test_upsert_and_remove() {
+        let mut map = SintesiMap::new();
+        map.upsert(entry("a", "h1"));
+        map.upsert(entry("a", "h2"));
+        map.upsert(entry("b", "h3"));
+
+        assert_eq!(map.entries.len(), 2);
+        assert_eq!(map.get("a").unwrap().code_signature_hash, "h2");
+        assert_eq!(map.get("a").unwrap().code_file_path(), "src/auth.ts");
+        assert_eq!(map.get("a").unwrap().symbol_name(), "login");
+        assert!(map.remove("b").is_some());
+        assert!(map.get("b").is_none());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sintesi-map.json");
+        let mut map = SintesiMap::new();
+        map.upsert(entry("a", "h1"));
+
+        map.save(&path).unwrap();
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(json.contains("\"codeSignatureHash\": \"h1\""));
+        assert!(json.contains("\"lastUpdated\": 1700000000000"));
+        assert_eq!(SintesiMap::load(&path).unwrap(), map);
+    }
+}
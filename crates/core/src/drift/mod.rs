@@ -0,0 +1,56 @@
+//! Drift tracking and documentation freshness module
+//!
+//! This module handles:
+//! - The Sintesi map linking code symbols to documentation anchors
+//! - Drift detection by comparing mapped hashes with current signatures
+//! - Deprecation tracking (newly deprecated or un-deprecated symbols)
+//! - Drift history (when each drift was first seen)
+//! - Freshness SLA policies evaluated against history and map timestamps
+//! - Sync checks tying documentation anchors to the map and the code
+//! - Following moved symbols by their stable IDs
+//!
+//! ## Module Structure
+//!
+//! - `map`: `SintesiMap` model and persistence
+//! - `detector`: Drift detection against the current code
+//! - `history`: Persistent record of when drift was first detected
+//! - `policy`: Freshness policies and violation reporting
+//! - `sync`: Documentation sync checks and map regeneration from anchors
+//! - `relocate`: Re-pointing entries at symbols that moved to another file
+
+pub mod detector;
+pub mod history;
+pub mod map;
+pub mod policy;
+pub mod relocate;
+pub mod sync;
+
+pub use detector::{
+    check_drift, compute_current_hashes, compute_current_hashes_from, current_signatures,
+    detect_drift, CurrentSignatures, DeprecationChange, DriftEntry, DriftResult, DriftStatus,
+};
+pub use history::{DriftHistory, DriftRecord};
+pub use map::{SintesiMap, SintesiMapEntry};
+pub use policy::{
+    evaluate_policies, FreshnessPolicy, PolicySet, PolicySeverity, PolicyViolation, ViolationKind,
+};
+pub use relocate::{relocate_moved_symbols, Relocation};
+pub use sync::{check_docs, sync_map, SyncReport};
+
+/// Map file name, relative to the project root
+pub const MAP_FILE: &str = "sintesi-map.json";
+
+/// Drift history file, relative to the project root
+pub const HISTORY_FILE: &str = ".sintesi/drift-history.json";
+
+/// Milliseconds in one day (timestamps are milliseconds since Unix epoch)
+pub const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Current time in milliseconds since Unix epoch
+pub fn now_millis() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,296 @@
+//! Documentation freshness SLA policies
+//!
+//! Teams declare rules such as "anchors referencing `src/payments/**` must be
+//! updated within 7 days of code drift". Policies are evaluated against the
+//! drift history (how long an entry has been drifting) and the map's
+//! `lastUpdated` timestamps (how old the documentation is), producing
+//! violations that CI can report and gate on.
+//!
+//! Policies are declared in JSON:
+//!
+//! ```json
+//! {
+//!   "policies": [
+//!     { "name": "payments", "paths": ["src/payments/**"], "maxDriftDays": 7 },
+//!     { "name": "review", "paths": ["**"], "maxAgeDays": 180, "severity": "warning" }
+//!   ]
+//! }
+//! ```
+
+use super::history::DriftHistory;
+use super::map::SintesiMap;
+use super::DAY_MS;
+use crate::error::Error;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Severity of a policy violation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PolicySeverity {
+    Warning,
+    #[default]
+    Error,
+}
+
+/// A freshness rule for anchors documenting matching code paths
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FreshnessPolicy {
+    /// Policy name shown in violations
+    pub name: String,
+    /// Glob patterns matched against the code file of each map entry
+    pub paths: Vec<String>,
+    /// Maximum days an entry may stay drifted before violating the policy
+    #[serde(default)]
+    pub max_drift_days: Option<u32>,
+    /// Maximum days since the anchor was last updated, drift or not
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+    /// Severity of violations
+    #[serde(default)]
+    pub severity: PolicySeverity,
+}
+
+/// A set of freshness policies, as loaded from configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PolicySet {
+    pub policies: Vec<FreshnessPolicy>,
+}
+
+impl PolicySet {
+    /// Load policies from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::from_reason(format!("Failed to read policies {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|e| Error::from_reason(format!("Invalid policies {}: {}", path.display(), e)))
+    }
+}
+
+/// What limit a violation exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The entry has been drifted longer than `max_drift_days`
+    Drift,
+    /// The anchor was last updated longer ago than `max_age_days`
+    Age,
+}
+
+/// A map entry that breaks a freshness policy
+#[derive(Debug, Clone)]
+pub struct PolicyViolation {
+    /// Name of the violated policy
+    pub policy: String,
+    /// Severity of the violated policy
+    pub severity: PolicySeverity,
+    /// Which limit was exceeded
+    pub kind: ViolationKind,
+    /// Anchor ID of the map entry
+    pub entry_id: String,
+    /// Code reference of the map entry
+    pub code_ref: String,
+    /// Markdown file containing the anchor
+    pub doc_file: String,
+    /// Whole days drifted (for `Drift`) or since last update (for `Age`)
+    pub days: u32,
+    /// The policy limit in days
+    pub limit_days: u32,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            ViolationKind::Drift => "has been drifted for",
+            ViolationKind::Age => "was last updated",
+        };
+        let suffix = match self.kind {
+            ViolationKind::Drift => "",
+            ViolationKind::Age => " ago",
+        };
+        write!(
+            f,
+            "{}: anchor \"{}\" ({}) {} {} days{} (policy \"{}\" allows {})",
+            self.doc_file,
+            self.entry_id,
+            self.code_ref,
+            what,
+            self.days,
+            suffix,
+            self.policy,
+            self.limit_days
+        )
+    }
+}
+
+/// Evaluate freshness policies against the map and drift history
+///
+/// # Arguments
+/// * `policies` - Policies to enforce
+/// * `map` - The Sintesi map (for code paths and `lastUpdated`)
+/// * `history` - Drift history (for when drift was first detected)
+/// * `now` - Evaluation time (milliseconds since Unix epoch)
+///
+/// # Returns
+/// Violations in map order, or an error if a policy has an invalid glob
+pub fn evaluate_policies(
+    policies: &[FreshnessPolicy],
+    map: &SintesiMap,
+    history: &DriftHistory,
+    now: i64,
+) -> Result<Vec<PolicyViolation>, Error> {
+    let compiled = policies
+        .iter()
+        .map(|policy| Ok((policy, compile_globs(policy)?)))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut violations = Vec::new();
+    for entry in &map.entries {
+        for (policy, globs) in &compiled {
+            if !globs.is_match(entry.code_file_path()) {
+                continue;
+            }
+
+            let mut check = |kind, since: i64, limit: Option<u32>| {
+                let Some(limit) = limit else {
+                    return;
+                };
+                let elapsed = now.saturating_sub(since);
+                if elapsed > i64::from(limit) * DAY_MS {
+                    violations.push(PolicyViolation {
+                        policy: policy.name.clone(),
+                        severity: policy.severity,
+                        kind,
+                        entry_id: entry.id.clone(),
+                        code_ref: entry.code_ref.clone(),
+                        doc_file: entry.doc_file.clone(),
+                        days: (elapsed / DAY_MS) as u32,
+                        limit_days: limit,
+                    });
+                }
+            };
+
+            if let Some(first_detected) = history.first_detected(&entry.id) {
+                check(ViolationKind::Drift, first_detected, policy.max_drift_days);
+            }
+            check(ViolationKind::Age, entry.last_updated, policy.max_age_days);
+        }
+    }
+
+    Ok(violations)
+}
+
+fn compile_globs(policy: &FreshnessPolicy) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in &policy.paths {
+        let glob = Glob::new(pattern).map_err(|e| {
+            Error::from_reason(format!(
+                "Invalid path pattern \"{}\" in policy \"{}\": {}",
+                pattern, policy.name, e
+            ))
+        })?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| Error::from_reason(format!("Invalid policy \"{}\": {}", policy.name, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::history::DriftRecord;
+    use crate::drift::map::SintesiMapEntry;
+    use crate::drift::DriftStatus;
+
+    fn entry(id: &str, code_ref: &str, last_updated: i64) -> SintesiMapEntry {
+        SintesiMapEntry {
+            id: id.to_string(),
+            code_ref: code_ref.to_string(),
+            doc_file: "docs/api.md".to_string(),
+            code_signature_hash: "h".to_string(),
+            last_updated,
+            symbol_id: None,
+            symbol_key: None,
+            deprecated: false,
+            content_hash: None,
+            code_signature: None,
+        }
+    }
+
+    fn policies() -> Vec<FreshnessPolicy> {
+        let json = r#"{ "policies": [
+            { "name": "payments", "paths": ["src/payments/**"], "maxDriftDays": 7 },
+            { "name": "review", "paths": ["**"], "maxAgeDays": 90, "severity": "warning" }
+        ] }"#;
+        serde_json::from_str::<PolicySet>(json).unwrap().policies
+    }
+
+    #[test]
+    fn test_drift_sla_violation() {
+        let now = 100 * DAY_MS;
+        let map = SintesiMap {
+            entries: vec![
+                entry("pay", "src/payments/charge.ts#charge", now - DAY_MS),
+                entry("auth", "src/auth.ts#login", now - DAY_MS),
+            ],
+            ..SintesiMap::new()
+        };
+        let mut history = DriftHistory::default();
+        for (id, code_ref) in [
+            ("pay", "src/payments/charge.ts#charge"),
+            ("auth", "src/auth.ts#login"),
+        ] {
+            history.records.insert(
+                id.to_string(),
+                DriftRecord {
+                    code_ref: code_ref.to_string(),
+                    status: DriftStatus::Drifted,
+                    first_detected: now - 9 * DAY_MS,
+                    last_seen: now,
+                },
+            );
+        }
+
+        let violations = evaluate_policies(&policies(), &map, &history, now).unwrap();
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].policy, "payments");
+        assert_eq!(violations[0].kind, ViolationKind::Drift);
+        assert_eq!(violations[0].severity, PolicySeverity::Error);
+        assert_eq!(
+            violations[0].to_string(),
+            "docs/api.md: anchor \"pay\" (src/payments/charge.ts#charge) has been drifted for 9 days (policy \"payments\" allows 7)"
+        );
+    }
+
+    #[test]
+    fn test_age_violation_and_invalid_glob() {
+        let now = 200 * DAY_MS;
+        let map = SintesiMap {
+            entries: vec![entry("auth", "src/auth.ts#login", now - 120 * DAY_MS)],
+            ..SintesiMap::new()
+        };
+
+        let violations =
+            evaluate_policies(&policies(), &map, &DriftHistory::default(), now).unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, ViolationKind::Age);
+        assert_eq!(violations[0].days, 120);
+        assert_eq!(violations[0].severity, PolicySeverity::Warning);
+
+        let bad = vec![FreshnessPolicy {
+            name: "bad".to_string(),
+            paths: vec!["src/[".to_string()],
+            max_drift_days: Some(1),
+            max_age_days: None,
+            severity: PolicySeverity::Error,
+        }];
+        assert!(evaluate_policies(&bad, &map, &DriftHistory::default(), now).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! Relocation of moved symbols
+//!
+//! Map entries are keyed by `path#symbol`, so moving or renaming a file
+//! orphans every anchor documenting it. Entries that record a symbol ID or
+//! key (`ast::symbol_id`) can be followed instead: when an entry's code
+//! reference no longer resolves, the project's source files (as discovery
+//! classifies them) are searched for the exported
+//! symbol with the same ID (the same declaration, e.g. after `auth.js`
+//! became `auth.ts`), or else for the one exported symbol with the same key,
+//! and the entry is pointed at it. The recorded signature hash is kept, so a
+//! symbol that moved and changed is still reported as drifted.
+
+use super::detector::current_signatures;
+use super::map::SintesiMap;
+use crate::ast::{symbol_id, symbol_key, AnalyzerOptions, AstAnalyzerInternal, SymbolFilter};
+use crate::content::discovery::DiscoveryConfig;
+use crate::provider::ContentProvider;
+use std::collections::HashMap;
+
+/// A map entry pointed at a symbol's new location
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relocation {
+    /// Anchor ID of the map entry
+    pub entry_id: String,
+    /// Previous code reference
+    pub from: String,
+    /// New code reference
+    pub to: String,
+}
+
+/// Point entries whose symbol moved at its new location
+///
+/// Only entries with a `symbol_id` or `symbol_key` whose code reference no
+/// longer resolves are considered. An ID or key matching several exported
+/// symbols is ambiguous; if neither matches exactly one, the entry is left
+/// unchanged (drift detection reports it as removed).
+///
+/// # Returns
+/// The relocations applied to `map`, in map order
+pub fn relocate_moved_symbols(
+    provider: &dyn ContentProvider,
+    map: &mut SintesiMap,
+) -> Vec<Relocation> {
+    let current = current_signatures(provider, map);
+    let moved: Vec<usize> = map
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| {
+            (e.symbol_id.is_some() || e.symbol_key.is_some())
+                && !current.hashes.contains_key(&e.code_ref)
+        })
+        .map(|(i, _)| i)
+        .collect();
+    if moved.is_empty() {
+        return Vec::new();
+    }
+
+    let Ok(files) = provider.list_files() else {
+        return Vec::new();
+    };
+    // Lockfiles, assets, and the like declare nothing to relocate to
+    let discovery = DiscoveryConfig::new();
+    let files: Vec<_> = files
+        .into_iter()
+        .filter(|path| {
+            discovery
+                .classify(path)
+                .is_some_and(|file| file.is_source())
+        })
+        .collect();
+    let analyzer = AstAnalyzerInternal::with_options(
+        AnalyzerOptions::drift_keys().filter(SymbolFilter::from_provider(provider)),
+    );
+    let mut by_id: HashMap<String, Vec<String>> = HashMap::new();
+    let mut by_key: HashMap<String, Vec<String>> = HashMap::new();
+    for symbol in analyzer.analyze_with(provider, &files).symbols {
+        if symbol.is_exported {
+            let code_ref = format!("{}#{}", symbol.file_path, symbol.name);
+            by_id
+                .entry(symbol_id(
+                    &symbol.file_path,
+                    &symbol.name,
+                    symbol.symbol_type,
+                ))
+                .or_default()
+                .push(code_ref.clone());
+            by_key
+                .entry(symbol_key(&symbol.name, symbol.symbol_type))
+                .or_default()
+                .push(code_ref);
+        }
+    }
+
+    let mut relocations = Vec::new();
+    for index in moved {
+        let entry = &mut map.entries[index];
+        // Maps written before keys were recorded hold the key in `symbolId`
+        let key = entry.symbol_key.as_ref().or(entry.symbol_id.as_ref());
+        let Some(to) = unique(&by_id, entry.symbol_id.as_ref()).or_else(|| unique(&by_key, key))
+        else {
+            continue;
+        };
+        relocations.push(Relocation {
+            entry_id: entry.id.clone(),
+            from: std::mem::replace(&mut entry.code_ref, to.clone()),
+            to,
+        });
+    }
+    relocations
+}
+
+/// The code reference of the only symbol with an ID or key
+fn unique(locations: &HashMap<String, Vec<String>>, id: Option<&String>) -> Option<String> {
+    match locations.get(id?)?.as_slice() {
+        [to] => Some(to.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::map::SintesiMapEntry;
+    use crate::drift::{check_drift, DriftStatus};
+    use crate::provider::MemoryProvider;
+    use crate::types::SymbolType;
+
+    fn entry(id: &str, code_ref: &str, symbol: &str) -> SintesiMapEntry {
+        SintesiMapEntry {
+            id: id.to_string(),
+            code_ref: code_ref.to_string(),
+            doc_file: "docs/api.md".to_string(),
+            code_signature_hash: String::new(),
+            last_updated: 0,
+            symbol_id: None,
+            symbol_key: Some(symbol_key(symbol, SymbolType::Function)),
+            deprecated: false,
+            content_hash: None,
+            code_signature: None,
+        }
+    }
+
+    #[test]
+    fn test_relocates_moved_file() {
+        let mut before = MemoryProvider::new();
+        before.set("src/auth.ts", "export function login() {}");
+        let mut map = SintesiMap::new();
+        map.upsert(entry("login", "src/auth.ts#login", "login"));
+        map.entries[0].code_signature_hash =
+            current_signatures(&before, &map).hashes["src/auth.ts#login"].clone();
+
+        let mut after = MemoryProvider::new();
+        after.set("src/auth/session.ts", "export function login() {}");
+        after.set("src/a.ts", "export function helper() {}");
+        let relocations = relocate_moved_symbols(&after, &mut map);
+
+        assert_eq!(
+            relocations,
+            [Relocation {
+                entry_id: "login".to_string(),
+                from: "src/auth.ts#login".to_string(),
+                to: "src/auth/session.ts#login".to_string(),
+            }]
+        );
+        assert_eq!(
+            check_drift(&after, &map).entries[0].status,
+            DriftStatus::InSync
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_id_is_not_relocated() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/a.ts", "export function login() {}");
+        provider.set("src/b.ts", "export function login() {}");
+        let mut map = SintesiMap::new();
+        map.upsert(entry("login", "src/auth.ts#login", "login"));
+
+        assert!(relocate_moved_symbols(&provider, &mut map).is_empty());
+        assert_eq!(map.entries[0].code_ref, "src/auth.ts#login");
+    }
+
+    #[test]
+    fn test_only_source_files_are_searched() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/session.ts", "export function login() {}");
+        // Not source to discovery, so its `login` does not make the key ambiguous
+        provider.set(
+            "db/auth.sql",
+            "CREATE FUNCTION login() RETURNS void AS $$ $$;",
+        );
+        let mut map = SintesiMap::new();
+        map.upsert(entry("login", "src/auth.ts#login", "login"));
+
+        assert_eq!(relocate_moved_symbols(&provider, &mut map).len(), 1);
+        assert_eq!(map.entries[0].code_ref, "src/session.ts#login");
+    }
+
+    #[test]
+    fn test_relocates_by_symbol_id_before_key() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/auth.ts", "export function login() {}");
+        provider.set("src/session.ts", "export function login() {}");
+        let mut map = SintesiMap::new();
+        // Ambiguous by key, but the ID names the declaration in `auth.ts`
+        map.upsert(SintesiMapEntry {
+            symbol_id: Some(symbol_id("src/auth.js", "login", SymbolType::Function)),
+            ..entry("login", "src/auth.js#login", "login")
+        });
+
+        let relocations = relocate_moved_symbols(&provider, &mut map);
+
+        assert_eq!(relocations[0].to, "src/auth.ts#login");
+        assert_eq!(map.entries[0].code_ref, "src/auth.ts#login");
+    }
+
+    #[test]
+    fn test_relocates_legacy_symbol_id() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/session.ts", "export function login() {}");
+        let mut map = SintesiMap::new();
+        map.upsert(SintesiMapEntry {
+            symbol_id: Some(symbol_key("login", SymbolType::Function)),
+            symbol_key: None,
+            ..entry("login", "src/auth.ts#login", "login")
+        });
+
+        assert_eq!(relocate_moved_symbols(&provider, &mut map).len(), 1);
+        assert_eq!(map.entries[0].code_ref, "src/session.ts#login");
+    }
+}
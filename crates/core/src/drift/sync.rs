@@ -0,0 +1,333 @@
+//! Documentation sync checks
+//!
+//! Ties anchor extraction, the map, and drift detection together for a set of
+//! documentation files: `check_docs` reports everything that keeps the docs
+//! from being in sync (drifted or removed symbols, anchors missing from the
+//! map, map entries whose anchor is gone), and `sync_map` rewrites the map so
+//! it matches the anchors and the current code.
+//!
+//! The map also records a canonical hash of each anchor's content, so
+//! `check_docs` can list anchors edited by hand since the last sync; rewrapped
+//! paragraphs, trailing whitespace, and bullet style do not count as edits.
+//!
+//! Sintesi runs this on its own `crates/core/README.md`; see
+//! `test_self_docs_in_sync` below and `examples/self_docs.rs` for the
+//! complete pipeline.
+
+use super::detector::{check_drift, current_signatures, DriftResult};
+use super::map::{SintesiMap, SintesiMapEntry};
+use crate::content::{anchor_content_hash, extract_anchors_from, SintesiAnchor};
+use crate::provider::ContentProvider;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Sync state of a set of documentation files
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// Drift of the map entries belonging to the checked files
+    pub drift: DriftResult,
+    /// Anchor IDs with a code reference that the map does not record
+    pub unmapped: Vec<String>,
+    /// Map entry IDs whose anchor no longer exists in its file
+    pub orphaned: Vec<String>,
+    /// Map entry IDs whose anchor content changed since it was recorded
+    /// (ignoring formatting); informational, not part of `is_clean`
+    pub edited: Vec<String>,
+    /// Anchor extraction errors
+    pub errors: Vec<String>,
+}
+
+impl SyncReport {
+    /// Check whether the docs are fully in sync with the map and the code
+    pub fn is_clean(&self) -> bool {
+        !self.drift.has_drift()
+            && self.unmapped.is_empty()
+            && self.orphaned.is_empty()
+            && self.errors.is_empty()
+    }
+}
+
+/// Check documentation files against the map and the current code
+///
+/// Only map entries whose `doc_file` is one of `doc_files` are considered.
+pub fn check_docs(
+    provider: &dyn ContentProvider,
+    map: &SintesiMap,
+    doc_files: &[PathBuf],
+) -> SyncReport {
+    let (anchors, errors) = collect_anchors(provider, doc_files);
+    let scoped = scope(map, doc_files);
+
+    let unmapped = anchors
+        .iter()
+        .filter(|(doc_file, anchor)| {
+            !scoped.entries.iter().any(|e| {
+                e.id == anchor.id
+                    && e.doc_file == *doc_file
+                    && Some(e.code_ref.as_str()) == anchor.code_ref.as_deref()
+            })
+        })
+        .map(|(_, anchor)| anchor.id.clone())
+        .collect();
+    let present: HashSet<(&str, &str)> = anchors
+        .iter()
+        .map(|(doc_file, anchor)| (doc_file.as_str(), anchor.id.as_str()))
+        .collect();
+    let orphaned = scoped
+        .entries
+        .iter()
+        .filter(|e| !present.contains(&(e.doc_file.as_str(), e.id.as_str())))
+        .map(|e| e.id.clone())
+        .collect();
+    let edited = scoped
+        .entries
+        .iter()
+        .filter(|e| {
+            let Some(recorded) = &e.content_hash else {
+                return false;
+            };
+            anchors.iter().any(|(doc_file, anchor)| {
+                anchor.id == e.id
+                    && *doc_file == e.doc_file
+                    && anchor_content_hash(&anchor.content) != *recorded
+            })
+        })
+        .map(|e| e.id.clone())
+        .collect();
+
+    SyncReport {
+        drift: check_drift(provider, &scoped),
+        unmapped,
+        orphaned,
+        edited,
+        errors,
+    }
+}
+
+/// Rebuild the map entries of documentation files from their anchors
+///
+/// Each anchor with a code reference gets an entry with the current signature
+/// hash, symbol ID and key, deprecation state, and canonical content hash.
+/// Entries whose code reference, hash, and deprecation are unchanged keep
+/// their `last_updated`; others are stamped with `now`. Anchors whose symbol
+/// does not exist are left out, so `check_docs` keeps reporting them. Entries
+/// of other documentation files are kept as they are.
+pub fn sync_map(
+    provider: &dyn ContentProvider,
+    map: &SintesiMap,
+    doc_files: &[PathBuf],
+    now: i64,
+) -> SintesiMap {
+    let (anchors, _) = collect_anchors(provider, doc_files);
+    let mut synced = SintesiMap {
+        version: map.version.clone(),
+        entries: anchors
+            .into_iter()
+            .filter_map(|(doc_file, anchor)| {
+                Some(SintesiMapEntry {
+                    id: anchor.id,
+                    code_ref: anchor.code_ref?,
+                    doc_file,
+                    code_signature_hash: String::new(),
+                    last_updated: now,
+                    symbol_id: None,
+                    symbol_key: None,
+                    deprecated: false,
+                    content_hash: Some(anchor_content_hash(&anchor.content)),
+                    code_signature: None,
+                })
+            })
+            .collect(),
+    };
+
+    let current = current_signatures(provider, &synced);
+    synced.entries.retain_mut(|entry| {
+        let Some(hash) = current.hashes.get(&entry.code_ref) else {
+            return false;
+        };
+        entry.code_signature_hash = hash.clone();
+        entry.symbol_id = current.symbol_ids.get(&entry.code_ref).cloned();
+        entry.symbol_key = current.symbol_keys.get(&entry.code_ref).cloned();
+        entry.deprecated = current.deprecated.contains(&entry.code_ref);
+        entry.code_signature = current.signatures.get(&entry.code_ref).cloned();
+        if let Some(previous) = map.get(&entry.id) {
+            if previous.code_ref == entry.code_ref
+                && previous.code_signature_hash == *hash
+                && previous.deprecated == entry.deprecated
+            {
+                entry.last_updated = previous.last_updated;
+            }
+        }
+        true
+    });
+
+    let doc_files = doc_file_names(doc_files);
+    let mut entries: Vec<SintesiMapEntry> = map
+        .entries
+        .iter()
+        .filter(|e| !doc_files.contains(&e.doc_file))
+        .cloned()
+        .collect();
+    entries.extend(synced.entries);
+    synced.entries = entries;
+    synced
+}
+
+/// Anchors of the documentation files, paired with their file, in file order
+fn collect_anchors(
+    provider: &dyn ContentProvider,
+    doc_files: &[PathBuf],
+) -> (Vec<(String, SintesiAnchor)>, Vec<String>) {
+    let mut anchors = Vec::new();
+    let mut errors = Vec::new();
+
+    for doc_file in doc_files {
+        let result = extract_anchors_from(provider, doc_file);
+        let mut file_anchors: Vec<SintesiAnchor> = result.anchors.into_values().collect();
+        file_anchors.sort_by_key(|a| a.start_line);
+        let name = doc_file.to_string_lossy().into_owned();
+        anchors.extend(file_anchors.into_iter().map(|a| (name.clone(), a)));
+        errors.extend(result.errors);
+    }
+
+    (anchors, errors)
+}
+
+/// The part of a map that belongs to the documentation files
+fn scope(map: &SintesiMap, doc_files: &[PathBuf]) -> SintesiMap {
+    let doc_files = doc_file_names(doc_files);
+    SintesiMap {
+        version: map.version.clone(),
+        entries: map
+            .entries
+            .iter()
+            .filter(|e| doc_files.contains(&e.doc_file))
+            .cloned()
+            .collect(),
+    }
+}
+
+fn doc_file_names(doc_files: &[PathBuf]) -> HashSet<String> {
+    doc_files
+        .iter()
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::{now_millis, DriftStatus, MAP_FILE};
+    use crate::provider::{DiskProvider, MemoryProvider};
+    use std::path::Path;
+
+    const DOC: &str = "docs/api.md";
+
+    fn provider(code: &str) -> MemoryProvider {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/auth.ts", code);
+        provider.set(
+            DOC,
+            "# API\n\n<!-- sintesi:start id=\"login\" code_ref=\"src/auth.ts#login\" -->\nLogs in.\n<!-- sintesi:end id=\"login\" -->\n",
+        );
+        provider
+    }
+
+    #[test]
+    fn test_sync_then_check() {
+        let docs = [PathBuf::from(DOC)];
+        let before = provider("export function login(user: string) {}");
+
+        let report = check_docs(&before, &SintesiMap::new(), &docs);
+        assert_eq!(report.unmapped, ["login"]);
+        assert!(!report.is_clean());
+
+        let map = sync_map(&before, &SintesiMap::new(), &docs, 1);
+        assert_eq!(map.entries.len(), 1);
+        let login = crate::ast::AstAnalyzerInternal::new()
+            .signature_of(
+                "src/auth.ts",
+                "export function login(user: string) {}",
+                "login",
+            )
+            .unwrap();
+        assert_eq!(map.entries[0].symbol_id, login.id);
+        assert_eq!(
+            map.entries[0].symbol_key.as_deref(),
+            Some(crate::ast::symbol_key("login", login.symbol_type).as_str())
+        );
+        assert!(check_docs(&before, &map, &docs).is_clean());
+        assert_eq!(sync_map(&before, &map, &docs, 2), map);
+
+        let with_anchor = |text: &str| {
+            let mut provider = provider("export function login(user: string) {}");
+            provider.set(
+                DOC,
+                format!("<!-- sintesi:start id=\"login\" code_ref=\"src/auth.ts#login\" -->\n{}\n<!-- sintesi:end id=\"login\" -->\n", text),
+            );
+            provider
+        };
+        let rewrapped = with_anchor("Logs\n  in.  ");
+        assert!(check_docs(&rewrapped, &map, &docs).edited.is_empty());
+        let report = check_docs(&with_anchor("Signs in."), &map, &docs);
+        assert_eq!(report.edited, ["login"]);
+        assert!(report.is_clean());
+
+        let after = provider("export function login(user: string, otp: string) {}");
+        let report = check_docs(&after, &map, &docs);
+        assert_eq!(report.drift.entries[0].status, DriftStatus::Drifted);
+        assert_eq!(sync_map(&after, &map, &docs, 2).entries[0].last_updated, 2);
+    }
+
+    #[test]
+    fn test_orphaned_entry() {
+        let docs = [PathBuf::from(DOC)];
+        let provider = provider("export function login() {}");
+        let mut map = sync_map(&provider, &SintesiMap::new(), &docs, 1);
+        let mut stale = map.entries[0].clone();
+        stale.id = "logout".to_string();
+        map.upsert(stale);
+
+        assert_eq!(check_docs(&provider, &map, &docs).orphaned, ["logout"]);
+        assert_eq!(sync_map(&provider, &map, &docs, 1).entries.len(), 1);
+    }
+
+    /// Sintesi documents itself: the anchors in `crates/core/README.md` must
+    /// match `sintesi-map.json` at the repository root and the current code.
+    /// Regenerate the map with `SINTESI_UPDATE_SELF_DOCS=1 cargo test self_docs`.
+    #[test]
+    fn test_self_docs_in_sync() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let provider = DiskProvider::new(&root);
+        let docs = [PathBuf::from("crates/core/README.md")];
+        let map_path = root.join(MAP_FILE);
+        let map = SintesiMap::load(&map_path).unwrap_or_default();
+
+        if std::env::var("SINTESI_UPDATE_SELF_DOCS").is_ok() {
+            let synced = sync_map(&provider, &map, &docs, now_millis());
+            if synced != map {
+                synced.save(&map_path).unwrap();
+            }
+            return;
+        }
+
+        let report = check_docs(&provider, &map, &docs);
+        // Both the Node API and the Rust sources are documented
+        for analyzed in ["packages/core/", "crates/core/src/"] {
+            assert!(
+                report
+                    .drift
+                    .entries
+                    .iter()
+                    .any(|entry| entry.code_ref.starts_with(analyzed)),
+                "no anchor documents {}",
+                analyzed
+            );
+        }
+        assert!(
+            report.is_clean(),
+            "crates/core/README.md is out of sync ({:?}); update the docs and rerun tests with SINTESI_UPDATE_SELF_DOCS=1",
+            report
+        );
+    }
+}
@@ -1,9 +1,31 @@
 use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum Error {
     Git(git2::Error),
     Reason(String),
+    /// An operation (e.g. `"analyze phase"`, `"search"`) ran past its time limit
+    Timeout {
+        operation: String,
+        limit: Duration,
+    },
+    /// An operation pushed the process's resident memory over a limit (bytes)
+    MemoryLimit {
+        operation: String,
+        limit: u64,
+    },
+    /// A write (e.g. `"save map"`) was refused in read-only mode (`readonly`)
+    ReadOnly {
+        operation: String,
+    },
+    /// A write to a path outside the workspace or its allowlist was refused
+    /// (`sandbox`)
+    PathNotAllowed {
+        operation: String,
+        path: PathBuf,
+    },
 }
 
 impl fmt::Display for Error {
@@ -11,6 +33,24 @@ impl fmt::Display for Error {
         match self {
             Error::Git(e) => write!(f, "Git error: {}", e),
             Error::Reason(s) => write!(f, "Error: {}", s),
+            Error::Timeout { operation, limit } => {
+                write!(f, "Timeout: {} exceeded {:?}", operation, limit)
+            }
+            Error::MemoryLimit { operation, limit } => write!(
+                f,
+                "Memory limit: {} exceeded {} MiB",
+                operation,
+                limit / (1024 * 1024)
+            ),
+            Error::ReadOnly { operation } => {
+                write!(f, "Read-only: {} is not allowed", operation)
+            }
+            Error::PathNotAllowed { operation, path } => write!(
+                f,
+                "Path not allowed: {} to {} is outside the writable paths",
+                operation,
+                path.display()
+            ),
         }
     }
 }
@@ -27,4 +67,9 @@ impl Error {
     pub fn from_reason(msg: impl Into<String>) -> Self {
         Error::Reason(msg.into())
     }
+
+    /// Whether the error is a time or memory limit being hit
+    pub fn is_limit(&self) -> bool {
+        matches!(self, Error::Timeout { .. } | Error::MemoryLimit { .. })
+    }
 }
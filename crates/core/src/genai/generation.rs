@@ -0,0 +1,145 @@
+//! Structured generation results
+//!
+//! The agent returns a `Generation` rather than bare markdown: the content,
+//! the sources it was grounded on (`Citation`), the model that wrote it, and
+//! the tokens it cost. Results serialize as camelCase JSON for audit logs, and
+//! `markdown_with_sources` renders the content followed by a list of its
+//! sources for docs that show where they came from.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A source a generation was grounded on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Citation {
+    /// ID of the anchor the source belongs to
+    pub anchor_id: Option<String>,
+    /// Code reference of the cited symbol (`src/auth.ts#login`)
+    pub code_ref: Option<String>,
+    /// The source text given to the model (a signature or existing docs)
+    pub snippet: String,
+}
+
+/// The model that produced a generation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelInfo {
+    /// Provider name (`openai`, `gemini`, `none` for the placeholder)
+    pub provider: String,
+    /// Model name as the provider knows it
+    pub model: String,
+}
+
+impl ModelInfo {
+    /// Describe a model by its provider and the provider's name for it
+    pub fn new(provider: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            model: model.into(),
+        }
+    }
+}
+
+impl Default for ModelInfo {
+    /// The placeholder agent, which calls no model
+    fn default() -> Self {
+        Self::new("none", "placeholder")
+    }
+}
+
+/// Tokens a generation cost, as reported by the provider
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenUsage {
+    /// Tokens in the prompt
+    pub input_tokens: u32,
+    /// Tokens in the response
+    pub output_tokens: u32,
+}
+
+impl TokenUsage {
+    /// Input and output tokens together, saturating at `u32::MAX`
+    pub fn total(&self) -> u32 {
+        self.input_tokens.saturating_add(self.output_tokens)
+    }
+}
+
+/// Documentation generated by the agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Generation {
+    /// Generated markdown, ready for injection into an anchor
+    pub markdown: String,
+    /// Sources the markdown was grounded on, in prompt order
+    pub citations: Vec<Citation>,
+    /// The model that wrote it
+    pub model: ModelInfo,
+    /// Tokens it cost
+    pub usage: TokenUsage,
+}
+
+impl Generation {
+    /// The markdown followed by a `Sources:` list of the cited code
+    /// references (citations without one are left out)
+    pub fn markdown_with_sources(&self) -> String {
+        let sources: Vec<&str> = self
+            .citations
+            .iter()
+            .filter_map(|citation| citation.code_ref.as_deref())
+            .collect();
+        if sources.is_empty() {
+            return self.markdown.clone();
+        }
+        let mut out = self.markdown.trim_end().to_string();
+        out.push_str("\n\nSources:\n\n");
+        for source in sources {
+            out.push_str(&format!("- `{}`\n", source));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_with_sources() {
+        let generation = Generation {
+            markdown: "Logs a user in.\n".to_string(),
+            citations: vec![
+                Citation {
+                    anchor_id: Some("login".to_string()),
+                    code_ref: Some("src/auth.ts#login".to_string()),
+                    snippet: "function login(user: string): void".to_string(),
+                },
+                Citation {
+                    anchor_id: Some("login".to_string()),
+                    code_ref: None,
+                    snippet: "Old docs".to_string(),
+                },
+            ],
+            model: ModelInfo::default(),
+            usage: TokenUsage {
+                input_tokens: 120,
+                output_tokens: 30,
+            },
+        };
+        assert_eq!(
+            generation.markdown_with_sources(),
+            "Logs a user in.\n\nSources:\n\n- `src/auth.ts#login`\n"
+        );
+        assert_eq!(generation.usage.total(), 150);
+        let usage = TokenUsage {
+            input_tokens: u32::MAX,
+            output_tokens: 1,
+        };
+        assert_eq!(usage.total(), u32::MAX);
+
+        let json = serde_json::to_value(&generation).unwrap();
+        assert_eq!(json["citations"][0]["codeRef"], "src/auth.ts#login");
+        assert_eq!(json["model"]["provider"], "none");
+        assert_eq!(json["usage"]["outputTokens"], 30);
+    }
+}
@@ -5,8 +5,17 @@
 //! - LLM API interactions (OpenAI, Gemini, etc.)
 //! - Content generation based on code signature changes
 //!
+//! Generations are returned as a `Generation`: the markdown with its
+//! citations, model, and token usage (see `generation`).
+//!
 //! NOTE: This module is currently a placeholder for future implementation.
 
+pub mod generation;
+
+pub use generation::{Citation, Generation, ModelInfo, TokenUsage};
+
+use crate::content::SintesiAnchor;
+
 /// Placeholder for Gen AI functionality
 ///
 /// This will be implemented in the future to handle:
@@ -14,30 +23,74 @@
 /// 2. Requesting LLM to update documentation based on changes
 /// 3. Returning formatted Markdown for injection
 pub struct GenAiAgent {
-    // Configuration will go here (API keys, model selection, etc.)
+    // Configuration will go here (API keys, etc.)
+    model: ModelInfo,
 }
 
 impl GenAiAgent {
     /// Create a new Gen AI agent
     pub fn new() -> Self {
-        Self {}
+        Self {
+            model: ModelInfo::default(),
+        }
     }
 
-    /// Generate documentation for a code signature (placeholder)
-    pub fn generate_documentation(&self, _signature: &str) -> String {
+    /// Report generations as written by this model
+    pub fn with_model(mut self, model: ModelInfo) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Generate documentation for the symbol at `code_ref` (placeholder)
+    pub fn generate_documentation(&self, code_ref: &str, signature: &str) -> Generation {
         // TODO: Implement actual LLM interaction
-        String::from("Generated documentation will go here")
+        self.generation(
+            "Generated documentation will go here",
+            vec![Citation {
+                anchor_id: None,
+                code_ref: Some(code_ref.to_string()),
+                snippet: signature.to_string(),
+            }],
+        )
     }
 
-    /// Update documentation based on signature change (placeholder)
+    /// Update an anchor's documentation after its signature changed
+    /// (placeholder)
+    ///
+    /// The generation cites the new signature and the anchor's current
+    /// content.
     pub fn update_documentation(
         &self,
+        anchor: &SintesiAnchor,
         _old_signature: &str,
-        _new_signature: &str,
-        _old_content: &str,
-    ) -> String {
+        new_signature: &str,
+    ) -> Generation {
         // TODO: Implement actual LLM interaction
-        String::from("Updated documentation will go here")
+        self.generation(
+            "Updated documentation will go here",
+            vec![
+                Citation {
+                    anchor_id: Some(anchor.id.clone()),
+                    code_ref: anchor.code_ref.clone(),
+                    snippet: new_signature.to_string(),
+                },
+                Citation {
+                    anchor_id: Some(anchor.id.clone()),
+                    code_ref: None,
+                    snippet: anchor.content.clone(),
+                },
+            ],
+        )
+    }
+
+    fn generation(&self, markdown: &str, citations: Vec<Citation>) -> Generation {
+        Generation {
+            markdown: markdown.to_string(),
+            citations,
+            model: self.model.clone(),
+            // No model was called
+            usage: TokenUsage::default(),
+        }
     }
 }
 
@@ -1,7 +1,12 @@
+use crate::error::Error;
+use crate::paths::{canonical, extended_length};
+use crate::readonly::ensure_writable;
 use git2::{Repository, DiffOptions, Diff};
-use std::path::Path;
+use std::collections::hash_map::{Entry, HashMap};
+use std::path::{Path, PathBuf};
 
 pub mod analyzer;
+pub mod remote;
 
 pub struct GitService {
     repo: Repository,
@@ -60,6 +65,7 @@ impl GitService {
     pub fn get_changed_files(&self, base_ref: Option<&str>, staged: bool) -> Result<Vec<String>, git2::Error> {
         let mut diff_opts = DiffOptions::new();
         diff_opts.include_untracked(true);
+        diff_opts.recurse_untracked_dirs(true);
         
         // Reuse the helper!
         let diff = self.get_diff_obj(base_ref, staged, &mut diff_opts)?;
@@ -81,4 +87,113 @@ impl GitService {
 
         Ok(files)
     }
+
+    /// Stage files and commit them on HEAD
+    ///
+    /// `paths` may be absolute or relative to the working directory. Uses the
+    /// repository's configured signature. Returns the new commit ID, or
+    /// `Error::ReadOnly` in read-only mode.
+    pub fn commit_paths(&self, paths: &[PathBuf], message: &str) -> Result<String, Error> {
+        ensure_writable("git commit")?;
+        let mut index = self.repo.index()?;
+        for path in paths {
+            index.add_path(&self.relative_path(path)?)?;
+        }
+        index.write()?;
+
+        let tree = self.repo.find_tree(index.write_tree()?)?;
+        let signature = self.repo.signature()?;
+        let parent = self.repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+        let id = self.repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)?;
+        Ok(id.to_string())
+    }
+
+    /// Blame every line of a file as it is in the working tree
+    ///
+    /// `path` may be absolute or relative to the working directory. Returns
+    /// one entry per line: the author name and time (milliseconds since Unix
+    /// epoch) of the commit that last changed it, or `None` for uncommitted
+    /// lines.
+    pub fn blame_lines(&self, path: &Path) -> Result<Vec<Option<(String, i64)>>, git2::Error> {
+        let relative = self.relative_path(path)?;
+        let content = std::fs::read(extended_length(&self.workdir()?.join(&relative)))
+            .map_err(|e| git2::Error::from_str(&format!("{}: {}", path.display(), e)))?;
+        let line_count = content.split_inclusive(|&b| b == b'\n').count();
+        let committed = match self.repo.blame_file(&relative, None) {
+            Ok(blame) => blame,
+            // Untracked files have no history
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(vec![None; line_count]),
+            Err(e) => return Err(e),
+        };
+        let blame = committed.blame_buffer(&content)?;
+
+        // Hunks split by uncommitted edits lose their signatures, so authors
+        // are read from the commits
+        let mut authors: HashMap<git2::Oid, (String, i64)> = HashMap::new();
+        let mut lines = Vec::with_capacity(line_count);
+        for line in 1..=line_count {
+            let id = blame.get_line(line).map(|hunk| hunk.final_commit_id());
+            let Some(id) = id.filter(|id| !id.is_zero()) else {
+                lines.push(None);
+                continue;
+            };
+            let author = match authors.entry(id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let author = self.repo.find_commit(id)?.author().to_owned();
+                    let name = author.name().or(author.email()).unwrap_or_default().to_string();
+                    entry.insert((name, author.when().seconds() * 1000))
+                }
+            };
+            lines.push(Some(author.clone()));
+        }
+        Ok(lines)
+    }
+
+    /// Canonical path of the working tree
+    pub fn workdir(&self) -> Result<PathBuf, git2::Error> {
+        let workdir = self
+            .repo
+            .workdir()
+            .ok_or_else(|| git2::Error::from_str("Bare repositories have no working tree"))?;
+        Ok(canonical(workdir))
+    }
+
+    /// Path relative to the working directory
+    fn relative_path(&self, path: &Path) -> Result<PathBuf, git2::Error> {
+        let workdir = self.workdir()?;
+        let absolute = if path.is_absolute() { path.to_path_buf() } else { workdir.join(path) };
+        let absolute = canonical(&absolute);
+        absolute
+            .strip_prefix(&workdir)
+            .map(Path::to_path_buf)
+            .map_err(|_| git2::Error::from_str(&format!("{} is outside the repository", path.display())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_and_blame_deep_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Ana").unwrap();
+        config.set_str("user.email", "ana@example.com").unwrap();
+        // Longer than Windows' MAX_PATH (260 characters)
+        let deep = (0..25).fold(dir.path().join("docs"), |path, _| path.join("node_modules"));
+        std::fs::create_dir_all(&deep).unwrap();
+        let doc = deep.join("README.md");
+        std::fs::write(&doc, "# Deep\n").unwrap();
+
+        let git = GitService::open(dir.path()).unwrap();
+        git.commit_paths(std::slice::from_ref(&doc), "docs").unwrap();
+        let blame = git.blame_lines(&doc).unwrap();
+        assert_eq!(blame.len(), 1);
+        assert_eq!(blame[0].as_ref().unwrap().0, "Ana");
+    }
 }
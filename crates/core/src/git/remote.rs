@@ -0,0 +1,219 @@
+//! Remote repository analysis
+//!
+//! Lets a central documentation service audit repositories it has no
+//! checkout of: the repository is shallow-cloned into a temporary directory,
+//! run through discovery, analysis, and (when it has a map) drift detection,
+//! and the directory is removed when the analysis is dropped out of scope.
+//!
+//! HTTPS remotes need the `remote-https` feature (which links OpenSSL);
+//! local paths, `file://`, and `git://` URLs work without it.
+
+use crate::ast::{AstAnalyzerInternal, SymbolInfo};
+use crate::content::{discover_files, DiscoveryConfig};
+use crate::drift::{check_drift, DriftResult, SintesiMap, MAP_FILE};
+use crate::error::Error;
+use crate::provider::DiskProvider;
+use git2::build::CheckoutBuilder;
+use git2::{FetchOptions, Repository};
+use std::path::Path;
+
+/// Result of analyzing a remote repository
+#[derive(Debug, Clone)]
+pub struct RemoteAnalysis {
+    /// Repository URL that was cloned
+    pub url: String,
+    /// Requested ref (branch, tag, or commit), `None` for the default branch
+    pub reference: Option<String>,
+    /// Commit that was analyzed
+    pub commit: String,
+    /// Number of markdown files discovered
+    pub markdown_files: usize,
+    /// Number of source files discovered
+    pub source_files: usize,
+    /// Symbols found in source files (paths relative to the repository root)
+    pub symbols: Vec<SymbolInfo>,
+    /// Drift against the repository's own map, if it has one
+    pub drift: Option<DriftResult>,
+    /// Per-file analysis errors
+    pub errors: Vec<String>,
+}
+
+/// Shallow-clone a repository and analyze it
+///
+/// # Arguments
+/// * `url` - Repository URL or local path
+/// * `reference` - Branch, tag, or commit to analyze (default branch if `None`)
+///
+/// # Returns
+/// The analysis; the temporary checkout has already been removed
+pub fn analyze_remote(url: &str, reference: Option<&str>) -> Result<RemoteAnalysis, Error> {
+    let checkout = tempfile::Builder::new()
+        .prefix("sintesi-remote-")
+        .tempdir()
+        .map_err(|e| Error::from_reason(format!("Failed to create temp dir: {}", e)))?;
+
+    let commit = shallow_checkout(url, reference, checkout.path())?;
+    let mut analysis = analyze_checkout(checkout.path());
+    analysis.url = url.to_string();
+    analysis.reference = reference.map(String::from);
+    analysis.commit = commit;
+
+    // `checkout` is dropped here, deleting the clone
+    Ok(analysis)
+}
+
+/// Fetch a single commit (depth 1) into `dir` and check it out
+fn shallow_checkout(url: &str, reference: Option<&str>, dir: &Path) -> Result<String, Error> {
+    let repo = Repository::init(dir)?;
+    let mut remote = repo.remote_anonymous(url)?;
+
+    let refspec = reference.unwrap_or("HEAD");
+    let mut options = FetchOptions::new();
+    options.depth(1);
+    if remote.fetch(&[refspec], Some(&mut options), None).is_err() {
+        // Not every transport supports shallow fetches (e.g. local paths);
+        // a genuinely unreachable remote fails again here with its real error
+        remote.fetch(&[refspec], None, None)?;
+    }
+
+    let commit = repo.find_reference("FETCH_HEAD")?.peel_to_commit()?;
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().force()))?;
+    repo.set_head_detached(commit.id())?;
+
+    Ok(commit.id().to_string())
+}
+
+/// Run discovery, analysis, and drift detection on a checked-out tree
+fn analyze_checkout(root: &Path) -> RemoteAnalysis {
+    let discovered = discover_files(root, DiscoveryConfig::new());
+    let analysis = AstAnalyzerInternal::new().analyze_files(root, &discovered.source_files);
+
+    let drift = SintesiMap::load(root.join(MAP_FILE))
+        .ok()
+        .map(|map| check_drift(&DiskProvider::new(root), &map));
+
+    RemoteAnalysis {
+        url: String::new(),
+        reference: None,
+        commit: String::new(),
+        markdown_files: discovered.markdown_files.len(),
+        source_files: discovered.source_files.len(),
+        symbols: analysis.symbols,
+        drift,
+        errors: analysis.errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::{DriftStatus, SintesiMapEntry};
+    use std::fs;
+
+    /// Create a repository with two commits on `main` and a `v1` tag on the first
+    fn origin() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+
+        let commit = |message: &str, files: &[(&str, &str)]| {
+            for (path, content) in files {
+                let full = dir.path().join(path);
+                fs::create_dir_all(full.parent().unwrap()).unwrap();
+                fs::write(full, content).unwrap();
+            }
+            let mut index = repo.index().unwrap();
+            index
+                .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+                .unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parents: Vec<git2::Commit> = repo
+                .head()
+                .ok()
+                .and_then(|h| h.peel_to_commit().ok())
+                .into_iter()
+                .collect();
+            let parents: Vec<&git2::Commit> = parents.iter().collect();
+            repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+        };
+
+        let first = commit(
+            "first",
+            &[
+                (
+                    "src/auth.ts",
+                    "export function login(user: string): boolean { return true; }",
+                ),
+                ("docs/auth.md", "# Auth\n"),
+            ],
+        );
+        let map = SintesiMap {
+            entries: vec![SintesiMapEntry {
+                id: "a".to_string(),
+                code_ref: "src/auth.ts#login".to_string(),
+                doc_file: "docs/auth.md".to_string(),
+                code_signature_hash: "stale".to_string(),
+                last_updated: 0,
+                symbol_id: None,
+                symbol_key: None,
+                deprecated: false,
+                content_hash: None,
+                code_signature: None,
+            }],
+            ..SintesiMap::new()
+        };
+        commit(
+            "second",
+            &[
+                ("src/session.ts", "export const TTL = 60;"),
+                (MAP_FILE, &serde_json::to_string(&map).unwrap()),
+            ],
+        );
+        repo.tag_lightweight("v1", &repo.find_object(first, None).unwrap(), false)
+            .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_analyze_default_branch() {
+        let origin = origin();
+        let url = origin.path().to_string_lossy().to_string();
+
+        let analysis = analyze_remote(&url, None).unwrap();
+
+        assert_eq!(analysis.commit.len(), 40);
+        assert_eq!(analysis.source_files, 2);
+        assert_eq!(analysis.markdown_files, 1);
+        let names: Vec<&str> = analysis.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert!(names.contains(&"login") && names.contains(&"TTL"));
+        assert!(analysis.symbols[0].file_path.starts_with("src/"));
+        let drift = analysis.drift.unwrap();
+        assert_eq!(drift.entries[0].status, DriftStatus::Drifted);
+    }
+
+    #[test]
+    fn test_analyze_tag() {
+        let origin = origin();
+        let url = origin.path().to_string_lossy().to_string();
+
+        let analysis = analyze_remote(&url, Some("refs/tags/v1")).unwrap();
+
+        assert_eq!(analysis.reference.as_deref(), Some("refs/tags/v1"));
+        assert_eq!(analysis.source_files, 1);
+        assert!(analysis.drift.is_none());
+    }
+
+    #[test]
+    fn test_invalid_remote() {
+        let missing = tempfile::tempdir().unwrap().path().join("missing");
+        assert!(analyze_remote(&missing.to_string_lossy(), None).is_err());
+    }
+}
@@ -1,8 +1,11 @@
+use crate::provider::{ContentProvider, DiskProvider};
+use crate::text::{SkippedFile, DEFAULT_MAX_FILE_SIZE};
 use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::Direction;
 use regex::Regex;
 use std::collections::HashMap;
-use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone)]
 pub struct FileNode {
@@ -13,6 +16,11 @@ pub struct FileNode {
 pub struct ProjectGraph {
     pub graph: DiGraph<FileNode, ()>,
     pub node_map: HashMap<PathBuf, NodeIndex>,
+    /// Resolved (extensionless) targets of each file's relative imports
+    imports: HashMap<PathBuf, Vec<PathBuf>>,
+    /// Files kept without edges: not text, or over `max_file_size`
+    pub skipped: Vec<SkippedFile>,
+    max_file_size: usize,
 }
 
 impl ProjectGraph {
@@ -20,9 +28,18 @@ impl ProjectGraph {
         Self {
             graph: DiGraph::new(),
             node_map: HashMap::new(),
+            imports: HashMap::new(),
+            skipped: Vec::new(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
         }
     }
 
+    /// Skip the imports of files over `bytes` in size
+    pub fn max_file_size(mut self, bytes: usize) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
     pub fn add_file(&mut self, path: PathBuf) -> NodeIndex {
         if let Some(&idx) = self.node_map.get(&path) {
             return idx;
@@ -46,91 +63,425 @@ impl ProjectGraph {
         let to_idx = self.add_file(to);
         self.graph.update_edge(from_idx, to_idx, ());
     }
-}
 
-pub fn build_graph(files: &[PathBuf], root: &Path) -> ProjectGraph {
-    let mut project_graph = ProjectGraph::new();
-    
-    // Pre-populate nodes
-    for file in files {
-        project_graph.add_file(file.clone());
+    /// Add or re-parse a file, replacing its outgoing dependencies
+    ///
+    /// Files whose imports now resolve to a newly added file are relinked too,
+    /// so the graph matches a full rebuild without rescanning the project.
+    /// Content over the size limit leaves the file without outgoing edges.
+    pub fn update_file(&mut self, path: PathBuf, content: &str) {
+        let is_new = !self.node_map.contains_key(&path);
+        self.add_file(path.clone());
+        let imports = self.imports_within_limit(&path, content);
+        self.imports.insert(path.clone(), imports);
+        self.relink(&path);
+        if is_new {
+            for importer in self.importers_of(&path) {
+                self.relink(&importer);
+            }
+        }
     }
 
-    let import_regex = Regex::new(r#"(?:import\s+(?:[\w\s{},*]+from\s+)?|require\()['"]([^'"]+)['"]"#).unwrap();
-
-// Helper to normalize paths (remove . and ..) without checking filesystem
-    fn normalize_path(path: &Path) -> PathBuf {
-        let mut components = path.components().peekable();
-        let mut ret = if let Some(c) = components.peek() {
-            match c {
-                std::path::Component::Prefix(..) => {
-                    let mut p = PathBuf::new();
-                    p.push(components.next().unwrap());
-                    p
-                }
-                std::path::Component::RootDir => {
-                    components.next();
-                    PathBuf::from("/")
-                }
-                _ => PathBuf::new(),
-            }
-        } else {
-            PathBuf::new()
+    /// Remove a file and its edges, relinking files that imported it
+    pub fn remove_file(&mut self, path: &Path) {
+        let Some(idx) = self.node_map.remove(path) else {
+            return;
         };
-    
-        for component in components {
-            match component {
-                std::path::Component::Prefix(..) => unreachable!(),
-                std::path::Component::RootDir => unreachable!(),
-                std::path::Component::CurDir => {}
-                std::path::Component::ParentDir => { ret.pop(); }
-                std::path::Component::Normal(c) => { ret.push(c); }
+        self.imports.remove(path);
+        self.skipped
+            .retain(|skipped| Path::new(&skipped.file_path) != path);
+        self.graph.remove_node(idx);
+        // `remove_node` moves the last node into the freed index
+        if let Some(moved) = self.graph.node_weight(idx) {
+            self.node_map.insert(moved.path.clone(), idx);
+        }
+        for importer in self.importers_of(path) {
+            self.relink(&importer);
+        }
+    }
+
+    /// Files that import `path`
+    pub fn dependents(&self, path: &Path) -> Vec<PathBuf> {
+        self.neighbors(path, Direction::Incoming)
+    }
+
+    /// Files imported by `path`
+    pub fn dependencies(&self, path: &Path) -> Vec<PathBuf> {
+        self.neighbors(path, Direction::Outgoing)
+    }
+
+    fn neighbors(&self, path: &Path, direction: Direction) -> Vec<PathBuf> {
+        let Some(&idx) = self.node_map.get(path) else {
+            return Vec::new();
+        };
+        self.graph
+            .neighbors_directed(idx, direction)
+            .map(|n| self.graph[n].path.clone())
+            .collect()
+    }
+
+    /// Imports of a file, or none if it is over the size limit (recorded in
+    /// `skipped`)
+    fn imports_within_limit(&mut self, path: &Path, content: &str) -> Vec<PathBuf> {
+        self.skipped
+            .retain(|skipped| Path::new(&skipped.file_path) != path);
+        let file_path = path.to_string_lossy();
+        match SkippedFile::over_limit(&file_path, content.len(), self.max_file_size) {
+            Some(skipped) => {
+                self.skipped.push(skipped);
+                Vec::new()
             }
+            None => parse_imports(path, content),
         }
-        ret
     }
 
-    for file_path in files {
-        // Only process JS/TS/RS files for now
-        let ext = file_path.extension().and_then(|s| s.to_str()).unwrap_or("");
-        if !["ts", "tsx", "js", "jsx", "rs"].contains(&ext) {
-            continue;
+    /// Recompute a file's outgoing edges from its recorded imports
+    fn relink(&mut self, path: &Path) {
+        let Some(&idx) = self.node_map.get(path) else {
+            return;
+        };
+        let targets: Vec<NodeIndex> = self.graph.neighbors(idx).collect();
+        for target in targets {
+            if let Some(edge) = self.graph.find_edge(idx, target) {
+                self.graph.remove_edge(edge);
+            }
         }
 
-        let full_path = root.join(file_path);
-        if let Ok(content) = fs::read_to_string(&full_path) {
-            for cap in import_regex.captures_iter(&content) {
-                if let Some(import_path) = cap.get(1) {
-                    let import_str = import_path.as_str();
-                    
-                    if import_str.starts_with('.') {
-                        // Resolve relative to the current file
-                        let current_dir = file_path.parent().unwrap_or(Path::new(""));
-                        let resolved_raw = current_dir.join(import_str);
-                        let resolved = normalize_path(&resolved_raw);
-                        
-                        // Try various extensions
-                         let candidates = vec![
-                            resolved.clone(),
-                            resolved.with_extension("ts"),
-                            resolved.with_extension("tsx"),
-                            resolved.with_extension("js"),
-                            resolved.with_extension("jsx"),
-                            resolved.join("index.ts"),
-                            resolved.join("index.js"),
-                        ];
-
-                        for candidate in candidates {
-                             if project_graph.node_map.contains_key(&candidate) {
-                                 project_graph.add_dependency(file_path.clone(), candidate);
-                                 break;
-                             }
-                        }
-                    }
-                }
+        let resolved: Vec<NodeIndex> = self
+            .imports
+            .get(path)
+            .into_iter()
+            .flatten()
+            .filter_map(|base| {
+                import_candidates(base)
+                    .into_iter()
+                    .find_map(|candidate| self.node_map.get(&candidate).copied())
+            })
+            .collect();
+        for target in resolved {
+            self.graph.update_edge(idx, target, ());
+        }
+    }
+
+    /// Files with an import that could resolve to `path`
+    fn importers_of(&self, path: &Path) -> Vec<PathBuf> {
+        self.imports
+            .iter()
+            .filter(|(_, bases)| {
+                bases
+                    .iter()
+                    .any(|base| import_candidates(base).iter().any(|c| c == path))
+            })
+            .map(|(file, _)| file.clone())
+            .collect()
+    }
+}
+
+impl Default for ProjectGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Normalize a path (remove `.` and `..`) without touching the filesystem
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut components = path.components().peekable();
+    let mut ret = if let Some(c) = components.peek() {
+        match c {
+            std::path::Component::Prefix(..) => {
+                let mut p = PathBuf::new();
+                p.push(components.next().unwrap());
+                p
             }
+            std::path::Component::RootDir => {
+                components.next();
+                PathBuf::from("/")
+            }
+            _ => PathBuf::new(),
+        }
+    } else {
+        PathBuf::new()
+    };
+
+    for component in components {
+        match component {
+            std::path::Component::Prefix(..) => unreachable!(),
+            std::path::Component::RootDir => unreachable!(),
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => { ret.pop(); }
+            std::path::Component::Normal(c) => { ret.push(c); }
         }
     }
+    ret
+}
+
+/// Candidate file paths for a resolved module specifier (without extension)
+pub(crate) fn module_candidates(resolved: &Path) -> Vec<PathBuf> {
+    vec![
+        resolved.to_path_buf(),
+        resolved.with_extension("ts"),
+        resolved.with_extension("tsx"),
+        resolved.with_extension("js"),
+        resolved.with_extension("jsx"),
+        resolved.join("index.ts"),
+        resolved.join("index.js"),
+    ]
+}
+
+/// Candidate files for an import target: JS/TS modules, or Rust modules
+/// (`auth.rs`, `auth/mod.rs`) for `mod` declarations
+fn import_candidates(resolved: &Path) -> Vec<PathBuf> {
+    let mut candidates = module_candidates(resolved);
+    candidates.push(resolved.with_extension("rs"));
+    candidates.push(resolved.join("mod.rs"));
+    candidates
+}
+
+fn import_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r#"(?:(?:import|export)\s+(?:[\w\s{},*]+from\s+)?|require\()['"]([^'"]+)['"]"#)
+            .unwrap()
+    })
+}
+
+fn mod_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*;").unwrap()
+    })
+}
+
+/// Resolved (extensionless) targets of the relative imports in a file
+///
+/// Re-exports (`export * from './auth'`) count as imports. For Rust files
+/// the targets are the modules declared with `mod name;`.
+fn parse_imports(file_path: &Path, content: &str) -> Vec<PathBuf> {
+    // Only process JS/TS/RS files and components for now
+    let ext = file_path.extension().and_then(|s| s.to_str()).unwrap_or("");
+    if ext == "rs" {
+        return parse_mods(file_path, content);
+    }
+    if !["ts", "tsx", "js", "jsx", "vue", "svelte"].contains(&ext) {
+        return Vec::new();
+    }
+
+    let current_dir = file_path.parent().unwrap_or(Path::new(""));
+    import_regex()
+        .captures_iter(content)
+        .filter_map(|cap| cap.get(1))
+        .map(|m| m.as_str())
+        .filter(|import| import.starts_with('.'))
+        // Resolve relative to the current file
+        .map(|import| normalize_path(&current_dir.join(import)))
+        .collect()
+}
+
+/// Module paths declared by a Rust file (`mod auth;` in `src/lib.rs` is
+/// `src/auth`, in `src/server.rs` it is `src/server/auth`)
+fn parse_mods(file_path: &Path, content: &str) -> Vec<PathBuf> {
+    let parent = file_path.parent().unwrap_or(Path::new(""));
+    let dir = match file_path.file_stem().and_then(|s| s.to_str()) {
+        Some("lib" | "main" | "mod") | None => parent.to_path_buf(),
+        Some(stem) => parent.join(stem),
+    };
+    mod_regex()
+        .captures_iter(content)
+        .map(|cap| dir.join(&cap[1]))
+        .collect()
+}
+
+pub fn build_graph(files: &[PathBuf], root: &Path) -> ProjectGraph {
+    build_graph_from(&DiskProvider::new(root), files)
+}
+
+/// Build the import graph from files read through a content provider
+///
+/// Unreadable files stay in the graph without outgoing edges, as do binary
+/// files and files over `DEFAULT_MAX_FILE_SIZE`, which are listed in
+/// `skipped`.
+pub fn build_graph_from(provider: &dyn ContentProvider, files: &[PathBuf]) -> ProjectGraph {
+    build_graph_with(provider, files, DEFAULT_MAX_FILE_SIZE)
+}
+
+/// Build the import graph, skipping the imports of files over
+/// `max_file_size` bytes
+pub fn build_graph_with(
+    provider: &dyn ContentProvider,
+    files: &[PathBuf],
+    max_file_size: usize,
+) -> ProjectGraph {
+    let mut project_graph = ProjectGraph::new().max_file_size(max_file_size);
+
+    // Pre-populate nodes so imports resolve regardless of file order
+    for file in files {
+        project_graph.add_file(file.clone());
+    }
+
+    for file_path in files {
+        let imports = match provider.read(file_path) {
+            Ok(content) => project_graph.imports_within_limit(file_path, &content),
+            Err(e) => {
+                let path = file_path.to_string_lossy();
+                project_graph
+                    .skipped
+                    .extend(SkippedFile::from_error(&path, &e));
+                continue;
+            }
+        };
+        project_graph.imports.insert(file_path.clone(), imports);
+        project_graph.relink(file_path);
+    }
 
     project_graph
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn paths(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_build_graph() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/lib")).unwrap();
+        fs::write(
+            dir.path().join("src/app.ts"),
+            "import { a } from './lib';\nimport x from 'react';",
+        )
+        .unwrap();
+        fs::write(dir.path().join("src/lib/index.ts"), "const b = require('../util');").unwrap();
+        fs::write(dir.path().join("src/util.js"), "").unwrap();
+        let files: Vec<PathBuf> = ["src/app.ts", "src/lib/index.ts", "src/util.js"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        let graph = build_graph(&files, dir.path());
+
+        assert_eq!(
+            graph.dependencies(Path::new("src/app.ts")),
+            [PathBuf::from("src/lib/index.ts")]
+        );
+        assert_eq!(
+            graph.dependents(Path::new("src/util.js")),
+            [PathBuf::from("src/lib/index.ts")]
+        );
+    }
+
+    #[test]
+    fn test_skips_large_and_binary_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("dist")).unwrap();
+        fs::write(
+            dir.path().join("src.ts"),
+            "import { b } from './dist/bundle';",
+        )
+        .unwrap();
+        let bundle = format!("import x from '../src';\n{}", "var a=1;".repeat(64));
+        fs::write(dir.path().join("dist/bundle.js"), &bundle).unwrap();
+        fs::write(
+            dir.path().join("logo.png"),
+            b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR",
+        )
+        .unwrap();
+        let files: Vec<PathBuf> = ["src.ts", "dist/bundle.js", "logo.png"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        let mut graph = build_graph_with(&DiskProvider::new(dir.path()), &files, 256);
+        let reasons: Vec<(&str, &str)> = graph
+            .skipped
+            .iter()
+            .map(|s| (s.file_path.as_str(), s.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            [("dist/bundle.js", "too-large"), ("logo.png", "binary")]
+        );
+        // Skipped files are still import targets
+        assert_eq!(
+            graph.dependencies(Path::new("src.ts")),
+            [PathBuf::from("dist/bundle.js")]
+        );
+        assert!(graph.dependencies(Path::new("dist/bundle.js")).is_empty());
+
+        graph.update_file(PathBuf::from("dist/bundle.js"), "import x from '../src';");
+        assert_eq!(graph.skipped.len(), 1);
+        assert_eq!(
+            graph.dependencies(Path::new("dist/bundle.js")),
+            [PathBuf::from("src.ts")]
+        );
+    }
+
+    #[test]
+    fn test_incremental_updates_match_rebuild() {
+        let mut graph = ProjectGraph::new();
+        graph.update_file(PathBuf::from("src/a.ts"), "import { b } from './b';");
+        graph.update_file(PathBuf::from("src/c.ts"), "import { b } from './b';");
+        assert!(graph.dependencies(Path::new("src/a.ts")).is_empty());
+
+        // A new file satisfies the pending imports
+        graph.update_file(PathBuf::from("src/b.ts"), "");
+        assert_eq!(
+            paths(graph.dependents(Path::new("src/b.ts"))),
+            [PathBuf::from("src/a.ts"), PathBuf::from("src/c.ts")]
+        );
+
+        // Re-parsing replaces outgoing edges
+        graph.update_file(PathBuf::from("src/a.ts"), "import { c } from './c';");
+        assert_eq!(
+            graph.dependencies(Path::new("src/a.ts")),
+            [PathBuf::from("src/c.ts")]
+        );
+        assert_eq!(
+            graph.dependents(Path::new("src/b.ts")),
+            [PathBuf::from("src/c.ts")]
+        );
+
+        // Removing a node keeps the index map consistent
+        graph.remove_file(Path::new("src/a.ts"));
+        assert!(!graph.node_map.contains_key(Path::new("src/a.ts")));
+        for (path, &idx) in &graph.node_map {
+            assert_eq!(&graph.graph[idx].path, path);
+        }
+        assert!(graph.dependents(Path::new("src/c.ts")).is_empty());
+        assert_eq!(
+            graph.dependents(Path::new("src/b.ts")),
+            [PathBuf::from("src/c.ts")]
+        );
+
+        graph.remove_file(Path::new("src/b.ts"));
+        assert!(graph.dependencies(Path::new("src/c.ts")).is_empty());
+    }
+
+    #[test]
+    fn test_reexports_and_rust_modules() {
+        let mut graph = ProjectGraph::new();
+        for file in ["src/auth.ts", "src/lib.rs", "src/server.rs", "src/server/routes/mod.rs"] {
+            graph.add_file(PathBuf::from(file));
+        }
+        graph.update_file(PathBuf::from("src/index.ts"), "export * from './auth';");
+        graph.update_file(PathBuf::from("src/lib.rs"), "pub mod server;\nmod missing;\n");
+        graph.update_file(PathBuf::from("src/server.rs"), "pub(crate) mod routes;\n");
+
+        assert_eq!(
+            graph.dependencies(Path::new("src/index.ts")),
+            [PathBuf::from("src/auth.ts")]
+        );
+        assert_eq!(
+            graph.dependencies(Path::new("src/lib.rs")),
+            [PathBuf::from("src/server.rs")]
+        );
+        assert_eq!(
+            graph.dependencies(Path::new("src/server.rs")),
+            [PathBuf::from("src/server/routes/mod.rs")]
+        );
+    }
+}
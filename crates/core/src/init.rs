@@ -0,0 +1,273 @@
+//! Project scaffolding
+//!
+//! `init` sets up a project for Sintesi in one step:
+//!
+//! - `sintesi.config.json` with the docs directory, map file, and an empty
+//!   freshness policy list (the `policies` key is read by `PolicySet::load`)
+//! - the `.sintesi/` state directory
+//! - an empty `sintesi-map.json`
+//! - a starter docs file showing the anchor syntax
+//! - optionally, a git pre-commit hook running `sintesi check`
+//!
+//! Existing files are left untouched unless `force` is set; an existing map
+//! is never overwritten. All files are written in one transaction.
+
+use crate::apply::Transaction;
+use crate::drift::{FreshnessPolicy, SintesiMap, MAP_FILE};
+use crate::error::Error;
+use crate::paths::canonical;
+use git2::Repository;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Configuration file name, relative to the project root
+pub const CONFIG_FILE: &str = "sintesi.config.json";
+
+/// State directory, relative to the project root
+pub const STATE_DIR: &str = ".sintesi";
+
+/// Name of the starter docs file inside the docs directory
+pub const STARTER_DOC: &str = "getting-started.md";
+
+const STARTER_DOC_CONTENT: &str = r#"# Getting Started
+
+Sintesi keeps this documentation in sync with the code. Wrap the text that
+describes a symbol in an anchor pointing at it:
+
+<!-- sintesi-ignore -->
+<!-- sintesi:start id="example" code_ref="src/index.ts#main" -->
+`main()` starts the application.
+<!-- sintesi:end id="example" -->
+<!-- sintesi-ignore-end -->
+
+Point `code_ref` at one of your own symbols (`path/to/file.ts#symbolName`) and
+remove the `sintesi-ignore` markers to start tracking it. When the symbol's
+signature changes, `sintesi check` reports the anchor as drifted.
+"#;
+
+const STATE_GITIGNORE: &str =
+    "# Per-run pipeline state and caches; drift history is kept\n*.state.json\nast-cache.json\n";
+
+const PRE_COMMIT_HOOK: &str =
+    "#!/bin/sh\n# Installed by `sintesi init`: block commits with drifted documentation\nexec npx sintesi check\n";
+
+/// Options for `init`
+#[derive(Debug, Clone)]
+pub struct InitOptions {
+    /// Documentation directory, relative to the project root
+    pub docs_dir: String,
+    /// Install a git pre-commit hook running `sintesi check`
+    pub pre_commit_hook: bool,
+    /// Overwrite existing scaffolding files (never the map)
+    pub force: bool,
+}
+
+impl Default for InitOptions {
+    fn default() -> Self {
+        Self {
+            docs_dir: "docs".to_string(),
+            pre_commit_hook: false,
+            force: false,
+        }
+    }
+}
+
+impl InitOptions {
+    /// Create default options (`docs/`, no hook, keep existing files)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the documentation directory
+    pub fn docs_dir(mut self, dir: impl Into<String>) -> Self {
+        self.docs_dir = dir.into();
+        self
+    }
+
+    /// Install a git pre-commit hook
+    pub fn pre_commit_hook(mut self, value: bool) -> Self {
+        self.pre_commit_hook = value;
+        self
+    }
+
+    /// Overwrite existing scaffolding files
+    pub fn force(mut self, value: bool) -> Self {
+        self.force = value;
+        self
+    }
+}
+
+/// Files touched by `init`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitResult {
+    /// Files written, relative to the project root
+    pub created: Vec<String>,
+    /// Files left as they were because they already existed
+    pub skipped: Vec<String>,
+}
+
+/// Contents of `sintesi.config.json`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectConfig<'a> {
+    docs_dir: &'a str,
+    map_file: &'a str,
+    policies: &'a [FreshnessPolicy],
+}
+
+/// Scaffold Sintesi configuration and state in a project
+///
+/// # Errors
+/// Fails if a pre-commit hook is requested outside a git repository, or if
+/// any file cannot be written (nothing is left half-written).
+pub fn init(root: impl AsRef<Path>, options: &InitOptions) -> Result<InitResult, Error> {
+    let root = root.as_ref();
+    let config = ProjectConfig {
+        docs_dir: &options.docs_dir,
+        map_file: MAP_FILE,
+        policies: &[],
+    };
+    let config = serde_json::to_string_pretty(&config)
+        .map_err(|e| Error::from_reason(format!("Failed to serialize config: {}", e)))?;
+
+    let mut files: Vec<(PathBuf, String, bool)> = vec![
+        (PathBuf::from(CONFIG_FILE), config + "\n", options.force),
+        (
+            Path::new(STATE_DIR).join(".gitignore"),
+            STATE_GITIGNORE.to_string(),
+            options.force,
+        ),
+        (PathBuf::from(MAP_FILE), SintesiMap::new().to_json()?, false),
+        (
+            Path::new(&options.docs_dir).join(STARTER_DOC),
+            STARTER_DOC_CONTENT.to_string(),
+            options.force,
+        ),
+    ];
+    if options.pre_commit_hook {
+        files.push((hook_path(root)?, PRE_COMMIT_HOOK.to_string(), options.force));
+    }
+
+    let mut result = InitResult::default();
+    let mut transaction = Transaction::new();
+    for (path, content, overwrite) in files {
+        let name = path.to_string_lossy().into_owned();
+        let full = root.join(&path);
+        if full.exists() && !overwrite {
+            result.skipped.push(name);
+            continue;
+        }
+        transaction.stage(full, content);
+        result.created.push(name);
+    }
+    transaction.commit()?;
+
+    if options.pre_commit_hook {
+        make_executable(&root.join(hook_path(root)?))?;
+    }
+    Ok(result)
+}
+
+/// Pre-commit hook path, relative to the project root when possible
+fn hook_path(root: &Path) -> Result<PathBuf, Error> {
+    let repo = Repository::discover(root).map_err(|_| {
+        Error::from_reason(format!(
+            "Cannot install a pre-commit hook: {} is not in a git repository",
+            root.display()
+        ))
+    })?;
+    let hook = repo.path().join("hooks").join("pre-commit");
+    let root = canonical(root);
+    Ok(hook
+        .strip_prefix(&root)
+        .map(Path::to_path_buf)
+        .unwrap_or(hook))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), Error> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).map_err(|e| {
+        Error::from_reason(format!(
+            "Failed to make {} executable: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), Error> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::extract_anchors;
+    use crate::drift::PolicySet;
+    use std::fs;
+
+    #[test]
+    fn test_init_scaffolds_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = init(dir.path(), &InitOptions::new()).unwrap();
+
+        assert_eq!(
+            result.created,
+            [
+                CONFIG_FILE,
+                ".sintesi/.gitignore",
+                MAP_FILE,
+                "docs/getting-started.md"
+            ]
+        );
+        assert!(SintesiMap::load(dir.path().join(MAP_FILE))
+            .unwrap()
+            .entries
+            .is_empty());
+        assert!(PolicySet::load(dir.path().join(CONFIG_FILE))
+            .unwrap()
+            .policies
+            .is_empty());
+        let doc = fs::read_to_string(dir.path().join("docs/getting-started.md")).unwrap();
+        assert_eq!(
+            extract_anchors("docs/getting-started.md", &doc).anchor_count,
+            0
+        );
+    }
+
+    #[test]
+    fn test_init_keeps_existing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(CONFIG_FILE), "{}").unwrap();
+        fs::write(dir.path().join(MAP_FILE), "{\"custom\": true}").unwrap();
+
+        let result = init(dir.path(), &InitOptions::new()).unwrap();
+        assert_eq!(result.skipped, [CONFIG_FILE, MAP_FILE]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join(CONFIG_FILE)).unwrap(),
+            "{}"
+        );
+
+        let result = init(dir.path(), &InitOptions::new().force(true)).unwrap();
+        assert_eq!(result.skipped, [MAP_FILE]);
+        assert_ne!(
+            fs::read_to_string(dir.path().join(CONFIG_FILE)).unwrap(),
+            "{}"
+        );
+    }
+
+    #[test]
+    fn test_init_installs_hook() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = InitOptions::new().pre_commit_hook(true);
+        assert!(init(dir.path(), &options).is_err());
+
+        Repository::init(dir.path()).unwrap();
+        let result = init(dir.path(), &options).unwrap();
+        assert_eq!(result.created.last().unwrap(), ".git/hooks/pre-commit");
+        let hook = fs::read_to_string(dir.path().join(".git/hooks/pre-commit")).unwrap();
+        assert!(hook.contains("sintesi check"));
+    }
+}
@@ -7,18 +7,25 @@
 //!
 //! ## Architecture
 //!
-//! This crate is organized into four main modules following the Doctype architecture:
+//! This crate is organized into the following main modules following the Doctype architecture:
 //!
 //! ### 1. Types (`types`)
 //! Core type definitions used across all modules. Includes:
 //! - `CodeSignature`
 //! - `SymbolType` enum
+//! - `ContentProvider` (`provider`): file contents from the working tree, a git
+//!   revision, or an in-memory overlay
+//! - Windows extended-length and UNC path handling (`paths`)
+//! - Reading files as text: BOMs, UTF-16, and skipped binary files (`text`)
+//! - Read-only mode refusing every write to the project (`readonly`)
+//! - A write sandbox confining writes to the workspace (`sandbox`)
 //!
 //! ### 2. AST (`ast`)
 //! Static analysis and signature extraction:
 //! - AST analysis for TypeScript/JavaScript
 //! - Signature extraction and normalization
 //! - SHA256 hashing for signatures
+//! - Incremental analysis cache keyed by file content hash
 //!
 //! ### 3. Content & Mapping (`content`)
 //! Markdown processing and file discovery:
@@ -26,15 +33,43 @@
 //! - Markdown anchor extraction using pulldown-cmark
 //! - Content injection into documentation
 //!
-//! ### 4. Gen AI Agent (`genai`)
+//! ### 4. Drift & Freshness (`drift`)
+//! Tracking documentation against code over time:
+//! - Sintesi map (code-to-anchor links with signature hashes)
+//! - Drift detection and drift history
+//! - Freshness SLA policies
+//!
+//! ### 5. Reporting (`report`)
+//! Delivering drift results to CI:
+//! - GitHub/GitLab PR comments and commit statuses
+//! - Coverage and drift badges (SVG and shields.io JSON)
+//! - Self-contained HTML reports
+//! - JUnit XML for CI test dashboards
+//!
+//! ### 6. Plan & Apply (`apply`)
+//! Reviewable documentation updates:
+//! - Ordered, serializable plans (anchors, file rewrites, map updates, commit)
+//! - Applying approved plans
+//!
+//! ### 7. Pipeline (`pipeline`)
+//! The whole flow in one call:
+//! - Discover, analyze, detect drift, generate, and plan
+//! - Dry runs with mocked generation and no writes
+//! - An orchestrator with hooks before and after each phase
+//!
+//! ### 8. Gen AI Agent (`genai`)
 //! LLM interaction for content generation (Probabilistic Logic):
 //! - Prompt engineering
 //! - API integration (OpenAI, Gemini, etc.)
-//! - Documentation generation and updates
+//! - Documentation generation and updates, with citations and token usage
 //!
-//! ### 5. NAPI Bindings (`napi`)
+//! ### 9. NAPI Bindings (`napi`)
 //! Node.js bindings layer that exposes Rust functionality to JavaScript/TypeScript.
 //! This layer is separate from the core logic to maintain clean architecture.
+//! Result objects have JSON Schemas (`schema`) shipped under `schemas/`.
+//! Both are built with the default `napi` feature; without it
+//! (`default-features = false`) the crate is a plain Rust library with no
+//! Node build requirements.
 
 // ============================================================================
 // Core Modules (Pure Rust Logic)
@@ -43,6 +78,11 @@
 /// Core type definitions
 pub mod types;
 pub mod error;
+pub mod provider;
+pub mod paths;
+pub mod text;
+pub mod readonly;
+pub mod sandbox;
 
 /// AST analysis and drift detection
 pub mod ast;
@@ -50,24 +90,58 @@ pub mod ast;
 /// Content management and markdown processing
 pub mod content;
 
+/// Drift tracking, history, and freshness policies
+pub mod drift;
+
+/// Reports and CI integrations
+pub mod report;
+
+/// Reviewable plans for documentation updates
+pub mod apply;
+
 /// Filesystem crawler and project context
 pub mod crawler;
 pub mod graph;
 pub mod context;
 pub mod git; // [NEW] Git module
 
+/// Project scaffolding (`sintesi init`)
+pub mod init;
+
+/// Migration from the legacy doctype anchor format
+pub mod migrate;
+
+/// Text search across project files
+pub mod search;
+
+/// Incrementally updated project state for watch/daemon mode
+pub mod daemon;
+
+/// End-to-end documentation pipeline (discover, analyze, drift, generate, plan)
+pub mod pipeline;
+
 /// Gen AI agent for documentation generation
 pub mod genai;
 
+/// Test projects, run assertions, and snapshots for downstream tests
+#[cfg(any(test, feature = "test-support"))]
+pub mod testing;
+
 /// NAPI bindings for Node.js (separate layer)
+#[cfg(feature = "napi")]
+#[cfg_attr(test, allow(dead_code))]
 mod napi;
 
+/// JSON Schemas for the result types returned by the bindings
+#[cfg(feature = "napi")]
+pub mod schema;
+
 // ============================================================================
 // Re-exports for convenient access
 // ============================================================================
 
 // Types
-pub use types::{CodeSignature, SymbolType};
+pub use types::{CodeSignature, SymbolType, TypeParam};
 
 // AST & Drift Detection
 pub use ast::{AstAnalyzerInternal, SignatureHasher};
@@ -79,7 +153,7 @@ pub use content::{
 };
 
 // Gen AI
-pub use genai::GenAiAgent;
+pub use genai::{GenAiAgent, Generation};
 
 // ============================================================================
 // NAPI Exports (for Node.js)
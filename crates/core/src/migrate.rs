@@ -0,0 +1,292 @@
+//! Migration from the legacy doctype format
+//!
+//! Projects set up before the rename use `doctype:start`/`doctype:end`
+//! anchors and a `doctype-map.json` map whose hashes came from an older
+//! hasher. `migrate_doctype_to_sintesi` rewrites the anchors, converts the
+//! map to `sintesi-map.json` with hashes from the current hasher, and reports
+//! everything it could not carry over. The legacy map is left in place.
+
+use crate::apply::Transaction;
+use crate::content::legacy::DOCTYPE_PREFIX;
+use crate::content::{discover_files, extract_anchors, rewrite_doctype_anchors, DiscoveryConfig};
+use crate::drift::{current_signatures, now_millis, SintesiMap, SintesiMapEntry, MAP_FILE};
+use crate::error::Error;
+use crate::provider::{ContentProvider, DiskProvider, MemoryProvider};
+use crate::sandbox::WriteSandbox;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Legacy map file name, relative to the project root
+pub const LEGACY_MAP_FILE: &str = "doctype-map.json";
+
+/// Something that could not be migrated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationIssue {
+    /// Anchor ID, map entry ID, or file the issue concerns
+    pub subject: String,
+    /// Why it was not migrated
+    pub reason: String,
+}
+
+/// Outcome of a migration
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    /// Markdown files whose anchors were rewritten, relative to the root
+    pub docs_rewritten: Vec<String>,
+    /// Number of anchor markers rewritten
+    pub anchors_rewritten: usize,
+    /// Map entries written to `sintesi-map.json`
+    pub entries_migrated: usize,
+    /// Anchors, entries, and files that were not migrated
+    pub issues: Vec<MigrationIssue>,
+}
+
+/// Legacy map entry; every field is optional so bad entries can be reported
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyEntry {
+    id: Option<String>,
+    #[serde(alias = "code_ref")]
+    code_ref: Option<String>,
+    #[serde(alias = "doc_file")]
+    doc_file: Option<String>,
+    #[serde(alias = "last_updated")]
+    last_updated: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct LegacyMap {
+    #[serde(default)]
+    entries: Vec<serde_json::Value>,
+}
+
+/// Migrate a project from the doctype dialect to sintesi
+///
+/// Legacy anchors in markdown files are rewritten in place. Each legacy map
+/// entry whose anchor and symbol still exist is carried over with its
+/// `lastUpdated` and a freshly computed signature hash; rewritten anchors
+/// without a legacy entry are added as new entries. Entries already in
+/// `sintesi-map.json` are kept unless a migrated entry has the same ID.
+///
+/// # Errors
+/// Fails if the legacy map is unreadable JSON or the files cannot be written;
+/// nothing is written in that case.
+pub fn migrate_doctype_to_sintesi(root: impl AsRef<Path>) -> Result<MigrationReport, Error> {
+    let root = root.as_ref();
+    let mut report = MigrationReport::default();
+    let mut transaction = Transaction::new().confine(WriteSandbox::load(root)?);
+
+    // Rewrite anchors, keeping the new content in an overlay so the map is
+    // built against the migrated docs before anything is written
+    let mut provider = MemoryProvider::overlay(DiskProvider::new(root));
+    let mut migrated_docs: Vec<(String, String)> = Vec::new();
+    let discovery = discover_files(root, DiscoveryConfig::new());
+    for path in discovery.markdown_files {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if !content.contains(DOCTYPE_PREFIX) {
+            continue;
+        }
+        let (rewritten, count) = rewrite_doctype_anchors(&content);
+        if count == 0 {
+            continue;
+        }
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        let name = relative.to_string_lossy().into_owned();
+
+        let extraction = extract_anchors(&relative, &rewritten);
+        for error in extraction.errors {
+            report.issues.push(MigrationIssue {
+                subject: name.clone(),
+                reason: error,
+            });
+        }
+        provider.set(relative, rewritten.clone());
+        transaction.stage(path.clone(), rewritten.clone());
+        report.docs_rewritten.push(name.clone());
+        report.anchors_rewritten += count;
+        migrated_docs.push((name, rewritten));
+    }
+
+    let legacy_entries = load_legacy_entries(root)?;
+    let mut map = SintesiMap::load(root.join(MAP_FILE)).unwrap_or_default();
+    let migrated = migrate_entries(&provider, &migrated_docs, legacy_entries, &mut report);
+    report.entries_migrated = migrated.len();
+    for entry in migrated {
+        map.upsert(entry);
+    }
+
+    if report.entries_migrated > 0 || root.join(LEGACY_MAP_FILE).exists() {
+        transaction.stage(root.join(MAP_FILE), map.to_json()?);
+    }
+    transaction.commit()?;
+    Ok(report)
+}
+
+/// Read the legacy map's entries (none if there is no legacy map)
+fn load_legacy_entries(root: &Path) -> Result<Vec<serde_json::Value>, Error> {
+    let path = root.join(LEGACY_MAP_FILE);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(Vec::new());
+    };
+    let legacy: LegacyMap = serde_json::from_str(&content)
+        .map_err(|e| Error::from_reason(format!("Invalid map {}: {}", path.display(), e)))?;
+    Ok(legacy.entries)
+}
+
+/// Build map entries from legacy entries and the migrated anchors
+fn migrate_entries(
+    provider: &dyn ContentProvider,
+    migrated_docs: &[(String, String)],
+    legacy_entries: Vec<serde_json::Value>,
+    report: &mut MigrationReport,
+) -> Vec<SintesiMapEntry> {
+    let now = now_millis();
+
+    // Anchors found in the migrated docs: (doc file, id, code_ref)
+    let mut anchors: Vec<(String, String, String)> = Vec::new();
+    for (doc_file, content) in migrated_docs {
+        let extraction = extract_anchors(doc_file, content);
+        let mut found: Vec<_> = extraction.anchors.into_values().collect();
+        found.sort_by_key(|a| a.start_line);
+        for anchor in found {
+            if let Some(code_ref) = anchor.code_ref {
+                anchors.push((doc_file.clone(), anchor.id, code_ref));
+            }
+        }
+    }
+
+    let mut entries: Vec<SintesiMapEntry> = Vec::new();
+    for (index, value) in legacy_entries.into_iter().enumerate() {
+        let legacy: Option<LegacyEntry> = serde_json::from_value(value).ok();
+        let Some((id, code_ref, doc_file, last_updated)) = legacy.and_then(|e| {
+            Some((
+                e.id?,
+                e.code_ref?,
+                e.doc_file?,
+                e.last_updated.unwrap_or(now),
+            ))
+        }) else {
+            report.issues.push(MigrationIssue {
+                subject: format!("{} entry {}", LEGACY_MAP_FILE, index),
+                reason: "missing id, codeRef, or docFile".to_string(),
+            });
+            continue;
+        };
+        let anchored = provider.read(Path::new(&doc_file)).is_ok_and(|content| {
+            extract_anchors(&doc_file, &content)
+                .anchors
+                .contains_key(&id)
+        });
+        if !anchored {
+            report.issues.push(MigrationIssue {
+                subject: id,
+                reason: format!("anchor not found in {}", doc_file),
+            });
+            continue;
+        }
+        entries.push(SintesiMapEntry {
+            id,
+            code_ref,
+            doc_file,
+            code_signature_hash: String::new(),
+            last_updated,
+            symbol_id: None,
+            symbol_key: None,
+            deprecated: false,
+            content_hash: None,
+            code_signature: None,
+        });
+    }
+
+    for (doc_file, id, code_ref) in anchors {
+        if !entries.iter().any(|e| e.id == id) {
+            entries.push(SintesiMapEntry {
+                id,
+                code_ref,
+                doc_file,
+                code_signature_hash: String::new(),
+                last_updated: now,
+                symbol_id: None,
+                symbol_key: None,
+                deprecated: false,
+                content_hash: None,
+                code_signature: None,
+            });
+        }
+    }
+
+    let lookup = SintesiMap {
+        entries: entries.clone(),
+        ..SintesiMap::new()
+    };
+    let current = current_signatures(provider, &lookup);
+    entries.retain_mut(|entry| match current.hashes.get(&entry.code_ref) {
+        Some(hash) => {
+            entry.code_signature_hash = hash.clone();
+            entry.symbol_id = current.symbol_ids.get(&entry.code_ref).cloned();
+            entry.symbol_key = current.symbol_keys.get(&entry.code_ref).cloned();
+            entry.deprecated = current.deprecated.contains(&entry.code_ref);
+            entry.code_signature = current.signatures.get(&entry.code_ref).cloned();
+            true
+        }
+        None => {
+            report.issues.push(MigrationIssue {
+                subject: entry.id.clone(),
+                reason: format!("symbol {} not found", entry.code_ref),
+            });
+            false
+        }
+    });
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::check_drift;
+
+    #[test]
+    fn test_migrates_anchors_and_map() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("src/auth.ts"),
+            "export function login(user: string) {}\nexport function logout() {}",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/auth.md"),
+            "<!-- doctype:start id=\"login\" code_ref=\"src/auth.ts#login\" -->\nLogs in.\n<!-- doctype:end id=\"login\" -->\n\n<!-- doctype:start id=\"logout\" code_ref=\"src/auth.ts#logout\" -->\nLogs out.\n<!-- doctype:end id=\"logout\" -->\n\n<!-- doctype:start id=\"reset\" code_ref=\"src/auth.ts#reset\" -->\nResets.\n<!-- doctype:end id=\"reset\" -->\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join(LEGACY_MAP_FILE),
+            r#"{"version": "1.0", "entries": [
+                {"id": "login", "codeRef": "src/auth.ts#login", "docFile": "docs/auth.md", "codeSignatureHash": "old", "lastUpdated": 42},
+                {"id": "gone", "codeRef": "src/auth.ts#gone", "docFile": "docs/auth.md", "codeSignatureHash": "old"},
+                {"codeRef": "src/auth.ts#login"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let report = migrate_doctype_to_sintesi(root).unwrap();
+
+        assert_eq!(report.docs_rewritten, ["docs/auth.md"]);
+        assert_eq!(report.anchors_rewritten, 6);
+        assert_eq!(report.entries_migrated, 2);
+        let subjects: Vec<&str> = report.issues.iter().map(|i| i.subject.as_str()).collect();
+        assert_eq!(subjects, ["gone", "doctype-map.json entry 2", "reset"]);
+
+        let map = SintesiMap::load(root.join(MAP_FILE)).unwrap();
+        assert_eq!(map.get("login").unwrap().last_updated, 42);
+        assert!(map.get("logout").is_some());
+        assert!(!check_drift(&DiskProvider::new(root), &map).has_drift());
+        let doc = fs::read_to_string(root.join("docs/auth.md")).unwrap();
+        assert!(!doc.contains("doctype:"));
+    }
+}
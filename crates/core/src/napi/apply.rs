@@ -0,0 +1,132 @@
+//! Plan & apply NAPI bindings
+//!
+//! Node.js bindings for building documentation update plans and applying
+//! them once approved. Plans cross the boundary as JSON strings. Anchor
+//! split and merge refactorings are applied directly.
+
+use super::convert::mirror;
+use crate::apply::{self, ApplyResult, Plan, PlanOptions, RefactorResult};
+use crate::drift::{check_drift, SintesiMap, MAP_FILE};
+use crate::provider::DiskProvider;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Outcome of applying a plan (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyResultJs {
+    /// Files written (relative to the project root), including the map
+    pub files_written: Vec<String>,
+    /// Commit created by the plan, if it had a commit step
+    pub commit: Option<String>,
+}
+
+mirror! {
+    ApplyResult { files_written, commit } => ApplyResultJs { files_written, commit }
+}
+
+/// Outcome of splitting or merging anchors (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RefactorResultJs {
+    /// IDs of the resulting anchors, in document order
+    pub anchor_ids: Vec<String>,
+    /// Files written (relative to the project root), including the map
+    pub files_written: Vec<String>,
+}
+
+mirror! {
+    RefactorResult { anchor_ids, files_written } => RefactorResultJs { anchor_ids, files_written }
+}
+
+/// Build a plan that brings drifted documentation back in sync
+///
+/// @param rootPath - Project root containing the map
+/// @param generated - Regenerated anchor content by anchor ID
+/// @param commitMessage - Commit the changes with this message
+/// @returns The plan as JSON, to present for approval and pass to `applyPlan`
+#[napi]
+pub fn create_plan(
+    root_path: String,
+    generated: BTreeMap<String, String>,
+    commit_message: Option<String>,
+) -> Result<String> {
+    let root = Path::new(&root_path);
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+    let drift = check_drift(&DiskProvider::new(root), &map);
+
+    let mut options = PlanOptions::new();
+    if let Some(message) = commit_message {
+        options = options.commit_message(message);
+    }
+    let plan = apply::create_plan(root, &map, &drift, &generated, &options)
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(plan.to_json())
+}
+
+/// Describe each step of a plan in one line, for approval prompts
+///
+/// @param plan - Plan JSON from `createPlan`
+#[napi]
+pub fn describe_plan(plan: String) -> Result<Vec<String>> {
+    let plan = Plan::from_json(&plan).map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(plan.steps.iter().map(|step| step.to_string()).collect())
+}
+
+/// Apply an approved plan
+///
+/// Nothing is written if any file changed since the plan was created.
+///
+/// @param rootPath - Project root the plan was created for
+/// @param plan - Plan JSON from `createPlan`
+#[napi]
+pub fn apply_plan(root_path: String, plan: String) -> Result<ApplyResultJs> {
+    let plan = Plan::from_json(&plan).map_err(|e| Error::from_reason(e.to_string()))?;
+    let result =
+        apply::apply_plan(&root_path, &plan).map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(result.into())
+}
+
+/// Split an anchor documenting several symbols into one anchor per symbol
+///
+/// The content is divided by the symbols each section mentions; the
+/// markdown and the map are updated together.
+///
+/// @param rootPath - Project root containing the map
+/// @param docFile - Markdown file containing the anchor (relative to the root)
+/// @param anchorId - Anchor to split
+/// @param codeRefs - One code reference per new anchor (at least two)
+#[napi]
+pub fn split_anchor(
+    root_path: String,
+    doc_file: String,
+    anchor_id: String,
+    code_refs: Vec<String>,
+) -> Result<RefactorResultJs> {
+    apply::split_anchor(&root_path, &doc_file, &anchor_id, &code_refs)
+        .map(RefactorResultJs::from)
+        .map_err(|e| Error::from_reason(e.to_string()))
+}
+
+/// Merge adjacent anchors into the first one
+///
+/// @param rootPath - Project root containing the map
+/// @param docFile - Markdown file containing the anchors (relative to the root)
+/// @param anchorIds - Anchors to merge, in document order (at least two)
+#[napi]
+pub fn merge_anchors(
+    root_path: String,
+    doc_file: String,
+    anchor_ids: Vec<String>,
+) -> Result<RefactorResultJs> {
+    apply::merge_anchors(&root_path, &doc_file, &anchor_ids)
+        .map(RefactorResultJs::from)
+        .map_err(|e| Error::from_reason(e.to_string()))
+}
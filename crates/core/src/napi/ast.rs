@@ -2,10 +2,52 @@
 //!
 //! Node.js bindings for AST analysis functionality using Oxc parser.
 
-use crate::ast::{AstAnalyzerInternal, SignatureHasher as SignatureHasherInternal};
-use crate::types::CodeSignature;
+use crate::ast::diff::{diff_contents, ParamChange, SignatureChange};
+use crate::ast::reexports::{module_exports, ExportedSymbol};
+use crate::ast::test_mapping::{map_tests_to_symbols, TestCase};
+use crate::ast::{
+    AnalyzerOptions, AstAnalyzerInternal, Diagnostic, DuplicateNames,
+    SignatureHasher as SignatureHasherInternal, SymbolInfo,
+};
+use crate::napi::convert::{mirror, IntoJs};
+use crate::napi::search::SkippedFileJs;
+use crate::provider::DiskProvider;
+use crate::text::read_text;
+use crate::types::{CodeSignature, SymbolType};
 use napi_derive::napi;
-use std::fs;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::Path;
+
+/// Options for `AstAnalyzer`
+#[napi(object)]
+pub struct AnalyzerOptionsJs {
+    /// Naming of symbols sharing a qualified name within a file: `"keep"`
+    /// (default) or `"suffix"` (`helper`, `helper~2`, as used for drift keys)
+    pub duplicate_names: Option<String>,
+    /// Also extract declarations inside function and method bodies
+    /// (callbacks, local helpers); only module-level declarations, class
+    /// members, and namespace members are extracted by default
+    pub deep: Option<bool>,
+    /// Include decorators, with their arguments, in class and property
+    /// signatures (method signatures always include theirs)
+    pub decorators: Option<bool>,
+    /// Report React function components as `Component` symbols whose
+    /// signature lists their props
+    pub react_components: Option<bool>,
+    /// List the members classes inherit from base classes declared in the
+    /// same file in their signatures
+    pub inherited_members: Option<bool>,
+    /// Size in bytes above which files are skipped instead of parsed
+    /// (1 MiB by default)
+    pub max_file_size: Option<u32>,
+    /// Also parse files detected as generated (`@generated` markers, source
+    /// map comments) or minified, which are skipped by default
+    pub include_generated: Option<bool>,
+    /// Also return non-exported symbols from `analyzeFile` and
+    /// `analyzeCode` (e.g. internal helpers anchored in architecture docs)
+    pub include_private: Option<bool>,
+}
 
 /// AST Analyzer for TypeScript/JavaScript code
 #[napi]
@@ -16,24 +58,65 @@ pub struct AstAnalyzer {
 #[napi]
 impl AstAnalyzer {
     /// Create a new AST analyzer instance
+    ///
+    /// Nested declarations are named after their enclosing namespaces,
+    /// classes, methods, and functions (`Outer.helper`, `MyClass.method`).
+    ///
+    /// @param options - Naming of duplicate symbols, extraction depth, decorators,
+    ///   React components, inherited members, file size limit, and
+    ///   non-exported symbols
     #[napi(constructor)]
-    pub fn new() -> Self {
-        Self {
-            internal: AstAnalyzerInternal::new(),
+    pub fn new(options: Option<AnalyzerOptionsJs>) -> napi::Result<Self> {
+        let options = options.unwrap_or(AnalyzerOptionsJs {
+            duplicate_names: None,
+            deep: None,
+            decorators: None,
+            react_components: None,
+            inherited_members: None,
+            max_file_size: None,
+            include_generated: None,
+            include_private: None,
+        });
+        let mut analyzer_options = AnalyzerOptions::new()
+            .deep(options.deep.unwrap_or(false))
+            .decorators(options.decorators.unwrap_or(false))
+            .react_components(options.react_components.unwrap_or(false))
+            .inherited_members(options.inherited_members.unwrap_or(false))
+            .include_generated(options.include_generated.unwrap_or(false))
+            .include_private(options.include_private.unwrap_or(false));
+        if let Some(bytes) = options.max_file_size {
+            analyzer_options = analyzer_options.max_file_size(bytes as usize);
         }
+        if let Some(strategy) = options.duplicate_names {
+            let strategy = match strategy.as_str() {
+                "keep" => DuplicateNames::Keep,
+                "suffix" => DuplicateNames::Suffix,
+                other => {
+                    return Err(napi::Error::from_reason(format!(
+                        "Unknown duplicateNames strategy: {} (expected \"keep\" or \"suffix\")",
+                        other
+                    )))
+                }
+            };
+            analyzer_options = analyzer_options.duplicate_names(strategy);
+        }
+        Ok(Self {
+            internal: AstAnalyzerInternal::with_options(analyzer_options),
+        })
     }
 
     /// Analyze a TypeScript/JavaScript file and return code signatures
     ///
     /// This method reads the file, parses it using Oxc, and extracts all
-    /// exported symbols with their signatures. Hashes are computed automatically.
+    /// exported symbols (all symbols with `includePrivate`) with their
+    /// signatures. Hashes are computed automatically.
     ///
     /// @param filePath - Absolute path to the TypeScript/JavaScript file
     /// @returns Array of code signatures found in the file (with hashes)
     #[napi]
     pub fn analyze_file(&self, file_path: String) -> napi::Result<Vec<CodeSignature>> {
         // Read file contents
-        let content = fs::read_to_string(&file_path)
+        let content = read_text(Path::new(&file_path))
             .map_err(|e| napi::Error::from_reason(format!("Failed to read file: {}", e)))?;
 
         // Analyze the file
@@ -46,24 +129,8 @@ impl AstAnalyzer {
         let signatures = result
             .symbols
             .into_iter()
-            .filter(|s| s.is_exported) // Only return exported symbols
-            .map(|s| {
-                let sig = CodeSignature {
-                    symbol_name: s.name.clone(),
-                    symbol_type: s.symbol_type,
-                    signature_text: s.signature.clone(),
-                    is_exported: s.is_exported,
-                    hash: None, // Temporary, will be set below
-                };
-
-                // Compute hash
-                let hash_result = hasher.hash(sig.clone());
-
-                CodeSignature {
-                    hash: Some(hash_result.hash),
-                    ..sig
-                }
-            })
+            .filter(|s| self.internal.is_listed(s)) // Exported symbols, unless includePrivate
+            .map(|s| self.internal.code_signature(&s, &hasher))
             .collect();
 
         Ok(signatures)
@@ -72,7 +139,8 @@ impl AstAnalyzer {
     /// Analyze TypeScript/JavaScript source code directly (without file)
     ///
     /// This method parses the provided code string using Oxc and extracts all
-    /// exported symbols with their signatures. Hashes are computed automatically.
+    /// exported symbols (all symbols with `includePrivate`) with their
+    /// signatures. Hashes are computed automatically.
     ///
     /// @param code - TypeScript/JavaScript source code
     /// @returns Array of code signatures found in the code (with hashes)
@@ -87,24 +155,8 @@ impl AstAnalyzer {
         let signatures = result
             .symbols
             .into_iter()
-            .filter(|s| s.is_exported) // Only return exported symbols
-            .map(|s| {
-                let sig = CodeSignature {
-                    symbol_name: s.name.clone(),
-                    symbol_type: s.symbol_type,
-                    signature_text: s.signature.clone(),
-                    is_exported: s.is_exported,
-                    hash: None,
-                };
-
-                // Compute hash
-                let hash_result = hasher.hash(sig.clone());
-
-                CodeSignature {
-                    hash: Some(hash_result.hash),
-                    ..sig
-                }
-            })
+            .filter(|s| self.internal.is_listed(s)) // Exported symbols, unless includePrivate
+            .map(|s| self.internal.code_signature(&s, &hasher))
             .collect();
 
         Ok(signatures)
@@ -124,37 +176,297 @@ impl AstAnalyzer {
         let signatures = result
             .symbols
             .into_iter()
-            .map(|s| {
-                let sig = CodeSignature {
-                    symbol_name: s.name.clone(),
-                    symbol_type: s.symbol_type,
-                    signature_text: s.signature.clone(),
-                    is_exported: s.is_exported,
-                    hash: None,
-                };
-
-                // Compute hash
-                let hash_result = hasher.hash(sig.clone());
-
-                CodeSignature {
-                    hash: Some(hash_result.hash),
-                    ..sig
-                }
-            })
+            .map(|s| self.internal.code_signature(&s, &hasher))
             .collect();
 
         Ok(AnalysisResultJs {
             signatures,
             errors: result.errors,
+            diagnostics: result.diagnostics.into_js(),
+            skipped: result.skipped.into_js(),
         })
     }
+
+    /// Get the current normalized signature and hash of a single symbol
+    ///
+    /// @param filePath - Path to the TypeScript/JavaScript file
+    /// @param symbolName - Name of the symbol
+    /// @returns The symbol's signature (with hash), or null if it is not found
+    #[napi]
+    pub fn get_signature_text(
+        &self,
+        file_path: String,
+        symbol_name: String,
+    ) -> napi::Result<Option<CodeSignature>> {
+        let content = read_text(Path::new(&file_path))
+            .map_err(|e| napi::Error::from_reason(format!("Failed to read file: {}", e)))?;
+
+        Ok(self
+            .internal
+            .signature_of(&file_path, &content, &symbol_name))
+    }
+
+    /// Get structured parse diagnostics for a file
+    ///
+    /// @param filePath - Path to the source file (TypeScript/JavaScript, Go,
+    /// Java, Python, or a JSON/YAML document)
+    /// @returns Diagnostics with 0-based line/column spans and the offending source line
+    #[napi]
+    pub fn get_diagnostics(&self, file_path: String) -> napi::Result<Vec<DiagnosticJs>> {
+        let content = read_text(Path::new(&file_path))
+            .map_err(|e| napi::Error::from_reason(format!("Failed to read file: {}", e)))?;
+
+        let result = self.internal.analyze_file(&file_path, &content);
+        Ok(result.diagnostics.into_js())
+    }
+
+    /// Map test cases to the source symbols they exercise
+    ///
+    /// Source files are analyzed with the same paths they are given in, so
+    /// relative imports in the test files resolve against them.
+    ///
+    /// @param testFiles - Paths of test files (TS/JS or Rust)
+    /// @param sourceFiles - Paths of source files whose symbols may be covered
+    /// @returns One entry per discovered test case
+    #[napi]
+    pub fn map_tests(
+        &self,
+        test_files: Vec<String>,
+        source_files: Vec<String>,
+    ) -> napi::Result<Vec<TestCaseJs>> {
+        let read = |path: &String| {
+            read_text(Path::new(path)).map_err(|e| {
+                napi::Error::from_reason(format!("Failed to read file {}: {}", path, e))
+            })
+        };
+
+        let mut symbols = Vec::new();
+        for path in &source_files {
+            symbols.extend(self.internal.analyze_file(path, &read(path)?).symbols);
+        }
+        let tests = test_files
+            .iter()
+            .map(|path| Ok((path.clone(), read(path)?)))
+            .collect::<napi::Result<Vec<_>>>()?;
+
+        let map = map_tests_to_symbols(&tests, &symbols);
+        Ok(map.tests.into_js())
+    }
+
+    /// Compare the exported symbols of two versions of a file
+    ///
+    /// @param filePath - Path of the file (its extension selects the language)
+    /// @param oldCode - Source before the change
+    /// @param newCode - Source after the change
+    /// @returns Added and removed symbols (with hashes), changed signatures
+    ///   with the parameters and return type that changed, and whether each
+    ///   change (and the diff as a whole) is breaking
+    #[napi]
+    pub fn diff_code(&self, file_path: String, old_code: String, new_code: String) -> ApiDiffJs {
+        let diff = diff_contents(&self.internal, &file_path, &old_code, &new_code);
+        let severity = diff.severity();
+        let hasher = SignatureHasherInternal::new();
+        let hashed = |symbol: &SymbolInfo| self.internal.code_signature(symbol, &hasher);
+
+        ApiDiffJs {
+            added: diff.added.iter().map(hashed).collect(),
+            removed: diff.removed.iter().map(hashed).collect(),
+            changed: diff.changed.into_js(),
+            severity: severity.into_js(),
+        }
+    }
+}
+
+/// List the names a module exports and the files declaring them
+///
+/// Follows `export ... from` and `export *` through barrel files, so every
+/// exported name is attributed to the module that declares it.
+///
+/// @param rootPath - Project root the file path is relative to
+/// @param filePath - Module to list, relative to the root
+/// @returns Exported names, sorted, with their declarations
+#[napi]
+pub fn resolve_exports(root_path: String, file_path: String) -> Vec<ExportedSymbolJs> {
+    module_exports(&DiskProvider::new(root_path), &file_path).into_js()
 }
 
 /// Analysis result including errors (for NAPI)
 #[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct AnalysisResultJs {
     /// All code signatures found
     pub signatures: Vec<CodeSignature>,
     /// Errors encountered during parsing
     pub errors: Vec<String>,
+    /// Structured parse diagnostics
+    pub diagnostics: Vec<DiagnosticJs>,
+    /// The code, if it was not parsed (over `maxFileSize`, or generated)
+    pub skipped: Vec<SkippedFileJs>,
+}
+
+/// A parse diagnostic with its location (for NAPI)
+///
+/// Lines and columns are 0-based; the end position is exclusive.
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticJs {
+    pub file_path: String,
+    pub message: String,
+    pub help: Option<String>,
+    /// "error", "warning" or "advice"
+    pub severity: String,
+    pub line: u32,
+    pub column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+    /// The source line containing the start position
+    pub excerpt: String,
+}
+
+mirror! {
+    Diagnostic {
+        file_path,
+        message,
+        help,
+        severity,
+        start,
+        end,
+        excerpt,
+    } => DiagnosticJs {
+        file_path,
+        message,
+        help,
+        severity,
+        line: start.line as u32,
+        column: start.column as u32,
+        end_line: end.line as u32,
+        end_column: end.column as u32,
+        excerpt,
+    }
+}
+
+/// A test case and the symbols it exercises (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCaseJs {
+    /// Full test title or Rust test function name
+    pub name: String,
+    /// File the test is declared in
+    pub file_path: String,
+    /// Line where the test is declared (0-indexed)
+    pub line: u32,
+    /// Code references (`file#symbol`) of the covered symbols
+    pub covers: Vec<String>,
+}
+
+mirror! {
+    TestCase { name, file_path, line, covers } => TestCaseJs { name, file_path, line, covers }
+}
+
+/// A name exported by a module and its declaration (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedSymbolJs {
+    /// Name the module exports
+    pub name: String,
+    /// File declaring the symbol
+    pub file_path: String,
+    /// Name of the symbol in the declaring file
+    pub symbol_name: String,
+    /// Code reference (`file#symbol`) of the declaration
+    pub code_ref: String,
+}
+
+mirror! {
+    ExportedSymbol { name, declaration } => ExportedSymbolJs {
+        code_ref: declaration.code_ref(),
+        name,
+        file_path: declaration.file_path,
+        symbol_name: declaration.name,
+    }
+}
+
+/// Differences between two versions of a file's exported symbols (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDiffJs {
+    /// Symbols only in the new version
+    pub added: Vec<CodeSignature>,
+    /// Symbols only in the old version
+    pub removed: Vec<CodeSignature>,
+    /// Symbols in both whose signature or kind differs
+    pub changed: Vec<SignatureChangeJs>,
+    /// "breaking" if a symbol was removed or any change is breaking,
+    /// "nonBreaking" otherwise (`None` if the API is unchanged)
+    pub severity: Option<String>,
+}
+
+/// A symbol whose signature changed (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureChangeJs {
+    pub symbol_name: String,
+    /// Kind of the symbol in the new version
+    pub symbol_type: SymbolType,
+    pub old_signature: String,
+    pub new_signature: String,
+    /// Parameters only in the new signature, as written (`remember?: boolean`)
+    pub added_params: Vec<String>,
+    /// Parameters only in the old signature, as written
+    pub removed_params: Vec<String>,
+    /// Parameters in both whose declaration differs
+    pub changed_params: Vec<ParamChangeJs>,
+    /// Whether the return type changed (added, removed, or retyped)
+    pub return_type_changed: bool,
+    pub old_return_type: Option<String>,
+    pub new_return_type: Option<String>,
+    /// "breaking" or "nonBreaking"
+    pub severity: String,
+}
+
+// Named after the new version of the symbol
+mirror! {
+    SignatureChange {
+        old,
+        new,
+        added_params,
+        removed_params,
+        changed_params,
+        return_type,
+        severity,
+    } => SignatureChangeJs {
+        symbol_name: new.name,
+        symbol_type: new.symbol_type,
+        old_signature: old.signature,
+        new_signature: new.signature,
+        added_params,
+        removed_params,
+        changed_params,
+        return_type_changed: return_type.is_some(),
+        old_return_type: return_type.as_ref().and_then(|r| r.old.clone()),
+        new_return_type: return_type.and_then(|r| r.new),
+        severity,
+    }
+}
+
+/// A parameter declared differently in the new signature (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ParamChangeJs {
+    pub name: String,
+    /// Old declaration (`user: string`)
+    pub old: String,
+    /// New declaration (`user: User`)
+    pub new: String,
+}
+
+mirror! {
+    ParamChange { name, old, new } => ParamChangeJs { name, old, new }
 }
@@ -3,14 +3,31 @@
 //! Node.js bindings for file discovery and markdown extraction.
 
 use napi_derive::napi;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
 
 use crate::content::discovery::{
     discover_files as discover_files_internal, DiscoveryConfig,
 };
-use crate::content::extractor::MarkdownExtractor as MarkdownExtractorInternal;
+use crate::content::extractor::{CodeRefValidation, MarkdownExtractor as MarkdownExtractorInternal};
+use crate::content::injector;
+use crate::content::scaffold::{scaffold, ScaffoldOptions};
+use crate::content::fixes::{self, BrokenKind, BrokenReference, Fix, ReferenceIndex};
+use crate::content::lint::{self, LintConfig, LintFinding, LintSeverity};
+use crate::content::spell::{Misspelling, SpellChecker};
+use crate::content::SintesiAnchor as SintesiAnchorInternal;
+use crate::ast::AstAnalyzerInternal;
+use crate::text::read_text;
+use crate::types::CodeSignature;
+use crate::napi::convert::{mirror, IntoJs};
+use crate::napi::search::SkippedFileJs;
 
 /// NAPI-compatible result structure for file discovery
 #[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct FileDiscoveryResult {
     /// List of markdown file paths found
     pub markdown_files: Vec<String>,
@@ -109,7 +126,8 @@ pub fn discover_files(
 
 /// NAPI-compatible Sintesi anchor structure
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct SintesiAnchor {
     /// Unique anchor ID
     pub id: String,
@@ -121,19 +139,97 @@ pub struct SintesiAnchor {
     pub start_line: u32,
     /// End line number (0-indexed)
     pub end_line: u32,
-    /// Content between anchor tags
+    /// Content between anchor tags, including nested anchors
     pub content: String,
+    /// ID of the anchor this one is nested in
+    pub parent: Option<String>,
+    /// IDs of anchors nested directly inside this one, in document order
+    pub children: Vec<String>,
+}
+
+mirror! {
+    SintesiAnchorInternal {
+        id,
+        code_ref,
+        file_path,
+        start_line,
+        end_line,
+        content,
+        parent,
+        children,
+        start_tag_span: _,
+        content_span: _,
+        end_tag_span: _,
+    } => SintesiAnchor {
+        id,
+        code_ref,
+        file_path: file_path.to_string_lossy().to_string(),
+        start_line,
+        end_line,
+        content,
+        parent,
+        children,
+    }
 }
 
 /// NAPI-compatible extraction result
 #[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ExtractionResult {
-    /// Map of anchor ID to anchor data (as a flat array for NAPI compatibility)
+    /// Anchors found, in document order
     pub anchors: Vec<SintesiAnchor>,
     /// Number of anchors found
     pub anchor_count: u32,
     /// Errors encountered during extraction
     pub errors: Vec<String>,
+    /// Set if the file was not parsed (over `maxFileSize`)
+    pub skipped: Option<SkippedFileJs>,
+}
+
+/// Options for anchor extraction
+#[napi(object)]
+pub struct ExtractOptions {
+    /// Project root code references are checked against
+    pub project_root: Option<String>,
+    /// Code reference check with a `projectRoot`: `"format"` (default),
+    /// `"files"` (the file must exist), or `"symbols"` (the file must also
+    /// declare or re-export the symbol)
+    pub validate_code_refs: Option<String>,
+    /// Size in bytes above which content is skipped instead of parsed
+    /// (1 MiB by default)
+    pub max_file_size: Option<u32>,
+}
+
+/// Build an extractor from NAPI options
+fn extractor(options: Option<ExtractOptions>) -> napi::Result<MarkdownExtractorInternal> {
+    let mut extractor = MarkdownExtractorInternal::new();
+    let Some(ExtractOptions {
+        project_root,
+        validate_code_refs,
+        max_file_size,
+    }) = options
+    else {
+        return Ok(extractor);
+    };
+    if let Some(bytes) = max_file_size {
+        extractor = extractor.max_file_size(bytes as usize);
+    }
+    let Some(root) = project_root else {
+        return Ok(extractor);
+    };
+    let level = match validate_code_refs.as_deref() {
+        None | Some("format") => CodeRefValidation::Format,
+        Some("files") => CodeRefValidation::Files,
+        Some("symbols") => CodeRefValidation::Symbols,
+        Some(other) => {
+            return Err(napi::Error::from_reason(format!(
+                "Unknown validateCodeRefs level: {} (expected \"format\", \"files\", or \"symbols\")",
+                other
+            )))
+        }
+    };
+    Ok(extractor.validate_code_refs(root, level))
 }
 
 /// Extract Sintesi anchors from markdown content
@@ -141,6 +237,7 @@ pub struct ExtractionResult {
 /// # Arguments
 /// * `file_path` - Path to the markdown file (for reference)
 /// * `content` - Markdown content to parse
+/// * `options` - Project root and code reference checks
 ///
 /// # Returns
 /// ExtractionResult with all found anchors and any errors
@@ -163,31 +260,75 @@ pub struct ExtractionResult {
 /// if (result.errors.length > 0) {
 ///   console.error('Errors:', result.errors);
 /// }
+///
+/// // Also report code_refs to missing files or symbols
+/// extractAnchors('docs/api.md', content, { projectRoot: '.', validateCodeRefs: 'symbols' });
 /// ```
 #[napi]
-pub fn extract_anchors(file_path: String, content: String) -> ExtractionResult {
-    let extractor = MarkdownExtractorInternal::new();
-    let result = extractor.extract_from_file(&file_path, &content);
+pub fn extract_anchors(
+    file_path: String,
+    content: String,
+    options: Option<ExtractOptions>,
+) -> napi::Result<ExtractionResult> {
+    let result = extractor(options)?.extract_from_file(&file_path, &content);
 
-    // Convert HashMap to Vec for NAPI
-    let anchors: Vec<SintesiAnchor> = result
-        .anchors
-        .into_iter()
-        .map(|(_, anchor)| SintesiAnchor {
-            id: anchor.id,
-            code_ref: anchor.code_ref,
-            file_path: anchor.file_path.to_string_lossy().to_string(),
-            start_line: anchor.start_line as u32,
-            end_line: anchor.end_line as u32,
-            content: anchor.content,
-        })
-        .collect();
+    let mut anchors: Vec<_> = result.anchors.into_values().collect();
+    anchors.sort_by_key(|a| a.start_line);
+    let anchors = anchors.into_iter().map(SintesiAnchor::from).collect();
 
-    ExtractionResult {
+    Ok(ExtractionResult {
         anchor_count: result.anchor_count as u32,
         anchors,
         errors: result.errors,
-    }
+        skipped: result.skipped.map(SkippedFileJs::from),
+    })
+}
+
+/// NAPI-compatible extraction result keyed by anchor ID
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyedExtractionResult {
+    /// Anchors by ID
+    pub anchors: BTreeMap<String, SintesiAnchor>,
+    /// Number of anchors found
+    pub anchor_count: u32,
+    /// Errors encountered during extraction
+    pub errors: Vec<String>,
+    /// Set if the file was not parsed (over `maxFileSize`)
+    pub skipped: Option<SkippedFileJs>,
+}
+
+/// Extract Sintesi anchors from markdown content, keyed by anchor ID
+///
+/// Same as `extractAnchors`, but `anchors` is a `Record<string, SintesiAnchor>`
+/// so callers looking anchors up by ID don't have to rebuild the map.
+///
+/// # Example (Node.js)
+/// ```javascript
+/// const { extractAnchorsById } = require('@sintesi/core');
+///
+/// const { anchors } = extractAnchorsById('docs/api.md', content);
+/// console.log(anchors['login']?.codeRef);
+/// ```
+#[napi]
+pub fn extract_anchors_by_id(
+    file_path: String,
+    content: String,
+    options: Option<ExtractOptions>,
+) -> napi::Result<KeyedExtractionResult> {
+    let result = extractor(options)?.extract_from_file(&file_path, &content);
+
+    Ok(KeyedExtractionResult {
+        anchor_count: result.anchor_count as u32,
+        anchors: result
+            .anchors
+            .into_iter()
+            .map(|(id, anchor)| (id, SintesiAnchor::from(anchor)))
+            .collect(),
+        errors: result.errors,
+        skipped: result.skipped.map(SkippedFileJs::from),
+    })
 }
 
 /// Validate markdown content for Sintesi anchors
@@ -221,6 +362,388 @@ pub fn validate_markdown_anchors(content: String) -> Vec<String> {
     extractor.validate(&content)
 }
 
+/// Validate fenced TypeScript/JavaScript examples inside anchors
+///
+/// Each example must parse, and its imports must resolve against the
+/// project: relative imports to existing files exporting the imported
+/// names, bare imports to declared or installed packages.
+///
+/// # Arguments
+/// * `project_root` - Root directory of the project
+/// * `markdown_path` - Path of the markdown file relative to `project_root`
+/// * `content` - Markdown content to validate
+///
+/// # Returns
+/// Array of validation error messages, empty if every example is valid
+///
+/// # Example (Node.js)
+/// ```javascript
+/// const { validateCodeExamples } = require('@sintesi/core');
+///
+/// const content = fs.readFileSync('docs/api.md', 'utf-8');
+/// const errors = validateCodeExamples(process.cwd(), 'docs/api.md', content);
+/// errors.forEach(err => console.error('  -', err));
+/// ```
+#[napi]
+pub fn validate_code_examples(
+    project_root: String,
+    markdown_path: String,
+    content: String,
+) -> Vec<String> {
+    crate::content::validate_code_examples(project_root, &markdown_path, &content)
+}
+
+/// A broken code_ref or link with suggested replacements (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokenReferenceJs {
+    /// "codeRef" or "link"
+    pub kind: String,
+    /// Anchor ID, for code_refs
+    pub anchor_id: Option<String>,
+    /// Line of the reference (0-indexed)
+    pub line: u32,
+    /// The reference as written
+    pub target: String,
+    /// Error message, including suggestions
+    pub message: String,
+    /// Replacement targets, best first
+    pub suggestions: Vec<String>,
+}
+
+mirror! {
+    BrokenReference {
+        kind,
+        anchor_id,
+        line,
+        target,
+        message,
+        suggestions,
+    } => BrokenReferenceJs {
+        kind: match kind {
+            BrokenKind::CodeRef => "codeRef".to_string(),
+            BrokenKind::Link => "link".to_string(),
+        },
+        anchor_id,
+        line,
+        target,
+        message,
+        suggestions,
+    }
+}
+
+/// A replacement to apply to a markdown document (for NAPI)
+#[napi(object)]
+pub struct ReferenceFix {
+    /// Line to rewrite (0-indexed)
+    pub line: u32,
+    /// Text to replace (usually the broken reference's `target`)
+    pub original: String,
+    /// Replacement text (usually one of its `suggestions`)
+    pub replacement: String,
+}
+
+/// Find broken code_refs and relative links in a markdown document
+///
+/// The project is walked (respecting .gitignore) and its source files are
+/// analyzed to resolve code_refs and compute suggestions.
+///
+/// # Arguments
+/// * `project_root` - Root directory of the project
+/// * `markdown_path` - Path of the markdown file relative to `project_root`
+/// * `content` - Markdown content to check
+///
+/// # Example (Node.js)
+/// ```javascript
+/// const { findBrokenReferences, applyFixes } = require('@sintesi/core');
+///
+/// const broken = findBrokenReferences(root, 'docs/api.md', content);
+/// const fixes = broken
+///   .filter(b => b.suggestions.length > 0)
+///   .map(b => ({ line: b.line, original: b.target, replacement: b.suggestions[0] }));
+/// fs.writeFileSync('docs/api.md', applyFixes(content, fixes));
+/// ```
+#[napi]
+pub fn find_broken_references(
+    project_root: String,
+    markdown_path: String,
+    content: String,
+) -> Vec<BrokenReferenceJs> {
+    let index = ReferenceIndex::from_project(&project_root);
+
+    fixes::find_broken_references(&markdown_path, &content, &index)
+        .into_js()
+}
+
+/// Apply accepted reference fixes to a markdown document
+///
+/// # Throws
+/// Error if a fix's original text is no longer on its line
+#[napi]
+pub fn apply_fixes(content: String, fixes: Vec<ReferenceFix>) -> napi::Result<String> {
+    let fixes: Vec<Fix> = fixes
+        .into_iter()
+        .map(|f| Fix {
+            line: f.line as usize,
+            original: f.original,
+            replacement: f.replacement,
+        })
+        .collect();
+
+    fixes::apply_fixes(&content, &fixes).map_err(napi::Error::from_reason)
+}
+
+/// NAPI-compatible options for the markdown linter
+#[napi(object)]
+pub struct LintOptions {
+    /// Maximum words per paragraph (default: 120)
+    pub max_paragraph_words: Option<u32>,
+    /// Report passive-voice constructions (default: true)
+    pub check_passive_voice: Option<bool>,
+    /// Words or phrases that must not appear (case-insensitive)
+    pub banned_words: Option<Vec<String>>,
+}
+
+/// A lint finding (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFindingJs {
+    /// Rule identifier (e.g. "heading-hierarchy", "banned-word")
+    pub rule: String,
+    /// "warning" or "error"
+    pub severity: String,
+    /// Line in the linted content (0-indexed)
+    pub line: u32,
+    /// Human-readable description
+    pub message: String,
+}
+
+mirror! {
+    LintFinding {
+        rule,
+        severity,
+        line,
+        message,
+    } => LintFindingJs {
+        rule,
+        severity: match severity {
+            LintSeverity::Warning => "warning".to_string(),
+            LintSeverity::Error => "error".to_string(),
+        },
+        line,
+        message,
+    }
+}
+
+/// Lint generated markdown for readability problems
+///
+/// # Arguments
+/// * `content` - Markdown content (a document or a single anchor's content)
+/// * `options` - Optional linter configuration
+///
+/// # Returns
+/// Findings ordered by line; any finding with severity "error" should block
+/// injection
+///
+/// # Example (Node.js)
+/// ```javascript
+/// const { lintMarkdown } = require('@sintesi/core');
+///
+/// const findings = lintMarkdown(generated, { bannedWords: ['simply', 'just'] });
+/// if (findings.some(f => f.severity === 'error')) {
+///   throw new Error('Generated docs failed lint');
+/// }
+/// ```
+#[napi]
+pub fn lint_markdown(content: String, options: Option<LintOptions>) -> Vec<LintFindingJs> {
+    let mut config = LintConfig::new();
+    if let Some(opts) = options {
+        if let Some(words) = opts.max_paragraph_words {
+            config = config.max_paragraph_words(words as usize);
+        }
+        if let Some(value) = opts.check_passive_voice {
+            config = config.check_passive_voice(value);
+        }
+        for word in opts.banned_words.unwrap_or_default() {
+            config = config.ban_word(word);
+        }
+    }
+
+    lint::lint_markdown(&content, &config).into_js()
+}
+
+/// NAPI-compatible options for spell checking
+#[napi(object)]
+pub struct SpellCheckOptions {
+    /// Word list to use (default: the system dictionary, if installed)
+    pub dictionary_path: Option<String>,
+    /// Project-specific words to accept
+    pub custom_words: Option<Vec<String>>,
+    /// Source files whose symbol names should be accepted
+    pub source_files: Option<Vec<String>>,
+}
+
+/// A misspelled word (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MisspellingJs {
+    /// The word as written
+    pub word: String,
+    /// ID of the anchor containing the word
+    pub anchor_id: String,
+    /// Line in the markdown file (0-indexed)
+    pub line: u32,
+    /// Column within the line (0-indexed)
+    pub column: u32,
+    /// Suggested corrections, best first
+    pub suggestions: Vec<String>,
+}
+
+mirror! {
+    Misspelling {
+        word,
+        anchor_id,
+        line,
+        column,
+        suggestions,
+    } => MisspellingJs {
+        word,
+        anchor_id,
+        line,
+        column,
+        suggestions,
+    }
+}
+
+/// Spell check the prose inside anchors
+///
+/// Spell checking is optional: when no dictionary is given and no system
+/// word list is installed, no misspellings are reported.
+///
+/// # Arguments
+/// * `content` - Markdown content to check
+/// * `options` - Dictionary, custom words, and source files for symbol names
+///
+/// # Throws
+/// Error if `dictionaryPath` cannot be read
+///
+/// # Example (Node.js)
+/// ```javascript
+/// const { spellCheckAnchors } = require('@sintesi/core');
+///
+/// const misspellings = spellCheckAnchors(content, {
+///   customWords: ['sintesi', 'monorepo'],
+///   sourceFiles: ['src/auth.ts'],
+/// });
+/// misspellings.forEach(m => console.warn(`${m.line + 1}:${m.column + 1} ${m.word}`));
+/// ```
+#[napi]
+pub fn spell_check_anchors(
+    content: String,
+    options: Option<SpellCheckOptions>,
+) -> napi::Result<Vec<MisspellingJs>> {
+    let options = options.unwrap_or(SpellCheckOptions {
+        dictionary_path: None,
+        custom_words: None,
+        source_files: None,
+    });
+
+    let checker = match &options.dictionary_path {
+        Some(path) => Some(SpellChecker::from_dictionary_file(path).map_err(|e| {
+            napi::Error::from_reason(format!("Failed to read dictionary {}: {}", path, e))
+        })?),
+        None => SpellChecker::with_system_dictionary(),
+    };
+    let Some(mut checker) = checker else {
+        return Ok(vec![]);
+    };
+
+    checker.add_words(options.custom_words.unwrap_or_default());
+    let analyzer = AstAnalyzerInternal::new();
+    for path in options.source_files.unwrap_or_default() {
+        if let Ok(source) = read_text(Path::new(&path)) {
+            checker.add_symbols(&analyzer.analyze_file(&path, &source).symbols);
+        }
+    }
+
+    Ok(checker.check_markdown(&content).into_js())
+}
+
+/// Replace the content of an anchor in a markdown file
+///
+/// Everything outside the anchor (line endings, surrounding whitespace, a
+/// byte order mark, nested anchors) is kept as it is, and the file keeps its
+/// encoding (UTF-8 or UTF-16).
+///
+/// # Arguments
+/// * `file_path` - Markdown file
+/// * `anchor_id` - ID of the anchor to rewrite
+/// * `new_content` - Replacement documentation (without anchor tags)
+/// * `dry_run` - Return the patched file without writing it (default: false)
+///
+/// # Returns
+/// The patched file content
+///
+/// # Throws
+/// Error if the file cannot be read or written, or the anchor is missing
+///
+/// # Example (Node.js)
+/// ```javascript
+/// const { injectAnchor } = require('@sintesi/core');
+///
+/// const preview = injectAnchor('docs/auth.md', 'login', generated, true);
+/// injectAnchor('docs/auth.md', 'login', generated);
+/// ```
+#[napi]
+pub fn inject_anchor(
+    file_path: String,
+    anchor_id: String,
+    new_content: String,
+    dry_run: Option<bool>,
+) -> napi::Result<String> {
+    injector::inject_file(
+        &file_path,
+        &anchor_id,
+        &new_content,
+        dry_run.unwrap_or(false),
+    )
+    .map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Generate a markdown skeleton with an anchor for each symbol of a file
+///
+/// Each symbol gets a heading and an anchor with a fresh UUID and its
+/// `code_ref`, holding a placeholder to regenerate. Members get a heading
+/// below their type, and overloads a single anchor.
+///
+/// # Arguments
+/// * `file_path` - Source file of the signatures, as used in `code_ref`s
+/// * `signatures` - Signatures of the file (`analyzeFile`)
+/// * `title` - Title of the document (default: the file path)
+/// * `include_private` - Scaffold symbols that are not exported (default: false)
+///
+/// # Example (Node.js)
+/// ```javascript
+/// const { AstAnalyzer, scaffoldAnchors } = require('@sintesi/core');
+///
+/// const signatures = new AstAnalyzer().analyzeFile('src/auth.ts');
+/// fs.writeFileSync('docs/auth.md', scaffoldAnchors('src/auth.ts', signatures, 'Auth'));
+/// ```
+#[napi]
+pub fn scaffold_anchors(
+    file_path: String,
+    signatures: Vec<CodeSignature>,
+    title: Option<String>,
+    include_private: Option<bool>,
+) -> String {
+    let mut options = ScaffoldOptions::new().include_private(include_private.unwrap_or(false));
+    options.title = title;
+    scaffold(&file_path, &signatures, &options)
+}
+
 /// Parse a code_ref string into file path and symbol name
 ///
 /// # Arguments
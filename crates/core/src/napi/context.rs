@@ -1,13 +1,16 @@
 use napi_derive::napi;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[napi(object)]
 pub struct PackageJson {
     pub name: Option<String>,
     pub version: Option<String>,
-    pub dependencies: Option<HashMap<String, String>>,
-    pub dev_dependencies: Option<HashMap<String, String>>,
-    pub scripts: Option<HashMap<String, String>>,
+    pub dependencies: Option<BTreeMap<String, String>>,
+    pub dev_dependencies: Option<BTreeMap<String, String>>,
+    pub peer_dependencies: Option<BTreeMap<String, String>>,
+    pub scripts: Option<BTreeMap<String, String>>,
+    pub main: Option<String>,
+    pub module: Option<String>,
 }
 
 #[napi(object)]
@@ -18,10 +21,41 @@ pub struct FileContext {
     pub imported_by: Vec<String>,
 }
 
+/// A package entry point (package.json `main`/`module`/`exports`/`bin`, or a Rust crate root)
+#[napi(object)]
+pub struct EntryPoint {
+    /// `main`, `module`, `export`, `bin`, or `lib`
+    pub kind: String,
+    /// Export subpath (`.`, `./client`) or binary name
+    pub name: Option<String>,
+    /// Path as declared, relative to the project root
+    pub target: String,
+    /// Project source file the entry resolves to
+    pub source: Option<String>,
+}
+
+/// A workspace package (a package.json or Cargo.toml directory)
+#[napi(object)]
+pub struct WorkspacePackage {
+    /// Name from the manifest (the directory when it declares none)
+    pub name: String,
+    /// Directory relative to the project root (empty for the root)
+    pub path: String,
+}
+
 #[napi(object)]
 pub struct ProjectContext {
     pub files: Vec<FileContext>,
     pub package_json: Option<PackageJson>,
+    pub entry_points: Vec<EntryPoint>,
+    /// Files reachable from the entry points through imports
+    pub public_surface: Vec<String>,
+    /// Headings and first paragraphs of the README and `docs/adr/*.md`
+    pub architecture_brief: Option<String>,
+    /// `nextjs`, `nestjs`, `express`, `react`, `tauri`, or `actix`
+    pub frameworks: Vec<String>,
+    /// Workspace packages, sorted by path
+    pub packages: Vec<WorkspacePackage>,
 }
 
 #[napi]
@@ -40,11 +74,29 @@ pub fn get_project_context(root_path: String) -> ProjectContext {
         version: p.version,
         dependencies: p.dependencies,
         dev_dependencies: p.dev_dependencies,
+        peer_dependencies: p.peer_dependencies,
         scripts: p.scripts,
+        main: p.main,
+        module: p.module,
     });
 
+    let entry_points = context.entry_points.into_iter().map(|e| EntryPoint {
+        kind: e.kind.as_str().to_string(),
+        name: e.name,
+        target: e.target,
+        source: e.source,
+    }).collect();
+
     ProjectContext {
         files: napi_files,
         package_json: napi_package_json,
+        entry_points,
+        public_surface: context.public_surface,
+        architecture_brief: context.architecture_brief,
+        frameworks: context.frameworks.iter().map(|f| f.as_str().to_string()).collect(),
+        packages: context.packages.into_iter().map(|p| WorkspacePackage {
+            name: p.name,
+            path: p.path,
+        }).collect(),
     }
 }
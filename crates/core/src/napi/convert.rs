@@ -0,0 +1,152 @@
+//! Conversions from core types to their NAPI mirrors
+//!
+//! Results cross the boundary as `#[napi(object)]` mirrors of the core types
+//! (`DriftEntry` as `DriftEntryJs`). `mirror!` generates a mirror's `From`
+//! impl: the core value is destructured without `..`, so a field added to a
+//! core type does not compile until its mirror exposes it or drops it with
+//! `field: _`. Mirror fields are converted with `IntoJs` (counts to `u32`,
+//! paths to `/`-separated strings, enums to their names, nested mirrors)
+//! unless given an expression over the core fields. Conversions that need
+//! the whole value are written by hand, destructuring it the same way, and
+//! registered with `into_js!`.
+//!
+//! ```ignore
+//! mirror! {
+//!     Relocation { entry_id, from, to } => RelocationJs { entry_id, from, to }
+//! }
+//! ```
+
+use crate::ast::diff::ChangeSeverity;
+use crate::ast::DiagnosticSeverity;
+use crate::content::LintRule;
+use crate::drift::DeprecationChange;
+use crate::report::RunStatus;
+use crate::text::SkipReason;
+use std::path::PathBuf;
+
+/// Conversion of a core value to the type NAPI exposes
+pub trait IntoJs {
+    /// The NAPI type
+    type Js;
+
+    /// Convert the value
+    fn into_js(self) -> Self::Js;
+}
+
+/// Types NAPI exposes as they are
+macro_rules! unchanged {
+    ($($ty:ty),*) => {
+        $(impl IntoJs for $ty {
+            type Js = Self;
+
+            fn into_js(self) -> Self {
+                self
+            }
+        })*
+    };
+}
+
+unchanged!(String, bool, u32, i32, i64, f64);
+
+/// Enums exposed by name (`as_str`)
+macro_rules! by_name {
+    ($($ty:ty),*) => {
+        $(impl IntoJs for $ty {
+            type Js = String;
+
+            fn into_js(self) -> String {
+                self.as_str().to_string()
+            }
+        })*
+    };
+}
+
+by_name!(
+    ChangeSeverity,
+    DeprecationChange,
+    DiagnosticSeverity,
+    LintRule,
+    RunStatus,
+    SkipReason
+);
+
+impl IntoJs for usize {
+    type Js = u32;
+
+    fn into_js(self) -> u32 {
+        self as u32
+    }
+}
+
+impl IntoJs for &'static str {
+    type Js = String;
+
+    fn into_js(self) -> String {
+        self.to_string()
+    }
+}
+
+impl IntoJs for PathBuf {
+    type Js = String;
+
+    fn into_js(self) -> String {
+        self.to_string_lossy().replace('\\', "/")
+    }
+}
+
+impl<T: IntoJs> IntoJs for Option<T> {
+    type Js = Option<T::Js>;
+
+    fn into_js(self) -> Self::Js {
+        self.map(T::into_js)
+    }
+}
+
+impl<T: IntoJs> IntoJs for Vec<T> {
+    type Js = Vec<T::Js>;
+
+    fn into_js(self) -> Self::Js {
+        self.into_iter().map(T::into_js).collect()
+    }
+}
+
+/// Implement `From` and `IntoJs` converting a core type to its NAPI mirror
+///
+/// The left side lists every field of the core type, as a struct pattern;
+/// the right side every field of the mirror, converted from the core field
+/// of the same name or computed by an expression.
+macro_rules! mirror {
+    ($core:ident { $($pattern:tt)* } => $js:ident { $($field:ident $(: $value:expr)?),* $(,)? }) => {
+        impl From<$core> for $js {
+            fn from(value: $core) -> Self {
+                let $core { $($pattern)* } = value;
+                Self {
+                    $($field: $crate::napi::convert::mirror!(@field $field $(, $value)?)),*
+                }
+            }
+        }
+
+        $crate::napi::convert::into_js!($core => $js);
+    };
+    (@field $field:ident) => {
+        $crate::napi::convert::IntoJs::into_js($field)
+    };
+    (@field $field:ident, $value:expr) => {
+        $value
+    };
+}
+
+/// Implement `IntoJs` for core types with a `From` conversion to a mirror
+macro_rules! into_js {
+    ($($core:ty => $js:ty),*) => {
+        $(impl $crate::napi::convert::IntoJs for $core {
+            type Js = $js;
+
+            fn into_js(self) -> $js {
+                self.into()
+            }
+        })*
+    };
+}
+
+pub(crate) use {into_js, mirror};
@@ -6,9 +6,22 @@ pub struct NapiFileInfo {
     pub extension: Option<String>,
 }
 
+/// Options for `getProjectFiles`
+#[napi(object)]
+pub struct CrawlOptionsJs {
+    /// Skip files whose content matches a file already listed
+    pub dedup_content: Option<bool>,
+}
+
+/// List project files (honoring `.gitignore`), skipping hard links of files
+/// already listed
 #[napi]
-pub fn get_project_files(root_path: String) -> Vec<NapiFileInfo> {
-    let files = crate::crawler::get_project_files(&root_path);
+pub fn get_project_files(root_path: String, options: Option<CrawlOptionsJs>) -> Vec<NapiFileInfo> {
+    let mut crawl_options = crate::crawler::CrawlOptions::new();
+    if let Some(options) = options {
+        crawl_options = crawl_options.dedup_content(options.dedup_content.unwrap_or(false));
+    }
+    let files = crate::crawler::get_project_files_with(&root_path, &crawl_options);
     files
         .into_iter()
         .map(|f| NapiFileInfo {
@@ -0,0 +1,123 @@
+//! Daemon NAPI bindings
+//!
+//! Node.js bindings for long-lived project state in watch/daemon mode.
+
+use super::convert::mirror;
+use super::search::{to_search_options, SearchOptionsJs, SearchPageJs};
+use crate::daemon::{FileUpdate, ProjectState};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::path::Path;
+
+/// What changed when a file was updated or removed (for NAPI)
+#[napi(object)]
+pub struct FileUpdateJs {
+    /// File path relative to the project root
+    pub path: String,
+    /// The file's exported API changed
+    pub symbols_changed: bool,
+    /// The file's anchors changed
+    pub anchors_changed: bool,
+    /// Files that import the updated file
+    pub dependents: Vec<String>,
+}
+
+mirror! {
+    FileUpdate {
+        path,
+        symbols_changed,
+        anchors_changed,
+        dependents,
+    } => FileUpdateJs {
+        path,
+        symbols_changed,
+        anchors_changed,
+        dependents,
+    }
+}
+
+fn path_str(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Project state that is patched file by file instead of rebuilt
+#[napi]
+pub struct ProjectDaemon {
+    state: ProjectState,
+}
+
+#[napi]
+impl ProjectDaemon {
+    /// Discover and analyze the project once
+    #[napi(constructor)]
+    pub fn new(root_path: String) -> Self {
+        Self {
+            state: ProjectState::load(root_path),
+        }
+    }
+
+    /// Apply new content for a file (path relative to the root)
+    #[napi]
+    pub fn update_file(&self, file_path: String, content: String) -> FileUpdateJs {
+        self.state.update_file(file_path, &content).into()
+    }
+
+    /// Re-read a changed file from disk (a missing file is treated as removed)
+    #[napi]
+    pub fn refresh_file(&self, file_path: String) -> FileUpdateJs {
+        self.state.refresh_file(file_path).into()
+    }
+
+    /// Forget a deleted file
+    #[napi]
+    pub fn remove_file(&self, file_path: String) -> FileUpdateJs {
+        self.state.remove_file(file_path).into()
+    }
+
+    /// Files that import the given file
+    #[napi]
+    pub fn get_dependents(&self, file_path: String) -> Vec<String> {
+        self.state
+            .dependents(file_path)
+            .iter()
+            .map(|p| path_str(p))
+            .collect()
+    }
+
+    /// Files imported by the given file
+    #[napi]
+    pub fn get_dependencies(&self, file_path: String) -> Vec<String> {
+        self.state
+            .dependencies(file_path)
+            .iter()
+            .map(|p| path_str(p))
+            .collect()
+    }
+
+    /// Refresh the files that differ from a git revision (`HEAD` by
+    /// default), forgetting deleted ones
+    ///
+    /// @param baseRef - The revision the daemon was last indexed at
+    /// @returns One update per changed file
+    #[napi]
+    pub fn reindex_changed(&self, base_ref: Option<String>) -> Result<Vec<FileUpdateJs>> {
+        self.state
+            .reindex_changed(base_ref.as_deref())
+            .map(|updates| updates.into_iter().map(Into::into).collect())
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Search the project like `searchProject`, using the daemon's trigram
+    /// index to skip files that cannot contain a literal
+    #[napi]
+    pub fn search(
+        &self,
+        pattern: String,
+        options: Option<SearchOptionsJs>,
+    ) -> Result<SearchPageJs> {
+        self.state
+            .search(&pattern, &to_search_options(options))
+            .map(Into::into)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+}
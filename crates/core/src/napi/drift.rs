@@ -0,0 +1,249 @@
+//! Drift NAPI bindings
+//!
+//! Node.js bindings for drift detection, moved-symbol relocation, and
+//! freshness policy evaluation.
+
+use super::convert::{into_js, mirror, IntoJs};
+use crate::drift::{
+    check_drift as check_map_drift, evaluate_policies, now_millis,
+    relocate_moved_symbols as relocate_map_symbols, DriftEntry, DriftHistory, DriftStatus,
+    PolicySet, PolicySeverity, PolicyViolation, Relocation, SintesiMap, ViolationKind,
+    HISTORY_FILE, MAP_FILE,
+};
+use crate::provider::DiskProvider;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::Path;
+
+/// Drift result for a single map entry (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftEntryJs {
+    /// Anchor ID of the map entry
+    pub entry_id: String,
+    /// Code reference (`file_path#symbol_name`)
+    pub code_ref: String,
+    /// Markdown file containing the anchor
+    pub doc_file: String,
+    /// "inSync", "drifted", "removed", or "ignored"
+    pub status: String,
+    /// Hash recorded in the map
+    pub expected_hash: String,
+    /// Hash of the current signature, if the symbol still exists
+    pub current_hash: Option<String>,
+    /// When drift was first detected (milliseconds since Unix epoch)
+    pub first_detected: Option<i64>,
+    /// Reason given by the ignore directive, for ignored entries
+    pub ignore_reason: Option<String>,
+    /// "deprecated" or "undeprecated" when the symbol's deprecation changed
+    /// since the documentation was written
+    pub deprecation: Option<String>,
+    /// "breaking" or "nonBreaking" for drifted and removed entries whose
+    /// change could be classified
+    pub severity: Option<String>,
+    /// Tests exercising the symbol, when the project's tests were mapped
+    pub test_count: Option<u32>,
+}
+
+/// A freshness policy violation (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyViolationJs {
+    /// Name of the violated policy
+    pub policy: String,
+    /// "warning" or "error"
+    pub severity: String,
+    /// "drift" or "age"
+    pub kind: String,
+    /// Anchor ID of the map entry
+    pub entry_id: String,
+    /// Code reference of the map entry
+    pub code_ref: String,
+    /// Markdown file containing the anchor
+    pub doc_file: String,
+    /// Days drifted (for "drift") or since last update (for "age")
+    pub days: u32,
+    /// The policy limit in days
+    pub limit_days: u32,
+    /// Human-readable description for CI logs
+    pub message: String,
+}
+
+impl IntoJs for DriftStatus {
+    type Js = String;
+
+    fn into_js(self) -> String {
+        match self {
+            DriftStatus::InSync => "inSync",
+            DriftStatus::Drifted => "drifted",
+            DriftStatus::Removed => "removed",
+            DriftStatus::Ignored => "ignored",
+        }
+        .to_string()
+    }
+}
+
+// `firstDetected` comes from the drift history, where there is one
+mirror! {
+    DriftEntry {
+        entry_id,
+        code_ref,
+        doc_file,
+        status,
+        expected_hash,
+        current_hash,
+        current_signature: _,
+        ignore_reason,
+        deprecation,
+        severity,
+        test_count,
+    } => DriftEntryJs {
+        entry_id,
+        code_ref,
+        doc_file,
+        status,
+        expected_hash,
+        current_hash,
+        first_detected: None,
+        ignore_reason,
+        deprecation,
+        severity,
+        test_count,
+    }
+}
+
+// The message is the violation as printed, so it is converted by hand, still
+// destructuring every field
+impl From<PolicyViolation> for PolicyViolationJs {
+    fn from(violation: PolicyViolation) -> Self {
+        let message = violation.to_string();
+        let PolicyViolation {
+            policy,
+            severity,
+            kind,
+            entry_id,
+            code_ref,
+            doc_file,
+            days,
+            limit_days,
+        } = violation;
+        Self {
+            policy,
+            severity: match severity {
+                PolicySeverity::Warning => "warning".to_string(),
+                PolicySeverity::Error => "error".to_string(),
+            },
+            kind: match kind {
+                ViolationKind::Drift => "drift".to_string(),
+                ViolationKind::Age => "age".to_string(),
+            },
+            entry_id,
+            code_ref,
+            doc_file,
+            days,
+            limit_days,
+            message,
+        }
+    }
+}
+
+into_js!(PolicyViolation => PolicyViolationJs);
+
+/// Check the project's map for drift and update the drift history
+///
+/// Reads `sintesi-map.json`, hashes the current signatures of every mapped
+/// symbol, and records newly drifted entries in `.sintesi/drift-history.json`.
+///
+/// @param rootPath - Project root containing the map
+/// @returns One entry per map entry
+#[napi]
+pub fn check_drift(root_path: String) -> Result<Vec<DriftEntryJs>> {
+    let root = Path::new(&root_path);
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+    let mut history = DriftHistory::load(root.join(HISTORY_FILE))
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+
+    let result = check_map_drift(&DiskProvider::new(root), &map);
+    history.record(&result, now_millis());
+    history
+        .save(root.join(HISTORY_FILE))
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+
+    Ok(result
+        .entries
+        .into_iter()
+        .map(|e| DriftEntryJs {
+            first_detected: history.first_detected(&e.entry_id),
+            ..e.into()
+        })
+        .collect())
+}
+
+/// A map entry pointed at a symbol's new location (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RelocationJs {
+    /// Anchor ID of the map entry
+    pub entry_id: String,
+    /// Previous code reference
+    pub from: String,
+    /// New code reference
+    pub to: String,
+}
+
+mirror! {
+    Relocation { entry_id, from, to } => RelocationJs { entry_id, from, to }
+}
+
+/// Follow symbols that moved to another file by their symbol IDs and keys
+///
+/// Map entries with a `symbolId` or `symbolKey` whose code reference no
+/// longer resolves are pointed at the exported symbol with the same ID, or
+/// else the one with the same key; the map is saved if anything moved. Run before `checkDrift` so file moves don't orphan docs.
+///
+/// @param rootPath - Project root containing the map
+/// @returns The relocated entries
+#[napi]
+pub fn relocate_moved_symbols(root_path: String) -> Result<Vec<RelocationJs>> {
+    let root = Path::new(&root_path);
+    let mut map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+
+    let relocations = relocate_map_symbols(&DiskProvider::new(root), &mut map);
+    if !relocations.is_empty() {
+        map.save(root.join(MAP_FILE))
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+    }
+
+    Ok(relocations.into_js())
+}
+
+/// Evaluate freshness SLA policies against the map and drift history
+///
+/// @param rootPath - Project root containing the map and drift history
+/// @param policiesPath - JSON file with a `policies` array
+/// @returns Policy violations; any with severity "error" should fail CI
+#[napi]
+pub fn evaluate_freshness_policies(
+    root_path: String,
+    policies_path: String,
+) -> Result<Vec<PolicyViolationJs>> {
+    let root = Path::new(&root_path);
+    let policies =
+        PolicySet::load(&policies_path).map_err(|e| Error::from_reason(e.to_string()))?;
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+    let history = DriftHistory::load(root.join(HISTORY_FILE))
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+
+    let violations = evaluate_policies(&policies.policies, &map, &history, now_millis())
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+
+    Ok(violations.into_js())
+}
@@ -1,5 +1,11 @@
 use napi_derive::napi;
-use crate::git::{GitService, analyzer::GitAnalyzer};
+use schemars::JsonSchema;
+use serde::Serialize;
+use crate::git::{GitService, analyzer::GitAnalyzer, remote};
+use crate::ast::{AstAnalyzerInternal, SignatureHasher};
+use crate::types::SymbolType;
+use super::convert::IntoJs;
+use super::drift::DriftEntryJs;
 use napi::bindgen_prelude::*;
 
 #[napi(object)]
@@ -31,10 +37,10 @@ impl GitBinding {
             let is_staged = staged.unwrap_or(false);
 
             let changed_files = service.get_changed_files(base_branch.as_deref(), is_staged)
-                .map_err(|e| Error::from_reason(&format!("Git error: {}", e)))?;
+                .map_err(|e| Error::from_reason(format!("Git error: {}", e)))?;
             
             let git_diff = service.get_diff(base_branch.as_deref(), is_staged)
-                .map_err(|e| Error::from_reason(&format!("Git error: {}", e)))?;
+                .map_err(|e| Error::from_reason(format!("Git error: {}", e)))?;
 
             let has_meaningful_changes = GitAnalyzer::has_meaningful_changes(&git_diff);
 
@@ -53,3 +59,73 @@ impl GitBinding {
         GitAnalyzer::has_meaningful_changes(&diff)
     }
 }
+
+/// A symbol found in a remote repository
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteSymbol {
+  /// File path relative to the repository root
+  pub file_path: String,
+  pub symbol_name: String,
+  pub symbol_type: SymbolType,
+  pub signature_text: String,
+  pub hash: String,
+}
+
+/// Result of analyzing a remote repository
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteAnalysisResult {
+  pub url: String,
+  pub reference: Option<String>,
+  /// Commit that was analyzed
+  pub commit: String,
+  pub markdown_files: u32,
+  pub source_files: u32,
+  pub symbols: Vec<RemoteSymbol>,
+  /// Drift against the repository's own map (empty if it has none)
+  pub drift: Vec<DriftEntryJs>,
+  pub errors: Vec<String>,
+}
+
+/// Shallow-clone a repository into a temp dir, analyze it, and clean up
+///
+/// @param url - Repository URL or local path
+/// @param reference - Branch, tag, or commit (default branch if omitted)
+#[napi]
+pub fn analyze_remote(url: String, reference: Option<String>) -> Result<RemoteAnalysisResult> {
+    let analysis = remote::analyze_remote(&url, reference.as_deref())
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+
+    let analyzer = AstAnalyzerInternal::new();
+    let hasher = SignatureHasher::new();
+    let symbols = analysis
+        .symbols
+        .iter()
+        .map(|s| RemoteSymbol {
+            file_path: s.file_path.clone(),
+            symbol_name: s.name.clone(),
+            symbol_type: s.symbol_type,
+            signature_text: s.signature.clone(),
+            hash: hasher.hash(analyzer.extract_signature(s)).hash,
+        })
+        .collect();
+    let drift = analysis
+        .drift
+        .map(|d| d.entries)
+        .unwrap_or_default()
+        .into_js();
+
+    Ok(RemoteAnalysisResult {
+        url: analysis.url,
+        reference: analysis.reference,
+        commit: analysis.commit,
+        markdown_files: analysis.markdown_files as u32,
+        source_files: analysis.source_files as u32,
+        symbols,
+        drift,
+        errors: analysis.errors,
+    })
+}
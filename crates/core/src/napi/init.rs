@@ -0,0 +1,118 @@
+//! Project setup NAPI bindings
+//!
+//! Node.js bindings for `sintesi init` and migrating projects from the
+//! legacy doctype format.
+
+use super::convert::mirror;
+use crate::init::{self, InitOptions, InitResult};
+use crate::migrate::{migrate_doctype_to_sintesi, MigrationIssue, MigrationReport};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Options for `initProject`
+#[napi(object)]
+pub struct InitOptionsJs {
+    /// Documentation directory, relative to the project root (default: `docs`)
+    pub docs_dir: Option<String>,
+    /// Install a git pre-commit hook running `sintesi check`
+    pub pre_commit_hook: Option<bool>,
+    /// Overwrite existing scaffolding files (never the map)
+    pub force: Option<bool>,
+}
+
+/// Files touched by `initProject` (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InitResultJs {
+    /// Files written, relative to the project root
+    pub created: Vec<String>,
+    /// Files left as they were because they already existed
+    pub skipped: Vec<String>,
+}
+
+mirror! {
+    InitResult { created, skipped } => InitResultJs { created, skipped }
+}
+
+/// Scaffold Sintesi in a project: `sintesi.config.json`, the `.sintesi/`
+/// state directory, an empty map, a starter docs file, and optionally a git
+/// pre-commit hook
+///
+/// @param rootPath - Project root
+/// @param options - Docs directory, hook installation, and overwrite behavior
+/// @returns Created and skipped files
+#[napi]
+pub fn init_project(root_path: String, options: Option<InitOptionsJs>) -> Result<InitResultJs> {
+    let mut init_options = InitOptions::new();
+    if let Some(options) = options {
+        if let Some(dir) = options.docs_dir {
+            init_options = init_options.docs_dir(dir);
+        }
+        init_options = init_options
+            .pre_commit_hook(options.pre_commit_hook.unwrap_or(false))
+            .force(options.force.unwrap_or(false));
+    }
+
+    let result =
+        init::init(&root_path, &init_options).map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(result.into())
+}
+
+/// Something that could not be migrated (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationIssueJs {
+    /// Anchor ID, map entry ID, or file the issue concerns
+    pub subject: String,
+    /// Why it was not migrated
+    pub reason: String,
+}
+
+mirror! {
+    MigrationIssue { subject, reason } => MigrationIssueJs { subject, reason }
+}
+
+/// Outcome of `migrateFromDoctype` (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationResultJs {
+    /// Markdown files whose anchors were rewritten, relative to the root
+    pub docs_rewritten: Vec<String>,
+    /// Number of anchor markers rewritten
+    pub anchors_rewritten: u32,
+    /// Map entries written to `sintesi-map.json`
+    pub entries_migrated: u32,
+    /// Anchors, entries, and files that were not migrated
+    pub issues: Vec<MigrationIssueJs>,
+}
+
+mirror! {
+    MigrationReport {
+        docs_rewritten,
+        anchors_rewritten,
+        entries_migrated,
+        issues,
+    } => MigrationResultJs {
+        docs_rewritten,
+        anchors_rewritten,
+        entries_migrated,
+        issues,
+    }
+}
+
+/// Migrate `doctype:start`/`doctype:end` anchors and `doctype-map.json` to
+/// the sintesi dialect, recomputing signature hashes
+///
+/// @param rootPath - Project root
+/// @returns Rewritten files, migrated entries, and anything not migrated
+#[napi]
+pub fn migrate_from_doctype(root_path: String) -> Result<MigrationResultJs> {
+    let report =
+        migrate_doctype_to_sintesi(&root_path).map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(report.into())
+}
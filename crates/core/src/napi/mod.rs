@@ -3,10 +3,22 @@
 //! This module provides Node.js bindings for the Rust core functionality.
 //! It exposes the core Rust logic to JavaScript/TypeScript through NAPI-RS.
 
+pub mod apply;
 pub mod ast;
 pub mod content;
 pub mod context;
+pub mod convert;
 pub mod crawler;
+pub mod daemon;
+pub mod drift;
 pub mod git;
 pub mod graph; // [NEW]
+pub mod init;
+pub mod pipeline;
+pub mod readonly;
+pub mod report;
+pub mod sandbox;
+pub mod search;
+#[cfg(feature = "test-support")]
+pub mod testing;
 pub mod utils;
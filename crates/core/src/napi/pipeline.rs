@@ -0,0 +1,392 @@
+//! Pipeline NAPI bindings
+//!
+//! Node.js bindings for running the whole documentation pipeline natively,
+//! as a dry run (the default) or applying the resulting plan.
+//!
+//! `runPipelineWithHooks` runs on a worker thread and calls JavaScript hooks
+//! through threadsafe functions, waiting for each (and for the promise it
+//! returns, if any) before the pipeline continues. A hook taking longer than
+//! the timeout of its phase fails the run.
+
+use super::drift::DriftEntryJs;
+use super::report::EvaluationJs;
+use super::search::SkippedFileJs;
+use crate::ast::SymbolInfo;
+use crate::drift::{DriftHistory, HISTORY_FILE};
+use crate::error::Error as CoreError;
+use crate::pipeline::{
+    self, Orchestrator, Phase, PhaseLimits, PipelineConfig, PipelineHooks, PipelineRun,
+};
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, UnknownReturnValue};
+use napi_derive::napi;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+/// Pipeline settings (for NAPI)
+#[napi(object)]
+pub struct PipelineOptions {
+    /// Mock generation and write nothing (default `true`)
+    pub dry_run: Option<bool>,
+    /// Regenerated anchor content by anchor ID, used instead of the mock
+    pub generated: Option<BTreeMap<String, String>>,
+    /// Commit the applied changes with this message
+    pub commit_message: Option<String>,
+    /// Time and memory limits by phase name (`discover`, `analyze`, `drift`,
+    /// `generate`, `plan`, `apply`)
+    pub limits: Option<HashMap<String, PhaseLimitsJs>>,
+    /// Size in bytes above which source and markdown files are skipped
+    /// (1 MiB by default)
+    pub max_file_size: Option<u32>,
+    /// Also analyze source files detected as generated or minified
+    pub include_generated: Option<bool>,
+    /// Attribute symbols of generated source files with a source map to
+    /// their original files, so coverage and anchors see the real sources
+    pub source_maps: Option<bool>,
+    /// Keep the analysis cache as one file per workspace package under
+    /// `.sintesi/ast-cache/`, loading only the packages analyzed
+    pub shard_cache: Option<bool>,
+}
+
+/// Time and memory limits of a pipeline phase (for NAPI)
+#[napi(object)]
+pub struct PhaseLimitsJs {
+    /// Longest the phase may take, hooks included, in milliseconds
+    pub timeout_ms: Option<u32>,
+    /// Most resident memory the process may use during the phase, in MiB
+    /// (Linux only)
+    pub max_memory_mb: Option<u32>,
+}
+
+/// JavaScript hooks called by `runPipelineWithHooks`
+///
+/// Hooks are called Node-style, with `null` as their first argument, and may
+/// return a promise. A hook that throws or rejects aborts the run.
+#[napi(object, object_to_js = false)]
+pub struct PipelineHooksJs {
+    /// `(err, filePath, symbolNames)` for each analyzed source file
+    pub on_file_analyzed: Option<ThreadsafeFunction<(String, Vec<String>)>>,
+    /// `(err, entries)` with the drift of every map entry
+    pub on_drift: Option<ThreadsafeFunction<(Vec<DriftEntryJs>,)>>,
+    /// `(err, drifted)` before generation; may return content by anchor ID,
+    /// which replaces the dry-run mock and `options.generated`
+    pub before_generate: Option<ThreadsafeFunction<(Vec<DriftEntryJs>,)>>,
+    /// `(err, filesWritten)` after the plan was applied (not called in a dry run)
+    pub after_inject: Option<ThreadsafeFunction<(Vec<String>,)>>,
+}
+
+/// Everything a pipeline run found and planned (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineRunJs {
+    /// Whether generation was mocked and nothing written
+    pub dry_run: bool,
+    /// Discovered source files
+    pub source_files: u32,
+    /// Discovered markdown files
+    pub markdown_files: u32,
+    /// Symbols found in the source files
+    pub symbols: u32,
+    /// Anchors found in the markdown files
+    pub anchors: u32,
+    /// Analysis and anchor extraction errors
+    pub errors: Vec<String>,
+    /// Source and markdown files left out because they are not text or are
+    /// over `maxFileSize`, and source files detected as generated
+    pub skipped: Vec<SkippedFileJs>,
+    /// How many of the `skipped` files are generated or minified
+    pub excluded_generated: u32,
+    /// Exported symbols documented by a map entry
+    pub documented: u32,
+    /// Exported symbols
+    pub total: u32,
+    /// Drift of each map entry
+    pub drift: Vec<DriftEntryJs>,
+    /// Content for drifted anchors by anchor ID (mocked in a dry run)
+    pub generated: BTreeMap<String, String>,
+    /// The plan as JSON, as returned by `createPlan`
+    pub plan: String,
+    /// One line per plan step
+    pub steps: Vec<String>,
+    /// CI gate status of the run
+    pub evaluation: EvaluationJs,
+    /// Files written by applying the plan (empty in a dry run)
+    pub files_written: Vec<String>,
+    /// Commit created by applying the plan
+    pub commit: Option<String>,
+}
+
+/// Run discovery, analysis, drift detection, generation, and planning
+///
+/// A dry run mocks generation and writes nothing (no files, map, or drift
+/// history) and makes no network calls; otherwise the plan is applied with
+/// the given generated content.
+///
+/// @param rootPath - Project root containing the map
+/// @param options - Dry run, generated content, commit message, and limits
+/// @returns Reports and the plan of the run
+#[napi]
+pub fn run_pipeline(root_path: String, options: Option<PipelineOptions>) -> Result<PipelineRunJs> {
+    let root = Path::new(&root_path);
+    let run = pipeline::run_pipeline(root, &to_config(options)?)
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(to_js(root, run))
+}
+
+/// Run the pipeline like `runPipeline`, calling JavaScript hooks on the way
+///
+/// @param rootPath - Project root containing the map
+/// @param options - Dry run, generated content, commit message, and limits
+/// @param hooks - Callbacks for analyzed files, drift, generation, and injection
+/// @returns A promise of the reports and plan of the run
+#[napi]
+pub fn run_pipeline_with_hooks(
+    root_path: String,
+    options: Option<PipelineOptions>,
+    hooks: PipelineHooksJs,
+) -> Result<AsyncTask<PipelineTask>> {
+    let config = to_config(options)?;
+    Ok(AsyncTask::new(PipelineTask {
+        root: PathBuf::from(root_path),
+        hooks: JsHooks {
+            hooks,
+            limits: config.limits.clone(),
+        },
+        config,
+    }))
+}
+
+/// Pipeline run on the libuv thread pool, so hooks can call back into JS
+pub struct PipelineTask {
+    root: PathBuf,
+    config: PipelineConfig,
+    hooks: JsHooks,
+}
+
+impl Task for PipelineTask {
+    type Output = PipelineRun;
+    type JsValue = PipelineRunJs;
+
+    fn compute(&mut self) -> Result<PipelineRun> {
+        Orchestrator::new(&self.root, self.config.clone())
+            .hook(&mut self.hooks)
+            .run()
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    fn resolve(&mut self, _env: Env, run: PipelineRun) -> Result<PipelineRunJs> {
+        Ok(to_js(&self.root, run))
+    }
+}
+
+/// Adapts `PipelineHooksJs` to the orchestrator's hooks
+struct JsHooks {
+    hooks: PipelineHooksJs,
+    limits: HashMap<Phase, PhaseLimits>,
+}
+
+impl JsHooks {
+    /// Longest a hook called during `phase` may take
+    fn timeout(&self, phase: Phase) -> Option<Duration> {
+        self.limits.get(&phase).and_then(|limits| limits.timeout)
+    }
+}
+
+impl PipelineHooks for JsHooks {
+    fn before_phase(
+        &mut self,
+        phase: Phase,
+        run: &mut PipelineRun,
+    ) -> std::result::Result<(), CoreError> {
+        if let (Phase::Generate, Some(hook)) = (phase, &self.hooks.before_generate) {
+            let drifted = run.drift.drifted().cloned().map(Into::into).collect();
+            let generated: Option<BTreeMap<String, String>> =
+                call_hook("beforeGenerate", hook, (drifted,), self.timeout(phase))?;
+            run.generated.extend(generated.unwrap_or_default());
+        }
+        Ok(())
+    }
+
+    fn file_analyzed(
+        &mut self,
+        file_path: &str,
+        symbols: &[SymbolInfo],
+    ) -> std::result::Result<(), CoreError> {
+        if let Some(hook) = &self.hooks.on_file_analyzed {
+            let names = symbols.iter().map(|s| s.name.clone()).collect();
+            call_hook::<_, UnknownReturnValue>(
+                "onFileAnalyzed",
+                hook,
+                (file_path.to_string(), names),
+                self.timeout(Phase::Analyze),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn after_phase(
+        &mut self,
+        phase: Phase,
+        run: &mut PipelineRun,
+    ) -> std::result::Result<(), CoreError> {
+        match phase {
+            Phase::Drift => {
+                if let Some(hook) = &self.hooks.on_drift {
+                    let entries = run.drift.entries.iter().cloned().map(Into::into).collect();
+                    let timeout = self.timeout(phase);
+                    call_hook::<_, UnknownReturnValue>("onDrift", hook, (entries,), timeout)?;
+                }
+            }
+            Phase::Apply => {
+                if let (Some(hook), Some(applied)) = (&self.hooks.after_inject, &run.applied) {
+                    let files = applied.files_written.clone();
+                    let timeout = self.timeout(phase);
+                    call_hook::<_, UnknownReturnValue>("afterInject", hook, (files,), timeout)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Value returned by a JavaScript hook: a promise or a plain value
+enum HookReturn<T: FromNapiValue> {
+    Pending(Promise<T>),
+    Ready(T),
+}
+
+impl<T: FromNapiValue> FromNapiValue for HookReturn<T> {
+    unsafe fn from_napi_value(env: sys::napi_env, value: sys::napi_value) -> Result<Self> {
+        let mut is_promise = false;
+        check_status!(sys::napi_is_promise(env, value, &mut is_promise))?;
+        Ok(match is_promise {
+            true => Self::Pending(Promise::from_napi_value(env, value)?),
+            false => Self::Ready(T::from_napi_value(env, value)?),
+        })
+    }
+}
+
+/// Call a hook from the worker thread and wait for its (awaited) result
+///
+/// The call runs on the napi runtime so a hook that never settles can be
+/// given up on after `timeout`.
+fn call_hook<A, T>(
+    name: &str,
+    hook: &ThreadsafeFunction<A>,
+    args: A,
+    timeout: Option<Duration>,
+) -> std::result::Result<T, CoreError>
+where
+    A: JsValuesTupleIntoVec + Send + 'static,
+    T: FromNapiValue + Send + 'static,
+{
+    let hook = hook.clone();
+    let (sender, receiver) = mpsc::channel();
+    spawn(async move {
+        let result = match hook.call_async::<HookReturn<T>>(Ok(args)).await {
+            Ok(HookReturn::Pending(promise)) => promise.await,
+            Ok(HookReturn::Ready(value)) => Ok(value),
+            Err(e) => Err(e),
+        };
+        // The receiver is gone if the hook timed out
+        let _ = sender.send(result);
+    });
+
+    let received = match timeout {
+        Some(limit) => receiver.recv_timeout(limit).map_err(|e| match e {
+            RecvTimeoutError::Timeout => CoreError::Timeout {
+                operation: format!("{} hook", name),
+                limit,
+            },
+            RecvTimeoutError::Disconnected => dropped_hook(name),
+        })?,
+        None => receiver.recv().map_err(|_| dropped_hook(name))?,
+    };
+    received.map_err(|e| CoreError::from_reason(format!("{} hook failed: {}", name, e.reason)))
+}
+
+fn dropped_hook(name: &str) -> CoreError {
+    CoreError::from_reason(format!("{} hook failed: the call was dropped", name))
+}
+
+pub(super) fn to_config(options: Option<PipelineOptions>) -> Result<PipelineConfig> {
+    let mut config = PipelineConfig::new();
+    if let Some(options) = options {
+        config = config.dry_run(options.dry_run.unwrap_or(true));
+        if let Some(generated) = options.generated {
+            config = config.generated(generated);
+        }
+        if let Some(message) = options.commit_message {
+            config = config.commit_message(message);
+        }
+        for (name, limits) in options.limits.unwrap_or_default() {
+            let phase = Phase::parse(&name)
+                .ok_or_else(|| Error::from_reason(format!("Unknown pipeline phase: {}", name)))?;
+            config = config.limit(phase, to_limits(limits));
+        }
+        if let Some(bytes) = options.max_file_size {
+            config = config.max_file_size(bytes as usize);
+        }
+        config = config
+            .include_generated(options.include_generated.unwrap_or(false))
+            .source_maps(options.source_maps.unwrap_or(false))
+            .shard_cache(options.shard_cache.unwrap_or(false));
+    }
+    Ok(config)
+}
+
+fn to_limits(limits: PhaseLimitsJs) -> PhaseLimits {
+    let mut result = PhaseLimits::new();
+    if let Some(ms) = limits.timeout_ms {
+        result = result.timeout(Duration::from_millis(ms as u64));
+    }
+    if let Some(mb) = limits.max_memory_mb {
+        result = result.max_memory(mb as u64 * 1024 * 1024);
+    }
+    result
+}
+
+pub(super) fn to_js(root: &Path, run: PipelineRun) -> PipelineRunJs {
+    // Read only: a dry run must not record drift
+    let history = DriftHistory::load(root.join(HISTORY_FILE)).unwrap_or_default();
+
+    let applied = run.applied.unwrap_or_default();
+    PipelineRunJs {
+        dry_run: run.dry_run,
+        source_files: run.discovery.source_files as u32,
+        markdown_files: run.discovery.markdown_files as u32,
+        symbols: run.analysis.symbols.len() as u32,
+        anchors: run.anchors.len() as u32,
+        errors: run
+            .analysis
+            .errors
+            .into_iter()
+            .chain(run.validation_errors)
+            .collect(),
+        excluded_generated: run.skipped.iter().filter(|s| s.is_generated()).count() as u32,
+        skipped: run.skipped.into_iter().map(Into::into).collect(),
+        documented: run.coverage.documented as u32,
+        total: run.coverage.total as u32,
+        drift: run
+            .drift
+            .entries
+            .into_iter()
+            .map(|e| DriftEntryJs {
+                first_detected: history.first_detected(&e.entry_id),
+                ..e.into()
+            })
+            .collect(),
+        generated: run.generated,
+        steps: run.plan.steps.iter().map(|step| step.to_string()).collect(),
+        plan: run.plan.to_json(),
+        evaluation: run.evaluation.into(),
+        files_written: applied.files_written,
+        commit: applied.commit,
+    }
+}
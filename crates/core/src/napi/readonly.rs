@@ -0,0 +1,27 @@
+//! Read-only mode NAPI bindings
+//!
+//! Lets a Node.js host turn read-only mode on for the process before
+//! analyzing untrusted code (see `crate::readonly`).
+
+use crate::readonly;
+use napi_derive::napi;
+
+/// Turn read-only mode on or off for the process
+///
+/// In read-only mode every write to the project (anchor injection, plans,
+/// the map, drift history, analysis cache, and git commits) fails with a
+/// "Read-only: ..." error. Turning it off has no effect while the
+/// `SINTESI_READ_ONLY` environment variable is set.
+///
+/// @param enabled - Whether writes are refused
+#[napi]
+pub fn set_read_only(enabled: bool) {
+    readonly::set_read_only(enabled);
+}
+
+/// Whether read-only mode is on (by `setReadOnly`, `SINTESI_READ_ONLY`, or a
+/// config with `"readOnly": true`)
+#[napi]
+pub fn is_read_only() -> bool {
+    readonly::is_read_only()
+}
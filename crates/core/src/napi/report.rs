@@ -0,0 +1,617 @@
+//! Report NAPI bindings
+//!
+//! Node.js bindings for forge reporting, badges, HTML/JUnit reports, report
+//! sinks, CI gating, and the staleness heatmap.
+
+use super::convert::{into_js, mirror};
+use crate::ast::{AnalyzerOptions, AstAnalyzerInternal, SymbolFilter, SymbolInfo};
+use crate::content::{discover_files, extract_anchors, DiscoveryConfig, SintesiAnchor};
+use crate::context::workspace_packages;
+use crate::drift::{
+    check_drift, evaluate_policies, now_millis, DriftHistory, DriftResult, PolicySet, SintesiMap,
+    HISTORY_FILE, MAP_FILE,
+};
+use crate::init::CONFIG_FILE;
+use crate::provider::DiskProvider;
+use crate::report::{
+    drift_comment, drift_findings, evaluate, write_drift_junit, AnchorStaleness, Badge,
+    CommitState, Coverage, Evaluation, Finding, FindingSeverity, ForgeConfig, ForgeReporter,
+    GatePolicy, GateReason, HtmlReport, OwnerStaleness, PackageReport, ReporterConfig,
+    ReporterRegistry, ReporterSpec, RunResult, RunStatus, StalenessReport,
+};
+use crate::text::read_text;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options for the HTML report
+#[napi(object)]
+pub struct HtmlReportOptions {
+    /// Report title
+    pub title: Option<String>,
+    /// Base URL for links into the repository (e.g. `https://github.com/acme/api/blob/main`)
+    pub repository_url: Option<String>,
+    /// Add a package overview and one section per workspace package
+    pub group_by_package: Option<bool>,
+}
+
+/// Documentation health of one workspace package (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageSummaryJs {
+    /// Package name
+    pub name: String,
+    /// Package directory relative to the project root (empty for the root)
+    pub path: String,
+    /// Exported symbols documented by a map entry
+    pub documented: u32,
+    /// Exported symbols
+    pub total: u32,
+    /// Coverage as a whole percentage
+    pub coverage: u32,
+    /// Drifted or removed map entries
+    pub drifted: u32,
+    /// Map entries in sync
+    pub in_sync: u32,
+    /// Map entries excluded by ignore directives
+    pub ignored: u32,
+    /// Anchors documenting the package
+    pub anchors: u32,
+}
+
+/// Reporter selection (for NAPI)
+#[napi(object)]
+pub struct ReporterSpecJs {
+    /// `console`, `json`, `sarif`, `html`, or `github`
+    pub kind: String,
+    /// Output file for `json`, `sarif`, and `html`, relative to the project root
+    pub output: Option<String>,
+}
+
+/// A finding to report (for NAPI)
+#[napi(object)]
+pub struct FindingJs {
+    /// Phase that produced the finding (`drift`, `lint`, `policy`, ...)
+    pub phase: String,
+    /// Rule within the phase
+    pub rule: String,
+    /// `error`, `warning`, or `note`
+    pub severity: String,
+    /// Human-readable description
+    pub message: String,
+    /// File the finding is about, relative to the project root
+    pub file: Option<String>,
+    /// Line in `file` (1-based)
+    pub line: Option<u32>,
+}
+
+/// Emit findings through reporters
+///
+/// @param rootPath - Project root; output files are relative to it
+/// @param findings - Findings from any pipeline phase
+/// @param reporters - Reporters to use (default: the `reporters` key of
+///   `sintesi.config.json`, or the console)
+#[napi]
+pub fn emit_findings(
+    root_path: String,
+    findings: Vec<FindingJs>,
+    reporters: Option<Vec<ReporterSpecJs>>,
+) -> Result<()> {
+    let findings = findings
+        .into_iter()
+        .map(|f| {
+            let severity = FindingSeverity::parse(&f.severity).ok_or_else(|| {
+                Error::from_reason(format!("Unknown finding severity: {}", f.severity))
+            })?;
+            let mut finding = Finding::new(f.phase, f.rule, severity, f.message);
+            finding.file = f.file;
+            finding.line = f.line.map(|line| line as usize);
+            Ok(finding)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    emit(Path::new(&root_path), &findings, reporters)
+}
+
+/// Check the project's map for drift and emit drifted entries through
+/// reporters
+///
+/// @param rootPath - Project root containing the map
+/// @param reporters - Reporters to use (default: the `reporters` key of
+///   `sintesi.config.json`, or the console)
+/// @returns Number of drifted or removed entries
+#[napi]
+pub fn report_drift(root_path: String, reporters: Option<Vec<ReporterSpecJs>>) -> Result<u32> {
+    let root = Path::new(&root_path);
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+    let findings = drift_findings(&check_drift(&DiskProvider::new(root), &map));
+    emit(root, &findings, reporters)?;
+    Ok(findings.len() as u32)
+}
+
+fn emit(root: &Path, findings: &[Finding], reporters: Option<Vec<ReporterSpecJs>>) -> Result<()> {
+    let specs = match reporters {
+        Some(reporters) => reporters
+            .into_iter()
+            .map(|r| ReporterSpec {
+                kind: r.kind,
+                output: r.output,
+            })
+            .collect(),
+        None => ReporterConfig::load(root.join(CONFIG_FILE))
+            .map_err(|e| Error::from_reason(e.to_string()))?
+            .specs(),
+    };
+    ReporterRegistry::new()
+        .emit(&specs, root, findings)
+        .map_err(|e| Error::from_reason(e.to_string()))
+}
+
+/// Check the project's map for drift and report it to the CI forge
+///
+/// The forge, token, and repository are read from the environment
+/// (`GITHUB_TOKEN`/`GITHUB_REPOSITORY` or `GITLAB_TOKEN`/`CI_PROJECT_ID`).
+///
+/// @param rootPath - Project root containing the map
+/// @param prNumber - PR number (GitHub) or merge request IID (GitLab) to comment on
+/// @param sha - Commit to set the `sintesi/drift` status on
+/// @returns false if no forge is configured in the environment, true once reported
+#[napi]
+pub fn report_drift_to_forge(
+    root_path: String,
+    pr_number: Option<u32>,
+    sha: Option<String>,
+) -> Result<bool> {
+    let Some(config) = ForgeConfig::from_env() else {
+        return Ok(false);
+    };
+
+    let root = Path::new(&root_path);
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+    let result = check_drift(&DiskProvider::new(root), &map);
+    let reporter = ForgeReporter::new(config);
+
+    if let Some(number) = pr_number {
+        reporter
+            .upsert_pr_comment(u64::from(number), &drift_comment(&result))
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+    }
+
+    if let Some(sha) = sha {
+        let drifted = result.drifted().count();
+        let (state, description) = if drifted == 0 {
+            (CommitState::Success, "Documentation in sync".to_string())
+        } else {
+            (
+                CommitState::Failure,
+                format!("{} documented symbols drifted", drifted),
+            )
+        };
+        reporter
+            .set_commit_status(&sha, state, &description, None)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+    }
+
+    Ok(true)
+}
+
+/// Generate "docs coverage" and "drift" badges
+///
+/// Writes `docs-coverage.svg` and `drift.svg`, each with a shields.io
+/// endpoint JSON file next to it.
+///
+/// @param rootPath - Project root containing the map
+/// @param outputDir - Directory for the badges (default: `.sintesi/badges` under the root)
+/// @returns Paths of the written SVG files
+#[napi]
+pub fn generate_badges(root_path: String, output_dir: Option<String>) -> Result<Vec<String>> {
+    let root = Path::new(&root_path);
+    let output_dir = output_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join(".sintesi/badges"));
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+
+    let filter = SymbolFilter::load(root.join(CONFIG_FILE))
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+    let discovered = discover_files(root, DiscoveryConfig::new());
+    let analysis = AstAnalyzerInternal::with_options(AnalyzerOptions::new().filter(filter))
+        .analyze_files(root, &discovered.source_files);
+    let provider = DiskProvider::new(root);
+    let coverage = Coverage::compute_resolved(&map, &analysis.symbols, &provider);
+    let drift = check_drift(&provider, &map);
+
+    let mut written = Vec::new();
+    for (name, badge) in [
+        ("docs-coverage.svg", Badge::coverage(&coverage)),
+        ("drift.svg", Badge::drift(&drift)),
+    ] {
+        let path = output_dir.join(name);
+        badge
+            .write(&path)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+/// Generate a self-contained HTML report of drift, coverage, and anchors
+///
+/// @param rootPath - Project root containing the map
+/// @param outputPath - Where to write the HTML file
+/// @param options - Title and repository link base
+#[napi]
+pub fn generate_html_report(
+    root_path: String,
+    output_path: String,
+    options: Option<HtmlReportOptions>,
+) -> Result<()> {
+    let root = Path::new(&root_path);
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+
+    let (symbols, drift, anchors) = collect_report_inputs(root, &map)?;
+    let provider = DiskProvider::new(root);
+    let coverage = Coverage::compute_resolved(&map, &symbols, &provider);
+
+    let packages = match options.as_ref().and_then(|o| o.group_by_package) {
+        Some(true) => PackageReport::split(
+            &workspace_packages(root),
+            &map,
+            &symbols,
+            &drift,
+            &anchors,
+            &provider,
+        ),
+        _ => Vec::new(),
+    };
+    let mut report = HtmlReport::new(drift, coverage, anchors).packages(packages);
+    if let Some(options) = options {
+        if let Some(title) = options.title {
+            report = report.title(title);
+        }
+        if let Some(url) = options.repository_url {
+            report = report.repository_url(url);
+        }
+    }
+    report
+        .write(&output_path)
+        .map_err(|e| Error::from_reason(e.to_string()))
+}
+
+/// Summarize drift, coverage, and anchors per workspace package
+///
+/// Packages are the directories with a package.json or a Cargo.toml
+/// declaring `[package]`; files outside every package are summarized under
+/// `(root)`. Packages with nothing to document are left out.
+///
+/// @param rootPath - Project root containing the map
+/// @returns One summary per package, sorted by path
+#[napi]
+pub fn package_report(root_path: String) -> Result<Vec<PackageSummaryJs>> {
+    let root = Path::new(&root_path);
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+    let (symbols, drift, anchors) = collect_report_inputs(root, &map)?;
+
+    Ok(PackageReport::split(
+        &workspace_packages(root),
+        &map,
+        &symbols,
+        &drift,
+        &anchors,
+        &DiskProvider::new(root),
+    )
+    .into_iter()
+    .map(|report| {
+        let drifted = report.drift.drifted().count();
+        let ignored = report.drift.ignored().count();
+        PackageSummaryJs {
+            coverage: report.coverage.percent(),
+            name: report.package.name,
+            path: report.package.path,
+            documented: report.coverage.documented as u32,
+            total: report.coverage.total as u32,
+            drifted: drifted as u32,
+            in_sync: (report.drift.entries.len() - drifted - ignored) as u32,
+            ignored: ignored as u32,
+            anchors: report.anchors.len() as u32,
+        }
+    })
+    .collect())
+}
+
+/// Analyzed symbols, drift, and anchors of a project, as reported
+fn collect_report_inputs(
+    root: &Path,
+    map: &SintesiMap,
+) -> Result<(Vec<SymbolInfo>, DriftResult, Vec<SintesiAnchor>)> {
+    let filter = SymbolFilter::load(root.join(CONFIG_FILE))
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+    let discovered = discover_files(root, DiscoveryConfig::new());
+    let analysis = AstAnalyzerInternal::with_options(AnalyzerOptions::new().filter(filter))
+        .analyze_files(root, &discovered.source_files);
+    let drift = check_drift(&DiskProvider::new(root), map);
+
+    let mut anchors = Vec::new();
+    for path in &discovered.markdown_files {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        if let Ok(content) = read_text(path) {
+            anchors.extend(extract_anchors(relative, &content).anchors.into_values());
+        }
+    }
+    anchors.sort_by(|a, b| (&a.file_path, a.start_line).cmp(&(&b.file_path, b.start_line)));
+    Ok((analysis.symbols, drift, anchors))
+}
+
+/// Check the project's map for drift and write the result as JUnit XML
+///
+/// @param rootPath - Project root containing the map
+/// @param outputPath - Where to write the XML file
+/// @returns Number of failing (drifted or removed) entries
+#[napi]
+pub fn write_drift_junit_report(root_path: String, output_path: String) -> Result<u32> {
+    let root = Path::new(&root_path);
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+    let result = check_drift(&DiskProvider::new(root), &map);
+
+    write_drift_junit(&result, &output_path).map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(result.drifted().count() as u32)
+}
+
+/// Gate settings overriding the `gate` key of `sintesi.config.json`
+#[napi(object)]
+pub struct GatePolicyJs {
+    /// Status when entries drifted: `pass`, `warn`, or `fail`
+    pub drift: Option<String>,
+    /// Status for non-breaking drift, leaving `drift` for breaking drift
+    pub non_breaking_drift: Option<String>,
+    /// Status when there are validation errors: `pass`, `warn`, or `fail`
+    pub validation: Option<String>,
+    /// Fail below this coverage percentage
+    pub min_coverage: Option<u32>,
+    /// Warn below this coverage percentage
+    pub warn_coverage: Option<u32>,
+}
+
+/// Why a run did not pass (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GateReasonJs {
+    /// `warn` or `fail`
+    pub status: String,
+    /// `drift`, `validation`, `coverage`, or `policy`
+    pub check: String,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+mirror! {
+    GateReason { status, check, message } => GateReasonJs { status, check, message }
+}
+
+/// Final status of a run (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvaluationJs {
+    /// `pass`, `warn`, or `fail`
+    pub status: String,
+    /// Process exit code for the status (1 for `fail`, 0 otherwise)
+    pub exit_code: i32,
+    /// Reasons for a status other than `pass`
+    pub reasons: Vec<GateReasonJs>,
+}
+
+mirror! {
+    Evaluation { status, reasons } => EvaluationJs {
+        status,
+        exit_code: status.exit_code(),
+        reasons,
+    }
+}
+
+/// Check the project and compute its CI status
+///
+/// Combines drift, anchor and configuration errors, documentation coverage,
+/// and freshness policy violations (from `sintesi.config.json`) into one
+/// pass/warn/fail status.
+///
+/// @param rootPath - Project root containing the map
+/// @param policy - Overrides for the configured gate
+/// @returns The status, its exit code, and the reasons behind it
+#[napi]
+pub fn evaluate_run(root_path: String, policy: Option<GatePolicyJs>) -> Result<EvaluationJs> {
+    let root = Path::new(&root_path);
+    let config_path = root.join(CONFIG_FILE);
+    let mut gate = GatePolicy::load(&config_path).map_err(|e| Error::from_reason(e.to_string()))?;
+    if let Some(policy) = policy {
+        if let Some(status) = policy.drift {
+            gate.drift = parse_status(&status)?;
+        }
+        if let Some(status) = policy.non_breaking_drift {
+            gate.non_breaking_drift = Some(parse_status(&status)?);
+        }
+        if let Some(status) = policy.validation {
+            gate.validation = parse_status(&status)?;
+        }
+        gate.min_coverage = policy.min_coverage.or(gate.min_coverage);
+        gate.warn_coverage = policy.warn_coverage.or(gate.warn_coverage);
+    }
+
+    let mut run = RunResult::default();
+    let map = SintesiMap::load(root.join(MAP_FILE)).unwrap_or_else(|e| {
+        run.validation_errors.push(e.to_string());
+        SintesiMap::new()
+    });
+    let provider = DiskProvider::new(root);
+    run.drift = check_drift(&provider, &map);
+
+    let discovered = discover_files(root, DiscoveryConfig::new());
+    let analysis = AstAnalyzerInternal::new().analyze_files(root, &discovered.source_files);
+    run.coverage = Some(Coverage::compute_resolved(
+        &map,
+        &analysis.symbols,
+        &provider,
+    ));
+    for path in &discovered.markdown_files {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        if let Ok(content) = read_text(path) {
+            run.validation_errors
+                .extend(extract_anchors(relative, &content).errors);
+        }
+    }
+
+    let has_policies = fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|config| config.get("policies").is_some());
+    if has_policies {
+        let violations = PolicySet::load(&config_path).and_then(|set| {
+            let history = DriftHistory::load(root.join(HISTORY_FILE))?;
+            evaluate_policies(&set.policies, &map, &history, now_millis())
+        });
+        match violations {
+            Ok(violations) => run.policy_violations = violations,
+            Err(e) => run.validation_errors.push(e.to_string()),
+        }
+    }
+
+    Ok(evaluate(&run, &gate).into())
+}
+
+/// Staleness of one anchor (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorStalenessJs {
+    /// Anchor ID of the map entry
+    pub entry_id: String,
+    /// Documentation file containing the anchor
+    pub doc_file: String,
+    /// Code reference the anchor documents
+    pub code_ref: String,
+    /// Author of most of the anchor's lines
+    pub owner: String,
+    /// Latest commit time of the anchor's lines (milliseconds since Unix epoch)
+    pub last_edited: Option<i64>,
+    /// Last sync of the map entry (milliseconds since Unix epoch)
+    pub last_updated: i64,
+    /// Days since the anchor was last edited or synced
+    pub days: u32,
+}
+
+mirror! {
+    AnchorStaleness {
+        entry_id,
+        doc_file,
+        code_ref,
+        owner,
+        last_edited,
+        last_updated,
+        days,
+    } => AnchorStalenessJs {
+        entry_id,
+        doc_file,
+        code_ref,
+        owner,
+        last_edited,
+        last_updated,
+        days,
+    }
+}
+
+/// Staleness of the anchors owned by one author (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerStalenessJs {
+    /// Author name
+    pub owner: String,
+    /// Number of anchors owned
+    pub anchors: u32,
+    /// Sum of the anchors' staleness in days
+    pub total_days: u32,
+    /// Average staleness in days
+    pub average_days: u32,
+    /// Staleness of the stalest anchor
+    pub max_days: u32,
+}
+
+// The average is computed by `OwnerStaleness`, so it is converted by hand,
+// still destructuring every field
+impl From<OwnerStaleness> for OwnerStalenessJs {
+    fn from(staleness: OwnerStaleness) -> Self {
+        let average_days = staleness.average_days();
+        let OwnerStaleness {
+            owner,
+            anchors,
+            total_days,
+            max_days,
+        } = staleness;
+        Self {
+            owner,
+            anchors: anchors as u32,
+            total_days: total_days.min(u64::from(u32::MAX)) as u32,
+            average_days,
+            max_days,
+        }
+    }
+}
+
+into_js!(OwnerStaleness => OwnerStalenessJs);
+
+/// Staleness per anchor and per owner (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StalenessReportJs {
+    /// Anchors, stalest first
+    pub anchors: Vec<AnchorStalenessJs>,
+    /// Owners, by total staleness (largest first)
+    pub owners: Vec<OwnerStalenessJs>,
+}
+
+mirror! {
+    StalenessReport { anchors, owners } => StalenessReportJs {
+        anchors,
+        owners,
+    }
+}
+
+/// Compute how stale each anchor is and who owns it
+///
+/// Staleness is the number of days since an anchor was last synced or
+/// edited; owners come from `git blame` on the anchor's lines.
+///
+/// @param rootPath - Project root containing the map, inside a git repository
+/// @returns Anchors (stalest first) and owners (by total staleness)
+#[napi]
+pub fn staleness_report(root_path: String) -> Result<StalenessReportJs> {
+    let root = Path::new(&root_path);
+    let map =
+        SintesiMap::load(root.join(MAP_FILE)).map_err(|e| Error::from_reason(e.to_string()))?;
+    let report = StalenessReport::compute(root, &map, now_millis())
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+
+    Ok(report.into())
+}
+
+fn parse_status(status: &str) -> Result<RunStatus> {
+    match status {
+        "pass" => Ok(RunStatus::Pass),
+        "warn" => Ok(RunStatus::Warn),
+        "fail" => Ok(RunStatus::Fail),
+        other => Err(Error::from_reason(format!(
+            "Unknown gate status: {}",
+            other
+        ))),
+    }
+}
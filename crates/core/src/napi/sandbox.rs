@@ -0,0 +1,25 @@
+//! Write sandbox NAPI bindings
+//!
+//! Lets a Node.js host confine every write of the process to a project
+//! before working on untrusted repository content (see `crate::sandbox`).
+
+use crate::sandbox::{self, WriteSandbox};
+use napi_derive::napi;
+
+/// Confine writes to a project root and its `writeAllowlist`
+///
+/// Saving the map, drift history, or analysis cache, anchor injection, and
+/// report files outside the root (or the config's `writeAllowlist`) then
+/// fail with a "Path not allowed: ..." error. Without a call the
+/// `SINTESI_WRITE_ROOT` environment variable names the root, if set.
+///
+/// @param rootPath - Project root, or null to remove the sandbox
+#[napi]
+pub fn set_write_root(root_path: Option<String>) -> napi::Result<()> {
+    let sandbox = root_path
+        .map(WriteSandbox::load)
+        .transpose()
+        .map_err(|e| napi::Error::from_reason(e.to_string()))?;
+    sandbox::install(sandbox);
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! Search NAPI bindings
+//!
+//! Node.js bindings for text search across project files.
+
+use super::convert::mirror;
+use crate::provider::DiskProvider;
+use crate::search::{self, SearchOptions, SearchPage, SearchResult};
+use crate::text::SkippedFile;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Options for `searchProject`
+#[napi(object)]
+pub struct SearchOptionsJs {
+    /// Match letters regardless of case
+    pub case_insensitive: Option<bool>,
+    /// Match against whole files so patterns can span lines
+    pub multiline: Option<bool>,
+    /// Treat the pattern as a literal string rather than a regular expression
+    pub fixed_string: Option<bool>,
+    /// Maximum number of results per page (unlimited if omitted)
+    pub limit: Option<u32>,
+    /// `nextCursor` of the previous page, to continue after it
+    pub cursor: Option<String>,
+    /// Give up after this many milliseconds
+    pub timeout_ms: Option<u32>,
+    /// Also search generated and minified files
+    pub include_generated: Option<bool>,
+}
+
+/// A single search match (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResultJs {
+    /// File containing the match, relative to the project root
+    pub file_path: String,
+    /// Line the match starts on (1-based)
+    pub line: u32,
+    /// Line the match ends on (1-based)
+    pub end_line: u32,
+    /// Byte offset of the match in the file
+    pub byte_offset: u32,
+    /// Byte offset of the match start within `lineText`
+    pub column_start: u32,
+    /// Byte offset just past the match end within `lineText`
+    pub column_end: u32,
+    /// The matched line(s), untrimmed, without the final line terminator
+    pub line_text: String,
+}
+
+/// One page of search results (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPageJs {
+    /// Matches, sorted by path, then position
+    pub results: Vec<SearchResultJs>,
+    /// Cursor for the next page, if the limit cut the results short
+    pub next_cursor: Option<String>,
+    /// Files searched for this page that are not text, or are generated or
+    /// minified
+    pub skipped: Vec<SkippedFileJs>,
+}
+
+/// A file left out because it is not text, too large, or generated (for NAPI)
+#[napi(object)]
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedFileJs {
+    /// Path of the file, relative to the project root
+    pub file_path: String,
+    /// `binary`, `undecodable` (neither UTF-8 nor UTF-16), `too-large`,
+    /// `generated`, or `minified`
+    pub reason: String,
+}
+
+mirror! {
+    SearchResult {
+        file_path,
+        line,
+        end_line,
+        byte_offset,
+        column_start,
+        column_end,
+        line_text,
+    } => SearchResultJs {
+        file_path,
+        line,
+        end_line,
+        byte_offset,
+        column_start,
+        column_end,
+        line_text,
+    }
+}
+
+mirror! {
+    SearchPage { results, next_cursor, skipped } => SearchPageJs { results, next_cursor, skipped }
+}
+
+mirror! {
+    SkippedFile { file_path, reason } => SkippedFileJs { file_path, reason }
+}
+
+/// Search the project's files (honoring `.gitignore`) for a pattern
+///
+/// @param rootPath - Project root
+/// @param pattern - Regular expression (matched within single lines unless
+///   `multiline` is set), or a literal string with `fixedString`
+/// @param options - Case sensitivity, matching mode, paging, and timeout
+/// @returns A page of matches sorted by path and position, with byte offsets
+///   and match ranges
+#[napi]
+pub fn search_project(
+    root_path: String,
+    pattern: String,
+    options: Option<SearchOptionsJs>,
+) -> Result<SearchPageJs> {
+    let page = search::search_project(
+        &DiskProvider::new(root_path),
+        &pattern,
+        &to_search_options(options),
+    )
+    .map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(page.into())
+}
+
+pub(crate) fn to_search_options(options: Option<SearchOptionsJs>) -> SearchOptions {
+    let mut search_options = SearchOptions::new();
+    if let Some(options) = options {
+        search_options = search_options
+            .case_insensitive(options.case_insensitive.unwrap_or(false))
+            .multiline(options.multiline.unwrap_or(false))
+            .fixed_string(options.fixed_string.unwrap_or(false))
+            .include_generated(options.include_generated.unwrap_or(false));
+        if let Some(limit) = options.limit {
+            search_options = search_options.limit(limit as usize);
+        }
+        if let Some(cursor) = options.cursor {
+            search_options = search_options.cursor(cursor);
+        }
+        if let Some(ms) = options.timeout_ms {
+            search_options = search_options.timeout(Duration::from_millis(ms as u64));
+        }
+    }
+    search_options
+}
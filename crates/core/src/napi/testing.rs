@@ -0,0 +1,91 @@
+//! Test support NAPI bindings
+//!
+//! Node.js bindings for `testing::TestProject`, built with the `test-support`
+//! feature so JavaScript test suites can run Sintesi against throwaway
+//! projects.
+
+use super::pipeline::{to_config, to_js, PipelineOptions, PipelineRunJs};
+use crate::testing::{self, TestProject};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::fs;
+
+/// A project in a temporary directory, removed when garbage collected
+#[napi(js_name = "TestProject")]
+pub struct TestProjectJs {
+    inner: TestProject,
+}
+
+#[napi]
+impl TestProjectJs {
+    /// Create a project from file paths and contents
+    #[napi(constructor)]
+    pub fn new(files: Option<HashMap<String, String>>) -> Result<Self> {
+        let project = Self {
+            inner: TestProject::new(),
+        };
+        for (path, content) in files.unwrap_or_default() {
+            project.write(path, content)?;
+        }
+        Ok(project)
+    }
+
+    /// Directory of the project
+    #[napi(getter)]
+    pub fn root(&self) -> String {
+        self.inner.root().to_string_lossy().to_string()
+    }
+
+    /// Write a file, creating its directories
+    #[napi]
+    pub fn write(&self, path: String, content: String) -> Result<()> {
+        let path = self.inner.root().join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::from_reason(e.to_string()))?;
+        }
+        fs::write(&path, content).map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Remove a file
+    #[napi]
+    pub fn remove(&self, path: String) -> Result<()> {
+        fs::remove_file(self.inner.root().join(path)).map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Content of a file
+    #[napi]
+    pub fn read(&self, path: String) -> Result<String> {
+        fs::read_to_string(self.inner.root().join(path))
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Link every anchor with a `code_ref` to the current signature of its
+    /// symbol, so the project starts documented and in sync
+    #[napi]
+    pub fn sync(&self) -> Result<()> {
+        self.inner
+            .link_anchors()
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Run the pipeline (a dry run unless `dryRun` is false)
+    #[napi]
+    pub fn run(&self, options: Option<PipelineOptions>) -> Result<PipelineRunJs> {
+        let run = self
+            .inner
+            .run_with(&to_config(options)?)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        Ok(to_js(self.inner.root(), run))
+    }
+
+    /// Run the pipeline and render the run as stable text, for snapshot tests
+    #[napi]
+    pub fn snapshot(&self, options: Option<PipelineOptions>) -> Result<String> {
+        let run = self
+            .inner
+            .run_with(&to_config(options)?)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        Ok(testing::snapshot(&run))
+    }
+}
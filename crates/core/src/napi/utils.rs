@@ -2,6 +2,7 @@
 //!
 //! Simple utility functions exposed to Node.js for testing and version info.
 
+use crate::schema::result_schemas;
 use napi_derive::napi;
 
 /// Simple hello world function to test the napi binding
@@ -15,3 +16,20 @@ pub fn hello_world() -> String {
 pub fn get_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
+
+/// Get the JSON Schemas of all result types
+///
+/// @returns JSON object mapping type name (e.g. "DriftEntry") to its schema
+#[napi]
+pub fn get_result_schemas() -> String {
+    let schemas: serde_json::Map<String, serde_json::Value> = result_schemas()
+        .into_iter()
+        .map(|(name, schema)| {
+            (
+                name.to_string(),
+                serde_json::to_value(schema).unwrap_or_default(),
+            )
+        })
+        .collect();
+    serde_json::Value::Object(schemas).to_string()
+}
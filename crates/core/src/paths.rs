@@ -0,0 +1,186 @@
+//! Windows long and UNC paths
+//!
+//! Windows file APIs reject paths longer than `MAX_PATH` (260 characters)
+//! unless they are in extended-length form (`\\?\C:\...`, or
+//! `\\?\UNC\server\share\...` for network shares), and deep `node_modules`
+//! trees easily go past it. Conversely `canonicalize` returns extended-length
+//! paths, which then fail to compare with, or strip as a prefix of, ordinary
+//! ones.
+//!
+//! `extended_length` converts paths right before they reach the file system
+//! (walk roots, reads, writes), and `canonical`/`strip_verbatim` turn
+//! canonicalized paths back into ordinary ones, so paths shown to users and
+//! recorded in the map never carry the prefix. They leave paths of other
+//! platforms unchanged.
+
+use std::path::{Path, PathBuf};
+
+/// Prefix of extended-length paths
+const VERBATIM: &str = r"\\?\";
+/// Prefix of extended-length UNC paths
+const VERBATIM_UNC: &str = r"\\?\UNC\";
+
+/// Path usable by Windows file APIs whatever its length
+///
+/// Every path is converted, not just long ones: walking a short root can
+/// still reach long paths below it. Relative paths are made absolute first.
+/// Elsewhere than on Windows the path is returned unchanged.
+pub fn extended_length(path: &Path) -> PathBuf {
+    if !cfg!(windows) {
+        return path.to_path_buf();
+    }
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    match absolute.to_str().and_then(to_extended) {
+        Some(extended) => PathBuf::from(extended),
+        None => absolute,
+    }
+}
+
+/// Canonical form of a path, without the extended-length prefix
+/// `canonicalize` adds on Windows; the path itself if it cannot be resolved
+pub fn canonical(path: &Path) -> PathBuf {
+    match path.canonicalize() {
+        Ok(canonical) => strip_verbatim(&canonical),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Ordinary form of an extended-length path (`\\?\C:\a` is `C:\a`,
+/// `\\?\UNC\server\share\a` is `\\server\share\a`); other paths are returned
+/// unchanged
+pub fn strip_verbatim(path: &Path) -> PathBuf {
+    let Some(text) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    if let Some(unc) = text.strip_prefix(VERBATIM_UNC) {
+        return PathBuf::from(format!(r"\\{}", unc));
+    }
+    match text.strip_prefix(VERBATIM) {
+        Some(rest) if is_drive_absolute(rest) => PathBuf::from(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Extended-length form of an absolute Windows path
+///
+/// Separators are made backslashes and `.`/`..` resolved, as Windows does
+/// not normalize extended-length paths. `None` for paths already in that
+/// form, device paths (`\\.\`), and relative paths.
+fn to_extended(path: &str) -> Option<String> {
+    if path.starts_with(VERBATIM) || path.starts_with(r"\\.\") {
+        return None;
+    }
+    let path = path.replace('/', "\\");
+    let (prefix, rest, root_parts) = if let Some(unc) = path.strip_prefix(r"\\") {
+        // The server and share cannot be left with `..`
+        (VERBATIM_UNC, unc, 2)
+    } else if is_drive_absolute(&path) {
+        (VERBATIM, path.as_str(), 1)
+    } else {
+        return None;
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in rest.split('\\') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if parts.len() > root_parts {
+                    parts.pop();
+                }
+            }
+            part => parts.push(part),
+        }
+    }
+    if parts.len() < root_parts {
+        return None;
+    }
+    let mut extended = format!("{}{}", prefix, parts.join("\\"));
+    if parts.len() == root_parts && prefix == VERBATIM {
+        // `\\?\C:` is the drive's current directory, not its root
+        extended.push('\\');
+    }
+    Some(extended)
+}
+
+/// Whether a path starts with a drive letter and a separator (`C:\`, `C:/`)
+fn is_drive_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_extended() {
+        let deep = format!(r"C:\repo\{}\index.js", ["node_modules"; 30].join(r"\"));
+        assert_eq!(
+            to_extended(&deep).as_deref(),
+            Some(format!(r"\\?\{}", deep).as_str())
+        );
+        assert_eq!(
+            to_extended("C:/repo/./docs/../src/a.ts").as_deref(),
+            Some(r"\\?\C:\repo\src\a.ts")
+        );
+        assert_eq!(
+            to_extended(r"\\server\share\docs\a.md").as_deref(),
+            Some(r"\\?\UNC\server\share\docs\a.md")
+        );
+        // `..` stops at the share and at the drive root
+        assert_eq!(
+            to_extended(r"\\server\share\..\..\a.md").as_deref(),
+            Some(r"\\?\UNC\server\share\a.md")
+        );
+        assert_eq!(to_extended(r"C:\..").as_deref(), Some(r"\\?\C:\"));
+
+        assert_eq!(to_extended(r"\\?\C:\repo"), None);
+        assert_eq!(to_extended(r"\\.\pipe\sintesi"), None);
+        assert_eq!(to_extended(r"docs\a.md"), None);
+        assert_eq!(to_extended(r"C:docs"), None);
+        assert_eq!(to_extended(r"\\server"), None);
+    }
+
+    #[test]
+    fn test_strip_verbatim() {
+        assert_eq!(
+            strip_verbatim(Path::new(r"\\?\C:\repo\src")),
+            Path::new(r"C:\repo\src")
+        );
+        assert_eq!(
+            strip_verbatim(Path::new(r"\\?\UNC\server\share\repo")),
+            Path::new(r"\\server\share\repo")
+        );
+        // Verbatim paths without an ordinary form are kept
+        assert_eq!(
+            strip_verbatim(Path::new(r"\\?\Volume{1234}\repo")),
+            Path::new(r"\\?\Volume{1234}\repo")
+        );
+        assert_eq!(
+            strip_verbatim(Path::new("/repo/src")),
+            Path::new("/repo/src")
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_unchanged_elsewhere() {
+        let deep = PathBuf::from(format!("/repo/{}/index.js", ["node_modules"; 30].join("/")));
+        assert_eq!(extended_length(&deep), deep);
+        assert_eq!(extended_length(Path::new("docs")), Path::new("docs"));
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_extended_length() {
+        let path = extended_length(Path::new(r"C:\repo\docs"));
+        assert_eq!(path, Path::new(r"\\?\C:\repo\docs"));
+        assert_eq!(strip_verbatim(&path), Path::new(r"C:\repo\docs"));
+        let relative = extended_length(Path::new("docs"));
+        assert!(relative.to_str().unwrap().starts_with(VERBATIM));
+    }
+}
@@ -0,0 +1,138 @@
+//! Per-phase resource limits
+//!
+//! A limited phase runs on a worker thread while the orchestrator waits for
+//! it, polling the clock and the process's resident memory. When a limit is
+//! hit the run fails with `Error::Timeout` or `Error::MemoryLimit` and the
+//! worker is abandoned: it finishes in the background and its results are
+//! dropped. Limited work does not write files itself (the orchestrator saves
+//! the analysis cache once `Analyze` is done), so abandoned work cannot race
+//! a retry. `Apply` is never interrupted, so files are not left half-written;
+//! its limits are checked once it is done.
+//!
+//! Resident memory is read from `/proc/self/status`, so memory limits only
+//! apply on Linux.
+
+use crate::error::Error;
+use std::fs;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a limited phase's clock and memory are checked
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Wall-clock and memory limits of a pipeline phase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseLimits {
+    /// Longest the phase may take, hooks included
+    pub timeout: Option<Duration>,
+    /// Most resident memory the process may use during the phase, in bytes
+    pub max_memory: Option<u64>,
+}
+
+impl PhaseLimits {
+    /// No limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail the phase after `timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fail the phase when resident memory goes over `bytes`
+    pub fn max_memory(mut self, bytes: u64) -> Self {
+        self.max_memory = Some(bytes);
+        self
+    }
+
+    /// Fail if the operation started longer ago than the timeout, or memory
+    /// is over the limit
+    pub(crate) fn check(&self, operation: &str, started: Instant) -> Result<(), Error> {
+        if let Some(timeout) = self.timeout.filter(|t| started.elapsed() > *t) {
+            return Err(Error::Timeout {
+                operation: operation.to_string(),
+                limit: timeout,
+            });
+        }
+        if let Some(limit) = self.max_memory {
+            if resident_memory().is_some_and(|used| used > limit) {
+                return Err(Error::MemoryLimit {
+                    operation: operation.to_string(),
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Run `work` on a worker thread, failing as soon as a limit is hit
+    ///
+    /// Time is counted from `started`, so time spent before (in hooks)
+    /// counts against the timeout.
+    pub(crate) fn run<T: Send + 'static>(
+        &self,
+        operation: &str,
+        started: Instant,
+        work: impl FnOnce() -> T + Send + 'static,
+    ) -> Result<T, Error> {
+        self.check(operation, started)?;
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name(format!("sintesi {}", operation))
+            .spawn(move || {
+                // The receiver is gone if a limit was hit in the meantime
+                let _ = sender.send(work());
+            })
+            .map_err(|e| Error::from_reason(format!("Failed to start {}: {}", operation, e)))?;
+
+        loop {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(value) => return Ok(value),
+                Err(RecvTimeoutError::Timeout) => self.check(operation, started)?,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(Error::from_reason(format!("{} panicked", operation)))
+                }
+            }
+        }
+    }
+}
+
+/// Resident memory of the process in bytes, where the platform reports it
+pub fn resident_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits() {
+        let limits = PhaseLimits::new().timeout(Duration::from_millis(20));
+        let error = limits
+            .run("analyze phase", Instant::now(), || {
+                thread::sleep(Duration::from_secs(2))
+            })
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Timeout: analyze phase exceeded 20ms");
+
+        assert_eq!(limits.run("plan phase", Instant::now(), || 42).unwrap(), 42);
+        assert!(PhaseLimits::new()
+            .run("plan phase", Instant::now(), || panic!("boom"))
+            .is_err());
+
+        if resident_memory().is_some() {
+            let error = PhaseLimits::new()
+                .max_memory(1)
+                .check("analyze phase", Instant::now())
+                .unwrap_err();
+            assert!(matches!(error, Error::MemoryLimit { limit: 1, .. }));
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! End-to-end pipeline
+//!
+//! `run_pipeline` runs the whole documentation flow natively, in order:
+//!
+//! 1. discover source and markdown files
+//! 2. analyze the sources (skipping files unchanged since the last run, see
+//!    `ast::cache`) and extract the anchors
+//! 3. detect drift of the map against the current code
+//! 4. generate content for drifted anchors
+//! 5. build the plan and evaluate the CI gate
+//!
+//! A dry run (the default) mocks generation with a placeholder showing the
+//! current signature and writes nothing: no files, no map, no drift history,
+//! and no network calls. It previews what a real run would do, and lets tests
+//! cover the whole flow. Otherwise generated content comes from the caller
+//! and the plan is applied.
+//!
+//! Each step is a `Phase` of an `Orchestrator`, which calls `PipelineHooks`
+//! before and after every phase so callers can observe the run, supply
+//! generated content, or stop it. Each phase can be given `PhaseLimits` so a
+//! pathological file or a hung hook cannot stall CI indefinitely.
+
+mod limits;
+mod orchestrator;
+
+pub use limits::{resident_memory, PhaseLimits};
+pub use orchestrator::{Orchestrator, Phase, PipelineHooks};
+
+use crate::apply::{ApplyResult, Plan};
+use crate::ast::AnalysisResult;
+use crate::content::{DiscoveryStats, SintesiAnchor};
+use crate::drift::DriftResult;
+use crate::error::Error;
+use crate::report::{Coverage, Evaluation};
+use crate::text::{SkippedFile, DEFAULT_MAX_FILE_SIZE};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Pipeline settings
+#[derive(Debug, Clone)]
+pub struct PipelineConfig {
+    /// Mock generation and write nothing (default `true`)
+    pub dry_run: bool,
+    /// Regenerated anchor content by anchor ID, used instead of the mock
+    pub generated: BTreeMap<String, String>,
+    /// Commit the applied changes with this message
+    pub commit_message: Option<String>,
+    /// Time and memory limits by phase (none by default)
+    pub limits: HashMap<Phase, PhaseLimits>,
+    /// Size in bytes above which source and markdown files are skipped
+    /// (default `DEFAULT_MAX_FILE_SIZE`)
+    pub max_file_size: usize,
+    /// Also analyze source files detected as generated or minified
+    pub include_generated: bool,
+    /// Attribute symbols of generated source files with a source map to
+    /// their original files (see `ast::sourcemap`)
+    pub source_maps: bool,
+    /// Shard the analysis cache by workspace package, under `AST_CACHE_DIR`
+    /// instead of `AST_CACHE_FILE`
+    pub shard_cache: bool,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            dry_run: true,
+            generated: BTreeMap::new(),
+            commit_message: None,
+            limits: HashMap::new(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            include_generated: false,
+            source_maps: false,
+            shard_cache: false,
+        }
+    }
+}
+
+impl PipelineConfig {
+    /// Create a dry-run configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable the dry run
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Use this regenerated content for the given anchors
+    pub fn generated(mut self, generated: BTreeMap<String, String>) -> Self {
+        self.generated = generated;
+        self
+    }
+
+    /// Commit the applied changes with the given message
+    pub fn commit_message(mut self, message: impl Into<String>) -> Self {
+        self.commit_message = Some(message.into());
+        self
+    }
+
+    /// Limit the time and memory a phase may use
+    pub fn limit(mut self, phase: Phase, limits: PhaseLimits) -> Self {
+        self.limits.insert(phase, limits);
+        self
+    }
+
+    /// Skip source and markdown files larger than `bytes`
+    pub fn max_file_size(mut self, bytes: usize) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Analyze generated and minified source files too
+    pub fn include_generated(mut self, include: bool) -> Self {
+        self.include_generated = include;
+        self
+    }
+
+    /// Attribute symbols of generated files to their original sources
+    pub fn source_maps(mut self, enabled: bool) -> Self {
+        self.source_maps = enabled;
+        self
+    }
+
+    /// Keep one analysis cache file per workspace package, each loaded only
+    /// when one of its files is analyzed
+    pub fn shard_cache(mut self, enabled: bool) -> Self {
+        self.shard_cache = enabled;
+        self
+    }
+}
+
+/// Everything a pipeline run found and planned
+#[derive(Debug, Clone, Default)]
+pub struct PipelineRun {
+    /// Whether generation was mocked and nothing written
+    pub dry_run: bool,
+    /// Discovered files
+    pub discovery: DiscoveryStats,
+    /// Analyzed symbols of the source files
+    pub analysis: AnalysisResult,
+    /// Anchors of the markdown files
+    pub anchors: Vec<SintesiAnchor>,
+    /// Anchor extraction errors
+    pub validation_errors: Vec<String>,
+    /// Source and markdown files left out because they are not text or are
+    /// over the size limit, and source files detected as generated
+    pub skipped: Vec<SkippedFile>,
+    /// Documentation coverage of exported symbols
+    pub coverage: Coverage,
+    /// Drift of the map entries
+    pub drift: DriftResult,
+    /// Content for drifted anchors by anchor ID (mocked in a dry run)
+    pub generated: BTreeMap<String, String>,
+    /// The plan bringing the documentation back in sync
+    pub plan: Plan,
+    /// CI gate status of the run
+    pub evaluation: Evaluation,
+    /// Outcome of applying the plan (`None` in a dry run)
+    pub applied: Option<ApplyResult>,
+}
+
+/// Run the documentation pipeline on a project
+///
+/// Shorthand for an `Orchestrator` without hooks.
+///
+/// # Arguments
+/// * `root` - Project root containing the map
+/// * `config` - Pipeline settings
+///
+/// # Returns
+/// The reports and plan of the run, or an error if the map or configuration
+/// cannot be loaded or a phase fails
+pub fn run_pipeline(root: impl AsRef<Path>, config: &PipelineConfig) -> Result<PipelineRun, Error> {
+    Orchestrator::new(root, config.clone()).run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{AstCache, AST_CACHE_FILE};
+    use crate::drift::{SintesiMap, SintesiMapEntry, MAP_FILE};
+    use crate::SignatureHasher;
+    use std::fs;
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("src/auth.ts"),
+            "export function login(user: string, remember: boolean): void {}\nexport function logout(): void {}\n",
+        )
+        .unwrap();
+        let doc = "# Auth\n\n<!-- sintesi:start id=\"login\" code_ref=\"src/auth.ts#login\" -->\nOld docs\n<!-- sintesi:end id=\"login\" -->\n";
+        fs::write(root.join("docs/auth.md"), doc).unwrap();
+        let map = SintesiMap {
+            entries: vec![SintesiMapEntry {
+                id: "login".to_string(),
+                code_ref: "src/auth.ts#login".to_string(),
+                doc_file: "docs/auth.md".to_string(),
+                code_signature_hash: SignatureHasher::new().hash_text("stale"),
+                last_updated: 0,
+                symbol_id: None,
+                symbol_key: None,
+                deprecated: false,
+                content_hash: None,
+                code_signature: None,
+            }],
+            ..SintesiMap::new()
+        };
+        map.save(root.join(MAP_FILE)).unwrap();
+        let map_json = fs::read_to_string(root.join(MAP_FILE)).unwrap();
+
+        let run = run_pipeline(root, &PipelineConfig::new()).unwrap();
+
+        assert!(run.dry_run && run.applied.is_none());
+        assert_eq!(
+            (run.discovery.source_files, run.discovery.markdown_files),
+            (1, 1)
+        );
+        assert_eq!(
+            run.coverage,
+            Coverage {
+                documented: 1,
+                total: 2
+            }
+        );
+        assert_eq!(run.drift.drifted().count(), 1);
+        assert!(run.generated["login"].contains("```\nfunction login("));
+        assert!(run
+            .plan
+            .steps
+            .iter()
+            .any(|step| step.to_string() == "Rewrite docs/auth.md"));
+        assert_eq!(run.evaluation.status.as_str(), "fail");
+
+        assert_eq!(fs::read_to_string(root.join("docs/auth.md")).unwrap(), doc);
+        assert_eq!(fs::read_to_string(root.join(MAP_FILE)).unwrap(), map_json);
+        assert!(!root.join(".sintesi").exists());
+
+        let applied = run_pipeline(
+            root,
+            &PipelineConfig::new()
+                .dry_run(false)
+                .generated(BTreeMap::from([(
+                    "login".to_string(),
+                    "New docs".to_string(),
+                )])),
+        )
+        .unwrap();
+        assert!(applied.applied.is_some());
+        assert_eq!(
+            AstCache::load(root.join(AST_CACHE_FILE)).len(),
+            1,
+            "a real run caches the analysis"
+        );
+        assert!(fs::read_to_string(root.join("docs/auth.md"))
+            .unwrap()
+            .contains("New docs"));
+    }
+}
@@ -0,0 +1,629 @@
+//! Phase-by-phase pipeline orchestration
+//!
+//! `Orchestrator` runs the pipeline phases in order and calls its hooks before
+//! and after each one. Hooks see the run as it fills up and may adjust it, e.g.
+//! supply generated content before `Generate`, and abort the run by returning
+//! an error. Every failure, from a phase or a hook, is reported as
+//! `<phase> phase failed: <cause>`, except for hitting a phase's limits (see
+//! `limits`), reported as `Error::Timeout` or `Error::MemoryLimit`, and
+//! refused writes, reported as `Error::ReadOnly` (read-only mode) or
+//! `Error::PathNotAllowed` (outside the write sandbox).
+
+use super::{PipelineConfig, PipelineRun};
+use crate::apply::{apply_plan, create_plan, PlanOptions};
+use crate::ast::test_mapping::map_project_tests;
+use crate::ast::{
+    AnalyzerOptions, AstAnalyzerInternal, AstCache, SymbolFilter, SymbolInfo, AST_CACHE_DIR,
+    AST_CACHE_FILE,
+};
+use crate::content::{discover_files, DiscoveryConfig, MarkdownExtractor, SintesiAnchor};
+use crate::context::workspace_packages;
+use crate::drift::{check_drift, SintesiMap, MAP_FILE};
+use crate::error::Error;
+use crate::init::CONFIG_FILE;
+use crate::provider::DiskProvider;
+use crate::readonly::{enable_from_config, ensure_writable};
+use crate::report::{evaluate, Coverage, GatePolicy, RunResult};
+use crate::text::{read_text, SkippedFile};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+/// A step of the pipeline, in run order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Find source and markdown files
+    Discover,
+    /// Analyze the sources, extract the anchors, and compute coverage
+    Analyze,
+    /// Check the map against the current code, counting the tests of each
+    /// documented symbol
+    Drift,
+    /// Collect content for drifted anchors (mocked in a dry run)
+    Generate,
+    /// Build the plan and evaluate the CI gate
+    Plan,
+    /// Write the plan (skipped in a dry run)
+    Apply,
+}
+
+impl Phase {
+    /// All phases, in run order
+    pub const ALL: [Phase; 6] = [
+        Phase::Discover,
+        Phase::Analyze,
+        Phase::Drift,
+        Phase::Generate,
+        Phase::Plan,
+        Phase::Apply,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Phase::Discover => "discover",
+            Phase::Analyze => "analyze",
+            Phase::Drift => "drift",
+            Phase::Generate => "generate",
+            Phase::Plan => "plan",
+            Phase::Apply => "apply",
+        }
+    }
+
+    /// Parse a phase from its name, as returned by `as_str`
+    pub fn parse(name: &str) -> Option<Phase> {
+        Phase::ALL.into_iter().find(|phase| phase.as_str() == name)
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Callbacks around each pipeline phase
+///
+/// All methods default to doing nothing. Returning an error aborts the run.
+pub trait PipelineHooks {
+    /// Called before `phase` runs
+    fn before_phase(&mut self, _phase: Phase, _run: &mut PipelineRun) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called for each analyzed source file, in discovery order, before
+    /// the `Analyze` phase ends
+    ///
+    /// `file_path` is relative to the project root with `/` separators.
+    fn file_analyzed(&mut self, _file_path: &str, _symbols: &[SymbolInfo]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called after `phase` has run
+    fn after_phase(&mut self, _phase: Phase, _run: &mut PipelineRun) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<H: PipelineHooks + ?Sized> PipelineHooks for &mut H {
+    fn before_phase(&mut self, phase: Phase, run: &mut PipelineRun) -> Result<(), Error> {
+        (**self).before_phase(phase, run)
+    }
+
+    fn file_analyzed(&mut self, file_path: &str, symbols: &[SymbolInfo]) -> Result<(), Error> {
+        (**self).file_analyzed(file_path, symbols)
+    }
+
+    fn after_phase(&mut self, phase: Phase, run: &mut PipelineRun) -> Result<(), Error> {
+        (**self).after_phase(phase, run)
+    }
+}
+
+/// Runs the pipeline phases on a project, calling hooks around each
+pub struct Orchestrator<'h> {
+    root: PathBuf,
+    config: PipelineConfig,
+    hooks: Vec<Box<dyn PipelineHooks + 'h>>,
+}
+
+/// Map, filter, and gate loaded before the first phase
+struct Inputs {
+    map: SintesiMap,
+    filter: SymbolFilter,
+    gate: GatePolicy,
+    source_files: Vec<PathBuf>,
+    markdown_files: Vec<PathBuf>,
+    /// Analysis cache to save once `Analyze` succeeded, and where
+    ast_cache: Option<(Arc<AstCache>, PathBuf)>,
+}
+
+impl<'h> Orchestrator<'h> {
+    /// Create an orchestrator for the project at `root`
+    pub fn new(root: impl AsRef<Path>, config: PipelineConfig) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            config,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Add hooks, called in the order they were added
+    pub fn hook(mut self, hooks: impl PipelineHooks + 'h) -> Self {
+        self.hooks.push(Box::new(hooks));
+        self
+    }
+
+    /// Phases this orchestrator runs (all but `Apply` in a dry run)
+    pub fn phases(&self) -> impl Iterator<Item = Phase> {
+        let dry_run = self.config.dry_run;
+        Phase::ALL
+            .into_iter()
+            .filter(move |phase| !(dry_run && *phase == Phase::Apply))
+    }
+
+    /// Run every phase
+    ///
+    /// # Returns
+    /// The reports and plan of the run, or an error if the map or
+    /// configuration cannot be loaded, or a phase or hook fails; a run that
+    /// is not dry fails with `Error::ReadOnly` in read-only mode, before any
+    /// phase
+    pub fn run(&mut self) -> Result<PipelineRun, Error> {
+        let root = self.root.clone();
+        enable_from_config(root.join(CONFIG_FILE))?;
+        if !self.config.dry_run {
+            ensure_writable("pipeline run")?;
+        }
+        let mut inputs = Inputs {
+            map: SintesiMap::load(root.join(MAP_FILE))?,
+            filter: SymbolFilter::load(root.join(CONFIG_FILE))?,
+            gate: GatePolicy::load(root.join(CONFIG_FILE))?,
+            source_files: Vec::new(),
+            markdown_files: Vec::new(),
+            ast_cache: None,
+        };
+        let mut run = PipelineRun {
+            dry_run: self.config.dry_run,
+            generated: self.config.generated.clone(),
+            ..PipelineRun::default()
+        };
+
+        for phase in self.phases().collect::<Vec<_>>() {
+            let started = Instant::now();
+            for hooks in &mut self.hooks {
+                hooks
+                    .before_phase(phase, &mut run)
+                    .map_err(|e| phase_error(phase, e))?;
+            }
+            let operation = format!("{} phase", phase);
+            let limits = self.config.limits.get(&phase).copied();
+            match limits {
+                // `Apply` is never interrupted, only checked afterwards
+                Some(limits) if phase != Phase::Apply => {
+                    let (root, config) = (self.root.clone(), self.config.clone());
+                    let (mut moved_inputs, mut moved_run) = (inputs, run);
+                    let (returned_inputs, returned_run, result) =
+                        limits.run(&operation, started, move || {
+                            let result =
+                                run_phase(&root, &config, phase, &mut moved_inputs, &mut moved_run);
+                            (moved_inputs, moved_run, result)
+                        })?;
+                    (inputs, run) = (returned_inputs, returned_run);
+                    result
+                }
+                _ => run_phase(&self.root, &self.config, phase, &mut inputs, &mut run),
+            }
+            .map_err(|e| phase_error(phase, e))?;
+            if phase == Phase::Analyze {
+                self.report_files(&inputs.source_files, &run)
+                    .map_err(|e| phase_error(phase, e))?;
+            }
+            for hooks in &mut self.hooks {
+                hooks
+                    .after_phase(phase, &mut run)
+                    .map_err(|e| phase_error(phase, e))?;
+            }
+            if let Some(limits) = limits {
+                limits.check(&operation, started)?;
+            }
+            // Saved here rather than by the phase, so an abandoned worker
+            // never writes a cache after the run failed
+            if let Some((cache, path)) = inputs.ast_cache.take() {
+                cache.prune();
+                cache.save(path).map_err(|e| phase_error(phase, e))?;
+            }
+        }
+        Ok(run)
+    }
+
+    /// Call `file_analyzed` hooks with the symbols of each source file
+    fn report_files(&mut self, source_files: &[PathBuf], run: &PipelineRun) -> Result<(), Error> {
+        if self.hooks.is_empty() {
+            return Ok(());
+        }
+        let mut symbols_by_file: HashMap<&str, Vec<SymbolInfo>> = HashMap::new();
+        for symbol in &run.analysis.symbols {
+            symbols_by_file
+                .entry(symbol.file_path.as_str())
+                .or_default()
+                .push(symbol.clone());
+        }
+        for path in source_files {
+            let relative = path.strip_prefix(&self.root).unwrap_or(path);
+            let file_path = relative.to_string_lossy().replace('\\', "/");
+            let symbols = symbols_by_file
+                .get(file_path.as_str())
+                .map_or(&[][..], Vec::as_slice);
+            for hooks in &mut self.hooks {
+                hooks.file_analyzed(&file_path, symbols)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run one phase's work on the inputs, filling in the run
+fn run_phase(
+    root: &Path,
+    config: &PipelineConfig,
+    phase: Phase,
+    inputs: &mut Inputs,
+    run: &mut PipelineRun,
+) -> Result<(), Error> {
+    match phase {
+        Phase::Discover => {
+            let discovered = discover_files(root, DiscoveryConfig::new());
+            run.discovery = discovered.stats;
+            inputs.source_files = discovered.source_files;
+            inputs.markdown_files = discovered.markdown_files;
+        }
+        Phase::Analyze => {
+            // Sources and markdown are independent: process them side by side
+            let filter = inputs.filter.clone();
+            let (cache, cache_path) = match config.shard_cache {
+                true => (
+                    AstCache::load_sharded(root.join(AST_CACHE_DIR), workspace_packages(root)),
+                    root.join(AST_CACHE_DIR),
+                ),
+                false => (
+                    AstCache::load(root.join(AST_CACHE_FILE)),
+                    root.join(AST_CACHE_FILE),
+                ),
+            };
+            let cache = Arc::new(cache);
+            let (mut analysis, (anchors, errors, skipped)) = thread::scope(|scope| {
+                let analysis = scope.spawn(|| {
+                    let options = AnalyzerOptions::new()
+                        .filter(filter)
+                        .max_file_size(config.max_file_size)
+                        .include_generated(config.include_generated)
+                        .source_maps(config.source_maps);
+                    AstAnalyzerInternal::with_options(options)
+                        .with_cache(cache.clone())
+                        .analyze_files(root, &inputs.source_files)
+                });
+                let anchors =
+                    extract_all_anchors(root, &inputs.markdown_files, config.max_file_size);
+                let analysis = analysis
+                    .join()
+                    .map_err(|_| Error::from_reason("source analysis panicked"))?;
+                Ok::<_, Error>((analysis, anchors))
+            })?;
+            // A dry run reads the cache but, like everything else, writes nothing
+            if !run.dry_run {
+                inputs.ast_cache = Some((cache, cache_path));
+            }
+            run.skipped = std::mem::take(&mut analysis.skipped);
+            run.skipped.extend(skipped);
+            run.analysis = analysis;
+            run.anchors = anchors;
+            run.validation_errors = errors;
+            run.coverage = Coverage::compute_resolved(
+                &inputs.map,
+                &run.analysis.symbols,
+                &DiskProvider::new(root),
+            );
+        }
+        Phase::Drift => {
+            run.drift = check_drift(&DiskProvider::new(root), &inputs.map);
+            // Without any tests found, every symbol would count as untested
+            let tests = map_project_tests(root, &inputs.source_files, &run.analysis.symbols);
+            if !tests.tests.is_empty() {
+                run.drift = std::mem::take(&mut run.drift).with_tests(&tests);
+            }
+        }
+        Phase::Generate => {
+            if run.dry_run {
+                for entry in run.drift.drifted().filter(|e| e.current_hash.is_some()) {
+                    run.generated
+                        .entry(entry.entry_id.clone())
+                        .or_insert_with(|| mock_content(&entry.code_ref, &run.analysis.symbols));
+                }
+            }
+        }
+        Phase::Plan => {
+            let mut options = PlanOptions::new();
+            if let Some(message) = &config.commit_message {
+                options = options.commit_message(message.clone());
+            }
+            run.plan = create_plan(root, &inputs.map, &run.drift, &run.generated, &options)?;
+            run.evaluation = evaluate(
+                &RunResult {
+                    drift: run.drift.clone(),
+                    validation_errors: run.validation_errors.clone(),
+                    coverage: Some(run.coverage),
+                    policy_violations: Vec::new(),
+                },
+                &inputs.gate,
+            );
+        }
+        Phase::Apply => {
+            run.applied = Some(apply_plan(root, &run.plan)?);
+        }
+    }
+    Ok(())
+}
+
+/// Anchors of the markdown files, sorted by file and line, extraction errors,
+/// and files that are not text or over `max_file_size`
+fn extract_all_anchors(
+    root: &Path,
+    markdown_files: &[PathBuf],
+    max_file_size: usize,
+) -> (Vec<SintesiAnchor>, Vec<String>, Vec<SkippedFile>) {
+    let extractor = MarkdownExtractor::new().max_file_size(max_file_size);
+    let mut anchors = Vec::new();
+    let mut errors = Vec::new();
+    let mut skipped = Vec::new();
+    for path in markdown_files {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        match read_text(path) {
+            Ok(content) => {
+                let extraction = extractor.extract_from_file(relative, &content);
+                anchors.extend(extraction.anchors.into_values());
+                errors.extend(extraction.errors);
+                skipped.extend(extraction.skipped);
+            }
+            Err(e) => {
+                let file_path = relative.to_string_lossy().replace('\\', "/");
+                skipped.extend(SkippedFile::from_error(&file_path, &e));
+            }
+        }
+    }
+    anchors.sort_by(|a, b| (&a.file_path, a.start_line).cmp(&(&b.file_path, b.start_line)));
+    (anchors, errors, skipped)
+}
+
+/// Prefix an error with the phase it happened in (limit errors name it already)
+fn phase_error(phase: Phase, error: Error) -> Error {
+    let cause = match error {
+        Error::Reason(reason) => reason,
+        error @ (Error::ReadOnly { .. } | Error::PathNotAllowed { .. }) => return error,
+        error if error.is_limit() => return error,
+        error => error.to_string(),
+    };
+    Error::from_reason(format!("{} phase failed: {}", phase, cause))
+}
+
+/// Placeholder documentation for a drifted symbol, showing its signature
+fn mock_content(code_ref: &str, symbols: &[SymbolInfo]) -> String {
+    let signature = symbols
+        .iter()
+        .find(|s| code_ref.split_once('#') == Some((s.file_path.as_str(), s.name.as_str())))
+        .map(|s| format!("\n\n```\n{}\n```", s.signature))
+        .unwrap_or_default();
+    format!("Documentation for `{}` (dry run).{}", code_ref, signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::SintesiMapEntry;
+    use crate::pipeline::PhaseLimits;
+    use std::fs;
+    use std::time::Duration;
+
+    /// Records hook calls and supplies content before generation
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<String>,
+    }
+
+    impl PipelineHooks for Recorder {
+        fn before_phase(&mut self, phase: Phase, run: &mut PipelineRun) -> Result<(), Error> {
+            self.calls.push(format!("before {}", phase));
+            if phase == Phase::Generate {
+                run.generated
+                    .insert("login".to_string(), "From a hook".to_string());
+            }
+            Ok(())
+        }
+
+        fn file_analyzed(&mut self, file_path: &str, symbols: &[SymbolInfo]) -> Result<(), Error> {
+            self.calls
+                .push(format!("{} ({} symbols)", file_path, symbols.len()));
+            Ok(())
+        }
+
+        fn after_phase(&mut self, phase: Phase, _run: &mut PipelineRun) -> Result<(), Error> {
+            self.calls.push(format!("after {}", phase));
+            Ok(())
+        }
+    }
+
+    struct Abort(Phase);
+
+    impl PipelineHooks for Abort {
+        fn before_phase(&mut self, phase: Phase, _run: &mut PipelineRun) -> Result<(), Error> {
+            match phase == self.0 {
+                true => Err(Error::from_reason("stopped by hook")),
+                false => Ok(()),
+            }
+        }
+    }
+
+    /// Takes its time before the given phase
+    struct Slow(Phase);
+
+    impl PipelineHooks for Slow {
+        fn before_phase(&mut self, phase: Phase, _run: &mut PipelineRun) -> Result<(), Error> {
+            if phase == self.0 {
+                thread::sleep(Duration::from_millis(50));
+            }
+            Ok(())
+        }
+    }
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("src/auth.ts"),
+            "export function login(): void {}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/auth.md"),
+            "<!-- sintesi:start id=\"login\" code_ref=\"src/auth.ts#login\" -->\nOld\n<!-- sintesi:end id=\"login\" -->\n",
+        )
+        .unwrap();
+        SintesiMap {
+            entries: vec![SintesiMapEntry {
+                id: "login".to_string(),
+                code_ref: "src/auth.ts#login".to_string(),
+                doc_file: "docs/auth.md".to_string(),
+                code_signature_hash: "stale".to_string(),
+                last_updated: 0,
+                symbol_id: None,
+                symbol_key: None,
+                deprecated: false,
+                content_hash: None,
+                code_signature: None,
+            }],
+            ..SintesiMap::new()
+        }
+        .save(root.join(MAP_FILE))
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_hooks_run_around_each_phase() {
+        let dir = project();
+        let mut recorder = Recorder::default();
+        let run = Orchestrator::new(dir.path(), PipelineConfig::new())
+            .hook(&mut recorder)
+            .run()
+            .unwrap();
+
+        let expected = [
+            "before discover",
+            "after discover",
+            "before analyze",
+            "src/auth.ts (1 symbols)",
+            "after analyze",
+            "before drift",
+            "after drift",
+            "before generate",
+            "after generate",
+            "before plan",
+            "after plan",
+        ];
+        assert_eq!(recorder.calls, expected);
+        // Hook content wins over the dry-run mock
+        assert_eq!(run.generated["login"], "From a hook");
+        assert_eq!(run.anchors.len(), 1);
+    }
+
+    #[test]
+    fn test_hook_errors_abort_the_run() {
+        let dir = project();
+        let error = Orchestrator::new(dir.path(), PipelineConfig::new())
+            .hook(Abort(Phase::Drift))
+            .run()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Error: drift phase failed: stopped by hook"
+        );
+        // Refused writes keep their type
+        let refused = Error::ReadOnly {
+            operation: "save map".to_string(),
+        };
+        assert!(matches!(
+            phase_error(Phase::Apply, refused),
+            Error::ReadOnly { .. }
+        ));
+
+        let phases: Vec<Phase> =
+            Orchestrator::new(dir.path(), PipelineConfig::new().dry_run(false))
+                .phases()
+                .collect();
+        assert_eq!(phases, Phase::ALL);
+        assert_eq!(Phase::parse("generate"), Some(Phase::Generate));
+        assert_eq!(Phase::parse("parse"), None);
+    }
+
+    #[test]
+    fn test_phase_limits() {
+        let dir = project();
+        let generous = PhaseLimits::new().timeout(Duration::from_secs(60));
+        let run = Orchestrator::new(
+            dir.path(),
+            PipelineConfig::new().limit(Phase::Analyze, generous),
+        )
+        .run()
+        .unwrap();
+        assert_eq!(run.analysis.symbols.len(), 1);
+
+        let tight = PhaseLimits::new().timeout(Duration::from_millis(10));
+        let error = Orchestrator::new(dir.path(), PipelineConfig::new().limit(Phase::Drift, tight))
+            .hook(Slow(Phase::Drift))
+            .run()
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            Error::Timeout { operation, .. } if operation == "drift phase"
+        ));
+    }
+
+    #[test]
+    fn test_timed_out_analyze_leaves_cache_untouched() {
+        let dir = project();
+        Orchestrator::new(dir.path(), PipelineConfig::new().dry_run(false))
+            .run()
+            .unwrap();
+        let cache_path = dir.path().join(AST_CACHE_FILE);
+        let cached = fs::read(&cache_path).unwrap();
+
+        // Enough new sources that analyzing them outlasts the limit
+        for i in 0..200 {
+            fs::write(
+                dir.path().join(format!("src/module{}.ts", i)),
+                format!(
+                    "export function handler{}(input: string): number {{ return {}; }}\n",
+                    i, i
+                ),
+            )
+            .unwrap();
+        }
+        let tight = PhaseLimits::new().timeout(Duration::from_millis(1));
+        let error = Orchestrator::new(
+            dir.path(),
+            PipelineConfig::new()
+                .dry_run(false)
+                .limit(Phase::Analyze, tight),
+        )
+        .run()
+        .unwrap_err();
+        assert!(matches!(&error, Error::Timeout { .. }));
+
+        // Give the abandoned worker time to finish
+        thread::sleep(Duration::from_millis(500));
+        assert!(fs::read(&cache_path).unwrap() == cached);
+    }
+}
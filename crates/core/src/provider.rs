@@ -0,0 +1,319 @@
+//! File-content providers
+//!
+//! A `ContentProvider` supplies file contents by project-relative path, so
+//! the analyzer, anchor extractor, graph builder, and drift detection run
+//! unchanged against:
+//!
+//! - the working tree (`DiskProvider`)
+//! - a git revision (`GitProvider`), e.g. to compare against `main`
+//! - an in-memory overlay (`MemoryProvider`), e.g. unsaved editor buffers or
+//!   planned rewrites on top of another provider
+
+use crate::crawler::UniqueFiles;
+use crate::paths::{canonical, extended_length};
+use crate::text::{decode, read_text};
+use git2::{ObjectType, Oid, Repository, TreeWalkMode, TreeWalkResult};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Source of file contents, addressed by paths relative to the project root
+pub trait ContentProvider: Send + Sync {
+    /// Read a file as text; missing files are `io::ErrorKind::NotFound`, and
+    /// files that are not text `io::ErrorKind::InvalidData` (see `text`)
+    fn read(&self, path: &Path) -> io::Result<String>;
+
+    /// Check whether a file exists
+    fn exists(&self, path: &Path) -> bool {
+        self.read(path).is_ok()
+    }
+
+    /// All files, relative to the project root
+    fn list_files(&self) -> io::Result<Vec<PathBuf>>;
+}
+
+/// Files in the working tree
+#[derive(Debug, Clone)]
+pub struct DiskProvider {
+    root: PathBuf,
+}
+
+impl DiskProvider {
+    /// Serve files under `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Project root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl ContentProvider for DiskProvider {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        read_text(&extended_length(&self.root.join(path)))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        extended_length(&self.root.join(path)).is_file()
+    }
+
+    /// Files not excluded by `.gitignore`; of several hard links to the same
+    /// file, only the first path in sorted order is listed
+    fn list_files(&self) -> io::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let root = extended_length(&self.root);
+        for entry in ignore::WalkBuilder::new(&root).build() {
+            let entry = entry.map_err(io::Error::other)?;
+            if entry.file_type().is_some_and(|t| t.is_file()) {
+                if let Ok(relative) = entry.path().strip_prefix(&root) {
+                    files.push(relative.to_path_buf());
+                }
+            }
+        }
+        files.sort();
+        let mut unique = UniqueFiles::new();
+        files.retain(|path| unique.insert(&root.join(path)));
+        Ok(files)
+    }
+}
+
+/// Files as of a git revision
+///
+/// The repository handle is not `Sync`, so reads are serialized.
+pub struct GitProvider {
+    repo: Mutex<Repository>,
+    tree: Oid,
+    /// Project root relative to the repository's working directory
+    prefix: PathBuf,
+}
+
+impl GitProvider {
+    /// Serve files under `root` as of `revision` (e.g. `HEAD`, `main`, a SHA)
+    ///
+    /// `root` may be a subdirectory of the repository; paths stay relative to it.
+    pub fn new(root: impl AsRef<Path>, revision: &str) -> Result<Self, git2::Error> {
+        let root = root.as_ref();
+        let repo = Repository::discover(root)?;
+        let tree = repo.revparse_single(revision)?.peel_to_tree()?.id();
+
+        let prefix = match repo.workdir() {
+            Some(workdir) => {
+                let workdir = canonical(workdir);
+                let root = canonical(root);
+                root.strip_prefix(&workdir)
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default()
+            }
+            None => PathBuf::new(),
+        };
+
+        Ok(Self {
+            repo: Mutex::new(repo),
+            tree,
+            prefix,
+        })
+    }
+}
+
+impl ContentProvider for GitProvider {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        let repo = self.repo.lock().unwrap_or_else(|e| e.into_inner());
+        let not_found = || io::Error::new(io::ErrorKind::NotFound, path.display().to_string());
+
+        let tree = repo.find_tree(self.tree).map_err(io::Error::other)?;
+        let entry = tree
+            .get_path(&self.prefix.join(path))
+            .map_err(|_| not_found())?;
+        if entry.kind() != Some(ObjectType::Blob) {
+            return Err(not_found());
+        }
+        let blob = repo.find_blob(entry.id()).map_err(io::Error::other)?;
+        decode(blob.content().to_vec())
+    }
+
+    fn list_files(&self) -> io::Result<Vec<PathBuf>> {
+        let repo = self.repo.lock().unwrap_or_else(|e| e.into_inner());
+        let tree = repo.find_tree(self.tree).map_err(io::Error::other)?;
+
+        let mut files = Vec::new();
+        tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() == Some(ObjectType::Blob) {
+                if let Some(name) = entry.name() {
+                    let path = Path::new(dir).join(name);
+                    if let Ok(relative) = path.strip_prefix(&self.prefix) {
+                        files.push(relative.to_path_buf());
+                    }
+                }
+            }
+            TreeWalkResult::Ok
+        })
+        .map_err(io::Error::other)?;
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// In-memory files, optionally layered over another provider
+///
+/// Files set in the overlay shadow the base; removed files are hidden even if
+/// the base has them.
+#[derive(Default)]
+pub struct MemoryProvider {
+    /// Overlay content; `None` marks a removed file
+    files: HashMap<PathBuf, Option<String>>,
+    base: Option<Box<dyn ContentProvider>>,
+}
+
+impl MemoryProvider {
+    /// Create an empty provider with no base
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an overlay on top of another provider
+    pub fn overlay(base: impl ContentProvider + 'static) -> Self {
+        Self {
+            files: HashMap::new(),
+            base: Some(Box::new(base)),
+        }
+    }
+
+    /// Set a file's content
+    pub fn set(&mut self, path: impl Into<PathBuf>, content: impl Into<String>) {
+        self.files.insert(path.into(), Some(content.into()));
+    }
+
+    /// Hide a file (including one that exists in the base)
+    pub fn remove(&mut self, path: impl Into<PathBuf>) {
+        self.files.insert(path.into(), None);
+    }
+}
+
+impl ContentProvider for MemoryProvider {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        match (self.files.get(path), &self.base) {
+            (Some(Some(content)), _) => Ok(content.clone()),
+            (None, Some(base)) => base.read(path),
+            _ => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                path.display().to_string(),
+            )),
+        }
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        match (self.files.get(path), &self.base) {
+            (Some(content), _) => content.is_some(),
+            (None, Some(base)) => base.exists(path),
+            (None, None) => false,
+        }
+    }
+
+    fn list_files(&self) -> io::Result<Vec<PathBuf>> {
+        let mut files = match &self.base {
+            Some(base) => base.list_files()?,
+            None => Vec::new(),
+        };
+        files.retain(|path| !self.files.contains_key(path));
+        files.extend(
+            self.files
+                .iter()
+                .filter(|(_, content)| content.is_some())
+                .map(|(path, _)| path.clone()),
+        );
+        files.sort();
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn commit_all(repo: &Repository, message: &str) -> Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_git_provider_reads_revision() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::create_dir_all(dir.path().join("app/src")).unwrap();
+        fs::write(dir.path().join("app/src/a.ts"), "v1").unwrap();
+        let first = commit_all(&repo, "first");
+        fs::write(dir.path().join("app/src/a.ts"), "v2").unwrap();
+        fs::write(dir.path().join("app/src/b.ts"), "new").unwrap();
+        commit_all(&repo, "second");
+
+        let provider = GitProvider::new(dir.path().join("app"), &first.to_string()).unwrap();
+
+        assert_eq!(provider.read(Path::new("src/a.ts")).unwrap(), "v1");
+        assert!(!provider.exists(Path::new("src/b.ts")));
+        assert_eq!(provider.list_files().unwrap(), [PathBuf::from("src/a.ts")]);
+        let head = GitProvider::new(dir.path(), "HEAD").unwrap();
+        assert_eq!(head.read(Path::new("app/src/a.ts")).unwrap(), "v2");
+    }
+
+    #[test]
+    fn test_memory_overlay() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.ts"), "disk a").unwrap();
+        fs::write(dir.path().join("b.ts"), "disk b").unwrap();
+
+        let mut provider = MemoryProvider::overlay(DiskProvider::new(dir.path()));
+        provider.set("a.ts", "memory a");
+        provider.set("c.ts", "memory c");
+        provider.remove("b.ts");
+
+        assert_eq!(provider.read(Path::new("a.ts")).unwrap(), "memory a");
+        assert_eq!(
+            provider.read(Path::new("b.ts")).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert_eq!(
+            provider.list_files().unwrap(),
+            [PathBuf::from("a.ts"), PathBuf::from("c.ts")]
+        );
+    }
+
+    #[test]
+    fn test_analysis_and_graph_run_against_memory() {
+        let mut provider = MemoryProvider::new();
+        provider.set(
+            "src/a.ts",
+            "import { b } from './b';\nexport function a() {}",
+        );
+        provider.set("src/b.ts", "export const b = 1;");
+        let files = provider.list_files().unwrap();
+
+        let analysis = crate::ast::AstAnalyzerInternal::new().analyze_with(&provider, &files);
+        assert!(analysis.errors.is_empty());
+        assert_eq!(analysis.symbols.len(), 2);
+
+        let graph = crate::graph::build_graph_from(&provider, &files);
+        assert_eq!(
+            graph.dependencies(Path::new("src/a.ts")),
+            [PathBuf::from("src/b.ts")]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Read-only mode
+//!
+//! Analysis of untrusted code (a pull request build running with repository
+//! credentials) must not change the project. In read-only mode every write
+//! to it fails with `Error::ReadOnly` before touching anything: transactional
+//! writes (anchor injection, plans, `init`, migrations), saving the map, the
+//! drift history, or the analysis cache, and git commits. Report files
+//! written to an explicitly given output path are not affected.
+//!
+//! The mode is process-wide. It is on when `set_read_only(true)` was called,
+//! when `SINTESI_READ_ONLY` is set to anything but `0`, `false`, or an empty
+//! string, or once a loaded config file says `"readOnly": true`
+//! (`enable_from_config`). It cannot be turned off by a config file, which
+//! an untrusted change could edit: only the environment or the embedding
+//! process decides.
+
+use crate::error::Error;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Environment variable turning read-only mode on
+pub const READ_ONLY_ENV: &str = "SINTESI_READ_ONLY";
+
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReadOnlyConfig {
+    #[serde(default)]
+    read_only: bool,
+}
+
+/// Turn read-only mode on or off for the process
+///
+/// Turning it off has no effect while `SINTESI_READ_ONLY` is set.
+pub fn set_read_only(enabled: bool) {
+    READ_ONLY.store(enabled, Ordering::SeqCst);
+}
+
+/// Whether writes to the project are refused
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::SeqCst) || env_enables(std::env::var(READ_ONLY_ENV).ok().as_deref())
+}
+
+/// Turn read-only mode on if the `readOnly` key of a config file is `true`
+///
+/// A missing config file leaves the mode as it is.
+pub fn enable_from_config(path: impl AsRef<Path>) -> Result<(), Error> {
+    if config_enables(path.as_ref())? {
+        set_read_only(true);
+    }
+    Ok(())
+}
+
+/// Whether a value of `SINTESI_READ_ONLY` turns read-only mode on
+fn env_enables(value: Option<&str>) -> bool {
+    value.is_some_and(|value| !matches!(value.trim(), "" | "0" | "false"))
+}
+
+/// The `readOnly` key of a config file (`false` if there is none)
+fn config_enables(path: &Path) -> Result<bool, Error> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(false);
+    };
+    let config: ReadOnlyConfig = serde_json::from_str(&content)
+        .map_err(|e| Error::from_reason(format!("Invalid config {}: {}", path.display(), e)))?;
+    Ok(config.read_only)
+}
+
+/// Fail with `Error::ReadOnly` if writes are refused
+///
+/// # Arguments
+/// * `operation` - The write about to happen (`"save map"`), for the error
+pub fn ensure_writable(operation: &str) -> Result<(), Error> {
+    match is_read_only() {
+        true => Err(Error::ReadOnly {
+            operation: operation.to_string(),
+        }),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The mode is process-wide: tests check how it is decided without
+    // turning it on under the tests that write files
+
+    #[test]
+    fn test_env_and_config_values() {
+        assert!(env_enables(Some("1")));
+        assert!(env_enables(Some("true")));
+        assert!(!env_enables(Some("0")));
+        assert!(!env_enables(Some(" false ")));
+        assert!(!env_enables(Some("")));
+        assert!(!env_enables(None));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sintesi.config.json");
+        assert!(!config_enables(&path).unwrap());
+        fs::write(&path, r#"{"readOnly": true, "gate": {}}"#).unwrap();
+        assert!(config_enables(&path).unwrap());
+        fs::write(&path, r#"{"symbols": {}}"#).unwrap();
+        assert!(!config_enables(&path).unwrap());
+        fs::write(&path, r#"{"readOnly": "yes"}"#).unwrap();
+        assert!(config_enables(&path).is_err());
+
+        let error = Error::ReadOnly {
+            operation: "save map".to_string(),
+        };
+        assert_eq!(error.to_string(), "Read-only: save map is not allowed");
+    }
+}
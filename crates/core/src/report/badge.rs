@@ -0,0 +1,209 @@
+//! Status badges
+//!
+//! Renders "docs coverage" and "drift" badges without any badge service:
+//! each badge is written as a flat-style SVG plus a shields.io endpoint JSON
+//! file next to it, so a README can embed either the committed/uploaded SVG
+//! or `https://img.shields.io/endpoint?url=...` pointing at the JSON.
+
+use super::coverage::Coverage;
+use crate::drift::DriftResult;
+use crate::error::Error;
+use crate::sandbox::check_path;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+/// Badge color, named after the shields.io palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeColor {
+    BrightGreen,
+    Green,
+    Yellow,
+    Orange,
+    Red,
+}
+
+impl BadgeColor {
+    /// shields.io color name
+    pub fn name(&self) -> &'static str {
+        match self {
+            BadgeColor::BrightGreen => "brightgreen",
+            BadgeColor::Green => "green",
+            BadgeColor::Yellow => "yellow",
+            BadgeColor::Orange => "orange",
+            BadgeColor::Red => "red",
+        }
+    }
+
+    fn hex(&self) -> &'static str {
+        match self {
+            BadgeColor::BrightGreen => "#4c1",
+            BadgeColor::Green => "#97ca00",
+            BadgeColor::Yellow => "#dfb317",
+            BadgeColor::Orange => "#fe7d37",
+            BadgeColor::Red => "#e05d44",
+        }
+    }
+}
+
+/// A two-part status badge (`label | message`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    pub label: String,
+    pub message: String,
+    pub color: BadgeColor,
+}
+
+impl Badge {
+    /// "docs coverage | 87%"
+    pub fn coverage(coverage: &Coverage) -> Self {
+        let percent = coverage.percent();
+        let color = match percent {
+            90.. => BadgeColor::BrightGreen,
+            75..=89 => BadgeColor::Green,
+            50..=74 => BadgeColor::Yellow,
+            25..=49 => BadgeColor::Orange,
+            _ => BadgeColor::Red,
+        };
+        Self {
+            label: "docs coverage".to_string(),
+            message: format!("{}%", percent),
+            color,
+        }
+    }
+
+    /// "drift | clean" or "drift | 3 issues"
+    pub fn drift(result: &DriftResult) -> Self {
+        let (message, color) = match result.drifted().count() {
+            0 => ("clean".to_string(), BadgeColor::BrightGreen),
+            1 => ("1 issue".to_string(), BadgeColor::Red),
+            n => (format!("{} issues", n), BadgeColor::Red),
+        };
+        Self {
+            label: "drift".to_string(),
+            message,
+            color,
+        }
+    }
+
+    /// Render as a flat-style SVG
+    pub fn to_svg(&self) -> String {
+        let label_width = text_width(&self.label);
+        let message_width = text_width(&self.message);
+        let width = label_width + message_width;
+        let label = escape_xml(&self.label);
+        let message = escape_xml(&self.message);
+
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}"><title>{label}: {message}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{label_x}" y="14">{label}</text><text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text><text x="{message_x}" y="14">{message}</text></g></svg>"##,
+            color = self.color.hex(),
+            label_x = label_width as f32 / 2.0,
+            message_x = label_width as f32 + message_width as f32 / 2.0,
+        )
+    }
+
+    /// Render as a shields.io endpoint JSON document
+    pub fn to_shields_json(&self) -> String {
+        json!({
+            "schemaVersion": 1,
+            "label": self.label,
+            "message": self.message,
+            "color": self.color.name(),
+        })
+        .to_string()
+    }
+
+    /// Write the SVG to `path` and the shields.io JSON next to it
+    /// (same file name with a `.json` extension)
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        check_path("write badge", path)?;
+        check_path("write badge", path.with_extension("json"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                Error::from_reason(format!("Failed to create {}: {}", parent.display(), e))
+            })?;
+        }
+        let write = |path: &Path, content: String| {
+            fs::write(path, content).map_err(|e| {
+                Error::from_reason(format!("Failed to write badge {}: {}", path.display(), e))
+            })
+        };
+        write(path, self.to_svg())?;
+        write(&path.with_extension("json"), self.to_shields_json())
+    }
+}
+
+/// Approximate rendered width of 11px Verdana text, plus padding
+fn text_width(text: &str) -> u32 {
+    let chars: u32 = text
+        .chars()
+        .map(|c| match c {
+            'i' | 'l' | 'j' | '.' | ',' | ':' | ' ' | '|' => 4,
+            'm' | 'w' | 'M' | 'W' | '%' => 10,
+            _ => 7,
+        })
+        .sum();
+    chars + 10
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::{DriftEntry, DriftStatus};
+
+    #[test]
+    fn test_coverage_badge() {
+        let badge = Badge::coverage(&Coverage {
+            documented: 8,
+            total: 10,
+        });
+        assert_eq!(badge.message, "80%");
+        assert_eq!(badge.color, BadgeColor::Green);
+        assert_eq!(
+            badge.to_shields_json(),
+            r#"{"color":"green","label":"docs coverage","message":"80%","schemaVersion":1}"#
+        );
+
+        let svg = badge.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("<title>docs coverage: 80%</title>"));
+        assert!(svg.contains("#97ca00"));
+    }
+
+    #[test]
+    fn test_drift_badge_written() {
+        let drifted = DriftResult {
+            entries: vec![DriftEntry {
+                entry_id: "a".to_string(),
+                code_ref: "src/auth.ts#login".to_string(),
+                doc_file: "docs/auth.md".to_string(),
+                status: DriftStatus::Drifted,
+                expected_hash: "x".to_string(),
+                current_hash: Some("y".to_string()),
+                ignore_reason: None,
+                deprecation: None,
+                current_signature: None,
+                severity: None,
+                test_count: None,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(Badge::drift(&DriftResult::default()).message, "clean");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("badges/drift.svg");
+        Badge::drift(&drifted).write(&path).unwrap();
+
+        assert!(fs::read_to_string(&path).unwrap().contains("1 issue"));
+        let json = fs::read_to_string(dir.path().join("badges/drift.json")).unwrap();
+        assert!(json.contains(r#""color":"red""#));
+    }
+}
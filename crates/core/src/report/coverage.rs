@@ -0,0 +1,187 @@
+//! Documentation coverage
+//!
+//! Coverage is the share of exported symbols that at least one map entry
+//! documents, matched by `file_path#symbol_name` or by the symbol ID the
+//! entry recorded (`ast::symbol_id`), so an entry still written against
+//! `src/auth.js` covers `src/auth.ts` after the conversion. `compute_resolved` also
+//! follows entries pointing at a barrel file to the module declaring the
+//! symbol, so documenting a re-export covers the original declaration.
+
+use crate::ast::reexports::resolve_reexport;
+use crate::ast::{symbol_id, SymbolInfo};
+use crate::drift::SintesiMap;
+use crate::provider::ContentProvider;
+use std::collections::HashSet;
+
+/// Documentation coverage of exported symbols
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Coverage {
+    /// Exported symbols referenced by a map entry
+    pub documented: usize,
+    /// All exported symbols
+    pub total: usize,
+}
+
+impl Coverage {
+    /// Compute coverage of `symbols` by the map's code references
+    pub fn compute(map: &SintesiMap, symbols: &[SymbolInfo]) -> Self {
+        let documented_refs: HashSet<String> =
+            map.entries.iter().map(|e| e.code_ref.clone()).collect();
+        Self::count(map, &documented_refs, symbols)
+    }
+
+    /// Compute coverage, attributing re-exported references to their declarations
+    ///
+    /// An entry whose `code_ref` names a re-export (e.g. `src/index.ts#login`
+    /// for `export { login } from './auth'`) documents the declaring symbol
+    /// (`src/auth.ts#login`) as well. Module files are read through `provider`.
+    pub fn compute_resolved(
+        map: &SintesiMap,
+        symbols: &[SymbolInfo],
+        provider: &dyn ContentProvider,
+    ) -> Self {
+        let mut documented_refs: HashSet<String> =
+            map.entries.iter().map(|e| e.code_ref.clone()).collect();
+        for entry in &map.entries {
+            if let Some(resolved) =
+                resolve_reexport(provider, entry.code_file_path(), entry.symbol_name())
+            {
+                documented_refs.insert(resolved.code_ref());
+            }
+        }
+        Self::count(map, &documented_refs, symbols)
+    }
+
+    fn count(map: &SintesiMap, documented_refs: &HashSet<String>, symbols: &[SymbolInfo]) -> Self {
+        let documented_ids: HashSet<&str> = map
+            .entries
+            .iter()
+            .filter_map(|e| e.symbol_id.as_deref())
+            .collect();
+        let mut coverage = Self::default();
+        for symbol in symbols.iter().filter(|s| s.is_exported) {
+            coverage.total += 1;
+            let code_ref = format!("{}#{}", symbol.file_path, symbol.name);
+            if documented_refs.contains(&code_ref)
+                || documented_ids.contains(
+                    symbol_id(&symbol.file_path, &symbol.name, symbol.symbol_type).as_str(),
+                )
+            {
+                coverage.documented += 1;
+            }
+        }
+        coverage
+    }
+
+    /// Coverage as a whole percentage (100 when there is nothing to document)
+    pub fn percent(&self) -> u32 {
+        if self.total == 0 {
+            return 100;
+        }
+        (self.documented * 100 / self.total) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::SintesiMapEntry;
+    use crate::provider::MemoryProvider;
+    use crate::types::SymbolType;
+
+    fn symbol(name: &str, is_exported: bool) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            symbol_type: SymbolType::Function,
+            signature: String::new(),
+            is_exported,
+            file_path: "src/auth.ts".to_string(),
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_coverage() {
+        let map = SintesiMap {
+            entries: vec![SintesiMapEntry {
+                id: "a".to_string(),
+                code_ref: "src/auth.ts#login".to_string(),
+                doc_file: "docs/auth.md".to_string(),
+                code_signature_hash: "h".to_string(),
+                last_updated: 0,
+                symbol_id: None,
+                symbol_key: None,
+                deprecated: false,
+                content_hash: None,
+                code_signature: None,
+            }],
+            ..SintesiMap::new()
+        };
+        let symbols = [
+            symbol("login", true),
+            symbol("logout", true),
+            symbol("helper", false),
+            symbol("refresh", true),
+        ];
+
+        let coverage = Coverage::compute(&map, &symbols);
+        assert_eq!(
+            coverage,
+            Coverage {
+                documented: 1,
+                total: 3
+            }
+        );
+        assert_eq!(coverage.percent(), 33);
+        assert_eq!(Coverage::default().percent(), 100);
+
+        // Written before `auth.js` became `auth.ts`
+        let converted = SintesiMap {
+            entries: vec![SintesiMapEntry {
+                code_ref: "src/auth.js#logout".to_string(),
+                symbol_id: Some(symbol_id("src/auth.js", "logout", SymbolType::Function)),
+                ..map.entries[0].clone()
+            }],
+            ..SintesiMap::new()
+        };
+        assert_eq!(Coverage::compute(&converted, &symbols).documented, 1);
+    }
+
+    #[test]
+    fn test_coverage_through_reexports() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/index.ts", "export { login } from './auth';");
+        provider.set(
+            "src/auth.ts",
+            "export function login() {}\nexport function logout() {}",
+        );
+        let map = SintesiMap {
+            entries: vec![SintesiMapEntry {
+                id: "a".to_string(),
+                code_ref: "src/index.ts#login".to_string(),
+                doc_file: "docs/auth.md".to_string(),
+                code_signature_hash: "h".to_string(),
+                last_updated: 0,
+                symbol_id: None,
+                symbol_key: None,
+                deprecated: false,
+                content_hash: None,
+                code_signature: None,
+            }],
+            ..SintesiMap::new()
+        };
+        let symbols = [symbol("login", true), symbol("logout", true)];
+
+        assert_eq!(Coverage::compute(&map, &symbols).documented, 0);
+        assert_eq!(
+            Coverage::compute_resolved(&map, &symbols, &provider),
+            Coverage {
+                documented: 1,
+                total: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,496 @@
+//! GitHub/GitLab API reporter
+//!
+//! Posts drift feedback on pull/merge requests directly through the forge's
+//! REST API, so CI setups need no JavaScript glue:
+//!
+//! - A single PR comment, updated in place on every run (found by a hidden
+//!   marker, on whichever page of comments it is) instead of piling up new
+//!   comments
+//! - A commit status (`sintesi/drift`) that branch protection can require
+//!
+//! Configuration comes from the environment CI already provides:
+//!
+//! | Forge  | Token                           | Repository          | API URL                        |
+//! |--------|---------------------------------|---------------------|--------------------------------|
+//! | GitHub | `GITHUB_TOKEN`                  | `GITHUB_REPOSITORY` | `GITHUB_API_URL` (optional)    |
+//! | GitLab | `GITLAB_TOKEN`                  | `CI_PROJECT_ID`     | `CI_API_V4_URL` (optional)     |
+
+use crate::drift::{DriftResult, DriftStatus};
+use crate::error::Error;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+/// Hidden marker identifying the comment this reporter owns
+const COMMENT_MARKER: &str = "<!-- sintesi:report -->";
+
+/// Commit status context / name
+const STATUS_CONTEXT: &str = "sintesi/drift";
+
+/// Which forge API to talk to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+}
+
+/// Connection settings for a forge API
+#[derive(Debug, Clone)]
+pub struct ForgeConfig {
+    /// Forge flavor
+    pub kind: ForgeKind,
+    /// API base URL (e.g. `https://api.github.com`)
+    pub api_url: String,
+    /// Access token
+    pub token: String,
+    /// `owner/repo` on GitHub, numeric ID or `group/project` path on GitLab
+    pub repository: String,
+}
+
+impl ForgeConfig {
+    /// Read configuration from CI environment variables
+    ///
+    /// GitHub is tried first, then GitLab. Returns `None` when neither has
+    /// both a token and a repository.
+    pub fn from_env() -> Option<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+    }
+
+    fn from_lookup(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        if let (Some(token), Some(repository)) = (var("GITHUB_TOKEN"), var("GITHUB_REPOSITORY")) {
+            return Some(Self {
+                kind: ForgeKind::GitHub,
+                api_url: var("GITHUB_API_URL").unwrap_or_else(|| "https://api.github.com".into()),
+                token,
+                repository,
+            });
+        }
+        if let (Some(token), Some(repository)) = (var("GITLAB_TOKEN"), var("CI_PROJECT_ID")) {
+            return Some(Self {
+                kind: ForgeKind::GitLab,
+                api_url: var("CI_API_V4_URL").unwrap_or_else(|| "https://gitlab.com/api/v4".into()),
+                token,
+                repository,
+            });
+        }
+        None
+    }
+}
+
+/// Commit status state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitState {
+    Pending,
+    Success,
+    Failure,
+    Error,
+}
+
+impl CommitState {
+    fn as_str(&self, kind: ForgeKind) -> &'static str {
+        match (self, kind) {
+            (CommitState::Pending, _) => "pending",
+            (CommitState::Success, _) => "success",
+            (CommitState::Failure, ForgeKind::GitHub) => "failure",
+            (CommitState::Failure, ForgeKind::GitLab) => "failed",
+            (CommitState::Error, ForgeKind::GitHub) => "error",
+            (CommitState::Error, ForgeKind::GitLab) => "failed",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Comment {
+    id: u64,
+    #[serde(default)]
+    body: String,
+}
+
+/// Posts PR comments and commit statuses to GitHub or GitLab
+pub struct ForgeReporter {
+    config: ForgeConfig,
+    agent: ureq::Agent,
+}
+
+impl ForgeReporter {
+    /// Create a reporter for the given forge
+    pub fn new(config: ForgeConfig) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("sintesi/", env!("CARGO_PKG_VERSION")))
+            .build();
+        Self { config, agent }
+    }
+
+    /// Create or update this reporter's comment on a pull/merge request
+    ///
+    /// # Arguments
+    /// * `number` - PR number (GitHub) or merge request IID (GitLab)
+    /// * `body` - Markdown comment body
+    pub fn upsert_pr_comment(&self, number: u64, body: &str) -> Result<(), Error> {
+        let body = format!("{}\n{}", COMMENT_MARKER, body);
+        let comments_url = match self.config.kind {
+            ForgeKind::GitHub => format!("{}/issues/{}/comments", self.repo_url(), number),
+            ForgeKind::GitLab => format!("{}/merge_requests/{}/notes", self.repo_url(), number),
+        };
+
+        let payload = json!({ "body": body });
+        match self.find_comment(&comments_url)? {
+            Some(id) => {
+                let (method, url) = match self.config.kind {
+                    ForgeKind::GitHub => (
+                        "PATCH",
+                        format!("{}/issues/comments/{}", self.repo_url(), id),
+                    ),
+                    ForgeKind::GitLab => ("PUT", format!("{}/{}", comments_url, id)),
+                };
+                self.request(method, &url).send_json(payload)
+            }
+            None => self.request("POST", &comments_url).send_json(payload),
+        }
+        .map_err(api_error)?;
+
+        Ok(())
+    }
+
+    /// ID of this reporter's comment, reading pages of comments until it is
+    /// found or there are no more
+    fn find_comment(&self, comments_url: &str) -> Result<Option<u64>, Error> {
+        let mut next = Some(format!("{}?per_page=100", comments_url));
+        while let Some(url) = next {
+            let response = self.request("GET", &url).call().map_err(api_error)?;
+            next = self.next_page(&response, comments_url);
+            let comments: Vec<Comment> = response
+                .into_json()
+                .map_err(|e| Error::from_reason(format!("Invalid API response: {}", e)))?;
+            if let Some(comment) = comments.iter().find(|c| c.body.contains(COMMENT_MARKER)) {
+                return Ok(Some(comment.id));
+            }
+        }
+        Ok(None)
+    }
+
+    /// URL of the page after `response`: GitHub links it (`Link: <url>;
+    /// rel="next"`), GitLab gives its number (`X-Next-Page`, empty on the
+    /// last page)
+    fn next_page(&self, response: &ureq::Response, comments_url: &str) -> Option<String> {
+        match self.config.kind {
+            ForgeKind::GitHub => response.header("Link")?.split(',').find_map(|link| {
+                let (url, rel) = link.split_once(';')?;
+                rel.contains(r#"rel="next""#)
+                    .then(|| url.trim().trim_start_matches('<').trim_end_matches('>'))
+                    .map(str::to_string)
+            }),
+            ForgeKind::GitLab => {
+                let page = response.header("X-Next-Page")?.trim();
+                (!page.is_empty()).then(|| format!("{}?per_page=100&page={}", comments_url, page))
+            }
+        }
+    }
+
+    /// Set the `sintesi/drift` commit status
+    pub fn set_commit_status(
+        &self,
+        sha: &str,
+        state: CommitState,
+        description: &str,
+        target_url: Option<&str>,
+    ) -> Result<(), Error> {
+        let state = state.as_str(self.config.kind);
+        let payload = match self.config.kind {
+            ForgeKind::GitHub => json!({
+                "state": state,
+                "context": STATUS_CONTEXT,
+                "description": description,
+                "target_url": target_url,
+            }),
+            ForgeKind::GitLab => json!({
+                "state": state,
+                "name": STATUS_CONTEXT,
+                "description": description,
+                "target_url": target_url,
+            }),
+        };
+
+        self.request("POST", &format!("{}/statuses/{}", self.repo_url(), sha))
+            .send_json(payload)
+            .map_err(api_error)?;
+        Ok(())
+    }
+
+    fn repo_url(&self) -> String {
+        let api = self.config.api_url.trim_end_matches('/');
+        match self.config.kind {
+            ForgeKind::GitHub => format!("{}/repos/{}", api, self.config.repository),
+            ForgeKind::GitLab => format!(
+                "{}/projects/{}",
+                api,
+                self.config.repository.replace('/', "%2F")
+            ),
+        }
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        let request = self.agent.request(method, url);
+        match self.config.kind {
+            ForgeKind::GitHub => request
+                .set("Authorization", &format!("Bearer {}", self.config.token))
+                .set("Accept", "application/vnd.github+json"),
+            ForgeKind::GitLab => request.set("PRIVATE-TOKEN", &self.config.token),
+        }
+    }
+}
+
+fn api_error(err: ureq::Error) -> Error {
+    match err {
+        ureq::Error::Status(code, response) => {
+            let url = response.get_url().to_string();
+            Error::from_reason(format!(
+                "API request to {} failed with status {}: {}",
+                url,
+                code,
+                response.into_string().unwrap_or_default()
+            ))
+        }
+        other => Error::from_reason(format!("API request failed: {}", other)),
+    }
+}
+
+/// Render a drift result as a PR comment body
+pub fn drift_comment(result: &DriftResult) -> String {
+    let drifted: Vec<_> = result.drifted().collect();
+    let ignored = ignored_section(result);
+    if drifted.is_empty() {
+        return format!(
+            "### ✅ Sintesi: documentation in sync\n\nAll {} documented symbols match their documentation.\n{}",
+            result.entries.len() - result.ignored().count(),
+            ignored
+        );
+    }
+
+    let mut body = format!(
+        "### ⚠️ Sintesi: {} of {} documented symbols drifted\n\n| Symbol | Documentation | Status |\n|---|---|---|\n",
+        drifted.len(),
+        result.entries.len()
+    );
+    for entry in drifted {
+        let status = match (entry.status, entry.deprecation) {
+            (_, Some(change)) => change.label(),
+            (DriftStatus::Drifted, None) => "signature changed",
+            (DriftStatus::Removed, None) => "symbol removed",
+            (DriftStatus::InSync | DriftStatus::Ignored, None) => continue,
+        };
+        body.push_str(&format!(
+            "| `{}` | `{}` (anchor `{}`) | {} |\n",
+            entry.code_ref, entry.doc_file, entry.entry_id, status
+        ));
+    }
+    body.push_str(&ignored);
+    body
+}
+
+/// List entries excluded by ignore directives, with their reasons
+fn ignored_section(result: &DriftResult) -> String {
+    let mut section = String::new();
+    for entry in result.ignored() {
+        if section.is_empty() {
+            section.push_str(
+                "\n<details><summary>Ignored symbols</summary>\n\n| Symbol | Reason |\n|---|---|\n",
+            );
+        }
+        section.push_str(&format!(
+            "| `{}` | {} |\n",
+            entry.code_ref,
+            entry.ignore_reason.as_deref().unwrap_or("no reason given")
+        ));
+    }
+    if !section.is_empty() {
+        section.push_str("\n</details>\n");
+    }
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::DriftEntry;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve canned JSON responses, one per connection, returning each
+    /// request as "METHOD path body"
+    fn serve(responses: Vec<&'static str>) -> (String, thread::JoinHandle<Vec<String>>) {
+        serve_with_headers(responses.into_iter().map(|r| ("", r)).collect())
+    }
+
+    /// Serve canned responses with extra header lines, `{url}` in them
+    /// replaced by the server's URL
+    fn serve_with_headers(
+        responses: Vec<(&'static str, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server_url = url.clone();
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (headers, response) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let parts: Vec<&str> = request_line.split_whitespace().collect();
+                requests.push(format!(
+                    "{} {} {}",
+                    parts[0],
+                    parts[1],
+                    String::from_utf8(body).unwrap()
+                ));
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+                    response.len(),
+                    headers.replace("{url}", &server_url),
+                    response
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn reporter(kind: ForgeKind, api_url: String, repository: &str) -> ForgeReporter {
+        ForgeReporter::new(ForgeConfig {
+            kind,
+            api_url,
+            token: "t".to_string(),
+            repository: repository.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_github_updates_existing_comment() {
+        let (url, server) = serve(vec![
+            r#"[{"id": 1, "body": "lgtm"}, {"id": 7, "body": "<!-- sintesi:report -->\nold"}]"#,
+            "{}",
+        ]);
+        reporter(ForgeKind::GitHub, url, "acme/api")
+            .upsert_pr_comment(12, "new")
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /repos/acme/api/issues/12/comments?per_page=100"));
+        assert!(requests[1].starts_with("PATCH /repos/acme/api/issues/comments/7 "));
+        assert!(requests[1].contains(r#""body":"<!-- sintesi:report -->\nnew""#));
+    }
+
+    #[test]
+    fn test_comment_is_found_on_later_pages() {
+        let (url, server) = serve_with_headers(vec![
+            (
+                "Link: <{url}/repositories/1/issues/12/comments?per_page=100&page=2>; rel=\"next\", <{url}/last>; rel=\"last\"\r\n",
+                r#"[{"id": 1, "body": "lgtm"}]"#,
+            ),
+            ("", r#"[{"id": 7, "body": "<!-- sintesi:report -->\nold"}]"#),
+            ("", "{}"),
+        ]);
+        reporter(ForgeKind::GitHub, url, "acme/api")
+            .upsert_pr_comment(12, "new")
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(
+            requests[1].starts_with("GET /repositories/1/issues/12/comments?per_page=100&page=2 ")
+        );
+        assert!(requests[2].starts_with("PATCH /repos/acme/api/issues/comments/7 "));
+
+        let (url, server) = serve_with_headers(vec![
+            ("X-Next-Page: 2\r\n", r#"[{"id": 1, "body": "lgtm"}]"#),
+            ("X-Next-Page: \r\n", "[]"),
+            ("", "{}"),
+        ]);
+        reporter(ForgeKind::GitLab, url, "group/api")
+            .upsert_pr_comment(3, "report")
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[1]
+            .starts_with("GET /projects/group%2Fapi/merge_requests/3/notes?per_page=100&page=2 "));
+        assert!(requests[2].starts_with("POST /projects/group%2Fapi/merge_requests/3/notes "));
+    }
+
+    #[test]
+    fn test_gitlab_creates_comment_and_status() {
+        let (url, server) = serve(vec!["[]", "{}", "{}"]);
+        let reporter = reporter(ForgeKind::GitLab, url, "group/api");
+        reporter.upsert_pr_comment(3, "report").unwrap();
+        reporter
+            .set_commit_status("abc", CommitState::Failure, "2 drifted", None)
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[1].starts_with("POST /projects/group%2Fapi/merge_requests/3/notes "));
+        assert!(requests[2].starts_with("POST /projects/group%2Fapi/statuses/abc "));
+        assert!(requests[2].contains(r#""state":"failed""#));
+        assert!(requests[2].contains(r#""name":"sintesi/drift""#));
+    }
+
+    #[test]
+    fn test_config_from_env_lookup() {
+        let vars: HashMap<&str, &str> =
+            HashMap::from([("GITLAB_TOKEN", "x"), ("CI_PROJECT_ID", "42")]);
+        let config = ForgeConfig::from_lookup(|k| vars.get(k).map(|v| v.to_string())).unwrap();
+        assert_eq!(config.kind, ForgeKind::GitLab);
+        assert_eq!(config.api_url, "https://gitlab.com/api/v4");
+        assert!(ForgeConfig::from_lookup(|_| None).is_none());
+    }
+
+    #[test]
+    fn test_drift_comment() {
+        let entry = |status| DriftEntry {
+            entry_id: "a".to_string(),
+            code_ref: "src/auth.ts#login".to_string(),
+            doc_file: "docs/auth.md".to_string(),
+            status,
+            expected_hash: String::new(),
+            current_hash: None,
+            ignore_reason: None,
+            deprecation: None,
+            current_signature: None,
+            severity: None,
+            test_count: None,
+        };
+        let clean = DriftResult {
+            entries: vec![entry(DriftStatus::InSync)],
+            ..Default::default()
+        };
+        assert!(drift_comment(&clean).contains("documentation in sync"));
+
+        let drifted = DriftResult {
+            entries: vec![entry(DriftStatus::Removed)],
+            ..Default::default()
+        };
+        let body = drift_comment(&drifted);
+        assert!(body.contains("1 of 1 documented symbols drifted"));
+        assert!(
+            body.contains("| `src/auth.ts#login` | `docs/auth.md` (anchor `a`) | symbol removed |")
+        );
+    }
+}
@@ -0,0 +1,385 @@
+//! CI gating
+//!
+//! `evaluate` combines everything a run found (drift, validation errors,
+//! coverage, freshness policy violations) into one pass/warn/fail status
+//! with the reasons behind it, so every CI integration gates the same way.
+//!
+//! The gate is configured under the `gate` key of `sintesi.config.json`:
+//!
+//! ```json
+//! { "gate": { "drift": "fail", "minCoverage": 60, "warnCoverage": 80 } }
+//! ```
+//!
+//! Freshness policy violations fail or warn according to their policy's
+//! severity.
+//!
+//! With `nonBreakingDrift`, drift classified as non-breaking (an optional
+//! parameter added, a symbol deprecated) gets its own status, so CI can fail
+//! on breaking drift only:
+//!
+//! ```json
+//! { "gate": { "drift": "fail", "nonBreakingDrift": "warn" } }
+//! ```
+
+use super::coverage::Coverage;
+use crate::ast::diff::ChangeSeverity;
+use crate::drift::{DriftResult, PolicySeverity, PolicyViolation};
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Outcome of a run, ordered from best to worst
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RunStatus {
+    #[default]
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl RunStatus {
+    /// Lowercase name (`pass`, `warn`, `fail`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunStatus::Pass => "pass",
+            RunStatus::Warn => "warn",
+            RunStatus::Fail => "fail",
+        }
+    }
+
+    /// Process exit code: 1 for `Fail`, 0 otherwise
+    pub fn exit_code(&self) -> i32 {
+        i32::from(*self == RunStatus::Fail)
+    }
+}
+
+/// What a run found
+#[derive(Debug, Clone, Default)]
+pub struct RunResult {
+    /// Drift of the map entries
+    pub drift: DriftResult,
+    /// Anchor extraction, map, and configuration errors
+    pub validation_errors: Vec<String>,
+    /// Documentation coverage, if it was computed
+    pub coverage: Option<Coverage>,
+    /// Freshness policy violations
+    pub policy_violations: Vec<PolicyViolation>,
+}
+
+/// How findings map to a status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GatePolicy {
+    /// Status when map entries are drifted or removed
+    pub drift: RunStatus,
+    /// Status for non-breaking drift, if it is gated apart from `drift`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_breaking_drift: Option<RunStatus>,
+    /// Status when there are validation errors
+    pub validation: RunStatus,
+    /// Fail below this coverage percentage
+    pub min_coverage: Option<u32>,
+    /// Warn below this coverage percentage
+    pub warn_coverage: Option<u32>,
+}
+
+impl Default for GatePolicy {
+    fn default() -> Self {
+        Self {
+            drift: RunStatus::Fail,
+            non_breaking_drift: None,
+            validation: RunStatus::Fail,
+            min_coverage: None,
+            warn_coverage: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GateConfig {
+    #[serde(default)]
+    gate: GatePolicy,
+}
+
+impl GatePolicy {
+    /// Default policy: drift and validation errors fail, coverage is not gated
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the status for drift
+    pub fn drift(mut self, status: RunStatus) -> Self {
+        self.drift = status;
+        self
+    }
+
+    /// Set the status for non-breaking drift, leaving `drift` for breaking
+    /// and unclassified drift
+    pub fn non_breaking_drift(mut self, status: RunStatus) -> Self {
+        self.non_breaking_drift = Some(status);
+        self
+    }
+
+    /// Set the status for validation errors
+    pub fn validation(mut self, status: RunStatus) -> Self {
+        self.validation = status;
+        self
+    }
+
+    /// Fail below a coverage percentage
+    pub fn min_coverage(mut self, percent: u32) -> Self {
+        self.min_coverage = Some(percent);
+        self
+    }
+
+    /// Warn below a coverage percentage
+    pub fn warn_coverage(mut self, percent: u32) -> Self {
+        self.warn_coverage = Some(percent);
+        self
+    }
+
+    /// Load the `gate` key of a config file (the default if there is none)
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let Ok(content) = fs::read_to_string(path) else {
+            return Ok(Self::default());
+        };
+        let config: GateConfig = serde_json::from_str(&content)
+            .map_err(|e| Error::from_reason(format!("Invalid config {}: {}", path.display(), e)))?;
+        Ok(config.gate)
+    }
+}
+
+/// Why a run did not pass
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateReason {
+    /// Status this reason demands
+    pub status: RunStatus,
+    /// Check that produced it (`drift`, `validation`, `coverage`, `policy`)
+    pub check: &'static str,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+/// Final status of a run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Evaluation {
+    /// Worst status demanded by any reason
+    pub status: RunStatus,
+    /// Reasons for a status other than `Pass`, in check order
+    pub reasons: Vec<GateReason>,
+}
+
+/// Combine a run's findings into a final status
+pub fn evaluate(run: &RunResult, policy: &GatePolicy) -> Evaluation {
+    let mut reasons = Vec::new();
+    let mut add = |status: RunStatus, check: &'static str, message: String| {
+        if status != RunStatus::Pass {
+            reasons.push(GateReason {
+                status,
+                check,
+                message,
+            });
+        }
+    };
+
+    let total = run.drift.entries.len();
+    match policy.non_breaking_drift {
+        None => {
+            let drifted = run.drift.drifted().count();
+            if drifted > 0 {
+                add(
+                    policy.drift,
+                    "drift",
+                    format!("{} of {} documented symbols drifted", drifted, total),
+                );
+            }
+        }
+        Some(non_breaking_status) => {
+            let (non_breaking, breaking): (Vec<_>, Vec<_>) = run
+                .drift
+                .drifted()
+                .partition(|e| e.severity == Some(ChangeSeverity::NonBreaking));
+            if !breaking.is_empty() {
+                add(
+                    policy.drift,
+                    "drift",
+                    format!(
+                        "{} of {} documented symbols drifted with breaking changes",
+                        breaking.len(),
+                        total
+                    ),
+                );
+            }
+            if !non_breaking.is_empty() {
+                add(
+                    non_breaking_status,
+                    "drift",
+                    format!(
+                        "{} of {} documented symbols drifted with non-breaking changes",
+                        non_breaking.len(),
+                        total
+                    ),
+                );
+            }
+        }
+    }
+
+    for error in &run.validation_errors {
+        add(policy.validation, "validation", error.clone());
+    }
+
+    if let Some(coverage) = run.coverage {
+        let percent = coverage.percent();
+        let below = |threshold: Option<u32>| threshold.is_some_and(|t| percent < t);
+        let status = if below(policy.min_coverage) {
+            Some((RunStatus::Fail, policy.min_coverage))
+        } else if below(policy.warn_coverage) {
+            Some((RunStatus::Warn, policy.warn_coverage))
+        } else {
+            None
+        };
+        if let Some((status, Some(threshold))) = status {
+            add(
+                status,
+                "coverage",
+                format!(
+                    "Documentation coverage {}% is below {}% ({} of {} exported symbols)",
+                    percent, threshold, coverage.documented, coverage.total
+                ),
+            );
+        }
+    }
+
+    for violation in &run.policy_violations {
+        let status = match violation.severity {
+            PolicySeverity::Warning => RunStatus::Warn,
+            PolicySeverity::Error => RunStatus::Fail,
+        };
+        add(status, "policy", violation.to_string());
+    }
+
+    Evaluation {
+        status: reasons
+            .iter()
+            .map(|r| r.status)
+            .max()
+            .unwrap_or(RunStatus::Pass),
+        reasons,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::{DriftEntry, DriftStatus, ViolationKind};
+
+    fn drifted_run() -> RunResult {
+        RunResult {
+            drift: DriftResult {
+                entries: vec![DriftEntry {
+                    entry_id: "login".to_string(),
+                    code_ref: "src/auth.ts#login".to_string(),
+                    doc_file: "docs/api.md".to_string(),
+                    status: DriftStatus::Drifted,
+                    expected_hash: "a".to_string(),
+                    current_hash: Some("b".to_string()),
+                    ignore_reason: None,
+                    deprecation: None,
+                    current_signature: None,
+                    severity: None,
+                    test_count: None,
+                }],
+                ..Default::default()
+            },
+            coverage: Some(Coverage {
+                documented: 7,
+                total: 10,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_worst_reason_decides() {
+        let run = drifted_run();
+        let evaluation = evaluate(&run, &GatePolicy::new());
+        assert_eq!(evaluation.status, RunStatus::Fail);
+        assert_eq!(evaluation.reasons.len(), 1);
+        assert_eq!(evaluation.status.exit_code(), 1);
+
+        let lenient = GatePolicy::new()
+            .drift(RunStatus::Warn)
+            .warn_coverage(80)
+            .min_coverage(50);
+        let evaluation = evaluate(&run, &lenient);
+        assert_eq!(evaluation.status, RunStatus::Warn);
+        let checks: Vec<&str> = evaluation.reasons.iter().map(|r| r.check).collect();
+        assert_eq!(checks, ["drift", "coverage"]);
+
+        let mut run = run;
+        run.validation_errors
+            .push("docs/api.md: unclosed anchor \"a\"".to_string());
+        run.policy_violations.push(PolicyViolation {
+            policy: "review".to_string(),
+            severity: PolicySeverity::Warning,
+            kind: ViolationKind::Age,
+            entry_id: "login".to_string(),
+            code_ref: "src/auth.ts#login".to_string(),
+            doc_file: "docs/api.md".to_string(),
+            days: 200,
+            limit_days: 180,
+        });
+        let evaluation = evaluate(&run, &lenient.min_coverage(75));
+        assert_eq!(evaluation.status, RunStatus::Fail);
+        let checks: Vec<&str> = evaluation.reasons.iter().map(|r| r.check).collect();
+        assert_eq!(checks, ["drift", "validation", "coverage", "policy"]);
+
+        assert_eq!(
+            evaluate(&RunResult::default(), &GatePolicy::new()),
+            Evaluation::default()
+        );
+    }
+
+    #[test]
+    fn test_non_breaking_drift_gated_apart() {
+        let mut run = drifted_run();
+        let breaking_only = GatePolicy::new().non_breaking_drift(RunStatus::Warn);
+        // Unclassified drift counts as breaking
+        assert_eq!(evaluate(&run, &breaking_only).status, RunStatus::Fail);
+
+        run.drift.entries[0].severity = Some(ChangeSeverity::NonBreaking);
+        let evaluation = evaluate(&run, &breaking_only);
+        assert_eq!(evaluation.status, RunStatus::Warn);
+        assert_eq!(
+            evaluation.reasons[0].message,
+            "1 of 1 documented symbols drifted with non-breaking changes"
+        );
+        assert_eq!(evaluate(&run, &GatePolicy::new()).status, RunStatus::Fail);
+    }
+
+    #[test]
+    fn test_policy_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sintesi.config.json");
+        assert_eq!(GatePolicy::load(&path).unwrap(), GatePolicy::new());
+
+        fs::write(
+            &path,
+            r#"{"docsDir": "docs", "gate": {"drift": "warn", "minCoverage": 60}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            GatePolicy::load(&path).unwrap(),
+            GatePolicy::new().drift(RunStatus::Warn).min_coverage(60)
+        );
+
+        fs::write(&path, r#"{"gate": {"nonBreakingDrift": "pass"}}"#).unwrap();
+        assert_eq!(
+            GatePolicy::load(&path).unwrap(),
+            GatePolicy::new().non_breaking_drift(RunStatus::Pass)
+        );
+    }
+}
@@ -0,0 +1,411 @@
+//! HTML report
+//!
+//! Renders project drift, documentation coverage, and the anchor inventory as
+//! a single self-contained HTML file (inline CSS and JS, no external assets),
+//! suitable for uploading as a CI artifact. Tables sort by clicking a column
+//! header, and files link into the repository when a base URL is configured.
+//! Grouped by package, the report adds a package overview and one section per
+//! workspace package with its own summary, drift, and anchors.
+
+use super::coverage::Coverage;
+use super::packages::PackageReport;
+use crate::content::SintesiAnchor;
+use crate::drift::{DriftResult, DriftStatus};
+use crate::error::Error;
+use crate::sandbox::check_path;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const STYLE: &str = r#"body{font-family:system-ui,-apple-system,sans-serif;margin:2rem auto;max-width:1100px;padding:0 1rem;color:#1f2328}
+h1{margin-bottom:.25rem}.meta{color:#656d76;margin-top:0}
+.summary{display:flex;gap:1rem;margin:1.5rem 0}.card{border:1px solid #d0d7de;border-radius:6px;padding:1rem 1.5rem;flex:1}
+.card .value{font-size:2rem;font-weight:600}.card .label{color:#656d76}
+table{border-collapse:collapse;width:100%;margin-bottom:2rem}th,td{border-bottom:1px solid #d0d7de;padding:.4rem .6rem;text-align:left;font-size:.9rem}
+th{cursor:pointer;user-select:none;background:#f6f8fa}th:after{content:" \2195";color:#8c959f}
+code{font-size:.85rem}.inSync{color:#1a7f37}.drifted{color:#bc4c00}.removed{color:#cf222e}.ignored,.none,.reason{color:#656d76}"#;
+
+const SCRIPT: &str = r#"document.querySelectorAll("table.sortable th").forEach(function(th,col){th.addEventListener("click",function(){var body=th.closest("table").tBodies[0],asc=th.dataset.asc!=="true";th.dataset.asc=asc;Array.from(body.rows).sort(function(a,b){var x=a.cells[col].textContent,y=b.cells[col].textContent;return (asc?1:-1)*x.localeCompare(y,undefined,{numeric:true});}).forEach(function(r){body.appendChild(r);});});});"#;
+
+/// A self-contained HTML report of documentation health
+pub struct HtmlReport {
+    title: String,
+    repository_url: Option<String>,
+    drift: DriftResult,
+    coverage: Coverage,
+    anchors: Vec<SintesiAnchor>,
+    packages: Vec<PackageReport>,
+}
+
+impl HtmlReport {
+    /// Create a report
+    ///
+    /// # Arguments
+    /// * `drift` - Drift result for the project's map
+    /// * `coverage` - Documentation coverage of exported symbols
+    /// * `anchors` - Anchor inventory (file paths relative to the repository root)
+    pub fn new(drift: DriftResult, coverage: Coverage, anchors: Vec<SintesiAnchor>) -> Self {
+        Self {
+            title: "Sintesi documentation report".to_string(),
+            repository_url: None,
+            drift,
+            coverage,
+            anchors,
+            packages: Vec::new(),
+        }
+    }
+
+    /// Set the report title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Link files into the repository, e.g. `https://github.com/acme/api/blob/main`
+    pub fn repository_url(mut self, url: impl Into<String>) -> Self {
+        self.repository_url = Some(url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Group drift, coverage, and anchors by workspace package
+    pub fn packages(mut self, packages: Vec<PackageReport>) -> Self {
+        self.packages = packages;
+        self
+    }
+
+    /// Render the report as HTML
+    pub fn render(&self) -> String {
+        let status_by_id: HashMap<&str, DriftStatus> = self
+            .drift
+            .entries
+            .iter()
+            .map(|e| (e.entry_id.as_str(), e.status))
+            .collect();
+
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<p class=\"meta\">{anchors} anchors, {entries} mapped symbols</p>\n",
+            title = escape_html(&self.title),
+            anchors = self.anchors.len(),
+            entries = self.drift.entries.len(),
+        );
+
+        html.push_str(&summary(&self.drift, self.coverage));
+        if self.packages.is_empty() {
+            html.push_str(&self.drift_table(&self.drift, "h2"));
+            html.push_str(&self.anchor_table(&self.anchors, &status_by_id, "h2"));
+        } else {
+            html.push_str(&self.package_overview());
+            for (index, report) in self.packages.iter().enumerate() {
+                html.push_str(&format!(
+                    "<h2 id=\"package-{}\">{}</h2>\n",
+                    index,
+                    escape_html(&report.package.name)
+                ));
+                if !report.package.path.is_empty() {
+                    html.push_str(&format!(
+                        "<p class=\"meta\"><code>{}</code></p>\n",
+                        escape_html(&report.package.path)
+                    ));
+                }
+                html.push_str(&summary(&report.drift, report.coverage));
+                html.push_str(&self.drift_table(&report.drift, "h3"));
+                html.push_str(&self.anchor_table(&report.anchors, &status_by_id, "h3"));
+            }
+        }
+
+        html.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SCRIPT));
+        html
+    }
+
+    /// Render the report and write it to `path`
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        check_path("write report", path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                Error::from_reason(format!("Failed to create {}: {}", parent.display(), e))
+            })?;
+        }
+        fs::write(path, self.render()).map_err(|e| {
+            Error::from_reason(format!("Failed to write report {}: {}", path.display(), e))
+        })
+    }
+
+    /// Table of packages with their coverage, drift, and anchor counts
+    fn package_overview(&self) -> String {
+        let mut html = String::from("<h2>Packages</h2>\n<table class=\"sortable\">\n<thead><tr><th>Package</th><th>Coverage</th><th>Drifted</th><th>In sync</th><th>Anchors</th></tr></thead>\n<tbody>\n");
+        for (index, report) in self.packages.iter().enumerate() {
+            let drifted = report.drift.drifted().count();
+            let ignored = report.drift.ignored().count();
+            html.push_str(&format!(
+                "<tr><td><a href=\"#package-{}\">{}</a></td><td>{}% ({} of {})</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                index,
+                escape_html(&report.package.name),
+                report.coverage.percent(),
+                report.coverage.documented,
+                report.coverage.total,
+                drifted,
+                report.drift.entries.len() - drifted - ignored,
+                report.anchors.len()
+            ));
+        }
+        html.push_str("</tbody>\n</table>\n");
+        html
+    }
+
+    /// Drift table under a heading of the given level
+    fn drift_table(&self, drift: &DriftResult, heading: &str) -> String {
+        let mut html = format!("<{h}>Drift</{h}>\n<table class=\"sortable\">\n<thead><tr><th>Status</th><th>Symbol</th><th>Documentation</th><th>Anchor</th></tr></thead>\n<tbody>\n", h = heading);
+        for entry in &drift.entries {
+            let reason = entry
+                .ignore_reason
+                .as_deref()
+                .or(entry.deprecation.map(|change| change.label()))
+                .or(match (entry.status, entry.test_count) {
+                    (DriftStatus::Drifted, Some(0)) => Some("untested"),
+                    _ => None,
+                })
+                .map(|reason| format!(" <span class=\"reason\">({})</span>", escape_html(reason)))
+                .unwrap_or_default();
+            html.push_str(&format!(
+                "<tr><td>{}{}</td><td>{}</td><td>{}</td><td><code>{}</code></td></tr>\n",
+                status_cell(Some(entry.status)),
+                reason,
+                self.code_ref_link(&entry.code_ref),
+                self.file_link(&entry.doc_file, None),
+                escape_html(&entry.entry_id)
+            ));
+        }
+        html.push_str("</tbody>\n</table>\n");
+        html
+    }
+
+    /// Anchor table under a heading of the given level, sorted by file and line
+    fn anchor_table(
+        &self,
+        anchors: &[SintesiAnchor],
+        status_by_id: &HashMap<&str, DriftStatus>,
+        heading: &str,
+    ) -> String {
+        let mut html = format!("<{h}>Anchors</{h}>\n<table class=\"sortable\">\n<thead><tr><th>Anchor</th><th>File</th><th>Lines</th><th>Symbol</th><th>Status</th></tr></thead>\n<tbody>\n", h = heading);
+        let mut anchors: Vec<&SintesiAnchor> = anchors.iter().collect();
+        anchors.sort_by(|a, b| (&a.file_path, a.start_line).cmp(&(&b.file_path, b.start_line)));
+        for anchor in anchors {
+            let file = anchor.file_path.to_string_lossy().replace('\\', "/");
+            html.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&anchor.id),
+                self.file_link(&file, Some(anchor.start_line)),
+                anchor.line_span(),
+                anchor
+                    .code_ref
+                    .as_deref()
+                    .map(|code_ref| self.code_ref_link(code_ref))
+                    .unwrap_or_default(),
+                status_cell(status_by_id.get(anchor.id.as_str()).copied())
+            ));
+        }
+        html.push_str("</tbody>\n</table>\n");
+        html
+    }
+
+    /// Link a file (and optionally a 0-indexed line) into the repository
+    fn file_link(&self, file: &str, line: Option<usize>) -> String {
+        let text = format!("<code>{}</code>", escape_html(file));
+        match &self.repository_url {
+            Some(base) => {
+                let fragment = line.map(|l| format!("#L{}", l + 1)).unwrap_or_default();
+                format!(
+                    "<a href=\"{}/{}{}\">{}</a>",
+                    escape_html(base),
+                    escape_html(file),
+                    fragment,
+                    text
+                )
+            }
+            None => text,
+        }
+    }
+
+    /// Link the file part of a `file_path#symbol_name` reference
+    fn code_ref_link(&self, code_ref: &str) -> String {
+        let file = code_ref.split('#').next().unwrap_or(code_ref);
+        match &self.repository_url {
+            Some(base) => format!(
+                "<a href=\"{}/{}\"><code>{}</code></a>",
+                escape_html(base),
+                escape_html(file),
+                escape_html(code_ref)
+            ),
+            None => format!("<code>{}</code>", escape_html(code_ref)),
+        }
+    }
+}
+
+/// Summary cards for coverage and drift
+fn summary(drift: &DriftResult, coverage: Coverage) -> String {
+    let drifted = drift.drifted().count();
+    let ignored = drift.ignored().count();
+    let mut html = String::from("<div class=\"summary\">\n");
+    html.push_str(&card(
+        &format!("{}%", coverage.percent()),
+        &format!(
+            "docs coverage ({} of {} exported symbols)",
+            coverage.documented, coverage.total
+        ),
+    ));
+    html.push_str(&card(&drifted.to_string(), "drifted or removed symbols"));
+    html.push_str(&card(
+        &(drift.entries.len() - drifted - ignored).to_string(),
+        "symbols in sync",
+    ));
+    if ignored > 0 {
+        html.push_str(&card(&ignored.to_string(), "ignored symbols"));
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+fn card(value: &str, label: &str) -> String {
+    format!(
+        "<div class=\"card\"><div class=\"value\">{}</div><div class=\"label\">{}</div></div>\n",
+        escape_html(value),
+        escape_html(label)
+    )
+}
+
+fn status_cell(status: Option<DriftStatus>) -> String {
+    let (class, text) = match status {
+        Some(DriftStatus::InSync) => ("inSync", "in sync"),
+        Some(DriftStatus::Drifted) => ("drifted", "drifted"),
+        Some(DriftStatus::Removed) => ("removed", "removed"),
+        Some(DriftStatus::Ignored) => ("ignored", "ignored"),
+        None => ("none", "unmapped"),
+    };
+    format!("<span class=\"{}\">{}</span>", class, text)
+}
+
+pub(super) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WorkspacePackage;
+    use crate::drift::{DriftEntry, SintesiMap};
+    use crate::provider::MemoryProvider;
+    use std::path::PathBuf;
+
+    fn report() -> HtmlReport {
+        let drift = DriftResult {
+            entries: vec![DriftEntry {
+                entry_id: "a1".to_string(),
+                code_ref: "src/auth.ts#login".to_string(),
+                doc_file: "docs/auth.md".to_string(),
+                status: DriftStatus::Drifted,
+                expected_hash: "x".to_string(),
+                current_hash: Some("y".to_string()),
+                ignore_reason: None,
+                deprecation: None,
+                current_signature: None,
+                severity: None,
+                test_count: None,
+            }],
+            ..Default::default()
+        };
+        let anchors = vec![
+            SintesiAnchor {
+                id: "a1".to_string(),
+                code_ref: Some("src/auth.ts#login".to_string()),
+                file_path: PathBuf::from("docs/auth.md"),
+                start_line: 4,
+                end_line: 9,
+                content: String::new(),
+                parent: None,
+                children: Vec::new(),
+                start_tag_span: 0..0,
+                content_span: 0..0,
+                end_tag_span: 0..0,
+            },
+            SintesiAnchor {
+                id: "<b>".to_string(),
+                code_ref: None,
+                file_path: PathBuf::from("docs/intro.md"),
+                start_line: 0,
+                end_line: 2,
+                content: String::new(),
+                parent: None,
+                children: Vec::new(),
+                start_tag_span: 0..0,
+                content_span: 0..0,
+                end_tag_span: 0..0,
+            },
+        ];
+        HtmlReport::new(
+            drift,
+            Coverage {
+                documented: 1,
+                total: 4,
+            },
+            anchors,
+        )
+    }
+
+    #[test]
+    fn test_render_report() {
+        let html = report().title("API docs").render();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>API docs</title>"));
+        assert!(html.contains("<div class=\"value\">25%</div>"));
+        assert!(html.contains("<span class=\"drifted\">drifted</span>"));
+        assert!(html.contains("<span class=\"none\">unmapped</span>"));
+        assert!(html.contains("<code>&lt;b&gt;</code>"));
+        assert!(!html.contains("<a href"));
+        assert!(!html.contains("<link") && !html.contains("src=\""));
+    }
+
+    #[test]
+    fn test_group_by_package() {
+        let report = report();
+        let packages = PackageReport::split(
+            &[WorkspacePackage {
+                name: "@acme/auth".to_string(),
+                path: "src".to_string(),
+            }],
+            &SintesiMap::new(),
+            &[],
+            &report.drift,
+            &report.anchors,
+            &MemoryProvider::new(),
+        );
+        let html = report.packages(packages).render();
+
+        assert!(html.contains("<h2>Packages</h2>"));
+        assert!(html.contains("<td><a href=\"#package-1\">@acme/auth</a></td><td>100% (0 of 0)</td><td>1</td><td>0</td><td>1</td>"));
+        assert!(html.contains("<h2 id=\"package-0\">(root)</h2>"));
+        assert!(html.contains(
+            "<h2 id=\"package-1\">@acme/auth</h2>\n<p class=\"meta\"><code>src</code></p>"
+        ));
+        assert_eq!(html.matches("<h3>Drift</h3>").count(), 2);
+        assert!(!html.contains("<h2>Drift</h2>"));
+    }
+
+    #[test]
+    fn test_repository_links() {
+        let html = report()
+            .repository_url("https://github.com/acme/api/blob/main/")
+            .render();
+
+        assert!(html.contains(
+            "<a href=\"https://github.com/acme/api/blob/main/docs/auth.md#L5\"><code>docs/auth.md</code></a>"
+        ));
+        assert!(html.contains(
+            "<a href=\"https://github.com/acme/api/blob/main/src/auth.ts\"><code>src/auth.ts#login</code></a>"
+        ));
+    }
+}
@@ -0,0 +1,224 @@
+//! JUnit XML output
+//!
+//! Emits drift results as JUnit XML so CI dashboards that already ingest
+//! test reports show documentation health. Each map entry becomes a test
+//! case, grouped into one test suite per documentation file: in-sync entries
+//! pass, drifted and removed entries fail. Failures of drifted symbols no
+//! test exercises say so.
+
+use crate::drift::{DriftEntry, DriftResult, DriftStatus};
+use crate::error::Error;
+use crate::sandbox::check_path;
+use std::fs;
+use std::path::Path;
+
+/// Render a drift result as a JUnit XML document
+pub fn drift_junit(result: &DriftResult) -> String {
+    // Suites in order of first appearance
+    let mut suites: Vec<(&str, Vec<&DriftEntry>)> = Vec::new();
+    for entry in &result.entries {
+        match suites.iter_mut().find(|(doc, _)| *doc == entry.doc_file) {
+            Some((_, entries)) => entries.push(entry),
+            None => suites.push((&entry.doc_file, vec![entry])),
+        }
+    }
+
+    let failures = result.drifted().count();
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"sintesi-drift\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n",
+        result.entries.len(),
+        failures
+    );
+
+    for (doc_file, entries) in suites {
+        let suite_failures = entries
+            .iter()
+            .filter(|e| matches!(e.status, DriftStatus::Drifted | DriftStatus::Removed))
+            .count();
+        let suite_skipped = entries
+            .iter()
+            .filter(|e| e.status == DriftStatus::Ignored)
+            .count();
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">\n",
+            escape_xml(doc_file),
+            entries.len(),
+            suite_failures,
+            suite_skipped
+        ));
+
+        for entry in entries {
+            let file = entry.code_ref.split('#').next().unwrap_or(&entry.code_ref);
+            let open = format!(
+                "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\"",
+                escape_xml(&entry.code_ref),
+                escape_xml(doc_file),
+                escape_xml(file)
+            );
+            if entry.status == DriftStatus::Ignored {
+                xml.push_str(&format!(
+                    "{}>\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                    open,
+                    escape_xml(entry.ignore_reason.as_deref().unwrap_or("ignored"))
+                ));
+                continue;
+            }
+            let failure = match (entry.status, entry.deprecation) {
+                (DriftStatus::InSync | DriftStatus::Ignored, _) => None,
+                (DriftStatus::Drifted, Some(change)) => Some((
+                    change.as_str(),
+                    format!(
+                        "{} is {} since anchor \"{}\" in {} was written",
+                        entry.code_ref,
+                        change.label(),
+                        entry.entry_id,
+                        doc_file
+                    ),
+                )),
+                (DriftStatus::Drifted, None) => Some((
+                    "drifted",
+                    format!(
+                        "Signature of {} changed since anchor \"{}\" in {} was written",
+                        entry.code_ref, entry.entry_id, doc_file
+                    ),
+                )),
+                (DriftStatus::Removed, _) => Some((
+                    "removed",
+                    format!(
+                        "{} no longer exists but is documented by anchor \"{}\" in {}",
+                        entry.code_ref, entry.entry_id, doc_file
+                    ),
+                )),
+            };
+
+            let failure = failure.map(|(kind, message)| match entry.test_count {
+                Some(0) if entry.status == DriftStatus::Drifted => {
+                    (kind, format!("{}; no test exercises it", message))
+                }
+                _ => (kind, message),
+            });
+
+            match failure {
+                None => xml.push_str(&format!("{}/>\n", open)),
+                Some((kind, message)) => xml.push_str(&format!(
+                    "{}>\n      <failure type=\"{}\" message=\"{}\">expected hash: {}\ncurrent hash: {}</failure>\n    </testcase>\n",
+                    open,
+                    kind,
+                    escape_xml(&message),
+                    escape_xml(&entry.expected_hash),
+                    escape_xml(entry.current_hash.as_deref().unwrap_or("none"))
+                )),
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Write a drift result as JUnit XML to `path`
+pub fn write_drift_junit(result: &DriftResult, path: impl AsRef<Path>) -> Result<(), Error> {
+    let path = path.as_ref();
+    check_path("write JUnit report", path)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            Error::from_reason(format!("Failed to create {}: {}", parent.display(), e))
+        })?;
+    }
+    fs::write(path, drift_junit(result)).map_err(|e| {
+        Error::from_reason(format!(
+            "Failed to write JUnit report {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, code_ref: &str, doc_file: &str, status: DriftStatus) -> DriftEntry {
+        DriftEntry {
+            entry_id: id.to_string(),
+            code_ref: code_ref.to_string(),
+            doc_file: doc_file.to_string(),
+            status,
+            expected_hash: "abc".to_string(),
+            current_hash: match status {
+                DriftStatus::Removed => None,
+                DriftStatus::InSync | DriftStatus::Ignored => Some("abc".to_string()),
+                DriftStatus::Drifted => Some("def".to_string()),
+            },
+            ignore_reason: None,
+            deprecation: None,
+            current_signature: None,
+            severity: None,
+            test_count: None,
+        }
+    }
+
+    #[test]
+    fn test_drift_junit() {
+        let mut result = DriftResult {
+            entries: vec![
+                entry(
+                    "a",
+                    "src/auth.ts#login",
+                    "docs/auth.md",
+                    DriftStatus::InSync,
+                ),
+                entry("b", "src/db.ts#connect", "docs/db.md", DriftStatus::Removed),
+                entry(
+                    "c",
+                    "src/auth.ts#logout",
+                    "docs/auth.md",
+                    DriftStatus::Drifted,
+                ),
+            ],
+            ..Default::default()
+        };
+
+        result.entries[2].test_count = Some(0);
+        let xml = drift_junit(&result);
+
+        assert!(xml.contains("<testsuites name=\"sintesi-drift\" tests=\"3\" failures=\"2\""));
+        assert!(xml.contains("<testsuite name=\"docs/auth.md\" tests=\"2\" failures=\"1\""));
+        assert!(xml.contains(
+            "<testcase name=\"src/auth.ts#login\" classname=\"docs/auth.md\" file=\"src/auth.ts\"/>"
+        ));
+        assert!(xml.contains("<failure type=\"removed\" message=\"src/db.ts#connect no longer exists but is documented by anchor &quot;b&quot; in docs/db.md\">expected hash: abc\ncurrent hash: none</failure>"));
+        assert!(xml.contains("was written; no test exercises it\">"));
+        assert!(
+            xml.find("docs/auth.md\" tests").unwrap() < xml.find("docs/db.md\" tests").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ignored_entries_are_skipped() {
+        let mut ignored = entry(
+            "a",
+            "src/gen.ts#client",
+            "docs/api.md",
+            DriftStatus::Ignored,
+        );
+        ignored.ignore_reason = Some("generated <code>".to_string());
+        let result = DriftResult {
+            entries: vec![ignored],
+            ..Default::default()
+        };
+
+        let xml = drift_junit(&result);
+
+        assert!(xml.contains("failures=\"0\" errors=\"0\" skipped=\"1\""));
+        assert!(xml.contains("<skipped message=\"generated &lt;code&gt;\"/>"));
+    }
+}
@@ -0,0 +1,38 @@
+//! Reporting module
+//!
+//! This module turns drift results into artifacts and notifications for CI:
+//!
+//! ## Module Structure
+//!
+//! - `coverage`: Documentation coverage of exported symbols
+//! - `badge`: SVG and shields.io badges for coverage and drift
+//! - `forge`: GitHub/GitLab REST reporter (PR comments and commit statuses)
+//! - `gate`: Pass/warn/fail status of a run for CI gating
+//! - `html`: Self-contained HTML report of drift, coverage, and anchors
+//! - `junit`: JUnit XML output for drift checks
+//! - `packages`: Drift, coverage, and anchors split by workspace package
+//! - `sink`: `Reporter` trait, built-in reporters, and the reporter registry
+//! - `staleness`: Anchor staleness per owner from git blame and sync times
+
+pub mod badge;
+pub mod coverage;
+pub mod forge;
+pub mod gate;
+pub mod html;
+pub mod junit;
+pub mod packages;
+pub mod sink;
+pub mod staleness;
+
+pub use badge::{Badge, BadgeColor};
+pub use coverage::Coverage;
+pub use forge::{drift_comment, CommitState, ForgeConfig, ForgeKind, ForgeReporter};
+pub use gate::{evaluate, Evaluation, GatePolicy, GateReason, RunResult, RunStatus};
+pub use html::HtmlReport;
+pub use junit::{drift_junit, write_drift_junit};
+pub use packages::PackageReport;
+pub use sink::{
+    drift_findings, lint_findings, policy_findings, Finding, FindingSeverity, Reporter,
+    ReporterConfig, ReporterRegistry, ReporterSpec,
+};
+pub use staleness::{AnchorStaleness, OwnerStaleness, StalenessReport};
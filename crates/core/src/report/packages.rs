@@ -0,0 +1,248 @@
+//! Per-package breakdown of a workspace
+//!
+//! Splits drift, coverage, and the anchor inventory by workspace package so
+//! monorepo maintainers can triage one package at a time. Map entries and
+//! symbols belong to the package of their source file; anchors to the package
+//! of the file their `code_ref` points at, or of their own file without one.
+//! A symbol documented through a barrel file counts towards the coverage of
+//! the package declaring it.
+
+use super::coverage::Coverage;
+use crate::ast::SymbolInfo;
+use crate::content::SintesiAnchor;
+use crate::context::{WorkspacePackage, ROOT_PACKAGE};
+use crate::drift::{DriftResult, SintesiMap};
+use crate::provider::ContentProvider;
+
+/// Drift, coverage, and anchors of one workspace package
+#[derive(Debug, Clone)]
+pub struct PackageReport {
+    /// The package
+    pub package: WorkspacePackage,
+    /// Drift of the map entries documenting the package's code
+    pub drift: DriftResult,
+    /// Documentation coverage of the package's exported symbols
+    pub coverage: Coverage,
+    /// Anchors documenting the package
+    pub anchors: Vec<SintesiAnchor>,
+}
+
+impl PackageReport {
+    /// Split a project's drift, coverage, and anchors by package
+    ///
+    /// Files outside every package are grouped under a root package named
+    /// `(root)` unless the project root is a package itself. Packages with
+    /// no symbols, entries, or anchors are left out.
+    ///
+    /// # Arguments
+    /// * `packages` - Workspace packages, from `detect_packages`
+    /// * `map` - The Sintesi map
+    /// * `symbols` - Analyzed symbols (file paths relative to the project root)
+    /// * `drift` - Drift result for the map
+    /// * `anchors` - Anchor inventory (file paths relative to the project root)
+    /// * `provider` - Reads modules to follow re-exports for coverage
+    pub fn split(
+        packages: &[WorkspacePackage],
+        map: &SintesiMap,
+        symbols: &[SymbolInfo],
+        drift: &DriftResult,
+        anchors: &[SintesiAnchor],
+        provider: &dyn ContentProvider,
+    ) -> Vec<Self> {
+        let mut packages = packages.to_vec();
+        if !packages.iter().any(|package| package.path.is_empty()) {
+            packages.insert(
+                0,
+                WorkspacePackage {
+                    name: ROOT_PACKAGE.to_string(),
+                    path: String::new(),
+                },
+            );
+        }
+        // Innermost package containing the file (the root contains all)
+        let index_of = |file: &str| {
+            packages
+                .iter()
+                .enumerate()
+                .filter(|(_, package)| package.contains(file))
+                .max_by_key(|(_, package)| package.path.len())
+                .map_or(0, |(index, _)| index)
+        };
+
+        let mut symbols_by_package: Vec<Vec<SymbolInfo>> = vec![Vec::new(); packages.len()];
+        for symbol in symbols {
+            symbols_by_package[index_of(&symbol.file_path)].push(symbol.clone());
+        }
+        let mut reports: Vec<Self> = packages
+            .iter()
+            .zip(&symbols_by_package)
+            .map(|(package, symbols)| Self {
+                package: package.clone(),
+                drift: DriftResult::default(),
+                coverage: Coverage::compute_resolved(map, symbols, provider),
+                anchors: Vec::new(),
+            })
+            .collect();
+
+        for entry in &drift.entries {
+            let report = &mut reports[index_of(code_ref_file(&entry.code_ref))];
+            report.drift.entries.push(entry.clone());
+            if let Some(status) = drift.symbol_status.get(&entry.code_ref) {
+                report
+                    .drift
+                    .symbol_status
+                    .insert(entry.code_ref.clone(), *status);
+            }
+        }
+        for anchor in anchors {
+            let own_file = anchor.file_path.to_string_lossy().replace('\\', "/");
+            let file = anchor
+                .code_ref
+                .as_deref()
+                .map_or(own_file.as_str(), code_ref_file);
+            reports[index_of(file)].anchors.push(anchor.clone());
+        }
+
+        reports.retain(|report| {
+            report.coverage.total > 0
+                || !report.drift.entries.is_empty()
+                || !report.anchors.is_empty()
+        });
+        reports
+    }
+}
+
+/// File part of a `file_path#symbol_name` reference
+fn code_ref_file(code_ref: &str) -> &str {
+    code_ref.split('#').next().unwrap_or(code_ref)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::{DriftEntry, DriftStatus, SintesiMapEntry};
+    use crate::provider::MemoryProvider;
+    use crate::types::SymbolType;
+    use std::path::PathBuf;
+
+    fn symbol(file_path: &str, name: &str) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            symbol_type: SymbolType::Function,
+            signature: String::new(),
+            is_exported: true,
+            file_path: file_path.to_string(),
+            deprecated: false,
+            release_tag: None,
+            location: None,
+            type_params: Vec::new(),
+        }
+    }
+
+    fn anchor(id: &str, file_path: &str, code_ref: Option<&str>) -> SintesiAnchor {
+        SintesiAnchor {
+            id: id.to_string(),
+            code_ref: code_ref.map(str::to_string),
+            file_path: PathBuf::from(file_path),
+            start_line: 0,
+            end_line: 1,
+            content: String::new(),
+            parent: None,
+            children: Vec::new(),
+            start_tag_span: 0..0,
+            content_span: 0..0,
+            end_tag_span: 0..0,
+        }
+    }
+
+    #[test]
+    fn test_split_by_package() {
+        let packages = [
+            WorkspacePackage {
+                name: "@acme/auth".to_string(),
+                path: "packages/auth".to_string(),
+            },
+            WorkspacePackage {
+                name: "@acme/idle".to_string(),
+                path: "packages/idle".to_string(),
+            },
+        ];
+        let map = SintesiMap {
+            entries: vec![SintesiMapEntry {
+                id: "a1".to_string(),
+                code_ref: "packages/auth/src/index.ts#login".to_string(),
+                doc_file: "docs/auth.md".to_string(),
+                code_signature_hash: "h".to_string(),
+                last_updated: 0,
+                symbol_id: None,
+                symbol_key: None,
+                deprecated: false,
+                content_hash: None,
+                code_signature: None,
+            }],
+            ..SintesiMap::new()
+        };
+        let symbols = [
+            symbol("packages/auth/src/index.ts", "login"),
+            symbol("packages/auth/src/index.ts", "logout"),
+            symbol("scripts/build.ts", "build"),
+        ];
+        let drift = DriftResult {
+            entries: vec![DriftEntry {
+                entry_id: "a1".to_string(),
+                code_ref: "packages/auth/src/index.ts#login".to_string(),
+                doc_file: "docs/auth.md".to_string(),
+                status: DriftStatus::Drifted,
+                expected_hash: "h".to_string(),
+                current_hash: Some("x".to_string()),
+                ignore_reason: None,
+                deprecation: None,
+                current_signature: None,
+                severity: None,
+                test_count: None,
+            }],
+            ..Default::default()
+        };
+        let anchors = [
+            anchor(
+                "a1",
+                "docs/auth.md",
+                Some("packages/auth/src/index.ts#login"),
+            ),
+            anchor("intro", "docs/intro.md", None),
+        ];
+
+        let reports = PackageReport::split(
+            &packages,
+            &map,
+            &symbols,
+            &drift,
+            &anchors,
+            &MemoryProvider::new(),
+        );
+        let names: Vec<&str> = reports.iter().map(|r| r.package.name.as_str()).collect();
+        assert_eq!(names, [ROOT_PACKAGE, "@acme/auth"]);
+
+        let root = &reports[0];
+        assert_eq!(
+            root.coverage,
+            Coverage {
+                documented: 0,
+                total: 1
+            }
+        );
+        assert!(root.drift.entries.is_empty());
+        assert_eq!(root.anchors[0].id, "intro");
+
+        let auth = &reports[1];
+        assert_eq!(
+            auth.coverage,
+            Coverage {
+                documented: 1,
+                total: 2
+            }
+        );
+        assert_eq!(auth.drift.drifted().count(), 1);
+        assert_eq!(auth.anchors[0].id, "a1");
+    }
+}
@@ -0,0 +1,681 @@
+//! Pluggable report sinks
+//!
+//! Pipeline phases (drift checks, linting, freshness policies) describe what
+//! they found as `Finding`s and hand them to `Reporter`s, so every consumer
+//! gets the same output instead of formatting results ad hoc. Built-in
+//! reporters:
+//!
+//! | Kind      | Output                                                 |
+//! |-----------|--------------------------------------------------------|
+//! | `console` | One line per finding on stdout                         |
+//! | `json`    | The findings as a JSON array, written to `output`      |
+//! | `sarif`   | A SARIF 2.1.0 log (code scanning), written to `output` |
+//! | `html`    | A self-contained HTML table, written to `output`       |
+//! | `github`  | GitHub Actions annotations (`::error file=...::`)      |
+//!
+//! `ReporterRegistry` creates reporters from `ReporterSpec`s and accepts
+//! custom kinds. Specs are read from the `reporters` key of
+//! `sintesi.config.json`:
+//!
+//! ```json
+//! {
+//!   "reporters": [
+//!     { "kind": "console" },
+//!     { "kind": "sarif", "output": ".sintesi/sintesi.sarif" }
+//!   ]
+//! }
+//! ```
+
+use super::html::escape_html;
+use crate::content::{LintFinding, LintSeverity};
+use crate::drift::{DeprecationChange, DriftResult, DriftStatus, PolicySeverity, PolicyViolation};
+use crate::error::Error;
+use crate::sandbox::check_path;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FindingSeverity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl FindingSeverity {
+    /// Lowercase name, as used in SARIF levels and configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FindingSeverity::Note => "note",
+            FindingSeverity::Warning => "warning",
+            FindingSeverity::Error => "error",
+        }
+    }
+
+    /// Parse a lowercase name
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "note" => Some(FindingSeverity::Note),
+            "warning" => Some(FindingSeverity::Warning),
+            "error" => Some(FindingSeverity::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Something a pipeline phase reports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Finding {
+    /// Phase that produced the finding (`drift`, `lint`, `policy`, ...)
+    pub phase: String,
+    /// Rule within the phase (`drifted`, `passive-voice`, ...)
+    pub rule: String,
+    /// Severity
+    pub severity: FindingSeverity,
+    /// Human-readable description
+    pub message: String,
+    /// File the finding is about, relative to the project root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Line in `file` (1-based)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl Finding {
+    /// Create a finding without a location
+    pub fn new(
+        phase: impl Into<String>,
+        rule: impl Into<String>,
+        severity: FindingSeverity,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            phase: phase.into(),
+            rule: rule.into(),
+            severity,
+            message: message.into(),
+            file: None,
+            line: None,
+        }
+    }
+
+    /// Locate the finding in a file
+    pub fn at(mut self, file: impl Into<String>, line: Option<usize>) -> Self {
+        self.file = Some(file.into());
+        self.line = line;
+        self
+    }
+
+    /// Rule qualified by its phase (`drift/drifted`)
+    pub fn rule_id(&self) -> String {
+        format!("{}/{}", self.phase, self.rule)
+    }
+}
+
+/// Findings for drifted and removed map entries
+///
+/// Deprecation changes get their own rules (`drift/deprecated`,
+/// `drift/undeprecated`) so the docs get a migration note. Drifted symbols no
+/// test exercises (when tests were mapped) add a `drift/untested` warning.
+pub fn drift_findings(result: &DriftResult) -> Vec<Finding> {
+    let untested = result.untested().map(|entry| {
+        Finding::new(
+            "drift",
+            "untested",
+            FindingSeverity::Warning,
+            format!(
+                "{} changed and no test exercises it (anchor \"{}\")",
+                entry.code_ref, entry.entry_id
+            ),
+        )
+        .at(entry.doc_file.clone(), None)
+    });
+    result
+        .drifted()
+        .map(|entry| {
+            let (rule, what) = match (entry.status, entry.deprecation) {
+                (_, Some(DeprecationChange::Deprecated)) => (
+                    "deprecated",
+                    "was deprecated since it was documented; add a migration note",
+                ),
+                (_, Some(DeprecationChange::Undeprecated)) => {
+                    ("undeprecated", "is no longer deprecated")
+                }
+                (DriftStatus::Removed, None) => ("removed", "no longer exists"),
+                _ => ("drifted", "changed since it was documented"),
+            };
+            Finding::new(
+                "drift",
+                rule,
+                FindingSeverity::Error,
+                format!(
+                    "{} {} (anchor \"{}\")",
+                    entry.code_ref, what, entry.entry_id
+                ),
+            )
+            .at(entry.doc_file.clone(), None)
+        })
+        .chain(untested)
+        .collect()
+}
+
+/// Findings for lint results of a markdown file
+pub fn lint_findings(file: &str, findings: &[LintFinding]) -> Vec<Finding> {
+    findings
+        .iter()
+        .map(|finding| {
+            let severity = match finding.severity {
+                LintSeverity::Warning => FindingSeverity::Warning,
+                LintSeverity::Error => FindingSeverity::Error,
+            };
+            Finding::new("lint", finding.rule.as_str(), severity, &finding.message)
+                .at(file, Some(finding.line + 1))
+        })
+        .collect()
+}
+
+/// Findings for freshness policy violations
+pub fn policy_findings(violations: &[PolicyViolation]) -> Vec<Finding> {
+    violations
+        .iter()
+        .map(|violation| {
+            let severity = match violation.severity {
+                PolicySeverity::Warning => FindingSeverity::Warning,
+                PolicySeverity::Error => FindingSeverity::Error,
+            };
+            Finding::new("policy", &violation.policy, severity, violation.to_string())
+                .at(violation.doc_file.clone(), None)
+        })
+        .collect()
+}
+
+/// A destination for findings
+pub trait Reporter: Send + Sync {
+    /// Emit findings (possibly none, so sinks can record a clean run)
+    fn report(&self, findings: &[Finding]) -> Result<(), Error>;
+}
+
+/// Prints one line per finding to stdout
+pub struct ConsoleReporter;
+
+impl Reporter for ConsoleReporter {
+    fn report(&self, findings: &[Finding]) -> Result<(), Error> {
+        print!("{}", render_console(findings));
+        Ok(())
+    }
+}
+
+/// Prints GitHub Actions workflow annotations to stdout
+pub struct GitHubReporter;
+
+impl Reporter for GitHubReporter {
+    fn report(&self, findings: &[Finding]) -> Result<(), Error> {
+        print!("{}", render_github(findings));
+        Ok(())
+    }
+}
+
+/// File formats written by `FileReporter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Json,
+    Sarif,
+    Html,
+}
+
+/// Writes findings to a file
+pub struct FileReporter {
+    format: FileFormat,
+    path: PathBuf,
+}
+
+impl FileReporter {
+    /// Create a reporter writing `format` to `path`
+    pub fn new(format: FileFormat, path: impl Into<PathBuf>) -> Self {
+        Self {
+            format,
+            path: path.into(),
+        }
+    }
+}
+
+impl Reporter for FileReporter {
+    fn report(&self, findings: &[Finding]) -> Result<(), Error> {
+        let content = match self.format {
+            FileFormat::Json => render_json(findings),
+            FileFormat::Sarif => render_sarif(findings),
+            FileFormat::Html => render_html(findings),
+        };
+        check_path("write report", &self.path)?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                Error::from_reason(format!("Failed to create {}: {}", parent.display(), e))
+            })?;
+        }
+        fs::write(&self.path, content).map_err(|e| {
+            Error::from_reason(format!(
+                "Failed to write report {}: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// `severity[phase/rule] file:line: message`, one line per finding
+pub fn render_console(findings: &[Finding]) -> String {
+    findings
+        .iter()
+        .map(|finding| {
+            let location = match (&finding.file, finding.line) {
+                (Some(file), Some(line)) => format!(" {}:{}:", file, line),
+                (Some(file), None) => format!(" {}:", file),
+                _ => String::new(),
+            };
+            format!(
+                "{}[{}]{} {}\n",
+                finding.severity.as_str(),
+                finding.rule_id(),
+                location,
+                finding.message
+            )
+        })
+        .collect()
+}
+
+/// GitHub Actions annotations, one workflow command per finding
+pub fn render_github(findings: &[Finding]) -> String {
+    findings
+        .iter()
+        .map(|finding| {
+            let command = match finding.severity {
+                FindingSeverity::Error => "error",
+                FindingSeverity::Warning => "warning",
+                FindingSeverity::Note => "notice",
+            };
+            let mut properties = Vec::new();
+            if let Some(file) = &finding.file {
+                properties.push(format!("file={}", escape_property(file)));
+            }
+            if let Some(line) = finding.line {
+                properties.push(format!("line={}", line));
+            }
+            properties.push(format!("title={}", escape_property(&finding.rule_id())));
+            format!(
+                "::{} {}::{}\n",
+                command,
+                properties.join(","),
+                escape_data(&finding.message)
+            )
+        })
+        .collect()
+}
+
+/// The findings as a pretty-printed JSON array
+pub fn render_json(findings: &[Finding]) -> String {
+    let mut json = serde_json::to_string_pretty(findings).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+/// A SARIF 2.1.0 log with one run
+pub fn render_sarif(findings: &[Finding]) -> String {
+    let rules: BTreeSet<String> = findings.iter().map(Finding::rule_id).collect();
+    let results: Vec<_> = findings
+        .iter()
+        .map(|finding| {
+            let mut result = json!({
+                "ruleId": finding.rule_id(),
+                "level": finding.severity.as_str(),
+                "message": { "text": finding.message },
+            });
+            if let Some(file) = &finding.file {
+                let mut location = json!({ "artifactLocation": { "uri": file } });
+                if let Some(line) = finding.line {
+                    location["region"] = json!({ "startLine": line });
+                }
+                result["locations"] = json!([{ "physicalLocation": location }]);
+            }
+            result
+        })
+        .collect();
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "sintesi",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                }
+            },
+            "results": results,
+        }],
+    });
+    let mut sarif = serde_json::to_string_pretty(&log).unwrap_or_default();
+    sarif.push('\n');
+    sarif
+}
+
+/// A self-contained HTML page listing the findings
+pub fn render_html(findings: &[Finding]) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Sintesi findings</title>\n<style>body{{font-family:system-ui,sans-serif;margin:2rem}}table{{border-collapse:collapse}}th,td{{border-bottom:1px solid #d0d7de;padding:.4rem .6rem;text-align:left}}.error{{color:#cf222e}}.warning{{color:#bc4c00}}.note{{color:#656d76}}</style>\n</head>\n<body>\n<h1>Sintesi findings</h1>\n<p>{} findings</p>\n<table>\n<thead><tr><th>Severity</th><th>Rule</th><th>Location</th><th>Message</th></tr></thead>\n<tbody>\n",
+        findings.len()
+    );
+    for finding in findings {
+        let location = match (&finding.file, finding.line) {
+            (Some(file), Some(line)) => format!("{}:{}", file, line),
+            (Some(file), None) => file.clone(),
+            _ => String::new(),
+        };
+        html.push_str(&format!(
+            "<tr><td class=\"{severity}\">{severity}</td><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&finding.rule_id()),
+            escape_html(&location),
+            escape_html(&finding.message),
+            severity = finding.severity.as_str(),
+        ));
+    }
+    html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    html
+}
+
+/// Escape workflow command data
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape workflow command property values
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Which reporter to create, and where it writes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReporterSpec {
+    /// Reporter kind (`console`, `json`, `sarif`, `html`, `github`, or a
+    /// custom registered kind)
+    pub kind: String,
+    /// Output file for file reporters, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+}
+
+impl ReporterSpec {
+    /// Create a spec for a reporter kind
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            output: None,
+        }
+    }
+
+    /// Set the output file
+    pub fn output(mut self, output: impl Into<String>) -> Self {
+        self.output = Some(output.into());
+        self
+    }
+}
+
+/// The `reporters` key of `sintesi.config.json`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReporterConfig {
+    /// Reporters to emit findings to; empty means the console only
+    #[serde(default)]
+    pub reporters: Vec<ReporterSpec>,
+}
+
+impl ReporterConfig {
+    /// Load reporter specs from a config file (none if it does not exist)
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let Ok(content) = fs::read_to_string(path) else {
+            return Ok(Self::default());
+        };
+        serde_json::from_str(&content)
+            .map_err(|e| Error::from_reason(format!("Invalid config {}: {}", path.display(), e)))
+    }
+
+    /// The configured specs, or a console reporter if there are none
+    pub fn specs(&self) -> Vec<ReporterSpec> {
+        if self.reporters.is_empty() {
+            vec![ReporterSpec::new("console")]
+        } else {
+            self.reporters.clone()
+        }
+    }
+}
+
+/// Creates a reporter from a spec; output paths are resolved against `root`
+type ReporterFactory =
+    Box<dyn Fn(&ReporterSpec, &Path) -> Result<Box<dyn Reporter>, Error> + Send + Sync>;
+
+/// Reporter kinds by name
+pub struct ReporterRegistry {
+    factories: HashMap<String, ReporterFactory>,
+}
+
+impl Default for ReporterRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            factories: HashMap::new(),
+        };
+        registry.register("console", |_, _| Ok(Box::new(ConsoleReporter)));
+        registry.register("github", |_, _| Ok(Box::new(GitHubReporter)));
+        for (kind, format) in [
+            ("json", FileFormat::Json),
+            ("sarif", FileFormat::Sarif),
+            ("html", FileFormat::Html),
+        ] {
+            registry.register(kind, move |spec, root| {
+                let output = spec.output.as_ref().ok_or_else(|| {
+                    Error::from_reason(format!("The {} reporter needs an output file", spec.kind))
+                })?;
+                Ok(Box::new(FileReporter::new(format, root.join(output))))
+            });
+        }
+        registry
+    }
+}
+
+impl ReporterRegistry {
+    /// Create a registry with the built-in reporters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or replace) a reporter kind
+    pub fn register(
+        &mut self,
+        kind: impl Into<String>,
+        factory: impl Fn(&ReporterSpec, &Path) -> Result<Box<dyn Reporter>, Error>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.factories.insert(kind.into(), Box::new(factory));
+    }
+
+    /// Create a reporter, resolving its output against the project root
+    ///
+    /// # Errors
+    /// Fails for unknown kinds and file reporters without an output.
+    pub fn create(&self, spec: &ReporterSpec, root: &Path) -> Result<Box<dyn Reporter>, Error> {
+        let factory = self
+            .factories
+            .get(&spec.kind)
+            .ok_or_else(|| Error::from_reason(format!("Unknown reporter: {}", spec.kind)))?;
+        factory(spec, root)
+    }
+
+    /// Create reporters for several specs and emit findings to each
+    ///
+    /// # Errors
+    /// Fails before emitting anything if a reporter cannot be created, and
+    /// with the first emit error otherwise (remaining reporters still run).
+    pub fn emit(
+        &self,
+        specs: &[ReporterSpec],
+        root: &Path,
+        findings: &[Finding],
+    ) -> Result<(), Error> {
+        let reporters = specs
+            .iter()
+            .map(|spec| self.create(spec, root))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut first_error = None;
+        for reporter in reporters {
+            if let Err(e) = reporter.report(findings) {
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::DriftEntry;
+    use std::sync::{Arc, Mutex};
+
+    fn findings() -> Vec<Finding> {
+        let result = DriftResult {
+            entries: vec![DriftEntry {
+                entry_id: "login".to_string(),
+                code_ref: "src/auth.ts#login".to_string(),
+                doc_file: "docs/api.md".to_string(),
+                status: DriftStatus::Drifted,
+                expected_hash: "a".to_string(),
+                current_hash: Some("b".to_string()),
+                ignore_reason: None,
+                deprecation: None,
+                current_signature: None,
+                severity: None,
+                test_count: None,
+            }],
+            ..Default::default()
+        };
+        let mut findings = drift_findings(&result);
+        findings.push(
+            Finding::new(
+                "lint",
+                "banned-word",
+                FindingSeverity::Warning,
+                "Avoid \"simply\"",
+            )
+            .at("docs/a,b.md", Some(3)),
+        );
+        findings
+    }
+
+    #[test]
+    fn test_renderers() {
+        let findings = findings();
+        assert_eq!(
+            render_console(&findings),
+            "error[drift/drifted] docs/api.md: src/auth.ts#login changed since it was documented (anchor \"login\")\nwarning[lint/banned-word] docs/a,b.md:3: Avoid \"simply\"\n"
+        );
+        assert!(render_github(&findings).ends_with(
+            "::warning file=docs/a%2Cb.md,line=3,title=lint/banned-word::Avoid \"simply\"\n"
+        ));
+
+        let sarif: serde_json::Value = serde_json::from_str(&render_sarif(&findings)).unwrap();
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["results"][1]["level"], "warning");
+        assert_eq!(
+            run["results"][1]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            3
+        );
+
+        let parsed: Vec<Finding> = serde_json::from_str(&render_json(&findings)).unwrap();
+        assert_eq!(parsed, findings);
+        assert!(render_html(&findings).contains("Avoid &quot;simply&quot;"));
+    }
+
+    struct Collect(Arc<Mutex<usize>>);
+
+    impl Reporter for Collect {
+        fn report(&self, findings: &[Finding]) -> Result<(), Error> {
+            *self.0.lock().unwrap() += findings.len();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_flags_untested_drift() {
+        let mut result = DriftResult {
+            entries: vec![DriftEntry {
+                entry_id: "login".to_string(),
+                code_ref: "src/auth.ts#login".to_string(),
+                doc_file: "docs/api.md".to_string(),
+                status: DriftStatus::Drifted,
+                expected_hash: "a".to_string(),
+                current_hash: Some("b".to_string()),
+                ignore_reason: None,
+                deprecation: None,
+                current_signature: None,
+                severity: None,
+                test_count: Some(2),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(drift_findings(&result).len(), 1);
+
+        result.entries[0].test_count = Some(0);
+        let findings = drift_findings(&result);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[1].rule_id(), "drift/untested");
+        assert_eq!(findings[1].severity, FindingSeverity::Warning);
+        assert_eq!(
+            findings[1].message,
+            "src/auth.ts#login changed and no test exercises it (anchor \"login\")"
+        );
+    }
+
+    #[test]
+    fn test_registry_creates_configured_reporters() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("sintesi.config.json"),
+            r#"{"docsDir": "docs", "reporters": [{"kind": "sarif", "output": "out/r.sarif"}, {"kind": "custom"}]}"#,
+        )
+        .unwrap();
+        let config = ReporterConfig::load(dir.path().join("sintesi.config.json")).unwrap();
+
+        let count = Arc::new(Mutex::new(0));
+        let mut registry = ReporterRegistry::new();
+        let shared = Arc::clone(&count);
+        registry.register("custom", move |_, _| {
+            Ok(Box::new(Collect(Arc::clone(&shared))))
+        });
+        registry
+            .emit(&config.specs(), dir.path(), &findings())
+            .unwrap();
+
+        assert_eq!(*count.lock().unwrap(), 2);
+        assert!(dir.path().join("out/r.sarif").exists());
+        assert!(registry
+            .create(&ReporterSpec::new("json"), dir.path())
+            .is_err());
+        assert!(registry
+            .create(&ReporterSpec::new("xml"), dir.path())
+            .is_err());
+        assert_eq!(
+            ReporterConfig::default().specs(),
+            [ReporterSpec::new("console")]
+        );
+    }
+}
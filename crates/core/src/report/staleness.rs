@@ -0,0 +1,287 @@
+//! Documentation staleness by owner
+//!
+//! Combines `git blame` on each anchor's lines with the map's `lastUpdated`
+//! timestamps. An anchor's staleness is the number of days since it was last
+//! touched, either by a sync (`lastUpdated`) or by a commit editing its
+//! lines; its owner is the author of most of those lines. Aggregating per
+//! owner gives a heatmap of who holds the stalest documentation, so review
+//! effort can be targeted.
+
+use crate::content::extract_anchors;
+use crate::drift::{SintesiMap, SintesiMapEntry, DAY_MS};
+use crate::error::Error;
+use crate::git::GitService;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Owner of anchors whose lines are not committed yet
+pub const UNCOMMITTED: &str = "Not Committed Yet";
+
+/// Staleness of one anchor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorStaleness {
+    /// Anchor ID of the map entry
+    pub entry_id: String,
+    /// Documentation file containing the anchor
+    pub doc_file: String,
+    /// Code reference the anchor documents
+    pub code_ref: String,
+    /// Author of most of the anchor's lines
+    pub owner: String,
+    /// Latest commit time of the anchor's lines (milliseconds since Unix epoch)
+    pub last_edited: Option<i64>,
+    /// Last sync of the map entry (milliseconds since Unix epoch)
+    pub last_updated: i64,
+    /// Days since the anchor was last edited or synced
+    pub days: u32,
+}
+
+/// Staleness of all anchors owned by one author
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnerStaleness {
+    /// Author name
+    pub owner: String,
+    /// Number of anchors owned
+    pub anchors: usize,
+    /// Sum of the anchors' staleness in days
+    pub total_days: u64,
+    /// Staleness of the stalest anchor
+    pub max_days: u32,
+}
+
+impl OwnerStaleness {
+    /// Average staleness of the owner's anchors in days
+    pub fn average_days(&self) -> u32 {
+        (self.total_days / self.anchors.max(1) as u64) as u32
+    }
+}
+
+/// Staleness per anchor and per owner
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StalenessReport {
+    /// Anchors, stalest first
+    pub anchors: Vec<AnchorStaleness>,
+    /// Owners, by total staleness (largest first)
+    pub owners: Vec<OwnerStaleness>,
+}
+
+impl StalenessReport {
+    /// Build the report for every map entry whose anchor exists
+    ///
+    /// # Arguments
+    /// * `root` - Project root inside a git repository
+    /// * `map` - The Sintesi map
+    /// * `now` - Current time (milliseconds since Unix epoch)
+    pub fn compute(root: impl AsRef<Path>, map: &SintesiMap, now: i64) -> Result<Self, Error> {
+        let root = root.as_ref();
+        let git = GitService::open(root)?;
+        let mut anchors = Vec::new();
+        let mut by_doc: Vec<(&str, Vec<&SintesiMapEntry>)> = Vec::new();
+        for entry in &map.entries {
+            match by_doc.iter_mut().find(|(doc, _)| *doc == entry.doc_file) {
+                Some((_, entries)) => entries.push(entry),
+                None => by_doc.push((&entry.doc_file, vec![entry])),
+            }
+        }
+
+        for (doc_file, entries) in by_doc {
+            let path = root.join(doc_file);
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let extraction = extract_anchors(doc_file, &content);
+            let blame = git.blame_lines(&path)?;
+            for entry in entries {
+                let Some(anchor) = extraction.anchors.get(&entry.id) else {
+                    continue;
+                };
+                // Blame the content, or the tags of an empty anchor
+                let lines = if anchor.end_line > anchor.start_line + 1 {
+                    anchor.start_line + 1..anchor.end_line
+                } else {
+                    anchor.start_line..anchor.end_line + 1
+                };
+                let lines = blame.get(lines).unwrap_or_default();
+                anchors.push(anchor_staleness(entry, lines, now));
+            }
+        }
+
+        Ok(Self::from_anchors(anchors))
+    }
+
+    /// Sort anchors and aggregate them per owner
+    pub fn from_anchors(mut anchors: Vec<AnchorStaleness>) -> Self {
+        anchors.sort_by(|a, b| {
+            b.days
+                .cmp(&a.days)
+                .then_with(|| a.entry_id.cmp(&b.entry_id))
+        });
+
+        let mut owners: Vec<OwnerStaleness> = Vec::new();
+        for anchor in &anchors {
+            let index = match owners.iter().position(|o| o.owner == anchor.owner) {
+                Some(index) => index,
+                None => {
+                    owners.push(OwnerStaleness {
+                        owner: anchor.owner.clone(),
+                        anchors: 0,
+                        total_days: 0,
+                        max_days: 0,
+                    });
+                    owners.len() - 1
+                }
+            };
+            let owner = &mut owners[index];
+            owner.anchors += 1;
+            owner.total_days += u64::from(anchor.days);
+            owner.max_days = owner.max_days.max(anchor.days);
+        }
+        owners.sort_by(|a, b| {
+            b.total_days
+                .cmp(&a.total_days)
+                .then_with(|| a.owner.cmp(&b.owner))
+        });
+
+        Self { anchors, owners }
+    }
+}
+
+/// Score one anchor from the blame of its lines
+///
+/// Uncommitted lines count as edited now.
+fn anchor_staleness(
+    entry: &SintesiMapEntry,
+    lines: &[Option<(String, i64)>],
+    now: i64,
+) -> AnchorStaleness {
+    let mut line_counts: HashMap<&str, (usize, i64)> = HashMap::new();
+    let mut last_edited = None;
+    for line in lines {
+        let (author, time) = match line {
+            Some((author, time)) => (author.as_str(), *time),
+            None => (UNCOMMITTED, now),
+        };
+        let count = line_counts.entry(author).or_default();
+        count.0 += 1;
+        count.1 = count.1.max(time);
+        last_edited = last_edited.max(Some(time));
+    }
+    // Most lines wins; ties go to the most recent editor, then by name
+    let owner = line_counts
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then_with(|| b.cmp(a)))
+        .map_or(UNCOMMITTED, |(author, _)| author);
+
+    let touched = last_edited.unwrap_or(0).max(entry.last_updated);
+    AnchorStaleness {
+        entry_id: entry.id.clone(),
+        doc_file: entry.doc_file.clone(),
+        code_ref: entry.code_ref.clone(),
+        owner: owner.to_string(),
+        last_edited,
+        last_updated: entry.last_updated,
+        days: ((now - touched).max(0) / DAY_MS) as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, last_updated: i64) -> SintesiMapEntry {
+        SintesiMapEntry {
+            id: id.to_string(),
+            code_ref: format!("src/auth.ts#{}", id),
+            doc_file: "docs/auth.md".to_string(),
+            code_signature_hash: "h".to_string(),
+            last_updated,
+            symbol_id: None,
+            symbol_key: None,
+            deprecated: false,
+            content_hash: None,
+            code_signature: None,
+        }
+    }
+
+    #[test]
+    fn test_owner_and_score() {
+        let now = 100 * DAY_MS;
+        let blame = |author: &str, day: i64| Some((author.to_string(), day * DAY_MS));
+        let lines = [blame("Ana", 10), blame("Ana", 20), blame("Bo", 40)];
+
+        let login = anchor_staleness(&entry("login", 30 * DAY_MS), &lines, now);
+        assert_eq!(login.owner, "Ana");
+        assert_eq!(login.last_edited, Some(40 * DAY_MS));
+        assert_eq!(login.days, 60);
+
+        let logout = anchor_staleness(&entry("logout", 95 * DAY_MS), &lines[2..], now);
+        let draft = anchor_staleness(&entry("draft", 0), &[None], now);
+        assert_eq!(draft.owner, UNCOMMITTED);
+        assert_eq!(draft.days, 0);
+
+        let report = StalenessReport::from_anchors(vec![logout, draft, login]);
+        let ids: Vec<&str> = report.anchors.iter().map(|a| a.entry_id.as_str()).collect();
+        assert_eq!(ids, ["login", "logout", "draft"]);
+        let owners: Vec<(&str, u64)> = report
+            .owners
+            .iter()
+            .map(|o| (o.owner.as_str(), o.total_days))
+            .collect();
+        assert_eq!(owners, [("Ana", 60), ("Bo", 5), (UNCOMMITTED, 0)]);
+    }
+
+    #[test]
+    fn test_compute_from_blame() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Ana").unwrap();
+        config.set_str("user.email", "ana@example.com").unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        let doc = "# Auth\n<!-- sintesi:start id=\"login\" code_ref=\"src/auth.ts#login\" -->\nSigns in.\n<!-- sintesi:end id=\"login\" -->\n";
+        fs::write(dir.path().join("docs/auth.md"), doc).unwrap();
+        GitService::open(dir.path())
+            .unwrap()
+            .commit_paths(&[dir.path().join("docs/auth.md")], "docs")
+            .unwrap();
+        fs::write(
+            dir.path().join("docs/auth.md"),
+            doc.replace("Signs in.", "Signs a user in."),
+        )
+        .unwrap();
+        // Untracked documents have no history yet
+        fs::write(
+            dir.path().join("docs/new.md"),
+            doc.replace("login", "draft"),
+        )
+        .unwrap();
+        let draft = SintesiMapEntry {
+            doc_file: "docs/new.md".to_string(),
+            ..entry("draft", 0)
+        };
+        let map = SintesiMap {
+            entries: vec![entry("login", 0), entry("missing", 0), draft],
+            ..SintesiMap::new()
+        };
+
+        let committed_at = repo
+            .head()
+            .unwrap()
+            .peel_to_commit()
+            .unwrap()
+            .time()
+            .seconds()
+            * 1000;
+        let report = StalenessReport::compute(dir.path(), &map, committed_at).unwrap();
+
+        assert_eq!(report.anchors.len(), 2);
+        assert!(report.anchors.iter().all(|a| a.owner == UNCOMMITTED));
+
+        fs::write(dir.path().join("docs/auth.md"), doc).unwrap();
+        let report = StalenessReport::compute(dir.path(), &map, committed_at + 3 * DAY_MS).unwrap();
+        assert_eq!(report.anchors[0].entry_id, "login");
+        assert_eq!(report.anchors[0].owner, "Ana");
+        assert_eq!(report.anchors[0].days, 3);
+    }
+}
@@ -0,0 +1,244 @@
+//! Write sandbox
+//!
+//! Paths written by Sintesi often come from repository content: the map's
+//! `docFile` entries, plan JSON, a configured docs directory. Untrusted
+//! content could point them outside the workspace (`../../.bashrc`, or a
+//! symlink into `~/.ssh`). A `WriteSandbox` only lets writes through to
+//! paths inside the project root and, if the config lists any, inside one of
+//! the `writeAllowlist` entries (relative to the root). Sintesi's own state,
+//! the map file and the `.sintesi` directory, is always allowed. Paths are
+//! resolved before the check: `..` segments are applied and symlinks of
+//! existing directories followed.
+//!
+//! Operations given a project root (applying plans, splitting and merging
+//! anchors, migrations) always confine their writes to it. Writes
+//! that only get a path (saving the map, drift history, or analysis cache,
+//! anchor injection, report files) are checked against the process-wide
+//! sandbox, when one is installed with `install` or named by the
+//! `SINTESI_WRITE_ROOT` environment variable. Either way a refused write
+//! fails with `Error::PathNotAllowed` before anything is written.
+
+use crate::drift::MAP_FILE;
+use crate::error::Error;
+use crate::init::{CONFIG_FILE, STATE_DIR};
+use crate::paths::canonical;
+use crate::readonly::ensure_writable;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
+
+/// Environment variable naming the root of the process-wide sandbox
+pub const WRITE_ROOT_ENV: &str = "SINTESI_WRITE_ROOT";
+
+static INSTALLED: RwLock<Option<WriteSandbox>> = RwLock::new(None);
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SandboxConfig {
+    #[serde(default)]
+    write_allowlist: Vec<String>,
+}
+
+/// The paths writes are allowed to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteSandbox {
+    /// Project root, resolved
+    root: PathBuf,
+    /// Allowed files and directories, resolved (the whole root if empty)
+    allowlist: Vec<PathBuf>,
+}
+
+impl WriteSandbox {
+    /// Allow writes anywhere inside `root`
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: resolve(root.as_ref()),
+            allowlist: Vec::new(),
+        }
+    }
+
+    /// Sandbox of a project: its root, narrowed to the `writeAllowlist` of
+    /// its config file if there is one
+    pub fn load(root: impl AsRef<Path>) -> Result<Self, Error> {
+        let root = root.as_ref();
+        let path = root.join(CONFIG_FILE);
+        let mut sandbox = Self::new(root);
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Ok(sandbox);
+        };
+        let config: SandboxConfig = serde_json::from_str(&content)
+            .map_err(|e| Error::from_reason(format!("Invalid config {}: {}", path.display(), e)))?;
+        for entry in config.write_allowlist {
+            sandbox = sandbox.allow(entry);
+        }
+        Ok(sandbox)
+    }
+
+    /// Only allow writes to the listed paths (relative to the root), plus
+    /// Sintesi's own state
+    pub fn allow(mut self, path: impl AsRef<Path>) -> Self {
+        if self.allowlist.is_empty() {
+            self.allowlist = vec![self.root.join(MAP_FILE), self.root.join(STATE_DIR)];
+        }
+        let path = resolve(&self.root.join(path));
+        self.allowlist.push(path);
+        self
+    }
+
+    /// Whether writing `path` is allowed
+    pub fn allows(&self, path: impl AsRef<Path>) -> bool {
+        let path = resolve(path.as_ref());
+        path.starts_with(&self.root)
+            && (self.allowlist.is_empty()
+                || self
+                    .allowlist
+                    .iter()
+                    .any(|allowed| path.starts_with(allowed)))
+    }
+
+    /// Fail with `Error::PathNotAllowed` unless writing `path` is allowed
+    ///
+    /// # Arguments
+    /// * `operation` - The write about to happen (`"save map"`), for the error
+    /// * `path` - The file it writes
+    pub fn check(&self, operation: &str, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        match self.allows(path) {
+            true => Ok(()),
+            false => Err(Error::PathNotAllowed {
+                operation: operation.to_string(),
+                path: path.to_path_buf(),
+            }),
+        }
+    }
+}
+
+/// Install the process-wide sandbox (`None` to remove it)
+pub fn install(sandbox: Option<WriteSandbox>) {
+    *INSTALLED.write().unwrap() = sandbox;
+}
+
+/// The process-wide sandbox: the installed one, else the one rooted at
+/// `SINTESI_WRITE_ROOT`
+pub fn installed() -> Result<Option<WriteSandbox>, Error> {
+    if let Some(sandbox) = INSTALLED.read().unwrap().clone() {
+        return Ok(Some(sandbox));
+    }
+    match std::env::var(WRITE_ROOT_ENV) {
+        Ok(root) if !root.trim().is_empty() => WriteSandbox::load(root.trim()).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Check a path against the process-wide sandbox, if there is one
+pub fn check_path(operation: &str, path: impl AsRef<Path>) -> Result<(), Error> {
+    match installed()? {
+        Some(sandbox) => sandbox.check(operation, path),
+        None => Ok(()),
+    }
+}
+
+/// Check a write to the project: refused in read-only mode (see `readonly`)
+/// or outside the process-wide sandbox
+pub fn guard_write(operation: &str, path: impl AsRef<Path>) -> Result<(), Error> {
+    ensure_writable(operation)?;
+    check_path(operation, path)
+}
+
+/// Symlinks followed at most when resolving a path, as `SYMLOOP_MAX`
+const MAX_LINKS: usize = 40;
+
+/// Absolute form of a path with `.` and `..` applied and the symlinks of its
+/// longest existing ancestor followed, as well as a dangling symlink (writing
+/// it would create its target)
+fn resolve(path: &Path) -> PathBuf {
+    resolve_links(path, MAX_LINKS)
+}
+
+fn resolve_links(path: &Path, links: usize) -> PathBuf {
+    let absolute = match path.is_absolute() {
+        true => path.to_path_buf(),
+        false => std::env::current_dir().unwrap_or_default().join(path),
+    };
+    let mut resolved = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            // Symlinks in `resolved` are already followed, so this is the
+            // parent of their target, as the file system sees it
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            component => {
+                resolved.push(component);
+                if resolved.exists() {
+                    resolved = canonical(&resolved);
+                } else if let Some(target) = fs::read_link(&resolved).ok().filter(|_| links > 0) {
+                    resolved.pop();
+                    resolved = resolve_links(&resolved.join(target), links - 1);
+                }
+            }
+        }
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confines_writes_to_root_and_allowlist() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("project");
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+
+        let sandbox = WriteSandbox::load(&root).unwrap();
+        assert!(sandbox.allows(root.join("docs/auth.md")));
+        assert!(sandbox.allows(root.join("src/new/file.ts")));
+        assert!(!sandbox.allows(root.join("../outside.md")));
+        assert!(!sandbox.allows(root.join("docs/../../outside.md")));
+        assert!(!sandbox.allows(dir.path().join("outside.md")));
+
+        fs::write(
+            root.join(CONFIG_FILE),
+            r#"{"writeAllowlist": ["docs"], "gate": {}}"#,
+        )
+        .unwrap();
+        let sandbox = WriteSandbox::load(&root).unwrap();
+        assert!(sandbox.allows(root.join("docs/guide/auth.md")));
+        assert!(sandbox.allows(root.join(MAP_FILE)));
+        assert!(sandbox.allows(root.join(STATE_DIR).join("ast-cache.json")));
+        assert!(!sandbox.allows(root.join("src/auth.ts")));
+        assert!(!sandbox.allows(root.join("docs-old/auth.md")));
+
+        let error = sandbox
+            .check("write files", root.join("src/auth.ts"))
+            .unwrap_err();
+        assert!(matches!(error, Error::PathNotAllowed { .. }));
+        assert!(error
+            .to_string()
+            .starts_with("Path not allowed: write files to "));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_follows_symlinks_out_of_the_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("project");
+        let outside = dir.path().join("outside");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("docs")).unwrap();
+
+        let sandbox = WriteSandbox::new(&root);
+        assert!(!sandbox.allows(root.join("docs/auth.md")));
+        // `..` leaves the symlink's target, not the link
+        assert!(!sandbox.allows(root.join("docs/../secret.md")));
+        // Writing a dangling symlink would create its target
+        std::os::unix::fs::symlink(outside.join("missing.md"), root.join("link.md")).unwrap();
+        assert!(!sandbox.allows(root.join("link.md")));
+    }
+}
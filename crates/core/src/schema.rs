@@ -0,0 +1,107 @@
+//! JSON Schemas for result types
+//!
+//! Every result object returned to JavaScript derives `Serialize` and
+//! `JsonSchema` with the same camelCase field names the bindings expose, so
+//! downstream tools can validate Sintesi output. The generated schemas are
+//! shipped with the package under `schemas/`; the test in this module fails
+//! when they are stale and regenerates them when run with
+//! `SINTESI_UPDATE_SCHEMAS=1`.
+
+use crate::apply::Plan;
+use crate::napi::apply::{ApplyResultJs, RefactorResultJs};
+use crate::napi::ast::{AnalysisResultJs, ApiDiffJs, DiagnosticJs, ExportedSymbolJs, TestCaseJs};
+use crate::napi::content::{
+    BrokenReferenceJs, ExtractionResult, FileDiscoveryResult, KeyedExtractionResult, LintFindingJs,
+    MisspellingJs, SintesiAnchor,
+};
+use crate::napi::drift::{DriftEntryJs, PolicyViolationJs, RelocationJs};
+use crate::napi::git::RemoteAnalysisResult;
+use crate::napi::init::{InitResultJs, MigrationResultJs};
+use crate::napi::pipeline::PipelineRunJs;
+use crate::napi::report::{EvaluationJs, PackageSummaryJs, StalenessReportJs};
+use crate::napi::search::{SearchPageJs, SearchResultJs, SkippedFileJs};
+use crate::types::CodeSignature;
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+/// Directory (relative to the crate root) the schemas are shipped in
+pub const SCHEMA_DIR: &str = "schemas";
+
+/// JSON Schemas of all result types, by type name
+pub fn result_schemas() -> Vec<(&'static str, RootSchema)> {
+    vec![
+        ("AnalysisResult", schema_for!(AnalysisResultJs)),
+        ("ApiDiff", schema_for!(ApiDiffJs)),
+        ("ApplyResult", schema_for!(ApplyResultJs)),
+        ("BrokenReference", schema_for!(BrokenReferenceJs)),
+        ("CodeSignature", schema_for!(CodeSignature)),
+        ("Diagnostic", schema_for!(DiagnosticJs)),
+        ("DriftEntry", schema_for!(DriftEntryJs)),
+        ("Evaluation", schema_for!(EvaluationJs)),
+        ("ExportedSymbol", schema_for!(ExportedSymbolJs)),
+        ("ExtractionResult", schema_for!(ExtractionResult)),
+        ("FileDiscoveryResult", schema_for!(FileDiscoveryResult)),
+        ("InitResult", schema_for!(InitResultJs)),
+        ("KeyedExtractionResult", schema_for!(KeyedExtractionResult)),
+        ("LintFinding", schema_for!(LintFindingJs)),
+        ("MigrationResult", schema_for!(MigrationResultJs)),
+        ("Misspelling", schema_for!(MisspellingJs)),
+        ("PackageSummary", schema_for!(PackageSummaryJs)),
+        ("PipelineRun", schema_for!(PipelineRunJs)),
+        ("Plan", schema_for!(Plan)),
+        ("PolicyViolation", schema_for!(PolicyViolationJs)),
+        ("RefactorResult", schema_for!(RefactorResultJs)),
+        ("Relocation", schema_for!(RelocationJs)),
+        ("RemoteAnalysisResult", schema_for!(RemoteAnalysisResult)),
+        ("SearchPage", schema_for!(SearchPageJs)),
+        ("SearchResult", schema_for!(SearchResultJs)),
+        ("SintesiAnchor", schema_for!(SintesiAnchor)),
+        ("SkippedFile", schema_for!(SkippedFileJs)),
+        ("StalenessReport", schema_for!(StalenessReportJs)),
+        ("TestCase", schema_for!(TestCaseJs)),
+    ]
+}
+
+/// Render a schema the way it is shipped (pretty-printed, trailing newline)
+pub fn render_schema(schema: &RootSchema) -> String {
+    let mut json = serde_json::to_string_pretty(schema).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_shipped_schemas_up_to_date() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(SCHEMA_DIR);
+        let update = std::env::var("SINTESI_UPDATE_SCHEMAS").is_ok();
+
+        for (name, schema) in result_schemas() {
+            let path = dir.join(format!("{}.schema.json", name));
+            let expected = render_schema(&schema);
+            if update {
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(&path, expected).unwrap();
+                continue;
+            }
+            let shipped = fs::read_to_string(&path).unwrap_or_default();
+            assert!(
+                shipped == expected,
+                "{} is stale; rerun tests with SINTESI_UPDATE_SCHEMAS=1",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_field_names_match_bindings() {
+        let schema = render_schema(&schema_for!(DriftEntryJs));
+        assert!(schema.contains("\"entryId\""));
+        assert!(schema.contains("\"firstDetected\""));
+        assert!(!schema.contains("entry_id"));
+    }
+}
@@ -0,0 +1,201 @@
+//! Trigram index for repeated literal searches
+//!
+//! `TrigramIndex` records which files contain each three-byte sequence
+//! (ASCII letters lowercased). A literal of three bytes or more can only
+//! occur in files containing all of its trigrams, so `search_indexed` reads
+//! those candidates instead of every file. Regular expressions and shorter
+//! literals fall back to scanning the indexed files.
+//!
+//! The index is kept by long-lived processes (see `daemon::ProjectState`)
+//! and patched per changed file with `update_file`/`remove_file`.
+
+use super::{search_files, SearchOptions, SearchPage};
+use crate::error::Error;
+use crate::provider::ContentProvider;
+use std::collections::{HashMap, HashSet};
+
+type Trigram = [u8; 3];
+
+/// Files containing each trigram
+#[derive(Debug, Clone, Default)]
+pub struct TrigramIndex {
+    /// Trigrams of each indexed file
+    files: HashMap<String, HashSet<Trigram>>,
+    /// Files containing each trigram
+    postings: HashMap<Trigram, HashSet<String>>,
+}
+
+impl TrigramIndex {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index every file a provider lists
+    ///
+    /// Files that cannot be read as UTF-8 text are left out, as they are
+    /// skipped by searches.
+    pub fn build(provider: &dyn ContentProvider) -> Self {
+        let mut index = Self::new();
+        for path in provider.list_files().unwrap_or_default() {
+            if let Ok(content) = provider.read(&path) {
+                index.update_file(&path.to_string_lossy().replace('\\', "/"), &content);
+            }
+        }
+        index
+    }
+
+    /// Index new content for a file, replacing what was indexed before
+    pub fn update_file(&mut self, path: &str, content: &str) {
+        self.remove_file(path);
+        let trigrams = trigrams(content.as_bytes());
+        for trigram in &trigrams {
+            self.postings
+                .entry(*trigram)
+                .or_default()
+                .insert(path.to_string());
+        }
+        self.files.insert(path.to_string(), trigrams);
+    }
+
+    /// Forget a file
+    pub fn remove_file(&mut self, path: &str) {
+        let Some(trigrams) = self.files.remove(path) else {
+            return;
+        };
+        for trigram in trigrams {
+            if let Some(paths) = self.postings.get_mut(&trigram) {
+                paths.remove(path);
+                if paths.is_empty() {
+                    self.postings.remove(&trigram);
+                }
+            }
+        }
+    }
+
+    /// Number of indexed files
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Check whether no file is indexed
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Indexed files that may match a search
+    ///
+    /// Every indexed file when the index cannot narrow the search (regular
+    /// expressions, literals shorter than a trigram, case-insensitive
+    /// literals with non-ASCII letters).
+    pub fn candidate_files(&self, pattern: &str, options: &SearchOptions) -> Vec<String> {
+        self.candidates(pattern, options)
+            .unwrap_or_else(|| self.files.keys().cloned().collect())
+    }
+
+    /// Files containing all trigrams of a literal search, if it is one
+    fn candidates(&self, pattern: &str, options: &SearchOptions) -> Option<Vec<String>> {
+        if !options.fixed_string || pattern.len() < 3 {
+            return None;
+        }
+        if options.case_insensitive && !pattern.is_ascii() {
+            return None;
+        }
+
+        let mut candidates: Option<HashSet<&String>> = None;
+        for trigram in trigrams(pattern.as_bytes()) {
+            let paths: HashSet<&String> = self
+                .postings
+                .get(&trigram)
+                .map(|paths| paths.iter().collect())
+                .unwrap_or_default();
+            candidates = Some(match candidates {
+                Some(found) => found.intersection(&paths).copied().collect(),
+                None => paths,
+            });
+        }
+        Some(candidates?.into_iter().cloned().collect())
+    }
+}
+
+/// Distinct trigrams of a byte string, with ASCII letters lowercased
+fn trigrams(bytes: &[u8]) -> HashSet<Trigram> {
+    bytes
+        .windows(3)
+        .map(|w| {
+            [
+                w[0].to_ascii_lowercase(),
+                w[1].to_ascii_lowercase(),
+                w[2].to_ascii_lowercase(),
+            ]
+        })
+        .collect()
+}
+
+/// Search the files of an index
+///
+/// Same results as `search_project` over the indexed files, but literal
+/// searches only read the files that contain all of the literal's trigrams.
+/// File contents are read from the provider, so the index only needs to be
+/// current enough to list every file that may match.
+///
+/// # Errors
+/// Same as `search_project`.
+pub fn search_indexed(
+    provider: &dyn ContentProvider,
+    index: &TrigramIndex,
+    pattern: &str,
+    options: &SearchOptions,
+) -> Result<SearchPage, Error> {
+    search_files(
+        provider,
+        index.candidate_files(pattern, options),
+        pattern,
+        options,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::MemoryProvider;
+    use crate::search::search_project;
+
+    #[test]
+    fn test_indexed_search_matches_full_scan() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/a.ts", "export function login() {}\n");
+        provider.set("src/b.ts", "// Login flow\nlogout();\n");
+        provider.set("docs/c.md", "Nothing here\n");
+        let mut index = TrigramIndex::build(&provider);
+        assert_eq!(index.len(), 3);
+
+        let cases = [
+            ("login", SearchOptions::new().fixed_string(true)),
+            (
+                "LOGIN",
+                SearchOptions::new()
+                    .fixed_string(true)
+                    .case_insensitive(true),
+            ),
+            ("log(in|out)", SearchOptions::new()),
+            ("lo", SearchOptions::new().fixed_string(true)),
+        ];
+        for (pattern, options) in &cases {
+            assert_eq!(
+                search_indexed(&provider, &index, pattern, options).unwrap(),
+                search_project(&provider, pattern, options).unwrap()
+            );
+        }
+        let literal = SearchOptions::new().fixed_string(true);
+        assert_eq!(index.candidates("export", &literal).unwrap(), ["src/a.ts"]);
+
+        provider.set("src/b.ts", "login();\n");
+        index.update_file("src/b.ts", "login();\n");
+        index.remove_file("src/a.ts");
+        let page = search_indexed(&provider, &index, "login", &literal).unwrap();
+        let paths: Vec<&str> = page.results.iter().map(|r| r.file_path.as_str()).collect();
+        assert_eq!(paths, ["src/b.ts"]);
+        assert!(index.candidates("logout", &literal).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,586 @@
+//! Project text search
+//!
+//! `search_project` runs a pattern over every file a content provider lists,
+//! so the same search works on the working tree, a git revision, or an
+//! in-memory overlay. Each match carries its byte offset in the file, its
+//! byte range within the matched line(s), and the untrimmed text, so editors
+//! and the anchor-rewrite tooling can edit exactly the matched text.
+//!
+//! Patterns are regular expressions matched within single lines by default.
+//! Multiline mode matches against the whole file, so a pattern can span
+//! lines (`(?s)<!-- sintesi:start.*?sintesi:end[^>]*-->` matches a whole
+//! anchor block). Fixed-string mode searches for the pattern literally with
+//! a SIMD substring search instead of the regex engine.
+//!
+//! Results are sorted by path, then position. A `limit` caps the size of a
+//! page; the page's `next_cursor` resumes the search right after its last
+//! result, so callers can stream large result sets page by page.
+//!
+//! A `timeout` bounds the wall-clock time of a search; it is checked between
+//! files and fails the search with `Error::Timeout`.
+//!
+//! Generated and minified files (see `text::detect_generated`) are left out
+//! and listed in the page's `skipped` unless `include_generated` is set:
+//! matches in bundles and codegen output are noise when editing sources.
+//!
+//! Processes that search the same project repeatedly can keep a
+//! `TrigramIndex` and use `search_indexed`, which only reads the files that
+//! may contain a literal.
+
+pub mod index;
+
+pub use index::{search_indexed, TrigramIndex};
+
+use crate::error::Error;
+use crate::provider::ContentProvider;
+use crate::text::SkippedFile;
+use memchr::memmem;
+use regex::{Regex, RegexBuilder};
+use std::time::{Duration, Instant};
+
+/// Options for `search_project`
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Match letters regardless of case
+    pub case_insensitive: bool,
+    /// Match against whole files so patterns can span lines; `^`/`$` still
+    /// match at line boundaries
+    pub multiline: bool,
+    /// Treat the pattern as a literal string rather than a regular expression
+    pub fixed_string: bool,
+    /// Maximum number of results per page (unlimited if `None`)
+    pub limit: Option<usize>,
+    /// Resume after the result a previous page's `next_cursor` points at
+    pub cursor: Option<String>,
+    /// Give up with `Error::Timeout` after this long (unlimited if `None`)
+    pub timeout: Option<Duration>,
+    /// Also search generated and minified files
+    pub include_generated: bool,
+}
+
+impl SearchOptions {
+    /// Create default options (case-sensitive, single-line regex)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match letters regardless of case
+    pub fn case_insensitive(mut self, value: bool) -> Self {
+        self.case_insensitive = value;
+        self
+    }
+
+    /// Let patterns span lines
+    pub fn multiline(mut self, value: bool) -> Self {
+        self.multiline = value;
+        self
+    }
+
+    /// Treat the pattern as a literal string
+    pub fn fixed_string(mut self, value: bool) -> Self {
+        self.fixed_string = value;
+        self
+    }
+
+    /// Return at most `limit` results
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Continue from a previous page
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// Fail searches running longer than `timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Search generated and minified files too
+    pub fn include_generated(mut self, include: bool) -> Self {
+        self.include_generated = include;
+        self
+    }
+}
+
+/// A single match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// File containing the match, relative to the provider root
+    pub file_path: String,
+    /// Line the match starts on (1-based)
+    pub line: usize,
+    /// Line the match ends on (1-based); differs from `line` only for
+    /// matches spanning lines
+    pub end_line: usize,
+    /// Byte offset of the match in the file
+    pub byte_offset: usize,
+    /// Byte offset of the match start within `line_text`
+    pub column_start: usize,
+    /// Byte offset just past the match end within `line_text`
+    pub column_end: usize,
+    /// The matched line(s), untrimmed, without the final line terminator
+    /// (unless the match itself ends with it)
+    pub line_text: String,
+}
+
+impl SearchResult {
+    /// The matched text
+    pub fn matched(&self) -> &str {
+        &self.line_text[self.column_start..self.column_end]
+    }
+}
+
+/// One page of search results
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchPage {
+    /// Matches, sorted by path, then position
+    pub results: Vec<SearchResult>,
+    /// Cursor for the next page, if the limit cut the results short
+    pub next_cursor: Option<String>,
+    /// Files searched for this page that are not text (binary or
+    /// undecodable), or are generated or minified
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Position of the last result of a page: `<byte offset>:<file path>`
+struct Cursor {
+    file_path: String,
+    byte_offset: usize,
+}
+
+impl Cursor {
+    fn parse(cursor: &str) -> Result<Self, Error> {
+        cursor
+            .split_once(':')
+            .and_then(|(offset, path)| {
+                Some(Cursor {
+                    file_path: path.to_string(),
+                    byte_offset: offset.parse().ok()?,
+                })
+            })
+            .ok_or_else(|| Error::from_reason(format!("Invalid search cursor: {}", cursor)))
+    }
+
+    fn after(result: &SearchResult) -> String {
+        format!("{}:{}", result.byte_offset, result.file_path)
+    }
+}
+
+/// How matches are found
+enum Matcher {
+    /// Regex matched within each line
+    Lines(Regex),
+    /// Regex matched against the whole file
+    Whole(Regex),
+    /// Literal substring (never contains a line break unless multiline)
+    Literal(Box<memmem::Finder<'static>>),
+}
+
+impl Matcher {
+    fn new(pattern: &str, options: &SearchOptions) -> Result<Self, Error> {
+        if options.fixed_string && !options.case_insensitive {
+            if !options.multiline && pattern.contains('\n') {
+                return Err(Error::from_reason(
+                    "Search pattern contains a line break; enable multiline mode",
+                ));
+            }
+            let finder = memmem::Finder::new(pattern.as_bytes()).into_owned();
+            return Ok(Matcher::Literal(Box::new(finder)));
+        }
+
+        // Case-insensitive literals go through the regex engine
+        let pattern = if options.fixed_string {
+            regex::escape(pattern)
+        } else {
+            pattern.to_string()
+        };
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(options.case_insensitive)
+            .multi_line(options.multiline)
+            .build()
+            .map_err(|e| Error::from_reason(format!("Invalid search pattern: {}", e)))?;
+        Ok(if options.multiline {
+            Matcher::Whole(regex)
+        } else {
+            Matcher::Lines(regex)
+        })
+    }
+
+    /// Byte ranges of the non-empty matches in `content`, in order
+    fn find(&self, content: &str) -> Vec<(usize, usize)> {
+        let mut ranges = Vec::new();
+        match self {
+            Matcher::Lines(regex) => {
+                let mut line_start = 0;
+                for raw_line in content.split_inclusive('\n') {
+                    let line = trim_line_end(raw_line);
+                    ranges.extend(
+                        regex
+                            .find_iter(line)
+                            .map(|m| (line_start + m.start(), line_start + m.end())),
+                    );
+                    line_start += raw_line.len();
+                }
+            }
+            Matcher::Whole(regex) => {
+                ranges.extend(regex.find_iter(content).map(|m| (m.start(), m.end())));
+            }
+            Matcher::Literal(finder) => {
+                let len = finder.needle().len();
+                ranges.extend(
+                    finder
+                        .find_iter(content.as_bytes())
+                        .map(|start| (start, start + len)),
+                );
+            }
+        }
+        ranges.retain(|(start, end)| start < end);
+        ranges
+    }
+}
+
+/// Strip a line's terminator (`\n` or `\r\n`)
+fn trim_line_end(line: &str) -> &str {
+    line.strip_suffix('\n')
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .unwrap_or(line)
+}
+
+/// Search every file of a project
+///
+/// Results are sorted by path, then position, and start after `cursor` when
+/// one is given. UTF-16 files are searched as text; binary and undecodable
+/// files, and generated files unless `include_generated` is set, are listed
+/// in the page's `skipped`. Empty matches are not reported.
+///
+/// # Errors
+/// Fails if the pattern is not a valid regular expression, a fixed string
+/// contains a line break outside multiline mode, the cursor is malformed, the
+/// files cannot be listed, or the timeout is exceeded.
+pub fn search_project(
+    provider: &dyn ContentProvider,
+    pattern: &str,
+    options: &SearchOptions,
+) -> Result<SearchPage, Error> {
+    let files = provider
+        .list_files()
+        .map_err(|e| Error::from_reason(format!("Failed to list files: {}", e)))?
+        .iter()
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .collect();
+    search_files(provider, files, pattern, options)
+}
+
+/// Search some of a project's files
+///
+/// Behaves like `search_project` restricted to `files` (paths relative to
+/// the provider root, in any order).
+///
+/// # Errors
+/// Fails if the pattern is not a valid regular expression, a fixed string
+/// contains a line break outside multiline mode, the cursor is malformed, or
+/// the timeout is exceeded.
+pub fn search_files(
+    provider: &dyn ContentProvider,
+    mut files: Vec<String>,
+    pattern: &str,
+    options: &SearchOptions,
+) -> Result<SearchPage, Error> {
+    let matcher = Matcher::new(pattern, options)?;
+    let cursor = options.cursor.as_deref().map(Cursor::parse).transpose()?;
+    files.sort();
+    if let Some(cursor) = &cursor {
+        files.retain(|path| *path >= cursor.file_path);
+    }
+
+    let limit = options.limit.unwrap_or(usize::MAX);
+    let started = Instant::now();
+    let mut page = SearchPage::default();
+    for file_path in files {
+        if let Some(timeout) = options.timeout.filter(|t| started.elapsed() >= *t) {
+            return Err(Error::Timeout {
+                operation: "search".to_string(),
+                limit: timeout,
+            });
+        }
+        let content = match provider.read(file_path.as_ref()) {
+            Ok(content) => content,
+            Err(e) => {
+                page.skipped.extend(SkippedFile::from_error(&file_path, &e));
+                continue;
+            }
+        };
+        if !options.include_generated {
+            if let Some(skipped) = SkippedFile::generated(&file_path, &content) {
+                page.skipped.push(skipped);
+                continue;
+            }
+        }
+        let mut ranges = matcher.find(&content);
+        if let Some(cursor) = cursor.as_ref().filter(|c| c.file_path == file_path) {
+            ranges.retain(|(start, _)| *start > cursor.byte_offset);
+        }
+        // Locate one result past the limit to learn whether another page exists
+        ranges.truncate((limit - page.results.len()).saturating_add(1));
+        page.results.extend(locate(&file_path, &content, ranges));
+        if page.results.len() > limit {
+            page.results.truncate(limit);
+            page.next_cursor = page.results.last().map(Cursor::after);
+            break;
+        }
+    }
+    Ok(page)
+}
+
+/// Turn ordered byte ranges into results with line information
+fn locate(file_path: &str, content: &str, ranges: Vec<(usize, usize)>) -> Vec<SearchResult> {
+    let bytes = content.as_bytes();
+    let mut line = 1;
+    let mut scanned = 0;
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            line += memchr::memchr_iter(b'\n', &bytes[scanned..start]).count();
+            scanned = start;
+            let line_start = memchr::memrchr(b'\n', &bytes[..start]).map_or(0, |i| i + 1);
+            let breaks = memchr::memchr_iter(b'\n', &bytes[start..end]).count();
+
+            // Extend to the end of the last matched line, unless the match
+            // already ends with a line break
+            let text_end = if bytes[end - 1] == b'\n' {
+                end
+            } else {
+                let line_end =
+                    memchr::memchr(b'\n', &bytes[end..]).map_or(content.len(), |i| end + i);
+                let rest = &content[end..line_end];
+                end + rest.strip_suffix('\r').unwrap_or(rest).len()
+            };
+
+            SearchResult {
+                file_path: file_path.to_string(),
+                line,
+                end_line: line + breaks - usize::from(bytes[end - 1] == b'\n'),
+                byte_offset: start,
+                column_start: start - line_start,
+                column_end: end - line_start,
+                line_text: content[line_start..text_end].to_string(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::MemoryProvider;
+    use crate::text::SkipReason;
+
+    #[test]
+    fn test_results_locate_matches() {
+        let mut provider = MemoryProvider::new();
+        provider.set(
+            "docs/a.md",
+            "# Title\r\n  <!-- sintesi:start id=\"a\" -->\n",
+        );
+        provider.set("src/b.ts", "const Sintesi = 1; // sintesi\n");
+
+        let results = search_project(&provider, "sintesi", &SearchOptions::new())
+            .unwrap()
+            .results;
+        assert_eq!(
+            results[0],
+            SearchResult {
+                file_path: "docs/a.md".to_string(),
+                line: 2,
+                end_line: 2,
+                byte_offset: 16,
+                column_start: 7,
+                column_end: 14,
+                line_text: "  <!-- sintesi:start id=\"a\" -->".to_string(),
+            }
+        );
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].column_start, 22);
+
+        let content = provider.read("src/b.ts".as_ref()).unwrap();
+        let options = SearchOptions::new().case_insensitive(true);
+        for result in search_project(&provider, "sintesi", &options)
+            .unwrap()
+            .results
+        {
+            if result.file_path == "src/b.ts" {
+                assert_eq!(&content[result.byte_offset..][..7], result.matched());
+            }
+        }
+        assert!(search_project(&provider, "(", &options).is_err());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/a.ts", "sintesi\n");
+
+        let options = SearchOptions::new().timeout(Duration::ZERO);
+        let error = search_project(&provider, "sintesi", &options).unwrap_err();
+        assert!(matches!(error, Error::Timeout { ref operation, .. } if operation == "search"));
+        assert!(error.is_limit());
+
+        let options = SearchOptions::new().timeout(Duration::from_secs(60));
+        assert_eq!(
+            search_project(&provider, "sintesi", &options)
+                .unwrap()
+                .results
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_multiline_and_fixed_string_modes() {
+        let mut provider = MemoryProvider::new();
+        provider.set(
+            "docs/a.md",
+            "Intro\n<!-- sintesi:start id=\"a\" -->\nText (a+b)\r\n<!-- sintesi:end id=\"a\" -->\nOutro\n",
+        );
+
+        let block = r#"(?s)<!-- sintesi:start.*?sintesi:end[^>]*-->"#;
+        let options = SearchOptions::new().multiline(true);
+        let results = search_project(&provider, block, &options).unwrap().results;
+        assert_eq!(results.len(), 1);
+        assert_eq!((results[0].line, results[0].end_line), (2, 4));
+        assert_eq!(results[0].byte_offset, 6);
+        assert!(results[0].line_text.ends_with("id=\"a\" -->"));
+        assert_eq!(results[0].matched(), results[0].line_text);
+
+        let fixed = SearchOptions::new().fixed_string(true);
+        let results = search_project(&provider, "(a+b)", &fixed).unwrap().results;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].line, 3);
+        assert_eq!(results[0].line_text, "Text (a+b)");
+        assert_eq!(results[0].matched(), "(a+b)");
+
+        let insensitive = fixed.clone().case_insensitive(true);
+        assert_eq!(
+            search_project(&provider, "TEXT (A+B)", &insensitive)
+                .unwrap()
+                .results
+                .len(),
+            1
+        );
+        assert!(search_project(&provider, "a\nb", &fixed).is_err());
+        let spanning = search_project(&provider, "-->\nText", &fixed.multiline(true))
+            .unwrap()
+            .results;
+        assert_eq!((spanning[0].line, spanning[0].end_line), (2, 3));
+    }
+
+    #[test]
+    fn test_pages_are_sorted_and_resumable() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/b.ts", "x x\nx\n");
+        provider.set("src/a.ts", "x\n");
+        provider.set("src/c.ts", "y\n");
+
+        let all = search_project(&provider, "x", &SearchOptions::new()).unwrap();
+        let positions: Vec<(&str, usize)> = all
+            .results
+            .iter()
+            .map(|r| (r.file_path.as_str(), r.byte_offset))
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                ("src/a.ts", 0),
+                ("src/b.ts", 0),
+                ("src/b.ts", 2),
+                ("src/b.ts", 4)
+            ]
+        );
+        assert_eq!(all.next_cursor, None);
+
+        let mut paged = Vec::new();
+        let mut options = SearchOptions::new().limit(2);
+        loop {
+            let page = search_project(&provider, "x", &options).unwrap();
+            assert!(page.results.len() <= 2);
+            paged.extend(page.results);
+            match page.next_cursor {
+                Some(cursor) => options = options.cursor(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(paged, all.results);
+
+        let exact = search_project(&provider, "x", &SearchOptions::new().limit(4)).unwrap();
+        assert_eq!(exact.next_cursor, None);
+        let bad = SearchOptions::new().cursor("src/a.ts");
+        assert!(search_project(&provider, "x", &bad).is_err());
+    }
+
+    #[test]
+    fn test_utf16_searched_and_binary_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let utf16: Vec<u8> = "\u{FEFF}const sintesi = 1;\r\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        std::fs::write(dir.path().join("a.ts"), utf16).unwrap();
+        std::fs::write(dir.path().join("b.png"), b"\x89PNG\r\n\x1a\n\0sintesi").unwrap();
+        std::fs::write(dir.path().join("c.ts"), b"// sintesi caf\xE9\n").unwrap();
+
+        let provider = crate::provider::DiskProvider::new(dir.path());
+        let page = search_project(&provider, "sintesi", &SearchOptions::new()).unwrap();
+        assert_eq!(page.results.len(), 1);
+        assert_eq!(page.results[0].line_text, "const sintesi = 1;");
+        let skipped: Vec<(&str, SkipReason)> = page
+            .skipped
+            .iter()
+            .map(|s| (s.file_path.as_str(), s.reason))
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                ("b.png", SkipReason::Binary),
+                ("c.ts", SkipReason::Undecodable)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generated_files_skipped_by_default() {
+        let mut provider = MemoryProvider::new();
+        provider.set("src/auth.ts", "export function login() {}\n");
+        provider.set(
+            "src/auth_pb.ts",
+            "// @generated\nexport function login() {}\n",
+        );
+        provider.set("dist/app.min.js", "function login(a){return a}".repeat(100));
+
+        let page = search_project(&provider, "login", &SearchOptions::new()).unwrap();
+        let paths: Vec<&str> = page.results.iter().map(|r| r.file_path.as_str()).collect();
+        assert_eq!(paths, ["src/auth.ts"]);
+        let skipped: Vec<(&str, SkipReason)> = page
+            .skipped
+            .iter()
+            .map(|s| (s.file_path.as_str(), s.reason))
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                ("dist/app.min.js", SkipReason::Minified),
+                ("src/auth_pb.ts", SkipReason::Generated)
+            ]
+        );
+
+        let all = SearchOptions::new().include_generated(true);
+        let page = search_project(&provider, "login", &all).unwrap();
+        assert_eq!(page.results.len(), 102);
+        assert!(page.skipped.is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+//! Test support for projects built on Sintesi
+//!
+//! Enabled by the `test-support` feature. A `TestProject` is a throwaway
+//! project made from a map of file paths to contents: write sources and
+//! markdown, link the anchors to the code as it is (`sync`), change the code,
+//! and run the pipeline to see what Sintesi reports.
+//!
+//! ```ignore
+//! let project = TestProject::new()
+//!     .file("src/auth.ts", "export function login(user: string): void {}\n")
+//!     .file("docs/auth.md", "<!-- sintesi:start id=\"login\" code_ref=\"src/auth.ts#login\" -->\n...")
+//!     .sync();
+//! project.write("src/auth.ts", "export function login(user: User): void {}\n");
+//!
+//! let run = project.run();
+//! run.assert_drifted(&["login"]);
+//! assert_eq!(snapshot(&run), "...");
+//! ```
+//!
+//! `snapshot` renders a run as stable text (anchors, drift, plan, coverage,
+//! and gate status) for comparison with a stored snapshot. Pipeline runs are
+//! dry runs unless configured otherwise, so a project can be run repeatedly.
+
+use crate::drift::{current_signatures, SintesiMap, SintesiMapEntry, MAP_FILE};
+use crate::error::Error;
+use crate::pipeline::{run_pipeline, PipelineConfig, PipelineRun};
+use crate::provider::DiskProvider;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A project in a temporary directory, removed when dropped
+#[derive(Debug)]
+pub struct TestProject {
+    dir: TempDir,
+}
+
+impl Default for TestProject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestProject {
+    /// Create a project with nothing but an empty map
+    ///
+    /// # Panics
+    /// If the temporary directory or the map cannot be created
+    pub fn new() -> Self {
+        let dir = tempfile::tempdir().expect("failed to create a test project directory");
+        SintesiMap::new()
+            .save(dir.path().join(MAP_FILE))
+            .expect("failed to write the test project map");
+        Self { dir }
+    }
+
+    /// Create a project from file paths and contents
+    pub fn from_files<P: AsRef<str>, C: AsRef<str>>(
+        files: impl IntoIterator<Item = (P, C)>,
+    ) -> Self {
+        let project = Self::new();
+        for (path, content) in files {
+            project.write(path.as_ref(), content.as_ref());
+        }
+        project
+    }
+
+    /// Add a file (see `write`)
+    pub fn file(self, path: &str, content: &str) -> Self {
+        self.write(path, content);
+        self
+    }
+
+    /// Link every anchor with a `code_ref` to the current signature of its
+    /// symbol, so the project starts documented and in sync
+    ///
+    /// # Panics
+    /// If the project's anchors cannot be extracted or the map written
+    pub fn sync(self) -> Self {
+        self.link_anchors()
+            .unwrap_or_else(|e| panic!("failed to sync the test project: {}", e));
+        self
+    }
+
+    /// Link every anchor with a `code_ref` to the current signature of its
+    /// symbol in the map (see `sync`)
+    pub fn link_anchors(&self) -> Result<(), Error> {
+        let run = self.run_with(&PipelineConfig::new())?;
+        let mut map = SintesiMap::load(self.root().join(MAP_FILE))?;
+        for anchor in &run.anchors {
+            let Some(code_ref) = &anchor.code_ref else {
+                continue;
+            };
+            map.upsert(SintesiMapEntry {
+                id: anchor.id.clone(),
+                code_ref: code_ref.clone(),
+                doc_file: anchor.file_path.to_string_lossy().replace('\\', "/"),
+                code_signature_hash: String::new(),
+                last_updated: 0,
+                symbol_id: None,
+                symbol_key: None,
+                deprecated: false,
+                content_hash: None,
+                code_signature: None,
+            });
+        }
+        let current = current_signatures(&DiskProvider::new(self.root()), &map);
+        for entry in &mut map.entries {
+            if let Some(hash) = current.hashes.get(&entry.code_ref) {
+                entry.code_signature_hash = hash.clone();
+                entry.code_signature = current.signatures.get(&entry.code_ref).cloned();
+            }
+        }
+        map.save(self.root().join(MAP_FILE))
+    }
+
+    /// Directory of the project
+    pub fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Write a file, creating its directories
+    ///
+    /// # Panics
+    /// If the file cannot be written
+    pub fn write(&self, path: &str, content: &str) {
+        let path = self.root().join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("failed to create a test project directory");
+        }
+        fs::write(&path, content)
+            .unwrap_or_else(|e| panic!("failed to write {}: {}", path.display(), e));
+    }
+
+    /// Remove a file
+    ///
+    /// # Panics
+    /// If the file cannot be removed
+    pub fn remove(&self, path: &str) {
+        let path = self.root().join(path);
+        fs::remove_file(&path)
+            .unwrap_or_else(|e| panic!("failed to remove {}: {}", path.display(), e));
+    }
+
+    /// Content of a file
+    ///
+    /// # Panics
+    /// If the file cannot be read
+    pub fn read(&self, path: &str) -> String {
+        let path = self.root().join(path);
+        fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e))
+    }
+
+    /// The project's map (empty before `sync`)
+    ///
+    /// # Panics
+    /// If the map cannot be read
+    pub fn map(&self) -> SintesiMap {
+        SintesiMap::load(self.root().join(MAP_FILE)).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Run the pipeline as a dry run
+    ///
+    /// # Panics
+    /// If the pipeline fails
+    pub fn run(&self) -> PipelineRun {
+        self.run_with(&PipelineConfig::new())
+            .unwrap_or_else(|e| panic!("pipeline failed: {}", e))
+    }
+
+    /// Run the pipeline with a configuration
+    pub fn run_with(&self, config: &PipelineConfig) -> Result<PipelineRun, Error> {
+        run_pipeline(self.root(), config)
+    }
+}
+
+/// Assertions on a pipeline run, panicking with what was found instead
+pub trait RunAssertions {
+    /// Assert the IDs of the anchors found, in file and line order
+    fn assert_anchors(&self, ids: &[&str]) -> &Self;
+
+    /// Assert the IDs of the drifted or removed map entries, in map order
+    fn assert_drifted(&self, ids: &[&str]) -> &Self;
+
+    /// Assert that no map entry drifted
+    fn assert_in_sync(&self) -> &Self {
+        self.assert_drifted(&[])
+    }
+
+    /// Assert the plan's steps, as printed (`Rewrite docs/auth.md`)
+    fn assert_plan(&self, steps: &[&str]) -> &Self;
+}
+
+impl RunAssertions for PipelineRun {
+    fn assert_anchors(&self, ids: &[&str]) -> &Self {
+        let found: Vec<&str> = self.anchors.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(found, ids, "anchors");
+        self
+    }
+
+    fn assert_drifted(&self, ids: &[&str]) -> &Self {
+        let found: Vec<&str> = self.drift.drifted().map(|e| e.entry_id.as_str()).collect();
+        assert_eq!(found, ids, "drifted map entries");
+        self
+    }
+
+    fn assert_plan(&self, steps: &[&str]) -> &Self {
+        let found: Vec<String> = self.plan.steps.iter().map(|s| s.to_string()).collect();
+        assert_eq!(found, steps, "plan steps");
+        self
+    }
+}
+
+/// A run as stable text, for snapshot tests
+///
+/// Lists the anchors (`docs/auth.md:3 login -> src/auth.ts#login`), the drift
+/// of each map entry, the plan steps, the skipped files, coverage, and the gate
+/// status. Nothing depends on the temporary directory or the time of the run.
+pub fn snapshot(run: &PipelineRun) -> String {
+    let mut out = String::new();
+    out.push_str("anchors:\n");
+    for anchor in &run.anchors {
+        let file_path = anchor.file_path.to_string_lossy().replace('\\', "/");
+        let _ = write!(out, "  {}:{} {}", file_path, anchor.start_line, anchor.id);
+        if let Some(code_ref) = &anchor.code_ref {
+            let _ = write!(out, " -> {}", code_ref);
+        }
+        out.push('\n');
+    }
+    out.push_str("drift:\n");
+    for entry in &run.drift.entries {
+        let _ = writeln!(
+            out,
+            "  {} {:?} ({})",
+            entry.entry_id, entry.status, entry.code_ref
+        );
+    }
+    out.push_str("plan:\n");
+    for step in &run.plan.steps {
+        let _ = writeln!(out, "  {}", step);
+    }
+    if !run.skipped.is_empty() {
+        out.push_str("skipped:\n");
+        for skipped in &run.skipped {
+            let _ = writeln!(out, "  {} ({})", skipped.file_path, skipped.reason);
+        }
+    }
+    let _ = writeln!(
+        out,
+        "coverage: {}/{}",
+        run.coverage.documented, run.coverage.total
+    );
+    let _ = writeln!(out, "status: {}", run.evaluation.status.as_str());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Auth\n\n<!-- sintesi:start id=\"login\" code_ref=\"src/auth.ts#login\" -->\nLog a user in.\n<!-- sintesi:end id=\"login\" -->\n";
+
+    #[test]
+    fn test_project_lifecycle() {
+        let project = TestProject::from_files([
+            (
+                "src/auth.ts",
+                "export function login(user: string): void {}\nexport function logout(): void {}\n",
+            ),
+            ("docs/auth.md", DOC),
+        ])
+        .sync();
+        assert_eq!(project.map().entries.len(), 1);
+        project
+            .run()
+            .assert_anchors(&["login"])
+            .assert_in_sync()
+            .assert_plan(&[]);
+
+        project.write(
+            "src/auth.ts",
+            "export function login(user: string, remember: boolean): void {}\nexport function logout(): void {}\n",
+        );
+        let run = project.run();
+        run.assert_drifted(&["login"]);
+        assert_eq!(
+            snapshot(&run),
+            "anchors:
+  docs/auth.md:2 login -> src/auth.ts#login
+drift:
+  login Drifted (src/auth.ts#login)
+plan:
+  Regenerate anchor \"login\" in docs/auth.md (src/auth.ts#login)
+  Rewrite docs/auth.md
+  Update map entry \"login\" (src/auth.ts#login)
+coverage: 1/2
+status: fail
+"
+        );
+        // Dry runs leave the project as it was
+        assert_eq!(project.read("docs/auth.md"), DOC);
+    }
+}
@@ -0,0 +1,400 @@
+//! Reading project files as text
+//!
+//! Files are read as bytes and decoded rather than with `read_to_string`, so
+//! files saved by Windows tools are not lost: a UTF-8 byte order mark is
+//! dropped, and UTF-16 (with a byte order mark, or little-endian without one,
+//! recognized by its zero bytes) is transcoded. Binary files and files in no
+//! supported encoding fail with `io::ErrorKind::InvalidData` carrying a
+//! `SkipReason`, so callers report them as `SkippedFile`s instead of dropping
+//! them silently. `read_text_with_format` also reports the encoding and byte
+//! order mark it found, for callers that write the file back.
+//!
+//! Files over a size limit (`DEFAULT_MAX_FILE_SIZE` unless configured) are
+//! reported the same way by the analyzer, the anchor extractor, and the import
+//! graph: bundled and minified artifacts take seconds to parse and document
+//! nothing.
+//!
+//! `detect_generated` recognizes smaller build output by its content:
+//! `@generated` or `DO NOT EDIT` markers in the header, a trailing
+//! `sourceMappingURL` comment, or lines of minified code. The analyzer and
+//! search leave such files out by default.
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// How many leading bytes are inspected to tell UTF-16 from binary content
+const SNIFF_LEN: usize = 8192;
+
+/// Size in bytes above which files are skipped unless configured otherwise
+pub const DEFAULT_MAX_FILE_SIZE: usize = 1024 * 1024;
+
+/// How many leading bytes are searched for generated-code markers
+const HEADER_LEN: usize = 1024;
+
+/// How many trailing bytes are searched for a source map comment
+const TRAILER_LEN: usize = 512;
+
+/// Length in bytes from which a line with little whitespace counts as
+/// minified code
+const MINIFIED_LINE_LEN: usize = 1000;
+
+/// Unicode encoding of a text file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+/// How a file's text is stored, to write it back the same way
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextFormat {
+    pub encoding: Encoding,
+    /// Whether the file starts with a byte order mark
+    pub bom: bool,
+}
+
+impl TextFormat {
+    /// Encode text in this format, byte order mark included
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        let bom = self.bom.then_some('\u{FEFF}');
+        let chars = bom.into_iter().chain(text.chars());
+        match self.encoding {
+            Encoding::Utf8 => chars.collect::<String>().into_bytes(),
+            Encoding::Utf16Le => encode_utf16(chars, u16::to_le_bytes),
+            Encoding::Utf16Be => encode_utf16(chars, u16::to_be_bytes),
+        }
+    }
+}
+
+/// Why a file was not read as text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Binary content (NUL bytes outside UTF-16 text)
+    Binary,
+    /// Neither valid UTF-8 nor valid UTF-16
+    Undecodable,
+    /// Over the size limit (both in bytes)
+    TooLarge { size: usize, limit: usize },
+    /// Marked as generated, or compiled output with a source map
+    Generated,
+    /// Mostly long lines of minified code
+    Minified,
+}
+
+impl SkipReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkipReason::Binary => "binary",
+            SkipReason::Undecodable => "undecodable",
+            SkipReason::TooLarge { .. } => "too-large",
+            SkipReason::Generated => "generated",
+            SkipReason::Minified => "minified",
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Binary => f.write_str("binary file"),
+            SkipReason::Undecodable => f.write_str("not valid UTF-8 or UTF-16 text"),
+            SkipReason::TooLarge { size, limit } => {
+                write!(f, "{} bytes, over the {} byte limit", size, limit)
+            }
+            SkipReason::Generated => f.write_str("generated code"),
+            SkipReason::Minified => f.write_str("minified code"),
+        }
+    }
+}
+
+impl StdError for SkipReason {}
+
+/// A file left out of a search or analysis, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    /// Path of the file, relative to the project root
+    pub file_path: String,
+    pub reason: SkipReason,
+}
+
+impl SkippedFile {
+    /// The skipped file, if `error` is a decoding error from `decode`
+    pub fn from_error(file_path: &str, error: &io::Error) -> Option<Self> {
+        let reason = error.get_ref()?.downcast_ref::<SkipReason>()?;
+        Some(Self {
+            file_path: file_path.to_string(),
+            reason: *reason,
+        })
+    }
+
+    /// The skipped file, if `size` is over `limit`
+    pub fn over_limit(file_path: &str, size: usize, limit: usize) -> Option<Self> {
+        (size > limit).then(|| Self {
+            file_path: file_path.to_string(),
+            reason: SkipReason::TooLarge { size, limit },
+        })
+    }
+
+    /// The skipped file, if `content` is generated or minified (see
+    /// `detect_generated`)
+    pub fn generated(file_path: &str, content: &str) -> Option<Self> {
+        detect_generated(content).map(|reason| Self {
+            file_path: file_path.to_string(),
+            reason,
+        })
+    }
+
+    /// Whether the file was left out as generated or minified code
+    pub fn is_generated(&self) -> bool {
+        matches!(self.reason, SkipReason::Generated | SkipReason::Minified)
+    }
+}
+
+/// `SkipReason::Generated` or `SkipReason::Minified` if `content` looks like
+/// build output rather than code written by hand
+///
+/// Generated files carry an `@generated` (or `DO NOT EDIT`) marker near the
+/// top, or end with a `sourceMappingURL` comment. Minified files are mostly
+/// made of lines over `MINIFIED_LINE_LEN` bytes, less than a tenth of which
+/// is whitespace; prose written one paragraph per line has far more.
+pub fn detect_generated(content: &str) -> Option<SkipReason> {
+    let bytes = content.as_bytes();
+    let header = &bytes[..bytes.len().min(HEADER_LEN)];
+    let trailer = &bytes[bytes.len().saturating_sub(TRAILER_LEN)..];
+    if memchr::memmem::find(header, b"@generated").is_some()
+        || memchr::memmem::find(header, b"DO NOT EDIT").is_some()
+        || memchr::memmem::find(trailer, b"# sourceMappingURL=").is_some()
+    {
+        return Some(SkipReason::Generated);
+    }
+
+    let minified: usize = content
+        .lines()
+        .filter(|line| {
+            let spaces = line.bytes().filter(u8::is_ascii_whitespace).count();
+            line.len() >= MINIFIED_LINE_LEN && spaces * 10 < line.len()
+        })
+        .map(str::len)
+        .sum();
+    (minified * 2 > bytes.len()).then_some(SkipReason::Minified)
+}
+
+/// Read a file as text, decoding UTF-8 or UTF-16 (see `decode`)
+pub fn read_text(path: &Path) -> io::Result<String> {
+    decode(fs::read(path)?)
+}
+
+/// Read a file as text, with the format it is stored in (see `decode`)
+pub fn read_text_with_format(path: &Path) -> io::Result<(String, TextFormat)> {
+    decode_with_format(fs::read(path)?)
+}
+
+/// Decode file content as text
+///
+/// # Errors
+/// `io::ErrorKind::InvalidData` with a `SkipReason` for binary content and
+/// content in no supported encoding
+pub fn decode(bytes: Vec<u8>) -> io::Result<String> {
+    decode_with_format(bytes).map(|(text, _)| text)
+}
+
+/// Decode file content as text, with the format it is stored in
+///
+/// # Errors
+/// As `decode`
+pub fn decode_with_format(bytes: Vec<u8>) -> io::Result<(String, TextFormat)> {
+    let skip = |reason: SkipReason| io::Error::new(io::ErrorKind::InvalidData, reason);
+    let format = |encoding: Encoding, bom: bool| TextFormat { encoding, bom };
+    if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        return String::from_utf8(rest.to_vec())
+            .map(|text| (text, format(Encoding::Utf8, true)))
+            .map_err(|_| skip(SkipReason::Undecodable));
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+        return decode_utf16(rest, u16::from_le_bytes)
+            .map(|text| (text, format(Encoding::Utf16Le, true)))
+            .ok_or(skip(SkipReason::Undecodable));
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+        return decode_utf16(rest, u16::from_be_bytes)
+            .map(|text| (text, format(Encoding::Utf16Be, true)))
+            .ok_or(skip(SkipReason::Undecodable));
+    }
+
+    let sniffed = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if memchr::memchr(0, sniffed).is_some() {
+        // UTF-16 text without a byte order mark: ASCII characters, which make
+        // up most of source code, leave every other byte zero (odd ones in
+        // little-endian) and the bytes in between nonzero
+        let zeros_at = |parity: usize| {
+            sniffed
+                .iter()
+                .skip(parity)
+                .step_by(2)
+                .filter(|&&byte| byte == 0)
+                .count()
+        };
+        let (even, odd) = (zeros_at(0), zeros_at(1));
+        let mostly = |zeros: usize| zeros * 4 > sniffed.len();
+        let utf16 = if mostly(odd) && even == 0 {
+            decode_utf16(&bytes, u16::from_le_bytes).map(|text| (text, Encoding::Utf16Le))
+        } else if mostly(even) && odd == 0 {
+            decode_utf16(&bytes, u16::from_be_bytes).map(|text| (text, Encoding::Utf16Be))
+        } else {
+            None
+        };
+        return utf16
+            .map(|(text, encoding)| (text, format(encoding, false)))
+            .ok_or(skip(SkipReason::Binary));
+    }
+    String::from_utf8(bytes)
+        .map(|text| (text, TextFormat::default()))
+        .map_err(|_| skip(SkipReason::Undecodable))
+}
+
+/// Encode characters as UTF-16 code units written with `unit`
+fn encode_utf16(chars: impl Iterator<Item = char>, unit: fn(u16) -> [u8; 2]) -> Vec<u8> {
+    let mut buffer = [0; 2];
+    chars
+        .flat_map(|ch| ch.encode_utf16(&mut buffer).to_vec())
+        .flat_map(unit)
+        .collect()
+}
+
+/// Decode UTF-16 code units read with `unit`; `None` if invalid
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> Option<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let units = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    fn reason(bytes: &[u8]) -> Option<SkipReason> {
+        let error = decode(bytes.to_vec()).unwrap_err();
+        SkippedFile::from_error("a", &error).map(|skipped| skipped.reason)
+    }
+
+    #[test]
+    fn test_decode() {
+        let text = "export const café = 1;\r\n";
+        assert_eq!(decode(text.into()).unwrap(), text);
+        assert_eq!(
+            decode([b"\xEF\xBB\xBF", text.as_bytes()].concat()).unwrap(),
+            text
+        );
+        assert_eq!(
+            decode([&b"\xFF\xFE"[..], &utf16le(text)].concat()).unwrap(),
+            text
+        );
+        let utf16be: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        assert_eq!(decode([&b"\xFE\xFF"[..], &utf16be].concat()).unwrap(), text);
+        // Without a byte order mark
+        assert_eq!(decode(utf16le(text)).unwrap(), text);
+        assert_eq!(decode(utf16be).unwrap(), text);
+    }
+
+    #[test]
+    fn test_format_round_trips() {
+        let text = "# Café 🎉\r\n";
+        let utf16 = [&b"\xFF\xFE"[..], &utf16le(text)].concat();
+        for bytes in [
+            text.as_bytes().to_vec(),
+            [b"\xEF\xBB\xBF", text.as_bytes()].concat(),
+            utf16.clone(),
+            utf16le(text),
+            text.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+        ] {
+            let (decoded, format) = decode_with_format(bytes.clone()).unwrap();
+            assert_eq!(decoded, text);
+            assert_eq!(format.encode(&decoded), bytes, "{:?}", format);
+        }
+        let (_, format) = decode_with_format(utf16).unwrap();
+        assert_eq!(
+            format,
+            TextFormat {
+                encoding: Encoding::Utf16Le,
+                bom: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_skip_reasons() {
+        assert_eq!(
+            reason(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some(SkipReason::Binary)
+        );
+        // A lone zero byte is not UTF-16, even at an even offset
+        assert_eq!(
+            reason(b"\x89PNG\r\n\x1a\n\0sintesi"),
+            Some(SkipReason::Binary)
+        );
+        assert_eq!(reason(b"caf\xE9"), Some(SkipReason::Undecodable));
+        // Unpaired surrogate
+        assert_eq!(reason(b"\xFF\xFE\x00\xD8"), Some(SkipReason::Undecodable));
+        assert_eq!(
+            SkippedFile::from_error("a", &io::Error::other("disk on fire")),
+            None
+        );
+
+        let large = SkippedFile::over_limit("dist/app.js", 2048, 1024).unwrap();
+        assert_eq!(large.reason.as_str(), "too-large");
+        assert_eq!(
+            large.reason.to_string(),
+            "2048 bytes, over the 1024 byte limit"
+        );
+        assert_eq!(SkippedFile::over_limit("src/a.ts", 1024, 1024), None);
+    }
+
+    #[test]
+    fn test_detect_generated() {
+        let code = "export function login(user: string): void {}\n".repeat(40);
+        assert_eq!(detect_generated(&code), None);
+        assert_eq!(
+            detect_generated(&format!("// @generated by protoc\n{}", code)),
+            Some(SkipReason::Generated)
+        );
+        assert_eq!(
+            detect_generated(&format!(
+                "// Code generated by sqlc. DO NOT EDIT.\n{}",
+                code
+            )),
+            Some(SkipReason::Generated)
+        );
+        assert_eq!(
+            detect_generated(&format!("{}//# sourceMappingURL=index.js.map\n", code)),
+            Some(SkipReason::Generated)
+        );
+        // Markers deep in the file are ordinary text
+        assert_eq!(
+            detect_generated(&format!("{}// @generated\n", code.repeat(2))),
+            None
+        );
+
+        let bundle = "function a(b,c){return b+c}var d=a(1,2);".repeat(50);
+        assert_eq!(detect_generated(&bundle), Some(SkipReason::Minified));
+        // A long data line in hand-written code, and long prose lines
+        assert_eq!(
+            detect_generated(&format!("{}{}\n", code, "A".repeat(1000))),
+            None
+        );
+        let prose = "Sintesi keeps documentation in sync with the code. ".repeat(30);
+        assert_eq!(detect_generated(&format!("{}\n\n{}\n", prose, prose)), None);
+    }
+}
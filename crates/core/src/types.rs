@@ -1,17 +1,16 @@
+#[cfg(feature = "napi")]
 use napi_derive::napi;
-use serde::Serialize;
-
-/**
- * Core type definitions for Sintesi
- */
-
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
+// Core type definitions for Sintesi
 
 /**
  * Signature information extracted from code
  */
-#[napi(object)]
-#[derive(Debug, Serialize, Clone)]
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Serialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct CodeSignature {
     /// Name of the symbol
     pub symbol_name: String,
@@ -23,13 +22,97 @@ pub struct CodeSignature {
     pub is_exported: bool,
     /// SHA256 hash of the signature (computed by Rust analyzer)
     pub hash: Option<String>,
+    /// Human-readable name for symbols without one of their own (anonymous
+    /// default exports are named `default` and displayed after their file)
+    pub display_name: Option<String>,
+    /// Whether the symbol is marked deprecated (not part of the hash)
+    pub deprecated: bool,
+    /// Release tag: "public", "beta", or "experimental" (not part of the hash)
+    pub release_tag: Option<String>,
+    /// Where the symbol is declared (not part of the hash)
+    pub location: Option<SymbolLocation>,
+    /// Generic type parameters, in declaration order (not part of the hash)
+    pub type_params: Vec<TypeParam>,
+    /// Symbol ID, from the symbol's file, name, and kind (`ast::symbol_id`;
+    /// not part of the hash), as recorded in the map's `symbolId`
+    pub id: Option<String>,
+}
+
+/**
+ * Where a symbol is declared in its file
+ */
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolLocation {
+    /// First line of the declaration (0-indexed)
+    pub start_line: u32,
+    /// Last line of the declaration (0-indexed)
+    pub end_line: u32,
+    /// Byte offset where the declaration starts
+    pub start_offset: u32,
+    /// Byte offset just past the end of the declaration
+    pub end_offset: u32,
+}
+
+impl SymbolLocation {
+    /// Location of the byte range `start..end` of a file whose lines start at
+    /// `line_starts` (byte offsets, ascending, beginning with 0)
+    pub fn from_offsets(line_starts: &[usize], start: usize, end: usize) -> Self {
+        let line_of = |offset: usize| match line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line.saturating_sub(1),
+        };
+        Self {
+            start_line: line_of(start) as u32,
+            // A declaration ending with its line terminator ends on that line
+            end_line: line_of(end.saturating_sub(1).max(start)) as u32,
+            start_offset: start as u32,
+            end_offset: end as u32,
+        }
+    }
+}
+
+/**
+ * A generic type parameter of a declaration (`T extends Base = Default`)
+ */
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeParam {
+    /// Name of the parameter (`T`)
+    pub name: String,
+    /// Constraint, as printed in the signature (`Base`, `Base & Other`)
+    pub constraint: Option<String>,
+    /// Default type, as printed in the signature
+    pub default: Option<String>,
+}
+
+impl TypeParam {
+    /// A parameter without constraint or default
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            constraint: None,
+            default: None,
+        }
+    }
+}
+
+/// Byte offsets where each line of `content` starts
+pub fn line_starts(content: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+        .collect()
 }
 
 /**
  * Types of symbols we track
  */
-#[napi(string_enum)]
-#[derive(Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "napi", napi(string_enum))]
+// `napi(string_enum)` derives `Clone` and `Copy` itself
+#[cfg_attr(not(feature = "napi"), derive(Clone, Copy))]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum SymbolType {
     Function,
     Class,
@@ -38,6 +121,24 @@ pub enum SymbolType {
     Enum,
     Variable,
     Const,
+    /// API operation (e.g. an OpenAPI path + method)
+    Operation,
+    /// Data schema definition (e.g. OpenAPI component schema)
+    Schema,
+    /// Database table (SQL DDL)
+    Table,
+    /// Database view (SQL DDL)
+    View,
+    /// RPC service definition (e.g. gRPC service)
+    Service,
+    /// Configuration key (JSON Schema property, package.json script, tsconfig option)
+    ConfigKey,
+    /// HTTP route (method + path + handler)
+    Route,
+    /// Trait implementation (Rust `impl Trait for Type`)
+    Impl,
+    /// UI component (Vue/Svelte single-file component)
+    Component,
 }
 
 
@@ -76,7 +76,7 @@ export class AIAgent {
     }
 
     async generateBatch(
-        items: Array<{ symbolName: string; signatureText: string }>,
+        items: Array<{ symbolName: string; signatureText: string; releaseTag?: string }>,
         prompt: string,
         systemPrompt: string,
     ): Promise<Array<{ symbolName: string; content: string }>> {
@@ -8,15 +8,47 @@
 
 import type { DocumentationStructure, Parameter, ReturnTypeInfo } from './structured-schema';
 
+/**
+ * Options for building Markdown documentation
+ */
+export interface MarkdownBuildOptions {
+    /** Release tag of the symbol ("public", "beta", or "experimental") */
+    releaseTag?: string;
+}
+
+/**
+ * Badge line for symbols with an unstable release tag (`@beta`, `@experimental`)
+ */
+export function releaseTagBadge(releaseTag?: string): string | undefined {
+    switch (releaseTag) {
+        case 'beta':
+            return '> 🧪 **Beta:** this API may change in minor releases.';
+        case 'experimental':
+            return '> ⚠️ **Experimental:** this API may change or be removed at any time.';
+        default:
+            return undefined;
+    }
+}
+
 /**
  * Build Markdown documentation from structured data
  *
  * This is the core function that converts JSON structure to Markdown.
  * The template is deterministic - the same input always produces the same output.
  */
-export function buildMarkdownFromStructure(doc: DocumentationStructure): string {
+export function buildMarkdownFromStructure(
+    doc: DocumentationStructure,
+    options: MarkdownBuildOptions = {},
+): string {
     const parts: string[] = [];
 
+    // 0. Release tag badge (beta/experimental APIs only)
+    const badge = releaseTagBadge(options.releaseTag);
+    if (badge) {
+        parts.push(badge);
+        parts.push('');
+    }
+
     // 1. Purpose (always present)
     parts.push(`**Purpose:** ${doc.purpose}`);
 
@@ -93,9 +125,10 @@ function formatReturnType(returnType: ReturnTypeInfo): string {
  */
 export function buildBatchMarkdown(
     docs: DocumentationStructure[],
+    releaseTags: Record<string, string | undefined> = {},
 ): Array<{ symbolName: string; content: string }> {
     return docs.map((doc) => ({
         symbolName: doc.symbolName,
-        content: buildMarkdownFromStructure(doc),
+        content: buildMarkdownFromStructure(doc, { releaseTag: releaseTags[doc.symbolName] }),
     }));
 }
@@ -180,7 +180,9 @@ export class VercelAIProvider implements IAIProvider {
             const usageAny = result.usage as any;
 
             // Build Markdown from structure (Zod schema already validated the data)
-            const markdown = buildMarkdownFromStructure(doc);
+            const markdown = buildMarkdownFromStructure(doc, {
+                releaseTag: request.newSignature?.releaseTag,
+            });
 
             return {
                 content: markdown,
@@ -210,7 +212,7 @@ export class VercelAIProvider implements IAIProvider {
     }
 
     async generateBatchDocumentation(
-        items: Array<{ symbolName: string; signatureText: string }>,
+        items: Array<{ symbolName: string; signatureText: string; releaseTag?: string }>,
         prompt: string,
         systemPrompt: string,
     ): Promise<BatchDocumentationResult> {
@@ -242,7 +244,10 @@ export class VercelAIProvider implements IAIProvider {
             // (Zod schema already validated all data during parsing)
             const success: Array<{ symbolName: string; content: string }> =
                 result.object.documentations.map((doc: DocumentationStructure) => {
-                    const markdown = buildMarkdownFromStructure(doc);
+                    const markdown = buildMarkdownFromStructure(doc, {
+                        releaseTag: items.find((item) => item.symbolName === doc.symbolName)
+                            ?.releaseTag,
+                    });
 
                     if (this.debug) {
                         if (this.logger) {
@@ -182,7 +182,7 @@ export interface IAIProvider {
      * This prevents wasting tokens when only a few items fail validation
      */
     generateBatchDocumentation?(
-        items: Array<{ symbolName: string; signatureText: string }>,
+        items: Array<{ symbolName: string; signatureText: string; releaseTag?: string }>,
         prompt: string,
         systemPrompt: string,
     ): Promise<BatchDocumentationResult>;
@@ -204,5 +204,22 @@ describe('Markdown Builder', () => {
             expect(result).not.toContain('<!--');
             expect(result).not.toContain('-->');
         });
+
+        it('should add a badge for beta and experimental APIs', () => {
+            const doc: DocumentationStructure = {
+                symbolName: 'createSession',
+                purpose: 'Creates a session',
+            };
+
+            expect(buildMarkdownFromStructure(doc, { releaseTag: 'beta' })).toBe(
+                '> 🧪 **Beta:** this API may change in minor releases.\n\n**Purpose:** Creates a session',
+            );
+            expect(buildMarkdownFromStructure(doc, { releaseTag: 'experimental' })).toContain(
+                '**Experimental:**',
+            );
+            expect(buildMarkdownFromStructure(doc, { releaseTag: 'public' })).toBe(
+                '**Purpose:** Creates a session',
+            );
+        });
     });
 });
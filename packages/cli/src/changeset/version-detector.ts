@@ -46,9 +46,16 @@ export class VersionTypeDetector {
 
         // Count changes by type
         const added = symbolChanges.filter((c) => c.changeType === 'added');
-        const deleted = symbolChanges.filter((c) => c.changeType === 'deleted');
+        const allDeleted = symbolChanges.filter((c) => c.changeType === 'deleted');
         const modified = symbolChanges.filter((c) => c.changeType === 'modified');
 
+        // Breaking changes to @beta/@experimental APIs do not require a major bump
+        const deleted = allDeleted.filter((c) => !this.isUnstable(c));
+        const unstableBreaking = [
+            ...allDeleted.filter((c) => this.isUnstable(c)),
+            ...modified.filter((c) => this.isUnstable(c) && this.isPotentiallyBreaking(c)),
+        ];
+
         this.logger.debug(
             `Changes: ${added.length} added, ${deleted.length} deleted, ${modified.length} modified`,
         );
@@ -70,13 +77,15 @@ export class VersionTypeDetector {
         }
 
         // Rule 2: MAJOR - Modified with potential breaking changes
-        const breakingModifications = modified.filter((c) => this.isPotentiallyBreaking(c));
+        const breakingModifications = modified.filter(
+            (c) => !this.isUnstable(c) && this.isPotentiallyBreaking(c),
+        );
         if (breakingModifications.length > 0) {
             const description = this.buildDetailedDescription({
                 type: 'major',
                 breakingModifications,
                 added,
-                modified: modified.filter((c) => !this.isPotentiallyBreaking(c)),
+                modified: modified.filter((c) => !breakingModifications.includes(c)),
             });
 
             return {
@@ -86,6 +95,22 @@ export class VersionTypeDetector {
             };
         }
 
+        // Rule 2b: MINOR - Breaking changes limited to beta/experimental APIs
+        if (unstableBreaking.length > 0) {
+            const description = this.buildDetailedDescription({
+                type: 'minor',
+                unstableBreaking,
+                added,
+                modified: modified.filter((c) => !unstableBreaking.includes(c)),
+            });
+
+            return {
+                versionType: 'minor',
+                reasoning: description,
+                confidence: 'medium',
+            };
+        }
+
         // Rule 3: MINOR - New symbols added
         if (added.length > 0) {
             const featureModifications = modified.filter((c) => this.isFeatureAddition(c));
@@ -141,6 +166,17 @@ export class VersionTypeDetector {
         };
     }
 
+    /**
+     * Check if a change affects a `@beta` or `@experimental` API
+     *
+     * Uses the release tag the symbol had before the change, so promoting an
+     * API to `@public` does not hide breaking changes made at the same time.
+     */
+    private isUnstable(change: SymbolChange): boolean {
+        const tag = (change.oldSignature ?? change.newSignature)?.releaseTag;
+        return tag === 'beta' || tag === 'experimental';
+    }
+
     /**
      * Check if a modification is potentially breaking
      *
@@ -253,6 +289,7 @@ export class VersionTypeDetector {
         modified?: SymbolChange[];
         deleted?: SymbolChange[];
         breakingModifications?: SymbolChange[];
+        unstableBreaking?: SymbolChange[];
         featureModifications?: SymbolChange[];
     }): string {
         const parts: string[] = [];
@@ -284,6 +321,12 @@ export class VersionTypeDetector {
             }
         }
 
+        // Handle breaking changes to beta/experimental APIs
+        if (changes.unstableBreaking && changes.unstableBreaking.length > 0) {
+            const symbols = changes.unstableBreaking.map((c) => `\`${c.symbolName}\``).join(', ');
+            parts.push(`Change beta/experimental API: ${symbols}`);
+        }
+
         // Handle additions
         if (changes.added && changes.added.length > 0) {
             const symbols = changes.added
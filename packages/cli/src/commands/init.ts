@@ -0,0 +1,41 @@
+/**
+ * CLI command: init
+ *
+ * Scaffolds Sintesi in the current project: configuration, state directory,
+ * an empty map, a starter docs file, and optionally a pre-commit hook.
+ */
+
+import { initProject } from '@sintesi/core';
+import { Logger } from '../utils/logger';
+
+export interface InitOptions {
+    docsDir?: string;
+    hook?: boolean;
+    force?: boolean;
+    verbose?: boolean;
+}
+
+export async function initCommand(options: InitOptions): Promise<boolean> {
+    const logger = new Logger(options.verbose);
+    logger.header('✨ Sintesi Init');
+
+    try {
+        const result = initProject(process.cwd(), {
+            docsDir: options.docsDir,
+            preCommitHook: options.hook,
+            force: options.force,
+        });
+
+        for (const file of result.created) {
+            logger.success(`Created ${file}`);
+        }
+        for (const file of result.skipped) {
+            logger.info(`Skipped ${file} (already exists, use --force to overwrite)`);
+        }
+        logger.info('Next: add anchors to your docs and run `sintesi check`.');
+        return true;
+    } catch (e) {
+        logger.error(`Initialization failed: ${e instanceof Error ? e.message : String(e)}`);
+        return false;
+    }
+}
@@ -4,6 +4,7 @@
  * Sintesi CLI - Main entry point
  *
  * Commands:
+ * - init: Set up Sintesi in the current project
  * - check: Verify documentation is in sync with code
  * - readme: Generate a README.md based on project context
  * - documentation: Generate comprehensive documentation site structure
@@ -21,6 +22,7 @@ import { checkCommand } from './commands/check';
 import { changesetCommand } from './commands/changeset';
 import { readmeCommand, ReadmeOptions } from './commands/readme';
 import { documentationCommand, DocumentationOptions } from './commands/documentation';
+import { initCommand, InitOptions } from './commands/init';
 import { CheckOptions, ChangesetOptions } from './types';
 
 // Parse command line arguments
@@ -31,6 +33,48 @@ yargs(hideBin(process.argv))
     .alias('v', 'version')
     .alias('h', 'help')
 
+    // Init command
+    .command(
+        'init',
+        'Set up Sintesi in the current project',
+        (yargs) => {
+            return yargs
+                .option('docs-dir', {
+                    alias: 'd',
+                    type: 'string',
+                    description: 'Documentation directory (default: docs)',
+                })
+                .option('hook', {
+                    type: 'boolean',
+                    description: 'Install a git pre-commit hook running `sintesi check`',
+                    default: false,
+                })
+                .option('force', {
+                    alias: 'f',
+                    type: 'boolean',
+                    description: 'Overwrite existing scaffolding files (never the map)',
+                    default: false,
+                })
+                .option('verbose', {
+                    type: 'boolean',
+                    description: 'Enable verbose logging',
+                    default: false,
+                });
+        },
+        async (argv) => {
+            const options: InitOptions = {
+                docsDir: argv['docs-dir'] as string,
+                hook: argv.hook as boolean,
+                force: argv.force as boolean,
+                verbose: argv.verbose as boolean,
+            };
+
+            if (!(await initCommand(options))) {
+                process.exit(1);
+            }
+        },
+    )
+
     // Readme command
     .command(
         'readme',
@@ -259,6 +303,7 @@ yargs(hideBin(process.argv))
     )
 
     // Help and examples
+    .example('$0 init --hook', 'Set up Sintesi and install a pre-commit hook')
     .example('$0 check', 'Check for documentation drift')
     .example('$0 check --verbose', 'Check with detailed output')
     .example('$0 changeset', 'Generate changeset from current changes')
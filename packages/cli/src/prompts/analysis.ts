@@ -53,6 +53,11 @@ export function getContextPrompt(
 
     let prompt = `## Package.json\n\`\`\`json\n${packageJsonSummary}\n\`\`\`\n\n`;
 
+    if (context.architectureBrief) {
+        prompt += `## Architecture Brief (README and ADRs)\n${context.architectureBrief}\n\n`;
+        prompt += `> **INSTRUCTION**: Keep the documentation consistent with the architecture and decisions described above. Do not contradict them.\n\n`;
+    }
+
     if (techStack) {
         prompt += `## Detected Tech Stack\n`;
         if (techStack.frameworks.length)
@@ -24,6 +24,16 @@ export interface TechStack {
     infrastructure: string[]; // e.g. Vercel, Docker
 }
 
+/** Display names of the frameworks detected by the core (`ProjectContext.frameworks`) */
+const FRAMEWORK_LABELS: Record<string, string> = {
+    nextjs: 'Next.js',
+    nestjs: 'NestJS',
+    express: 'Express',
+    react: 'React',
+    tauri: 'Tauri',
+    actix: 'Actix Web',
+};
+
 export class GenerationContextService {
     private retrievalService: RetrievalService;
     private skeletonizer: SkeletonizerService;
@@ -407,13 +417,19 @@ export class GenerationContextService {
             infrastructure: [],
         };
 
+        // Detected by the core across nested packages, Cargo crates, and config files
+        const coreFrameworks = (context.frameworks ?? []).map((id) => FRAMEWORK_LABELS[id] ?? id);
+
         const pkg = context.packageJson;
-        if (!pkg) return stack;
+        if (!pkg) {
+            stack.frameworks.push(...coreFrameworks);
+            return stack;
+        }
 
         const allDeps = {
             ...pkg.dependencies,
             ...pkg.devDependencies,
-            ...(pkg as any).peerDependencies,
+            ...pkg.peerDependencies,
         };
         const depNames = Object.keys(allDeps);
 
@@ -431,6 +447,9 @@ export class GenerationContextService {
         if (depNames.includes('express')) stack.frameworks.push('Express');
         if (depNames.includes('fastify')) stack.frameworks.push('Fastify');
         if (depNames.includes('@nestjs/core')) stack.frameworks.push('NestJS');
+        for (const name of coreFrameworks) {
+            if (!stack.frameworks.includes(name)) stack.frameworks.push(name);
+        }
 
         // Libraries
         if (depNames.some((d) => d.includes('tailwind'))) stack.libraries.push('TailwindCSS');
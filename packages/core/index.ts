@@ -16,25 +16,41 @@ export {
     discoverFiles,
     helloWorld,
     getVersion,
+    // Read-only mode for untrusted builds
+    setReadOnly,
+    isReadOnly,
+    setWriteRoot,
     AstAnalyzer, // Also export with Rust name
+    resolveExports,
     // Markdown extraction (Rust-powered)
     extractAnchors,
+    extractAnchorsById,
     validateMarkdownAnchors,
+    injectAnchor,
+    scaffoldAnchors,
     parseCodeRef,
     // Project Context
     getProjectContext,
     GitBinding,
     GraphAnalyzer,
+    // Project scaffolding
+    initProject,
+    // Search
+    searchProject,
 } from './native-loader';
 
 export type {
+    AnalyzerOptionsJs as AnalyzerOptions,
     CodeSignature,
+    SymbolLocation,
+    ExportedSymbolJs as ExportedSymbol,
     FileDiscoveryResult,
     FileDiscoveryOptions,
     SymbolTypeValue,
     // Markdown extraction types
     SintesiAnchor,
     ExtractionResult,
+    KeyedExtractionResult,
     CodeRefParts,
     // Project Context types
     ProjectContext,
@@ -43,4 +59,9 @@ export type {
     GitBinding as GitBindingType,
     GraphAnalyzer as GraphAnalyzerType,
     ChangeSummary,
+    InitOptionsJs as InitOptions,
+    InitResultJs as InitResult,
+    SearchOptionsJs as SearchOptions,
+    SearchPageJs as SearchPage,
+    SearchResultJs as SearchResult,
 } from './native-loader';
@@ -97,6 +97,11 @@ function loadNativeModule(): any {
                         anchorCount: 0,
                         errors: [],
                     }),
+                    extractAnchorsById: () => ({
+                        anchors: {},
+                        anchorCount: 0,
+                        errors: [],
+                    }),
                     validateMarkdownAnchors: () => [],
                     parseCodeRef: (ref: string) => {
                         const [filePath, symbolName] = ref.split('#');
@@ -143,12 +148,16 @@ const {
     helloWorld: helloWorldVal,
     getVersion: getVersionVal,
     AstAnalyzer: AstAnalyzerVal,
+    resolveExports: resolveExportsVal,
     extractAnchors: extractAnchorsVal,
+    extractAnchorsById: extractAnchorsByIdVal,
     validateMarkdownAnchors: validateMarkdownAnchorsVal,
     parseCodeRef: parseCodeRefVal,
     getProjectContext: getProjectContextVal,
     GitBinding: GitBindingVal,
     GraphAnalyzer: GraphAnalyzerVal,
+    initProject: initProjectVal,
+    searchProject: searchProjectVal,
 } = nativeModule;
 
 export const SymbolType = SymbolTypeVal;
@@ -156,17 +165,24 @@ export const discoverFiles = discoverFilesVal as typeof CoreTypes.discoverFiles;
 export const helloWorld = helloWorldVal as typeof CoreTypes.helloWorld;
 export const getVersion = getVersionVal as typeof CoreTypes.getVersion;
 export const AstAnalyzer = AstAnalyzerVal as typeof CoreTypes.AstAnalyzer;
+export const resolveExports = resolveExportsVal as typeof CoreTypes.resolveExports;
 export const extractAnchors = extractAnchorsVal as typeof CoreTypes.extractAnchors;
+export const extractAnchorsById = extractAnchorsByIdVal as typeof CoreTypes.extractAnchorsById;
 export const validateMarkdownAnchors =
     validateMarkdownAnchorsVal as typeof CoreTypes.validateMarkdownAnchors;
 export const parseCodeRef = parseCodeRefVal as typeof CoreTypes.parseCodeRef;
 export const getProjectContext = getProjectContextVal as typeof CoreTypes.getProjectContext;
 export const GitBinding = GitBindingVal as typeof CoreTypes.GitBinding;
 export const GraphAnalyzer = GraphAnalyzerVal as typeof CoreTypes.GraphAnalyzer;
+export const initProject = initProjectVal as typeof CoreTypes.initProject;
+export const searchProject = searchProjectVal as typeof CoreTypes.searchProject;
 
 export type AstAnalyzer = CoreTypes.AstAnalyzer;
+export type AnalyzerOptionsJs = CoreTypes.AnalyzerOptionsJs;
 
 export type CodeSignature = CoreTypes.CodeSignature;
+export type SymbolLocation = CoreTypes.SymbolLocation;
+export type ExportedSymbolJs = CoreTypes.ExportedSymbolJs;
 
 export type FileDiscoveryResult = CoreTypes.FileDiscoveryResult;
 export type FileDiscoveryOptions = CoreTypes.FileDiscoveryOptions;
@@ -174,6 +190,7 @@ export type FileDiscoveryOptions = CoreTypes.FileDiscoveryOptions;
 // Markdown extraction types
 export type SintesiAnchor = CoreTypes.SintesiAnchor;
 export type ExtractionResult = CoreTypes.ExtractionResult;
+export type KeyedExtractionResult = CoreTypes.KeyedExtractionResult;
 export type CodeRefParts = CoreTypes.CodeRefParts;
 
 // Project Context types
@@ -185,5 +202,14 @@ export type GitBinding = CoreTypes.GitBinding;
 export type GraphAnalyzer = CoreTypes.GraphAnalyzer;
 export type ChangeSummary = CoreTypes.ChangeSummary;
 
+// Project scaffolding types
+export type InitOptionsJs = CoreTypes.InitOptionsJs;
+export type InitResultJs = CoreTypes.InitResultJs;
+
+// Search types
+export type SearchOptionsJs = CoreTypes.SearchOptionsJs;
+export type SearchPageJs = CoreTypes.SearchPageJs;
+export type SearchResultJs = CoreTypes.SearchResultJs;
+
 // Export SymbolType as a type (it's a const enum in the .d.ts)
 export type { SymbolType as SymbolTypeValue } from './native-types';
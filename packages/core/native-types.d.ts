@@ -22,6 +22,48 @@ export interface CodeSignature {
     isExported: boolean;
     /** SHA256 hash of the signature (computed by Rust analyzer) */
     hash?: string;
+    /**
+     * Human-readable name for symbols without one of their own (anonymous
+     * default exports are named `default` and displayed after their file)
+     */
+    displayName?: string;
+    /** Whether the symbol is marked deprecated (not part of the hash) */
+    deprecated: boolean;
+    /** Release tag: "public", "beta", or "experimental" (not part of the hash) */
+    releaseTag?: string;
+    /** Where the symbol is declared (not part of the hash) */
+    location?: SymbolLocation;
+    /** Generic type parameters, in declaration order (not part of the hash) */
+    typeParams: Array<TypeParam>;
+    /**
+     * Symbol ID, from the symbol's file, name, and kind (`ast::symbol_id`;
+     * not part of the hash), as recorded in the map's `symbolId`
+     */
+    id?: string;
+}
+/**
+ * A generic type parameter of a declaration (`T extends Base = Default`)
+ */
+export interface TypeParam {
+    /** Name of the parameter (`T`) */
+    name: string;
+    /** Constraint, as printed in the signature (`Base`, `Base & Other`) */
+    constraint?: string;
+    /** Default type, as printed in the signature */
+    default?: string;
+}
+/**
+ * Where a symbol is declared in its file
+ */
+export interface SymbolLocation {
+    /** First line of the declaration (0-indexed) */
+    startLine: number;
+    /** Last line of the declaration (0-indexed) */
+    endLine: number;
+    /** Byte offset where the declaration starts */
+    startOffset: number;
+    /** Byte offset just past the end of the declaration */
+    endOffset: number;
 }
 /**
  * Types of symbols we track
@@ -41,6 +83,28 @@ export interface AnalysisResultJs {
     signatures: Array<CodeSignature>;
     /** Errors encountered during parsing */
     errors: Array<string>;
+    /** Structured parse diagnostics */
+    diagnostics: Array<DiagnosticJs>;
+    /** The code, if it was not parsed (over `maxFileSize`, or generated) */
+    skipped: Array<SkippedFileJs>;
+}
+/**
+ * A parse diagnostic with its location (for NAPI)
+ *
+ * Lines and columns are 0-based; the end position is exclusive.
+ */
+export interface DiagnosticJs {
+    filePath: string;
+    message: string;
+    help?: string;
+    /** "error", "warning" or "advice" */
+    severity: string;
+    line: number;
+    column: number;
+    endLine: number;
+    endColumn: number;
+    /** The source line containing the start position */
+    excerpt: string;
 }
 /** NAPI-compatible result structure for file discovery */
 export interface FileDiscoveryResult {
@@ -103,8 +167,12 @@ export interface SintesiAnchor {
     startLine: number;
     /** End line number (0-indexed) */
     endLine: number;
-    /** Content between anchor tags */
+    /** Content between anchor tags, including nested anchors */
     content: string;
+    /** ID of the anchor this one is nested in */
+    parent?: string;
+    /** IDs of anchors nested directly inside this one, in document order */
+    children: Array<string>;
 }
 /** NAPI-compatible extraction result */
 export interface ExtractionResult {
@@ -114,6 +182,24 @@ export interface ExtractionResult {
     anchorCount: number;
     /** Errors encountered during extraction */
     errors: Array<string>;
+    /** Set if the file was not parsed (over `maxFileSize`) */
+    skipped?: SkippedFileJs;
+}
+/** Options for anchor extraction */
+export interface ExtractOptions {
+    /** Project root code references are checked against */
+    projectRoot?: string;
+    /**
+     * Code reference check with a `projectRoot`: `"format"` (default),
+     * `"files"` (the file must exist), or `"symbols"` (the file must also
+     * declare or re-export the symbol)
+     */
+    validateCodeRefs?: string;
+    /**
+     * Size in bytes above which content is skipped instead of parsed
+     * (1 MiB by default)
+     */
+    maxFileSize?: number;
 }
 /**
  * Extract Sintesi anchors from markdown content
@@ -121,6 +207,7 @@ export interface ExtractionResult {
  * # Arguments
  * * `file_path` - Path to the markdown file (for reference)
  * * `content` - Markdown content to parse
+ * * `options` - Project root and code reference checks
  *
  * # Returns
  * ExtractionResult with all found anchors and any errors
@@ -143,9 +230,46 @@ export interface ExtractionResult {
  * if (result.errors.length > 0) {
  *   console.error('Errors:', result.errors);
  * }
+ *
+ * // Also report code_refs to missing files or symbols
+ * extractAnchors('docs/api.md', content, { projectRoot: '.', validateCodeRefs: 'symbols' });
  * ```
  */
-export declare function extractAnchors(filePath: string, content: string): ExtractionResult;
+export declare function extractAnchors(
+    filePath: string,
+    content: string,
+    options?: ExtractOptions | undefined | null,
+): ExtractionResult;
+/** NAPI-compatible extraction result keyed by anchor ID */
+export interface KeyedExtractionResult {
+    /** Anchors by ID */
+    anchors: Record<string, SintesiAnchor>;
+    /** Number of anchors found */
+    anchorCount: number;
+    /** Errors encountered during extraction */
+    errors: Array<string>;
+    /** Set if the file was not parsed (over `maxFileSize`) */
+    skipped?: SkippedFileJs;
+}
+/**
+ * Extract Sintesi anchors from markdown content, keyed by anchor ID
+ *
+ * Same as `extractAnchors`, but `anchors` is a `Record<string, SintesiAnchor>`
+ * so callers looking anchors up by ID don't have to rebuild the map.
+ *
+ * # Example (Node.js)
+ * ```javascript
+ * const { extractAnchorsById } = require('@sintesi/core');
+ *
+ * const { anchors } = extractAnchorsById('docs/api.md', content);
+ * console.log(anchors['login']?.codeRef);
+ * ```
+ */
+export declare function extractAnchorsById(
+    filePath: string,
+    content: string,
+    options?: ExtractOptions | undefined | null,
+): KeyedExtractionResult;
 /**
  * Validate markdown content for Sintesi anchors
  *
@@ -174,6 +298,66 @@ export declare function extractAnchors(filePath: string, content: string): Extra
  * ```
  */
 export declare function validateMarkdownAnchors(content: string): Array<string>;
+/**
+ * Replace the content of an anchor in a markdown file
+ *
+ * Everything outside the anchor (line endings, surrounding whitespace, a
+ * byte order mark, nested anchors) is kept as it is, and the file keeps its
+ * encoding (UTF-8 or UTF-16).
+ *
+ * # Arguments
+ * * `file_path` - Markdown file
+ * * `anchor_id` - ID of the anchor to rewrite
+ * * `new_content` - Replacement documentation (without anchor tags)
+ * * `dry_run` - Return the patched file without writing it (default: false)
+ *
+ * # Returns
+ * The patched file content
+ *
+ * # Throws
+ * Error if the file cannot be read or written, or the anchor is missing
+ *
+ * # Example (Node.js)
+ * ```javascript
+ * const { injectAnchor } = require('@sintesi/core');
+ *
+ * const preview = injectAnchor('docs/auth.md', 'login', generated, true);
+ * injectAnchor('docs/auth.md', 'login', generated);
+ * ```
+ */
+export declare function injectAnchor(
+    filePath: string,
+    anchorId: string,
+    newContent: string,
+    dryRun?: boolean | undefined | null,
+): string;
+/**
+ * Generate a markdown skeleton with an anchor for each symbol of a file
+ *
+ * Each symbol gets a heading and an anchor with a fresh UUID and its
+ * `code_ref`, holding a placeholder to regenerate. Members get a heading
+ * below their type, and overloads a single anchor.
+ *
+ * # Arguments
+ * * `file_path` - Source file of the signatures, as used in `code_ref`s
+ * * `signatures` - Signatures of the file (`analyzeFile`)
+ * * `title` - Title of the document (default: the file path)
+ * * `include_private` - Scaffold symbols that are not exported (default: false)
+ *
+ * # Example (Node.js)
+ * ```javascript
+ * const { AstAnalyzer, scaffoldAnchors } = require('@sintesi/core');
+ *
+ * const signatures = new AstAnalyzer().analyzeFile('src/auth.ts');
+ * fs.writeFileSync('docs/auth.md', scaffoldAnchors('src/auth.ts', signatures, 'Auth'));
+ * ```
+ */
+export declare function scaffoldAnchors(
+    filePath: string,
+    signatures: Array<CodeSignature>,
+    title?: string | undefined | null,
+    includePrivate?: boolean | undefined | null,
+): string;
 /**
  * Parse a code_ref string into file path and symbol name
  *
@@ -205,7 +389,10 @@ export interface PackageJson {
     version?: string;
     dependencies?: Record<string, string>;
     devDependencies?: Record<string, string>;
+    peerDependencies?: Record<string, string>;
     scripts?: Record<string, string>;
+    main?: string;
+    module?: string;
 }
 export interface FileContext {
     path: string;
@@ -213,34 +400,273 @@ export interface FileContext {
     imports: Array<string>;
     importedBy: Array<string>;
 }
+/** A package entry point (package.json `main`/`module`/`exports`/`bin`, or a Rust crate root) */
+export interface EntryPoint {
+    /** `main`, `module`, `export`, `bin`, or `lib` */
+    kind: string;
+    /** Export subpath (`.`, `./client`) or binary name */
+    name?: string;
+    /** Path as declared, relative to the project root */
+    target: string;
+    /** Project source file the entry resolves to */
+    source?: string;
+}
+/** A workspace package (a package.json or Cargo.toml directory) */
+export interface WorkspacePackage {
+    /** Name from the manifest (the directory when it declares none) */
+    name: string;
+    /** Directory relative to the project root (empty for the root) */
+    path: string;
+}
 export interface ProjectContext {
     files: Array<FileContext>;
     packageJson?: PackageJson;
+    entryPoints: Array<EntryPoint>;
+    /** Files reachable from the entry points through imports */
+    publicSurface: Array<string>;
+    /** Headings and first paragraphs of the README and `docs/adr/*.md` */
+    architectureBrief?: string;
+    /** `nextjs`, `nestjs`, `express`, `react`, `tauri`, or `actix` */
+    frameworks: Array<string>;
+    /** Workspace packages, sorted by path */
+    packages: Array<WorkspacePackage>;
 }
 export declare function getProjectContext(rootPath: string): ProjectContext;
 export interface NapiFileInfo {
     path: string;
     extension?: string;
 }
-export declare function getProjectFiles(rootPath: string): Array<NapiFileInfo>;
+/** Options for `getProjectFiles` */
+export interface CrawlOptionsJs {
+    /** Skip files whose content matches a file already listed */
+    dedupContent?: boolean;
+}
+/**
+ * List project files (honoring `.gitignore`), skipping hard links of files
+ * already listed
+ */
+export declare function getProjectFiles(
+    rootPath: string,
+    options?: CrawlOptionsJs | undefined | null,
+): Array<NapiFileInfo>;
 export interface ChangeSummary {
     gitDiff: string;
     changedFiles: Array<string>;
     hasMeaningfulChanges: boolean;
 }
+/** Options for `initProject` */
+export interface InitOptionsJs {
+    /** Documentation directory, relative to the project root (default: `docs`) */
+    docsDir?: string;
+    /** Install a git pre-commit hook running `sintesi check` */
+    preCommitHook?: boolean;
+    /** Overwrite existing scaffolding files (never the map) */
+    force?: boolean;
+}
+/** Files touched by `initProject` (for NAPI) */
+export interface InitResultJs {
+    /** Files written, relative to the project root */
+    created: Array<string>;
+    /** Files left as they were because they already existed */
+    skipped: Array<string>;
+}
+/**
+ * Scaffold Sintesi in a project: `sintesi.config.json`, the `.sintesi/`
+ * state directory, an empty map, a starter docs file, and optionally a git
+ * pre-commit hook
+ *
+ * @param rootPath - Project root
+ * @param options - Docs directory, hook installation, and overwrite behavior
+ * @returns Created and skipped files
+ */
+export declare function initProject(
+    rootPath: string,
+    options?: InitOptionsJs | undefined | null,
+): InitResultJs;
+/** Options for `searchProject` */
+export interface SearchOptionsJs {
+    /** Match letters regardless of case */
+    caseInsensitive?: boolean;
+    /** Match against whole files so patterns can span lines */
+    multiline?: boolean;
+    /** Treat the pattern as a literal string rather than a regular expression */
+    fixedString?: boolean;
+    /** Maximum number of results per page (unlimited if omitted) */
+    limit?: number;
+    /** `nextCursor` of the previous page, to continue after it */
+    cursor?: string;
+    /** Give up after this many milliseconds */
+    timeoutMs?: number;
+    /** Also search generated and minified files */
+    includeGenerated?: boolean;
+}
+/** A single search match (for NAPI) */
+export interface SearchResultJs {
+    /** File containing the match, relative to the project root */
+    filePath: string;
+    /** Line the match starts on (1-based) */
+    line: number;
+    /** Line the match ends on (1-based) */
+    endLine: number;
+    /** Byte offset of the match in the file */
+    byteOffset: number;
+    /** Byte offset of the match start within `lineText` */
+    columnStart: number;
+    /** Byte offset just past the match end within `lineText` */
+    columnEnd: number;
+    /** The matched line(s), untrimmed, without the final line terminator */
+    lineText: string;
+}
+/** One page of search results (for NAPI) */
+export interface SearchPageJs {
+    /** Matches, sorted by path, then position */
+    results: Array<SearchResultJs>;
+    /** Cursor for the next page, if the limit cut the results short */
+    nextCursor?: string;
+    /** Files searched for this page that are not text, or are generated or minified */
+    skipped: Array<SkippedFileJs>;
+}
+/** A file left out because it is not text, too large, or generated (for NAPI) */
+export interface SkippedFileJs {
+    /** Path of the file, relative to the project root */
+    filePath: string;
+    /**
+     * `binary`, `undecodable` (neither UTF-8 nor UTF-16), `too-large`,
+     * `generated`, or `minified`
+     */
+    reason: string;
+}
+/**
+ * Search the project's files (honoring `.gitignore`) for a pattern
+ *
+ * @param rootPath - Project root
+ * @param pattern - Regular expression (matched within single lines unless
+ *   `multiline` is set), or a literal string with `fixedString`
+ * @param options - Case sensitivity, matching mode, and paging
+ * @returns A page of matches sorted by path and position, with byte offsets
+ *   and match ranges
+ */
+export declare function searchProject(
+    rootPath: string,
+    pattern: string,
+    options?: SearchOptionsJs | undefined | null,
+): SearchPageJs;
 /** Simple hello world function to test the napi binding */
 export declare function helloWorld(): string;
 /** Get version information */
 export declare function getVersion(): string;
+/**
+ * Turn read-only mode on or off for the process
+ *
+ * In read-only mode every write to the project (anchor injection, plans,
+ * the map, drift history, analysis cache, and git commits) fails with a
+ * "Read-only: ..." error. Turning it off has no effect while the
+ * `SINTESI_READ_ONLY` environment variable is set.
+ *
+ * @param enabled - Whether writes are refused
+ */
+export declare function setReadOnly(enabled: boolean): void;
+/**
+ * Whether read-only mode is on (by `setReadOnly`, `SINTESI_READ_ONLY`, or a
+ * config with `"readOnly": true`)
+ */
+export declare function isReadOnly(): boolean;
+/**
+ * Confine writes to a project root and its `writeAllowlist`
+ *
+ * Saving the map, drift history, or analysis cache, anchor injection, and
+ * report files outside the root (or the config's `writeAllowlist`) then
+ * fail with a "Path not allowed: ..." error. Without a call the
+ * `SINTESI_WRITE_ROOT` environment variable names the root, if set.
+ *
+ * @param rootPath - Project root, or null to remove the sandbox
+ */
+export declare function setWriteRoot(rootPath?: string | undefined | null): void;
+/** A name exported by a module and its declaration (for NAPI) */
+export interface ExportedSymbolJs {
+    /** Name the module exports */
+    name: string;
+    /** File declaring the symbol */
+    filePath: string;
+    /** Name of the symbol in the declaring file */
+    symbolName: string;
+    /** Code reference (`file#symbol`) of the declaration */
+    codeRef: string;
+}
+/**
+ * List the names a module exports and the files declaring them
+ *
+ * Follows `export ... from` and `export *` through barrel files, so every
+ * exported name is attributed to the module that declares it.
+ *
+ * @param rootPath - Project root the file path is relative to
+ * @param filePath - Module to list, relative to the root
+ * @returns Exported names, sorted, with their declarations
+ */
+export declare function resolveExports(rootPath: string, filePath: string): Array<ExportedSymbolJs>;
+/** Options for `AstAnalyzer` */
+export interface AnalyzerOptionsJs {
+    /**
+     * Naming of symbols sharing a qualified name within a file: `"keep"`
+     * (default) or `"suffix"` (`helper`, `helper~2`, as used for drift keys)
+     */
+    duplicateNames?: 'keep' | 'suffix';
+    /**
+     * Also extract declarations inside function and method bodies
+     * (callbacks, local helpers); only module-level declarations, class
+     * members, and namespace members are extracted by default
+     */
+    deep?: boolean;
+    /**
+     * Include decorators, with their arguments, in class and property
+     * signatures (method signatures always include theirs)
+     */
+    decorators?: boolean;
+    /**
+     * Report React function components as `Component` symbols whose
+     * signature lists their props
+     */
+    reactComponents?: boolean;
+    /**
+     * List the members classes inherit from base classes declared in the
+     * same file in their signatures
+     */
+    inheritedMembers?: boolean;
+    /**
+     * Size in bytes above which files are skipped instead of parsed
+     * (1 MiB by default)
+     */
+    maxFileSize?: number;
+    /**
+     * Also parse files detected as generated (`@generated` markers, source
+     * map comments) or minified, which are skipped by default
+     */
+    includeGenerated?: boolean;
+    /**
+     * Also return non-exported symbols from `analyzeFile` and
+     * `analyzeCode` (e.g. internal helpers anchored in architecture docs)
+     */
+    includePrivate?: boolean;
+}
 /** AST Analyzer for TypeScript/JavaScript code */
 export declare class AstAnalyzer {
-    /** Create a new AST analyzer instance */
-    constructor();
+    /**
+     * Create a new AST analyzer instance
+     *
+     * Nested declarations are named after their enclosing namespaces,
+     * classes, methods, and functions (`Outer.helper`, `MyClass.method`).
+     *
+     * @param options - Naming of duplicate symbols, extraction depth, decorators,
+     *   React components, inherited members, file size limit, and
+     *   non-exported symbols
+     */
+    constructor(options?: AnalyzerOptionsJs | undefined | null);
     /**
      * Analyze a TypeScript/JavaScript file and return code signatures
      *
      * This method reads the file, parses it using Oxc, and extracts all
-     * exported symbols with their signatures. Hashes are computed automatically.
+     * exported symbols (all symbols with `includePrivate`) with their
+     * signatures. Hashes are computed automatically.
      *
      * @param filePath - Absolute path to the TypeScript/JavaScript file
      * @returns Array of code signatures found in the file (with hashes)
@@ -250,7 +676,8 @@ export declare class AstAnalyzer {
      * Analyze TypeScript/JavaScript source code directly (without file)
      *
      * This method parses the provided code string using Oxc and extracts all
-     * exported symbols with their signatures. Hashes are computed automatically.
+     * exported symbols (all symbols with `includePrivate`) with their
+     * signatures. Hashes are computed automatically.
      *
      * @param code - TypeScript/JavaScript source code
      * @returns Array of code signatures found in the code (with hashes)
@@ -263,6 +690,14 @@ export declare class AstAnalyzer {
      * @returns Detailed analysis result with symbols and errors (with hashes)
      */
     analyzeWithErrors(code: string): AnalysisResultJs;
+    /**
+     * Get structured parse diagnostics for a file
+     *
+     * @param filePath - Path to the source file (TypeScript/JavaScript, Go,
+     * Java, Python, or a JSON/YAML document)
+     * @returns Diagnostics with 0-based line/column spans and the offending source line
+     */
+    getDiagnostics(filePath: string): Array<DiagnosticJs>;
 }
 export declare class GitBinding {
     constructor(rootPath: string);